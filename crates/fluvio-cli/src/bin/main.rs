@@ -8,6 +8,9 @@ use fluvio_cli::{Root, HelpOpt};
 use fluvio_future::task::run_block_on;
 
 fn main() -> Result<()> {
+    #[cfg(feature = "otlp")]
+    let _otlp = init_otlp();
+    #[cfg(not(feature = "otlp"))]
     fluvio_future::subscriber::init_tracer(None);
 
     print_help_hack()?;
@@ -22,6 +25,23 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Export spans over OTLP when a collector endpoint is configured,
+/// otherwise log the usual way
+#[cfg(feature = "otlp")]
+fn init_otlp() -> Option<fluvio::otel::OtlpGuard> {
+    let otlp_error = match fluvio::otel::init_otlp_tracer("fluvio-cli") {
+        Ok(Some(guard)) => return Some(guard),
+        Ok(None) => None,
+        Err(err) => Some(err),
+    };
+    fluvio_future::subscriber::init_tracer(None);
+    // reported once the fallback subscriber is installed
+    if let Some(err) = otlp_error {
+        tracing::warn!("failed to initialize OTLP exporter: {err:#}");
+    }
+    None
+}
+
 fn print_help_hack() -> Result<()> {
     let mut args = std::env::args();
     if args.len() < 2 {
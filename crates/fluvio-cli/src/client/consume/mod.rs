@@ -156,6 +156,11 @@ mod cmd {
         #[arg(long, value_parser=parse_isolation)]
         pub isolation: Option<Isolation>,
 
+        /// Read committed records from the nearest in-sync replica instead of the leader.
+        /// Replica locality is taken from the `client.rack` setting of the cluster profile.
+        #[arg(long)]
+        pub read_from_follower: bool,
+
         /// Suppress items items that have an unknown output type
         #[arg(long = "suppress-unknown")]
         pub suppress_unknown: bool,
@@ -387,6 +392,10 @@ mod cmd {
                 builder.isolation(isolation);
             }
 
+            if self.read_from_follower {
+                builder.read_from_follower(true);
+            }
+
             let consume_config = builder.build()?;
             debug!("consume config: {:#?}", consume_config);
 
@@ -829,6 +838,7 @@ mod cmd {
                 aggregate_initial: Default::default(),
                 params: Default::default(),
                 isolation: Default::default(),
+                read_from_follower: Default::default(),
                 beginning: Default::default(),
                 transforms: Default::default(),
                 transforms_line: Default::default(),
@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+
 use clap::Parser;
 use anyhow::Result;
 
 use fluvio::Fluvio;
+use fluvio::metadata::partition::{PartitionUsage, PartitionUsageRequest};
+use fluvio_types::PartitionId;
 
 use crate::common::output::Terminal;
 use crate::common::OutputFormat;
@@ -11,6 +15,12 @@ use crate::common::OutputFormat;
 pub struct ListConsumerOpt {
     #[clap(flatten)]
     output: OutputFormat,
+    /// Show how many records each consumer is behind
+    #[arg(long)]
+    lag: bool,
+    /// Only show offsets mirrored from another cluster, with the source offset they were translated from
+    #[arg(long)]
+    mirrored: bool,
 }
 
 impl ListConsumerOpt {
@@ -18,47 +28,118 @@ impl ListConsumerOpt {
     where
         O: Terminal,
     {
-        let consumers = fluvio.consumer_offsets().await?;
+        let mut consumers = fluvio.consumer_offsets().await?;
+        if self.mirrored {
+            consumers.retain(|consumer| consumer.mirror_source().is_some());
+        }
+
+        let lags = if self.lag {
+            let usages = fluvio
+                .admin()
+                .await
+                .partition_usage(PartitionUsageRequest::default())
+                .await?;
+            Some(consumer_lags(usages))
+        } else {
+            None
+        };
 
-        display::format_response_output(out, consumers, self.output.format)?;
+        display::format_response_output(out, consumers, lags, self.mirrored, self.output.format)?;
         Ok(())
     }
 }
 
+/// lag of each consumer, keyed by consumer id, topic and partition
+fn consumer_lags(usages: Vec<PartitionUsage>) -> HashMap<(String, String, PartitionId), i64> {
+    usages
+        .into_iter()
+        .flat_map(|usage| {
+            let (topic, partition) = usage.replica.split();
+            usage.consumers.into_iter().map(move |consumer| {
+                (
+                    (consumer.consumer_id, topic.clone(), partition),
+                    consumer.records,
+                )
+            })
+        })
+        .collect()
+}
+
 mod display {
 
+    use std::collections::HashMap;
     use std::time::{Duration, SystemTime};
 
     use comfy_table::{Row, Cell};
 
-    use fluvio::consumer::ConsumerOffset;
+    use fluvio::consumer::{ConsumerOffset, MirroredOffset};
+    use fluvio_types::PartitionId;
     use serde::Serialize;
 
     use crate::common::t_println;
     use crate::common::output::{OutputType, OutputError, Terminal, TableOutputHandler};
 
+    #[derive(Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+    struct ConsumerRow {
+        #[serde(flatten)]
+        consumer: ConsumerOffset,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        lag: Option<i64>,
+    }
+
     #[derive(Serialize)]
-    struct ListConsumers(Vec<ConsumerOffset>);
+    #[serde(transparent)]
+    struct ListConsumers {
+        consumers: Vec<ConsumerRow>,
+        #[serde(skip)]
+        with_lag: bool,
+        #[serde(skip)]
+        with_mirror: bool,
+    }
 
     impl IntoIterator for ListConsumers {
-        type Item = ConsumerOffset;
+        type Item = ConsumerRow;
         type IntoIter = std::vec::IntoIter<Self::Item>;
 
         fn into_iter(self) -> Self::IntoIter {
-            self.0.into_iter()
+            self.consumers.into_iter()
         }
     }
 
     pub fn format_response_output<O>(
         out: std::sync::Arc<O>,
         consumers: Vec<ConsumerOffset>,
+        lags: Option<HashMap<(String, String, PartitionId), i64>>,
+        with_mirror: bool,
         output_type: OutputType,
     ) -> Result<(), OutputError>
     where
         O: Terminal,
     {
         if !consumers.is_empty() {
-            out.render_list(&ListConsumers(consumers), output_type)?;
+            let with_lag = lags.is_some();
+            let consumers = consumers
+                .into_iter()
+                .map(|consumer| {
+                    let lag = lags.as_ref().and_then(|lags| {
+                        lags.get(&(
+                            consumer.consumer_id.clone(),
+                            consumer.topic.clone(),
+                            consumer.partition,
+                        ))
+                        .copied()
+                    });
+                    ConsumerRow { consumer, lag }
+                })
+                .collect();
+            out.render_list(
+                &ListConsumers {
+                    consumers,
+                    with_lag,
+                    with_mirror,
+                },
+                output_type,
+            )?;
         } else {
             t_println!(out, "No consumers found");
         }
@@ -68,7 +149,16 @@ mod display {
 
     impl TableOutputHandler for ListConsumers {
         fn header(&self) -> Row {
-            Row::from(["CONSUMER", "TOPIC", "PARTITION", "OFFSET", "LAST SEEN"])
+            let mut header = Row::from(["CONSUMER", "TOPIC", "PARTITION", "OFFSET", "LAST SEEN"]);
+            if self.with_lag {
+                header.add_cell(Cell::new("LAG"));
+            }
+            if self.with_mirror {
+                header.add_cell(Cell::new("SOURCE CLUSTER"));
+                header.add_cell(Cell::new("SOURCE PARTITION"));
+                header.add_cell(Cell::new("SOURCE OFFSET"));
+            }
+            header
         }
 
         fn errors(&self) -> Vec<String> {
@@ -80,26 +170,47 @@ mod display {
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs();
-            let mut list = self.0.clone();
+            let mut list = self.consumers.clone();
             list.sort();
             list.into_iter()
-                .map(|consumer| {
+                .map(|ConsumerRow { consumer, lag }| {
+                    let mirror_source = consumer.mirror_source().cloned();
                     let ConsumerOffset {
                         consumer_id,
                         offset,
                         modified_time,
                         topic,
                         partition,
+                        ..
                     } = consumer;
                     let last_seen =
                         humantime::Duration::from(Duration::from_secs(now - modified_time));
-                    Row::from([
+                    let mut row = Row::from([
                         Cell::new(consumer_id),
                         Cell::new(topic),
                         Cell::new(partition),
                         Cell::new(offset),
                         Cell::new(last_seen),
-                    ])
+                    ]);
+                    if self.with_lag {
+                        row.add_cell(Cell::new(
+                            lag.map(|lag| lag.to_string()).unwrap_or("-".to_owned()),
+                        ));
+                    }
+                    if self.with_mirror {
+                        if let Some(MirroredOffset {
+                            cluster,
+                            topic,
+                            partition,
+                            offset,
+                        }) = mirror_source
+                        {
+                            row.add_cell(Cell::new(cluster));
+                            row.add_cell(Cell::new(format!("{topic}-{partition}")));
+                            row.add_cell(Cell::new(offset));
+                        }
+                    }
+                    row
                 })
                 .collect()
         }
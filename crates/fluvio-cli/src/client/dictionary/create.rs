@@ -0,0 +1,44 @@
+//!
+//! # Create a compression dictionary
+//!
+//! Uploads a dictionary trained with `zstd --train` to the cluster
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::{Dictionary, Fluvio};
+use fluvio::metadata::dictionary::DictionarySpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateDictionaryOpt {
+    /// The name of the dictionary, referenced by topics
+    name: String,
+
+    /// The path to the trained zstd dictionary
+    #[arg(short, long)]
+    file: PathBuf,
+}
+
+impl CreateDictionaryOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let data = std::fs::read(&self.file)?;
+        let dictionary = Dictionary::new(data.clone())?;
+        let spec = DictionarySpec::new(dictionary.id(), data);
+
+        debug!(name = self.name, ?spec, "creating dictionary");
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("dictionary \"{}\" created", self.name);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+//! # List compression dictionaries CLI
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::dictionary::DictionarySpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListDictionariesOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListDictionariesOpt {
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let lists = admin.all::<DictionarySpec>().await?;
+
+        output::dictionaries_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    use comfy_table::{Row, Cell};
+    use comfy_table::CellAlignment;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::dictionary::DictionarySpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListDictionaries(Vec<Metadata<DictionarySpec>>);
+
+    /// Format dictionary list
+    pub fn dictionaries_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_dictionaries: Vec<Metadata<DictionarySpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("dictionaries: {:#?}", list_dictionaries);
+
+        if !list_dictionaries.is_empty() {
+            let dictionaries = ListDictionaries(list_dictionaries);
+            out.render_list(&dictionaries, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no dictionaries");
+            Ok(())
+        }
+    }
+
+    impl TableOutputHandler for ListDictionaries {
+        fn header(&self) -> Row {
+            Row::from(["NAME", "ID", "SIZE"])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    Row::from([
+                        Cell::new(&r.name).set_alignment(CellAlignment::Left),
+                        Cell::new(r.spec.dictionary_id).set_alignment(CellAlignment::Right),
+                        Cell::new(bytesize::ByteSize(r.spec.data.len() as u64))
+                            .set_alignment(CellAlignment::Right),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,71 @@
+mod create;
+mod delete;
+mod list;
+
+pub use cmd::DictionaryCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::create::CreateDictionaryOpt;
+    use super::delete::DeleteDictionaryOpt;
+    use super::list::ListDictionariesOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum DictionaryCmd {
+        /// Upload a trained zstd dictionary
+        #[command(
+            name = "create",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Create(CreateDictionaryOpt),
+
+        /// Delete a dictionary
+        #[command(
+            name = "delete",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Delete(DeleteDictionaryOpt),
+
+        /// List all dictionaries
+        #[command(
+            name = "list",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        List(ListDictionariesOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for DictionaryCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Create(create) => {
+                    create.process(fluvio).await?;
+                }
+                Self::Delete(delete) => {
+                    delete.process(fluvio).await?;
+                }
+                Self::List(list) => {
+                    list.process(out, fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
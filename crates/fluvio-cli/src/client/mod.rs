@@ -4,6 +4,7 @@ mod hub;
 mod produce;
 mod partition;
 mod tableformat;
+mod dictionary;
 mod smartmodule;
 mod smartmodule_invocation;
 mod consumer;
@@ -57,6 +58,7 @@ mod cmd {
     use super::topic::TopicCmd;
     use super::partition::PartitionCmd;
     use super::tableformat::TableFormatCmd;
+    use super::dictionary::DictionaryCmd;
     use super::hub::HubCmd;
 
     #[async_trait]
@@ -137,6 +139,13 @@ mod cmd {
         #[command(subcommand, name = "table-format", visible_alias = "tf")]
         TableFormat(TableFormatCmd),
 
+        /// Manage zstd compression dictionaries
+        ///
+        /// Dictionaries are trained from sample records with `zstd --train`
+        /// and referenced by topics with `--compression-dictionary`
+        #[command(subcommand, name = "dictionary")]
+        Dictionary(DictionaryCmd),
+
         /// Work with the SmartModule Hub
         #[command(subcommand, name = "hub")]
         Hub(HubCmd),
@@ -180,6 +189,9 @@ mod cmd {
                 Self::TableFormat(tableformat) => {
                     tableformat.process(out, target).await?;
                 }
+                Self::Dictionary(dictionary) => {
+                    dictionary.process(out, target).await?;
+                }
                 Self::Hub(hub) => {
                     hub.process(out, target).await?;
                 }
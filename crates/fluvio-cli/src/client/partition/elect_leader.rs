@@ -0,0 +1,51 @@
+//!
+//! # Elect Preferred Leader
+//!
+//! CLI tree to move partition leadership back to preferred replicas.
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::partition::{PartitionSpec, UpdatePartitionAction};
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_types::PartitionId;
+
+/// Move leaders back to preferred replica, which is first replica in replica map.
+/// Leadership only moves if preferred replica is online and in sync
+#[derive(Debug, Parser)]
+pub struct ElectLeaderOpt {
+    /// Topic name
+    #[arg(long, short, required_unless_present = "all")]
+    topic: Option<String>,
+    /// Partition of topic, all partitions of topic if not set
+    #[arg(long, short, requires = "topic")]
+    partition: Option<PartitionId>,
+    /// Elect preferred leaders of all partitions
+    #[arg(long, conflicts_with = "topic")]
+    all: bool,
+}
+
+impl ElectLeaderOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+
+        let key = match (&self.topic, self.partition) {
+            (Some(topic), Some(partition)) => ReplicaKey::new(topic.clone(), partition).to_string(),
+            (Some(topic), None) => topic.clone(),
+            (None, _) => String::new(),
+        };
+
+        admin
+            .update::<PartitionSpec>(key.clone(), UpdatePartitionAction::ElectPreferredLeader)
+            .await?;
+
+        if key.is_empty() {
+            println!("requested preferred leader election for all partitions");
+        } else {
+            println!("requested preferred leader election for: \"{key}\"");
+        }
+
+        Ok(())
+    }
+}
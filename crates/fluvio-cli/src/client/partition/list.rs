@@ -22,6 +22,9 @@ pub struct ListPartitionOpt {
     /// Show system partitions only
     #[arg(long, short, required = false)]
     system: bool,
+    /// Show storage usage, segments and follower lag
+    #[arg(long, short)]
+    detailed: bool,
 }
 
 impl ListPartitionOpt {
@@ -33,6 +36,14 @@ impl ListPartitionOpt {
         let output = self.output.format;
         let admin = fluvio.admin().await;
 
+        if self.detailed {
+            let usages = admin
+                .partition_usage(PartitionUsageRequest::default().system(self.system))
+                .await?;
+            display::format_partition_usage_output(out, usages, output)?;
+            return Ok(());
+        }
+
         let partitions = admin
             .list_with_config::<PartitionSpec, String>(ListRequest::default().system(self.system))
             .await?;
@@ -54,6 +65,7 @@ mod display {
 
     use fluvio::metadata::objects::Metadata;
     use fluvio::metadata::partition::*;
+    use fluvio_types::{PartitionId, SpuId};
 
     //use crate::error::CliError;
     use crate::common::t_println;
@@ -90,6 +102,150 @@ mod display {
         Ok(())
     }
 
+    #[derive(Serialize)]
+    struct FollowerUsage {
+        spu: SpuId,
+        leo: i64,
+        lag_records: i64,
+        lag_bytes: i64,
+    }
+
+    #[derive(Serialize)]
+    struct PartitionUsageRow {
+        topic: String,
+        partition: PartitionId,
+        leader: SpuId,
+        size: i64,
+        segments: u32,
+        log_start_offset: i64,
+        hw: i64,
+        leo: i64,
+        followers: Vec<FollowerUsage>,
+        consumers: usize,
+    }
+
+    impl From<PartitionUsage> for PartitionUsageRow {
+        fn from(usage: PartitionUsage) -> Self {
+            let (topic, partition) = usage.replica.split();
+            Self {
+                topic,
+                partition,
+                leader: usage.leader,
+                size: usage.size,
+                segments: usage.segments,
+                log_start_offset: usage.log_start_offset,
+                hw: usage.hw,
+                leo: usage.leo,
+                followers: usage
+                    .followers
+                    .into_iter()
+                    .map(|follower| FollowerUsage {
+                        spu: follower.spu,
+                        leo: follower.leo,
+                        lag_records: follower.records,
+                        lag_bytes: follower.bytes,
+                    })
+                    .collect(),
+                consumers: usage.consumers.len(),
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    struct ListUsages(Vec<PartitionUsageRow>);
+
+    impl IntoIterator for ListUsages {
+        type Item = PartitionUsageRow;
+        type IntoIter = std::vec::IntoIter<Self::Item>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.0.into_iter()
+        }
+    }
+
+    /// Process partition usage based on output type
+    pub fn format_partition_usage_output<O>(
+        out: std::sync::Arc<O>,
+        usages: Vec<PartitionUsage>,
+        output_type: OutputType,
+    ) -> Result<(), OutputError>
+    where
+        O: Terminal,
+    {
+        if !usages.is_empty() {
+            let mut rows: Vec<PartitionUsageRow> = usages.into_iter().map(Into::into).collect();
+            rows.sort_by(|a, b| (&a.topic, a.partition).cmp(&(&b.topic, b.partition)));
+            out.render_list(&ListUsages(rows), output_type)?;
+        } else {
+            t_println!(out, "No partitions found");
+        }
+
+        Ok(())
+    }
+
+    fn printable_size(size: i64) -> String {
+        match size {
+            PartitionStatus::SIZE_NOT_SUPPORTED => "NA".to_string(),
+            PartitionStatus::SIZE_ERROR => "ERROR".to_string(),
+            _ => bytesize::ByteSize::b(size as u64).to_string(),
+        }
+    }
+
+    impl TableOutputHandler for ListUsages {
+        fn header(&self) -> Row {
+            Row::from([
+                "TOPIC",
+                "PARTITION",
+                "LEADER",
+                "SIZE",
+                "SEGMENTS",
+                "LOG START",
+                "HW",
+                "LEO",
+                "FOLLOWER LAG",
+                "CONSUMERS",
+            ])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|usage| {
+                    let follower_lag = usage
+                        .followers
+                        .iter()
+                        .map(|follower| {
+                            format!(
+                                "{}: {} ({})",
+                                follower.spu,
+                                follower.lag_records,
+                                bytesize::ByteSize::b(follower.lag_bytes as u64)
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    Row::from([
+                        Cell::new(&usage.topic),
+                        Cell::new(usage.partition),
+                        Cell::new(usage.leader),
+                        Cell::new(printable_size(usage.size)),
+                        Cell::new(usage.segments),
+                        Cell::new(usage.log_start_offset),
+                        Cell::new(usage.hw),
+                        Cell::new(usage.leo),
+                        Cell::new(follower_lag),
+                        Cell::new(usage.consumers),
+                    ])
+                })
+                .collect()
+        }
+    }
+
     impl TableOutputHandler for ListSpus {
         /// table header implementation
         fn header(&self) -> Row {
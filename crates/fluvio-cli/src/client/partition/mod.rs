@@ -1,4 +1,5 @@
 mod list;
+mod elect_leader;
 
 pub use cmd::PartitionCmd;
 
@@ -18,6 +19,7 @@ mod cmd {
     use crate::common::FluvioExtensionMetadata;
 
     use super::list::ListPartitionOpt;
+    use super::elect_leader::ElectLeaderOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "partition", about = "Partition operations")]
@@ -28,6 +30,13 @@ mod cmd {
             help_template = crate::common::COMMAND_TEMPLATE,
         )]
         List(ListPartitionOpt),
+
+        /// Move partition leaders back to their preferred replica
+        #[command(
+            name = "elect-leader",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        ElectLeader(ElectLeaderOpt),
     }
 
     #[async_trait]
@@ -41,6 +50,9 @@ mod cmd {
                 Self::List(list) => {
                     list.process(out, fluvio).await?;
                 }
+                Self::ElectLeader(elect) => {
+                    elect.process(fluvio).await?;
+                }
             }
 
             Ok(())
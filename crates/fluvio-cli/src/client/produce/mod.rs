@@ -24,6 +24,7 @@ mod cmd {
     use fluvio::{
         Compression, Fluvio, FluvioError, TopicProducerPool, TopicProducerConfigBuilder, RecordKey,
         ProduceOutput, DeliverySemantic, SmartModuleContextData, Isolation, SmartModuleInvocation,
+        PartitionerKind,
     };
     use fluvio_extension_common::Terminal;
     use fluvio_types::{print_cli_ok, PartitionId};
@@ -90,6 +91,11 @@ mod cmd {
         #[arg(long)]
         pub compression: Option<Compression>,
 
+        /// Compression level to use when sending records.
+        /// Overrides the level configured on the topic.
+        #[arg(long)]
+        pub compression_level: Option<i32>,
+
         #[cfg(feature = "producer-file-io")]
         /// Path to a file to produce to the topic.
         /// Default: Each line treated as single record unless `--raw` specified.
@@ -181,6 +187,13 @@ mod cmd {
         /// Remote cluster to consume from
         #[arg(short = 'm', long, conflicts_with = "partition")]
         pub mirror: Option<String>,
+
+        /// Partitioner used to assign records to partitions.
+        /// Supported values: round-robin (default) - key-less records spread one by one,
+        /// sticky - key-less records stick to one partition until batch is full or linger expires,
+        /// murmur2 - keys placed as by Kafka default partitioner, key-less records sticky.
+        #[arg(long, conflicts_with_all = &["partition", "mirror"])]
+        pub partitioner: Option<PartitionerKind>,
     }
 
     fn validate_key_separator(separator: &str) -> std::result::Result<String, String> {
@@ -204,6 +217,9 @@ mod cmd {
             if let Some(compression) = self.compression {
                 config_builder.compression(compression);
             }
+            if let Some(level) = self.compression_level {
+                config_builder.compression_level(level);
+            }
             // Linger
             if let Some(linger) = self.linger {
                 config_builder.linger(linger);
@@ -216,6 +232,10 @@ mod cmd {
             if let Some(max_request_size) = self.max_request_size {
                 config_builder.max_request_size(max_request_size);
             }
+            // Partitioner
+            if let Some(partitioner) = self.partitioner {
+                config_builder.partitioner_kind(partitioner);
+            }
             // Isolation
             if let Some(isolation) = self.isolation {
                 config_builder.isolation(isolation);
@@ -0,0 +1,31 @@
+use clap::Parser;
+use fluvio_sc_schema::mirror::MirrorLinkConfig;
+use fluvio_sc_schema::topic::CompressionAlgorithm;
+
+/// Limits on records sent between home and remote
+#[derive(Debug, Default, Parser)]
+pub struct LinkOpt {
+    /// Maximum rate each SPU sends records over the mirror link
+    /// Ex: `2048`, '512 Ki', '10 MiB'
+    #[arg(long, value_name = "bytes")]
+    max_bytes_per_sec: Option<bytesize::ByteSize>,
+
+    /// Compression applied to records on the wire, independent of topic compression
+    #[arg(long, value_name = "compression")]
+    compression: Option<CompressionAlgorithm>,
+}
+
+impl LinkOpt {
+    /// link config, `None` if no limits are set
+    pub fn into_config(self) -> Option<MirrorLinkConfig> {
+        let config = MirrorLinkConfig {
+            max_bytes_per_sec: self.max_bytes_per_sec.map(|rate| rate.as_u64()),
+            compression: self.compression,
+        };
+        if config.is_unlimited() {
+            None
+        } else {
+            Some(config)
+        }
+    }
+}
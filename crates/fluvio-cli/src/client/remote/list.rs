@@ -36,6 +36,18 @@ impl ListOpt {
                         remote: r.id,
                         sc_status: status.pairing_sc.to_string(),
                         spu_status: status.pairing_spu.to_string(),
+                        directions: status.directions(),
+                        link: r
+                            .link
+                            .as_ref()
+                            .map(|link| link.to_string())
+                            .unwrap_or_else(|| "-".to_string()),
+                        traffic: status
+                            .connection_stat
+                            .link
+                            .as_ref()
+                            .map(|stat| stat.to_string())
+                            .unwrap_or_else(|| "-".to_string()),
                         last_seen: item.status.last_seen(now),
                         errors: status.pair_errors(),
                     })
@@ -52,6 +64,9 @@ struct RemoteStatusRow {
     remote: String,
     sc_status: String,
     spu_status: String,
+    directions: String,
+    link: String,
+    traffic: String,
     last_seen: String,
     errors: String,
 }
@@ -101,7 +116,16 @@ mod output {
     impl TableOutputHandler for TableList {
         /// table header implementation
         fn header(&self) -> Row {
-            Row::from(["REMOTE", "SC STATUS", "SPU STATUS", "LAST SEEN", "ERRORS"])
+            Row::from([
+                "REMOTE",
+                "SC STATUS",
+                "SPU STATUS",
+                "DIRECTIONS",
+                "LINK",
+                "TRAFFIC",
+                "LAST SEEN",
+                "ERRORS",
+            ])
         }
 
         /// return errors in string format
@@ -118,6 +142,9 @@ mod output {
                         Cell::new(&e.remote).set_alignment(CellAlignment::Left),
                         Cell::new(&e.sc_status).set_alignment(CellAlignment::Left),
                         Cell::new(&e.spu_status).set_alignment(CellAlignment::Left),
+                        Cell::new(&e.directions).set_alignment(CellAlignment::Left),
+                        Cell::new(&e.link).set_alignment(CellAlignment::Left),
+                        Cell::new(&e.traffic).set_alignment(CellAlignment::Left),
                         Cell::new(&e.last_seen).set_alignment(CellAlignment::Left),
                         Cell::new(&e.errors).set_alignment(CellAlignment::Left),
                     ])
@@ -2,6 +2,8 @@ pub mod unregister;
 pub mod list;
 pub mod register;
 pub mod export;
+pub mod update;
+mod link;
 
 use std::sync::Arc;
 use anyhow::Result;
@@ -10,6 +12,7 @@ use fluvio_extension_common::target::ClusterTarget;
 use unregister::UnregisterOpt;
 use list::ListOpt;
 use register::RegisterOpt;
+use update::UpdateOpt;
 use fluvio::FluvioAdmin;
 use fluvio_extension_common::output::Terminal;
 use self::export::ExportOpt;
@@ -22,6 +25,9 @@ pub enum RemoteCmd {
     /// List all remote clusters
     #[command(name = "list")]
     List(ListOpt),
+    /// Change link settings of a remote cluster
+    #[command(name = "update")]
+    Update(UpdateOpt),
     /// Unregister a remote cluster
     #[command(name = "unregister")]
     Unregister(UnregisterOpt),
@@ -38,6 +44,7 @@ impl RemoteCmd {
     ) -> Result<()> {
         match self {
             Self::Register(reg) => reg.execute(out, cluster_target).await,
+            Self::Update(update) => update.execute(out, cluster_target).await,
             Self::Unregister(del) => del.execute(out, cluster_target).await,
             Self::List(list) => list.execute(out, cluster_target).await,
             Self::Export(meta) => meta.execute(out, cluster_target).await,
@@ -6,9 +6,14 @@ use fluvio_extension_common::target::ClusterTarget;
 use fluvio_extension_common::Terminal;
 use fluvio_sc_schema::mirror::Remote;
 
+use super::link::LinkOpt;
+
 #[derive(Debug, Parser)]
 pub struct RegisterOpt {
     name: String,
+
+    #[clap(flatten)]
+    link: LinkOpt,
 }
 
 impl RegisterOpt {
@@ -26,6 +31,7 @@ impl RegisterOpt {
         let spec = MirrorSpec {
             mirror_type: MirrorType::Remote(Remote {
                 id: self.name.clone(),
+                link: self.link.into_config(),
             }),
         };
 
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+use fluvio_extension_common::target::ClusterTarget;
+use fluvio_extension_common::Terminal;
+use fluvio_sc_schema::mirror::{MirrorSpec, UpdateMirrorAction};
+
+use super::get_admin;
+use super::link::LinkOpt;
+
+#[derive(Debug, Parser)]
+pub struct UpdateOpt {
+    name: String,
+
+    /// Link settings replace the current ones, omit all to remove limits
+    #[clap(flatten)]
+    link: LinkOpt,
+}
+
+impl UpdateOpt {
+    pub async fn execute<T: Terminal>(
+        self,
+        _out: Arc<T>,
+        cluster_target: ClusterTarget,
+    ) -> Result<()> {
+        let admin = get_admin(cluster_target).await?;
+        let link = self.link.into_config();
+        let summary = link
+            .as_ref()
+            .map(|link| link.to_string())
+            .unwrap_or_else(|| "unlimited".to_string());
+        admin
+            .update::<MirrorSpec>(self.name.clone(), UpdateMirrorAction::SetLink(link))
+            .await?;
+        println!("remote cluster \"{}\" link was set to {summary}", self.name);
+        Ok(())
+    }
+}
@@ -3,12 +3,17 @@
 //!
 //! CLI tree to increment the number of partitions of a topic.
 //!
+use std::path::PathBuf;
+
 use clap::Parser;
 use anyhow::Result;
 
-use fluvio_sc_schema::topic::{AddMirror, TopicSpec, UpdateTopicAction};
+use fluvio_sc_schema::topic::{AddMirror, TopicSpec, Transform, UpdateTopicAction};
+use fluvio_smartengine::transformation::TransformationConfig;
 use fluvio::Fluvio;
 
+use crate::CliError;
+
 /// Option for Listing Mirror
 #[derive(Debug, Parser)]
 pub struct AddMirrorOpt {
@@ -19,6 +24,15 @@ pub struct AddMirrorOpt {
     /// if set, it will mirror from home to remote
     #[arg(long)]
     home_to_remote: bool,
+
+    /// (Optional) Path to a file with SmartModules applied to records before they are sent to the mirror
+    #[arg(long, alias = "transforms-file")]
+    transforms: Option<PathBuf>,
+
+    /// (Optional) SmartModule applied to records before they are sent to the mirror, as JSON formatted string.
+    /// E.g. --transforms-line='{"uses":"infinyon/jolt@0.1.0","with":{"spec":"[{\"operation\":\"remove\",\"spec\":{\"email\":\"\"}}]"}}'
+    #[arg(long, conflicts_with = "transforms", alias = "transform")]
+    transforms_line: Vec<String>,
 }
 
 impl AddMirrorOpt {
@@ -28,6 +42,7 @@ impl AddMirrorOpt {
         let request = AddMirror {
             remote_cluster: self.remote.clone(),
             home_to_mirror: self.home_to_remote,
+            transforms: self.mirror_transforms()?,
         };
 
         let action = UpdateTopicAction::AddMirror(request);
@@ -42,4 +57,31 @@ impl AddMirrorOpt {
 
         Ok(())
     }
+
+    fn mirror_transforms(&self) -> Result<Vec<Transform>> {
+        let config = if !self.transforms_line.is_empty() {
+            TransformationConfig::try_from(self.transforms_line.clone()).map_err(|err| {
+                CliError::InvalidArg(format!("unable to parse `transform` argument: {err}"))
+            })?
+        } else if let Some(transforms) = &self.transforms {
+            TransformationConfig::from_file(transforms).map_err(|err| {
+                CliError::InvalidArg(format!("unable to process `transforms` argument: {err}"))
+            })?
+        } else {
+            return Ok(vec![]);
+        };
+
+        Ok(config
+            .transforms
+            .into_iter()
+            .map(|step| Transform {
+                uses: step.uses,
+                with: step
+                    .with
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect(),
+            })
+            .collect())
+    }
 }
@@ -19,6 +19,7 @@ use fluvio::metadata::topic::ReplicaSpec;
 use fluvio::metadata::topic::SegmentBasedPolicy;
 use fluvio::metadata::topic::TopicStorageConfig;
 use fluvio::metadata::topic::CompressionAlgorithm;
+use fluvio::metadata::topic::EncryptionConfig;
 
 use fluvio_controlplane_metadata::topic::config::TopicConfig;
 use fluvio_sc_schema::shared::validate_resource_name;
@@ -145,6 +146,10 @@ pub struct CreateTopicOpt {
     /// signify that this topic can be mirror from home to edge
     #[arg(long)]
     home_to_remote: bool,
+
+    /// signify that home and remotes accept producers and replicate to each other
+    #[arg(long, conflicts_with = "home_to_remote")]
+    bidirectional: bool,
 }
 
 impl CreateTopicOpt {
@@ -181,6 +186,9 @@ impl CreateTopicOpt {
             let mut config = MirrorConfig::read_from_json_file(mirror_assign_file, &topic_name)?;
 
             config.set_home_to_remote(self.home_to_remote)?;
+            if self.bidirectional {
+                config.set_bidirectional(true)?;
+            }
 
             let targets = match config {
                 MirrorConfig::Home(ref c) => c
@@ -218,6 +226,7 @@ impl CreateTopicOpt {
         } else if self.mirror {
             let mut home_mirror = HomeMirrorConfig::from(vec![]);
             home_mirror.source = self.home_to_remote;
+            home_mirror.set_bidirectional(self.bidirectional);
             let mirror_map = MirrorConfig::Home(home_mirror);
             ReplicaSpec::Mirror(mirror_map)
         } else {
@@ -238,6 +247,10 @@ impl CreateTopicOpt {
         if let Some(compression_type) = self.setting.compression_type {
             topic_spec.set_compression_type(compression_type);
         }
+        topic_spec.set_compression_level(self.setting.compression_level);
+        topic_spec.set_compression_dictionary(self.setting.compression_dictionary);
+        topic_spec.set_min_in_sync_replicas(self.setting.min_in_sync_replicas);
+        topic_spec.set_unclean_leader_election(self.setting.unclean_leader_election);
 
         if self.setting.dedup {
             let sm = admin
@@ -261,7 +274,10 @@ impl CreateTopicOpt {
 
         topic_spec.set_system(self.setting.system);
 
-        if self.setting.segment_size.is_some() || self.setting.max_partition_size.is_some() {
+        if self.setting.segment_size.is_some()
+            || self.setting.max_partition_size.is_some()
+            || self.setting.encryption_key_id.is_some()
+        {
             let mut storage = TopicStorageConfig::default();
 
             if let Some(segment_size) = self.setting.segment_size {
@@ -272,6 +288,10 @@ impl CreateTopicOpt {
                 storage.max_partition_size = Some(max_partition_size.as_u64());
             }
 
+            if let Some(key_id) = self.setting.encryption_key_id {
+                storage.encryption = Some(EncryptionConfig { key_id });
+            }
+
             topic_spec.set_storage(storage);
         }
 
@@ -316,15 +336,29 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "bytes")]
     segment_size: Option<bytesize::ByteSize>,
 
-    /// Compression configuration for topic
+    /// Compression configuration for topic.
+    /// Batches produced with a different compression are recompressed by the SPU.
     #[arg(long, value_name = "compression")]
     compression_type: Option<CompressionAlgorithm>,
 
+    /// Compression level used by producers and SPU recompression, gzip: 0-9, zstd: 1-22
+    #[arg(long, value_name = "level", requires = "compression_type")]
+    compression_level: Option<i32>,
+
+    /// Name of zstd dictionary used to compress records, see `fluvio dictionary`
+    #[arg(long, value_name = "dictionary", requires = "compression_type")]
+    compression_dictionary: Option<String>,
+
     /// Max partition size (by default measured in bytes)
     /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
     #[arg(long, value_name = "bytes")]
     max_partition_size: Option<bytesize::ByteSize>,
 
+    /// Encrypt partition segments at rest with the master key of this id
+    /// Key must be available in the encryption key directory of every SPU
+    #[arg(long, value_name = "key id")]
+    encryption_key_id: Option<String>,
+
     /// Deduplicate records in the topic
     #[arg(long)]
     dedup: bool,
@@ -338,6 +372,15 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "time", value_parser=parse_duration, requires = "dedup", default_value = "5s")]
     dedup_age: Duration,
 
+    /// Min number of in sync replicas, including leader, for committed produce to be accepted
+    #[arg(long, value_name = "integer")]
+    min_in_sync_replicas: Option<u16>,
+
+    /// Allow out of sync replica to become leader when all in sync replicas are lost.
+    /// Favors availability over durability, records not replicated to new leader are lost
+    #[arg(long)]
+    unclean_leader_election: bool,
+
     /// Flag to create a system topic
     /// System topics are for internal operations
     #[arg(long, short = 's', hide = true)]
@@ -0,0 +1,49 @@
+//!
+//! # Drain SPU
+//!
+//! CLI tree to move leadership and replicas off an SPU
+//!
+use anyhow::Result;
+use clap::Parser;
+
+use fluvio::Fluvio;
+use fluvio::metadata::spu::{SpuDrain, SpuSpec, UpdateSpuAction};
+use fluvio_types::SpuId;
+
+/// Drain SPU. Leadership of its partitions is moved to other in sync replicas
+/// and no new partitions are assigned to it. Progress is shown by `fluvio cluster spu list`
+#[derive(Debug, Parser)]
+pub struct DrainSpuOpt {
+    /// SPU id
+    #[arg(short = 'i', long = "id")]
+    id: SpuId,
+
+    /// Also migrate replicas of SPU to other SPUs
+    #[arg(long, conflicts_with = "cancel")]
+    migrate_replicas: bool,
+
+    /// Cancel drain, SPU becomes eligible for leadership and new partitions again
+    #[arg(long)]
+    cancel: bool,
+}
+
+impl DrainSpuOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let action = if self.cancel {
+            UpdateSpuAction::CancelDrain
+        } else {
+            UpdateSpuAction::Drain(SpuDrain {
+                migrate_replicas: self.migrate_replicas,
+            })
+        };
+        admin.update::<SpuSpec>(self.id, action).await?;
+
+        if self.cancel {
+            println!("cancelled drain of spu: {}", self.id);
+        } else {
+            println!("draining spu: {}", self.id);
+        }
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ mod list;
 mod display;
 mod register;
 mod unregister;
+mod drain;
 
 use anyhow::Result;
 
@@ -12,6 +13,7 @@ use fluvio::Fluvio;
 use list::ListSpusOpt;
 use register::RegisterCustomSpuOpt;
 use unregister::UnregisterCustomSpuOpt;
+use drain::DrainSpuOpt;
 
 use super::common::COMMAND_TEMPLATE;
 use super::common::output::Terminal;
@@ -32,6 +34,13 @@ pub enum SpuCmd {
     )]
     Unregister(UnregisterCustomSpuOpt),
 
+    /// Move leadership and optionally replicas off an SPU before taking it down
+    #[command(
+        name = "drain",
+        help_template = COMMAND_TEMPLATE,
+    )]
+    Drain(DrainSpuOpt),
+
     /// List all SPUs known by this cluster (managed AND custom)
     #[command(
         name = "list",
@@ -49,6 +58,9 @@ impl SpuCmd {
             Self::Unregister(unregister) => {
                 unregister.process(fluvio).await?;
             }
+            Self::Drain(drain) => {
+                drain.process(fluvio).await?;
+            }
             Self::List(list) => {
                 list.process(out, fluvio).await?;
             }
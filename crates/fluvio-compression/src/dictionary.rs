@@ -0,0 +1,82 @@
+use bytes::Bytes;
+
+use crate::error::CompressionError;
+
+/// A trained zstd dictionary.
+///
+/// Dictionaries are trained offline (e.g. `zstd --train`) from sample records and
+/// stored in the cluster. The dictionary id is embedded by zstd in every frame
+/// compressed with the dictionary, so consumers can find the matching dictionary.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Dictionary {
+    id: u32,
+    data: Bytes,
+}
+
+impl std::fmt::Debug for Dictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dictionary")
+            .field("id", &self.id)
+            .field("len", &self.data.len())
+            .finish()
+    }
+}
+
+impl Dictionary {
+    /// Load dictionary from its raw content, reading the dictionary id from the dictionary header
+    #[cfg(feature = "zstd")]
+    pub fn new(data: impl Into<Bytes>) -> Result<Self, CompressionError> {
+        let data = data.into();
+        let id = crate::zstd::dictionary_id(&data).ok_or(CompressionError::InvalidDictionary)?;
+        Ok(Self { id, data })
+    }
+
+    /// Load dictionary from its raw content, reading the dictionary id from the dictionary header
+    #[cfg(not(feature = "zstd"))]
+    pub fn new(_data: impl Into<Bytes>) -> Result<Self, CompressionError> {
+        Err(CompressionError::InvalidDictionary)
+    }
+
+    /// Id of dictionary as embedded in the compressed frames
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Raw dictionary content
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Dictionary id referenced by a compressed zstd frame, if any
+    #[cfg(feature = "zstd")]
+    pub fn id_from_frame(src: &[u8]) -> Option<u32> {
+        crate::zstd::frame_dictionary_id(src)
+    }
+
+    /// Dictionary id referenced by a compressed zstd frame, if any
+    #[cfg(not(feature = "zstd"))]
+    pub fn id_from_frame(_src: &[u8]) -> Option<u32> {
+        None
+    }
+}
+
+/// Tuning applied when compressing a batch
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompressionOptions {
+    /// Compression level, `None` uses the algorithm default
+    pub level: Option<i32>,
+    /// Dictionary used by zstd
+    pub dictionary: Option<Dictionary>,
+}
+
+impl CompressionOptions {
+    pub fn level(mut self, level: i32) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    pub fn dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+}
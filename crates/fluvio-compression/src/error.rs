@@ -15,6 +15,18 @@ pub enum CompressionError {
     UnreachableError,
     #[error("unknown compression format: {0}")]
     UnknownCompressionFormat(String),
+    #[error("compression level {level} is not valid for {algorithm}, expected {expected}")]
+    InvalidLevel {
+        algorithm: String,
+        level: i32,
+        expected: String,
+    },
+    #[error("{0} compression does not support dictionaries")]
+    DictionaryNotSupported(String),
+    #[error("invalid zstd dictionary")]
+    InvalidDictionary,
+    #[error("batch was compressed with dictionary {0} which is not available")]
+    MissingDictionary(u32),
     #[error("error flushing Snap encoder: {0}")]
     #[cfg(feature = "compress")]
     SnapError(#[from] Box<IntoInnerError<FrameEncoder<Writer<BytesMut>>>>),
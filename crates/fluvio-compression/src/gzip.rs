@@ -8,18 +8,29 @@ use flate2::write::GzEncoder;
 use crate::error::CompressionError;
 
 pub fn compress(src: &[u8]) -> Result<Bytes, CompressionError> {
-    let mut encoder = GzEncoder::new(BytesMut::new().writer(), Compression::default());
+    compress_with(src, Compression::default().level())
+}
+
+pub fn compress_with(src: &[u8], level: u32) -> Result<Bytes, CompressionError> {
+    let mut encoder = GzEncoder::new(BytesMut::new().writer(), Compression::new(level));
     encoder.write_all(src)?;
     Ok(encoder.finish()?.into_inner().freeze())
 }
 
 pub fn uncompress<T: Read>(src: T) -> Result<Vec<u8>, CompressionError> {
-    let mut decoder = GzDecoder::new(src);
     let mut buffer: Vec<u8> = Vec::new();
-    decoder.read_to_end(&mut buffer)?;
+    decoder(src).read_to_end(&mut buffer)?;
     Ok(buffer)
 }
 
+pub(crate) fn decoder<T: Read>(src: T) -> impl Read {
+    GzDecoder::new(src)
+}
+
+pub(crate) fn level_range() -> std::ops::RangeInclusive<i32> {
+    0..=9
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Buf;
@@ -36,4 +47,14 @@ mod tests {
 
         assert_eq!(uncompressed, text);
     }
+
+    #[test]
+    fn test_compress_level() {
+        let text = "FLUVIO_AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+        let compressed = compress_with(text.as_bytes(), 9).unwrap();
+
+        let uncompressed = String::from_utf8(uncompress(compressed.reader()).unwrap()).unwrap();
+
+        assert_eq!(uncompressed, text);
+    }
 }
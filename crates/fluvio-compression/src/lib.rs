@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 mod error;
+mod dictionary;
 
 use bytes::Bytes;
 
@@ -17,6 +18,7 @@ mod lz4;
 mod zstd;
 
 pub use error::CompressionError;
+pub use dictionary::{Dictionary, CompressionOptions};
 use serde::{Serialize, Deserialize};
 
 /// The compression algorithm used to compress and decompress records in fluvio batches
@@ -93,6 +95,69 @@ impl std::fmt::Display for Compression {
 }
 
 impl Compression {
+    /// Range of compression levels accepted by the algorithm, `None` if levels are not supported
+    pub fn level_range(&self) -> Option<std::ops::RangeInclusive<i32>> {
+        match *self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Some(gzip::level_range()),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Some(zstd::level_range()),
+            _ => None,
+        }
+    }
+
+    /// Check that level can be used with this algorithm
+    pub fn validate_level(&self, level: i32) -> Result<(), CompressionError> {
+        match self.level_range() {
+            Some(range) if range.contains(&level) => Ok(()),
+            range => Err(CompressionError::InvalidLevel {
+                algorithm: self.to_string(),
+                level,
+                expected: range
+                    .map(|range| format!("{}..={}", range.start(), range.end()))
+                    .unwrap_or_else(|| "no level".to_string()),
+            }),
+        }
+    }
+
+    /// Whether the algorithm can use a trained dictionary
+    pub fn supports_dictionary(&self) -> bool {
+        #[cfg(feature = "zstd")]
+        if *self == Compression::Zstd {
+            return true;
+        }
+        false
+    }
+
+    /// Compress the given data with level and dictionary from options
+    pub fn compress_with(
+        &self,
+        src: &[u8],
+        options: &CompressionOptions,
+    ) -> Result<Bytes, CompressionError> {
+        if let Some(level) = options.level {
+            self.validate_level(level)?;
+        }
+        if options.dictionary.is_some() && !self.supports_dictionary() {
+            return Err(CompressionError::DictionaryNotSupported(self.to_string()));
+        }
+
+        match *self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => match options.level {
+                Some(level) => gzip::compress_with(src, level as u32),
+                None => gzip::compress(src),
+            },
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => zstd::compress_with(
+                src,
+                options.level.unwrap_or(zstd::DEFAULT_LEVEL),
+                options.dictionary.as_ref(),
+            ),
+            _ => self.compress(src),
+        }
+    }
+
     /// Compress the given data, returning the compressed data
     pub fn compress(&self, src: &[u8]) -> Result<Bytes, CompressionError> {
         match *self {
@@ -135,6 +200,84 @@ impl Compression {
             }
         }
     }
+
+    /// Uncompress the given data like [`Compression::uncompress`], failing with
+    /// [`std::io::ErrorKind::InvalidData`] once uncompressed data exceeds `max_size` bytes
+    #[allow(unused_variables)]
+    pub fn uncompress_bounded(
+        &self,
+        src: &[u8],
+        max_size: usize,
+    ) -> Result<Option<Vec<u8>>, CompressionError> {
+        match *self {
+            Compression::None => Ok(None),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                let output = read_bounded(gzip::decoder(src), max_size)?;
+                Ok(Some(output))
+            }
+            #[cfg(feature = "snap")]
+            Compression::Snappy => {
+                let output = read_bounded(snappy::decoder(src), max_size)?;
+                Ok(Some(output))
+            }
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => {
+                let output = read_bounded(lz4::decoder(src), max_size)?;
+                Ok(Some(output))
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                let output = read_bounded(zstd::decoder(src)?, max_size)?;
+                Ok(Some(output))
+            }
+        }
+    }
+
+    /// Uncompress the given data like [`Compression::uncompress`], using the dictionary
+    /// if the data was compressed with one
+    #[cfg(feature = "zstd")]
+    pub fn uncompress_with(
+        &self,
+        src: &[u8],
+        dictionary: Option<&Dictionary>,
+    ) -> Result<Option<Vec<u8>>, CompressionError> {
+        match *self {
+            Compression::Zstd => {
+                let output = zstd::uncompress_with(src, dictionary)?;
+                Ok(Some(output))
+            }
+            _ => self.uncompress(src),
+        }
+    }
+
+    /// Uncompress the given data like [`Compression::uncompress`], using the dictionary
+    /// if the data was compressed with one
+    #[cfg(not(feature = "zstd"))]
+    pub fn uncompress_with(
+        &self,
+        src: &[u8],
+        _dictionary: Option<&Dictionary>,
+    ) -> Result<Option<Vec<u8>>, CompressionError> {
+        self.uncompress(src)
+    }
+}
+
+/// read uncompressed data, reading at most one byte over the limit to detect it was exceeded
+#[cfg(any(feature = "gzip", feature = "snap", feature = "lz4", feature = "zstd"))]
+fn read_bounded(decoder: impl std::io::Read, max_size: usize) -> Result<Vec<u8>, CompressionError> {
+    use std::io::Read;
+
+    let mut buffer: Vec<u8> = Vec::new();
+    decoder.take(max_size as u64 + 1).read_to_end(&mut buffer)?;
+    if buffer.len() > max_size {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("uncompressed data exceeds {max_size} bytes"),
+        )
+        .into());
+    }
+    Ok(buffer)
 }
 
 #[cfg(any(feature = "gzip", feature = "snap", feature = "lz4", feature = "zstd"))]
@@ -180,4 +323,50 @@ mod tests {
         #[cfg(feature = "zstd")]
         assert_eq!(Compression::from(CompressionType::Zstd), Compression::Zstd);
     }
+
+    #[test]
+    fn validates_level() {
+        assert!(Compression::None.validate_level(1).is_err());
+
+        #[cfg(feature = "gzip")]
+        {
+            assert!(Compression::Gzip.validate_level(9).is_ok());
+            assert!(Compression::Gzip.validate_level(10).is_err());
+        }
+
+        #[cfg(feature = "lz4")]
+        assert!(Compression::Lz4.validate_level(1).is_err());
+
+        #[cfg(feature = "zstd")]
+        {
+            assert!(Compression::Zstd.validate_level(19).is_ok());
+            assert!(Compression::Zstd.validate_level(100).is_err());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "compress")]
+    fn uncompress_bounded() {
+        let text = "FLUVIO_AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".repeat(10);
+        for compression in [
+            Compression::Gzip,
+            Compression::Snappy,
+            Compression::Lz4,
+            Compression::Zstd,
+        ] {
+            let compressed = compression.compress(text.as_bytes()).expect("compress");
+            let uncompressed = compression
+                .uncompress_bounded(&compressed, text.len())
+                .expect("within bound");
+            assert_eq!(uncompressed.as_deref(), Some(text.as_bytes()));
+
+            let err = compression
+                .uncompress_bounded(&compressed, text.len() - 1)
+                .expect_err("exceeds bound");
+            assert!(
+                err.to_string().contains("exceeds"),
+                "{compression:?}: {err}"
+            );
+        }
+    }
 }
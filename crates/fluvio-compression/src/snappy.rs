@@ -16,11 +16,15 @@ pub fn compress(src: &[u8]) -> Result<Bytes, CompressionError> {
 
 pub fn uncompress<T: Read>(src: T) -> Result<Vec<u8>, CompressionError> {
     let mut buffer: Vec<u8> = Vec::new();
-    FrameDecoder::new(src).read_to_end(&mut buffer)?;
+    decoder(src).read_to_end(&mut buffer)?;
 
     Ok(buffer)
 }
 
+pub(crate) fn decoder<T: Read>(src: T) -> impl Read {
+    FrameDecoder::new(src)
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Buf;
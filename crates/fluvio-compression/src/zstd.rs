@@ -2,22 +2,71 @@ use std::io::{Read, Write};
 
 use bytes::{BufMut, Bytes, BytesMut};
 use zstd::{Decoder, Encoder};
+use zstd::zstd_safe;
 
+use crate::Dictionary;
 use crate::error::CompressionError;
 
+pub(crate) const DEFAULT_LEVEL: i32 = 1;
+
 pub fn compress(src: &[u8]) -> Result<Bytes, CompressionError> {
-    let mut encoder = Encoder::new(BytesMut::new().writer(), 1)?;
+    compress_with(src, DEFAULT_LEVEL, None)
+}
+
+pub fn compress_with(
+    src: &[u8],
+    level: i32,
+    dictionary: Option<&Dictionary>,
+) -> Result<Bytes, CompressionError> {
+    let writer = BytesMut::new().writer();
+    let mut encoder = match dictionary {
+        Some(dictionary) => Encoder::with_dictionary(writer, level, dictionary.data())?,
+        None => Encoder::new(writer, level)?,
+    };
     encoder.write_all(src)?;
     Ok(encoder.finish()?.into_inner().freeze())
 }
 
 pub fn uncompress<T: Read>(src: T) -> Result<Vec<u8>, CompressionError> {
-    let mut decoder = Decoder::new(src)?;
+    let mut buffer: Vec<u8> = Vec::new();
+    decoder(src)?.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+pub(crate) fn decoder<T: Read>(src: T) -> Result<impl Read, CompressionError> {
+    Ok(Decoder::new(src)?)
+}
+
+/// Uncompress frame, using dictionary if frame was compressed with one
+pub fn uncompress_with(
+    src: &[u8],
+    dictionary: Option<&Dictionary>,
+) -> Result<Vec<u8>, CompressionError> {
+    let Some(id) = frame_dictionary_id(src) else {
+        return uncompress(src);
+    };
+    let dictionary = dictionary
+        .filter(|dictionary| dictionary.id() == id)
+        .ok_or(CompressionError::MissingDictionary(id))?;
+
+    let mut decoder = Decoder::with_dictionary(src, dictionary.data())?;
     let mut buffer: Vec<u8> = Vec::new();
     decoder.read_to_end(&mut buffer)?;
     Ok(buffer)
 }
 
+pub(crate) fn level_range() -> std::ops::RangeInclusive<i32> {
+    zstd::compression_level_range()
+}
+
+pub(crate) fn dictionary_id(dictionary: &[u8]) -> Option<u32> {
+    zstd_safe::get_dict_id_from_dict(dictionary).map(|id| id.get())
+}
+
+pub(crate) fn frame_dictionary_id(src: &[u8]) -> Option<u32> {
+    zstd_safe::get_dict_id_from_frame(src).map(|id| id.get())
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Buf;
@@ -34,4 +83,21 @@ mod tests {
 
         assert_eq!(uncompressed, text);
     }
+
+    #[test]
+    fn test_compress_level() {
+        let text = "FLUVIO_AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".repeat(10);
+        let compressed = compress_with(text.as_bytes(), 19, None).unwrap();
+
+        assert!(compressed.len() < text.len());
+        assert_eq!(frame_dictionary_id(&compressed), None);
+
+        let uncompressed = uncompress_with(&compressed, None).unwrap();
+        assert_eq!(uncompressed, text.as_bytes());
+    }
+
+    #[test]
+    fn test_raw_content_is_not_dictionary() {
+        assert!(Dictionary::new(b"not a trained dictionary".to_vec()).is_err());
+    }
 }
@@ -13,7 +13,7 @@ pub use fluvio_connector_derive::connector;
 use fluvio::{Offset, metadata::topic::TopicSpec};
 use futures::stream::LocalBoxStream;
 use async_trait::async_trait;
-use ::tracing::{info, error};
+use ::tracing::{info, error, warn};
 
 pub type Error = anyhow::Error;
 pub type Result<T> = std::result::Result<T, Error>;
@@ -21,7 +21,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub mod future {
     pub use fluvio_future::task::run_block_on;
     pub use tokio::select;
-    pub use fluvio_future::subscriber::init_logger;
+    pub use super::init_logger;
     pub use fluvio_future::retry;
 }
 
@@ -29,6 +29,37 @@ pub mod tracing {
     pub use ::tracing::*;
 }
 
+/// Keeps the OTLP exporter, if any, running until dropped
+pub struct LoggerGuard {
+    #[cfg(feature = "otlp")]
+    _otlp: Option<fluvio::otel::OtlpGuard>,
+}
+
+/// Initializes logging to stderr.
+///
+/// With the `otlp` feature, spans are also exported to the collector set in
+/// `OTEL_EXPORTER_OTLP_ENDPOINT`. Keep the returned guard alive for the
+/// lifetime of the connector so that pending spans are flushed on exit.
+#[must_use]
+pub fn init_logger() -> LoggerGuard {
+    #[cfg(feature = "otlp")]
+    let otlp_error = match fluvio::otel::init_otlp_tracer("fluvio-connector") {
+        Ok(Some(guard)) => return LoggerGuard { _otlp: Some(guard) },
+        Ok(None) => None,
+        Err(err) => Some(err),
+    };
+    fluvio_future::subscriber::init_logger();
+    // reported once the fallback logger is installed
+    #[cfg(feature = "otlp")]
+    if let Some(err) = otlp_error {
+        warn!("failed to initialize OTLP exporter: {err:#}");
+    }
+    LoggerGuard {
+        #[cfg(feature = "otlp")]
+        _otlp: None,
+    }
+}
+
 #[async_trait]
 pub trait Source<'a, I> {
     async fn connect(self, offset: Option<Offset>) -> Result<LocalBoxStream<'a, I>>;
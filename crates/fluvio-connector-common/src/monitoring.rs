@@ -1,8 +1,10 @@
 use std::{io::Error as IoError, sync::Arc, collections::HashMap};
 
+use async_trait::async_trait;
 use futures_util::{AsyncWriteExt, StreamExt};
 
 use fluvio::metrics::ClientMetrics;
+use fluvio_metrics::{MetricType, MetricsSource, OpenMetricsEncoder, init_metrics_server};
 use fluvio_future::task::spawn;
 use fluvio_future::net::unix::UnixListener;
 use tracing::{error, info, trace};
@@ -10,6 +12,8 @@ use serde::Serialize;
 use fluvio_smartengine::metrics::SmartModuleChainMetrics;
 
 const SOCKET_PATH: &str = "/tmp/fluvio-connector.sock";
+/// env var with address to serve OpenMetrics at `/metrics`
+const METRICS_ADDR_ENV: &str = "FLUVIO_METRICS_ADDR_CONNECTOR";
 
 #[derive(Debug, Serialize)]
 pub struct ConnectorMetrics {
@@ -66,6 +70,10 @@ impl ConnectorMetrics {
 }
 
 pub fn init_monitoring(metrics: Arc<ConnectorMetrics>) {
+    if let Ok(addr) = std::env::var(METRICS_ADDR_ENV) {
+        info!(%addr, "starting metrics endpoint");
+        init_metrics_server(addr, metrics.clone());
+    }
     spawn(async move {
         if let Err(err) = start_monitoring(metrics).await {
             error!("error running monitoring: {}", err);
@@ -120,3 +128,95 @@ async fn start_monitoring(metrics: Arc<ConnectorMetrics>) -> Result<(), IoError>
         fluvio_future::timer::sleep(std::time::Duration::from_secs(5)).await;
     }
 }
+
+#[async_trait]
+impl MetricsSource for ConnectorMetrics {
+    async fn encode(&self, encoder: &mut OpenMetricsEncoder) {
+        let counters = [
+            ("consumer", self.fluvio_metrics.consumer()),
+            (
+                "producer_connector",
+                self.fluvio_metrics.producer_connector(),
+            ),
+            ("producer_client", self.fluvio_metrics.producer_client()),
+        ];
+        let mut records = encoder.family(
+            "fluvio_connector_records",
+            MetricType::Counter,
+            "Records consumed and produced by the connector",
+        );
+        for (client, counter) in &counters {
+            records.sample(&[("client", *client)], counter.records());
+        }
+        let mut bytes = encoder.family(
+            "fluvio_connector_bytes",
+            MetricType::Counter,
+            "Bytes consumed and produced by the connector",
+        );
+        for (client, counter) in &counters {
+            bytes.sample(&[("client", *client)], counter.bytes());
+        }
+
+        let mut smartmodules = self.fluvio_metrics.smartmodules();
+        for (name, metrics) in &self.smartmodule_metrics {
+            match smartmodules.get(name) {
+                Some(existing) => existing.append(metrics),
+                None => {
+                    smartmodules.insert(name.clone(), metrics.clone());
+                }
+            }
+        }
+        encode_smartmodule_metrics(encoder, &smartmodules);
+    }
+}
+
+fn encode_smartmodule_metrics(
+    encoder: &mut OpenMetricsEncoder,
+    metrics: &HashMap<String, SmartModuleChainMetrics>,
+) {
+    let counters: [(&str, &str, fn(&SmartModuleChainMetrics) -> u64); 5] = [
+        (
+            "fluvio_connector_smartmodule_invocations",
+            "SmartModule chain invocations",
+            SmartModuleChainMetrics::invocation_count,
+        ),
+        (
+            "fluvio_connector_smartmodule_bytes_in",
+            "Bytes processed by SmartModule chain",
+            SmartModuleChainMetrics::bytes_in,
+        ),
+        (
+            "fluvio_connector_smartmodule_records_out",
+            "Records returned by SmartModule chain",
+            SmartModuleChainMetrics::records_out,
+        ),
+        (
+            "fluvio_connector_smartmodule_records_err",
+            "Records failed in SmartModule chain",
+            SmartModuleChainMetrics::records_err,
+        ),
+        (
+            "fluvio_connector_smartmodule_fuel_used",
+            "Fuel consumed by SmartModule chain",
+            SmartModuleChainMetrics::fuel_used,
+        ),
+    ];
+    for (name, help, value) in counters {
+        let mut family = encoder.family(name, MetricType::Counter, help);
+        for (smartmodule, chain) in metrics {
+            family.sample(&[("smartmodule", smartmodule.as_str())], value(chain));
+        }
+    }
+
+    let mut cpu = encoder.family(
+        "fluvio_connector_smartmodule_cpu_seconds",
+        MetricType::Counter,
+        "CPU time spent in SmartModule chain",
+    );
+    for (smartmodule, chain) in metrics {
+        cpu.sample(
+            &[("smartmodule", smartmodule.as_str())],
+            chain.cpu_ms() as f64 / 1000.0,
+        );
+    }
+}
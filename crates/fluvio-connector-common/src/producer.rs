@@ -26,6 +26,11 @@ pub async fn producer_from_config(config: &ConnectorConfig) -> Result<(Fluvio, T
         if let Some(batch_size) = producer_params.batch_size {
             config_builder = config_builder.batch_size(batch_size.as_u64() as usize)
         };
+
+        // Partitioner
+        if let Some(partitioner) = producer_params.partitioner {
+            config_builder = config_builder.partitioner_kind(partitioner)
+        };
     };
 
     let producer_config = config_builder.build()?;
@@ -128,7 +128,7 @@ fn init_and_parse_config(config_type_path: &Path) -> TokenStream {
             }
         }
 
-        ::fluvio_connector_common::future::init_logger();
+        let _logger = ::fluvio_connector_common::future::init_logger();
 
         let opts = ConnectorOpt::parse();
 
@@ -18,6 +18,7 @@ pub use fluvio_controlplane_metadata::topic::config as topic_config;
 pub use fluvio_smartengine::transformation::TransformationStep;
 pub use fluvio_types::PartitionId;
 pub use fluvio_types::compression::Compression;
+pub use fluvio_types::partitioner::PartitionerKind;
 
 use crate::metadata::Direction;
 
@@ -311,6 +312,9 @@ pub struct ProducerParameters {
     )]
     #[schemars(skip)]
     pub batch_size: Option<ByteSize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partitioner: Option<PartitionerKind>,
 }
 #[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Hash, JsonSchema)]
 pub struct SecretConfig {
@@ -702,6 +706,7 @@ mod tests {
                     linger: Some(Duration::from_millis(1)),
                     compression: Some(Compression::Gzip),
                     batch_size: Some(ByteSize::mb(44)),
+                    partitioner: None,
                 }),
                 consumer: Some(ConsumerParameters {
                     partition: ConsumerPartitionConfig::One(10),
@@ -753,6 +758,8 @@ mod tests {
                         replication: Some(2),
                         ignore_rack_assignment: Some(true),
                         maps: None,
+                        min_in_sync_replicas: None,
+                        unclean_leader_election: None,
                     },
                     retention: RetentionConfig {
                         time: Some(Duration::from_secs(120)),
@@ -760,6 +767,7 @@ mod tests {
                     },
                     compression: CompressionConfig {
                         type_: CompressionAlgorithm::Lz4,
+                        ..Default::default()
                     },
                     deduplication: Some(Deduplication {
                         bounds: Bounds {
@@ -780,6 +788,7 @@ mod tests {
                     linger: Some(Duration::from_millis(1)),
                     compression: Some(Compression::Gzip),
                     batch_size: Some(ByteSize::mb(44)),
+                    partitioner: Some(PartitionerKind::Sticky),
                 }),
                 consumer: Some(ConsumerParameters {
                     partition: ConsumerPartitionConfig::One(10),
@@ -991,6 +1000,7 @@ mod tests {
                     linger: None,
                     compression: None,
                     batch_size: Some(ByteSize::b(1600)),
+                    partitioner: None,
                 }),
                 consumer: Some(ConsumerParameters {
                     max_bytes: Some(ByteSize::b(1400)),
@@ -1215,6 +1225,7 @@ mod tests {
                     linger: None,
                     compression: None,
                     batch_size: Some(ByteSize::b(1600)),
+                    partitioner: None,
                 }),
                 consumer: Some(ConsumerParameters {
                     max_bytes: Some(ByteSize::b(1400)),
@@ -0,0 +1,25 @@
+use crate::k8_types::{Crd, CrdNames, GROUP, V1, Spec, Status, DefaultHeader};
+
+use super::DictionarySpec;
+use super::DictionaryStatus;
+
+const DICTIONARY_API: Crd = Crd {
+    group: GROUP,
+    version: V1,
+    names: CrdNames {
+        kind: "Dictionary",
+        plural: "dictionaries",
+        singular: "dictionary",
+    },
+};
+
+impl Spec for DictionarySpec {
+    type Header = DefaultHeader;
+    type Status = DictionaryStatus;
+
+    fn metadata() -> &'static Crd {
+        &DICTIONARY_API
+    }
+}
+
+impl Status for DictionaryStatus {}
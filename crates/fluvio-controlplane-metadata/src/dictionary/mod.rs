@@ -0,0 +1,68 @@
+mod spec;
+mod status;
+
+pub use self::spec::*;
+pub use self::status::*;
+
+#[cfg(feature = "k8")]
+mod k8;
+
+mod metadata {
+
+    use crate::core::{Spec, Status, Removable, Creatable};
+    use crate::extended::{SpecExt, ObjectType};
+
+    use super::*;
+
+    impl Spec for DictionarySpec {
+        const LABEL: &'static str = "Dictionary";
+        type IndexKey = String;
+        type Status = DictionaryStatus;
+        type Owner = Self;
+    }
+
+    impl SpecExt for DictionarySpec {
+        const OBJECT_TYPE: ObjectType = ObjectType::Dictionary;
+    }
+
+    impl Removable for DictionarySpec {
+        type DeleteKey = String;
+    }
+
+    impl Creatable for DictionarySpec {}
+
+    impl Status for DictionaryStatus {}
+
+    #[cfg(feature = "k8")]
+    mod extended {
+
+        use crate::store::k8::K8ExtendedSpec;
+        use crate::store::k8::K8ConvertError;
+        use crate::store::k8::K8MetaItem;
+        use crate::store::MetadataStoreObject;
+        use crate::k8_types::K8Obj;
+        use crate::store::k8::default_convert_from_k8;
+
+        use super::DictionarySpec;
+
+        impl K8ExtendedSpec for DictionarySpec {
+            type K8Spec = Self;
+
+            fn convert_from_k8(
+                k8_obj: K8Obj<Self::K8Spec>,
+                multi_namespace_context: bool,
+            ) -> Result<MetadataStoreObject<Self, K8MetaItem>, K8ConvertError<Self::K8Spec>>
+            {
+                default_convert_from_k8(k8_obj, multi_namespace_context)
+            }
+
+            fn convert_status_from_k8(status: Self::Status) -> Self::Status {
+                status
+            }
+
+            fn into_k8(self) -> Self::K8Spec {
+                self
+            }
+        }
+    }
+}
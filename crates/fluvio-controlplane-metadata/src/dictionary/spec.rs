@@ -0,0 +1,39 @@
+//!
+//! # Dictionary Spec
+//!
+//! Trained zstd dictionary referenced by topics using dictionary compression.
+//!
+
+use fluvio_protocol::{ByteBuf, Encoder, Decoder};
+
+#[derive(Default, Encoder, Decoder, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct DictionarySpec {
+    /// zstd dictionary id, embedded in every frame compressed with this dictionary
+    pub dictionary_id: u32,
+    /// raw dictionary content as produced by `zstd --train`
+    #[cfg_attr(feature = "use_serde", serde(with = "crate::smartmodule::base64"))]
+    pub data: ByteBuf,
+}
+
+impl std::fmt::Debug for DictionarySpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DictionarySpec")
+            .field("dictionary_id", &self.dictionary_id)
+            .field("size", &self.data.len())
+            .finish()
+    }
+}
+
+impl DictionarySpec {
+    pub fn new(dictionary_id: u32, data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            dictionary_id,
+            data: ByteBuf::from(data.into()),
+        }
+    }
+}
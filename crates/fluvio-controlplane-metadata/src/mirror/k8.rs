@@ -44,6 +44,7 @@ mod test_v1_spec {
             cluster.spec.mirror_type,
             MirrorType::Remote(Remote {
                 id: "offshore-edge-1".to_owned(),
+                link: None,
             })
         );
     }
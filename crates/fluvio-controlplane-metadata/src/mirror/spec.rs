@@ -2,6 +2,8 @@ use std::fmt;
 
 use fluvio_protocol::{Encoder, Decoder};
 
+use crate::topic::CompressionAlgorithm;
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Encoder, Decoder)]
 #[cfg_attr(
     feature = "use_serde",
@@ -63,6 +65,77 @@ impl Default for MirrorType {
 )]
 pub struct Remote {
     pub id: String,
+    /// limits applied to the connection from this remote
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub link: Option<MirrorLinkConfig>,
+}
+
+/// Settings for records sent over a mirror connection.
+/// These are owned by home and pushed to remote, so metered edge links can be managed centrally.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct MirrorLinkConfig {
+    /// maximum bytes per second each SPU sends over the link, shared by all partitions
+    /// of the mirror it leads. Unlimited if not set
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_bytes_per_sec: Option<u64>,
+    /// compression applied to sync payloads, independent of topic compression
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub compression: Option<CompressionAlgorithm>,
+}
+
+impl MirrorLinkConfig {
+    /// compression used on the wire, `Any` is treated as no compression
+    pub fn wire_compression(&self) -> Option<&CompressionAlgorithm> {
+        self.compression
+            .as_ref()
+            .filter(|c| !matches!(c, CompressionAlgorithm::None | CompressionAlgorithm::Any))
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.max_bytes_per_sec.is_none() && self.wire_compression().is_none()
+    }
+}
+
+impl fmt::Display for MirrorLinkConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rate = self
+            .max_bytes_per_sec
+            .map(|rate| format!("{rate}B/s"))
+            .unwrap_or_else(|| "unlimited".to_string());
+        match self.wire_compression() {
+            Some(compression) => write!(f, "{rate} {compression:?}"),
+            None => write!(f, "{rate}"),
+        }
+    }
+}
+
+/// Update of existing mirror
+#[derive(Debug, Encoder, Decoder, Clone)]
+pub enum UpdateMirrorAction {
+    /// replace link settings of a remote
+    #[fluvio(tag = 0)]
+    SetLink(Option<MirrorLinkConfig>),
+}
+
+impl Default for UpdateMirrorAction {
+    fn default() -> Self {
+        Self::SetLink(None)
+    }
 }
 
 #[derive(Debug, Clone, Default, Eq, PartialEq, Encoder, Decoder)]
@@ -13,6 +13,20 @@ pub struct MirrorStatus {
     #[cfg_attr(feature = "use_serde", serde(default))]
     pub pairing_spu: MirrorPairStatus,
     pub connection_stat: ConnectionStat,
+    /// replication from this cluster to mirror, reported by bidirectional mirrors
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub outbound: Option<MirrorDirectionStatus>,
+    /// replication from mirror to this cluster, reported by bidirectional mirrors
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub inbound: Option<MirrorDirectionStatus>,
 }
 
 impl MirrorStatus {
@@ -25,27 +39,78 @@ impl MirrorStatus {
             pairing_sc: pairing,
             pairing_spu: MirrorPairStatus::Waiting,
             connection_status,
-            connection_stat: ConnectionStat { last_seen },
+            connection_stat: ConnectionStat::new(last_seen),
+            ..Default::default()
         }
     }
 
     pub fn new_by_spu(pairing_spu: MirrorPairStatus, last_seen: u64) -> Self {
         Self {
             pairing_spu,
-            connection_stat: ConnectionStat { last_seen },
+            connection_stat: ConnectionStat::new(last_seen),
             ..Default::default()
         }
     }
 
+    /// status reported by spu with traffic of mirror link
+    pub fn new_by_spu_link(
+        pairing_spu: MirrorPairStatus,
+        last_seen: u64,
+        link: Option<MirrorLinkStat>,
+    ) -> Self {
+        let mut status = Self::new_by_spu(pairing_spu, last_seen);
+        status.connection_stat.link = link;
+        status
+    }
+
     pub fn merge_from_sc(&mut self, other: Self) {
         self.pairing_sc = other.pairing_sc;
         self.connection_status = other.connection_status;
-        self.connection_stat = other.connection_stat;
+        self.connection_stat.last_seen = other.connection_stat.last_seen;
+    }
+
+    pub fn new_by_spu_direction(
+        direction: MirrorDirection,
+        pairing: MirrorPairStatus,
+        last_seen: u64,
+    ) -> Self {
+        let mut status = Self::new_by_spu(pairing.clone(), last_seen);
+        let direction_status = Some(MirrorDirectionStatus { pairing, last_seen });
+        match direction {
+            MirrorDirection::Outbound => status.outbound = direction_status,
+            MirrorDirection::Inbound => status.inbound = direction_status,
+        }
+        status
     }
 
     pub fn merge_from_spu(&mut self, other: Self) {
         self.pairing_spu = other.pairing_spu;
-        self.connection_stat = other.connection_stat;
+        self.connection_stat.last_seen = other.connection_stat.last_seen;
+        if other.connection_stat.link.is_some() {
+            self.connection_stat.link = other.connection_stat.link;
+        }
+        if other.outbound.is_some() {
+            self.outbound = other.outbound;
+        }
+        if other.inbound.is_some() {
+            self.inbound = other.inbound;
+        }
+    }
+
+    /// summary of each replication direction, empty if mirror is not bidirectional
+    pub fn directions(&self) -> String {
+        match (&self.outbound, &self.inbound) {
+            (None, None) => "-".to_string(),
+            (outbound, inbound) => {
+                let display = |status: &Option<MirrorDirectionStatus>| {
+                    status
+                        .as_ref()
+                        .map(|s| s.pairing.to_string())
+                        .unwrap_or_else(|| "-".to_string())
+                };
+                format!("out:{} in:{}", display(outbound), display(inbound))
+            }
+        }
     }
 
     pub fn pair_errors(self) -> String {
@@ -78,6 +143,24 @@ pub enum MirrorPairStatus {
     DetailFailure(String),
 }
 
+/// Direction of replication relative to the reporting cluster
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MirrorDirection {
+    Outbound,
+    Inbound,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct MirrorDirectionStatus {
+    pub pairing: MirrorPairStatus,
+    pub last_seen: u64, // number of milliseconds since last seen
+}
+
 #[derive(Encoder, Decoder, Debug, Clone, Eq, PartialEq, Default)]
 #[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConnectionStatus {
@@ -96,6 +179,64 @@ pub enum ConnectionStatus {
 )]
 pub struct ConnectionStat {
     pub last_seen: u64, // number of milliseconds since last seen
+    /// traffic sent over mirror link, reported by spu
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub link: Option<MirrorLinkStat>,
+}
+
+impl ConnectionStat {
+    pub fn new(last_seen: u64) -> Self {
+        Self {
+            last_seen,
+            link: None,
+        }
+    }
+}
+
+/// Counters of records sent over mirror link since spu started
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct MirrorLinkStat {
+    /// bytes written to the connection, after link compression
+    pub bytes_sent: u64,
+    /// bytes of records before link compression
+    pub bytes_uncompressed: u64,
+    /// time spent waiting for rate limit
+    pub throttled_ms: u64,
+    /// records in local log not yet acknowledged by mirror
+    pub pending_records: u64,
+}
+
+impl MirrorLinkStat {
+    /// ratio of bytes sent to bytes read, 1.0 if nothing was sent
+    pub fn compression_ratio(&self) -> f64 {
+        if self.bytes_uncompressed == 0 {
+            1.0
+        } else {
+            self.bytes_sent as f64 / self.bytes_uncompressed as f64
+        }
+    }
+}
+
+impl std::fmt::Display for MirrorLinkStat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "sent:{}B ratio:{:.2} throttled:{}ms pending:{}",
+            self.bytes_sent,
+            self.compression_ratio(),
+            self.throttled_ms,
+            self.pending_records
+        )
+    }
 }
 
 impl MirrorStatus {
@@ -162,9 +303,8 @@ mod test {
             pairing_sc: MirrorPairStatus::Successful,
             pairing_spu: MirrorPairStatus::Waiting,
             connection_status: ConnectionStatus::Online,
-            connection_stat: ConnectionStat {
-                last_seen: 1713902927812,
-            },
+            connection_stat: ConnectionStat::new(1713902927812),
+            ..Default::default()
         };
 
         let since = Duration::from_millis(1713902932152);
@@ -175,9 +315,58 @@ mod test {
             pairing_sc: MirrorPairStatus::Successful,
             pairing_spu: MirrorPairStatus::Waiting,
             connection_status: ConnectionStatus::Online,
-            connection_stat: ConnectionStat { last_seen: 0 },
+            connection_stat: ConnectionStat::new(0),
+            ..Default::default()
         };
         let last_seen = default_status.last_seen(since);
         assert_eq!(last_seen, "-");
     }
+
+    #[test]
+    fn test_merge_direction_from_spu() {
+        let mut status = MirrorStatus::default();
+        assert_eq!(status.directions(), "-");
+
+        status.merge_from_spu(MirrorStatus::new_by_spu_direction(
+            MirrorDirection::Outbound,
+            MirrorPairStatus::Successful,
+            10,
+        ));
+        assert_eq!(status.directions(), "out:Connected in:-");
+
+        status.merge_from_spu(MirrorStatus::new_by_spu_direction(
+            MirrorDirection::Inbound,
+            MirrorPairStatus::Failed,
+            20,
+        ));
+        assert_eq!(status.directions(), "out:Connected in:Failed");
+        assert_eq!(status.connection_stat.last_seen, 20);
+        assert_eq!(status.outbound.expect("outbound").last_seen, 10);
+    }
+
+    #[test]
+    fn test_merge_link_from_spu() {
+        let mut status = MirrorStatus::default();
+        let link = MirrorLinkStat {
+            bytes_sent: 100,
+            bytes_uncompressed: 400,
+            throttled_ms: 5,
+            pending_records: 2,
+        };
+
+        status.merge_from_spu(MirrorStatus::new_by_spu_link(
+            MirrorPairStatus::Successful,
+            10,
+            Some(link.clone()),
+        ));
+        // plain status update keeps last reported link traffic
+        status.merge_from_spu(MirrorStatus::new_by_spu(MirrorPairStatus::Successful, 20));
+
+        assert_eq!(status.connection_stat.last_seen, 20);
+        assert_eq!(status.connection_stat.link, Some(link));
+        assert_eq!(
+            status.connection_stat.link.expect("link").to_string(),
+            "sent:100B ratio:0.25 throttled:5ms pending:2"
+        );
+    }
 }
@@ -3,7 +3,9 @@
 use fluvio_types::SpuId;
 use fluvio_protocol::{link::ErrorCode, Decoder, Encoder};
 
-use crate::topic::{CleanupPolicy, CompressionAlgorithm, Deduplication, TopicSpec, TopicStorageConfig};
+use crate::topic::{
+    CleanupPolicy, CompressionAlgorithm, Deduplication, TopicSpec, TopicStorageConfig, Transform,
+};
 
 /// Spec for Partition
 /// Each partition has replicas spread among SPU
@@ -33,6 +35,37 @@ pub struct PartitionSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 14)]
     pub mirror: Option<PartitionMirrorConfig>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub compression_level: Option<i32>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub min_in_sync_replicas: Option<u16>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 20)]
+    pub unclean_leader_election: bool,
+    /// name of zstd dictionary used to compress records
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub compression_dictionary: Option<String>,
+    /// incremented on every leader change
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 20)]
+    pub leader_epoch: u32,
+    /// log end offset of leader when it was elected, -1 if unknown.
+    /// followers truncate records beyond it
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 20)]
+    pub leader_leo: i64,
 }
 
 impl PartitionSpec {
@@ -59,11 +92,29 @@ impl PartitionSpec {
             cleanup_policy: topic.get_clean_policy().cloned(),
             storage: topic.get_storage().cloned(),
             compression_type: topic.get_compression_type().clone(),
+            compression_level: topic.get_compression_level(),
+            min_in_sync_replicas: topic.get_min_in_sync_replicas(),
+            unclean_leader_election: topic.is_unclean_leader_election(),
+            compression_dictionary: topic.get_compression_dictionary().cloned(),
             deduplication: topic.get_deduplication().cloned(),
             system: topic.is_system(),
+            leader_epoch: 0,
+            leader_leo: 0,
         }
     }
 
+    /// switch leader, starting new leader epoch
+    pub fn change_leader(&mut self, leader: SpuId, leader_leo: i64) {
+        self.leader = leader;
+        self.leader_epoch += 1;
+        self.leader_leo = leader_leo;
+    }
+
+    /// preferred leader is first replica in replica map
+    pub fn preferred_leader(&self) -> Option<SpuId> {
+        self.replicas.first().copied()
+    }
+
     pub fn has_spu(&self, spu: &SpuId) -> bool {
         self.replicas.contains(spu)
     }
@@ -79,6 +130,9 @@ impl PartitionSpec {
     pub fn mirror_string(&self) -> String {
         if let Some(mirror) = &self.mirror {
             let external = mirror.external_cluster();
+            if mirror.is_bidirectional() {
+                return format!("{external}(bidirectional)");
+            }
             match mirror {
                 PartitionMirrorConfig::Remote(remote) => {
                     if remote.target {
@@ -163,6 +217,22 @@ impl PartitionMirrorConfig {
         }
     }
 
+    /// both clusters accept producers and replicate to each other
+    pub fn is_bidirectional(&self) -> bool {
+        match self {
+            Self::Remote(r) => r.bidirectional,
+            Self::Home(h) => h.bidirectional,
+        }
+    }
+
+    /// smartmodules applied to records sent over this mirror link
+    pub fn transforms(&self) -> &[Transform] {
+        match self {
+            Self::Remote(r) => &r.transforms,
+            Self::Home(h) => &h.transforms,
+        }
+    }
+
     #[deprecated(since = "0.29.1")]
     pub fn is_home_mirror(&self) -> bool {
         matches!(self, Self::Home(_))
@@ -170,6 +240,9 @@ impl PartitionMirrorConfig {
 
     /// check whether this mirror should accept traffic
     pub fn accept_traffic(&self) -> Option<ErrorCode> {
+        if self.is_bidirectional() {
+            return None;
+        }
         match self {
             Self::Remote(r) => {
                 if r.target {
@@ -215,6 +288,20 @@ pub struct HomePartitionConfig {
     )]
     #[fluvio(min_version = 18)]
     pub source: bool,
+    // if this is set, home and remote replicate to each other
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "crate::is_false")
+    )]
+    #[fluvio(min_version = 20)]
+    pub bidirectional: bool,
+    // smartmodules applied to records before they are sent to mirror
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    #[fluvio(min_version = 20)]
+    pub transforms: Vec<Transform>,
 }
 
 impl std::fmt::Display for HomePartitionConfig {
@@ -242,6 +329,19 @@ pub struct RemotePartitionConfig {
     )]
     #[fluvio(min_version = 18)]
     pub target: bool,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "crate::is_false")
+    )]
+    #[fluvio(min_version = 20)]
+    pub bidirectional: bool,
+    // smartmodules applied to records before they are sent to mirror
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    #[fluvio(min_version = 20)]
+    pub transforms: Vec<Transform>,
 }
 
 impl std::fmt::Display for RemotePartitionConfig {
@@ -41,6 +41,29 @@ pub struct PartitionStatus {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 16)]
     pub base_offset: i64,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 20)]
+    pub segments: u32,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub unclean_election: Option<UncleanElection>,
+}
+
+/// Last leader elected from out of sync replica.
+/// Records which were not replicated to the new leader are truncated
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct UncleanElection {
+    pub previous_leader: SpuId,
+    pub leader: SpuId,
+    pub lost_records: u64,
 }
 
 impl Default for PartitionStatus {
@@ -53,6 +76,8 @@ impl Default for PartitionStatus {
             replicas: Default::default(),
             is_being_deleted: Default::default(),
             base_offset: Default::default(),
+            segments: Default::default(),
+            unclean_election: Default::default(),
         }
     }
 }
@@ -142,6 +167,24 @@ impl PartitionStatus {
         self.replicas.iter().map(|lrs| lrs.spu).collect()
     }
 
+    /// replicas in the in sync replica set, leader first
+    pub fn in_sync_replicas(&self) -> Vec<SpuId> {
+        std::iter::once(&self.leader)
+            .chain(self.replicas.iter())
+            .filter(|replica| replica.in_sync)
+            .map(|replica| replica.spu)
+            .collect()
+    }
+
+    /// log end offset reported by replica, -1 if unknown
+    pub fn replica_leo(&self, spu: SpuId) -> Offset {
+        std::iter::once(&self.leader)
+            .chain(self.replicas.iter())
+            .find(|replica| replica.spu == spu)
+            .map(|replica| replica.leo)
+            .unwrap_or(-1)
+    }
+
     pub fn offline_replicas(&self) -> Vec<i32> {
         vec![]
     }
@@ -183,6 +226,10 @@ pub struct ReplicaStatus {
     pub spu: SpuId,
     pub hw: i64,
     pub leo: i64,
+    /// replica is member of in sync replica set
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 20)]
+    pub in_sync: bool,
 }
 
 impl fmt::Display for ReplicaStatus {
@@ -197,13 +244,19 @@ impl Default for ReplicaStatus {
             spu: -1,
             hw: -1,
             leo: -1,
+            in_sync: false,
         }
     }
 }
 
 impl ReplicaStatus {
     pub fn new(spu: SpuId, hw: Offset, leo: Offset) -> Self {
-        Self { spu, hw, leo }
+        Self {
+            spu,
+            hw,
+            leo,
+            in_sync: false,
+        }
     }
 
     /// compute lag score respect to leader
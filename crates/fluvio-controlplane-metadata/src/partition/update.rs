@@ -0,0 +1,9 @@
+use fluvio_protocol::{Decoder, Encoder};
+
+#[derive(Debug, Default, Encoder, Decoder, Clone, PartialEq, Eq)]
+pub enum UpdatePartitionAction {
+    /// move leadership back to preferred replica if it is in sync
+    #[default]
+    #[fluvio(tag = 0)]
+    ElectPreferredLeader,
+}
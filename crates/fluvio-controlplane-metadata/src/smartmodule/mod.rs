@@ -8,6 +8,9 @@ pub use self::spec::*;
 pub use self::status::*;
 pub use self::package::*;
 
+#[cfg(feature = "use_serde")]
+pub(crate) use self::spec::base64;
+
 #[cfg(feature = "k8")]
 mod k8;
 #[cfg(feature = "k8")]
@@ -167,7 +167,7 @@ pub enum SmartModuleWasmFormat {
 }
 
 #[cfg(feature = "use_serde")]
-mod base64 {
+pub(crate) mod base64 {
     use std::ops::Deref;
 
     use serde::{Serialize, Deserialize};
@@ -1,8 +1,10 @@
 mod spec;
 mod status;
+mod update;
 
 pub use self::spec::*;
 pub use self::status::*;
+pub use self::update::*;
 pub use custom_metadata::CustomSpuKey;
 
 #[cfg(feature = "k8")]
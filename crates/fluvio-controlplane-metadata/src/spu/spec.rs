@@ -38,6 +38,14 @@ pub struct SpuSpec {
     #[fluvio(min_version = 1)]
     #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
     pub public_endpoint_local: Option<Endpoint>,
+
+    /// SPU is being drained, leadership moves off and no new replicas are assigned to it
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub drain: Option<SpuDrain>,
 }
 
 impl fmt::Display for SpuSpec {
@@ -102,6 +110,18 @@ impl SpuSpec {
         }
     }
 
+    /// SPU is being drained
+    pub fn is_draining(&self) -> bool {
+        self.drain.is_some()
+    }
+
+    /// replicas on this SPU are moved to other SPUs
+    pub fn is_migrating_replicas(&self) -> bool {
+        self.drain
+            .as_ref()
+            .is_some_and(|drain| drain.migrate_replicas)
+    }
+
     pub fn private_server_address(&self) -> ServerAddress {
         let private_ep = &self.private_endpoint;
         ServerAddress {
@@ -154,6 +174,7 @@ impl From<CustomSpuSpec> for SpuSpec {
             rack: spec.rack,
             spu_type: SpuType::Custom,
             public_endpoint_local: spec.public_endpoint_local,
+            drain: None,
         }
     }
 }
@@ -173,6 +194,18 @@ impl From<SpuSpec> for CustomSpuSpec {
     }
 }
 
+/// Drain of SPU before decommission or maintenance
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase", default)
+)]
+pub struct SpuDrain {
+    /// move replicas hosted by SPU to other SPUs, otherwise only leadership is moved
+    pub migrate_replicas: bool,
+}
+
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "use_serde",
@@ -13,6 +13,13 @@ use fluvio_protocol::{Encoder, Decoder};
 #[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpuStatus {
     pub resolution: SpuStatusResolution,
+    /// progress of drain, set while SPU is being drained
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub drain: Option<SpuDrainStatus>,
 }
 
 impl fmt::Display for SpuStatus {
@@ -26,6 +33,7 @@ impl SpuStatus {
     pub fn offline() -> Self {
         Self {
             resolution: SpuStatusResolution::Offline,
+            ..Default::default()
         }
     }
     /// Resolution to string label
@@ -61,6 +69,36 @@ impl SpuStatus {
     }
 }
 
+/// partitions remaining on draining SPU
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct SpuDrainStatus {
+    /// partitions still led by SPU
+    pub leaders: u32,
+    /// replicas still hosted by SPU, only moved if replicas are migrated
+    pub replicas: u32,
+    /// nothing remains to be moved off SPU
+    pub complete: bool,
+}
+
+impl fmt::Display for SpuDrainStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.complete {
+            write!(f, "drained")
+        } else {
+            write!(
+                f,
+                "draining: {} leaders, {} replicas",
+                self.leaders, self.replicas
+            )
+        }
+    }
+}
+
 #[derive(Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SpuStatusResolution {
@@ -0,0 +1,19 @@
+use fluvio_protocol::{Decoder, Encoder};
+
+use super::SpuDrain;
+
+#[derive(Debug, Encoder, Decoder, Clone, PartialEq, Eq)]
+pub enum UpdateSpuAction {
+    /// start draining SPU
+    #[fluvio(tag = 0)]
+    Drain(SpuDrain),
+    /// stop draining SPU, it becomes eligible for leadership and new replicas again
+    #[fluvio(tag = 1)]
+    CancelDrain,
+}
+
+impl Default for UpdateSpuAction {
+    fn default() -> Self {
+        Self::Drain(SpuDrain::default())
+    }
+}
@@ -5,6 +5,7 @@ use fluvio_types::{ReplicationFactor, TopicName, PartitionCount, IgnoreRackAssig
 
 use crate::topic::{
     ReplicaSpec, TopicReplicaParam, SegmentBasedPolicy, CleanupPolicy, TopicStorageConfig,
+    EncryptionConfig,
 };
 
 use super::{TopicSpec, PartitionMap, CompressionAlgorithm, deduplication::Deduplication};
@@ -46,6 +47,13 @@ pub struct TopicConfig {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub deduplication: Option<Deduplication>,
+
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub encryption: Option<EncryptionConfig>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -97,6 +105,18 @@ pub struct PartitionConfig {
         serde(skip_serializing_if = "Option::is_none", default)
     )]
     pub maps: Option<Vec<PartitionMap>>,
+
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub min_in_sync_replicas: Option<u16>,
+
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub unclean_leader_election: Option<bool>,
 }
 
 #[derive(Debug, Default, Builder, Clone, PartialEq, Eq)]
@@ -136,6 +156,22 @@ pub struct RetentionConfig {
 pub struct CompressionConfig {
     #[cfg_attr(feature = "use_serde", serde(rename = "type", default))]
     pub type_: CompressionAlgorithm,
+
+    /// compression level used by producers unless they set their own
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub level: Option<i32>,
+
+    /// name of zstd dictionary
+    #[builder(default)]
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub dictionary: Option<String>,
 }
 
 impl TopicConfig {
@@ -162,6 +198,8 @@ impl Default for PartitionConfig {
             ignore_rack_assignment: Some(DEFAULT_IGNORE_RACK_ASSIGMENT),
             max_size: Default::default(),
             maps: Default::default(),
+            min_in_sync_replicas: Default::default(),
+            unclean_leader_election: Default::default(),
         }
     }
 }
@@ -192,13 +230,20 @@ impl From<TopicConfig> for TopicSpec {
             }));
         };
 
+        topic_spec.set_min_in_sync_replicas(config.partition.min_in_sync_replicas);
+        topic_spec.set_unclean_leader_election(
+            config.partition.unclean_leader_election.unwrap_or_default(),
+        );
         topic_spec.set_compression_type(config.compression.type_);
+        topic_spec.set_compression_level(config.compression.level);
+        topic_spec.set_compression_dictionary(config.compression.dictionary);
         topic_spec.set_deduplication(config.deduplication);
 
-        if segment_size.is_some() || max_partition_size.is_some() {
+        if segment_size.is_some() || max_partition_size.is_some() || config.encryption.is_some() {
             topic_spec.set_storage(TopicStorageConfig {
                 segment_size,
                 max_partition_size,
+                encryption: config.encryption,
             });
         }
 
@@ -233,6 +278,8 @@ partition:
     replicas:
     - 1
     - 2
+  min-in-sync-replicas: 2
+  unclean-leader-election: true
 retention:
   time: 2m
   segment-size: 2.0 KB
@@ -245,6 +292,8 @@ deduplication:
   filter:
     transform:
       uses: fluvio/dedup-bloom-filter@0.1.0
+encryption:
+  key-id: test-key
 "#;
 
         //when
@@ -299,6 +348,33 @@ compression:
         );
     }
 
+    #[cfg(feature = "use_serde")]
+    #[test]
+    fn test_compression_tuning_config_to_spec() {
+        //given
+        let input = r#"meta:
+  name: events
+compression:
+  type: Zstd
+  level: 19
+  dictionary: json-events
+"#;
+
+        //when
+        use std::str::FromStr;
+
+        let spec: TopicSpec = TopicConfig::from_str(input).expect("deserialized").into();
+
+        //then
+        assert_eq!(spec.get_compression_type(), &CompressionAlgorithm::Zstd);
+        assert_eq!(spec.get_compression_level(), Some(19));
+        assert_eq!(
+            spec.get_compression_dictionary().map(|d| d.as_str()),
+            Some("json-events")
+        );
+        assert!(spec.validate_config().is_none());
+    }
+
     #[test]
     fn test_default_config_to_spec() {
         //given
@@ -339,8 +415,13 @@ compression:
         test_spec.set_storage(TopicStorageConfig {
             segment_size: Some(2000),
             max_partition_size: Some(1000),
+            encryption: Some(EncryptionConfig {
+                key_id: "test-key".to_string(),
+            }),
         });
         test_spec.set_deduplication(Some(test_deduplication()));
+        test_spec.set_min_in_sync_replicas(Some(2));
+        test_spec.set_unclean_leader_election(true);
 
         assert_eq!(spec, test_spec);
     }
@@ -361,6 +442,8 @@ compression:
                     replicas: vec![1, 2],
                     ..Default::default()
                 }]),
+                min_in_sync_replicas: Some(2),
+                unclean_leader_election: Some(true),
             },
             retention: RetentionConfig {
                 time: Some(Duration::from_secs(120)),
@@ -368,8 +451,12 @@ compression:
             },
             compression: CompressionConfig {
                 type_: CompressionAlgorithm::Lz4,
+                ..Default::default()
             },
             deduplication: Some(test_deduplication()),
+            encryption: Some(EncryptionConfig {
+                key_id: "test-key".to_string(),
+            }),
         }
     }
 
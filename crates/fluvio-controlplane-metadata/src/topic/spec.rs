@@ -13,7 +13,7 @@ use fluvio_protocol::{Encoder, Decoder};
 
 use crate::partition::{HomePartitionConfig, PartitionMirrorConfig, RemotePartitionConfig};
 
-use super::deduplication::Deduplication;
+use super::deduplication::{Deduplication, Transform};
 
 #[derive(Debug, Clone, PartialEq, Default, Encoder, Decoder)]
 #[cfg_attr(
@@ -39,6 +39,27 @@ pub struct TopicSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 13)]
     system: bool,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    compression_level: Option<i32>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    compression_dictionary: Option<String>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    min_in_sync_replicas: Option<u16>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 20)]
+    unclean_leader_election: bool,
 }
 
 impl From<ReplicaSpec> for TopicSpec {
@@ -119,6 +140,43 @@ impl TopicSpec {
         &self.compression_type
     }
 
+    /// compression level used by producers unless they set their own
+    pub fn get_compression_level(&self) -> Option<i32> {
+        self.compression_level
+    }
+
+    pub fn set_compression_level(&mut self, level: Option<i32>) {
+        self.compression_level = level;
+    }
+
+    /// name of zstd dictionary used to compress records
+    pub fn get_compression_dictionary(&self) -> Option<&String> {
+        self.compression_dictionary.as_ref()
+    }
+
+    pub fn set_compression_dictionary(&mut self, dictionary: Option<String>) {
+        self.compression_dictionary = dictionary;
+    }
+
+    /// min number of in sync replicas, including leader, required to accept committed writes
+    pub fn get_min_in_sync_replicas(&self) -> Option<u16> {
+        self.min_in_sync_replicas
+    }
+
+    pub fn set_min_in_sync_replicas(&mut self, min_in_sync_replicas: Option<u16>) {
+        self.min_in_sync_replicas = min_in_sync_replicas;
+    }
+
+    /// allow out of sync replica to become leader when no in sync replica is available.
+    /// Records not replicated to new leader are lost
+    pub fn is_unclean_leader_election(&self) -> bool {
+        self.unclean_leader_election
+    }
+
+    pub fn set_unclean_leader_election(&mut self, unclean_leader_election: bool) {
+        self.unclean_leader_election = unclean_leader_election;
+    }
+
     pub fn get_storage(&self) -> Option<&TopicStorageConfig> {
         self.storage.as_ref()
     }
@@ -187,6 +245,58 @@ impl TopicSpec {
                     ));
                 }
             }
+            if let Some(encryption) = &storage.encryption {
+                if encryption.key_id.trim().is_empty() {
+                    return Some("encryption key_id must not be empty".to_string());
+                }
+            }
+        }
+
+        if let Some(level) = self.compression_level {
+            match self.compression_type.level_range() {
+                Some(range) if !range.contains(&level) => {
+                    return Some(format!(
+                        "compression level {level} is not valid for {}, expected {}..={}",
+                        self.compression_type,
+                        range.start(),
+                        range.end()
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    return Some(format!(
+                        "compression level is not supported by {} compression",
+                        self.compression_type
+                    ));
+                }
+            }
+        }
+
+        if let Some(dictionary) = &self.compression_dictionary {
+            if dictionary.trim().is_empty() {
+                return Some("compression dictionary must not be empty".to_string());
+            }
+            if self.compression_type != CompressionAlgorithm::Zstd {
+                return Some("compression dictionary requires zstd compression".to_string());
+            }
+            if self.deduplication.is_some() {
+                return Some(
+                    "compression dictionary can not be used with deduplication".to_string(),
+                );
+            }
+        }
+
+        if let Some(min_in_sync) = self.min_in_sync_replicas {
+            if min_in_sync == 0 {
+                return Some("min_in_sync_replicas must be at least 1".to_string());
+            }
+            if let Some(replication) = self.replicas.replication_factor() {
+                if min_in_sync as ReplicationFactor > replication {
+                    return Some(format!(
+                        "min_in_sync_replicas {min_in_sync} is greater than replication factor {replication}"
+                    ));
+                }
+            }
         }
 
         None
@@ -275,6 +385,7 @@ impl ReplicaSpec {
         match self {
             Self::Computed(_) => "computed",
             Self::Assigned(_) => "assigned",
+            Self::Mirror(mirror) if mirror.is_bidirectional() => "bidirectional",
             Self::Mirror(mirror) => match mirror {
                 MirrorConfig::Remote(remote_config) => {
                     if remote_config.target {
@@ -677,9 +788,34 @@ impl MirrorConfig {
         }
     }
 
+    /// Set replication in both directions
+    pub fn set_bidirectional(&mut self, bidirectional: bool) -> Result<()> {
+        match self {
+            Self::Remote(_) => Err(anyhow!(
+                "remote mirror config cannot be set to bidirectional"
+            )),
+            Self::Home(home) => {
+                home.set_bidirectional(bidirectional);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn is_bidirectional(&self) -> bool {
+        match self {
+            Self::Remote(remote) => remote.bidirectional,
+            Self::Home(home) => home.bidirectional,
+        }
+    }
+
     /// Validate partition map for assigned topics
     pub fn validate(&self) -> anyhow::Result<()> {
-        Ok(())
+        match self {
+            Self::Home(home) if home.bidirectional && home.source => Err(anyhow!(
+                "bidirectional mirror cannot be set to home to remote"
+            )),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -732,7 +868,7 @@ impl HomeMirrorConfig {
                     ..Default::default()
                 })
                 .collect(),
-            source: false,
+            ..Default::default()
         })
     }
 }
@@ -786,7 +922,7 @@ cfg_if::cfg_if! {
             match home {
                 MultiHome::V1(v1) => Ok(HomeMirrorInner {
                     partitions: v1,
-                    source: false,
+                    ..Default::default()
                 }),
                 MultiHome::V2(v2) => Ok(v2),
             }
@@ -809,13 +945,19 @@ pub struct HomeMirrorInner {
     )]
     #[fluvio(min_version = 18)]
     pub source: bool, // source of mirror
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "crate::is_false", default)
+    )]
+    #[fluvio(min_version = 20)]
+    pub bidirectional: bool, // home and remotes replicate to each other
 }
 
 impl From<Vec<HomePartitionConfig>> for HomeMirrorConfig {
     fn from(partitions: Vec<HomePartitionConfig>) -> Self {
         Self(HomeMirrorInner {
             partitions,
-            source: false,
+            ..Default::default()
         })
     }
 }
@@ -862,6 +1004,14 @@ impl HomeMirrorInner {
             partition.source = home_to_remote;
         });
     }
+
+    /// set replication in both directions
+    pub fn set_bidirectional(&mut self, bidirectional: bool) {
+        self.bidirectional = bidirectional;
+        self.partitions.iter_mut().for_each(|partition| {
+            partition.bidirectional = bidirectional;
+        });
+    }
 }
 
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
@@ -887,6 +1037,19 @@ pub struct RemoteMirrorConfig {
     #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "crate::is_false"))]
     #[fluvio(min_version = 18)]
     pub target: bool,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "crate::is_false", default)
+    )]
+    #[fluvio(min_version = 20)]
+    pub bidirectional: bool,
+    // smartmodules applied by remote before records are sent to home
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Vec::is_empty", default)
+    )]
+    #[fluvio(min_version = 20)]
+    pub transforms: Vec<Transform>,
 }
 
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
@@ -925,6 +1088,8 @@ impl RemoteMirrorConfig {
                     home_cluster: self.home_cluster.clone(),
                     home_spu_endpoint: home_spu.endpoint.clone(),
                     target: self.target,
+                    bidirectional: self.bidirectional,
+                    transforms: self.transforms.clone(),
                 })),
                 ..Default::default()
             });
@@ -985,6 +1150,26 @@ impl SegmentBasedPolicy {
 pub struct TopicStorageConfig {
     pub segment_size: Option<u32>,       // segment size
     pub max_partition_size: Option<u64>, // max partition size
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 20)]
+    pub encryption: Option<EncryptionConfig>,
+}
+
+/// Encryption at rest of the partition segments.
+/// Batch records are sealed with a per segment data key wrapped by the master key `key_id`
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    derive(schemars::JsonSchema),
+    serde(rename_all = "kebab-case")
+)]
+pub struct EncryptionConfig {
+    /// name of the master key known to the SPU key provider
+    pub key_id: String,
 }
 
 #[derive(Decoder, Default, Encoder, Debug, Clone, Eq, PartialEq)]
@@ -1009,6 +1194,18 @@ pub enum CompressionAlgorithm {
     Zstd,
 }
 
+impl CompressionAlgorithm {
+    /// Range of compression levels that can be configured on a topic, `None` if levels are not supported.
+    /// zstd fast levels (negative) can only be set on the producer.
+    pub fn level_range(&self) -> Option<std::ops::RangeInclusive<i32>> {
+        match self {
+            Self::Gzip => Some(0..=9),
+            Self::Zstd => Some(1..=22),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error("Invalid compression type in topic")]
 pub struct InvalidCompressionAlgorithm;
@@ -1261,6 +1458,45 @@ mod test {
         assert!(topic_spec_decoded.deduplication.is_none());
     }
 
+    #[test]
+    fn test_validate_compression_tuning() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((1, 1, false).into()).into();
+        topic_spec.set_compression_type(CompressionAlgorithm::Zstd);
+        topic_spec.set_compression_level(Some(19));
+        topic_spec.set_compression_dictionary(Some("events".to_string()));
+        assert!(topic_spec.validate_config().is_none());
+
+        topic_spec.set_compression_level(Some(23));
+        assert!(topic_spec.validate_config().is_some());
+
+        topic_spec.set_compression_level(None);
+        topic_spec.set_compression_type(CompressionAlgorithm::Gzip);
+        assert!(topic_spec.validate_config().is_some());
+
+        topic_spec.set_compression_dictionary(None);
+        topic_spec.set_compression_level(Some(9));
+        assert!(topic_spec.validate_config().is_none());
+
+        topic_spec.set_compression_type(CompressionAlgorithm::Lz4);
+        assert!(topic_spec.validate_config().is_some());
+    }
+
+    #[test]
+    fn test_validate_min_in_sync_replicas() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((1, 3, false).into()).into();
+        topic_spec.set_min_in_sync_replicas(Some(2));
+        assert!(topic_spec.validate_config().is_none());
+
+        topic_spec.set_min_in_sync_replicas(Some(3));
+        assert!(topic_spec.validate_config().is_none());
+
+        topic_spec.set_min_in_sync_replicas(Some(4));
+        assert!(topic_spec.validate_config().is_some());
+
+        topic_spec.set_min_in_sync_replicas(Some(0));
+        assert!(topic_spec.validate_config().is_some());
+    }
+
     #[test]
     fn test_partition_map_str() {
         // Test multiple
@@ -1289,7 +1525,10 @@ mod test {
 #[cfg(test)]
 mod mirror_test {
     use crate::{
-        topic::{PartitionMap, HomeMirrorConfig},
+        topic::{
+            PartitionMap, HomeMirrorConfig, MirrorConfig, RemoteMirrorConfig, SpuMirrorConfig,
+            Transform,
+        },
         partition::{PartitionMirrorConfig, HomePartitionConfig},
     };
 
@@ -1323,4 +1562,50 @@ mod mirror_test {
             .into()
         );
     }
+
+    #[test]
+    fn test_bidirectional_home_mirror() {
+        let mut mirror = MirrorConfig::Home(HomeMirrorConfig::from_simple(
+            "boats",
+            vec!["boat1".to_owned()],
+        ));
+        assert!(!mirror.is_bidirectional());
+
+        mirror.set_bidirectional(true).expect("bidirectional");
+        assert!(mirror.is_bidirectional());
+        assert!(mirror.validate().is_ok());
+
+        let maps = mirror.as_partition_maps();
+        let partition_mirror = maps.maps()[0].mirror.as_ref().expect("mirror");
+        assert!(partition_mirror.is_bidirectional());
+        assert!(partition_mirror.accept_traffic().is_none());
+
+        mirror.set_home_to_remote(true).expect("home to remote");
+        assert!(mirror.validate().is_err());
+
+        let mut remote = MirrorConfig::Remote(Default::default());
+        assert!(remote.set_bidirectional(true).is_err());
+    }
+
+    #[test]
+    fn test_remote_mirror_transforms() {
+        let transform = Transform {
+            uses: "infinyon/jolt@0.1.0".to_owned(),
+            ..Default::default()
+        };
+        let remote = RemoteMirrorConfig {
+            home_cluster: "home".to_owned(),
+            home_spus: vec![SpuMirrorConfig {
+                id: 5001,
+                key: "boats-0".to_owned(),
+                endpoint: "localhost:9010".to_owned(),
+            }],
+            transforms: vec![transform.clone()],
+            ..Default::default()
+        };
+
+        let maps = remote.as_partition_maps();
+        let partition_mirror = maps.maps()[0].mirror.as_ref().expect("mirror");
+        assert_eq!(partition_mirror.transforms(), &[transform]);
+    }
 }
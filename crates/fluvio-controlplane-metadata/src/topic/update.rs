@@ -1,5 +1,7 @@
 use fluvio_protocol::{Decoder, Encoder};
 
+use super::Transform;
+
 #[derive(Debug, Default, Encoder, Decoder, Clone)]
 pub struct AddPartition {
     pub count: u32,
@@ -10,6 +12,9 @@ pub struct AddMirror {
     pub remote_cluster: String,
     // if set, this is mirror home
     pub home_to_mirror: bool,
+    // smartmodules applied to records before they are sent over this mirror
+    #[fluvio(min_version = 20)]
+    pub transforms: Vec<Transform>,
 }
 
 #[derive(Debug, Encoder, Decoder, Clone)]
@@ -25,6 +25,11 @@ pub struct Replica {
     pub storage: Option<TopicStorageConfig>,
     pub compression_type: CompressionAlgorithm,
     pub deduplication: Option<Deduplication>,
+    pub compression_level: Option<i32>,
+    pub min_in_sync_replicas: Option<u16>,
+    pub compression_dictionary: Option<String>,
+    pub leader_epoch: u32,
+    pub leader_leo: i64,
 }
 
 impl Replica {
@@ -69,6 +74,11 @@ where
             storage: spec.storage,
             compression_type: spec.compression_type,
             deduplication: spec.deduplication,
+            compression_level: spec.compression_level,
+            min_in_sync_replicas: spec.min_in_sync_replicas,
+            compression_dictionary: spec.compression_dictionary,
+            leader_epoch: spec.leader_epoch,
+            leader_leo: spec.leader_leo,
         }
     }
 }
@@ -29,6 +29,7 @@ pub struct RegisterSpuRequest {
 
 impl Request for RegisterSpuRequest {
     const API_KEY: u16 = InternalScKey::RegisterSpu as u16;
+    const DEFAULT_API_VERSION: i16 = 1;
     type Response = RegisterSpuResponse;
 }
 
@@ -36,6 +37,9 @@ impl Request for RegisterSpuRequest {
 pub struct RegisterSpuResponse {
     error_code: ErrorCode,
     error_message: Option<String>,
+    /// SC asks for all consumer offsets in the next status report, changes are sent afterwards
+    #[fluvio(min_version = 1)]
+    consumer_offsets_snapshot: bool,
 }
 
 // -----------------------------------
@@ -61,9 +65,19 @@ impl RegisterSpuResponse {
         RegisterSpuResponse {
             error_code: ErrorCode::None,
             error_message: None,
+            consumer_offsets_snapshot: false,
         }
     }
 
+    pub fn with_consumer_offsets_snapshot(mut self) -> Self {
+        self.consumer_offsets_snapshot = true;
+        self
+    }
+
+    pub fn consumer_offsets_snapshot(&self) -> bool {
+        self.consumer_offsets_snapshot
+    }
+
     #[deprecated = "Replace by failed_registration"]
     pub fn failed_registeration() -> Self {
         Self::failed_registration()
@@ -73,6 +87,7 @@ impl RegisterSpuResponse {
         RegisterSpuResponse {
             error_code: ErrorCode::SpuRegisterationFailed,
             error_message: None,
+            consumer_offsets_snapshot: false,
         }
     }
 
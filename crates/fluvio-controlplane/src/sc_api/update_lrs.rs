@@ -6,6 +6,7 @@ use fluvio_protocol::Decoder;
 use fluvio_protocol::Encoder;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_controlplane_metadata::partition::ReplicaStatus;
+use fluvio_types::SpuId;
 
 use super::api::InternalScKey;
 
@@ -44,7 +45,7 @@ impl fmt::Display for UpdateLrsRequest {
 impl Request for UpdateLrsRequest {
     const API_KEY: u16 = InternalScKey::UpdateLrs as u16;
     type Response = UpdateLrsResponse;
-    const DEFAULT_API_VERSION: i16 = 1;
+    const DEFAULT_API_VERSION: i16 = 4;
 }
 
 #[derive(Decoder, Encoder, Debug, Default, Clone)]
@@ -65,6 +66,26 @@ pub struct LrsRequest {
     pub size: i64,
     #[fluvio(min_version = 1)]
     pub base_offset: i64,
+    #[fluvio(min_version = 2)]
+    pub segments: u32,
+    /// offsets committed by consumers, only sent for consumer offsets partition
+    #[fluvio(min_version = 2)]
+    pub consumers: Vec<LrsConsumerOffset>,
+    /// followers currently in sync with leader
+    #[fluvio(min_version = 3)]
+    pub in_sync_replicas: Vec<SpuId>,
+    /// `consumers` only has offsets changed since last report, otherwise it has all offsets
+    #[fluvio(min_version = 4)]
+    pub consumers_delta: bool,
+}
+
+/// Offset committed by consumer for a replica
+#[derive(Decoder, Encoder, Debug, Default, Clone, Eq, PartialEq)]
+pub struct LrsConsumerOffset {
+    pub replica_id: ReplicaKey,
+    pub consumer_id: String,
+    pub offset: i64,
+    pub modified_time: u64,
 }
 
 impl PartialEq for LrsRequest {
@@ -102,6 +123,17 @@ impl LrsRequest {
             replicas,
             size,
             base_offset,
+            ..Default::default()
         }
     }
+
+    pub fn with_segments(mut self, segments: u32) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    pub fn with_in_sync_replicas(mut self, in_sync_replicas: Vec<SpuId>) -> Self {
+        self.in_sync_replicas = in_sync_replicas;
+        self
+    }
 }
@@ -33,6 +33,8 @@ impl fmt::Display for UpdateMirrorStatRequest {
 
 impl Request for UpdateMirrorStatRequest {
     const API_KEY: u16 = InternalScKey::UpdateMirror as u16;
+    // version of mirror status with per direction status
+    const DEFAULT_API_VERSION: i16 = 20;
     type Response = UpdateMirrorResponse;
 }
 
@@ -13,6 +13,7 @@ use super::update_mirror::UpdateMirrorRequest;
 use super::update_spu::UpdateSpuRequest;
 use super::update_replica::UpdateReplicaRequest;
 use super::update_smartmodule::UpdateSmartModuleRequest;
+use super::update_dictionary::UpdateDictionaryRequest;
 
 #[repr(u16)]
 #[derive(Eq, PartialEq, Debug, Encoder, Decoder, Clone, Copy)]
@@ -23,6 +24,7 @@ pub enum InternalSpuApi {
     UpdateSmartModule = 1003,
     // UpdateDerivedStream = 1004,
     UpdateMirror = 1004,
+    UpdateDictionary = 1005,
 }
 
 impl Default for InternalSpuApi {
@@ -41,6 +43,8 @@ pub enum InternalSpuRequest {
     UpdateSmartModuleRequest(RequestMessage<UpdateSmartModuleRequest>),
     #[fluvio(tag = 3)]
     UpdateMirrorRequest(RequestMessage<UpdateMirrorRequest>),
+    #[fluvio(tag = 4)]
+    UpdateDictionaryRequest(RequestMessage<UpdateDictionaryRequest>),
 }
 
 // Added to satisfy Encoder/Decoder traits
@@ -74,6 +78,9 @@ impl ApiMessage for InternalSpuRequest {
             InternalSpuApi::UpdateMirror => {
                 api_decode!(Self, UpdateMirrorRequest, src, header)
             }
+            InternalSpuApi::UpdateDictionary => {
+                api_decode!(Self, UpdateDictionaryRequest, src, header)
+            }
         }
     }
 }
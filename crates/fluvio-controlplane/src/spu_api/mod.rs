@@ -3,3 +3,4 @@ pub mod update_replica;
 pub mod update_smartmodule;
 pub mod update_spu;
 pub mod update_mirror;
+pub mod update_dictionary;
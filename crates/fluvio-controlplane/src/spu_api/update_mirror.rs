@@ -20,6 +20,7 @@ pub type UpdateMirrorRequest = ControlPlaneRequest<Mirror>;
 
 impl Request for UpdateMirrorRequest {
     const API_KEY: u16 = InternalSpuApi::UpdateMirror as u16;
+    const DEFAULT_API_VERSION: i16 = 20; // align with pubic api to carry mirror link config
     type Response = UpdateMirrorResponse;
 }
 
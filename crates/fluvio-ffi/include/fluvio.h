@@ -0,0 +1,92 @@
+/*
+ * C API for the Fluvio client.
+ *
+ * Every fallible function returns a fluvio_status_t. When it is not
+ * FLUVIO_OK, fluvio_last_error_message() describes the failure. Handles are
+ * opaque and must be released with the matching *_free function.
+ */
+#ifndef FLUVIO_H
+#define FLUVIO_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum fluvio_status {
+    FLUVIO_OK = 0,
+    FLUVIO_INVALID_ARGUMENT = 1,
+    FLUVIO_TIMEOUT = 2,
+    FLUVIO_END_OF_STREAM = 3,
+    FLUVIO_CONNECTION = 4,
+    FLUVIO_CONFIG = 5,
+    FLUVIO_NOT_FOUND = 6,
+    FLUVIO_ALREADY_EXISTS = 7,
+    FLUVIO_OFFSET_OUT_OF_RANGE = 8,
+    FLUVIO_PERMISSION_DENIED = 9,
+    FLUVIO_PRODUCER = 10,
+    FLUVIO_SERVER = 11,
+    FLUVIO_PANIC = 12,
+    FLUVIO_UNKNOWN = 13,
+} fluvio_status_t;
+
+typedef enum fluvio_offset_kind {
+    FLUVIO_OFFSET_BEGINNING = 0,
+    FLUVIO_OFFSET_END = 1,
+    FLUVIO_OFFSET_ABSOLUTE = 2,
+} fluvio_offset_kind_t;
+
+typedef struct FluvioClient fluvio_client_t;
+typedef struct FluvioProducer fluvio_producer_t;
+typedef struct FluvioConsumer fluvio_consumer_t;
+typedef struct FluvioRecord fluvio_record_t;
+typedef struct FluvioTopicList fluvio_topic_list_t;
+
+const char *fluvio_ffi_version(void);
+const char *fluvio_last_error_message(void);
+
+/* Client. A NULL profile selects the current profile; timeout_ms 0 selects the default. */
+fluvio_status_t fluvio_connect(const char *profile, uint64_t timeout_ms, fluvio_client_t **out);
+void fluvio_client_free(fluvio_client_t *client);
+
+/* Producer. A NULL key sends a record without key. */
+fluvio_status_t fluvio_producer_create(fluvio_client_t *client, const char *topic,
+                                       fluvio_producer_t **out);
+fluvio_status_t fluvio_producer_send(fluvio_producer_t *producer, const uint8_t *key,
+                                     size_t key_len, const uint8_t *value, size_t value_len);
+fluvio_status_t fluvio_producer_flush(fluvio_producer_t *producer);
+void fluvio_producer_free(fluvio_producer_t *producer);
+
+/* Consumer. A negative partition consumes all partitions; consumer_id may be NULL. */
+fluvio_status_t fluvio_consumer_create(fluvio_client_t *client, const char *topic,
+                                       int32_t partition, fluvio_offset_kind_t offset_kind,
+                                       int64_t offset, const char *consumer_id,
+                                       fluvio_consumer_t **out);
+fluvio_status_t fluvio_consumer_next(fluvio_consumer_t *consumer, uint64_t timeout_ms,
+                                     fluvio_record_t **out);
+fluvio_status_t fluvio_consumer_commit(fluvio_consumer_t *consumer);
+void fluvio_consumer_free(fluvio_consumer_t *consumer);
+
+/* Record. Returned buffers are owned by the record; accessors return 0 or NULL for a NULL record. */
+int64_t fluvio_record_offset(const fluvio_record_t *record);
+uint32_t fluvio_record_partition(const fluvio_record_t *record);
+int64_t fluvio_record_timestamp(const fluvio_record_t *record);
+const uint8_t *fluvio_record_key(const fluvio_record_t *record, size_t *len);
+const uint8_t *fluvio_record_value(const fluvio_record_t *record, size_t *len);
+void fluvio_record_free(fluvio_record_t *record);
+
+/* Admin. Returned names are owned by the list. */
+fluvio_status_t fluvio_topic_create(fluvio_client_t *client, const char *name,
+                                    uint32_t partitions, uint32_t replication);
+fluvio_status_t fluvio_topic_list(fluvio_client_t *client, fluvio_topic_list_t **out);
+size_t fluvio_topic_list_len(const fluvio_topic_list_t *list);
+const char *fluvio_topic_list_get(const fluvio_topic_list_t *list, size_t index);
+void fluvio_topic_list_free(fluvio_topic_list_t *list);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FLUVIO_H */
@@ -0,0 +1,106 @@
+use std::ffi::{CString, c_char};
+use std::ptr;
+
+use fluvio::metadata::topic::TopicSpec;
+
+use crate::client::FluvioClient;
+use crate::error::{FluvioStatus, ffi_call};
+use crate::{free_handle, handle, str_arg, write_out};
+
+/// Opaque handle to a list of topic names
+pub struct FluvioTopicList {
+    names: Vec<CString>,
+}
+
+/// Creates a topic with `partitions` partitions replicated `replication` times.
+///
+/// # Safety
+///
+/// `client` must be a live client handle and `name` a NUL terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_topic_create(
+    client: *mut FluvioClient,
+    name: *const c_char,
+    partitions: u32,
+    replication: u32,
+) -> FluvioStatus {
+    ffi_call(|| {
+        let client = unsafe { handle(client, "client") }?;
+        let name = unsafe { str_arg(name, "name") }?;
+        let spec = TopicSpec::new_computed(partitions, replication, None);
+        client.inner.admin()?.create(name.to_owned(), false, spec)?;
+        Ok(FluvioStatus::Ok)
+    })
+}
+
+/// Lists the names of all topics of the cluster.
+///
+/// # Safety
+///
+/// `client` must be a live client handle and `out` valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_topic_list(
+    client: *mut FluvioClient,
+    out: *mut *mut FluvioTopicList,
+) -> FluvioStatus {
+    ffi_call(|| {
+        let client = unsafe { handle(client, "client") }?;
+        let topics = client.inner.admin()?.all::<TopicSpec>()?;
+        let names = topics
+            .into_iter()
+            .filter_map(|topic| CString::new(topic.name).ok())
+            .collect();
+        unsafe { write_out(out, FluvioTopicList { names }) }?;
+        Ok(FluvioStatus::Ok)
+    })
+}
+
+/// Returns the number of topics in the list, or 0 if `list` is NULL.
+///
+/// # Safety
+///
+/// `list` must be NULL or a live topic list handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_topic_list_len(list: *const FluvioTopicList) -> usize {
+    unsafe { list.as_ref() }.map_or(0, |list| list.names.len())
+}
+
+/// Returns the name at `index`, or NULL if it is out of bounds or `list` is
+/// NULL. The string is owned by the list.
+///
+/// # Safety
+///
+/// `list` must be NULL or a live topic list handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_topic_list_get(
+    list: *const FluvioTopicList,
+    index: usize,
+) -> *const c_char {
+    unsafe { list.as_ref() }
+        .and_then(|list| list.names.get(index))
+        .map_or(ptr::null(), |name| name.as_ptr())
+}
+
+/// Releases a topic list and the strings returned by [`fluvio_topic_list_get`].
+///
+/// # Safety
+///
+/// `list` must be NULL or a handle returned by [`fluvio_topic_list`] that was
+/// not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_topic_list_free(list: *mut FluvioTopicList) {
+    unsafe { free_handle(list) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_null_topic_list() {
+        unsafe {
+            assert_eq!(fluvio_topic_list_len(ptr::null()), 0);
+            assert!(fluvio_topic_list_get(ptr::null(), 0).is_null());
+        }
+    }
+}
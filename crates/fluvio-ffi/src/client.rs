@@ -0,0 +1,51 @@
+use std::ffi::c_char;
+
+use fluvio::blocking::Fluvio;
+
+use crate::error::{FluvioStatus, ffi_call};
+use crate::{free_handle, opt_str_arg, timeout_arg, write_out};
+
+/// Opaque handle to a connected Fluvio client
+pub struct FluvioClient {
+    pub(crate) inner: Fluvio,
+}
+
+/// Connects to the cluster of the given profile, or of the current profile
+/// when `profile` is NULL.
+///
+/// `timeout_ms` bounds every call made through the client and the handles
+/// created from it; 0 selects the library default.
+///
+/// # Safety
+///
+/// `profile` must be NULL or a NUL terminated string and `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_connect(
+    profile: *const c_char,
+    timeout_ms: u64,
+    out: *mut *mut FluvioClient,
+) -> FluvioStatus {
+    ffi_call(|| {
+        let profile = unsafe { opt_str_arg(profile, "profile") }?;
+        let fluvio = match profile {
+            Some(profile) => Fluvio::connect_with_profile(profile)?,
+            None => Fluvio::connect()?,
+        };
+        let timeout = timeout_arg(timeout_ms, fluvio.timeout());
+        let client = FluvioClient {
+            inner: fluvio.with_timeout(timeout),
+        };
+        unsafe { write_out(out, client) }?;
+        Ok(FluvioStatus::Ok)
+    })
+}
+
+/// Releases a client. Producers and consumers created from it stay usable.
+///
+/// # Safety
+///
+/// `client` must be NULL or a handle returned by [`fluvio_connect`] that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_client_free(client: *mut FluvioClient) {
+    unsafe { free_handle(client) }
+}
@@ -0,0 +1,275 @@
+use std::ffi::c_char;
+use std::ptr;
+use std::time::Duration;
+
+use fluvio::Offset;
+use fluvio::blocking::ConsumerIterator;
+use fluvio::consumer::{ConsumerConfigExt, OffsetManagementStrategy, Record};
+
+use crate::client::FluvioClient;
+use crate::error::{FfiError, FluvioStatus, ffi_call};
+use crate::{free_handle, handle, opt_str_arg, str_arg, timeout_arg, write_len, write_out};
+
+/// Opaque handle to a consumer stream
+pub struct FluvioConsumer {
+    inner: ConsumerIterator,
+    timeout: Duration,
+}
+
+/// Opaque handle to a consumed record
+pub struct FluvioRecord {
+    inner: Record,
+}
+
+/// Where a consumer starts reading, passed as `offset_kind` to [`fluvio_consumer_create`]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FluvioOffsetKind {
+    /// `offset` records after the beginning of the partition
+    Beginning = 0,
+    /// `offset` records before the end of the partition
+    End = 1,
+    /// the absolute `offset`
+    Absolute = 2,
+}
+
+impl TryFrom<i32> for FluvioOffsetKind {
+    type Error = FfiError;
+
+    fn try_from(kind: i32) -> Result<Self, Self::Error> {
+        match kind {
+            0 => Ok(Self::Beginning),
+            1 => Ok(Self::End),
+            2 => Ok(Self::Absolute),
+            _ => Err(FfiError::InvalidArgument("offset_kind")),
+        }
+    }
+}
+
+fn start_offset(kind: FluvioOffsetKind, offset: i64) -> anyhow::Result<Offset> {
+    match kind {
+        FluvioOffsetKind::Absolute => Ok(Offset::absolute(offset)?),
+        FluvioOffsetKind::Beginning | FluvioOffsetKind::End => {
+            let relative =
+                u32::try_from(offset).map_err(|_| FfiError::InvalidArgument("offset"))?;
+            if kind == FluvioOffsetKind::Beginning {
+                Ok(Offset::from_beginning(relative))
+            } else {
+                Ok(Offset::from_end(relative))
+            }
+        }
+    }
+}
+
+/// Creates a consumer of `topic`.
+///
+/// A negative `partition` consumes every partition of the topic. When
+/// `consumer_id` is not NULL the consumer resumes from the offset stored
+/// under that name and [`fluvio_consumer_commit`] stores its progress.
+///
+/// # Safety
+///
+/// `client` must be a live client handle, `topic` a NUL terminated string,
+/// `consumer_id` NULL or a NUL terminated string and `out` valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_consumer_create(
+    client: *mut FluvioClient,
+    topic: *const c_char,
+    partition: i32,
+    offset_kind: i32,
+    offset: i64,
+    consumer_id: *const c_char,
+    out: *mut *mut FluvioConsumer,
+) -> FluvioStatus {
+    ffi_call(|| {
+        let client = unsafe { handle(client, "client") }?;
+        let topic = unsafe { str_arg(topic, "topic") }?;
+        let consumer_id = unsafe { opt_str_arg(consumer_id, "consumer_id") }?;
+
+        let mut builder = ConsumerConfigExt::builder();
+        builder
+            .topic(topic)
+            .offset_start(start_offset(offset_kind.try_into()?, offset)?);
+        if let Ok(partition) = u32::try_from(partition) {
+            builder.partition(partition);
+        }
+        if let Some(consumer_id) = consumer_id {
+            builder
+                .offset_consumer(consumer_id)
+                .offset_strategy(OffsetManagementStrategy::Manual);
+        }
+        let consumer = FluvioConsumer {
+            inner: client.inner.consumer_with_config(builder.build()?)?,
+            timeout: client.inner.timeout(),
+        };
+        unsafe { write_out(out, consumer) }?;
+        Ok(FluvioStatus::Ok)
+    })
+}
+
+/// Waits at most `timeout_ms` (0 for the client default) for the next record.
+///
+/// Returns `FLUVIO_TIMEOUT` when no record arrived in time, in which case the
+/// call can be retried, and `FLUVIO_END_OF_STREAM` once the stream is closed.
+///
+/// # Safety
+///
+/// `consumer` must be a live consumer handle and `out` valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_consumer_next(
+    consumer: *mut FluvioConsumer,
+    timeout_ms: u64,
+    out: *mut *mut FluvioRecord,
+) -> FluvioStatus {
+    ffi_call(|| {
+        let consumer = unsafe { handle(consumer, "consumer") }?;
+        let timeout = timeout_arg(timeout_ms, consumer.timeout);
+        match consumer.inner.next_timeout(timeout) {
+            Some(record) => {
+                unsafe { write_out(out, FluvioRecord { inner: record? }) }?;
+                Ok(FluvioStatus::Ok)
+            }
+            None => Ok(FluvioStatus::EndOfStream),
+        }
+    })
+}
+
+/// Commits the offset of the last returned record and waits until the
+/// cluster stored it. Requires a consumer created with a `consumer_id`.
+///
+/// # Safety
+///
+/// `consumer` must be a live consumer handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_consumer_commit(consumer: *mut FluvioConsumer) -> FluvioStatus {
+    ffi_call(|| {
+        let consumer = unsafe { handle(consumer, "consumer") }?;
+        consumer.inner.offset_commit()?;
+        consumer.inner.offset_flush()?;
+        Ok(FluvioStatus::Ok)
+    })
+}
+
+/// Releases a consumer.
+///
+/// # Safety
+///
+/// `consumer` must be NULL or a handle returned by [`fluvio_consumer_create`]
+/// that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_consumer_free(consumer: *mut FluvioConsumer) {
+    unsafe { free_handle(consumer) }
+}
+
+/// Returns the offset of the record within its partition, or 0 if `record` is NULL.
+///
+/// # Safety
+///
+/// `record` must be NULL or a live record handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_record_offset(record: *const FluvioRecord) -> i64 {
+    unsafe { record.as_ref() }.map_or(0, |record| record.inner.offset())
+}
+
+/// Returns the partition the record was read from, or 0 if `record` is NULL.
+///
+/// # Safety
+///
+/// `record` must be NULL or a live record handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_record_partition(record: *const FluvioRecord) -> u32 {
+    unsafe { record.as_ref() }.map_or(0, |record| record.inner.partition())
+}
+
+/// Returns the record timestamp in milliseconds, or -1 if it has none or
+/// `record` is NULL.
+///
+/// # Safety
+///
+/// `record` must be NULL or a live record handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_record_timestamp(record: *const FluvioRecord) -> i64 {
+    unsafe { record.as_ref() }.map_or(-1, |record| record.inner.timestamp())
+}
+
+/// Returns the record key and stores its length in `len` if it is not NULL.
+/// Returns NULL with length 0 if the record has no key or `record` is NULL.
+/// The buffer is owned by the record.
+///
+/// # Safety
+///
+/// `record` must be NULL or a live record handle and `len` NULL or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_record_key(
+    record: *const FluvioRecord,
+    len: *mut usize,
+) -> *const u8 {
+    let key = unsafe { record.as_ref() }.and_then(|record| record.inner.key());
+    unsafe { write_len(len, key.map_or(0, <[u8]>::len)) };
+    key.map_or(ptr::null(), <[u8]>::as_ptr)
+}
+
+/// Returns the record value and stores its length in `len` if it is not NULL.
+/// Returns NULL with length 0 if `record` is NULL. The buffer is owned by the
+/// record.
+///
+/// # Safety
+///
+/// `record` must be NULL or a live record handle and `len` NULL or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_record_value(
+    record: *const FluvioRecord,
+    len: *mut usize,
+) -> *const u8 {
+    let value = unsafe { record.as_ref() }.map(|record| record.inner.value());
+    unsafe { write_len(len, value.map_or(0, <[u8]>::len)) };
+    value.map_or(ptr::null(), <[u8]>::as_ptr)
+}
+
+/// Releases a record and the buffers returned by its accessors.
+///
+/// # Safety
+///
+/// `record` must be NULL or a handle returned by [`fluvio_consumer_next`]
+/// that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_record_free(record: *mut FluvioRecord) {
+    unsafe { free_handle(record) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_offset() {
+        assert_eq!(
+            start_offset(FluvioOffsetKind::Beginning, 0).expect("offset"),
+            Offset::beginning()
+        );
+        assert_eq!(
+            start_offset(FluvioOffsetKind::End, 2).expect("offset"),
+            Offset::from_end(2)
+        );
+        assert!(start_offset(FluvioOffsetKind::End, -1).is_err());
+        assert!(start_offset(FluvioOffsetKind::Absolute, -1).is_err());
+        assert!(FluvioOffsetKind::try_from(3).is_err());
+    }
+
+    #[test]
+    fn test_null_record() {
+        let record = ptr::null();
+        let mut len = 1;
+        unsafe {
+            assert_eq!(fluvio_record_offset(record), 0);
+            assert_eq!(fluvio_record_partition(record), 0);
+            assert_eq!(fluvio_record_timestamp(record), -1);
+            assert!(fluvio_record_key(record, &mut len).is_null());
+            assert_eq!(len, 0);
+            len = 1;
+            assert!(fluvio_record_value(record, &mut len).is_null());
+            assert_eq!(len, 0);
+            assert!(fluvio_record_value(record, ptr::null_mut()).is_null());
+        }
+    }
+}
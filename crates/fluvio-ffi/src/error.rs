@@ -0,0 +1,206 @@
+use std::cell::RefCell;
+use std::ffi::{CString, c_char};
+use std::io::Error as IoError;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+use anyhow::Error;
+
+use fluvio::FluvioError;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::ApiError;
+use fluvio_socket::SocketError;
+
+/// Status returned by every fallible function of the C API.
+///
+/// The numeric values are part of the ABI and must never be reordered.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FluvioStatus {
+    Ok = 0,
+    InvalidArgument = 1,
+    Timeout = 2,
+    EndOfStream = 3,
+    Connection = 4,
+    Config = 5,
+    NotFound = 6,
+    AlreadyExists = 7,
+    OffsetOutOfRange = 8,
+    PermissionDenied = 9,
+    Producer = 10,
+    Server = 11,
+    Panic = 12,
+    Unknown = 13,
+}
+
+/// Errors raised by the FFI layer itself
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum FfiError {
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(&'static str),
+}
+
+impl From<&ErrorCode> for FluvioStatus {
+    fn from(code: &ErrorCode) -> Self {
+        match code {
+            ErrorCode::None => Self::Ok,
+            ErrorCode::RequestTimedOut { .. } => Self::Timeout,
+            ErrorCode::OffsetOutOfRange | ErrorCode::OffsetEvicted { .. } => Self::OffsetOutOfRange,
+            ErrorCode::PermissionDenied => Self::PermissionDenied,
+            ErrorCode::TopicNotFound | ErrorCode::SpuNotFound => Self::NotFound,
+            ErrorCode::TopicAlreadyExists | ErrorCode::SpuAlreadyExists => Self::AlreadyExists,
+            ErrorCode::TopicInvalidConfiguration
+            | ErrorCode::TopicInvalidName
+            | ErrorCode::InvalidCreateRequest
+            | ErrorCode::InvalidDeleteRequest => Self::InvalidArgument,
+            _ => Self::Server,
+        }
+    }
+}
+
+impl From<&FluvioError> for FluvioStatus {
+    fn from(err: &FluvioError) -> Self {
+        match err {
+            FluvioError::Io(_) | FluvioError::Socket(_) => Self::Connection,
+            FluvioError::TopicNotFound(_)
+            | FluvioError::PartitionNotFound(_, _)
+            | FluvioError::SPUNotFound(_) => Self::NotFound,
+            FluvioError::AdminApi(ApiError::Code(code, _)) => code.into(),
+            FluvioError::AdminApi(ApiError::NoResourceFound(_)) => Self::NotFound,
+            FluvioError::ClientConfig(_)
+            | FluvioError::ConsumerConfig(_)
+            | FluvioError::TopicProducerConfigBuilder(_)
+            | FluvioError::MinimumPlatformVersion { .. }
+            | FluvioError::MaximumPlatformVersion { .. } => Self::Config,
+            FluvioError::CrossingOffsets(_, _) | FluvioError::NegativeOffset(_) => {
+                Self::InvalidArgument
+            }
+            FluvioError::Producer(_) => Self::Producer,
+            FluvioError::Timeout(_) => Self::Timeout,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl From<&Error> for FluvioStatus {
+    fn from(err: &Error) -> Self {
+        if let Some(err) = err.downcast_ref::<FfiError>() {
+            return match err {
+                FfiError::InvalidArgument(_) => Self::InvalidArgument,
+            };
+        }
+        if let Some(err) = err.downcast_ref::<FluvioError>() {
+            return err.into();
+        }
+        if let Some(code) = err.downcast_ref::<ErrorCode>() {
+            return code.into();
+        }
+        if let Some(ApiError::Code(code, _)) = err.downcast_ref::<ApiError>() {
+            return code.into();
+        }
+        if err.downcast_ref::<SocketError>().is_some() || err.downcast_ref::<IoError>().is_some() {
+            return Self::Connection;
+        }
+        Self::Unknown
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
+
+/// Runs the body of an exported function, recording any error as the
+/// thread's last error and converting it into a [`FluvioStatus`].
+pub(crate) fn ffi_call(f: impl FnOnce() -> anyhow::Result<FluvioStatus>) -> FluvioStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(status)) => {
+            clear_last_error();
+            status
+        }
+        Ok(Err(err)) => {
+            let status = FluvioStatus::from(&err);
+            set_last_error(format!("{err:#}"));
+            status
+        }
+        Err(_) => {
+            set_last_error("panic inside the Fluvio client".to_owned());
+            FluvioStatus::Panic
+        }
+    }
+}
+
+/// Returns the message of the last error raised on the calling thread, or
+/// NULL if the last call succeeded.
+///
+/// The string is owned by the library and stays valid until the next call
+/// into the library from the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn fluvio_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_status_from_error_code() {
+        assert_eq!(
+            FluvioStatus::from(&ErrorCode::TopicAlreadyExists),
+            FluvioStatus::AlreadyExists
+        );
+        assert_eq!(
+            FluvioStatus::from(&ErrorCode::OffsetOutOfRange),
+            FluvioStatus::OffsetOutOfRange
+        );
+        assert_eq!(
+            FluvioStatus::from(&ErrorCode::StorageError),
+            FluvioStatus::Server
+        );
+    }
+
+    #[test]
+    fn test_status_from_anyhow() {
+        let err: Error = FluvioError::Timeout(Duration::from_secs(1)).into();
+        assert_eq!(FluvioStatus::from(&err), FluvioStatus::Timeout);
+
+        let err: Error =
+            FluvioError::AdminApi(ApiError::Code(ErrorCode::TopicNotFound, None)).into();
+        assert_eq!(FluvioStatus::from(&err), FluvioStatus::NotFound);
+
+        let err: Error = ErrorCode::PermissionDenied.into();
+        assert_eq!(FluvioStatus::from(&err), FluvioStatus::PermissionDenied);
+
+        let err = anyhow::anyhow!("something else");
+        assert_eq!(FluvioStatus::from(&err), FluvioStatus::Unknown);
+    }
+
+    #[test]
+    fn test_ffi_call_records_last_error() {
+        let status = ffi_call(|| Err(FfiError::InvalidArgument("topic").into()));
+        assert_eq!(status, FluvioStatus::InvalidArgument);
+        assert!(!fluvio_last_error_message().is_null());
+
+        let status = ffi_call(|| Ok(FluvioStatus::Ok));
+        assert_eq!(status, FluvioStatus::Ok);
+        assert!(fluvio_last_error_message().is_null());
+
+        let status = ffi_call(|| panic!("boom"));
+        assert_eq!(status, FluvioStatus::Panic);
+    }
+}
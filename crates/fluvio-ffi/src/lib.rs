@@ -0,0 +1,129 @@
+//! C ABI for the Fluvio client.
+//!
+//! The library exposes opaque handles over the [`fluvio::blocking`] facade.
+//! Every fallible function returns a [`FluvioStatus`]; the error message of a
+//! failed call can be read with [`fluvio_last_error_message`]. Handles are
+//! created by `*_create`/`fluvio_connect` functions and must be released with
+//! the matching `*_free` function.
+//!
+//! The C declarations live in `include/fluvio.h`.
+
+mod admin;
+mod client;
+mod consumer;
+mod error;
+mod producer;
+
+pub use admin::FluvioTopicList;
+pub use client::FluvioClient;
+pub use consumer::{FluvioConsumer, FluvioOffsetKind, FluvioRecord};
+pub use error::{FluvioStatus, fluvio_last_error_message};
+pub use producer::FluvioProducer;
+
+use std::ffi::{CStr, c_char};
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::error::FfiError;
+
+const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+/// Returns the version of this library as a static NUL terminated string
+#[unsafe(no_mangle)]
+pub extern "C" fn fluvio_ffi_version() -> *const c_char {
+    VERSION.as_ptr().cast()
+}
+
+/// Borrows the handle behind `ptr`, failing if it is NULL.
+///
+/// # Safety
+///
+/// `ptr` must be NULL or point to a live value of type `T`.
+unsafe fn handle<'a, T>(ptr: *mut T, name: &'static str) -> Result<&'a mut T> {
+    unsafe { ptr.as_mut() }.ok_or_else(|| FfiError::InvalidArgument(name).into())
+}
+
+/// Reads a UTF-8 string argument, failing if it is NULL or not valid UTF-8.
+///
+/// # Safety
+///
+/// `ptr` must be NULL or point to a NUL terminated string.
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &'static str) -> Result<&'a str> {
+    if ptr.is_null() {
+        return Err(FfiError::InvalidArgument(name).into());
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| FfiError::InvalidArgument(name).into())
+}
+
+/// Reads an optional UTF-8 string argument, NULL meaning none.
+///
+/// # Safety
+///
+/// `ptr` must be NULL or point to a NUL terminated string.
+unsafe fn opt_str_arg<'a>(ptr: *const c_char, name: &'static str) -> Result<Option<&'a str>> {
+    if ptr.is_null() {
+        Ok(None)
+    } else {
+        unsafe { str_arg(ptr, name) }.map(Some)
+    }
+}
+
+/// Reads a byte buffer argument, NULL meaning none.
+///
+/// # Safety
+///
+/// `ptr` must be NULL or point to at least `len` readable bytes.
+unsafe fn bytes_arg<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(unsafe { std::slice::from_raw_parts(ptr, len) })
+    }
+}
+
+/// Stores `value` into the out parameter `out`.
+///
+/// # Safety
+///
+/// `out` must be NULL or valid for writes.
+unsafe fn write_out<T>(out: *mut *mut T, value: T) -> Result<()> {
+    if out.is_null() {
+        return Err(FfiError::InvalidArgument("out").into());
+    }
+    unsafe { out.write(Box::into_raw(Box::new(value))) };
+    Ok(())
+}
+
+/// Stores a buffer length into `len`, ignoring NULL.
+///
+/// # Safety
+///
+/// `len` must be NULL or valid for writes.
+unsafe fn write_len(len: *mut usize, value: usize) {
+    if !len.is_null() {
+        unsafe { len.write(value) };
+    }
+}
+
+/// Releases a handle previously returned through [`write_out`].
+///
+/// # Safety
+///
+/// `ptr` must be NULL or a pointer obtained from [`write_out`] that was not freed yet.
+unsafe fn free_handle<T>(ptr: *mut T) {
+    if !ptr.is_null() {
+        drop(unsafe { Box::from_raw(ptr) });
+    }
+}
+
+/// Converts a timeout in milliseconds, 0 meaning the client default
+fn timeout_arg(timeout_ms: u64, default: Duration) -> Duration {
+    if timeout_ms == 0 {
+        default
+    } else {
+        Duration::from_millis(timeout_ms)
+    }
+}
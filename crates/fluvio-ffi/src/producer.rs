@@ -0,0 +1,89 @@
+use std::ffi::c_char;
+
+use fluvio::RecordKey;
+use fluvio::blocking::TopicProducer;
+
+use crate::client::FluvioClient;
+use crate::error::{FluvioStatus, ffi_call};
+use crate::{bytes_arg, free_handle, handle, str_arg, write_out};
+
+/// Opaque handle to a topic producer
+pub struct FluvioProducer {
+    inner: TopicProducer,
+}
+
+/// Creates a producer for `topic` with the default configuration.
+///
+/// # Safety
+///
+/// `client` must be a live client handle, `topic` a NUL terminated string and
+/// `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_producer_create(
+    client: *mut FluvioClient,
+    topic: *const c_char,
+    out: *mut *mut FluvioProducer,
+) -> FluvioStatus {
+    ffi_call(|| {
+        let client = unsafe { handle(client, "client") }?;
+        let topic = unsafe { str_arg(topic, "topic") }?;
+        let producer = FluvioProducer {
+            inner: client.inner.topic_producer(topic)?,
+        };
+        unsafe { write_out(out, producer) }?;
+        Ok(FluvioStatus::Ok)
+    })
+}
+
+/// Enqueues a record. A NULL `key` sends a record without key.
+///
+/// Records are batched; use [`fluvio_producer_flush`] to wait for delivery.
+///
+/// # Safety
+///
+/// `producer` must be a live producer handle, `key` must be NULL or point to
+/// `key_len` bytes and `value` must point to `value_len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_producer_send(
+    producer: *mut FluvioProducer,
+    key: *const u8,
+    key_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> FluvioStatus {
+    ffi_call(|| {
+        let producer = unsafe { handle(producer, "producer") }?;
+        let key = match unsafe { bytes_arg(key, key_len) } {
+            Some(key) => RecordKey::from(key.to_vec()),
+            None => RecordKey::NULL,
+        };
+        let value = unsafe { bytes_arg(value, value_len) }.unwrap_or_default();
+        producer.inner.send(key, value.to_vec())?;
+        Ok(FluvioStatus::Ok)
+    })
+}
+
+/// Sends all pending records and waits for the cluster acknowledgment.
+///
+/// # Safety
+///
+/// `producer` must be a live producer handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_producer_flush(producer: *mut FluvioProducer) -> FluvioStatus {
+    ffi_call(|| {
+        let producer = unsafe { handle(producer, "producer") }?;
+        producer.inner.flush()?;
+        Ok(FluvioStatus::Ok)
+    })
+}
+
+/// Releases a producer. Pending records that were not flushed may be lost.
+///
+/// # Safety
+///
+/// `producer` must be NULL or a handle returned by [`fluvio_producer_create`]
+/// that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fluvio_producer_free(producer: *mut FluvioProducer) {
+    unsafe { free_handle(producer) }
+}
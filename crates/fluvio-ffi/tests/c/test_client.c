@@ -0,0 +1,137 @@
+/*
+ * End to end test of the C API against a running cluster, usually the local
+ * cluster started by `fluvio cluster start --local`.
+ *
+ * Usage: test_client [topic]
+ */
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <time.h>
+#include <unistd.h>
+
+#include "fluvio.h"
+
+#define CHECK(call)                                                                    \
+    do {                                                                               \
+        fluvio_status_t status_ = (call);                                              \
+        if (status_ != FLUVIO_OK) {                                                    \
+            const char *message_ = fluvio_last_error_message();                        \
+            fprintf(stderr, "%s:%d: %s failed with %d: %s\n", __FILE__, __LINE__, #call, \
+                    status_, message_ ? message_ : "(no message)");                    \
+            exit(1);                                                                   \
+        }                                                                              \
+    } while (0)
+
+#define EXPECT(cond)                                                                   \
+    do {                                                                               \
+        if (!(cond)) {                                                                 \
+            fprintf(stderr, "%s:%d: expected %s\n", __FILE__, __LINE__, #cond);        \
+            exit(1);                                                                   \
+        }                                                                              \
+    } while (0)
+
+#define RECORDS 10
+
+static int topic_listed(fluvio_client_t *client, const char *topic) {
+    fluvio_topic_list_t *list = NULL;
+    int found = 0;
+
+    CHECK(fluvio_topic_list(client, &list));
+    for (size_t i = 0; i < fluvio_topic_list_len(list); i++) {
+        if (strcmp(fluvio_topic_list_get(list, i), topic) == 0) {
+            found = 1;
+        }
+    }
+    EXPECT(fluvio_topic_list_get(list, fluvio_topic_list_len(list)) == NULL);
+    fluvio_topic_list_free(list);
+    return found;
+}
+
+static void test_invalid_arguments(void) {
+    EXPECT(fluvio_producer_create(NULL, "topic", NULL) == FLUVIO_INVALID_ARGUMENT);
+    EXPECT(fluvio_last_error_message() != NULL);
+    EXPECT(fluvio_producer_flush(NULL) == FLUVIO_INVALID_ARGUMENT);
+    fluvio_producer_free(NULL);
+    fluvio_consumer_free(NULL);
+    fluvio_record_free(NULL);
+
+    size_t len = 1;
+    EXPECT(fluvio_record_offset(NULL) == 0);
+    EXPECT(fluvio_record_value(NULL, &len) == NULL && len == 0);
+    EXPECT(fluvio_topic_list_len(NULL) == 0);
+    EXPECT(fluvio_topic_list_get(NULL, 0) == NULL);
+}
+
+static void test_produce_consume(fluvio_client_t *client, const char *topic) {
+    fluvio_producer_t *producer = NULL;
+    fluvio_consumer_t *consumer = NULL;
+    fluvio_record_t *record = NULL;
+    char value[64];
+    size_t len = 0;
+
+    /* the topic may not be provisioned right after its creation */
+    fluvio_status_t status = FLUVIO_NOT_FOUND;
+    for (int attempt = 0; attempt < 30 && status == FLUVIO_NOT_FOUND; attempt++) {
+        status = fluvio_producer_create(client, topic, &producer);
+        if (status == FLUVIO_NOT_FOUND) {
+            sleep(1);
+        }
+    }
+    CHECK(status);
+    for (int i = 0; i < RECORDS; i++) {
+        int n = snprintf(value, sizeof(value), "record-%d", i);
+        const uint8_t *key = (i % 2) ? (const uint8_t *)"odd" : NULL;
+        CHECK(fluvio_producer_send(producer, key, key ? 3 : 0, (const uint8_t *)value, n));
+    }
+    CHECK(fluvio_producer_flush(producer));
+    fluvio_producer_free(producer);
+
+    CHECK(fluvio_consumer_create(client, topic, 0, FLUVIO_OFFSET_BEGINNING, 0, "ffi-test",
+                                 &consumer));
+    for (int i = 0; i < RECORDS; i++) {
+        CHECK(fluvio_consumer_next(consumer, 0, &record));
+        snprintf(value, sizeof(value), "record-%d", i);
+        const uint8_t *data = fluvio_record_value(record, &len);
+        EXPECT(len == strlen(value) && memcmp(data, value, len) == 0);
+        EXPECT(fluvio_record_offset(record) == i);
+        EXPECT(fluvio_record_partition(record) == 0);
+        const uint8_t *key = fluvio_record_key(record, &len);
+        EXPECT((i % 2) ? (key != NULL && len == 3) : (key == NULL && len == 0));
+        fluvio_record_free(record);
+    }
+    CHECK(fluvio_consumer_commit(consumer));
+    EXPECT(fluvio_consumer_next(consumer, 500, &record) == FLUVIO_TIMEOUT);
+    fluvio_consumer_free(consumer);
+
+    /* the committed offset is used to resume */
+    CHECK(fluvio_consumer_create(client, topic, 0, FLUVIO_OFFSET_BEGINNING, 0, "ffi-test",
+                                 &consumer));
+    EXPECT(fluvio_consumer_next(consumer, 500, &record) == FLUVIO_TIMEOUT);
+    fluvio_consumer_free(consumer);
+}
+
+int main(int argc, char **argv) {
+    fluvio_client_t *client = NULL;
+    char topic[64];
+
+    if (argc > 1) {
+        snprintf(topic, sizeof(topic), "%s", argv[1]);
+    } else {
+        snprintf(topic, sizeof(topic), "ffi-test-%ld", (long)time(NULL));
+    }
+    printf("fluvio-ffi %s, topic %s\n", fluvio_ffi_version(), topic);
+
+    test_invalid_arguments();
+
+    CHECK(fluvio_connect(NULL, 30000, &client));
+    CHECK(fluvio_topic_create(client, topic, 1, 1));
+    EXPECT(fluvio_topic_create(client, topic, 1, 1) == FLUVIO_ALREADY_EXISTS);
+    EXPECT(topic_listed(client, topic));
+
+    test_produce_consume(client, topic);
+
+    fluvio_client_free(client);
+    printf("ok\n");
+    return 0;
+}
@@ -0,0 +1,176 @@
+use std::fmt::{Display, Write};
+
+use crate::histogram::Histogram;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricType {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+        }
+    }
+}
+
+/// Encode metrics into the OpenMetrics text format.
+///
+/// Samples must be grouped by family, a family is started with [`OpenMetricsEncoder::family`]
+/// and all samples of it have to be written before the next one is started.
+#[derive(Debug, Default)]
+pub struct OpenMetricsEncoder {
+    out: String,
+}
+
+impl OpenMetricsEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// start a new metric family, writing its metadata
+    pub fn family(&mut self, name: &str, ty: MetricType, help: &str) -> MetricFamily<'_> {
+        let _ = writeln!(self.out, "# TYPE {name} {}", ty.as_str());
+        let _ = writeln!(self.out, "# HELP {name} {}", escape_help(help));
+        MetricFamily {
+            out: &mut self.out,
+            name: name.to_owned(),
+            ty,
+        }
+    }
+
+    /// shortcut for a counter family with a single sample
+    pub fn counter(&mut self, name: &str, help: &str, value: u64) {
+        self.family(name, MetricType::Counter, help)
+            .sample(&[], value);
+    }
+
+    /// shortcut for a gauge family with a single sample
+    pub fn gauge(&mut self, name: &str, help: &str, value: impl Display) {
+        self.family(name, MetricType::Gauge, help)
+            .sample(&[], value);
+    }
+
+    /// terminate exposition and return text
+    pub fn finish(mut self) -> String {
+        self.out.push_str("# EOF\n");
+        self.out
+    }
+}
+
+pub struct MetricFamily<'a> {
+    out: &'a mut String,
+    name: String,
+    ty: MetricType,
+}
+
+impl MetricFamily<'_> {
+    /// add sample with labels, counters get `_total` suffix
+    pub fn sample(&mut self, labels: &[(&str, &str)], value: impl Display) -> &mut Self {
+        let suffix = match self.ty {
+            MetricType::Counter => "_total",
+            _ => "",
+        };
+        self.write_sample(suffix, labels, None, value);
+        self
+    }
+
+    /// add histogram sample set with labels
+    pub fn histogram(&mut self, labels: &[(&str, &str)], histogram: &Histogram) -> &mut Self {
+        let mut cumulative = 0;
+        for (bound, count) in histogram.buckets() {
+            cumulative += count;
+            self.write_sample("_bucket", labels, Some(&bound.to_string()), cumulative);
+        }
+        let count = histogram.count();
+        self.write_sample("_bucket", labels, Some("+Inf"), count);
+        self.write_sample("_sum", labels, None, histogram.sum_secs());
+        self.write_sample("_count", labels, None, count);
+        self
+    }
+
+    fn write_sample(
+        &mut self,
+        suffix: &str,
+        labels: &[(&str, &str)],
+        le: Option<&str>,
+        value: impl Display,
+    ) {
+        let _ = write!(self.out, "{}{suffix}", self.name);
+        if !labels.is_empty() || le.is_some() {
+            self.out.push('{');
+            let mut first = true;
+            let le = le.map(|le| ("le", le));
+            for (key, value) in labels.iter().copied().chain(le) {
+                if !first {
+                    self.out.push(',');
+                }
+                first = false;
+                let _ = write!(self.out, "{key}=\"{}\"", escape_label(value));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_families() {
+        let mut encoder = OpenMetricsEncoder::new();
+        encoder
+            .family("fluvio_records", MetricType::Counter, "records seen")
+            .sample(&[("client", "connector")], 2)
+            .sample(&[("client", "cli\"x")], 3);
+        encoder.gauge("fluvio_replicas", "replicas", 4);
+
+        let histogram = Histogram::new(&[0.01, 0.1]);
+        histogram.observe(Duration::from_millis(5));
+        histogram.observe(Duration::from_millis(50));
+        histogram.observe(Duration::from_secs(1));
+        encoder
+            .family("fluvio_latency_seconds", MetricType::Histogram, "latency")
+            .histogram(&[("api", "produce")], &histogram);
+
+        let text = encoder.finish();
+        assert_eq!(
+            text,
+            "# TYPE fluvio_records counter\n\
+             # HELP fluvio_records records seen\n\
+             fluvio_records_total{client=\"connector\"} 2\n\
+             fluvio_records_total{client=\"cli\\\"x\"} 3\n\
+             # TYPE fluvio_replicas gauge\n\
+             # HELP fluvio_replicas replicas\n\
+             fluvio_replicas 4\n\
+             # TYPE fluvio_latency_seconds histogram\n\
+             # HELP fluvio_latency_seconds latency\n\
+             fluvio_latency_seconds_bucket{api=\"produce\",le=\"0.01\"} 1\n\
+             fluvio_latency_seconds_bucket{api=\"produce\",le=\"0.1\"} 2\n\
+             fluvio_latency_seconds_bucket{api=\"produce\",le=\"+Inf\"} 3\n\
+             fluvio_latency_seconds_sum{api=\"produce\"} 1.055\n\
+             fluvio_latency_seconds_count{api=\"produce\"} 3\n\
+             # EOF\n"
+        );
+    }
+}
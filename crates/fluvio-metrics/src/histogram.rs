@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// default bucket bounds in seconds for request latencies
+pub const LATENCY_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Lock free histogram of durations.
+///
+/// Buckets are stored non cumulative, observations above the last bound are only
+/// reflected in count and sum.
+#[derive(Debug)]
+pub struct Histogram {
+    bounds: Vec<f64>,
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    sum_us: AtomicU64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::latency()
+    }
+}
+
+impl Histogram {
+    /// create histogram with bucket upper bounds in seconds, bounds must be sorted
+    pub fn new(bounds: &[f64]) -> Self {
+        Self {
+            bounds: bounds.to_vec(),
+            buckets: bounds.iter().map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+            sum_us: AtomicU64::new(0),
+        }
+    }
+
+    /// histogram using [`LATENCY_BUCKETS`]
+    pub fn latency() -> Self {
+        Self::new(LATENCY_BUCKETS)
+    }
+
+    pub fn observe(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        if let Some(index) = self.bounds.iter().position(|bound| secs <= *bound) {
+            self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_us
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// upper bound and number of observations for each bucket, non cumulative
+    pub fn buckets(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        self.bounds
+            .iter()
+            .zip(self.buckets.iter())
+            .map(|(bound, count)| (*bound, count.load(Ordering::Relaxed)))
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// sum of all observations in seconds
+    pub fn sum_secs(&self) -> f64 {
+        self.sum_us.load(Ordering::Relaxed) as f64 / 1_000_000.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_histogram_observe() {
+        let histogram = Histogram::new(&[0.001, 0.01]);
+        histogram.observe(Duration::from_micros(500));
+        histogram.observe(Duration::from_millis(1));
+        histogram.observe(Duration::from_millis(5));
+        histogram.observe(Duration::from_millis(20));
+
+        let buckets: Vec<_> = histogram.buckets().collect();
+        assert_eq!(buckets, vec![(0.001, 2), (0.01, 1)]);
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(histogram.sum_secs(), 0.0265);
+    }
+}
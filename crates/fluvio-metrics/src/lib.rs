@@ -0,0 +1,17 @@
+//! OpenMetrics exposition for Fluvio processes.
+//!
+//! Each process (SPU, SC, connectors) implements [`MetricsSource`] over its own
+//! counters and optionally serves them over HTTP with [`init_metrics_server`]
+//! so they can be scraped by Prometheus compatible collectors.
+
+mod encoder;
+mod histogram;
+mod server;
+
+pub use encoder::{OpenMetricsEncoder, MetricFamily, MetricType};
+pub use histogram::{Histogram, LATENCY_BUCKETS};
+pub use server::{MetricsSource, init_metrics_server, run_metrics_server, start_metrics_server};
+
+/// content type of the OpenMetrics text format
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
@@ -0,0 +1,179 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::{AsyncReadExt, AsyncWriteExt, StreamExt};
+use tracing::{debug, error, info};
+
+use fluvio_future::future::timeout;
+use fluvio_future::net::{TcpListener, TcpStream};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+
+use crate::OPENMETRICS_CONTENT_TYPE;
+use crate::encoder::OpenMetricsEncoder;
+
+const METRICS_PATH: &str = "/metrics";
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+const REQUEST_HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Process metrics which can be exposed in OpenMetrics format
+#[async_trait]
+pub trait MetricsSource: Send + Sync + 'static {
+    async fn encode(&self, encoder: &mut OpenMetricsEncoder);
+}
+
+/// spawn HTTP server exposing metrics of `source` at `GET /metrics`
+pub fn init_metrics_server<S: MetricsSource>(addr: String, source: Arc<S>) {
+    spawn(run_metrics_server(addr, source));
+}
+
+/// serve metrics of `source`, server is restarted on error
+pub async fn run_metrics_server<S: MetricsSource>(addr: String, source: Arc<S>) {
+    loop {
+        if let Err(err) = start_metrics_server(&addr, source.clone()).await {
+            error!(%addr, "error running metrics server: {}", err);
+        }
+        info!("metrics server stopped. Trying to restart in 5 seconds");
+        sleep(Duration::from_secs(5)).await;
+    }
+}
+
+pub async fn start_metrics_server<S: MetricsSource>(
+    addr: &str,
+    source: Arc<S>,
+) -> Result<(), IoError> {
+    let listener = TcpListener::bind(addr).await?;
+    info!(addr, "metrics server started");
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        let stream = stream?;
+        let source = source.clone();
+        spawn(async move {
+            if let Err(err) = handle_connection(stream, source).await {
+                debug!("error serving metrics: {}", err);
+            }
+        });
+    }
+    Ok(())
+}
+
+async fn handle_connection<S: MetricsSource>(
+    mut stream: TcpStream,
+    source: Arc<S>,
+) -> Result<(), IoError> {
+    let head = timeout(REQUEST_HEAD_TIMEOUT, read_request_head(&mut stream))
+        .await
+        .map_err(|_| IoError::new(ErrorKind::TimedOut, "timed out reading request head"))??;
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+    // ignore query string
+    let path = path.split('?').next().unwrap_or_default();
+
+    let response = match (method, path) {
+        ("GET", METRICS_PATH) => {
+            let mut encoder = OpenMetricsEncoder::new();
+            source.encode(&mut encoder).await;
+            response("200 OK", OPENMETRICS_CONTENT_TYPE, &encoder.finish())
+        }
+        (_, METRICS_PATH) => response("405 Method Not Allowed", "text/plain", ""),
+        _ => response("404 Not Found", "text/plain", ""),
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+/// read until the end of the request headers, the body is ignored
+async fn read_request_head(stream: &mut TcpStream) -> Result<String, IoError> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    loop {
+        let limit = buf.len().min(MAX_REQUEST_HEAD - head.len());
+        if limit == 0 {
+            return Err(IoError::new(
+                ErrorKind::InvalidData,
+                "request head too large",
+            ));
+        }
+        let read = stream.read(&mut buf[..limit]).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..read]);
+        if head.windows(4).any(|window| window == b"\r\n\r\n") {
+            break;
+        }
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+fn response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use futures_util::{AsyncReadExt, AsyncWriteExt};
+
+    use fluvio_future::net::TcpStream;
+    use fluvio_future::timer::sleep;
+
+    use crate::MetricType;
+
+    use super::*;
+
+    struct TestSource;
+
+    #[async_trait]
+    impl MetricsSource for TestSource {
+        async fn encode(&self, encoder: &mut OpenMetricsEncoder) {
+            encoder
+                .family("fluvio_test", MetricType::Counter, "test counter")
+                .sample(&[], 1);
+        }
+    }
+
+    async fn get(addr: &str, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.expect("connect");
+        stream.write_all(request.as_bytes()).await.expect("write");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.expect("read");
+        response
+    }
+
+    #[fluvio_future::test]
+    async fn test_metrics_server() {
+        let port = portpicker::pick_unused_port().expect("No free ports left");
+        let addr = format!("127.0.0.1:{port}");
+        init_metrics_server(addr.clone(), Arc::new(TestSource));
+        sleep(Duration::from_millis(100)).await;
+
+        let response = get(&addr, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(OPENMETRICS_CONTENT_TYPE));
+        assert!(response.ends_with("fluvio_test_total 1\n# EOF\n"));
+
+        let response = get(&addr, "GET / HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let response = get(&addr, "POST /metrics HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+
+        // oversized head is rejected without a response
+        let mut stream = TcpStream::connect(&addr).await.expect("connect");
+        let request = format!(
+            "GET /metrics HTTP/1.1\r\nX-Pad: {}",
+            "a".repeat(MAX_REQUEST_HEAD)
+        );
+        stream.write_all(request.as_bytes()).await.expect("write");
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response).await;
+        assert!(response.is_empty());
+    }
+}
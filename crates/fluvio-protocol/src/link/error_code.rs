@@ -63,6 +63,11 @@ pub enum ErrorCode {
     #[fluvio(tag = 73)]
     #[error("Partition is short-circuited")]
     PartitionShortCircuited,
+    #[fluvio(tag = 74)]
+    #[error(
+        "Not enough in sync replicas: {in_sync} in sync, topic requires at least {min_in_sync}"
+    )]
+    NotEnoughReplicas { in_sync: u16, min_in_sync: u16 },
 
     // Spu errors
     #[fluvio(tag = 1000)]
@@ -131,6 +136,18 @@ pub enum ErrorCode {
     #[fluvio(tag = 3005)]
     #[error("max retry attempts reached")]
     MaxRetryReached,
+    #[fluvio(tag = 3006)]
+    #[error("the stream does not support seek, pause or resume")]
+    StreamControlNotSupported,
+    #[fluvio(tag = 3007)]
+    #[error("partition {0} is not consumed by the stream")]
+    StreamPartitionNotFound(u32),
+    #[fluvio(tag = 3008)]
+    #[error("the SPU does not support consumer offset metadata")]
+    OffsetMetadataNotSupported,
+    #[fluvio(tag = 3009)]
+    #[error("consumer offset metadata of {0} bytes is too large")]
+    OffsetMetadataTooLarge(u32),
 
     // Managed Connector Errors
     #[fluvio(tag = 5000)]
@@ -206,6 +223,18 @@ pub enum ErrorCode {
     #[fluvio(tag = 9000)]
     #[error("a compression error occurred in the SPU")]
     CompressionError,
+    #[fluvio(tag = 9001)]
+    #[error("a compression dictionary error occurred")]
+    DictionaryError,
+    #[fluvio(tag = 9002)]
+    #[error("the compression dictionary was not found")]
+    DictionaryNotFound,
+    #[fluvio(tag = 9003)]
+    #[error("the compression dictionary already exists")]
+    DictionaryAlreadyExists,
+    #[fluvio(tag = 9004)]
+    #[error("the compression dictionary is in use by topic {0}")]
+    DictionaryInUse(String),
 
     // Deduplication
     #[fluvio(tag = 10000)]
@@ -336,6 +365,9 @@ mod test {
 
         // Stream Fetch error
         assert_tag!(ErrorCode::FetchSessionNotFoud, 3002, 0);
+        assert_tag!(ErrorCode::StreamControlNotSupported, 3006, 0);
+        assert_tag!(ErrorCode::OffsetMetadataNotSupported, 3008, 0);
+        assert_tag!(ErrorCode::OffsetMetadataTooLarge(4097), 3009, 0);
     }
 
     #[test]
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::io::Error;
 use std::io::ErrorKind;
 use std::mem::size_of;
@@ -10,6 +11,7 @@ use tracing::trace;
 use fluvio_types::Timestamp;
 use fluvio_compression::Compression;
 use fluvio_compression::CompressionError;
+use fluvio_compression::{CompressionOptions, Dictionary};
 
 use crate::bytes::Buf;
 use crate::bytes::BufMut;
@@ -19,8 +21,13 @@ use crate::Version;
 use super::ConsumerRecord;
 use super::Record;
 use super::Offset;
+use super::{RecordHeaders, RECORD_HEADERS_VERSION};
 
 const ATTR_SCHEMA_PRESENT: i16 = 0x10;
+const ATTR_ENCRYPTED: i16 = 0x20;
+const ATTR_DICTIONARY: i16 = 0x40;
+const ATTR_MIRRORED: i16 = 0x80;
+const ATTR_RECORD_HEADERS: i16 = 0x100;
 const ATTR_COMPRESSION_CODEC_MASK: i16 = 0x07;
 pub const NO_TIMESTAMP: i64 = -1;
 
@@ -32,6 +39,11 @@ pub trait BatchRecords: Default + Debug + Encoder + Decoder + Send + Sync {
     fn remainder_bytes(&self, remainder: usize) -> usize {
         remainder
     }
+
+    /// version records are encoded with in a batch with this header
+    fn records_version(&self, header: &BatchHeader) -> Version {
+        header.records_version()
+    }
 }
 
 /// A type describing in-memory records
@@ -61,7 +73,15 @@ impl Decoder for RawRecords {
         Ok(())
     }
 }
-impl BatchRecords for MemoryRecords {}
+impl BatchRecords for MemoryRecords {
+    fn records_version(&self, _header: &BatchHeader) -> Version {
+        if self.iter().any(|record| !record.headers().is_empty()) {
+            RECORD_HEADERS_VERSION
+        } else {
+            0
+        }
+    }
+}
 
 impl BatchRecords for RawRecords {}
 
@@ -90,6 +110,8 @@ pub struct Batch<R = MemoryRecords> {
     pub header: BatchHeader,
     // only encoded if schema_id is indicated in the header attr
     pub schema_id: SchemaId,
+    // only encoded if batch is marked as mirrored in the header attr
+    mirror_origin: Option<MirrorOrigin>,
     records: R,
 }
 
@@ -188,47 +210,146 @@ impl<R> Batch<R> {
         self.header.set_schema_id();
         self.schema_id = sid;
     }
+
+    /// origin of mirrored batch, None if batch was produced in this cluster
+    pub fn mirror_origin(&self) -> Option<MirrorOrigin> {
+        self.mirror_origin
+    }
+
+    /// mark batch as mirrored from origin
+    pub fn set_mirror_origin(&mut self, origin: MirrorOrigin) {
+        self.header.set_mirrored(true);
+        self.mirror_origin = Some(origin);
+    }
+
+    /// remove mirror origin, so batch is treated as produced in this cluster
+    pub fn clear_mirror_origin(&mut self) {
+        self.header.set_mirrored(false);
+        self.mirror_origin = None;
+    }
+
+    /// decode schema id and mirror origin which are between header and records
+    pub fn decode_header_extensions<T>(
+        &mut self,
+        src: &mut T,
+        version: Version,
+    ) -> Result<(), Error>
+    where
+        T: Buf,
+    {
+        if self.header.has_schema() {
+            let mut sid = SCHEMA_ID_NULL;
+            sid.decode(src, version)?;
+            self.schema_id = sid;
+            trace!(schema_id=?self.schema_id);
+        }
+        if self.header.is_mirrored() {
+            let mut origin = MirrorOrigin::default();
+            origin.decode(src, version)?;
+            trace!(?origin);
+            self.mirror_origin = Some(origin);
+        }
+        Ok(())
+    }
 }
 
 impl TryFrom<Batch<RawRecords>> for Batch {
     type Error = CompressionError;
     fn try_from(batch: Batch<RawRecords>) -> Result<Self, Self::Error> {
-        let records = batch.memory_records()?;
+        batch.into_memory_batch_with(None)
+    }
+}
+
+impl Batch<RawRecords> {
+    /// Decompress batch, using dictionary for batches compressed with one
+    pub fn into_memory_batch_with(
+        self,
+        dictionary: Option<&Dictionary>,
+    ) -> Result<Batch, CompressionError> {
+        let records = self.memory_records_with(dictionary)?;
         Ok(Batch {
-            base_offset: batch.base_offset,
-            batch_len: (BATCH_HEADER_SIZE + records.write_size(0)) as i32,
-            header: batch.header,
+            base_offset: self.base_offset,
+            batch_len: (BATCH_HEADER_SIZE + records.write_size(self.header.records_version()))
+                as i32,
+            header: self.header,
             schema_id: SCHEMA_ID_NULL,
+            mirror_origin: self.mirror_origin,
             records,
         })
     }
 }
 
-impl TryFrom<Batch> for Batch<RawRecords> {
-    type Error = CompressionError;
-    fn try_from(f: Batch) -> Result<Self, Self::Error> {
-        let mut buf = Vec::new();
-        f.records.encode(&mut buf, 0)?;
+impl Batch<RawRecords> {
+    /// Decompress records, using dictionary for batches compressed with one,
+    /// and compress them again with a different codec
+    pub fn recompress(
+        self,
+        compression: Compression,
+        dictionary: Option<&Dictionary>,
+        options: &CompressionOptions,
+    ) -> Result<Batch<RawRecords>, CompressionError> {
+        let schema_id = self.schema_id();
+        let mut batch = self.into_memory_batch_with(dictionary)?;
+        batch.schema_id = schema_id;
+        batch.header.set_compression(compression);
+        batch.compress_with(options)
+    }
 
-        let compression = f.get_compression()?;
-        let compressed_records = compression.compress(&buf)?;
-        let compressed_records_len = compressed_records.len() as i32;
-        let records = RawRecords(compressed_records);
-        let schema_id = f.schema_id();
+    /// Remove headers from records, for consumers which can't decode them.
+    /// Records are compressed again with the batch codec at `level`.
+    /// Batches without record headers are returned as they are
+    pub fn without_record_headers(
+        self,
+        dictionary: Option<&Dictionary>,
+        level: Option<i32>,
+    ) -> Result<Batch<RawRecords>, CompressionError> {
+        if !self.header.has_record_headers() {
+            return Ok(self);
+        }
+        let schema_id = self.schema_id();
+        let mut batch = self.into_memory_batch_with(dictionary)?;
+        batch.schema_id = schema_id;
+        for record in batch.records.iter_mut() {
+            record.headers = 0;
+            record.header_entries = RecordHeaders::default();
+        }
+        batch.compress_with(&CompressionOptions {
+            level,
+            dictionary: None,
+        })
+    }
 
-        Ok(Batch {
-            base_offset: f.base_offset,
-            batch_len: compressed_records_len,
-            header: f.header,
-            schema_id,
-            records,
+    /// Decompress records with `dictionary` and compress them again with the batch
+    /// codec at `level` without it, for consumers which can't decompress with dictionaries.
+    /// Batches compressed without dictionary are returned as they are
+    pub fn without_dictionary(
+        self,
+        dictionary: Option<&Dictionary>,
+        level: Option<i32>,
+    ) -> Result<Batch<RawRecords>, CompressionError> {
+        if !self.header.has_dictionary() {
+            return Ok(self);
+        }
+        let schema_id = self.schema_id();
+        let mut batch = self.into_memory_batch_with(dictionary)?;
+        batch.schema_id = schema_id;
+        batch.compress_with(&CompressionOptions {
+            level,
+            dictionary: None,
         })
     }
 }
 
+impl TryFrom<Batch> for Batch<RawRecords> {
+    type Error = CompressionError;
+    fn try_from(f: Batch) -> Result<Self, Self::Error> {
+        f.compress_with(&CompressionOptions::default())
+    }
+}
+
 impl<R> Batch<R>
 where
-    R: Encoder,
+    R: BatchRecords,
 {
     /// check if batch is valid after decoded
     pub fn validate_decoding(&self) -> bool {
@@ -236,12 +357,14 @@ where
     }
 
     fn calc_batch_len(&self) -> i32 {
-        let blen = if self.header.has_schema() {
-            BATCH_HEADER_SIZE + self.records.write_size(0) + size_of::<SchemaId>()
-        } else {
-            BATCH_HEADER_SIZE + self.records.write_size(0)
-        };
-        blen as i32
+        (BATCH_HEADER_SIZE
+            + self.header.extensions_len()
+            + self.records.write_size(self.records_version())) as i32
+    }
+
+    /// version records of this batch are encoded with
+    pub fn records_version(&self) -> Version {
+        self.records.records_version(&self.header)
     }
 }
 
@@ -285,6 +408,34 @@ impl Batch {
         self.header.last_offset_delta = self.records().len() as i32 - 1;
     }
 
+    /// Encode and compress records using header compression, tuned by level and dictionary from options
+    pub fn compress_with(
+        self,
+        options: &CompressionOptions,
+    ) -> Result<Batch<RawRecords>, CompressionError> {
+        let records_version = self.records_version();
+        let mut buf = Vec::new();
+        self.records.encode(&mut buf, records_version)?;
+
+        let compression = self.get_compression()?;
+        let compressed_records = compression.compress_with(&buf, options)?;
+        let compressed_records_len = compressed_records.len() as i32;
+        let records = RawRecords(compressed_records);
+        let schema_id = self.schema_id();
+        let mut header = self.header;
+        header.set_dictionary(options.dictionary.is_some());
+        header.set_record_headers(records_version >= RECORD_HEADERS_VERSION);
+
+        Ok(Batch {
+            base_offset: self.base_offset,
+            batch_len: compressed_records_len,
+            header,
+            schema_id,
+            mirror_origin: self.mirror_origin,
+            records,
+        })
+    }
+
     pub fn into_consumer_records_iter(
         self,
         partition: PartitionId,
@@ -306,37 +457,58 @@ impl Batch {
 
 impl Batch<RawRecords> {
     pub fn memory_records(&self) -> Result<MemoryRecords, CompressionError> {
-        let mut records: MemoryRecords = Default::default();
+        self.memory_records_with(None)
+    }
 
-        let mut decode = |data: &[u8]| -> Result<(), CompressionError> {
-            match records.decode(&mut &*data, 0) {
-                Ok(_) => Ok(()),
-                Err(err) if err.kind() == ErrorKind::UnexpectedEof => {
-                    debug!("not enough bytes for decoding memory records from raw");
-                    Ok(())
-                }
-                Err(err) => Err(err.into()),
-            }
-        };
+    /// Id of dictionary needed to uncompress the records, if batch was compressed with one
+    pub fn dictionary_id(&self) -> Option<u32> {
+        if self.header.has_dictionary() {
+            Dictionary::id_from_frame(&self.records.0)
+        } else {
+            None
+        }
+    }
 
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "compress")] {
-                match self.get_compression()? {
-                    Compression::None => decode(&self.records.0)?,
-                    compression => {
-                        let decompressed = compression
-                            .uncompress(&self.records.0)?
-                            .ok_or(CompressionError::UnreachableError)?;
-                        decode(&decompressed)?
-                    }
-                }
-            } else {
-                decode(&self.records.0)?
+    /// Decode records like [`Batch::memory_records`], using dictionary for batches compressed with one
+    pub fn memory_records_with(
+        &self,
+        dictionary: Option<&Dictionary>,
+    ) -> Result<MemoryRecords, CompressionError> {
+        let mut records: MemoryRecords = Default::default();
+
+        let data = self.uncompressed_records(dictionary)?;
+        match records.decode(&mut &*data, self.header.records_version()) {
+            Ok(_) => {}
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => {
+                debug!("not enough bytes for decoding memory records from raw");
             }
+            Err(err) => return Err(err.into()),
         }
 
         Ok(records)
     }
+
+    #[cfg(feature = "compress")]
+    fn uncompressed_records(
+        &self,
+        dictionary: Option<&Dictionary>,
+    ) -> Result<Cow<'_, [u8]>, CompressionError> {
+        match self.get_compression()? {
+            Compression::None => Ok(Cow::Borrowed(&self.records.0)),
+            compression => compression
+                .uncompress_with(&self.records.0, dictionary)?
+                .map(Cow::Owned)
+                .ok_or(CompressionError::UnreachableError),
+        }
+    }
+
+    #[cfg(not(feature = "compress"))]
+    fn uncompressed_records(
+        &self,
+        _dictionary: Option<&Dictionary>,
+    ) -> Result<Cow<'_, [u8]>, CompressionError> {
+        Ok(Cow::Borrowed(&self.records.0))
+    }
 }
 
 impl<T: Into<MemoryRecords>> From<T> for Batch {
@@ -372,21 +544,15 @@ where
         trace!("decoding batch");
         self.decode_from_file_buf(src, version)?;
         trace!("decoding batch header");
-        let rec_len = if self.header.has_schema() {
-            let mut sid = SCHEMA_ID_NULL;
-            sid.decode(src, version)?;
-            self.schema_id = sid;
-            trace!(schema_id=?self.schema_id);
-            self.batch_len as usize - BATCH_HEADER_SIZE - size_of::<SchemaId>()
-        } else {
-            self.batch_len as usize - BATCH_HEADER_SIZE
-        };
+        self.decode_header_extensions(src, version)?;
+        let rec_len = self.batch_len as usize - BATCH_HEADER_SIZE - self.header.extensions_len();
         trace!(rec_len, "decoding batch records with len");
         // not checking remaining bytes, because we do it in the record set
         let mut buf = src.take(rec_len);
 
         if buf.remaining() > 0 {
-            self.records.decode(&mut buf, version)?;
+            self.records
+                .decode(&mut buf, self.header.records_version())?;
         }
 
         trace!("decoding batch records done");
@@ -399,8 +565,10 @@ impl<R> Encoder for Batch<R>
 where
     R: BatchRecords,
 {
-    fn write_size(&self, version: Version) -> usize {
-        BATCH_FILE_HEADER_SIZE + self.records.write_size(version)
+    fn write_size(&self, _version: Version) -> usize {
+        BATCH_FILE_HEADER_SIZE
+            + self.header.extensions_len()
+            + self.records.write_size(self.records_version())
     }
 
     fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
@@ -416,9 +584,13 @@ where
         self.header.partition_leader_epoch.encode(dest, version)?;
         self.header.magic.encode(dest, version)?;
 
+        let records_version = self.records_version();
+        let mut header = self.header.clone();
+        header.set_record_headers(records_version >= RECORD_HEADERS_VERSION);
+
         let mut out: Vec<u8> = Vec::new();
         let buf = &mut out;
-        self.header.attributes.encode(buf, version)?;
+        header.attributes.encode(buf, version)?;
         self.header.last_offset_delta.encode(buf, version)?;
         self.header.first_timestamp.encode(buf, version)?;
         self.header.max_time_stamp.encode(buf, version)?;
@@ -428,7 +600,12 @@ where
         if self.header.has_schema() {
             self.schema_id.encode(buf, version)?;
         }
-        self.records.encode(buf, version)?;
+        if self.header.is_mirrored() {
+            self.mirror_origin
+                .unwrap_or_default()
+                .encode(buf, version)?;
+        }
+        self.records.encode(buf, records_version)?;
 
         let crc = crc32c::crc32c(&out);
         crc.encode(dest, version)?;
@@ -444,6 +621,7 @@ impl<R: Clone> Clone for Batch<R> {
             batch_len: self.batch_len,
             header: self.header.clone(),
             schema_id: self.schema_id.clone(),
+            mirror_origin: self.mirror_origin,
             records: self.records.clone(),
         }
     }
@@ -486,6 +664,117 @@ impl BatchHeader {
     pub fn set_schema_id(&mut self) {
         self.attributes |= ATTR_SCHEMA_PRESENT;
     }
+
+    /// records of this batch are sealed in an encryption envelope
+    pub fn is_encrypted(&self) -> bool {
+        self.attributes & ATTR_ENCRYPTED != 0
+    }
+
+    pub fn set_encrypted(&mut self, encrypted: bool) {
+        if encrypted {
+            self.attributes |= ATTR_ENCRYPTED;
+        } else {
+            self.attributes &= !ATTR_ENCRYPTED;
+        }
+    }
+
+    /// records are compressed with a zstd dictionary, id of dictionary is in the compressed frame
+    pub fn has_dictionary(&self) -> bool {
+        self.attributes & ATTR_DICTIONARY != 0
+    }
+
+    pub fn set_dictionary(&mut self, dictionary: bool) {
+        if dictionary {
+            self.attributes |= ATTR_DICTIONARY;
+        } else {
+            self.attributes &= !ATTR_DICTIONARY;
+        }
+    }
+
+    /// batch was replicated from another cluster by mirroring, origin follows the header
+    pub fn is_mirrored(&self) -> bool {
+        self.attributes & ATTR_MIRRORED != 0
+    }
+
+    fn set_mirrored(&mut self, mirrored: bool) {
+        if mirrored {
+            self.attributes |= ATTR_MIRRORED;
+        } else {
+            self.attributes &= !ATTR_MIRRORED;
+        }
+    }
+
+    /// records carry headers, encoded from [`RECORD_HEADERS_VERSION`]
+    pub fn has_record_headers(&self) -> bool {
+        self.attributes & ATTR_RECORD_HEADERS != 0
+    }
+
+    pub fn set_record_headers(&mut self, record_headers: bool) {
+        if record_headers {
+            self.attributes |= ATTR_RECORD_HEADERS;
+        } else {
+            self.attributes &= !ATTR_RECORD_HEADERS;
+        }
+    }
+
+    /// attributes describing how records are encoded.
+    /// Encryption and mirror flags are left out, they change when batch is stored or forwarded
+    pub fn records_attributes(&self) -> i16 {
+        self.attributes
+            & (ATTR_COMPRESSION_CODEC_MASK
+                | ATTR_SCHEMA_PRESENT
+                | ATTR_DICTIONARY
+                | ATTR_RECORD_HEADERS)
+    }
+
+    /// version records of this batch are encoded with
+    pub fn records_version(&self) -> Version {
+        if self.has_record_headers() {
+            RECORD_HEADERS_VERSION
+        } else {
+            0
+        }
+    }
+
+    /// size of schema id and mirror origin encoded between header and records
+    pub fn extensions_len(&self) -> usize {
+        let mut len = 0;
+        if self.has_schema() {
+            len += size_of::<SchemaId>();
+        }
+        if self.is_mirrored() {
+            len += MIRROR_ORIGIN_SIZE;
+        }
+        len
+    }
+}
+
+const MIRROR_ORIGIN_SIZE: usize = size_of::<i32>() + size_of::<Offset>();
+
+/// Where a mirrored batch was first produced
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+pub struct MirrorOrigin {
+    /// fingerprint of origin cluster id
+    pub cluster: i32,
+    /// base offset of batch in origin partition
+    pub offset: Offset,
+}
+
+impl MirrorOrigin {
+    pub fn new(cluster_id: &str, offset: Offset) -> Self {
+        Self {
+            cluster: Self::fingerprint(cluster_id),
+            offset,
+        }
+    }
+
+    pub fn fingerprint(cluster_id: &str) -> i32 {
+        crc32c::crc32c(cluster_id.as_bytes()) as i32
+    }
+
+    pub fn is_from(&self, cluster_id: &str) -> bool {
+        self.cluster == Self::fingerprint(cluster_id)
+    }
 }
 impl Default for BatchHeader {
     fn default() -> Self {
@@ -644,6 +933,160 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_batch_header_encrypted_flag() {
+        let mut header = BatchHeader::default();
+        header.set_compression(Compression::Gzip);
+        assert!(!header.is_encrypted());
+
+        header.set_encrypted(true);
+        assert!(header.is_encrypted());
+        assert_eq!(
+            header.get_compression().expect("compression"),
+            Compression::Gzip
+        );
+
+        header.set_encrypted(false);
+        assert!(!header.is_encrypted());
+        assert_eq!(
+            header.get_compression().expect("compression"),
+            Compression::Gzip
+        );
+    }
+
+    #[test]
+    fn test_batch_mirror_origin() -> Result<(), IoError> {
+        let mut batch = Batch::<MemoryRecords>::default();
+        batch.add_record(Record::new("mirrored"));
+        assert!(!batch.header.is_mirrored());
+        assert_eq!(batch.mirror_origin(), None);
+
+        batch.set_schema_id(SchemaId(7));
+        batch.set_mirror_origin(MirrorOrigin::new("peer", 42));
+
+        let mut out = vec![];
+        batch.encode(&mut out, 0)?;
+        assert_eq!(out.len(), batch.write_size(0));
+        let decoded = Batch::<MemoryRecords>::decode_from(&mut Cursor::new(out), 0)?;
+        assert!(decoded.validate_decoding());
+
+        let origin = decoded.mirror_origin().expect("origin");
+        assert!(origin.is_from("peer"));
+        assert!(!origin.is_from("home"));
+        assert_eq!(origin.offset, 42);
+        // kafka header fields are not used to carry origin
+        assert_eq!(decoded.header.producer_id, -1);
+        assert_eq!(decoded.header.partition_leader_epoch, -1);
+        assert_eq!(decoded.schema_id(), SchemaId(7));
+        assert_eq!(decoded.records().len(), 1);
+
+        let mut raw = Batch::<RawRecords>::try_from(decoded).expect("compress");
+        assert_eq!(raw.mirror_origin(), Some(origin));
+        raw.clear_mirror_origin();
+        let mut out = vec![];
+        raw.encode(&mut out, 0)?;
+        let decoded = Batch::<MemoryRecords>::decode_from(&mut Cursor::new(out), 0)?;
+        assert!(!decoded.header.is_mirrored());
+        assert_eq!(decoded.mirror_origin(), None);
+        assert_eq!(decoded.schema_id(), SchemaId(7));
+        assert_eq!(decoded.records().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_record_headers_flag() -> Result<(), IoError> {
+        let mut record = Record::new("traced");
+        record.headers_mut().insert("traceparent", "abc");
+        let batch = Batch::from(vec![Record::new("plain"), record]);
+
+        let mut out = vec![];
+        batch.encode(&mut out, 0)?;
+        assert_eq!(out.len(), batch.write_size(0));
+        let decoded = Batch::<MemoryRecords>::decode_from(&mut Cursor::new(out), 0)?;
+        assert!(decoded.header.has_record_headers());
+        assert!(decoded.validate_decoding());
+        assert_eq!(
+            decoded.records()[1].headers().get("traceparent"),
+            Some(b"abc".as_slice())
+        );
+
+        let raw = Batch::<RawRecords>::try_from(decoded).expect("compress");
+        assert!(raw.header.has_record_headers());
+        assert_eq!(
+            raw.header.get_compression().expect("compression"),
+            Compression::None
+        );
+        let records = raw.memory_records().expect("records");
+        assert_eq!(
+            records[1].headers().get("traceparent"),
+            Some(b"abc".as_slice())
+        );
+
+        // older consumers get the records without headers, in the original encoding
+        let raw = raw
+            .recompress(Compression::Gzip, None, &CompressionOptions::default())
+            .expect("recompress");
+        let stripped = raw.without_record_headers(None, Some(9)).expect("strip");
+        assert!(!stripped.header.has_record_headers());
+        assert_eq!(
+            stripped.header.get_compression().expect("compression"),
+            Compression::Gzip
+        );
+        let mut out = vec![];
+        stripped.encode(&mut out, 0)?;
+        let legacy = Batch::<MemoryRecords>::decode_from(&mut Cursor::new(out), 0)?;
+        assert_eq!(legacy.records().len(), 2);
+        assert!(legacy.records()[1].headers().is_empty());
+        assert_eq!(legacy.records()[1].value.as_ref(), b"traced");
+
+        // batches without record headers keep the original encoding
+        let plain = Batch::from(vec![Record::new("plain")]);
+        let raw = Batch::<RawRecords>::try_from(plain).expect("compress");
+        assert!(!raw.header.has_record_headers());
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_compress_with_level() {
+        let value = "FLUVIO_AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".repeat(10);
+        let mut batch = Batch::<MemoryRecords>::default();
+        batch.add_record(Record::new(value.clone()));
+        batch.header.set_compression(Compression::Zstd);
+
+        let raw: Batch<RawRecords> = batch
+            .compress_with(&CompressionOptions::default().level(19))
+            .expect("compress");
+        assert!(!raw.header.has_dictionary());
+        assert_eq!(raw.dictionary_id(), None);
+
+        let records = raw.memory_records().expect("uncompress");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].value().as_ref(), value.as_bytes());
+    }
+
+    #[test]
+    fn test_batch_recompress() {
+        let mut batch = Batch::<MemoryRecords>::default();
+        batch.add_record(Record::new("hello"));
+        batch.add_record(Record::new("world"));
+        batch.header.set_compression(Compression::Gzip);
+        batch.set_base_offset(10);
+
+        let raw: Batch<RawRecords> = batch.try_into().expect("compress");
+        let recompressed = raw
+            .recompress(Compression::Zstd, None, &CompressionOptions::default())
+            .expect("recompress");
+        assert_eq!(
+            recompressed.get_compression().expect("compression"),
+            Compression::Zstd
+        );
+        assert_eq!(recompressed.get_base_offset(), 10);
+
+        let records = recompressed.memory_records().expect("uncompress");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].value().as_ref(), b"world");
+    }
+
     #[test]
     fn test_batch_offset_delta() {
         let mut batch = Batch::<MemoryRecords>::default();
@@ -920,6 +1363,26 @@ mod test {
         assert!(not_compressed.batch_len() > compressed.batch_len());
     }
 
+    #[test]
+    fn test_recompress_keeps_records_and_schema_id() {
+        let mut batch = Batch::from(vec![Record::new("a"), Record::new("b")]);
+        batch.header.set_compression(Compression::Gzip);
+        batch.set_schema_id(SchemaId(42));
+        let gzip: Batch<RawRecords> = Batch::try_from(batch).unwrap();
+
+        let recompressed = gzip
+            .recompress(Compression::None, None, &CompressionOptions::default())
+            .unwrap();
+
+        assert_eq!(recompressed.get_compression().unwrap(), Compression::None);
+        assert!(recompressed.header.has_schema());
+        assert_eq!(recompressed.schema_id(), SchemaId(42));
+        let records = recompressed.memory_records().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].value.as_ref(), b"a");
+        assert_eq!(records[1].value.as_ref(), b"b");
+    }
+
     #[test]
     fn batch_header_id_set() {
         let mut batch = Batch::from(vec![Record::default(), Record::default()]);
@@ -0,0 +1,108 @@
+//! Values too large for a single request, split over consecutive records of a partition.
+//!
+//! Every chunk record carries a [`CHUNK_HEADER`] identifying the value it belongs to,
+//! its position and the number of chunks. Consumers concatenate the values of all
+//! chunks, in order, to restore the original value.
+
+use bytes::Bytes;
+
+use crate::{Decoder, Encoder};
+
+use super::RecordHeaders;
+
+/// Record header holding the encoded [`RecordChunk`]
+pub const CHUNK_HEADER: &str = "fluvio-chunk";
+
+const CHUNK_VERSION: i16 = 0;
+
+/// Position of a record in a value split into chunks
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Encoder, Decoder)]
+pub struct RecordChunk {
+    id: u64,
+    index: u32,
+    count: u32,
+    value_len: u64,
+}
+
+impl RecordChunk {
+    pub fn new(id: u64, index: u32, count: u32, value_len: u64) -> Self {
+        Self {
+            id,
+            index,
+            count,
+            value_len,
+        }
+    }
+
+    /// Identifies the chunks of one value, chosen by the producer
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Position of this chunk, starting at 0
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Number of chunks of the value
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Length of the whole value
+    pub fn value_len(&self) -> u64 {
+        self.value_len
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.index + 1 >= self.count
+    }
+
+    /// Length of the encoded header value
+    pub fn header_len() -> usize {
+        Self::default().write_size(CHUNK_VERSION)
+    }
+
+    /// Writes this chunk position into record headers
+    pub fn inject(&self, headers: &mut RecordHeaders) {
+        let mut value = Vec::with_capacity(Self::header_len());
+        // encoding of fixed size integers into a vector can't fail
+        let _ = self.encode(&mut value, CHUNK_VERSION);
+        headers.insert(CHUNK_HEADER, Bytes::from(value));
+    }
+
+    /// Reads a chunk position from record headers, ignoring an invalid header
+    pub fn extract(headers: &RecordHeaders) -> Option<Self> {
+        let mut value = headers.get(CHUNK_HEADER)?;
+        if value.len() != Self::header_len() {
+            return None;
+        }
+        let chunk = Self::decode_from(&mut value, CHUNK_VERSION).ok()?;
+        (chunk.count > 0 && chunk.index < chunk.count).then_some(chunk)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_inject_extract_headers() {
+        let chunk = RecordChunk::new(7, 1, 3, 1000);
+        let mut headers = RecordHeaders::default();
+        headers.insert("other", "header");
+        chunk.inject(&mut headers);
+
+        assert_eq!(headers.len(), 2);
+        assert_eq!(headers.get(CHUNK_HEADER).map(|v| v.len()), Some(24));
+        assert_eq!(RecordChunk::extract(&headers), Some(chunk));
+        assert!(!chunk.is_last());
+        assert!(RecordChunk::new(7, 2, 3, 1000).is_last());
+
+        headers.insert(CHUNK_HEADER, "invalid");
+        assert_eq!(RecordChunk::extract(&headers), None);
+
+        RecordChunk::new(7, 3, 3, 1000).inject(&mut headers);
+        assert_eq!(RecordChunk::extract(&headers), None);
+    }
+}
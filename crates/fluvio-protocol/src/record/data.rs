@@ -32,6 +32,7 @@ use super::batch::MemoryRecords;
 use super::batch::NO_TIMESTAMP;
 use super::batch::Batch;
 use super::Offset;
+use super::TraceContext;
 
 #[cfg(feature = "compress")]
 use super::batch::RawRecords;
@@ -429,12 +430,122 @@ impl RecordHeader {
     }
 }
 
+/// First record version that encodes header entries.
+///
+/// Below it records encode a zero header count, as they did before headers
+/// were supported, so older decoders and SmartModules can read them.
+pub const RECORD_HEADERS_VERSION: Version = 23;
+
+/// Key-value headers attached to a record.
+///
+/// Encoded like Kafka record headers: a varint count followed by each
+/// header as a varint length-prefixed UTF-8 key and byte value. A record
+/// without headers encodes as a single zero count.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RecordHeaders(Vec<(String, Bytes)>);
+
+impl RecordHeaders {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the value of the first header with the given key
+    pub fn get(&self, key: &str) -> Option<&[u8]> {
+        self.0
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Sets the header, replacing any existing header with the same key
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<Bytes>) {
+        let key = key.into();
+        self.remove(&key);
+        self.0.push((key, value.into()));
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.0.retain(|(name, _)| name != key);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_ref()))
+    }
+}
+
+impl<K: Into<String>, V: Into<Bytes>> FromIterator<(K, V)> for RecordHeaders {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut headers = Self::default();
+        for (key, value) in iter {
+            headers.insert(key, value);
+        }
+        headers
+    }
+}
+
+impl Encoder for RecordHeaders {
+    fn write_size(&self, _version: Version) -> usize {
+        self.0.iter().fold(
+            (self.0.len() as i64).var_write_size(),
+            |sum, (key, value)| {
+                sum + (key.len() as i64).var_write_size()
+                    + key.len()
+                    + (value.len() as i64).var_write_size()
+                    + value.len()
+            },
+        )
+    }
+
+    fn encode<T>(&self, dest: &mut T, _version: Version) -> Result<(), Error>
+    where
+        T: BufMut,
+    {
+        (self.0.len() as i64).encode_varint(dest)?;
+        for (key, value) in self.0.iter() {
+            (key.len() as i64).encode_varint(dest)?;
+            dest.put_slice(key.as_bytes());
+            (value.len() as i64).encode_varint(dest)?;
+            dest.put_slice(value);
+        }
+        Ok(())
+    }
+}
+
+impl Decoder for RecordHeaders {
+    fn decode<T>(&mut self, src: &mut T, _version: Version) -> Result<(), Error>
+    where
+        T: Buf,
+    {
+        let mut count: i64 = 0;
+        count.decode_varint(src)?;
+        self.0.clear();
+        for _ in 0..count.max(0) {
+            let mut key: Vec<u8> = Vec::new();
+            key.decode_varint(src)?;
+            let key =
+                String::from_utf8(key).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+            let mut value: Option<Vec<u8>> = None;
+            value.decode_varint(src)?;
+            self.0.push((key, value.unwrap_or_default().into()));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct Record<B = RecordData> {
     pub preamble: RecordHeader,
     pub key: Option<B>,
     pub value: B,
+    /// Number of headers the record was decoded with, use [`Record::headers`] for the entries
     pub headers: i64,
+    pub header_entries: RecordHeaders,
 }
 
 impl<B: Default> Record<B> {
@@ -472,6 +583,16 @@ impl<B: Default> Record<B> {
     pub fn into_key(self) -> Option<B> {
         self.key
     }
+
+    /// Returns the headers of this record
+    pub fn headers(&self) -> &RecordHeaders {
+        &self.header_entries
+    }
+
+    /// Returns a mutable reference to the headers of this record
+    pub fn headers_mut(&mut self) -> &mut RecordHeaders {
+        &mut self.header_entries
+    }
 }
 
 impl Record {
@@ -516,7 +637,7 @@ impl<B: Debug> Debug for Record<B> {
             .field("preamble", &self.preamble)
             .field("key", &self.key)
             .field("value", &self.value)
-            .field("headers", &self.headers)
+            .field("headers", &self.header_entries)
             .finish()
     }
 }
@@ -529,7 +650,11 @@ where
         let inner_size = self.preamble.write_size(version)
             + self.key.write_size(version)
             + self.value.write_size(version)
-            + self.headers.var_write_size();
+            + if version >= RECORD_HEADERS_VERSION {
+                self.header_entries.write_size(version)
+            } else {
+                0i64.var_write_size()
+            };
         let len: i64 = inner_size as i64;
         len.var_write_size() + inner_size
     }
@@ -542,7 +667,11 @@ where
         self.preamble.encode(&mut out, version)?;
         self.key.encode(&mut out, version)?;
         self.value.encode(&mut out, version)?;
-        self.headers.encode_varint(&mut out)?;
+        if version >= RECORD_HEADERS_VERSION {
+            self.header_entries.encode(&mut out, version)?;
+        } else {
+            0i64.encode_varint(&mut out)?;
+        }
         let len: i64 = out.len() as i64;
         trace!("record encode as {} bytes", len);
         len.encode_varint(dest)?;
@@ -575,7 +704,13 @@ where
         trace!("offset delta: {}", self.preamble.offset_delta);
         self.key.decode(src, version)?;
         self.value.decode(src, version)?;
-        self.headers.decode_varint(src)?;
+        if version >= RECORD_HEADERS_VERSION {
+            self.header_entries.decode(src, version)?;
+            self.headers = self.header_entries.len() as i64;
+        } else {
+            self.headers.decode_varint(src)?;
+            self.header_entries = RecordHeaders::default();
+        }
 
         Ok(())
     }
@@ -635,6 +770,16 @@ impl ConsumerRecord {
         self.inner().value().as_ref()
     }
 
+    /// Returns the headers of the Record
+    pub fn headers(&self) -> &RecordHeaders {
+        self.inner().headers()
+    }
+
+    /// Returns the trace context the producer attached to the Record, if any
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        TraceContext::extract(self.headers())
+    }
+
     /// Return the timestamp of the Record
     pub fn timestamp(&self) -> Timestamp {
         if self.timestamp_base <= 0 {
@@ -687,6 +832,39 @@ mod test {
     }
 
     /// test decoding of records when one of the batch was truncated
+    #[test]
+    fn test_encode_decode_record_headers() -> Result<(), IoError> {
+        let mut record = Record::new("dog");
+        record.headers_mut().insert("traceparent", "abc");
+        record.headers_mut().insert("empty", Bytes::new());
+
+        let mut out = vec![];
+        record.encode(&mut out, RECORD_HEADERS_VERSION)?;
+        assert_eq!(record.write_size(RECORD_HEADERS_VERSION), out.len());
+
+        let decoded =
+            Record::<RecordData>::decode_from(&mut Cursor::new(&out), RECORD_HEADERS_VERSION)?;
+        assert_eq!(decoded.headers, 2);
+        assert_eq!(decoded.headers(), record.headers());
+        assert_eq!(decoded.headers().get("traceparent"), Some("abc".as_bytes()));
+        assert_eq!(decoded.headers().get("empty"), Some([].as_slice()));
+        assert_eq!(decoded.value.as_ref(), b"dog");
+
+        // records without headers keep the original encoding
+        let mut out = vec![];
+        Record::new("dog").encode(&mut out, RECORD_HEADERS_VERSION)?;
+        assert_eq!(out.last(), Some(&0x0));
+
+        // older versions encode an empty header count
+        let mut legacy = vec![];
+        record.encode(&mut legacy, RECORD_HEADERS_VERSION - 1)?;
+        assert_eq!(record.write_size(RECORD_HEADERS_VERSION - 1), legacy.len());
+        assert_eq!(legacy, out);
+        let decoded = Record::<RecordData>::decode_from(&mut Cursor::new(&legacy), 0)?;
+        assert!(decoded.headers().is_empty());
+        Ok(())
+    }
+
     #[test]
     fn test_decode_batch_truncation() {
         use super::RecordSet;
@@ -5,9 +5,13 @@ mod data;
 pub use self::data::*;
 
 mod batch;
+mod chunk;
 mod replica;
+mod trace;
 pub use batch::*;
+pub use chunk::*;
 pub use replica::*;
+pub use trace::*;
 
 pub type Offset = i64;
 pub type Size = u32;
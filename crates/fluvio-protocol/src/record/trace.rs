@@ -0,0 +1,188 @@
+//! W3C trace context propagated through record headers and requests.
+//!
+//! See <https://www.w3.org/TR/trace-context/> for the header format.
+
+use crate::{Decoder, Encoder};
+
+use super::RecordHeaders;
+
+/// Record header holding the W3C `traceparent` value
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+/// Record header holding the W3C `tracestate` value
+pub const TRACESTATE_HEADER: &str = "tracestate";
+
+const TRACEPARENT_VERSION: &str = "00";
+const FLAG_SAMPLED: u8 = 0x01;
+
+/// A validated W3C trace context.
+///
+/// An empty (default) value means no context is present; it is what older
+/// clients send in requests that carry a trace context.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+pub struct TraceContext {
+    traceparent: String,
+    tracestate: String,
+}
+
+impl TraceContext {
+    /// Builds a context from its trace id, parent span id and sampled flag
+    pub fn new(trace_id: u128, span_id: u64, sampled: bool) -> Self {
+        let flags = if sampled { FLAG_SAMPLED } else { 0 };
+        Self {
+            traceparent: format!(
+                "{TRACEPARENT_VERSION}-{trace_id:032x}-{span_id:016x}-{flags:02x}"
+            ),
+            tracestate: String::new(),
+        }
+    }
+
+    /// Parses a `traceparent` value, returning `None` if it is not valid
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let (_, span_id, _) = parse_traceparent(traceparent)?;
+        if span_id == 0 {
+            return None;
+        }
+        Some(Self {
+            traceparent: traceparent.to_ascii_lowercase(),
+            tracestate: String::new(),
+        })
+    }
+
+    /// Sets the vendor specific `tracestate` value
+    pub fn with_tracestate(mut self, tracestate: impl Into<String>) -> Self {
+        self.tracestate = tracestate.into();
+        self
+    }
+
+    /// Returns true if this is the empty context
+    pub fn is_empty(&self) -> bool {
+        self.traceparent.is_empty()
+    }
+
+    pub fn traceparent(&self) -> &str {
+        &self.traceparent
+    }
+
+    pub fn tracestate(&self) -> Option<&str> {
+        (!self.tracestate.is_empty()).then_some(self.tracestate.as_str())
+    }
+
+    pub fn trace_id(&self) -> u128 {
+        parse_traceparent(&self.traceparent).map_or(0, |(trace_id, _, _)| trace_id)
+    }
+
+    /// Id of the span the context was propagated from
+    pub fn span_id(&self) -> u64 {
+        parse_traceparent(&self.traceparent).map_or(0, |(_, span_id, _)| span_id)
+    }
+
+    pub fn is_sampled(&self) -> bool {
+        parse_traceparent(&self.traceparent).is_some_and(|(_, _, flags)| flags & FLAG_SAMPLED != 0)
+    }
+
+    /// Writes this context into record headers
+    pub fn inject(&self, headers: &mut RecordHeaders) {
+        if self.is_empty() {
+            return;
+        }
+        headers.insert(TRACEPARENT_HEADER, self.traceparent.clone());
+        match self.tracestate() {
+            Some(tracestate) => headers.insert(TRACESTATE_HEADER, tracestate.to_owned()),
+            None => headers.remove(TRACESTATE_HEADER),
+        }
+    }
+
+    /// Reads a context from record headers, ignoring an invalid `traceparent`
+    pub fn extract(headers: &RecordHeaders) -> Option<Self> {
+        let traceparent = std::str::from_utf8(headers.get(TRACEPARENT_HEADER)?).ok()?;
+        let context = Self::parse(traceparent)?;
+        match headers
+            .get(TRACESTATE_HEADER)
+            .and_then(|value| std::str::from_utf8(value).ok())
+        {
+            Some(tracestate) => Some(context.with_tracestate(tracestate)),
+            None => Some(context),
+        }
+    }
+}
+
+/// Splits a `traceparent` into trace id, span id and flags
+fn parse_traceparent(traceparent: &str) -> Option<(u128, u64, u8)> {
+    let mut parts = traceparent.split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let span_id = parts.next()?;
+    let flags = parts.next()?;
+    // future versions may append fields, version 00 must not
+    if version.len() != 2 || version.eq_ignore_ascii_case("ff") {
+        return None;
+    }
+    if version == TRACEPARENT_VERSION && parts.next().is_some() {
+        return None;
+    }
+    if trace_id.len() != 32 || span_id.len() != 16 || flags.len() != 2 {
+        return None;
+    }
+    u8::from_str_radix(version, 16).ok()?;
+    let trace_id = u128::from_str_radix(trace_id, 16).ok()?;
+    let span_id = u64::from_str_radix(span_id, 16).ok()?;
+    let flags = u8::from_str_radix(flags, 16).ok()?;
+    (trace_id != 0).then_some((trace_id, span_id, flags))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_parse_traceparent() {
+        let context = TraceContext::parse(TRACEPARENT).expect("valid");
+        assert_eq!(context.trace_id(), 0x4bf92f3577b34da6a3ce929d0e0e4736);
+        assert_eq!(context.span_id(), 0x00f067aa0ba902b7);
+        assert!(context.is_sampled());
+        assert_eq!(
+            TraceContext::new(context.trace_id(), context.span_id(), true),
+            context
+        );
+
+        assert!(TraceContext::parse("").is_none());
+        assert!(
+            TraceContext::parse("00-00000000000000000000000000000000-00f067aa0ba902b7-01")
+                .is_none()
+        );
+        assert!(
+            TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01")
+                .is_none()
+        );
+        assert!(
+            TraceContext::parse("ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+                .is_none()
+        );
+        assert!(
+            TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-xx")
+                .is_none()
+        );
+        assert!(
+            TraceContext::parse("01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00-xx")
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_inject_extract_headers() {
+        let context = TraceContext::parse(TRACEPARENT)
+            .expect("valid")
+            .with_tracestate("vendor=value");
+        let mut headers = RecordHeaders::default();
+        headers.insert("other", "header");
+        context.inject(&mut headers);
+
+        assert_eq!(headers.len(), 3);
+        assert_eq!(TraceContext::extract(&headers), Some(context));
+
+        headers.insert(TRACEPARENT_HEADER, "invalid");
+        assert_eq!(TraceContext::extract(&headers), None);
+    }
+}
@@ -24,6 +24,7 @@ pub enum AdminPublicApiKey {
     Watch = 1004,
     Mirroring = 1005,
     Update = 1006,
+    PartitionUsage = 1007,
 }
 
 impl Default for AdminPublicApiKey {
@@ -0,0 +1,11 @@
+pub use fluvio_controlplane_metadata::dictionary::*;
+
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec};
+
+impl AdminSpec for DictionarySpec {}
+
+impl CreatableAdminSpec for DictionarySpec {}
+
+impl DeletableAdminSpec for DictionarySpec {
+    type DeleteKey = String;
+}
@@ -10,6 +10,7 @@ pub mod shared;
 pub mod tableformat;
 pub mod mirror;
 pub mod mirroring;
+pub mod dictionary;
 
 pub mod remote_file;
 
@@ -77,6 +78,12 @@ mod error {
                 ApiError::Code(ErrorCode::TableFormatNotFound, _) => {
                     write!(f, "TableFormat not found")
                 }
+                ApiError::Code(ErrorCode::DictionaryAlreadyExists, _) => {
+                    write!(f, "Dictionary already exists")
+                }
+                ApiError::Code(ErrorCode::DictionaryNotFound, _) => {
+                    write!(f, "Dictionary not found")
+                }
                 ApiError::Code(_, Some(msg)) => {
                     write!(f, "{msg}")
                 }
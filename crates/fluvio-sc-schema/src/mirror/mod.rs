@@ -1,6 +1,6 @@
 pub use fluvio_controlplane_metadata::mirror::*;
 
-use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec};
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec, UpdatableAdminSpec};
 
 impl AdminSpec for MirrorSpec {}
 
@@ -9,3 +9,8 @@ impl CreatableAdminSpec for MirrorSpec {}
 impl DeletableAdminSpec for MirrorSpec {
     type DeleteKey = String;
 }
+
+impl UpdatableAdminSpec for MirrorSpec {
+    type UpdateKey = String;
+    type UpdateAction = UpdateMirrorAction;
+}
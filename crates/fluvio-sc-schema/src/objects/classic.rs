@@ -660,6 +660,9 @@ mod create {
         }
     }
 
+    // dictionaries were introduced after dynamic objects, classic protocol is not supported
+    impl ClassicCreatableAdminSpec for crate::dictionary::DictionarySpec {}
+
     impl ClassicCreatableAdminSpec for MirrorSpec {
         const CREATE_TYPE: u8 = 6;
 
@@ -1,9 +1,19 @@
 pub use fluvio_controlplane_metadata::partition::*;
+pub use usage::*;
+
+mod usage;
 
 mod convert {
 
-    use crate::{AdminSpec};
+    use crate::{AdminSpec, UpdatableAdminSpec};
     use super::*;
 
     impl AdminSpec for PartitionSpec {}
+
+    /// key is either partition name, topic name for all of its partitions
+    /// or empty for all partitions
+    impl UpdatableAdminSpec for PartitionSpec {
+        type UpdateKey = String;
+        type UpdateAction = UpdatePartitionAction;
+    }
 }
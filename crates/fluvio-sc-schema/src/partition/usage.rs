@@ -0,0 +1,121 @@
+//!
+//! # Partition Usage
+//!
+//! Storage usage and lag of partitions, aggregated by SC from SPU reports.
+//!
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_types::SpuId;
+
+use crate::AdminPublicApiKey;
+use crate::objects::COMMON_VERSION;
+
+/// Request usage of partitions, empty `topics` means all topics
+#[derive(Encoder, Decoder, Default, Debug, Clone)]
+pub struct PartitionUsageRequest {
+    pub topics: Vec<String>,
+    pub system: bool,
+}
+
+impl Request for PartitionUsageRequest {
+    const API_KEY: u16 = AdminPublicApiKey::PartitionUsage as u16;
+    const MIN_API_VERSION: i16 = 20;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = PartitionUsageResponse;
+}
+
+impl PartitionUsageRequest {
+    pub fn new(topics: Vec<String>) -> Self {
+        Self {
+            topics,
+            ..Default::default()
+        }
+    }
+
+    /// request system partitions instead of user partitions
+    pub fn system(mut self, system: bool) -> Self {
+        self.system = system;
+        self
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone)]
+pub struct PartitionUsageResponse {
+    pub partitions: Vec<PartitionUsage>,
+}
+
+/// Storage usage and lag of a single partition
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct PartitionUsage {
+    pub replica: ReplicaKey,
+    pub leader: SpuId,
+    pub log_start_offset: i64,
+    pub hw: i64,
+    pub leo: i64,
+    /// size on disk of leader replica, negative if unknown
+    pub size: i64,
+    pub segments: u32,
+    pub followers: Vec<FollowerLag>,
+    pub consumers: Vec<ConsumerLag>,
+}
+
+/// How far a follower is behind the leader
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct FollowerLag {
+    pub spu: SpuId,
+    pub leo: i64,
+    pub records: i64,
+    /// estimated from the average record size of the partition
+    pub bytes: i64,
+}
+
+/// How far a consumer is behind the high watermark
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerLag {
+    pub consumer_id: String,
+    pub offset: i64,
+    pub records: i64,
+    pub modified_time: u64,
+}
+
+impl PartitionUsage {
+    /// average bytes per record, `None` if size is unknown or partition is empty
+    pub fn average_record_size(&self) -> Option<i64> {
+        let records = self.leo - self.log_start_offset;
+        if self.size < 0 || records <= 0 {
+            None
+        } else {
+            Some(self.size / records)
+        }
+    }
+
+    /// estimated bytes needed to replicate `records`
+    pub fn estimate_bytes(&self, records: i64) -> i64 {
+        self.average_record_size()
+            .map(|size| size * records.max(0))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_estimate_bytes() {
+        let usage = PartitionUsage {
+            log_start_offset: 100,
+            leo: 200,
+            size: 10_000,
+            ..Default::default()
+        };
+        assert_eq!(usage.average_record_size(), Some(100));
+        assert_eq!(usage.estimate_bytes(5), 500);
+        assert_eq!(usage.estimate_bytes(-1), 0);
+
+        let unknown = PartitionUsage { size: -2, ..usage };
+        assert_eq!(unknown.estimate_bytes(5), 0);
+    }
+}
@@ -17,6 +17,7 @@ use fluvio_protocol::core::Decoder;
 use fluvio_protocol::link::versions::ApiVersionsRequest;
 
 use crate::mirroring::ObjectMirroringRequest;
+use crate::partition::PartitionUsageRequest;
 use crate::AdminPublicApiKey;
 use crate::objects::{
     ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiUpdateRequest,
@@ -33,6 +34,7 @@ pub enum AdminPublicDecodedRequest {
     WatchRequest(RequestMessage<ObjectApiWatchRequest>),
     MirroringRequest(RequestMessage<ObjectMirroringRequest>),
     UpdateRequest(RequestMessage<ObjectApiUpdateRequest>),
+    PartitionUsageRequest(RequestMessage<PartitionUsageRequest>),
 }
 
 impl Default for AdminPublicDecodedRequest {
@@ -93,6 +95,9 @@ impl ApiMessage for AdminPublicDecodedRequest {
                 header,
                 ObjectApiUpdateRequest::decode_from(src, version)?,
             ))),
+            AdminPublicApiKey::PartitionUsage => {
+                api_decode!(Self, PartitionUsageRequest, src, header)
+            }
         }
     }
 }
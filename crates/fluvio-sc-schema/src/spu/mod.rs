@@ -1,5 +1,13 @@
-pub use fluvio_controlplane_metadata::spu::{SpuSpec};
+pub use fluvio_controlplane_metadata::spu::{SpuSpec, SpuDrain, SpuDrainStatus, UpdateSpuAction};
 
-use crate::{AdminSpec};
+use fluvio_types::SpuId;
+
+use crate::{AdminSpec, UpdatableAdminSpec};
 
 impl AdminSpec for SpuSpec {}
+
+/// SPUs are updated by id
+impl UpdatableAdminSpec for SpuSpec {
+    type UpdateKey = SpuId;
+    type UpdateAction = UpdateSpuAction;
+}
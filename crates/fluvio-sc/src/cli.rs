@@ -8,10 +8,12 @@
 //!     3) cli parameters
 //!
 
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::process;
 use std::path::PathBuf;
 use std::convert::TryFrom;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
 use clap::Args;
@@ -22,6 +24,7 @@ use clap::Parser;
 use fluvio_types::print_cli_err;
 use fluvio_types::defaults::TLS_SERVER_SECRET_NAME;
 use fluvio_future::rust_tls::TlsAcceptor;
+use fluvio_stream_dispatcher::metadata::raft::{NodeId, RaftConfig, RaftTls};
 
 use crate::services::auth::basic::BasicRbacPolicy;
 use crate::config::ScConfig;
@@ -67,6 +70,49 @@ pub struct ScOpt {
     /// only allow white list of controllers
     #[arg(long)]
     white_list: Vec<String>,
+
+    /// address to serve OpenMetrics at `/metrics`, disabled if not set
+    #[arg(long, value_name = "host:port", env = "FLV_METRICS_ADDR")]
+    metrics_addr: Option<String>,
+
+    /// seconds between moving partition leaders back to preferred replicas, 0 disables
+    #[arg(long, value_name = "seconds", env = "FLV_LEADER_REBALANCE_INTERVAL")]
+    leader_rebalance_interval: Option<u64>,
+
+    /// percentage of partitions preferring a SPU but led by others, above which leaders are rebalanced
+    #[arg(
+        long,
+        value_name = "percent",
+        value_parser = clap::value_parser!(u8).range(0..=100),
+        env = "FLV_LEADER_IMBALANCE_THRESHOLD"
+    )]
+    leader_imbalance_threshold: Option<u8>,
+
+    /// id of this SC in replicated metadata cluster, must be one of raft peers
+    #[arg(long, requires = "raft_peer", env = "FLV_RAFT_ID")]
+    raft_id: Option<u64>,
+
+    /// member of replicated metadata cluster, including this SC.
+    /// Local metadata is replicated across members when set
+    #[arg(
+        long,
+        value_name = "id=host:port",
+        requires_all = ["raft_id", "local"],
+        value_delimiter = ',',
+        env = "FLV_RAFT_PEERS"
+    )]
+    raft_peer: Vec<String>,
+
+    /// public endpoint of member of replicated metadata cluster.
+    /// Client connections to a standby SC are forwarded to the leader's public endpoint
+    #[arg(
+        long,
+        value_name = "id=host:port",
+        requires = "raft_id",
+        value_delimiter = ',',
+        env = "FLV_RAFT_PUBLIC_PEERS"
+    )]
+    raft_public_peer: Vec<String>,
 }
 
 #[derive(Debug, Args)]
@@ -106,6 +152,28 @@ impl ScOpt {
         }
     }
 
+    /// configuration of replicated local metadata, if enabled.
+    /// With TLS, members use SC certificates to authenticate each other.
+    pub fn raft_config(&self) -> Result<Option<RaftConfig>> {
+        let Some(id) = self.raft_id else {
+            return Ok(None);
+        };
+        let members = RaftConfig::parse_members(&self.raft_peer)?;
+        if !members.contains_key(&id) {
+            return Err(anyhow!("raft id {id} is not one of raft peers"));
+        }
+        let mut config = RaftConfig::new(id, members);
+        if self.tls.tls {
+            config.tls = Some(self.tls.try_build_raft_tls()?);
+        }
+        Ok(Some(config))
+    }
+
+    /// public endpoints of replicated metadata members, by raft id
+    pub fn raft_public_endpoints(&self) -> Result<BTreeMap<NodeId, String>> {
+        RaftConfig::parse_members(&self.raft_public_peer)
+    }
+
     /// as sc configuration, 2nd part of tls configuration(proxy addr, tls config)
     /// 3rd part is path to read only metadata config
     #[allow(clippy::wrong_self_convention)]
@@ -128,6 +196,16 @@ impl ScOpt {
         config.x509_auth_scopes = self.x509_auth_scopes;
         config.white_list = self.white_list.into_iter().collect();
         config.read_only_metadata = self.run_mode.read_only.is_some();
+        config.metrics_endpoint = self.metrics_addr;
+
+        if let Some(interval) = self.leader_rebalance_interval {
+            config.leader_rebalance_interval =
+                (interval > 0).then_some(Duration::from_secs(interval));
+        }
+
+        if let Some(threshold) = self.leader_imbalance_threshold {
+            config.leader_imbalance_threshold = threshold;
+        }
 
         // Set Configuration Authorization Policy
 
@@ -236,4 +314,35 @@ impl TlsConfig {
 
         Ok(acceptor)
     }
+
+    /// TLS between replicated metadata members, server certificate is used as client certificate
+    /// and peers are always authenticated with ca cert
+    pub fn try_build_raft_tls(&self) -> Result<RaftTls> {
+        let server_crt_path = self
+            .server_cert
+            .as_ref()
+            .ok_or_else(|| anyhow!("missing server cert"))?;
+        let server_key_path = self
+            .server_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("missing server key"))?;
+        let ca_path = self
+            .ca_cert
+            .as_ref()
+            .ok_or_else(|| anyhow!("missing ca cert, required to authenticate raft peers"))?;
+
+        let acceptor = fluvio_future::rust_tls::AcceptorBuilder::with_safe_defaults()
+            .client_authenticate(ca_path)?
+            .load_server_certs(server_crt_path, server_key_path)?
+            .build();
+        let connector = fluvio_future::rust_tls::ConnectorBuilder::with_safe_defaults()
+            .load_ca_cert(ca_path)?
+            .load_client_certs(server_crt_path, server_key_path)?
+            .build();
+
+        Ok(RaftTls {
+            acceptor,
+            connector,
+        })
+    }
 }
@@ -4,12 +4,15 @@
 //! Stores configuration parameter used by Streaming Controller module.
 //!
 use std::collections::HashSet;
+use std::time::Duration;
 use std::{io::Error as IoError, path::PathBuf};
 
 use fluvio_types::defaults::SC_PUBLIC_PORT;
 use fluvio_types::defaults::SC_PRIVATE_PORT;
 
 pub const DEFAULT_NAMESPACE: &str = "default";
+pub const DEFAULT_LEADER_REBALANCE_INTERVAL: Duration = Duration::from_secs(300);
+pub const DEFAULT_LEADER_IMBALANCE_THRESHOLD: u8 = 10;
 
 // -----------------------------------
 // Traits
@@ -29,6 +32,12 @@ pub struct ScConfig {
     pub namespace: String,
     pub x509_auth_scopes: Option<PathBuf>,
     pub white_list: HashSet<String>,
+    /// OpenMetrics http endpoint, disabled if not set
+    pub metrics_endpoint: Option<String>,
+    /// interval of moving leaders back to preferred replicas, disabled if not set
+    pub leader_rebalance_interval: Option<Duration>,
+    /// percentage of partitions preferring SPU but led by others, above which SPU is rebalanced
+    pub leader_imbalance_threshold: u8,
 }
 
 impl ::std::default::Default for ScConfig {
@@ -40,6 +49,9 @@ impl ::std::default::Default for ScConfig {
             namespace: DEFAULT_NAMESPACE.to_owned(),
             x509_auth_scopes: None,
             white_list: HashSet::new(),
+            metrics_endpoint: None,
+            leader_rebalance_interval: Some(DEFAULT_LEADER_REBALANCE_INTERVAL),
+            leader_imbalance_threshold: DEFAULT_LEADER_IMBALANCE_THRESHOLD,
         }
     }
 }
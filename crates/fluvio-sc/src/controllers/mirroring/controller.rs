@@ -9,7 +9,7 @@ use adaptive_backoff::prelude::{
 use fluvio::config::TlsPolicy;
 use fluvio_socket::{AsyncResponse, ClientConfig, MultiplexerSocket, StreamSocket};
 use futures_util::StreamExt;
-use fluvio_future::{net::DomainConnector, task::spawn, timer::sleep};
+use fluvio_future::{net::DomainConnector, timer::sleep};
 use fluvio_sc_schema::{
     core::MetadataItem,
     mirror::{ConnectionStatus, Home, MirrorPairStatus, MirrorSpec, MirrorStatus, MirrorType},
@@ -22,7 +22,7 @@ use fluvio_controlplane_metadata::mirroring::{
     MirrorConnect, MirroringRemoteClusterRequest, MirroringSpecWrapper,
 };
 
-use crate::core::SharedContext;
+use crate::core::{SharedContext, spawn_until_shutdown};
 
 const MIRRORING_CONTROLLER_INTERVAL: u64 = 1;
 
@@ -41,7 +41,7 @@ impl<C: MetadataItem> RemoteMirrorController<C> {
         };
 
         info!("starting mirroring controller");
-        spawn(controller.dispatch_loop());
+        spawn_until_shutdown(ctx.shutdown().clone(), controller.dispatch_loop());
     }
 
     #[instrument(skip(self), name = "MirroringControllerLoop")]
@@ -222,6 +222,14 @@ impl<C: MetadataItem> RemoteMirrorController<C> {
         };
 
         info!(home_spec.source, "home is source");
+
+        // smartmodules configured on home for this remote
+        let transforms = home_spec
+            .partitions()
+            .iter()
+            .find(|partition| partition.remote_cluster == home.remote_id)
+            .map(|partition| partition.transforms.clone())
+            .unwrap_or_default();
         // Create a new replica spec for the topic
         let new_replica: ReplicaSpec =
             ReplicaSpec::Mirror(MirrorConfig::Remote(RemoteMirrorConfig {
@@ -235,6 +243,8 @@ impl<C: MetadataItem> RemoteMirrorController<C> {
                 ],
                 home_cluster: home.id.clone(),
                 target: home_spec.source,
+                bidirectional: home_spec.bidirectional,
+                transforms,
             }));
 
         // Check if the topic already exists
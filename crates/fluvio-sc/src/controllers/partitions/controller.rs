@@ -2,16 +2,18 @@
 //! # Partition Controller
 //!
 
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use fluvio_controlplane_metadata::store::ChangeListener;
 use fluvio_future::timer::sleep;
 use tracing::{debug, trace, info, error, instrument};
 
-use fluvio_future::task::spawn;
 use fluvio_controlplane_metadata::core::MetadataItem;
 use fluvio_controlplane_metadata::store::k8::K8MetaItem;
+use fluvio_types::event::StickyEvent;
 
+use crate::core::{SharedScMetrics, spawn_until_shutdown};
 use crate::stores::StoreContext;
 use crate::stores::partition::PartitionSpec;
 use crate::stores::spu::SpuSpec;
@@ -24,20 +26,28 @@ pub struct PartitionController<C: MetadataItem = K8MetaItem> {
     partitions: StoreContext<PartitionSpec, C>,
     spus: StoreContext<SpuSpec, C>,
     reducer: PartitionReducer<C>,
+    metrics: SharedScMetrics,
 }
 
 impl<C> PartitionController<C>
 where
     C: MetadataItem + 'static,
 {
-    pub fn start(partitions: StoreContext<PartitionSpec, C>, spus: StoreContext<SpuSpec, C>) {
+    pub fn start(
+        partitions: StoreContext<PartitionSpec, C>,
+        spus: StoreContext<SpuSpec, C>,
+        metrics: SharedScMetrics,
+        shutdown: Arc<StickyEvent>,
+    ) {
         let controller = Self {
-            reducer: PartitionReducer::new(partitions.store().clone(), spus.store().clone()),
+            reducer: PartitionReducer::new(partitions.store().clone(), spus.store().clone())
+                .with_metrics(metrics.clone()),
             partitions,
             spus,
+            metrics,
         };
 
-        spawn(controller.dispatch_loop());
+        spawn_until_shutdown(shutdown, controller.dispatch_loop());
     }
 }
 
@@ -104,15 +114,18 @@ where
             return;
         }
 
+        let start = Instant::now();
         let (updates, _) = changes.parts();
         trace!(meta_changes = &*format!("{updates:#?}"), "metadata changes");
 
         let actions = self.reducer.process_partition_update(updates).await;
 
         debug!("generated partition actions: {}", actions.len());
+        let count = actions.len();
         for action in actions.into_iter() {
             self.partitions.send_action(action).await;
         }
+        self.metrics.partition().record(count, start.elapsed());
     }
 
     /// sync spu states to partition
@@ -129,6 +142,7 @@ where
             return;
         }
 
+        let start = Instant::now();
         let epoch = changes.epoch;
         let (updates, deletes) = changes.parts();
         debug!(
@@ -144,9 +158,11 @@ where
             .await;
 
         debug!("there were election actions: {}", actions.len());
+        let count = actions.len();
         for action in actions.into_iter() {
             self.partitions.send_action(action).await;
         }
+        self.metrics.partition().record(count, start.elapsed());
     }
 }
 
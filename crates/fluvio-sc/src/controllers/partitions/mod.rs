@@ -1,7 +1,10 @@
 mod controller;
 mod reducer;
+mod rebalance;
 
 pub use self::controller::*;
+pub use self::rebalance::*;
+pub(crate) use self::reducer::PartitionReducer;
 pub use common::*;
 
 mod common {
@@ -0,0 +1,81 @@
+//!
+//! # Leader Rebalance Controller
+//!
+//! Leaders move away from preferred replicas when SPUs restart.
+//! Periodically moves leadership back once preferred replicas are in sync.
+//!
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use fluvio_future::timer::sleep;
+use tracing::{debug, info, instrument};
+
+use fluvio_controlplane_metadata::core::MetadataItem;
+use fluvio_controlplane_metadata::store::k8::K8MetaItem;
+use fluvio_types::event::StickyEvent;
+
+use crate::core::{SharedScMetrics, spawn_until_shutdown};
+use crate::stores::StoreContext;
+use crate::stores::partition::PartitionSpec;
+use crate::stores::spu::SpuSpec;
+
+use super::reducer::PartitionReducer;
+
+#[derive(Debug)]
+pub struct LeaderRebalanceController<C: MetadataItem = K8MetaItem> {
+    partitions: StoreContext<PartitionSpec, C>,
+    reducer: PartitionReducer<C>,
+    interval: Duration,
+    imbalance_threshold: u8,
+    metrics: SharedScMetrics,
+}
+
+impl<C> LeaderRebalanceController<C>
+where
+    C: MetadataItem + 'static,
+{
+    pub fn start(
+        partitions: StoreContext<PartitionSpec, C>,
+        spus: StoreContext<SpuSpec, C>,
+        interval: Duration,
+        imbalance_threshold: u8,
+        metrics: SharedScMetrics,
+        shutdown: Arc<StickyEvent>,
+    ) {
+        let controller = Self {
+            reducer: PartitionReducer::new(partitions.store().clone(), spus.store().clone()),
+            partitions,
+            interval,
+            imbalance_threshold,
+            metrics,
+        };
+
+        spawn_until_shutdown(shutdown, controller.dispatch_loop());
+    }
+
+    #[instrument(skip(self), name = "LeaderRebalanceController")]
+    async fn dispatch_loop(self) {
+        info!(
+            interval = ?self.interval,
+            imbalance_threshold = self.imbalance_threshold,
+            "started"
+        );
+        loop {
+            sleep(self.interval).await;
+
+            let start = Instant::now();
+            let actions = self
+                .reducer
+                .rebalance_leaders(self.imbalance_threshold)
+                .await;
+
+            debug!("leader rebalance actions: {}", actions.len());
+            let count = actions.len();
+            for action in actions.into_iter() {
+                self.partitions.send_action(action).await;
+            }
+            self.metrics.partition().record(count, start.elapsed());
+        }
+    }
+}
@@ -3,14 +3,18 @@
 //!
 //! Partition metadata information on cached in the local Controller.
 //!
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use fluvio_controlplane::PartitionMetadata;
-use tracing::{debug, info, instrument};
+use fluvio_controlplane_metadata::partition::{ReplicaKey, UncleanElection};
+use fluvio_types::SpuId;
+use tracing::{debug, info, warn, instrument};
 
 use fluvio_controlplane_metadata::store::k8::K8MetaItem;
 use fluvio_controlplane_metadata::core::MetadataItem;
 
+use crate::core::{ScMetrics, SharedScMetrics};
 use crate::stores::partition::{
     PartitionSpec, PartitionResolution, PartitionLocalStore, SimplePolicy, PartitonStatusExtension,
     ElectionPolicy,
@@ -58,6 +62,7 @@ type PartitionWSAction<C = K8MetaItem> = WSAction<PartitionSpec, C>;
 pub struct PartitionReducer<C: MetadataItem = K8MetaItem> {
     partition_store: Arc<PartitionLocalStore<C>>,
     spu_store: Arc<SpuLocalStore<C>>,
+    metrics: SharedScMetrics,
 }
 
 impl<C: MetadataItem> Default for PartitionReducer<C> {
@@ -65,6 +70,7 @@ impl<C: MetadataItem> Default for PartitionReducer<C> {
         Self {
             partition_store: PartitionLocalStore::new_shared(),
             spu_store: SpuLocalStore::new_shared(),
+            metrics: ScMetrics::shared(),
         }
     }
 }
@@ -77,9 +83,16 @@ impl<C: MetadataItem> PartitionReducer<C> {
         Self {
             partition_store: partition_store.into(),
             spu_store: spu_store.into(),
+            metrics: ScMetrics::shared(),
         }
     }
 
+    /// record elections into shared metrics
+    pub fn with_metrics(mut self, metrics: SharedScMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
     #[instrument(skip(self, updates))]
     pub async fn process_partition_update(
         &self,
@@ -146,6 +159,8 @@ impl<C: MetadataItem> PartitionReducer<C> {
         let offline_leader_spu_id = offline_spu.spec.id;
 
         let spu_status = self.spu_store.online_status().await;
+        let draining = self.spu_store.draining_spu_ids().await;
+        let non_draining: HashSet<SpuId> = spu_status.difference(&draining).copied().collect();
 
         let policy = SimplePolicy::new();
 
@@ -154,12 +169,17 @@ impl<C: MetadataItem> PartitionReducer<C> {
             let partition_kv = partition_kv_epoch.inner();
             // find partition who's leader is same as offline spu
             if partition_kv.spec.leader == offline_leader_spu_id {
-                // find suitable leader
-                if let Some(candidate_leader) =
-                    partition_kv.status.candidate_leader(&spu_status, &policy)
+                // find suitable leader, draining spus only if there is no other choice
+                if let Some(candidate_leader) = partition_kv
+                    .status
+                    .candidate_leader(&non_draining, &policy)
+                    .or_else(|| partition_kv.status.candidate_leader(&spu_status, &policy))
                 {
                     let mut part_kv_change = partition_kv.clone();
-                    part_kv_change.spec.leader = candidate_leader;
+                    part_kv_change.spec.change_leader(
+                        candidate_leader,
+                        partition_kv.status.replica_leo(candidate_leader),
+                    );
 
                     // we only change leader, status happens next cycle
                     actions.push(PartitionWSAction::UpdateSpec((
@@ -174,6 +194,8 @@ impl<C: MetadataItem> PartitionReducer<C> {
                     );
 
                 // change the
+                } else if let Some(unclean) = self.unclean_election(partition_kv, &spu_status) {
+                    actions.extend(unclean);
                 } else {
                     // check partition is already offline
                     if partition_kv.status.is_online() {
@@ -209,6 +231,7 @@ impl<C: MetadataItem> PartitionReducer<C> {
     ) {
         debug!(spu = %online_spu.key(),"performing election check spu online");
         let online_leader_spu_id = online_spu.spec.id;
+        let spu_status = self.spu_store.online_status().await;
 
         let policy = SimplePolicy::new();
         // go thru each partitions which are not online and try to promote given online spu
@@ -217,6 +240,7 @@ impl<C: MetadataItem> PartitionReducer<C> {
             let partition_kv = partition_kv_epoch.inner();
             if !partition_kv.status.is_readable() {
                 if partition_kv.spec.leader != online_leader_spu_id {
+                    let mut elected = false;
                     // switch leader if online leader is different
                     for replica_status in partition_kv.status.replica_iter() {
                         if replica_status.spu == online_leader_spu_id
@@ -225,7 +249,9 @@ impl<C: MetadataItem> PartitionReducer<C> {
                                 .is_suitable()
                         {
                             let mut part_kv_change = partition_kv.clone();
-                            part_kv_change.spec.leader = online_leader_spu_id;
+                            part_kv_change
+                                .spec
+                                .change_leader(online_leader_spu_id, replica_status.leo);
                             actions.push(PartitionWSAction::UpdateSpec((
                                 part_kv_change.key_owned(),
                                 part_kv_change.spec,
@@ -235,6 +261,16 @@ impl<C: MetadataItem> PartitionReducer<C> {
                                 online_spu = online_leader_spu_id,
                                 "changing to new leader",
                             );
+                            elected = true;
+                        }
+                    }
+
+                    // no in sync replica survived, spu which came back may take over if allowed
+                    if !elected && !spu_status.contains(&partition_kv.spec.leader) {
+                        if let Some(unclean) = self
+                            .unclean_election(partition_kv, &HashSet::from([online_leader_spu_id]))
+                        {
+                            actions.extend(unclean);
                         }
                     }
                 } else {
@@ -258,6 +294,305 @@ impl<C: MetadataItem> PartitionReducer<C> {
     }
 }
 
+impl<C: MetadataItem> PartitionReducer<C> {
+    /// elect most caught up live replica when partition allows unclean leader election.
+    /// Other replicas truncate records beyond log end offset of new leader,
+    /// lost records are recorded in partition status so it is visible to clients
+    fn unclean_election(
+        &self,
+        partition: &PartitionMetadata<C>,
+        online: &HashSet<SpuId>,
+    ) -> Option<[PartitionWSAction<C>; 2]> {
+        if !partition.spec.unclean_leader_election {
+            return None;
+        }
+        let (candidate_leader, lost_records) = partition.status.unclean_candidate_leader(online)?;
+
+        warn!(
+            partition = %partition.key(),
+            old_leader = partition.spec.leader,
+            candidate_leader,
+            lost_records,
+            "unclean leader election, replicas will truncate records not replicated to new leader",
+        );
+        self.metrics.record_unclean_election(lost_records);
+
+        let mut spec = partition.spec.clone();
+        spec.change_leader(
+            candidate_leader,
+            partition.status.replica_leo(candidate_leader),
+        );
+        let mut status = partition.status.clone();
+        status.unclean_election = Some(UncleanElection {
+            previous_leader: partition.spec.leader,
+            leader: candidate_leader,
+            lost_records,
+        });
+        Some([
+            PartitionWSAction::UpdateSpec((partition.key_owned(), spec)),
+            PartitionWSAction::UpdateStatus((partition.key_owned(), status)),
+        ])
+    }
+
+    /// move leadership back to preferred replica of partitions selected by filter
+    #[instrument(skip(self, filter))]
+    pub async fn elect_preferred_leaders<F>(&self, filter: F) -> Vec<PartitionWSAction<C>>
+    where
+        F: Fn(&PartitionMetadata<C>) -> bool,
+    {
+        // draining spus can't become leader
+        let mut spu_status = self.spu_store.online_status().await;
+        let draining = self.spu_store.draining_spu_ids().await;
+        spu_status.retain(|spu| !draining.contains(spu));
+        let mut actions = vec![];
+
+        for partition_kv_epoch in self.partition_store.read().await.values() {
+            let partition_kv = partition_kv_epoch.inner();
+            if !filter(partition_kv) {
+                continue;
+            }
+
+            if let Some(preferred_leader) = preferred_candidate(partition_kv, &spu_status) {
+                let mut part_kv_change = partition_kv.clone();
+                part_kv_change.spec.change_leader(
+                    preferred_leader,
+                    partition_kv.status.replica_leo(preferred_leader),
+                );
+                actions.push(PartitionWSAction::UpdateSpec((
+                    part_kv_change.key_owned(),
+                    part_kv_change.spec,
+                )));
+                info!(
+                    partition = %partition_kv.key(),
+                    old_leader = partition_kv.spec.leader,
+                    preferred_leader,
+                    "moving leader to preferred replica",
+                );
+            }
+        }
+        actions
+    }
+
+    /// elect preferred leaders for SPUs which lead less than their share of partitions.
+    /// SPU is imbalanced if percentage of partitions preferring it but led by other SPUs
+    /// exceeds threshold
+    #[instrument(skip(self))]
+    pub async fn rebalance_leaders(&self, imbalance_threshold: u8) -> Vec<PartitionWSAction<C>> {
+        // preferred spu => (preferred, not led by preferred)
+        let mut leadership: HashMap<SpuId, (usize, usize)> = HashMap::new();
+        for partition_kv_epoch in self.partition_store.read().await.values() {
+            let partition = partition_kv_epoch.inner();
+            if let Some(preferred_leader) = partition.spec.preferred_leader() {
+                let entry = leadership.entry(preferred_leader).or_default();
+                entry.0 += 1;
+                if partition.spec.leader != preferred_leader {
+                    entry.1 += 1;
+                }
+            }
+        }
+
+        let imbalanced: HashSet<SpuId> = leadership
+            .into_iter()
+            .filter(|(spu, (preferred, not_led))| {
+                let imbalanced = *not_led * 100 > *preferred * imbalance_threshold as usize;
+                if imbalanced {
+                    debug!(spu, preferred, not_led, "leader imbalance");
+                }
+                imbalanced
+            })
+            .map(|(spu, _)| spu)
+            .collect();
+
+        if imbalanced.is_empty() {
+            return vec![];
+        }
+
+        self.elect_preferred_leaders(|partition| {
+            partition
+                .spec
+                .preferred_leader()
+                .is_some_and(|preferred| imbalanced.contains(&preferred))
+        })
+        .await
+    }
+}
+
+impl<C: MetadataItem> PartitionReducer<C> {
+    /// move leadership off draining spus and migrate replicas of spus draining with migration.
+    /// Replica is migrated by first adding replacement replica, and once it is in sync,
+    /// removing replica of draining spu. Replication factor is number of replicas assigned to partition by topic
+    #[instrument(skip(self, replication_factors))]
+    pub async fn drain_spus(
+        &self,
+        replication_factors: &HashMap<ReplicaKey, usize>,
+    ) -> Vec<PartitionWSAction<C>> {
+        let mut draining = HashSet::new();
+        let mut migrating = HashSet::new();
+        let mut schedulable = HashSet::new();
+        for spu in self.spu_store.read().await.values() {
+            if spu.spec.is_draining() {
+                draining.insert(spu.spec.id);
+                if spu.spec.is_migrating_replicas() {
+                    migrating.insert(spu.spec.id);
+                }
+            } else if spu.status.is_online() {
+                schedulable.insert(spu.spec.id);
+            }
+        }
+
+        if draining.is_empty() {
+            return vec![];
+        }
+
+        let partitions = self.partition_store.read().await;
+
+        // number of replicas per spu, replacements go to least loaded spus
+        let mut replica_count: HashMap<SpuId, usize> =
+            schedulable.iter().map(|spu| (*spu, 0)).collect();
+        for partition in partitions.values() {
+            for replica in &partition.spec.replicas {
+                if let Some(count) = replica_count.get_mut(replica) {
+                    *count += 1;
+                }
+            }
+        }
+
+        let mut actions = vec![];
+        for partition_kv_epoch in partitions.values() {
+            let partition = partition_kv_epoch.inner();
+            if partition.status.is_being_deleted || partition.ctx().item().is_being_deleted() {
+                continue;
+            }
+
+            let mut spec = partition.spec.clone();
+
+            if draining.contains(&spec.leader) {
+                if let Some(leader) = drain_leader_candidate(partition, &schedulable) {
+                    info!(
+                        partition = %partition.key(),
+                        old_leader = spec.leader,
+                        leader,
+                        "moving leader off draining spu",
+                    );
+                    spec.change_leader(leader, partition.status.replica_leo(leader));
+                }
+            }
+
+            if spec
+                .replicas
+                .iter()
+                .any(|replica| migrating.contains(replica))
+            {
+                if let Some(replication) = replication_factors.get(partition.key()) {
+                    migrate_replicas(
+                        partition,
+                        &mut spec,
+                        *replication,
+                        &migrating,
+                        &mut replica_count,
+                    );
+                }
+            }
+
+            if spec != partition.spec {
+                actions.push(PartitionWSAction::UpdateSpec((partition.key_owned(), spec)));
+            }
+        }
+
+        actions
+    }
+}
+
+/// in sync replica which can take over leadership from draining spu, in order of replica map
+fn drain_leader_candidate<C: MetadataItem>(
+    partition: &PartitionMetadata<C>,
+    schedulable: &HashSet<SpuId>,
+) -> Option<SpuId> {
+    if !partition.status.is_readable() || partition.status.leader.spu != partition.spec.leader {
+        return None;
+    }
+    let in_sync = partition.status.in_sync_replicas();
+    partition
+        .spec
+        .replicas
+        .iter()
+        .find(|replica| schedulable.contains(replica) && in_sync.contains(replica))
+        .copied()
+}
+
+/// add replacements for replicas on migrating spus,
+/// then remove migrating replicas once replacements are in sync
+fn migrate_replicas<C: MetadataItem>(
+    partition: &PartitionMetadata<C>,
+    spec: &mut PartitionSpec,
+    replication: usize,
+    migrating: &HashSet<SpuId>,
+    replica_count: &mut HashMap<SpuId, usize>,
+) {
+    let remaining: Vec<SpuId> = spec
+        .replicas
+        .iter()
+        .filter(|replica| !migrating.contains(replica))
+        .copied()
+        .collect();
+
+    if remaining.len() < replication {
+        for _ in remaining.len()..replication {
+            let Some(target) = replica_count
+                .iter()
+                .filter(|(spu, _)| !spec.replicas.contains(*spu))
+                .min_by_key(|(spu, count)| (*count, *spu))
+                .map(|(spu, _)| *spu)
+            else {
+                debug!(partition = %partition.key(), "no spu available for replica migration");
+                break;
+            };
+            info!(partition = %partition.key(), target, "adding replica to replace draining spu");
+            spec.replicas.push(target);
+            if let Some(count) = replica_count.get_mut(&target) {
+                *count += 1;
+            }
+        }
+        return;
+    }
+
+    // replacements must be in sync and leadership moved before draining replicas are removed
+    let in_sync = partition.status.in_sync_replicas();
+    if partition.spec.leader != spec.leader
+        || migrating.contains(&spec.leader)
+        || partition.status.leader.spu != spec.leader
+        || !remaining.iter().all(|replica| in_sync.contains(replica))
+    {
+        return;
+    }
+
+    info!(partition = %partition.key(), replicas = ?remaining, "removing replicas of draining spus");
+    spec.replicas = remaining;
+}
+
+/// preferred replica if leadership can be moved to it.
+/// Preferred replica must be online and in sync with current leader which is serving partition
+fn preferred_candidate<C: MetadataItem>(
+    partition: &PartitionMetadata<C>,
+    online_spus: &HashSet<SpuId>,
+) -> Option<SpuId> {
+    let preferred_leader = partition.spec.preferred_leader()?;
+    if partition.spec.leader == preferred_leader
+        || partition.status.is_being_deleted
+        || partition.ctx().item().is_being_deleted()
+        || !partition.status.is_readable()
+        || partition.status.leader.spu != partition.spec.leader
+        || !online_spus.contains(&preferred_leader)
+        || !partition
+            .status
+            .in_sync_replicas()
+            .contains(&preferred_leader)
+    {
+        return None;
+    }
+    Some(preferred_leader)
+}
+
 // -----------------------------------
 //  Unit Tests
 //      >> utils::init_logger();
@@ -267,6 +602,216 @@ impl<C: MetadataItem> PartitionReducer<C> {
 #[cfg(test)]
 pub mod test {
 
+    use std::collections::HashMap;
+
+    use fluvio_controlplane_metadata::partition::{
+        ReplicaKey, ReplicaStatus, PartitionStatus, UncleanElection,
+    };
+    use fluvio_controlplane_metadata::spu::SpuDrain;
+    use fluvio_types::SpuId;
+
+    use crate::stores::partition::{PartitionSpec, PartitionResolution, DefaultPartitionStore};
+    use crate::stores::spu::{SpuLocalStore, SpuMd, SpuMetadata};
+    use crate::stores::actions::WSAction;
+
+    use crate::core::ScMetrics;
+
+    use super::{PartitionReducer, PartitionMetadata};
+
+    /// online partition with current leader and in sync followers
+    fn partition(
+        key: (&str, u32),
+        replicas: Vec<SpuId>,
+        leader: SpuId,
+        in_sync: Vec<SpuId>,
+    ) -> PartitionMetadata<u32> {
+        let mut leader_status = ReplicaStatus::new(leader, 10, 10);
+        leader_status.in_sync = true;
+        let followers = replicas
+            .iter()
+            .filter(|spu| **spu != leader)
+            .map(|spu| {
+                let mut status = ReplicaStatus::new(*spu, 10, 10);
+                status.in_sync = in_sync.contains(spu);
+                status
+            })
+            .collect();
+        let mut status = PartitionStatus::new(leader_status, followers);
+        status.resolution = PartitionResolution::Online;
+
+        PartitionMetadata::new(key, PartitionSpec::new(leader, replicas), status)
+    }
+
+    fn reducer(partitions: Vec<PartitionMetadata<u32>>) -> PartitionReducer<u32> {
+        let spus: Vec<SpuMetadata<u32>> = (0..3)
+            .map(|id| SpuMetadata::quick((format!("spu-{id}"), id, id != 2, None)))
+            .collect();
+        PartitionReducer::new(
+            DefaultPartitionStore::bulk_new(partitions),
+            SpuLocalStore::bulk_new(spus),
+        )
+    }
+
+    fn new_leaders(actions: Vec<WSAction<PartitionSpec, u32>>) -> Vec<(ReplicaKey, SpuId)> {
+        let mut leaders: Vec<_> = actions
+            .into_iter()
+            .map(|action| match action {
+                WSAction::UpdateSpec((key, spec)) => (key, spec.leader),
+                _ => panic!("unexpected action"),
+            })
+            .collect();
+        leaders.sort_by_key(|(key, _)| key.to_string());
+        leaders
+    }
+
+    #[fluvio_future::test]
+    async fn test_elect_preferred_leaders() {
+        let reducer = reducer(vec![
+            // already led by preferred replica
+            partition(("t1", 0), vec![0, 1], 0, vec![1]),
+            // preferred replica in sync
+            partition(("t1", 1), vec![0, 1], 1, vec![0]),
+            // preferred replica out of sync
+            partition(("t1", 2), vec![1, 0], 0, vec![]),
+            // preferred replica offline
+            partition(("t2", 0), vec![2, 0], 0, vec![2]),
+        ]);
+
+        let actions = reducer.elect_preferred_leaders(|_| true).await;
+        assert_eq!(new_leaders(actions), vec![(("t1", 1).into(), 0)]);
+
+        let actions = reducer
+            .elect_preferred_leaders(|partition| partition.key().topic == "t2")
+            .await;
+        assert!(actions.is_empty());
+    }
+
+    #[fluvio_future::test]
+    async fn test_rebalance_leaders_with_threshold() {
+        // spu 0 prefers 4 partitions and leads 3 of them, spu 1 prefers 2 and leads none
+        let reducer = reducer(vec![
+            partition(("t1", 0), vec![0, 1], 0, vec![1]),
+            partition(("t1", 1), vec![0, 1], 0, vec![1]),
+            partition(("t1", 2), vec![0, 1], 0, vec![1]),
+            partition(("t1", 3), vec![0, 1], 1, vec![0]),
+            partition(("t2", 0), vec![1, 0], 0, vec![1]),
+            partition(("t2", 1), vec![1, 0], 0, vec![1]),
+        ]);
+
+        let actions = reducer.rebalance_leaders(25).await;
+        assert_eq!(
+            new_leaders(actions),
+            vec![(("t2", 0).into(), 1), (("t2", 1).into(), 1)]
+        );
+
+        let actions = reducer.rebalance_leaders(10).await;
+        assert_eq!(
+            new_leaders(actions),
+            vec![
+                (("t1", 3).into(), 0),
+                (("t2", 0).into(), 1),
+                (("t2", 1).into(), 1)
+            ]
+        );
+
+        assert!(reducer.rebalance_leaders(100).await.is_empty());
+    }
+
+    #[fluvio_future::test]
+    async fn test_unclean_leader_election() {
+        // leader on offline spu 2, follower on spu 0 is 6 records behind
+        let lagging = |topic: &str, unclean_leader_election: bool| {
+            let mut status = PartitionStatus::new(
+                ReplicaStatus::new(2, 10, 10),
+                vec![ReplicaStatus::new(0, 4, 4)],
+            );
+            status.resolution = PartitionResolution::Online;
+            let mut spec = PartitionSpec::new(2, vec![2, 0]);
+            spec.unclean_leader_election = unclean_leader_election;
+            PartitionMetadata::new((topic, 0), spec, status)
+        };
+        let metrics = ScMetrics::shared();
+        let reducer = reducer(vec![lagging("clean", false), lagging("unclean", true)])
+            .with_metrics(metrics.clone());
+
+        let mut offline_spu: SpuMetadata<u32> = SpuMetadata::quick(("spu-2", 2, false, None));
+        offline_spu.status.set_offline();
+        let mut actions = reducer
+            .update_election_from_spu_changes(vec![offline_spu])
+            .await;
+        actions.sort_by_key(|action| match action {
+            WSAction::UpdateSpec((key, _)) | WSAction::UpdateStatus((key, _)) => key.to_string(),
+            _ => panic!("unexpected action"),
+        });
+
+        assert_eq!(actions.len(), 3);
+        assert!(matches!(
+            &actions[0],
+            WSAction::UpdateStatus((_, status)) if status.resolution == PartitionResolution::LeaderOffline
+                && status.unclean_election.is_none()
+        ));
+        assert!(matches!(
+            &actions[1],
+            WSAction::UpdateSpec((_, spec)) if spec.leader == 0
+                && spec.leader_epoch == 1
+                && spec.leader_leo == 4
+        ));
+        assert!(matches!(
+            &actions[2],
+            WSAction::UpdateStatus((_, status)) if status.unclean_election == Some(UncleanElection {
+                previous_leader: 2,
+                leader: 0,
+                lost_records: 6,
+            })
+        ));
+        assert_eq!(metrics.unclean_elections(), 1);
+    }
+
+    #[fluvio_future::test]
+    async fn test_drain_spu_with_replica_migration() {
+        let spus: Vec<SpuMetadata<u32>> = (0..4)
+            .map(|id| {
+                let mut spu = SpuMetadata::quick((format!("spu-{id}"), id, true, None));
+                if id == 0 {
+                    spu.spec.drain = Some(SpuDrain {
+                        migrate_replicas: true,
+                    });
+                }
+                spu
+            })
+            .collect();
+        let reducer = PartitionReducer::new(
+            DefaultPartitionStore::bulk_new(vec![
+                // led by draining spu, replacement not yet added
+                partition(("t1", 0), vec![0, 1], 0, vec![1]),
+                // replacement is in sync
+                partition(("t1", 1), vec![1, 0, 2], 1, vec![0, 2]),
+            ]),
+            SpuLocalStore::bulk_new(spus),
+        );
+        let replication_factors: HashMap<ReplicaKey, usize> =
+            [(("t1", 0).into(), 2), (("t1", 1).into(), 2)].into();
+
+        let mut specs: Vec<_> = reducer
+            .drain_spus(&replication_factors)
+            .await
+            .into_iter()
+            .map(|action| match action {
+                WSAction::UpdateSpec((key, spec)) => (key, spec.leader, spec.replicas),
+                _ => panic!("unexpected action"),
+            })
+            .collect();
+        specs.sort_by_key(|(key, _, _)| key.to_string());
+
+        assert_eq!(
+            specs,
+            vec![
+                (("t1", 0).into(), 1, vec![0, 1, 3]),
+                (("t1", 1).into(), 1, vec![1, 2]),
+            ]
+        );
+    }
+
     /*
     #[fluvio_future::test]
     async fn test_process_partition_actions_without_partitions()  {
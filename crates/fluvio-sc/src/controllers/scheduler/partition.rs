@@ -121,7 +121,8 @@ where
         param: &TopicReplicaParam,
         actual_replica_map: Option<&ReplicaPartitionMap>,
     ) -> ReplicaPartitionMap {
-        let mut online_spus = self.spus.online_spu_ids().await;
+        // draining spus don't get new replicas
+        let mut online_spus = self.spus.schedulable_spu_ids().await;
         online_spus.sort_unstable();
 
         trace!(?online_spus, "online");
@@ -1,16 +1,14 @@
 //!
 //! # Spu Controller
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::io::Error as IoError;
 
 use fluvio_future::timer::sleep;
 use fluvio_stream_model::core::MetadataItem;
 use tracing::{debug, info, error, trace, instrument};
 
-use fluvio_future::task::spawn;
-
-use crate::core::SharedContext;
+use crate::core::{SharedContext, SharedScMetrics, spawn_until_shutdown};
 use crate::stores::StoreContext;
 use crate::stores::spu::*;
 
@@ -19,6 +17,7 @@ use crate::stores::spu::*;
 pub struct SpuController<C: MetadataItem> {
     spus: StoreContext<SpuSpec, C>,
     health_check: SharedHealthCheck,
+    metrics: SharedScMetrics,
     counter: u64, // how many time we have been sync
 }
 
@@ -27,11 +26,12 @@ impl<C: MetadataItem + 'static> SpuController<C> {
         let controller = Self {
             spus: ctx.spus().clone(),
             health_check: ctx.health().clone(),
+            metrics: ctx.metrics().clone(),
             counter: 0,
         };
 
         info!("starting spu controller");
-        spawn(controller.dispatch_loop());
+        spawn_until_shutdown(ctx.shutdown().clone(), controller.dispatch_loop());
     }
 
     #[instrument(skip(self), name = "SpuControllerLoop")]
@@ -77,6 +77,7 @@ impl<C: MetadataItem + 'static> SpuController<C> {
     /// sync spu status with store
     #[instrument(skip(self),fields(counter=self.counter))]
     async fn sync_store(&self) -> Result<(), IoError> {
+        let start = Instant::now();
         // first get status values
         let spus = self.spus.store().clone_values().await;
 
@@ -119,12 +120,14 @@ impl<C: MetadataItem + 'static> SpuController<C> {
 
         drop(health_read);
 
+        let count = changes.len();
         for updated_spu in changes.into_iter() {
             let key = updated_spu.key;
             let status = updated_spu.status;
             debug!(id = updated_spu.spec.id, status = %status, "updating spu status");
             self.spus.update_status(key, status).await?;
         }
+        self.metrics.spu().record(count, start.elapsed());
 
         Ok(())
     }
@@ -0,0 +1,204 @@
+//!
+//! # Spu Drain Controller
+//!
+//! Moves leadership and replicas off draining SPUs and reports drain progress in SPU status.
+//!
+
+use std::collections::HashMap;
+use std::io::Error as IoError;
+use std::time::{Duration, Instant};
+
+use fluvio_future::timer::sleep;
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, error, instrument};
+
+use crate::controllers::partitions::{PartitionReducer, PartitionWSAction};
+use crate::core::{SharedContext, SharedScMetrics, spawn_until_shutdown};
+use crate::stores::StoreContext;
+use crate::stores::partition::{PartitionSpec, ReplicaKey};
+use crate::stores::spu::*;
+use crate::stores::actions::WSAction;
+use crate::stores::topic::{TopicSpec, TopicStatus};
+
+pub struct SpuDrainController<C: MetadataItem> {
+    spus: StoreContext<SpuSpec, C>,
+    partitions: StoreContext<PartitionSpec, C>,
+    topics: StoreContext<TopicSpec, C>,
+    reducer: PartitionReducer<C>,
+    metrics: SharedScMetrics,
+}
+
+impl<C: MetadataItem + 'static> SpuDrainController<C> {
+    pub fn start(ctx: SharedContext<C>) {
+        let controller = Self {
+            spus: ctx.spus().clone(),
+            partitions: ctx.partitions().clone(),
+            topics: ctx.topics().clone(),
+            reducer: PartitionReducer::new(
+                ctx.partitions().store().clone(),
+                ctx.spus().store().clone(),
+            ),
+            metrics: ctx.metrics().clone(),
+        };
+
+        info!("starting spu drain controller");
+        spawn_until_shutdown(ctx.shutdown().clone(), controller.dispatch_loop());
+    }
+
+    #[instrument(skip(self), name = "SpuDrainControllerLoop")]
+    async fn dispatch_loop(self) {
+        info!("started");
+        loop {
+            if let Err(err) = self.inner_loop().await {
+                error!("error with inner loop: {:#?}", err);
+                debug!("sleeping 10 seconds try again");
+                sleep(Duration::from_secs(10)).await;
+            }
+        }
+    }
+
+    async fn inner_loop(&self) -> Result<(), IoError> {
+        use tokio::select;
+
+        debug!("initializing listeners");
+        let mut spu_listener = self.spus.change_listener();
+        let _ = spu_listener.wait_for_initial_sync().await;
+
+        let mut partition_listener = self.partitions.change_listener();
+        let _ = partition_listener.wait_for_initial_sync().await;
+        debug!("finished initializing listeners");
+
+        loop {
+            self.sync_drain().await?;
+
+            select! {
+                _ = spu_listener.listen() => {
+                    debug!("detected changes in spu store");
+                    spu_listener.load_last();
+                },
+                _ = partition_listener.listen() => {
+                    debug!("detected changes in partition store");
+                    partition_listener.load_last();
+                },
+            }
+        }
+    }
+
+    /// move partitions off draining spus and update drain progress
+    async fn sync_drain(&self) -> Result<(), IoError> {
+        let spus = self.spus.store().clone_values().await;
+        if !spus
+            .iter()
+            .any(|spu| spu.spec.is_draining() || spu.status.drain.is_some())
+        {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        let actions = self
+            .reducer
+            .drain_spus(&self.replication_factors().await)
+            .await;
+
+        debug!("drain actions: {}", actions.len());
+        let count = actions.len();
+        let topic_changes = self.migrated_topic_status(&actions).await;
+        for action in actions.into_iter() {
+            self.partitions.send_action(action).await;
+        }
+        for (topic, status) in topic_changes.into_iter() {
+            debug!(%topic, "updating replica map of migrated partitions");
+            self.topics.update_status(topic, status).await?;
+        }
+
+        let partitions = self.partitions.store().read().await;
+        let changes: Vec<_> = spus
+            .into_iter()
+            .filter_map(|spu| {
+                let progress = spu.spec.drain.as_ref().map(|drain| {
+                    let id = spu.spec.id;
+                    let leaders = partitions
+                        .values()
+                        .filter(|partition| partition.spec.leader == id)
+                        .count() as u32;
+                    let replicas = partitions
+                        .values()
+                        .filter(|partition| partition.spec.replicas.contains(&id))
+                        .count() as u32;
+                    SpuDrainStatus {
+                        leaders,
+                        replicas,
+                        complete: leaders == 0 && (!drain.migrate_replicas || replicas == 0),
+                    }
+                });
+                (progress != spu.status.drain).then(|| {
+                    let mut status = spu.status.clone();
+                    status.drain = progress;
+                    (spu.key, spu.spec.id, status)
+                })
+            })
+            .collect();
+        drop(partitions);
+
+        for (key, id, status) in changes.into_iter() {
+            if let Some(progress) = &status.drain {
+                info!(id, %progress, "drain progress");
+            }
+            self.spus.update_status(key, status).await?;
+        }
+        self.metrics.drain().record(count, start.elapsed());
+
+        Ok(())
+    }
+
+    /// topic status with replica map following replicas migrated by `actions`
+    async fn migrated_topic_status(
+        &self,
+        actions: &[PartitionWSAction<C>],
+    ) -> Vec<(String, TopicStatus)> {
+        let topics = self.topics.store().read().await;
+        let mut changes: HashMap<String, TopicStatus> = HashMap::new();
+        for action in actions {
+            let WSAction::UpdateSpec((key, spec)) = action else {
+                continue;
+            };
+            let Some(topic) = topics.get(&key.topic) else {
+                continue;
+            };
+            let status = changes
+                .entry(key.topic.clone())
+                .or_insert_with(|| topic.status.clone());
+            if let Some(replicas) = status.replica_map.get_mut(&key.partition) {
+                replicas.clone_from(&spec.replicas);
+            }
+        }
+        changes
+            .into_iter()
+            .filter(|(name, status)| {
+                topics
+                    .get(name)
+                    .is_some_and(|topic| topic.status != *status)
+            })
+            .collect()
+    }
+
+    /// number of replicas assigned by topic to each partition
+    async fn replication_factors(&self) -> HashMap<ReplicaKey, usize> {
+        self.topics
+            .store()
+            .read()
+            .await
+            .values()
+            .flat_map(|topic| {
+                topic
+                    .status
+                    .replica_map
+                    .iter()
+                    .map(|(partition, replicas)| {
+                        (ReplicaKey::new(topic.key(), *partition), replicas.len())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
@@ -1,4 +1,6 @@
 mod actions;
 mod controller;
+mod drain;
 
 pub use self::controller::*;
+pub use self::drain::*;
@@ -5,6 +5,7 @@
 
 use std::cmp::min;
 use std::ops::Add;
+use std::time::Instant;
 
 use fluvio_controlplane_metadata::spu::SpuSpec;
 use fluvio_controlplane_metadata::topic::CleanupPolicy;
@@ -17,9 +18,7 @@ use fluvio_stream_model::store::k8::K8MetaItem;
 use fluvio_types::defaults::{STORAGE_RETENTION_SECONDS, CONSUMER_STORAGE_TOPIC};
 use tracing::{info, instrument, trace, debug};
 
-use fluvio_future::task::spawn;
-
-use crate::core::SharedContext;
+use crate::core::{SharedContext, SharedScMetrics, spawn_until_shutdown};
 use crate::stores::topic::TopicSpec;
 use crate::stores::partition::PartitionSpec;
 use crate::stores::StoreContext;
@@ -37,6 +36,7 @@ pub struct TopicController<C: MetadataItem = K8MetaItem> {
     topics: StoreContext<TopicSpec, C>,
     partitions: StoreContext<PartitionSpec, C>,
     reducer: TopicReducer<C>,
+    metrics: SharedScMetrics,
 }
 
 impl<C> TopicController<C>
@@ -59,9 +59,10 @@ where
             topics,
             partitions,
             spus,
+            metrics: ctx.metrics().clone(),
         };
 
-        spawn(controller.dispatch_loop());
+        spawn_until_shutdown(ctx.shutdown().clone(), controller.dispatch_loop());
     }
 }
 
@@ -112,11 +113,13 @@ impl<C: MetadataItem> TopicController<C> {
             return;
         }
 
+        let start = Instant::now();
         let (updates, _) = changes.parts();
 
         let actions = self.reducer.process_requests(updates).await;
 
-        self.handle_actions(actions).await;
+        let count = self.handle_actions(actions).await;
+        self.metrics.topic().record(count, start.elapsed());
     }
 
     #[instrument(skip(self, listener))]
@@ -140,13 +143,17 @@ impl<C: MetadataItem> TopicController<C> {
             return;
         };
 
+        let start = Instant::now();
         let actions = self.reducer.process_spu_update().await;
 
-        self.handle_actions(actions).await;
+        let count = self.handle_actions(actions).await;
+        self.metrics.topic().record(count, start.elapsed());
     }
 
-    async fn handle_actions(&mut self, actions: TopicActions<C>) {
-        if actions.topics.is_empty() && actions.partitions.is_empty() {
+    /// send actions to stores, returns number of actions sent
+    async fn handle_actions(&mut self, actions: TopicActions<C>) -> usize {
+        let count = actions.topics.len() + actions.partitions.len();
+        if count == 0 {
             debug!("no actions needed");
         } else {
             debug!(
@@ -162,6 +169,7 @@ impl<C: MetadataItem> TopicController<C> {
                 self.partitions.send_action(action).await;
             }
         }
+        count
     }
 }
 
@@ -180,7 +188,7 @@ where
 
         let controller = Self { topics };
 
-        spawn(controller.dispatch_loop());
+        spawn_until_shutdown(ctx.shutdown().clone(), controller.dispatch_loop());
     }
 
     #[instrument(name = "SystemTopicController", skip(self))]
@@ -223,6 +231,7 @@ where
             spec.set_storage(TopicStorageConfig {
                 segment_size: Some(OFFSET_TOPIC_SEGMENT_SIZE),
                 max_partition_size: Some(OFFSET_TOPIC_PARTITION_SIZE),
+                ..Default::default()
             });
             self.topics
                 .send_action(WSAction::UpdateSpec((
@@ -299,6 +299,8 @@ impl<C: MetadataItem> TopicNextState<C> {
                                             home_cluster: src.home_cluster.clone(),
                                             home_spu_endpoint: spu.endpoint.clone(),
                                             target: src.target,
+                                            bidirectional: src.bidirectional,
+                                            transforms: src.transforms.clone(),
                                         }),
                                     );
                                 }
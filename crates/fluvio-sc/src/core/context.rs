@@ -3,18 +3,26 @@
 //!
 //! Metadata stores a copy of the data from KV store in local memory.
 //!
+use std::future::Future;
 use std::sync::Arc;
 
+use tokio::select;
+use tracing::debug;
+
+use fluvio_future::task::spawn;
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_model::core::MetadataItem;
+use fluvio_types::event::StickyEvent;
 
 use crate::config::ScConfig;
+use crate::core::{ScMetrics, SharedScMetrics};
 use crate::stores::spu::*;
 use crate::stores::partition::*;
 use crate::stores::topic::*;
 use crate::stores::spg::*;
 use crate::stores::smartmodule::*;
 use crate::stores::tableformat::*;
+use crate::stores::dictionary::*;
 use crate::stores::*;
 
 pub type SharedContext<C> = Arc<Context<C>>;
@@ -31,7 +39,12 @@ pub struct Context<C: MetadataItem> {
     smartmodules: StoreContext<SmartModuleSpec, C>,
     tableformats: StoreContext<TableFormatSpec, C>,
     mirrors: StoreContext<MirrorSpec, C>,
+    dictionaries: StoreContext<DictionarySpec, C>,
     health: SharedHealthCheck,
+    consumer_offsets: SharedConsumerOffsets,
+    metrics: SharedScMetrics,
+    /// notified when SC stops serving, e.g. replicated metadata leadership was lost
+    shutdown: Arc<StickyEvent>,
     config: ScConfig,
 }
 
@@ -54,7 +67,11 @@ impl<C: MetadataItem> Context<C> {
             smartmodules: StoreContext::new(),
             tableformats: StoreContext::new(),
             mirrors: StoreContext::new(),
+            dictionaries: StoreContext::new(),
             health: HealthCheck::shared(),
+            consumer_offsets: ConsumerOffsets::shared(),
+            metrics: ScMetrics::shared(),
+            shutdown: StickyEvent::shared(),
             config,
         }
     }
@@ -90,11 +107,30 @@ impl<C: MetadataItem> Context<C> {
         &self.mirrors
     }
 
+    pub fn dictionaries(&self) -> &StoreContext<DictionarySpec, C> {
+        &self.dictionaries
+    }
+
     /// spu health channel
     pub fn health(&self) -> &SharedHealthCheck {
         &self.health
     }
 
+    /// consumer offsets reported by spu
+    pub fn consumer_offsets(&self) -> &SharedConsumerOffsets {
+        &self.consumer_offsets
+    }
+
+    /// controller reconciliation metrics
+    pub fn metrics(&self) -> &SharedScMetrics {
+        &self.metrics
+    }
+
+    /// controllers and services stop once notified
+    pub fn shutdown(&self) -> &Arc<StickyEvent> {
+        &self.shutdown
+    }
+
     /// reference to config
     pub fn config(&self) -> &ScConfig {
         &self.config
@@ -104,3 +140,16 @@ impl<C: MetadataItem> Context<C> {
         &self.config.namespace
     }
 }
+
+/// spawn task which is dropped once `shutdown` is notified
+pub(crate) fn spawn_until_shutdown<F>(shutdown: Arc<StickyEvent>, future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    spawn(async move {
+        select! {
+            _ = future => {},
+            _ = shutdown.listen() => debug!("shutdown, stopping task"),
+        }
+    });
+}
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use fluvio_metrics::{Histogram, MetricType, OpenMetricsEncoder};
+
+pub type SharedScMetrics = Arc<ScMetrics>;
+
+/// Reconciliation activity of SC controllers
+#[derive(Debug, Default)]
+pub struct ScMetrics {
+    topic: ReconcileMetrics,
+    partition: ReconcileMetrics,
+    spu: ReconcileMetrics,
+    drain: ReconcileMetrics,
+    unclean_elections: AtomicU64,
+    unclean_election_lost_records: AtomicU64,
+}
+
+impl ScMetrics {
+    pub fn shared() -> SharedScMetrics {
+        Arc::new(Self::default())
+    }
+
+    pub fn topic(&self) -> &ReconcileMetrics {
+        &self.topic
+    }
+
+    pub fn partition(&self) -> &ReconcileMetrics {
+        &self.partition
+    }
+
+    pub fn spu(&self) -> &ReconcileMetrics {
+        &self.spu
+    }
+
+    pub fn drain(&self) -> &ReconcileMetrics {
+        &self.drain
+    }
+
+    /// record leader elected out of sync replica, which may have lost records
+    pub fn record_unclean_election(&self, lost_records: u64) {
+        self.unclean_elections.fetch_add(1, Ordering::Relaxed);
+        self.unclean_election_lost_records
+            .fetch_add(lost_records, Ordering::Relaxed);
+    }
+
+    pub fn unclean_elections(&self) -> u64 {
+        self.unclean_elections.load(Ordering::Relaxed)
+    }
+
+    pub fn encode(&self, encoder: &mut OpenMetricsEncoder) {
+        let controllers = [
+            ("topic", &self.topic),
+            ("partition", &self.partition),
+            ("spu", &self.spu),
+            ("drain", &self.drain),
+        ];
+
+        let mut reconciliations = encoder.family(
+            "fluvio_sc_reconciliations",
+            MetricType::Counter,
+            "Reconciliation rounds processed by controller",
+        );
+        for (controller, metrics) in controllers {
+            reconciliations.sample(
+                &[("controller", controller)],
+                metrics.reconciliations.load(Ordering::Relaxed),
+            );
+        }
+
+        let mut actions = encoder.family(
+            "fluvio_sc_reconcile_actions",
+            MetricType::Counter,
+            "Actions emitted by controller reconciliation",
+        );
+        for (controller, metrics) in controllers {
+            actions.sample(
+                &[("controller", controller)],
+                metrics.actions.load(Ordering::Relaxed),
+            );
+        }
+
+        let mut duration = encoder.family(
+            "fluvio_sc_reconcile_duration_seconds",
+            MetricType::Histogram,
+            "Time spent in controller reconciliation",
+        );
+        for (controller, metrics) in controllers {
+            duration.histogram(&[("controller", controller)], &metrics.duration);
+        }
+
+        encoder.counter(
+            "fluvio_sc_unclean_leader_elections",
+            "Leaders elected from out of sync replicas",
+            self.unclean_elections.load(Ordering::Relaxed),
+        );
+        encoder.counter(
+            "fluvio_sc_unclean_election_lost_records",
+            "Records potentially lost by unclean leader elections",
+            self.unclean_election_lost_records.load(Ordering::Relaxed),
+        );
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReconcileMetrics {
+    reconciliations: AtomicU64,
+    actions: AtomicU64,
+    duration: Histogram,
+}
+
+impl ReconcileMetrics {
+    /// record a reconciliation round which generated `actions`
+    pub fn record(&self, actions: usize, elapsed: Duration) {
+        self.reconciliations.fetch_add(1, Ordering::Relaxed);
+        self.actions.fetch_add(actions as u64, Ordering::Relaxed);
+        self.duration.observe(elapsed);
+    }
+
+    pub fn reconciliations(&self) -> u64 {
+        self.reconciliations.load(Ordering::Relaxed)
+    }
+
+    pub fn actions(&self) -> u64 {
+        self.actions.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode_reconcile_metrics() {
+        let metrics = ScMetrics::default();
+        metrics.topic().record(2, Duration::from_millis(1));
+        metrics.topic().record(0, Duration::from_millis(1));
+
+        let mut encoder = OpenMetricsEncoder::new();
+        metrics.encode(&mut encoder);
+        let text = encoder.finish();
+
+        assert_eq!(metrics.topic().reconciliations(), 2);
+        assert!(text.contains("fluvio_sc_reconciliations_total{controller=\"topic\"} 2\n"));
+        assert!(text.contains("fluvio_sc_reconcile_actions_total{controller=\"topic\"} 2\n"));
+        assert!(text.contains("fluvio_sc_reconcile_actions_total{controller=\"spu\"} 0\n"));
+        assert!(text.contains("fluvio_sc_unclean_leader_elections_total 0\n"));
+    }
+}
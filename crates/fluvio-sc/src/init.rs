@@ -13,10 +13,11 @@ use fluvio_stream_model::core::MetadataItem;
 use crate::controllers::mirroring::controller::RemoteMirrorController;
 use crate::core::Context;
 use crate::core::SharedContext;
-use crate::controllers::partitions::PartitionController;
-use crate::controllers::spus::SpuController;
+use crate::controllers::partitions::{PartitionController, LeaderRebalanceController};
+use crate::controllers::spus::{SpuController, SpuDrainController};
 use crate::controllers::topics::controller::{TopicController, SystemTopicController};
 use crate::config::ScConfig;
+use crate::monitoring::init_metrics_endpoint;
 use crate::services::start_internal_server;
 use crate::dispatcher::dispatcher::MetadataDispatcher;
 use crate::services::auth::basic::BasicRbacPolicy;
@@ -36,52 +37,67 @@ where
     use crate::stores::spg::SpuGroupSpec;
     use crate::stores::tableformat::TableFormatSpec;
     use crate::stores::smartmodule::SmartModuleSpec;
+    use crate::stores::dictionary::DictionarySpec;
 
     let (sc_config, auth_policy) = sc_config_policy;
 
     let namespace = sc_config.namespace.clone();
     let ctx = Context::shared_metadata(sc_config);
 
-    MetadataDispatcher::<SpuSpec, C, M>::start(
+    MetadataDispatcher::<SpuSpec, C, M>::start_with_shutdown(
         namespace.clone(),
         metadata_client.clone(),
         ctx.spus().clone(),
+        ctx.shutdown().clone(),
     );
 
-    MetadataDispatcher::<TopicSpec, C, M>::start(
+    MetadataDispatcher::<TopicSpec, C, M>::start_with_shutdown(
         namespace.clone(),
         metadata_client.clone(),
         ctx.topics().clone(),
+        ctx.shutdown().clone(),
     );
 
-    MetadataDispatcher::<PartitionSpec, C, M>::start(
+    MetadataDispatcher::<PartitionSpec, C, M>::start_with_shutdown(
         namespace.clone(),
         metadata_client.clone(),
         ctx.partitions().clone(),
+        ctx.shutdown().clone(),
     );
 
-    MetadataDispatcher::<SpuGroupSpec, C, M>::start(
+    MetadataDispatcher::<SpuGroupSpec, C, M>::start_with_shutdown(
         namespace.clone(),
         metadata_client.clone(),
         ctx.spgs().clone(),
+        ctx.shutdown().clone(),
     );
 
-    MetadataDispatcher::<TableFormatSpec, C, M>::start(
+    MetadataDispatcher::<TableFormatSpec, C, M>::start_with_shutdown(
         namespace.clone(),
         metadata_client.clone(),
         ctx.tableformats().clone(),
+        ctx.shutdown().clone(),
     );
 
-    MetadataDispatcher::<SmartModuleSpec, C, M>::start(
+    MetadataDispatcher::<SmartModuleSpec, C, M>::start_with_shutdown(
         namespace.clone(),
         metadata_client.clone(),
         ctx.smartmodules().clone(),
+        ctx.shutdown().clone(),
     );
 
-    MetadataDispatcher::<MirrorSpec, C, M>::start(
+    MetadataDispatcher::<MirrorSpec, C, M>::start_with_shutdown(
         namespace.clone(),
         metadata_client.clone(),
         ctx.mirrors().clone(),
+        ctx.shutdown().clone(),
+    );
+
+    MetadataDispatcher::<DictionarySpec, C, M>::start_with_shutdown(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.dictionaries().clone(),
+        ctx.shutdown().clone(),
     );
 
     start_main_loop_services(ctx, auth_policy).await
@@ -99,14 +115,35 @@ where
     let config = ctx.config();
 
     whitelist!(config, "spu", SpuController::start(ctx.clone()));
+    whitelist!(config, "spu", SpuDrainController::start(ctx.clone()));
     whitelist!(config, "topic", TopicController::start(ctx.clone()));
     whitelist!(config, "topic", SystemTopicController::start(ctx.clone()));
     whitelist!(
         config,
         "partition",
-        PartitionController::start(ctx.partitions().clone(), ctx.spus().clone())
+        PartitionController::start(
+            ctx.partitions().clone(),
+            ctx.spus().clone(),
+            ctx.metrics().clone(),
+            ctx.shutdown().clone()
+        )
     );
 
+    if let Some(interval) = config.leader_rebalance_interval {
+        whitelist!(
+            config,
+            "partition",
+            LeaderRebalanceController::start(
+                ctx.partitions().clone(),
+                ctx.spus().clone(),
+                interval,
+                config.leader_imbalance_threshold,
+                ctx.metrics().clone(),
+                ctx.shutdown().clone()
+            )
+        );
+    }
+
     whitelist!(config, "internal", start_internal_server(ctx.clone()));
     whitelist!(
         config,
@@ -119,6 +156,8 @@ where
         RemoteMirrorController::start(ctx.clone())
     );
 
+    init_metrics_endpoint(ctx.clone());
+
     mod pub_server {
 
         use std::sync::Arc;
@@ -164,7 +164,12 @@ impl K8SpuController {
             let spec = spg_obj.spec();
             let replicas = spec.replicas;
             for i in 0..replicas {
-                let (spu_name, spu) = spg_obj.as_spu(i, &services);
+                let (spu_name, mut spu) = spg_obj.as_spu(i, &services);
+
+                // drain is requested by admin and is not part of group
+                if let Some(existing) = self.spus.store().value(&spu_name).await {
+                    spu.spec.drain.clone_from(&existing.spec.drain);
+                }
 
                 debug!(id=i,spu=?spu,"applying spu");
 
@@ -140,6 +140,7 @@ impl SpuGroupObj {
                 port: spu_public_ep.port,
                 encryption: spu_public_ep.encryption,
             }),
+            drain: None,
         };
 
         /*
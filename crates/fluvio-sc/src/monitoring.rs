@@ -0,0 +1,76 @@
+//!
+//! # SC metrics
+//!
+//! Expose controller reconciliation and cluster state in OpenMetrics format.
+//!
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::info;
+
+use fluvio_metrics::{MetricType, MetricsSource, OpenMetricsEncoder, run_metrics_server};
+use fluvio_stream_model::core::MetadataItem;
+
+use crate::core::{SharedContext, spawn_until_shutdown};
+
+/// serve OpenMetrics over http if metrics endpoint is configured
+pub(crate) fn init_metrics_endpoint<C>(ctx: SharedContext<C>)
+where
+    C: MetadataItem + 'static,
+{
+    if let Some(addr) = ctx.config().metrics_endpoint.clone() {
+        info!(%addr, "starting metrics endpoint");
+        let shutdown = ctx.shutdown().clone();
+        spawn_until_shutdown(
+            shutdown,
+            run_metrics_server(addr, Arc::new(ScMetricsSource { ctx })),
+        );
+    }
+}
+
+struct ScMetricsSource<C: MetadataItem> {
+    ctx: SharedContext<C>,
+}
+
+#[async_trait]
+impl<C> MetricsSource for ScMetricsSource<C>
+where
+    C: MetadataItem + 'static,
+{
+    async fn encode(&self, encoder: &mut OpenMetricsEncoder) {
+        self.ctx.metrics().encode(encoder);
+
+        let (spus_online, spus_offline) = {
+            let spus = self.ctx.spus().store().read().await;
+            let online = spus.values().filter(|spu| spu.status.is_online()).count();
+            (online, spus.len() - online)
+        };
+        encoder
+            .family("fluvio_sc_spus", MetricType::Gauge, "SPUs known by the SC")
+            .sample(&[("status", "online")], spus_online)
+            .sample(&[("status", "offline")], spus_offline);
+
+        encoder.gauge(
+            "fluvio_sc_topics",
+            "Topics known by the SC",
+            self.ctx.topics().store().count().await,
+        );
+
+        let (partitions_online, partitions_offline) = {
+            let partitions = self.ctx.partitions().store().read().await;
+            let online = partitions
+                .values()
+                .filter(|partition| partition.status.is_online())
+                .count();
+            (online, partitions.len() - online)
+        };
+        encoder
+            .family(
+                "fluvio_sc_partitions",
+                MetricType::Gauge,
+                "Partitions known by the SC",
+            )
+            .sample(&[("status", "online")], partitions_online)
+            .sample(&[("status", "offline")], partitions_offline);
+    }
+}
@@ -253,6 +253,10 @@ mod policy {
                 ObjectType::TableFormat,
                 vec![ActionUrn::new(Action::All, None)],
             );
+            root_policy.insert(
+                ObjectType::Dictionary,
+                vec![ActionUrn::new(Action::All, None)],
+            );
             root_policy.insert(
                 ObjectType::Mirror,
                 vec![
@@ -22,6 +22,7 @@ where
     info!("starting internal services");
 
     let addr = ctx.config().private_endpoint.clone();
+    let shutdown = ctx.shutdown().clone();
     let server = FluvioApiServer::new(addr, ctx, ScInternalService::new());
-    server.run();
+    server.run_with_shutdown(shutdown);
 }
@@ -19,6 +19,7 @@ use fluvio_controlplane::spu_api::update_mirror::MirrorMsg;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
 use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
+use fluvio_controlplane::spu_api::update_dictionary::{DictionaryMsg, UpdateDictionaryRequest};
 use fluvio_controlplane::spu_api::update_spu::UpdateSpuRequest;
 use fluvio_controlplane_metadata::message::Message;
 use fluvio_sc_schema::mirror::MirrorSpec;
@@ -33,7 +34,10 @@ use anyhow::Result;
 use fluvio_future::timer::sleep;
 use fluvio_service::ConnectInfo;
 use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
+use fluvio_controlplane_metadata::dictionary::DictionarySpec;
 use fluvio_types::SpuId;
+use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
+use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::api::RequestMessage;
 use fluvio_service::{FluvioService, wait_for_request};
 use fluvio_socket::{FluvioSocket, SocketError, FluvioSink};
@@ -84,7 +88,9 @@ where
                 debug!(spu_id,"registration req");
 
                 let register_res = if context.spus().store().validate_spu_for_registered(spu_id).await {
-                    RegisterSpuResponse::ok()
+                    // consumer offsets are only kept in memory and changes may be lost while SPU
+                    // was disconnected, so all of them are requested again
+                    RegisterSpuResponse::ok().with_consumer_offsets_snapshot()
                 } else {
                     status = false;
                     debug!(spu_id,"spu validation failed");
@@ -135,6 +141,7 @@ where
     let mut partition_spec_listener = context.partitions().change_listener();
     let mut sm_spec_listener = context.smartmodules().change_listener();
     let mut mirror_spec_listener = context.mirrors().change_listener();
+    let mut dictionary_spec_listener = context.dictionaries().change_listener();
 
     // send initial changes
 
@@ -146,6 +153,8 @@ where
 
         send_spu_spec_changes(&mut spu_spec_listener, &mut sink, spu_id).await?;
         send_smartmodule_changes(&mut sm_spec_listener, &mut sink, spu_id).await?;
+        // dictionaries must be known before replicas referencing them
+        send_dictionary_changes(&mut dictionary_spec_listener, &mut sink, spu_id).await?;
         send_replica_spec_changes(&mut partition_spec_listener, &mut sink, spu_id).await?;
         send_mirror_changes(&mut mirror_spec_listener, &mut sink, spu_id).await?;
 
@@ -211,6 +220,10 @@ where
                 debug!("mirror lister changed");
             }
 
+            _ = dictionary_spec_listener.listen() => {
+                debug!("dictionary lister changed");
+            }
+
         }
     }
 
@@ -232,18 +245,30 @@ where
     }
     let mut actions = vec![];
     let read_guard = ctx.partitions().store().read().await;
+    let mut consumers = None;
     for lrs_req in requests.into_iter() {
         if let Some(partition) = read_guard.get(&lrs_req.id) {
             let mut current_status = partition.inner().status().clone();
             let key = lrs_req.id.clone();
-            let new_status = PartitionStatus::new2(
-                lrs_req.leader,
-                lrs_req.replicas,
+            // leader is always in sync with itself
+            let mut leader = lrs_req.leader;
+            leader.in_sync = true;
+            let mut replicas = lrs_req.replicas;
+            for replica in replicas.iter_mut() {
+                replica.in_sync = lrs_req.in_sync_replicas.contains(&replica.spu);
+            }
+            let mut new_status = PartitionStatus::new2(
+                leader,
+                replicas,
                 lrs_req.size,
                 PartitionResolution::Online,
                 lrs_req.base_offset,
             );
+            new_status.segments = lrs_req.segments;
             current_status.merge(new_status);
+            if key == ReplicaKey::from(CONSUMER_REPLICA_KEY) {
+                consumers = Some((lrs_req.consumers, lrs_req.consumers_delta));
+            }
 
             actions.push(WSAction::<PartitionSpec, C>::UpdateStatus((
                 key,
@@ -260,6 +285,12 @@ where
 
     drop(read_guard);
 
+    match consumers {
+        Some((consumers, true)) => ctx.consumer_offsets().merge(consumers).await,
+        Some((consumers, false)) => ctx.consumer_offsets().update(consumers).await,
+        None => {}
+    }
+
     for action in actions.into_iter() {
         ctx.partitions().send_action(action).await;
     }
@@ -604,3 +635,60 @@ async fn send_mirror_changes<C: MetadataItem>(
     sink.send_request(&message).await?;
     Ok(())
 }
+
+#[instrument(level = "trace", skip(sink))]
+async fn send_dictionary_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<DictionarySpec, C>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: false,
+            meta: true,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, deletes) = changes.parts();
+
+    let request = if is_sync_all {
+        UpdateDictionaryRequest::with_all(
+            epoch,
+            updates.into_iter().map(|dict| dict.into()).collect(),
+        )
+    } else {
+        let mut changes: Vec<DictionaryMsg> = updates
+            .into_iter()
+            .map(|dict| Message::update(dict.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|dict| Message::delete(dict.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdateDictionaryRequest::with_changes(epoch, changes)
+    };
+
+    debug!(?request, "sending dictionary to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
@@ -12,6 +12,7 @@ use fluvio_sc_schema::objects::{
     ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiUpdateRequest,
     ObjectApiWatchRequest,
 };
+use fluvio_sc_schema::partition::PartitionUsageRequest;
 use fluvio_sc_schema::AdminPublicApiKey;
 
 // Fluvi Client version 0.14.0 corresponds to Platform version 10.0.0
@@ -66,6 +67,12 @@ pub async fn handle_api_versions_request(
         ObjectApiUpdateRequest::MAX_API_VERSION,
     ));
 
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::PartitionUsage,
+        PartitionUsageRequest::MIN_API_VERSION,
+        PartitionUsageRequest::MAX_API_VERSION,
+    ));
+
     trace!("flv api versions response: {:#?}", response);
 
     Ok(request.new_response(response))
@@ -8,6 +8,7 @@ use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::spg::SpuGroupSpec;
 use fluvio_controlplane_metadata::spu::CustomSpuSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
+use fluvio_controlplane_metadata::dictionary::DictionarySpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
@@ -38,6 +39,8 @@ pub async fn handle_create_request<AC: AuthContext, C: MetadataItem>(
         super::tableformat::handle_create_tableformat_request(create, auth_context).await?
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<MirrorSpec>> {
         super::mirror::handle_register_mirror(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<DictionarySpec>> {
+        super::dictionary::handle_create_dictionary_request(create, auth_context).await?
     } else {
         error!("unknown create request: {:#?}", req);
         Status::new(
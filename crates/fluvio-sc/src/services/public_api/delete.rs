@@ -15,6 +15,7 @@ use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::spg::SpuGroupSpec;
 use fluvio_controlplane_metadata::spu::CustomSpuSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
+use fluvio_controlplane_metadata::dictionary::DictionarySpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
@@ -46,6 +47,8 @@ pub async fn handle_delete_request<AC: AuthContext, C: MetadataItem>(
         super::tableformat::handle_delete_tableformat(req.key(), auth_ctx).await?
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<MirrorSpec>> {
         super::mirror::handle_unregister_mirror(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<DictionarySpec>> {
+        super::dictionary::handle_delete_dictionary(req.key(), auth_ctx).await?
     } else {
         error!("unknown create request: {:#?}", del_req);
         Status::new(
@@ -0,0 +1,102 @@
+//!
+//! # Create Dictionary Request
+//!
+//! Validates dictionary content and stores it in KV store.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_compression::Dictionary;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::dictionary::DictionarySpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for dictionary request
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_dictionary_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<DictionarySpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating dictionary");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(DictionarySpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if auth_ctx
+        .global_ctx
+        .dictionaries()
+        .store()
+        .contains_key(&name)
+        .await
+    {
+        debug!("dictionary already exists");
+        return Ok(Status::new(
+            name.to_string(),
+            ErrorCode::DictionaryAlreadyExists,
+            Some(format!("dictionary '{name}' already defined")),
+        ));
+    }
+
+    // dictionary id is embedded in compressed frames, it must match the content
+    match Dictionary::new(spec.data.to_vec()) {
+        Ok(dictionary) if dictionary.id() == spec.dictionary_id => {}
+        Ok(dictionary) => {
+            return Ok(Status::new(
+                name,
+                ErrorCode::DictionaryError,
+                Some(format!(
+                    "dictionary id {} does not match content id {}",
+                    spec.dictionary_id,
+                    dictionary.id()
+                )),
+            ));
+        }
+        Err(err) => {
+            return Ok(Status::new(
+                name,
+                ErrorCode::DictionaryError,
+                Some(err.to_string()),
+            ));
+        }
+    }
+
+    let status = if let Err(err) = auth_ctx
+        .global_ctx
+        .dictionaries()
+        .create_spec(name.clone(), spec)
+        .await
+    {
+        Status::new(name, ErrorCode::DictionaryError, Some(err.to_string()))
+    } else {
+        info!(%name, "dictionary created");
+        Status::new_ok(name)
+    };
+
+    trace!("create dictionary response {:#?}", status);
+
+    Ok(status)
+}
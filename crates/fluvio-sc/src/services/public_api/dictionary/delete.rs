@@ -0,0 +1,71 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::dictionary::DictionarySpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for delete dictionary request
+#[instrument(skip(name, auth_ctx))]
+pub async fn handle_delete_dictionary<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(%name, "deleting dictionary");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(DictionarySpec::OBJECT_TYPE, InstanceAction::Delete, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let dictionaries = auth_ctx.global_ctx.dictionaries();
+    if dictionaries.store().value(&name).await.is_none() {
+        return Ok(Status::new(
+            name,
+            ErrorCode::DictionaryNotFound,
+            Some("not found".to_owned()),
+        ));
+    }
+
+    // consumers of topics using the dictionary can't decode records without it
+    let topics = auth_ctx.global_ctx.topics().store().clone_values().await;
+    if let Some(topic) = topics
+        .iter()
+        .find(|topic| topic.spec.get_compression_dictionary() == Some(&name))
+    {
+        return Ok(Status::new(
+            name,
+            ErrorCode::DictionaryInUse(topic.key.clone()),
+            None,
+        ));
+    }
+
+    let status = if let Err(err) = dictionaries.delete(name.clone()).await {
+        Status::new(name, ErrorCode::DictionaryError, Some(err.to_string()))
+    } else {
+        info!(%name, "dictionary deleted");
+        Status::new_ok(name)
+    };
+
+    trace!("delete dictionary resp {:#?}", status);
+
+    Ok(status)
+}
@@ -1,7 +1,9 @@
 mod register;
 mod unregister;
 mod list;
+mod update;
 
 pub use register::*;
 pub use unregister::*;
 pub use list::*;
+pub use update::*;
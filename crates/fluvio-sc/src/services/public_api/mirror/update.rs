@@ -0,0 +1,53 @@
+use fluvio_auth::AuthContext;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::{
+    core::MetadataItem,
+    mirror::{MirrorType, UpdateMirrorAction},
+    Status,
+};
+use anyhow::Result;
+use tracing::info;
+
+use crate::services::auth::AuthServiceContext;
+
+pub async fn handle_update_mirror<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    action: UpdateMirrorAction,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    info!(name, ?action, "update mirror cluster");
+    if auth_ctx.global_ctx.config().read_only_metadata {
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::Other("unable to change read-only configuration".to_owned()),
+            Some(String::from("read-only error")),
+        ));
+    }
+
+    let ctx = auth_ctx.global_ctx.clone();
+    let Some(mirror) = ctx.mirrors().store().value(&name).await else {
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::MirrorNotFound,
+            Some(format!("remote cluster {name:?} not found")),
+        ));
+    };
+
+    let mut spec = mirror.spec().clone();
+    match (&mut spec.mirror_type, action) {
+        (MirrorType::Remote(remote), UpdateMirrorAction::SetLink(link)) => {
+            remote.link = link;
+        }
+        (MirrorType::Home(_), _) => {
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::MirrorInvalidType,
+                Some("link settings can only be changed on remote cluster".to_owned()),
+            ));
+        }
+    }
+
+    ctx.mirrors().create_spec(name.clone(), spec).await?;
+
+    Ok(Status::new_ok(name))
+}
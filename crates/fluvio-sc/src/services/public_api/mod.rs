@@ -14,6 +14,7 @@ mod tableformat;
 mod derivedstream;
 mod mirror;
 mod mirroring;
+mod dictionary;
 
 pub use server::start_public_server;
 
@@ -41,7 +42,8 @@ mod server {
     {
         let addr = ctx.global_ctx.config().public_endpoint.clone();
         debug!("starting public api service");
+        let shutdown = ctx.global_ctx.shutdown().clone();
         let server = FluvioApiServer::new(addr, ctx, PublicService::new());
-        server.run();
+        server.run_with_shutdown(shutdown);
     }
 }
@@ -11,6 +11,12 @@ use fluvio_auth::{AuthContext, TypeAction};
 
 use crate::services::auth::AuthServiceContext;
 
+mod usage;
+mod update;
+
+pub use usage::handle_partition_usage_request;
+pub use update::handle_partition_update_request;
+
 #[instrument(skip(_filters, auth_ctx))]
 pub async fn handle_fetch_request<AC: AuthContext, C: MetadataItem>(
     _filters: ListFilters,
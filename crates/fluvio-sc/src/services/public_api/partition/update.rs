@@ -0,0 +1,108 @@
+//!
+//! # Update Partition Request
+//!
+//! Move leadership of partitions back to their preferred replica.
+//!
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
+
+use anyhow::Result;
+use tracing::{info, instrument, trace};
+
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_sc_schema::{
+    partition::{PartitionSpec, UpdatePartitionAction},
+    Status,
+};
+
+use crate::controllers::partitions::PartitionReducer;
+use crate::services::auth::AuthServiceContext;
+
+/// partitions selected by update key
+enum PartitionTarget {
+    All,
+    Partition(ReplicaKey),
+    Topic(String),
+}
+
+/// Handler for partition update request.
+/// Key is partition name, topic name for all of its partitions or empty for all partitions
+#[instrument(skip(key, action, auth_ctx))]
+pub async fn handle_partition_update_request<AC: AuthContext, C: MetadataItem>(
+    key: String,
+    action: UpdatePartitionAction,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(%key, ?action, "Updating partitions");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(PartitionSpec::OBJECT_TYPE, InstanceAction::Update, &key)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                key,
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let partitions = auth_ctx.global_ctx.partitions();
+    let target = if key.is_empty() {
+        PartitionTarget::All
+    } else {
+        match ReplicaKey::from_str(&key) {
+            Ok(replica) if partitions.store().contains_key(&replica).await => {
+                PartitionTarget::Partition(replica)
+            }
+            _ if auth_ctx
+                .global_ctx
+                .topics()
+                .store()
+                .contains_key(&key)
+                .await =>
+            {
+                PartitionTarget::Topic(key.clone())
+            }
+            _ => {
+                return Ok(Status::new(
+                    key,
+                    ErrorCode::TopicNotFound,
+                    Some("topic or partition not found".to_owned()),
+                ));
+            }
+        }
+    };
+
+    let actions = match action {
+        UpdatePartitionAction::ElectPreferredLeader => {
+            let reducer = PartitionReducer::new(
+                partitions.store().clone(),
+                auth_ctx.global_ctx.spus().store().clone(),
+            );
+            reducer
+                .elect_preferred_leaders(|partition| match &target {
+                    PartitionTarget::All => true,
+                    PartitionTarget::Partition(replica) => partition.key() == replica,
+                    PartitionTarget::Topic(topic) => &partition.key().topic == topic,
+                })
+                .await
+        }
+    };
+
+    info!(%key, leaders = actions.len(), "electing preferred leaders");
+    for action in actions.into_iter() {
+        partitions.send_action(action).await;
+    }
+
+    Ok(Status::new_ok(key))
+}
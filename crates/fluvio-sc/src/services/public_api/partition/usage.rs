@@ -0,0 +1,108 @@
+use std::io::{Error, ErrorKind};
+
+use tracing::{trace, debug, instrument};
+use anyhow::Result;
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_sc_schema::partition::{
+    ConsumerLag, FollowerLag, PartitionSpec, PartitionUsage, PartitionUsageRequest,
+    PartitionUsageResponse,
+};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, TypeAction};
+
+use crate::services::auth::AuthServiceContext;
+
+/// Aggregate storage usage and lag of partitions from status reported by SPUs
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_partition_usage_request<AC: AuthContext, C: MetadataItem>(
+    request: RequestMessage<PartitionUsageRequest>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<ResponseMessage<PartitionUsageResponse>> {
+    let (header, req) = request.get_header_request();
+    debug!(?req, "partition usage request");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_type_action(PartitionSpec::OBJECT_TYPE, TypeAction::Read)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(
+                RequestMessage::<PartitionUsageRequest>::response_with_header(
+                    &header,
+                    PartitionUsageResponse::default(),
+                ),
+            );
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error").into());
+    }
+
+    let partitions: Vec<_> = auth_ctx
+        .global_ctx
+        .partitions()
+        .store()
+        .read()
+        .await
+        .values()
+        .filter(|value| value.inner().spec().system == req.system)
+        .filter(|value| req.topics.is_empty() || req.topics.contains(&value.inner().key().topic))
+        .map(|value| value.inner().clone())
+        .collect();
+
+    let consumer_offsets = auth_ctx.global_ctx.consumer_offsets();
+    let mut usages = Vec::with_capacity(partitions.len());
+    for partition in partitions {
+        let status = partition.status();
+        let leader = &status.leader;
+        let mut usage = PartitionUsage {
+            replica: partition.key().clone(),
+            leader: partition.spec().leader,
+            log_start_offset: status.base_offset,
+            hw: leader.hw,
+            leo: leader.leo,
+            size: status.size,
+            segments: status.segments,
+            ..Default::default()
+        };
+
+        usage.followers = status
+            .replicas
+            .iter()
+            .map(|follower| {
+                let records = follower.leader_lag(leader).max(0);
+                FollowerLag {
+                    spu: follower.spu,
+                    leo: follower.leo,
+                    records,
+                    bytes: usage.estimate_bytes(records),
+                }
+            })
+            .collect();
+
+        usage.consumers = consumer_offsets
+            .replica(partition.key())
+            .await
+            .into_iter()
+            .map(|consumer| ConsumerLag {
+                records: (leader.hw - consumer.offset - 1).max(0),
+                consumer_id: consumer.consumer_id,
+                offset: consumer.offset,
+                modified_time: consumer.modified_time,
+            })
+            .collect();
+
+        usages.push(usage);
+    }
+
+    debug!("partition usage resp: {} items", usages.len());
+    Ok(
+        RequestMessage::<PartitionUsageRequest>::response_with_header(
+            &header,
+            PartitionUsageResponse { partitions: usages },
+        ),
+    )
+}
@@ -29,6 +29,16 @@ use fluvio_sc_schema::AdminPublicDecodedRequest;
 
 use crate::services::auth::{AuthGlobalContext, AuthServiceContext};
 
+/// Notifies controllers using the tcp stream once it's done with,
+/// including when the connection is closed early on error or server shutdown
+struct ConnectionEnd(Arc<StickyEvent>);
+
+impl Drop for ConnectionEnd {
+    fn drop(&mut self) {
+        self.0.notify();
+    }
+}
+
 #[derive(Debug)]
 pub struct PublicService<A, C> {
     data: PhantomData<(A, C)>,
@@ -78,6 +88,7 @@ where
         let mut shared_sink = sink.as_shared();
 
         let end_event = StickyEvent::shared();
+        let _end_guard = ConnectionEnd(end_event.clone());
 
         api_loop!(
             api_stream,
@@ -115,6 +126,12 @@ where
                 shared_sink,
                 "list handler"
             ),
+            AdminPublicDecodedRequest::PartitionUsageRequest(request) => call_service!(
+                request,
+                super::partition::handle_partition_usage_request(request, &service_context),
+                shared_sink,
+                "partition usage handler"
+            ),
             AdminPublicDecodedRequest::MirroringRequest(request) =>
                 super::mirroring::handle_mirroring_request(request, service_context.clone(), shared_sink.clone(), end_event.clone())?,
             AdminPublicDecodedRequest::WatchRequest(request) =>
@@ -127,9 +144,6 @@ where
 
         );
 
-        // we are done with this tcp stream, notify any controllers use this strep
-        end_event.notify();
-
         Ok(())
     }
 }
@@ -0,0 +1,72 @@
+//!
+//! # Update Spu Request
+//!
+//! Start or stop draining SPU.
+//!
+use tracing::{info, trace, instrument};
+use std::io::{Error, ErrorKind};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::spu::{SpuSpec, UpdateSpuAction};
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_types::SpuId;
+
+use crate::dispatcher::core::MetadataItem;
+use crate::stores::spu::SpuLocalStorePolicy;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for spu update request
+#[instrument(skip(action, auth_ctx))]
+pub async fn handle_spu_update_request<AC: AuthContext, C: MetadataItem>(
+    spu_id: SpuId,
+    action: UpdateSpuAction,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    info!(spu_id, ?action, "Updating spu");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(
+            SpuSpec::OBJECT_TYPE,
+            InstanceAction::Update,
+            &spu_id.to_string(),
+        )
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                format!("spu-{spu_id}"),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let Some(spu) = auth_ctx.global_ctx.spus().store().get_by_id(spu_id).await else {
+        return Ok(Status::new(
+            format!("spu-{spu_id}"),
+            ErrorCode::SpuNotFound,
+            Some("not found".to_owned()),
+        ));
+    };
+
+    let spu_name = spu.key_owned();
+    let mut spec = spu.spec;
+    spec.drain = match action {
+        UpdateSpuAction::Drain(drain) => Some(drain),
+        UpdateSpuAction::CancelDrain => None,
+    };
+
+    auth_ctx
+        .global_ctx
+        .spus()
+        .create_spec(spu_name.clone(), spec)
+        .await?;
+
+    Ok(Status::new_ok(spu_name))
+}
@@ -112,6 +112,24 @@ async fn validate_topic_request<C: MetadataItem>(
         );
     }
 
+    // check if compression dictionary is present
+    if let Some(dictionary) = topic_spec.get_compression_dictionary() {
+        if !metadata
+            .dictionaries()
+            .store()
+            .contains_key(dictionary)
+            .await
+        {
+            return Status::new(
+                name.to_string(),
+                ErrorCode::DictionaryNotFound,
+                Some(format!(
+                    "compression dictionary '{dictionary}' not found\nHint: try `fluvio dictionary create {dictionary} --file <dictionary>`"
+                )),
+            );
+        }
+    }
+
     // check if deduplication filter is present
     if let Some(deduplication) = topic_spec.get_deduplication() {
         let sm_name = deduplication.filter.transform.uses.as_str();
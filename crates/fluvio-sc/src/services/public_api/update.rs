@@ -11,6 +11,9 @@ use tracing::{instrument, trace, debug, error};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_controlplane_metadata::partition::PartitionSpec;
+use fluvio_controlplane_metadata::spu::SpuSpec;
+use fluvio_controlplane_metadata::mirror::MirrorSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
 use fluvio_sc_schema::objects::{ObjectApiUpdateRequest, UpdateRequest};
@@ -31,6 +34,15 @@ pub async fn handle_update_request<AC: AuthContext, C: MetadataItem>(
     let status = if let Some(req) = del_req.downcast()? as Option<UpdateRequest<TopicSpec>> {
         let action = req.action.clone();
         super::topic::update::handle_topic_update_request(req.key(), action, auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<UpdateRequest<PartitionSpec>> {
+        let action = req.action.clone();
+        super::partition::handle_partition_update_request(req.key(), action, auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<UpdateRequest<SpuSpec>> {
+        let action = req.action.clone();
+        super::spu::handle_spu_update_request(req.key(), action, auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<UpdateRequest<MirrorSpec>> {
+        let action = req.action.clone();
+        super::mirror::handle_update_mirror(req.key(), action, auth_ctx).await?
     } else {
         error!("unknown update request: {:#?}", del_req);
         Status::new(
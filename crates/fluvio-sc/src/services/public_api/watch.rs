@@ -20,6 +20,7 @@ use fluvio_controlplane_metadata::spu::SpuSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
+use fluvio_controlplane_metadata::dictionary::DictionarySpec;
 
 use crate::services::auth::AuthServiceContext;
 use crate::stores::StoreContext;
@@ -84,6 +85,14 @@ pub fn handle_watch_request<AC, C: MetadataItem + 'static>(
             header,
             false,
         )
+    } else if (req.downcast()? as Option<WatchRequest<DictionarySpec>>).is_some() {
+        WatchController::<DictionarySpec, C>::update(
+            sink,
+            end_event,
+            auth_ctx.global_ctx.dictionaries().clone(),
+            header,
+            false,
+        )
     } else {
         debug!("Invalid Watch Req {:?}", req);
         return Err(anyhow!("Not Valid Watch Request",));
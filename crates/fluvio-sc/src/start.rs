@@ -1,14 +1,20 @@
 use std::{
+    collections::BTreeMap,
     sync::Arc,
     path::{PathBuf, Path},
     time::Duration,
 };
 
 use anyhow::Result;
-use tracing::info;
+use tokio::select;
+use tracing::{info, warn};
 
 use fluvio_future::{task::run_block_on, timer::sleep};
-use fluvio_stream_dispatcher::metadata::{SharedClient, MetadataClient, local::LocalMetadataStorage};
+use fluvio_stream_dispatcher::metadata::{
+    SharedClient, MetadataClient,
+    local::LocalMetadataStorage,
+    raft::{NodeId, RaftConfig, RaftMetadataStorage},
+};
 use fluvio_stream_model::{store::k8::K8MetaItem, core::MetadataItem};
 use k8_client::{K8Client, K8Config, memory::MemoryClient};
 
@@ -29,9 +35,31 @@ pub fn main_loop(opt: ScOpt) {
     match opt.mode() {
         RunMode::Local(metadata) => {
             info!(?metadata, "Running in local mode");
-            let client = create_local_metadata_store(metadata);
+            let metadata = metadata.to_path_buf();
+            let (raft_config, public_endpoints) = match opt
+                .raft_config()
+                .and_then(|raft_config| Ok((raft_config, opt.raft_public_endpoints()?)))
+            {
+                Ok(config) => config,
+                Err(err) => {
+                    fluvio_types::print_cli_err!(err);
+                    std::process::exit(-1);
+                }
+            };
             let ((sc_config, auth_policy), tls_option) = opt.parse_cli_or_exit();
-            local_main_loop(sc_config, client, auth_policy, tls_option)
+            match raft_config {
+                Some(raft_config) => replicated_main_loop(
+                    metadata,
+                    (raft_config, public_endpoints),
+                    sc_config,
+                    auth_policy,
+                    tls_option,
+                ),
+                None => {
+                    let client = create_local_metadata_store(&metadata);
+                    local_main_loop(sc_config, client, auth_policy, tls_option)
+                }
+            }
         }
         RunMode::ReadOnly(read_only_path) => {
             let read_only_path = read_only_path.to_path_buf();
@@ -108,7 +136,7 @@ fn k8_main_loop<C>(
 
         proxy::start_if(sc_config, tls_option).await;
 
-        println!("Streaming Controller started successfully");
+        info!("Streaming Controller started successfully");
         // do infinite loop
         loop {
             sleep(Duration::from_secs(60)).await;
@@ -132,7 +160,7 @@ fn local_main_loop<C, M>(
         crate::init::start_main_loop((sc_config.clone(), auth_policy), client).await;
         proxy::start_if(sc_config, tls_option).await;
 
-        println!("Streaming Controller started successfully");
+        info!("Streaming Controller started successfully");
         // do infinite loop
         loop {
             sleep(Duration::from_secs(60)).await;
@@ -140,6 +168,136 @@ fn local_main_loop<C, M>(
     });
 }
 
+/// Only leader of replicated metadata runs controllers and serves requests,
+/// other members forward client connections to the leader until they are elected.
+/// Leader which loses leadership stops its controllers and services and stands by again.
+fn replicated_main_loop(
+    path: PathBuf,
+    raft: (RaftConfig, BTreeMap<NodeId, String>),
+    sc_config: ScConfig,
+    auth_policy: Option<BasicRbacPolicy>,
+    tls_option: Option<(String, TlsConfig)>,
+) {
+    let (raft_config, public_endpoints) = raft;
+    run_block_on(async move {
+        let client = Arc::new(
+            RaftMetadataStorage::start(&path, raft_config)
+                .await
+                .expect("failed to start replicated metadata"),
+        );
+        proxy::start_if(sc_config.clone(), tls_option).await;
+
+        loop {
+            info!(id = client.id(), "waiting for metadata leadership");
+            select! {
+                _ = client.wait_for_leadership() => {},
+                _ = forward::forward_to_leader(
+                    sc_config.public_endpoint.clone(),
+                    client.clone(),
+                    public_endpoints.clone(),
+                ) => {},
+            }
+            info!(
+                id = client.id(),
+                "elected metadata leader, starting main loop"
+            );
+
+            let ctx = crate::init::start_main_loop(
+                (sc_config.clone(), auth_policy.clone()),
+                client.clone(),
+            )
+            .await;
+            info!("Streaming Controller started successfully");
+
+            // new leader may be elected while this one is partitioned, stop serving stale metadata
+            client.wait_for_demotion().await;
+            warn!(
+                id = client.id(),
+                "lost metadata leadership, stopping controllers and services"
+            );
+            ctx.shutdown().notify();
+        }
+    });
+}
+
+/// Forwarding of client connections from standby SC to the metadata leader
+mod forward {
+    use std::collections::BTreeMap;
+    use std::io::Error as IoError;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use futures_util::StreamExt;
+    use futures_util::io::copy;
+    use tokio::select;
+    use tracing::{debug, info, warn};
+
+    use fluvio_future::net::{TcpListener, TcpStream};
+    use fluvio_future::task::spawn;
+    use fluvio_future::timer::sleep;
+    use fluvio_stream_dispatcher::metadata::raft::{NodeId, RaftMetadataStorage};
+
+    const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// forward connections accepted at `addr` to public endpoint of leader, runs until dropped.
+    /// Without leader endpoints nothing listens at `addr`, clients fail over to next SC address
+    pub async fn forward_to_leader(
+        addr: String,
+        client: Arc<RaftMetadataStorage>,
+        endpoints: BTreeMap<NodeId, String>,
+    ) {
+        if endpoints.is_empty() {
+            return std::future::pending().await;
+        }
+        loop {
+            // demoted leader may still be closing its public server
+            if let Err(err) = accept_loop(&addr, &client, &endpoints).await {
+                debug!(%addr, %err, "unable to forward connections, retrying");
+            }
+            sleep(RETRY_INTERVAL).await;
+        }
+    }
+
+    async fn accept_loop(
+        addr: &str,
+        client: &RaftMetadataStorage,
+        endpoints: &BTreeMap<NodeId, String>,
+    ) -> Result<(), IoError> {
+        let listener = TcpListener::bind(addr).await?;
+        info!(addr, "forwarding client connections to metadata leader");
+        let mut incoming = listener.incoming();
+        while let Some(stream) = incoming.next().await {
+            let stream = stream?;
+            let leader = client
+                .leader()
+                .await
+                .filter(|leader| *leader != client.id())
+                .and_then(|leader| endpoints.get(&leader).cloned());
+            let Some(leader) = leader else {
+                warn!("no metadata leader to forward connection to, closing it");
+                continue;
+            };
+            spawn(async move {
+                if let Err(err) = forward(stream, &leader).await {
+                    debug!(%leader, %err, "forwarded connection closed");
+                }
+            });
+        }
+        Ok(())
+    }
+
+    async fn forward(stream: TcpStream, leader: &str) -> Result<(), IoError> {
+        let upstream = TcpStream::connect(leader).await?;
+        let (mut reader, mut writer) = (stream.clone(), stream);
+        let (mut upstream_reader, mut upstream_writer) = (upstream.clone(), upstream);
+        select! {
+            result = copy(&mut reader, &mut upstream_writer) => result?,
+            result = copy(&mut upstream_reader, &mut writer) => result?,
+        };
+        Ok(())
+    }
+}
+
 mod proxy {
     use std::process;
     use tracing::info;
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_lock::RwLock;
+use tracing::debug;
+
+use fluvio_controlplane::sc_api::update_lrs::LrsConsumerOffset;
+use fluvio_protocol::record::ReplicaKey;
+
+pub type SharedConsumerOffsets = Arc<ConsumerOffsets>;
+
+/// Consumer offsets reported by the SPU leading the consumer offsets partition.
+/// This is only kept in memory, SPU reports all offsets when it connects and changed ones afterwards.
+#[derive(Debug, Default)]
+pub struct ConsumerOffsets {
+    offsets: RwLock<HashMap<ReplicaKey, Vec<LrsConsumerOffset>>>,
+}
+
+impl ConsumerOffsets {
+    pub fn shared() -> SharedConsumerOffsets {
+        Arc::new(Self::default())
+    }
+
+    /// replace all offsets with latest report
+    pub async fn update(&self, consumers: Vec<LrsConsumerOffset>) {
+        debug!(consumers = consumers.len(), "updating consumer offsets");
+        let mut offsets: HashMap<ReplicaKey, Vec<LrsConsumerOffset>> = HashMap::new();
+        for consumer in consumers {
+            offsets
+                .entry(consumer.replica_id.clone())
+                .or_default()
+                .push(consumer);
+        }
+        *self.offsets.write().await = offsets;
+    }
+
+    /// update offsets with changes since last report
+    pub async fn merge(&self, consumers: Vec<LrsConsumerOffset>) {
+        debug!(consumers = consumers.len(), "merging consumer offsets");
+        let mut offsets = self.offsets.write().await;
+        for consumer in consumers {
+            let replica = offsets.entry(consumer.replica_id.clone()).or_default();
+            match replica
+                .iter_mut()
+                .find(|current| current.consumer_id == consumer.consumer_id)
+            {
+                Some(current) => *current = consumer,
+                None => replica.push(consumer),
+            }
+        }
+    }
+
+    /// offsets committed by consumers of the replica
+    pub async fn replica(&self, replica: &ReplicaKey) -> Vec<LrsConsumerOffset> {
+        self.offsets
+            .read()
+            .await
+            .get(replica)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn offset(topic: &str, partition: u32, consumer: &str, offset: i64) -> LrsConsumerOffset {
+        LrsConsumerOffset {
+            replica_id: ReplicaKey::new(topic, partition),
+            consumer_id: consumer.to_owned(),
+            offset,
+            modified_time: 0,
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_consumer_offsets_update() {
+        let offsets = ConsumerOffsets::default();
+        offsets
+            .update(vec![
+                offset("topic", 0, "c1", 10),
+                offset("topic", 0, "c2", 5),
+                offset("topic", 1, "c1", 3),
+            ])
+            .await;
+
+        assert_eq!(offsets.replica(&ReplicaKey::new("topic", 0)).await.len(), 2);
+        assert_eq!(offsets.replica(&ReplicaKey::new("topic", 1)).await.len(), 1);
+
+        offsets.update(vec![offset("topic", 1, "c1", 4)]).await;
+        assert!(
+            offsets
+                .replica(&ReplicaKey::new("topic", 0))
+                .await
+                .is_empty()
+        );
+        assert_eq!(
+            offsets.replica(&ReplicaKey::new("topic", 1)).await[0].offset,
+            4
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_consumer_offsets_merge() {
+        let offsets = ConsumerOffsets::default();
+        offsets
+            .update(vec![
+                offset("topic", 0, "c1", 10),
+                offset("topic", 0, "c2", 5),
+            ])
+            .await;
+
+        offsets
+            .merge(vec![
+                offset("topic", 0, "c2", 7),
+                offset("topic", 1, "c1", 3),
+            ])
+            .await;
+
+        let replica = offsets.replica(&ReplicaKey::new("topic", 0)).await;
+        assert_eq!(replica.len(), 2);
+        assert_eq!(replica[0].offset, 10);
+        assert_eq!(replica[1].offset, 7);
+        assert_eq!(offsets.replica(&ReplicaKey::new("topic", 1)).await.len(), 1);
+    }
+}
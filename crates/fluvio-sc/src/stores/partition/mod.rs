@@ -7,6 +7,8 @@ pub type PartitionAdminStore = PartitionLocalStore<K8MetaItem>;
 
 mod policy;
 mod store;
+mod consumer_offset;
 
 pub use store::*;
 pub use policy::*;
+pub use consumer_offset::*;
@@ -32,16 +32,17 @@ impl SimplePolicy {
 }
 
 impl ElectionPolicy for SimplePolicy {
+    /// only replicas in the in sync replica set reported by the leader are clean candidates,
+    /// the least lagging one is preferred
     fn potential_leader_score(
         &self,
         replica_status: &ReplicaStatus,
         leader: &ReplicaStatus,
     ) -> ElectionScoring {
-        let lag = leader.leo - replica_status.leo;
-        if lag < 4 {
-            ElectionScoring::Score(lag as u16)
-        } else {
-            ElectionScoring::NotSuitable
+        if !replica_status.in_sync {
+            return ElectionScoring::NotSuitable;
         }
+        let lag = (leader.leo - replica_status.leo).clamp(0, u16::MAX as i64);
+        ElectionScoring::Score(lag as u16)
     }
 }
@@ -348,10 +348,13 @@ fn find_status(status: &mut [ReplicaStatus], spu: SpuId) -> Option<&'_ mut Repli
 }
 
 pub(crate) trait PartitonStatusExtension: Sized {
+    /// least lagging live replica out of the in sync replicas, none if clean election is not possible
     fn candidate_leader<P>(&self, online: &HashSet<SpuId>, policy: &P) -> Option<SpuId>
     where
         P: ElectionPolicy;
 
+    fn unclean_candidate_leader(&self, online: &HashSet<SpuId>) -> Option<(SpuId, u64)>;
+
     fn merge(&mut self, other: Self);
 
     fn update_lrs(&mut self);
@@ -386,11 +389,25 @@ impl PartitonStatusExtension for PartitionStatus {
         candidate_spu
     }
 
+    /// most caught up live replica regardless of lag, with number of records it is missing
+    fn unclean_candidate_leader(&self, online: &HashSet<SpuId>) -> Option<(SpuId, u64)> {
+        self.replicas
+            .iter()
+            .filter(|candidate| online.contains(&candidate.spu))
+            .max_by_key(|candidate| (candidate.leo, -candidate.spu))
+            .map(|candidate| {
+                let lost = (self.leader.leo - candidate.leo.max(0)).max(0) as u64;
+                (candidate.spu, lost)
+            })
+    }
+
     /// merge status from spu
     /// ignore changes from spu = -1 or offsets = -1
     fn merge(&mut self, other: Self) {
         self.resolution = other.resolution;
         self.size = other.size;
+        self.base_offset = other.base_offset;
+        self.segments = other.segments;
         if let Some(old) = self.leader.merge(&other.leader) {
             self.replicas.push(old); // move old leader to replicas
         }
@@ -453,6 +470,7 @@ impl ReplicaStatusExtension for ReplicaStatus {
             if source.hw != -1 {
                 self.hw = source.hw;
             }
+            self.in_sync = source.in_sync;
             None
         } else {
             let old = Self::new(self.spu, self.hw, self.leo);
@@ -461,6 +479,7 @@ impl ReplicaStatusExtension for ReplicaStatus {
 
             self.leo = source.leo;
             self.hw = source.hw;
+            self.in_sync = source.in_sync;
 
             Some(old)
         }
@@ -475,49 +494,39 @@ mod test {
     use crate::stores::partition::PartitonStatusExtension;
 
     use super::PartitionStatus;
+    use super::PartitionResolution;
     use super::ReplicaStatus;
-    use super::ElectionPolicy;
-    use super::ElectionScoring;
-
-    struct SimplePolicy {}
-
-    impl ElectionPolicy for SimplePolicy {
-        fn potential_leader_score(
-            &self,
-            replica_status: &ReplicaStatus,
-            leader: &ReplicaStatus,
-        ) -> ElectionScoring {
-            let lag = leader.leo - replica_status.leo;
-            if lag < 4 {
-                ElectionScoring::Score(lag as u16)
-            } else {
-                ElectionScoring::NotSuitable
-            }
+    use crate::stores::partition::SimplePolicy;
+
+    fn in_sync(mut status: PartitionStatus) -> PartitionStatus {
+        for replica in status.replicas.iter_mut() {
+            replica.in_sync = true;
         }
+        status
     }
 
     #[test]
     fn test_candidate_spu_no_candidate() {
         let status = PartitionStatus::leader((5000, 0, 0));
         let online_spu = HashSet::new();
-        let policy = SimplePolicy {};
+        let policy = SimplePolicy::new();
 
         assert!(status.candidate_leader(&online_spu, &policy).is_none());
     }
 
     #[test]
     fn test_candidate_spu_best() {
-        let status = PartitionStatus::new(
+        let status = in_sync(PartitionStatus::new(
             (5000, 100, 110),
             vec![
                 (5001, 100, 110).into(), // caught up with leader  (best)
                 (5002, 100, 105).into(), // need 5 offset to caught with leaser
             ],
-        );
+        ));
         let mut online_spu = HashSet::new();
         online_spu.insert(5001);
         online_spu.insert(5002);
-        let policy = SimplePolicy {};
+        let policy = SimplePolicy::new();
 
         assert_eq!(status.candidate_leader(&online_spu, &policy), Some(5001)); // 5001 has least lag
     }
@@ -526,19 +535,19 @@ mod test {
     /// even if follower didn't catch up HW
     #[test]
     fn test_candidate_spu_best_conflict() {
-        let status = PartitionStatus::new(
+        let status = in_sync(PartitionStatus::new(
             (5000, 100, 110),
             vec![
                 (5001, 95, 110).into(),  // caught up with leader  (best)
                 (5002, 100, 105).into(), // need 5 offset to caught with leaser
             ],
-        );
+        ));
 
         let mut online_spu = HashSet::new();
         online_spu.insert(5000);
         online_spu.insert(5001);
         online_spu.insert(5002);
-        let policy = SimplePolicy {};
+        let policy = SimplePolicy::new();
 
         assert_eq!(status.candidate_leader(&online_spu, &policy), Some(5001)); // 5001 has least lag
     }
@@ -546,20 +555,40 @@ mod test {
     /// check when we don't have any online
     #[test]
     fn test_candidate_spu_no_online() {
-        let status = PartitionStatus::new(
+        let status = in_sync(PartitionStatus::new(
             (5000, 100, 110),
             vec![
                 (5001, 95, 110).into(),  // caught up with leader  (best)
                 (5002, 100, 105).into(), // need 5 offset to caught with leaser
             ],
-        );
+        ));
 
         let online_spu = HashSet::new();
-        let policy = SimplePolicy {};
+        let policy = SimplePolicy::new();
 
         assert!(status.candidate_leader(&online_spu, &policy).is_none());
     }
 
+    /// replica which is not in sync is never a clean candidate, even with least lag
+    #[test]
+    fn test_candidate_spu_out_of_sync() {
+        let mut status = PartitionStatus::new(
+            (5000, 100, 110),
+            vec![
+                (5001, 100, 110).into(), // caught up with leader but dropped out of isr
+                (5002, 100, 105).into(), // lags but in sync
+            ],
+        );
+        status.replicas[1].in_sync = true;
+        let online_spu = HashSet::from([5001, 5002]);
+        let policy = SimplePolicy::new();
+
+        assert_eq!(status.candidate_leader(&online_spu, &policy), Some(5002));
+
+        status.replicas[1].in_sync = false;
+        assert!(status.candidate_leader(&online_spu, &policy).is_none());
+    }
+
     #[test]
     fn test_merge_initial() {
         let mut target = PartitionStatus::default();
@@ -575,6 +604,19 @@ mod test {
         assert_eq!(target.replicas[0], (5001, 9, 11).into());
     }
 
+    #[test]
+    fn test_merge_storage_usage() {
+        let mut target = PartitionStatus::leader((5000, 10, 11));
+        let mut source =
+            PartitionStatus::new2((5000, 20, 20), vec![], 1024, PartitionResolution::Online, 5);
+        source.segments = 3;
+        target.merge(source);
+
+        assert_eq!(target.size, 1024);
+        assert_eq!(target.base_offset, 5);
+        assert_eq!(target.segments, 3);
+    }
+
     #[test]
     fn test_merge_lrs_full() {
         let mut target = PartitionStatus::new(
@@ -620,6 +662,29 @@ mod test {
         assert_eq!(target.replicas.len(), 1);
         assert_eq!(target.replicas[0], (5001, 0, 0).into());
     }
+
+    #[test]
+    fn test_merge_in_sync_replicas() {
+        let mut target = PartitionStatus::new(
+            (5000, 100, 110),
+            vec![(5001, 100, 110).into(), (5002, 100, 110).into()],
+        );
+        target.leader.in_sync = true;
+        for replica in target.replicas.iter_mut() {
+            replica.in_sync = true;
+        }
+        assert_eq!(target.in_sync_replicas(), vec![5000, 5001, 5002]);
+
+        let mut leader: ReplicaStatus = (5000, 110, 150).into();
+        leader.in_sync = true;
+        let mut follower: ReplicaStatus = (5001, 110, 150).into();
+        follower.in_sync = true;
+        let source = PartitionStatus::new(leader, vec![follower, (5002, 100, 110).into()]);
+
+        target.merge(source);
+
+        assert_eq!(target.in_sync_replicas(), vec![5000, 5001]);
+    }
 }
 
 #[cfg(test)]
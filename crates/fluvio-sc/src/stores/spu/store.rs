@@ -56,6 +56,10 @@ where
 
     async fn online_spu_ids(&self) -> Vec<SpuId>;
 
+    async fn schedulable_spu_ids(&self) -> Vec<SpuId>;
+
+    async fn draining_spu_ids(&self) -> HashSet<SpuId>;
+
     async fn spu_ids(&self) -> Vec<SpuId>;
 
     async fn online_spus(&self) -> Vec<SpuMetadata<C>>;
@@ -129,6 +133,25 @@ where
             .collect()
     }
 
+    /// online spus which can be assigned new replicas
+    async fn schedulable_spu_ids(&self) -> Vec<SpuId> {
+        self.read()
+            .await
+            .values()
+            .filter(|spu| spu.status.is_online() && !spu.spec.is_draining())
+            .map(|spu| spu.spec.id)
+            .collect()
+    }
+
+    async fn draining_spu_ids(&self) -> HashSet<SpuId> {
+        self.read()
+            .await
+            .values()
+            .filter(|spu| spu.spec.is_draining())
+            .map(|spu| spu.spec.id)
+            .collect()
+    }
+
     async fn spu_ids(&self) -> Vec<SpuId> {
         let mut ids: Vec<SpuId> = self.read().await.values().map(|spu| spu.spec.id).collect();
         ids.sort_unstable();
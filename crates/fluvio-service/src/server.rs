@@ -6,6 +6,7 @@ use std::sync::Arc;
 use std::os::unix::io::AsRawFd;
 
 use futures_util::StreamExt;
+use futures_util::future::{Either, select};
 use async_trait::async_trait;
 use tracing::{instrument, debug, error, info};
 use anyhow::Result;
@@ -83,10 +84,15 @@ where
 {
     pub fn run(self) -> Arc<StickyEvent> {
         let shutdown = StickyEvent::shared();
-        spawn(self.accept_incoming(shutdown.clone()));
+        self.run_with_shutdown(shutdown.clone());
         shutdown
     }
 
+    /// serve until `shutdown` is notified, open connections are closed then
+    pub fn run_with_shutdown(self, shutdown: Arc<StickyEvent>) {
+        spawn(self.accept_incoming(shutdown));
+    }
+
     #[instrument(skip(shutdown))]
     async fn accept_incoming(self, shutdown: Arc<StickyEvent>) {
         debug!("Binding TcpListener");
@@ -109,7 +115,13 @@ where
                     let context = self.context.clone();
                     let service = self.service.clone();
                     let host = self.addr.clone();
-                    spawn(Self::handle_request(stream, context, service, host));
+                    spawn(Self::handle_request(
+                        stream,
+                        context,
+                        service,
+                        host,
+                        shutdown.clone(),
+                    ));
                 }
                 Err(e) => {
                     error!("Error from TCP Stream: {:?}", e);
@@ -120,8 +132,14 @@ where
         info!("Closed TcpListener");
     }
 
-    #[instrument(skip(stream, context, service))]
-    async fn handle_request(stream: TcpStream, context: C, service: Arc<S>, host: String) {
+    #[instrument(skip(stream, context, service, shutdown))]
+    async fn handle_request(
+        stream: TcpStream,
+        context: C,
+        service: Arc<S>,
+        host: String,
+        shutdown: Arc<StickyEvent>,
+    ) {
         let peer_addr = stream
             .peer_addr()
             .map(|addr| addr.to_string())
@@ -137,7 +155,14 @@ where
             peer: peer_addr.clone(),
         };
 
-        let result = service.respond(context, socket, connection_info).await;
+        let respond = service.respond(context, socket, connection_info);
+        let result = match select(respond, shutdown.listen_pinned()).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => {
+                info!(%host, %peer_addr, "server shutdown, closing connection");
+                return;
+            }
+        };
         match result {
             Ok(_) => {
                 info!(%host, %peer_addr, "Response sent successfully, closing connection");
@@ -247,4 +272,22 @@ mod test {
         assert_eq!(service.processed_requests.load(Ordering::SeqCst), 4);
         shutdown.notify();
     }
+
+    #[fluvio_future::test]
+    async fn test_shutdown_closes_connections() {
+        let port = portpicker::pick_unused_port().expect("No free ports left");
+        let socket_addr = format!("127.0.0.1:{port}");
+
+        let shutdown = create_server(socket_addr.clone()).run();
+        let mut socket = create_client(socket_addr.clone()).await;
+        let msg = RequestMessage::new_request(EchoRequest::new("hello".to_owned()));
+        let reply = socket.send(&msg).await.expect("send");
+        assert_eq!(reply.response.msg, "hello");
+
+        shutdown.notify();
+        sleep(Duration::from_millis(100)).await;
+
+        assert!(socket.send(&msg).await.is_err());
+        assert!(FluvioSocket::connect(&socket_addr).await.is_err());
+    }
 }
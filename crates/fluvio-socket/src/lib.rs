@@ -1,4 +1,5 @@
 mod error;
+mod limit;
 mod multiplexing;
 mod sink;
 mod socket;
@@ -12,6 +13,7 @@ pub mod test_request;
 pub use fluvio_future::net::{BoxConnection, Connection};
 pub use self::error::SocketError;
 pub use self::socket::FluvioSocket;
+pub use limit::*;
 pub use multiplexing::*;
 pub use sink::*;
 
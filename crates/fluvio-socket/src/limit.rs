@@ -0,0 +1,106 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use async_lock::{Semaphore, SemaphoreGuardArc};
+
+/// Called with the time a request waited for a free slot before being sent
+pub type QueueTimeObserver = Arc<dyn Fn(Duration) + Send + Sync>;
+
+/// Bounds the number of requests awaiting a response on a connection.
+///
+/// Requests beyond the limit wait in line until an earlier request is answered.
+/// Streams are not counted, they hold the connection for their whole lifetime.
+#[derive(Clone)]
+pub struct InFlightLimit {
+    permits: Arc<Semaphore>,
+    max: usize,
+    observer: Option<QueueTimeObserver>,
+}
+
+impl fmt::Debug for InFlightLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "InFlightLimit {}", self.max)
+    }
+}
+
+impl InFlightLimit {
+    /// limit of `max` requests, at least one request is always allowed
+    pub fn new(max: usize) -> Self {
+        let max = max.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(max)),
+            max,
+            observer: None,
+        }
+    }
+
+    /// report queueing time of every request to `observer`
+    pub fn with_observer(mut self, observer: QueueTimeObserver) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// wait for a free slot, which is held until the guard is dropped
+    pub(crate) async fn acquire(&self) -> SemaphoreGuardArc {
+        if let Some(guard) = self.permits.try_acquire_arc() {
+            self.observe(Duration::ZERO);
+            return guard;
+        }
+
+        let queued_at = Instant::now();
+        let guard = self.permits.acquire_arc().await;
+        self.observe(queued_at.elapsed());
+        guard
+    }
+
+    fn observe(&self, queue_time: Duration) {
+        if let Some(observer) = &self.observer {
+            observer(queue_time);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use fluvio_future::timer::sleep;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_in_flight_limit() {
+        let waits = Arc::new(Mutex::new(Vec::new()));
+        let recorded = waits.clone();
+        let limit = InFlightLimit::new(1).with_observer(Arc::new(move |wait| {
+            recorded.lock().expect("lock").push(wait);
+        }));
+
+        let first = limit.acquire().await;
+        let release = async {
+            sleep(Duration::from_millis(50)).await;
+            drop(first);
+        };
+        let (_, _second) = futures_util::future::join(release, limit.acquire()).await;
+
+        let waits = waits.lock().expect("lock");
+        assert_eq!(waits.len(), 2);
+        assert_eq!(waits[0], Duration::ZERO);
+        assert!(waits[1] >= Duration::from_millis(40), "{:?}", waits[1]);
+    }
+
+    #[test]
+    fn test_in_flight_limit_minimum() {
+        assert_eq!(InFlightLimit::new(0).max(), 1);
+    }
+}
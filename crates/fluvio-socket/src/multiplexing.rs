@@ -16,7 +16,7 @@ use std::future::Future;
 use async_channel::bounded;
 use async_channel::Receiver;
 use async_channel::Sender;
-use async_lock::Mutex;
+use async_lock::{Mutex, SemaphoreGuardArc};
 use bytes::Bytes;
 use event_listener::Event;
 use futures_util::ready;
@@ -34,6 +34,7 @@ use fluvio_protocol::api::RequestHeader;
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::Decoder;
 
+use crate::InFlightLimit;
 use crate::SocketError;
 use crate::ExclusiveFlvSink;
 use crate::FluvioSocket;
@@ -61,6 +62,7 @@ pub struct MultiplexerSocket {
     sink: ExclusiveFlvSink,
     stale: Arc<AtomicBool>,
     terminate: Arc<Event>,
+    in_flight: Option<InFlightLimit>,
 }
 
 impl fmt::Debug for MultiplexerSocket {
@@ -97,6 +99,7 @@ impl MultiplexerSocket {
             sink: ExclusiveFlvSink::new(sink),
             terminate: Arc::new(Event::new()),
             stale: stale.clone(),
+            in_flight: None,
         };
 
         MultiPlexingResponseDispatcher::run(
@@ -110,6 +113,23 @@ impl MultiplexerSocket {
         multiplexer
     }
 
+    /// bound requests awaiting a response, streams are not counted
+    pub fn with_in_flight_limit(mut self, limit: InFlightLimit) -> Self {
+        self.in_flight = Some(limit);
+        self
+    }
+
+    pub fn in_flight_limit(&self) -> Option<&InFlightLimit> {
+        self.in_flight.as_ref()
+    }
+
+    async fn acquire_in_flight(&self) -> Option<SemaphoreGuardArc> {
+        match &self.in_flight {
+            Some(limit) => Some(limit.acquire().await),
+            None => None,
+        }
+    }
+
     pub fn set_stale(&self) {
         self.stale.store(true, SeqCst);
     }
@@ -142,6 +162,7 @@ impl MultiplexerSocket {
             wait_time
         });
 
+        let _in_flight = self.acquire_in_flight().await;
         let correlation_id = self.next_correlation_id();
         let bytes_lock = SharedMsg(Arc::new(Mutex::new(None)), Arc::new(Event::new()));
 
@@ -224,7 +245,10 @@ impl MultiplexerSocket {
     where
         R: Request,
     {
-        self.create_stream(req_msg, 1).await
+        let in_flight = self.acquire_in_flight().await;
+        let mut response = self.create_stream(req_msg, 1).await?;
+        response.in_flight = in_flight;
+        Ok(response)
     }
 
     /// create stream response
@@ -270,6 +294,7 @@ impl MultiplexerSocket {
             receiver: Box::pin(receiver),
             header: req_msg.header,
             correlation_id,
+            in_flight: None,
             data: PhantomData,
         })
     }
@@ -283,6 +308,8 @@ pub struct AsyncResponse<R> {
     receiver: Pin<Box<Receiver<Option<Bytes>>>>,
     header: RequestHeader,
     correlation_id: i32,
+    /// slot of a request counted against in flight limit, released with response
+    in_flight: Option<SemaphoreGuardArc>,
     data: PhantomData<R>,
 }
 
@@ -331,7 +358,10 @@ impl<R: Request> Future for AsyncResponse<R> {
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.project();
-        match ready!(this.receiver.poll_next(cx)) {
+        let next = ready!(this.receiver.poll_next(cx));
+        // response arrived, let next queued request go
+        this.in_flight.take();
+        match next {
             Some(Some(bytes)) => {
                 use bytes::Buf;
                 let response_len = bytes.len();
@@ -1,5 +1,6 @@
 use std::default::Default;
 use std::fmt;
+use std::io::{Error as IoError, ErrorKind};
 use std::fmt::{Debug, Display};
 use std::ops::Deref;
 use std::sync::Arc;
@@ -82,7 +83,10 @@ impl VersionedSocket {
 /// Low level configuration option to directly connect to Fluvio
 /// This can bypass higher level validation required for CLI and end user application
 pub struct ClientConfig {
+    /// comma separated addresses to connect to
     addr: String,
+    /// address of `addr` the socket is connected to
+    connected_addr: Option<String>,
     client_id: String,
     connector: DomainConnector,
     use_spu_local_address: bool,
@@ -118,6 +122,7 @@ impl ClientConfig {
     ) -> Self {
         Self {
             addr: addr.into(),
+            connected_addr: None,
             client_id: "fluvio".to_owned(),
             connector,
             use_spu_local_address,
@@ -133,6 +138,11 @@ impl ClientConfig {
         &self.addr
     }
 
+    /// address the socket created by `connect` is connected to
+    pub fn connected_addr(&self) -> Option<&str> {
+        self.connected_addr.as_deref()
+    }
+
     pub fn client_id(&self) -> &str {
         &self.client_id
     }
@@ -153,13 +163,38 @@ impl ClientConfig {
         self.addr = domain
     }
 
+    /// addresses in comma separated `addr`, tried in order when connecting
+    fn endpoints(&self) -> Vec<String> {
+        self.addr
+            .split(',')
+            .map(str::trim)
+            .filter(|endpoint| !endpoint.is_empty())
+            .map(|endpoint| endpoint.to_owned())
+            .collect()
+    }
+
+    /// connect to first reachable address, all addresses are kept for reconnecting
     #[instrument(skip(self))]
-    pub async fn connect(self) -> Result<VersionedSocket, SocketError> {
-        debug!(add = %self.addr, "try connection to");
-        let socket =
-            FluvioSocket::connect_with_connector(&self.addr, self.connector.as_ref()).await?;
-        info!(add = %self.addr, "connect to socket");
-        VersionedSocket::connect(socket, Arc::new(self)).await
+    pub async fn connect(mut self) -> Result<VersionedSocket, SocketError> {
+        let mut last_err = None;
+        for endpoint in self.endpoints() {
+            debug!(add = %endpoint, "try connection to");
+            match FluvioSocket::connect_with_connector(&endpoint, self.connector.as_ref()).await {
+                Ok(socket) => {
+                    info!(add = %endpoint, "connect to socket");
+                    self.connected_addr = Some(endpoint);
+                    return VersionedSocket::connect(socket, Arc::new(self)).await;
+                }
+                Err(err) => {
+                    debug!(add = %endpoint, %err, "unable to connect");
+                    last_err = Some(err);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| SocketError::Io {
+            source: IoError::new(ErrorKind::InvalidInput, "no address to connect to"),
+            msg: format!("invalid address: {}", self.addr),
+        }))
     }
 
     /// create new config with prefix add to domain, this is useful for SNI
@@ -171,6 +206,7 @@ impl ClientConfig {
 
         Self {
             addr: self.addr.clone(),
+            connected_addr: None,
             client_id: self.client_id.clone(),
             connector,
             use_spu_local_address: self.use_spu_local_address,
@@ -180,6 +216,7 @@ impl ClientConfig {
     pub fn recreate(&self) -> Self {
         Self {
             addr: self.addr.clone(),
+            connected_addr: None,
             client_id: self.client_id.clone(),
             connector: self
                 .connector
@@ -398,6 +435,7 @@ mod test {
     use fluvio_protocol::link::versions::ApiVersionKey;
 
     use super::ApiVersionsResponse;
+    use super::ClientConfig;
     use super::Versions;
 
     #[derive(Encoder, Decoder, Default, Debug)]
@@ -422,6 +460,15 @@ mod test {
         type Response = u8;
     }
 
+    #[test]
+    fn test_client_config_endpoints() {
+        let config = ClientConfig::with_addr("sc-0:9003, sc-1:9003,,sc-2:9003".to_owned());
+        assert_eq!(
+            config.endpoints(),
+            vec!["sc-0:9003", "sc-1:9003", "sc-2:9003"]
+        );
+    }
+
     #[test]
     fn test_version_lookup() {
         let mut response = ApiVersionsResponse::default();
@@ -17,4 +17,16 @@ pub use fluvio_protocol::link::versions::{ApiVersions, ApiVersionsRequest, ApiVe
 pub use isolation::*;
 
 /// Default API version for all API
-pub const COMMON_VERSION: i16 = 25;
+pub const COMMON_VERSION: i16 = 31;
+
+/// First version of consumers decompressing records with zstd dictionaries.
+/// Older consumers get batches compressed without dictionary
+pub const DICTIONARY_API: i16 = 26;
+
+/// First version of consumers decoding mirror origin of batches replicated between clusters.
+/// Older consumers get batches with origin removed
+pub const MIRROR_ORIGIN_API: i16 = 27;
+
+/// First version carrying W3C trace context in produce and fetch requests.
+/// Consumers from this version decode records with headers, older ones get records without
+pub const TRACE_CONTEXT_API: i16 = 29;
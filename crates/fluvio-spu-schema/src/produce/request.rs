@@ -12,6 +12,7 @@ use fluvio_protocol::derive::FluvioDefault;
 use fluvio_protocol::Version;
 use fluvio_protocol::api::Request;
 use fluvio_protocol::record::RecordSet;
+use fluvio_protocol::record::TraceContext;
 use fluvio_types::PartitionId;
 
 use crate::COMMON_VERSION;
@@ -74,6 +75,10 @@ pub struct PartitionProduceData<R> {
 
     /// The record data to be produced.
     pub records: R,
+
+    /// Trace context of the producer span that created the records, empty if none.
+    #[fluvio(min_version = 29)]
+    pub trace_context: TraceContext,
 }
 
 impl<R> Encoder for ProduceRequest<R>
@@ -146,6 +151,7 @@ impl<R: Encoder + Decoder + Default + Debug + Clone> Clone for PartitionProduceD
         Self {
             partition_index: self.partition_index,
             records: self.records.clone(),
+            trace_context: self.trace_context.clone(),
         }
     }
 }
@@ -344,6 +350,7 @@ mod tests {
                                 .expect("compressed batch"),
                         ],
                     },
+                    ..Default::default()
                 }],
                 data: Default::default(),
             }],
@@ -25,6 +25,7 @@ use super::consumer_offset::{
     UpdateConsumerOffsetRequest, DeleteConsumerOffsetRequest, FetchConsumerOffsetsRequest,
 };
 use super::update_offset::UpdateOffsetsRequest;
+use super::stream_seek::StreamSeekRequest;
 use super::mirror::StartMirrorRequest;
 
 #[allow(clippy::large_enum_variant)]
@@ -41,6 +42,7 @@ pub enum SpuServerRequest {
     UpdateConsumerOffsetRequest(RequestMessage<UpdateConsumerOffsetRequest>),
     DeleteConsumerOffsetRequest(RequestMessage<DeleteConsumerOffsetRequest>),
     FetchConsumerOffsetsRequest(RequestMessage<FetchConsumerOffsetsRequest>),
+    StreamSeekRequest(RequestMessage<StreamSeekRequest>),
     StartMirrorRequest(RequestMessage<StartMirrorRequest>),
 }
 
@@ -56,6 +58,7 @@ impl fmt::Display for SpuServerRequest {
             Self::UpdateConsumerOffsetRequest(_) => write!(f, "UpdateConsumerOffsetRequest"),
             Self::DeleteConsumerOffsetRequest(_) => write!(f, "DeleteConsumerOffsetRequest"),
             Self::FetchConsumerOffsetsRequest(_) => write!(f, "FetchConsumerOffsetsRequest"),
+            Self::StreamSeekRequest(_) => write!(f, "StreamSeekRequest"),
             Self::StartMirrorRequest(_) => write!(f, "StartMirrorRequest"),
         }
     }
@@ -98,6 +101,7 @@ impl ApiMessage for SpuServerRequest {
             SpuServerApiKey::FetchConsumerOffsets => {
                 api_decode!(Self, FetchConsumerOffsetsRequest, src, header)
             }
+            SpuServerApiKey::StreamSeek => api_decode!(Self, StreamSeekRequest, src, header),
             SpuServerApiKey::StartMirror => api_decode!(Self, StartMirrorRequest, src, header),
         }
     }
@@ -23,6 +23,7 @@ pub enum SpuServerApiKey {
     UpdateConsumerOffset = 1006,
     DeleteConsumerOffset = 1007,
     FetchConsumerOffsets = 1008,
+    StreamSeek = 1009,
 
     StartMirror = 2000,
 }
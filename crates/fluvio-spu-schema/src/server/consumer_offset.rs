@@ -7,10 +7,22 @@ use crate::COMMON_VERSION;
 use crate::errors::ErrorCode;
 use super::SpuServerApiKey;
 
+/// version where consumer offsets can be mirrored between clusters
+pub const MIRRORED_CONSUMER_OFFSET_API: i16 = 27;
+
+/// version where an opaque metadata blob can be stored along with a consumer offset
+pub const CONSUMER_OFFSET_METADATA_API: i16 = 31;
+
+/// largest metadata blob accepted along with a consumer offset, in bytes
+pub const MAX_CONSUMER_OFFSET_METADATA_SIZE: usize = 4096;
+
 #[derive(Decoder, Encoder, Default, Debug)]
 pub struct UpdateConsumerOffsetRequest {
     pub offset: Offset,
     pub session_id: u32,
+    /// replaces the metadata stored with the offset, keeps it if none
+    #[fluvio(min_version = 31)]
+    pub metadata: Option<Vec<u8>>,
 }
 
 impl Request for UpdateConsumerOffsetRequest {
@@ -21,7 +33,16 @@ impl Request for UpdateConsumerOffsetRequest {
 
 impl UpdateConsumerOffsetRequest {
     pub fn new(offset: Offset, session_id: u32) -> Self {
-        Self { offset, session_id }
+        Self {
+            offset,
+            session_id,
+            metadata: None,
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: Vec<u8>) -> Self {
+        self.metadata = Some(metadata);
+        self
     }
 }
 
@@ -103,6 +124,23 @@ pub struct ConsumerOffset {
     pub replica_id: ReplicaKey,
     pub offset: Offset,
     pub modified_time: u64,
+    /// set when offset was mirrored from another cluster
+    #[fluvio(min_version = 27)]
+    pub mirror_source: Option<MirroredOffsetSource>,
+    /// opaque metadata committed with the offset
+    #[fluvio(min_version = 31)]
+    pub metadata: Vec<u8>,
+}
+
+/// Where a mirrored consumer offset came from
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct MirroredOffsetSource {
+    /// cluster the offset was committed on
+    pub cluster: String,
+    /// partition on source cluster
+    pub replica_id: ReplicaKey,
+    /// offset in source partition, before translation
+    pub offset: Offset,
 }
 
 impl ConsumerOffset {
@@ -117,6 +155,54 @@ impl ConsumerOffset {
             replica_id: replica_id.into(),
             offset,
             modified_time,
+            mirror_source: None,
+            metadata: Vec::new(),
         }
     }
+
+    pub fn with_metadata(mut self, metadata: Vec<u8>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn with_mirror_source(mut self, mirror_source: Option<MirroredOffsetSource>) -> Self {
+        self.mirror_source = mirror_source;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_update_consumer_offset_metadata_versions() {
+        let request = UpdateConsumerOffsetRequest::new(10, 1).with_metadata(b"meta".to_vec());
+
+        let mut dest = Vec::new();
+        request
+            .encode(&mut dest, CONSUMER_OFFSET_METADATA_API)
+            .expect("should encode");
+        let decoded = UpdateConsumerOffsetRequest::decode_from(
+            &mut std::io::Cursor::new(dest),
+            CONSUMER_OFFSET_METADATA_API,
+        )
+        .expect("should decode");
+        assert_eq!(decoded.offset, 10);
+        assert_eq!(decoded.metadata, Some(b"meta".to_vec()));
+
+        // older clients never send metadata, so the stored one is kept
+        let mut dest = Vec::new();
+        request
+            .encode(&mut dest, CONSUMER_OFFSET_METADATA_API - 1)
+            .expect("should encode");
+        let decoded = UpdateConsumerOffsetRequest::decode_from(
+            &mut std::io::Cursor::new(dest),
+            CONSUMER_OFFSET_METADATA_API - 1,
+        )
+        .expect("should decode");
+        assert_eq!(decoded.offset, 10);
+        assert_eq!(decoded.metadata, None);
+    }
 }
@@ -4,6 +4,9 @@ use crate::COMMON_VERSION;
 
 use super::SpuServerApiKey;
 
+/// version where home sends link settings and records can be compressed on the wire
+pub const MIRROR_LINK_API: i16 = 28;
+
 /// Request to start mirror request
 /// After this, SPU to SPU will use internal mirror protocol
 /// This should be moved to Fluvio
@@ -11,6 +14,9 @@ use super::SpuServerApiKey;
 pub struct StartMirrorRequest {
     pub remote_replica: String,
     pub remote_cluster_id: String,
+    /// id of home cluster as known by remote, used to stamp origin of bidirectional mirror records
+    #[fluvio(min_version = 26)]
+    pub home_cluster_id: String,
 }
 
 impl Request for StartMirrorRequest {
@@ -13,6 +13,7 @@ use fluvio_protocol::record::RawRecords;
 use fluvio_protocol::{Encoder, Decoder};
 use fluvio_protocol::api::Request;
 use fluvio_protocol::record::RecordSet;
+use fluvio_protocol::record::TraceContext;
 use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
 use fluvio_types::{PartitionId, defaults::FLUVIO_CLIENT_MAX_FETCH_BYTES};
 
@@ -54,6 +55,11 @@ pub const SMARTMODULE_TIMESTAMP: i16 = 22;
 
 pub const OFFSET_MANAGEMENT_API: i16 = 23;
 
+pub const FOLLOWER_FETCH_API: i16 = 26;
+
+/// Streams can be repositioned with [`StreamSeekRequest`](super::stream_seek::StreamSeekRequest)
+pub const STREAM_SEEK_API: i16 = 30;
+
 /// Fetch records continuously
 /// Output will be send back as stream
 #[allow(deprecated)]
@@ -87,6 +93,14 @@ pub struct StreamFetchRequest<R> {
     #[builder(default)]
     #[fluvio(min_version = 23)]
     pub consumer_id: Option<String>,
+    /// allow in sync follower to serve committed records if SPU is not leader
+    #[builder(default)]
+    #[fluvio(min_version = 26)]
+    pub read_from_follower: bool,
+    /// trace context of the consumer span that opened the stream, empty if none
+    #[builder(default)]
+    #[fluvio(min_version = 29)]
+    pub trace_context: TraceContext,
     #[builder(setter(skip))]
     data: PhantomData<R>,
 }
@@ -119,6 +133,9 @@ pub(crate) struct DerivedStreamInvocation {
 pub struct StreamFetchResponse<R> {
     pub topic: String,
     pub stream_id: u32,
+    /// seek epoch the records were read in, 0 until the stream is repositioned
+    #[fluvio(min_version = 30)]
+    pub seek_epoch: u32,
     pub partition: FetchablePartitionResponse<R>,
 }
 
@@ -153,6 +170,9 @@ mod file {
             trace!("topic {}", self.topic);
             self.topic.encode(src, version)?;
             self.stream_id.encode(src, version)?;
+            if version >= STREAM_SEEK_API {
+                self.seek_epoch.encode(src, version)?;
+            }
             self.partition.file_encode(src, data, version)?;
             Ok(())
         }
@@ -164,7 +184,9 @@ mod tests {
 
     use fluvio_smartmodule::dataplane::smartmodule::Lookback;
 
-    use crate::server::smartmodule::{SmartModuleInvocationWasm, SmartModuleKind};
+    use crate::server::smartmodule::{
+        COMMON_VERSION_HAS_SM_NAME, SmartModuleInvocationWasm, SmartModuleKind,
+    };
 
     use super::*;
 
@@ -230,7 +252,8 @@ mod tests {
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0, 10, 116, 101, 115, 116, 45, 97, 100, 104,
-            111, 99, 0,
+            111, 99, 0, 0, // empty trace context
+            0, 0, 0, 0,
         ];
         assert_eq!(dest, expected);
     }
@@ -255,7 +278,7 @@ mod tests {
             ..Default::default()
         };
         value
-            .encode(&mut dest, DefaultStreamFetchRequest::MAX_API_VERSION - 1)
+            .encode(&mut dest, COMMON_VERSION_HAS_SM_NAME - 1)
             .expect("should encode");
         let expected = vec![
             // Pre sm name encoding
@@ -307,7 +330,7 @@ mod tests {
             0x00, 0x03, 0x6f, 0x6e, 0x65, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00,
             0x00, 0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00,
         ];
         let mut value = DefaultStreamFetchRequest::default();
         value
@@ -344,7 +367,7 @@ mod tests {
         value
             .decode(
                 &mut std::io::Cursor::new(bytes),
-                DefaultStreamFetchRequest::MAX_API_VERSION - 1,
+                COMMON_VERSION_HAS_SM_NAME - 1,
             )
             .unwrap();
         assert_eq!(value.topic, "one");
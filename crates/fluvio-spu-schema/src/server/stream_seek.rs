@@ -0,0 +1,45 @@
+//!
+//! # Stream Seek
+//!
+//! Repositions a running fetch stream without opening a new one.
+//!
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::Offset;
+use fluvio_protocol::{Encoder, Decoder};
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// Restart a stream from `offset`.
+///
+/// Records sent after the seek are tagged with `epoch` so that the client can
+/// drop responses which were already in flight when it issued the seek.
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct StreamSeekRequest {
+    pub session_id: u32,
+    pub offset: Offset,
+    pub epoch: u32,
+}
+
+impl Request for StreamSeekRequest {
+    const API_KEY: u16 = SpuServerApiKey::StreamSeek as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = StreamSeekResponse;
+}
+
+impl StreamSeekRequest {
+    pub fn new(session_id: u32, offset: Offset, epoch: u32) -> Self {
+        Self {
+            session_id,
+            offset,
+            epoch,
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct StreamSeekResponse {
+    pub error_code: ErrorCode,
+}
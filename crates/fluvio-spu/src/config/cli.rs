@@ -14,7 +14,7 @@ use clap::Parser;
 use fluvio_types::print_cli_err;
 use fluvio_types::SpuId;
 use fluvio_future::rust_tls::TlsAcceptor;
-use fluvio_types::defaults::SPU_PEER_MAX_BYTES;
+use fluvio_types::defaults::{SPU_PEER_MAX_BYTES, SPU_MIN_IN_SYNC_REPLICAS, SPU_REPLICA_MAX_LAG_MS};
 
 use super::SpuConfig;
 
@@ -34,7 +34,7 @@ pub struct SpuOpt {
     /// Spu server for internal cluster communication
     pub bind_private: Option<String>,
 
-    /// Address of the SC Server
+    /// Address of the SC Server, comma separated addresses of all SC instances when SC is replicated
     #[arg(long, value_name = "host:port", env = "FLV_SC_PRIVATE_HOST")]
     pub sc_addr: Option<String>,
 
@@ -50,6 +50,10 @@ pub struct SpuOpt {
     #[arg(long, value_name = "integer", env = "FLV_LOG_INDEX_MAX_INTERVAL_BYTES")]
     pub index_max_interval_bytes: Option<u32>,
 
+    /// directory of master keys used to encrypt topics at rest, files are named `<key_id>.<version>.key`
+    #[arg(long, value_name = "dir", env = "FLV_ENCRYPTION_KEY_DIR")]
+    pub encryption_key_dir: Option<String>,
+
     /// max bytes to transfer between leader and follower
     #[arg(
         long,
@@ -59,6 +63,28 @@ pub struct SpuOpt {
     )]
     pub peer_max_bytes: u32,
 
+    /// min in sync replicas for topics which doesn't set their own
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_MIN_IN_SYNC_REPLICAS",
+        default_value_t = SPU_MIN_IN_SYNC_REPLICAS
+    )]
+    pub min_in_sync_replicas: u16,
+
+    /// max time in milliseconds a follower can lag behind leader before it is dropped from in sync replicas
+    #[arg(
+        long,
+        value_name = "integer",
+        env = "FLV_REPLICA_MAX_LAG_MS",
+        default_value_t = SPU_REPLICA_MAX_LAG_MS
+    )]
+    pub replica_max_lag_ms: u64,
+
+    /// max records a follower can lag behind leader before it is dropped from in sync replicas
+    #[arg(long, value_name = "integer", env = "FLV_REPLICA_MAX_LAG_RECORDS")]
+    pub replica_max_lag_records: Option<u64>,
+
     #[arg(
         long,
         value_name = "integer",
@@ -66,6 +92,10 @@ pub struct SpuOpt {
     )]
     pub smart_engine_max_memory: Option<usize>,
 
+    /// address to serve OpenMetrics at `/metrics`, disabled if not set
+    #[arg(long, value_name = "host:port", env = "FLV_METRICS_ADDR")]
+    pub metrics_addr: Option<String>,
+
     #[clap(flatten)]
     tls: TlsConfig,
 }
@@ -119,6 +149,11 @@ impl SpuOpt {
             config.log.index_max_interval_bytes = index_max_interval_bytes;
         }
 
+        if let Some(encryption_key_dir) = self.encryption_key_dir {
+            info!("using encryption key dir: {}", encryption_key_dir);
+            config.log.encryption_key_dir = Some(PathBuf::from(encryption_key_dir));
+        }
+
         if let Some(public_addr) = self.bind_public {
             info!("overriding public addr: {}", public_addr);
             config.public_endpoint = public_addr;
@@ -142,6 +177,9 @@ impl SpuOpt {
         }
 
         config.peer_max_bytes = self.peer_max_bytes;
+        config.replication.min_in_sync_replicas = self.min_in_sync_replicas;
+        config.replication.max_lag_ms = self.replica_max_lag_ms;
+        config.replication.max_lag_records = self.replica_max_lag_records;
 
         if let Some(smart_engine_max_memory) = self.smart_engine_max_memory {
             info!(
@@ -151,6 +189,11 @@ impl SpuOpt {
             config.smart_engine.store_max_memory = smart_engine_max_memory;
         }
 
+        if let Some(metrics_addr) = self.metrics_addr {
+            info!("using metrics addr: {}", metrics_addr);
+            config.metrics_endpoint = Some(metrics_addr);
+        }
+
         Ok((config, tls_port))
     }
 
@@ -25,7 +25,7 @@ use fluvio_types::defaults::SPU_SMARTENGINE_STORE_MAX_BYTES;
 
 // environment variables
 
-use fluvio_types::defaults::SPU_MIN_IN_SYNC_REPLICAS;
+use fluvio_types::defaults::{SPU_MIN_IN_SYNC_REPLICAS, SPU_REPLICA_MAX_LAG_MS};
 use fluvio_types::defaults::FLV_LOG_BASE_DIR;
 use fluvio_types::defaults::FLV_LOG_SIZE;
 use fluvio_types::SpuId;
@@ -36,13 +36,20 @@ use fluvio_types::defaults::{
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct ReplicationConfig {
+    /// used for topics which doesn't set their own min in sync replicas
     pub min_in_sync_replicas: u16,
+    /// follower is dropped from in sync replicas if it hasn't caught up with leader for this long
+    pub max_lag_ms: u64,
+    /// follower is dropped from in sync replicas if it is behind leader by more than this many records
+    pub max_lag_records: Option<u64>,
 }
 
 impl Default for ReplicationConfig {
     fn default() -> Self {
         Self {
             min_in_sync_replicas: SPU_MIN_IN_SYNC_REPLICAS,
+            max_lag_ms: SPU_REPLICA_MAX_LAG_MS,
+            max_lag_records: None,
         }
     }
 }
@@ -57,6 +64,8 @@ pub struct Log {
     pub flush_write_count: u32,
     pub flush_idle_msec: u32,
     pub max_batch_size: u32,
+    /// directory of master keys for encrypted topics
+    pub encryption_key_dir: Option<PathBuf>,
 }
 
 impl Default for Log {
@@ -72,6 +81,7 @@ impl Default for Log {
             flush_write_count: STORAGE_FLUSH_WRITE_COUNT,
             flush_idle_msec: STORAGE_FLUSH_IDLE_MSEC,
             max_batch_size: STORAGE_MAX_BATCH_SIZE,
+            encryption_key_dir: None,
         }
     }
 }
@@ -100,7 +110,7 @@ pub struct SpuConfig {
     pub public_endpoint: String,
     pub private_endpoint: String,
 
-    // sc (remote server) endpoint
+    // sc (remote server) endpoint, comma separated list when SC is replicated
     pub sc_endpoint: String,
     pub sc_retry_ms: u16,
 
@@ -111,6 +121,9 @@ pub struct SpuConfig {
     pub peer_max_bytes: u32,
 
     pub smart_engine: SmartEngineConfig,
+
+    // OpenMetrics http endpoint, disabled if not set
+    pub metrics_endpoint: Option<String>,
 }
 
 impl Default for SpuConfig {
@@ -126,6 +139,7 @@ impl Default for SpuConfig {
             log: Log::default(),
             peer_max_bytes: fluvio_storage::FileReplica::PREFER_MAX_LEN,
             smart_engine: SmartEngineConfig::default(),
+            metrics_endpoint: None,
         }
     }
 }
@@ -140,8 +154,13 @@ impl SpuConfig {
         &self.rack
     }
 
-    pub fn sc_endpoint(&self) -> &str {
-        &self.sc_endpoint
+    /// SC endpoints to try in order, only leader SC accepts connections
+    pub fn sc_endpoints(&self) -> Vec<&str> {
+        self.sc_endpoint
+            .split(',')
+            .map(str::trim)
+            .filter(|endpoint| !endpoint.is_empty())
+            .collect()
     }
 
     pub fn public_socket_addr(&self) -> &str {
@@ -174,6 +193,7 @@ impl From<&SpuConfig> for ReplicaConfig {
             .flush_write_count(log.flush_write_count)
             .flush_idle_msec(log.flush_idle_msec)
             .max_batch_size(log.max_batch_size)
+            .encryption_key_dir(log.encryption_key_dir.clone())
             .build()
     }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 use std::fmt::Debug;
 
@@ -8,7 +8,7 @@ use futures_util::stream::StreamExt;
 use anyhow::{anyhow, Result};
 
 use fluvio_controlplane::sc_api::register_spu::RegisterSpuRequest;
-use fluvio_controlplane::sc_api::update_lrs::UpdateLrsRequest;
+use fluvio_controlplane::sc_api::update_lrs::{LrsConsumerOffset, LrsRequest, UpdateLrsRequest};
 use fluvio_controlplane::spu_api::api::{InternalSpuRequest, InternalSpuApi};
 use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
@@ -18,10 +18,13 @@ use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::Encoder as FlvEncoder;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
 use fluvio_socket::{FluvioSocket, FluvioSink};
 use fluvio_storage::FileReplica;
 use fluvio_controlplane::sc_api::update_mirror::UpdateMirrorStatRequest;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
+use fluvio_controlplane::spu_api::update_dictionary::UpdateDictionaryRequest;
 use fluvio_controlplane::sc_api::update_partition::UpdatePartitionStatRequest;
 
 use crate::core::SharedGlobalContext;
@@ -37,6 +40,7 @@ struct DispatcherCounter {
     pub reconnect: u64,       // number of reconnect to sc
     pub smartmodule: u64,     // number of sm updates from sc
     pub mirror: u64,          // number of mirror updates from sc
+    pub dictionary: u64,      // number of dictionary updates from sc
 }
 
 /// Controller for handling connection to SC
@@ -47,6 +51,52 @@ pub struct ScDispatcher<S> {
     mirror_status_update: SharedMirrorStatusUpdate,
     partition_status_update: SharedPartitionStatusUpdate,
     counter: DispatcherCounter,
+    /// index of SC endpoint last connected to
+    sc_endpoint_index: usize,
+    reported_consumers: ReportedConsumerOffsets,
+}
+
+/// Consumer offsets last reported to SC, so only changed offsets are sent
+#[derive(Debug, Default)]
+struct ReportedConsumerOffsets {
+    offsets: HashMap<(ReplicaKey, String), LrsConsumerOffset>,
+    /// SC asked for all offsets
+    snapshot: bool,
+}
+
+impl ReportedConsumerOffsets {
+    fn request_snapshot(&mut self) {
+        self.snapshot = true;
+    }
+
+    /// Offsets to report out of current ones, and whether they are only the changed ones.
+    /// All offsets are reported when SC asked for them or when offset was removed
+    fn report(&mut self, current: Vec<LrsConsumerOffset>) -> (Vec<LrsConsumerOffset>, bool) {
+        let current: HashMap<_, _> = current
+            .into_iter()
+            .map(|offset| {
+                (
+                    (offset.replica_id.clone(), offset.consumer_id.clone()),
+                    offset,
+                )
+            })
+            .collect();
+
+        let removed = self.offsets.keys().any(|key| !current.contains_key(key));
+        if self.snapshot || removed {
+            self.snapshot = false;
+            self.offsets = current;
+            return (self.offsets.values().cloned().collect(), false);
+        }
+
+        let changed: Vec<_> = current
+            .into_iter()
+            .filter(|(key, offset)| self.offsets.get(key) != Some(offset))
+            .collect();
+        let report = changed.iter().map(|(_, offset)| offset.clone()).collect();
+        self.offsets.extend(changed);
+        (report, true)
+    }
 }
 
 impl ScDispatcher<FileReplica> {
@@ -57,6 +107,8 @@ impl ScDispatcher<FileReplica> {
             partition_status_update: ctx.partition_status_update_owned(),
             ctx,
             counter: DispatcherCounter::default(),
+            sc_endpoint_index: 0,
+            reported_consumers: ReportedConsumerOffsets::default(),
         }
     }
 
@@ -174,6 +226,10 @@ impl ScDispatcher<FileReplica> {
                                 break;
                             }
                         },
+                        Some(Ok(InternalSpuRequest::UpdateDictionaryRequest(request))) => {
+                            self.counter.dictionary += 1;
+                            self.handle_update_dictionary_request(request);
+                        },
                         Some(Err(err)) => {
                             error!(%err, "Api error");
                             break;
@@ -196,7 +252,8 @@ impl ScDispatcher<FileReplica> {
     /// send lrs status back to sc
     #[instrument(skip(self))]
     async fn send_lrs_status_back_to_sc(&mut self, sc_sink: &mut FluvioSink) -> Result<()> {
-        let requests = self.lrs_status_update.remove_all().await;
+        let mut requests = self.lrs_status_update.remove_all().await;
+        self.attach_consumer_offsets(&mut requests).await;
 
         Self::send_unique_status(requests, sc_sink, |unique_requests| {
             RequestMessage::new_request(UpdateLrsRequest::new(unique_requests))
@@ -204,6 +261,54 @@ impl ScDispatcher<FileReplica> {
         .await
     }
 
+    /// attach committed consumer offsets to status of consumer offsets partition,
+    /// so SC can report consumer lag. Only offsets changed since last report are sent
+    /// unless SC asked for all of them
+    async fn attach_consumer_offsets(&mut self, requests: &mut Vec<LrsRequest>) {
+        let consumer_replica: ReplicaKey = CONSUMER_REPLICA_KEY.into();
+        let Some(replica) = self.ctx.leaders_state().get(&consumer_replica).await else {
+            return;
+        };
+        let index = match requests.iter().position(|r| r.id == consumer_replica) {
+            Some(index) => index,
+            // snapshot is sent without waiting for change of partition status
+            None if self.reported_consumers.snapshot => {
+                requests.push(replica.as_lrs_request().await);
+                requests.len() - 1
+            }
+            None => return,
+        };
+        let request = &mut requests[index];
+
+        let consumers = match self
+            .ctx
+            .consumer_offset()
+            .get_or_insert(&replica, self.ctx.follower_notifier())
+            .await
+        {
+            Ok(storage) => storage.list().await,
+            Err(err) => Err(err),
+        };
+        match consumers {
+            Ok(consumers) => {
+                let consumers = consumers
+                    .into_iter()
+                    .map(|(key, value)| LrsConsumerOffset {
+                        replica_id: key.replica_id,
+                        consumer_id: key.consumer_id,
+                        offset: value.offset,
+                        modified_time: value.modified_time,
+                    })
+                    .collect();
+                (request.consumers, request.consumers_delta) =
+                    self.reported_consumers.report(consumers);
+            }
+            Err(err) => {
+                warn!(%err, "unable to list consumer offsets");
+            }
+        }
+    }
+
     // send partition status back to sc
     #[instrument(skip(self))]
     async fn send_partition_status_back_to_sc(&mut self, sc_sink: &mut FluvioSink) -> Result<()> {
@@ -260,7 +365,7 @@ impl ScDispatcher<FileReplica> {
         skip(self),
         fields(socket = socket.id())
     )]
-    async fn send_spu_registration(&self, socket: &mut FluvioSocket) -> Result<bool> {
+    async fn send_spu_registration(&mut self, socket: &mut FluvioSocket) -> Result<bool> {
         let local_spu_id = self.ctx.local_spu_id();
 
         debug!(%local_spu_id, "sending spu registration request",);
@@ -287,6 +392,10 @@ impl ScDispatcher<FileReplica> {
         } else {
             info!(local_spu_id, "spu registration successful");
 
+            if register_resp.consumer_offsets_snapshot() {
+                self.reported_consumers.request_snapshot();
+            }
+
             Ok(true)
         }
     }
@@ -295,28 +404,39 @@ impl ScDispatcher<FileReplica> {
     /// or if we received termination message
     async fn create_socket_to_sc(&mut self) -> FluvioSocket {
         let spu_id = self.ctx.local_spu_id();
-        let sc_endpoint = self.ctx.config().sc_endpoint().to_string();
+        let config = self.ctx.config();
+        let sc_endpoints: Vec<String> = config
+            .sc_endpoints()
+            .into_iter()
+            .map(|endpoint| endpoint.to_owned())
+            .collect();
 
-        let wait_interval = self.ctx.config().sc_retry_ms;
+        let wait_interval = config.sc_retry_ms;
         loop {
-            info!(
-                %sc_endpoint,
-                spu_id,
-                "trying to create socket to sc",
-
-            );
-            match FluvioSocket::connect(&sc_endpoint).await {
-                Ok(socket) => {
-                    info!(spu_id, "connected to sc for spu");
-                    self.counter.reconnect += 1;
-                    return socket;
-                }
-                Err(err) => {
-                    warn!("error connecting to sc: {}", err);
-                    info!(wait_interval, spu_id, "sleeping ms");
-                    sleep(Duration::from_millis(wait_interval as u64)).await;
+            // start with last connected SC, followed by others in order
+            for attempt in 0..sc_endpoints.len() {
+                let index = (self.sc_endpoint_index + attempt) % sc_endpoints.len();
+                let sc_endpoint = &sc_endpoints[index];
+                info!(
+                    %sc_endpoint,
+                    spu_id,
+                    "trying to create socket to sc",
+
+                );
+                match FluvioSocket::connect(sc_endpoint).await {
+                    Ok(socket) => {
+                        info!(spu_id, %sc_endpoint, "connected to sc for spu");
+                        self.counter.reconnect += 1;
+                        self.sc_endpoint_index = index;
+                        return socket;
+                    }
+                    Err(err) => {
+                        warn!(%sc_endpoint, "error connecting to sc: {}", err);
+                    }
                 }
             }
+            info!(wait_interval, spu_id, "sleeping ms");
+            sleep(Duration::from_millis(wait_interval as u64)).await;
         }
     }
 
@@ -461,4 +581,78 @@ impl ScDispatcher<FileReplica> {
 
         Ok(())
     }
+
+    ///
+    /// Handle compression dictionary update sent by SC
+    ///
+    #[instrument(skip(self, req_msg), name = "update_dictionary_request")]
+    fn handle_update_dictionary_request(
+        &mut self,
+        req_msg: RequestMessage<UpdateDictionaryRequest>,
+    ) {
+        let (_, request) = req_msg.get_header_request();
+
+        let actions = if !request.all.is_empty() {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.all.len(),
+                "received dictionary sync all"
+            );
+            self.ctx.dictionaries_localstore().sync_all(request.all)
+        } else {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.changes.len(),
+                "received dictionary changes"
+            );
+            self.ctx
+                .dictionaries_localstore()
+                .apply_changes(request.changes)
+        };
+
+        debug!(actions = actions.count(), "finished dictionary update");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn offset(consumer: &str, offset: i64) -> LrsConsumerOffset {
+        LrsConsumerOffset {
+            replica_id: ReplicaKey::new("topic", 0),
+            consumer_id: consumer.to_owned(),
+            offset,
+            modified_time: 0,
+        }
+    }
+
+    #[test]
+    fn test_reported_consumer_offsets() {
+        let mut reported = ReportedConsumerOffsets::default();
+        reported.request_snapshot();
+
+        let (report, delta) = reported.report(vec![offset("c1", 1), offset("c2", 2)]);
+        assert!(!delta);
+        assert_eq!(report.len(), 2);
+
+        // only changed offsets
+        let (report, delta) = reported.report(vec![offset("c1", 1), offset("c2", 3)]);
+        assert!(delta);
+        assert_eq!(report, vec![offset("c2", 3)]);
+
+        let (report, delta) = reported.report(vec![offset("c1", 1), offset("c2", 3)]);
+        assert!(delta);
+        assert!(report.is_empty());
+
+        // removed offset is reported with all of them
+        let (report, delta) = reported.report(vec![offset("c2", 3)]);
+        assert!(!delta);
+        assert_eq!(report, vec![offset("c2", 3)]);
+
+        reported.request_snapshot();
+        let (report, delta) = reported.report(vec![offset("c2", 3)]);
+        assert!(!delta);
+        assert_eq!(report.len(), 1);
+    }
 }
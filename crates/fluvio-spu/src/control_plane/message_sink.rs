@@ -7,7 +7,9 @@ use async_lock::Mutex;
 use fluvio_controlplane::sc_api::update_lrs::LrsRequest;
 use fluvio_controlplane::sc_api::update_partition::PartitionStatRequest;
 use fluvio_controlplane::sc_api::update_mirror::MirrorStatRequest;
-use fluvio_controlplane_metadata::mirror::{MirrorPairStatus, MirrorStatus};
+use fluvio_controlplane_metadata::mirror::{
+    MirrorDirection, MirrorLinkStat, MirrorPairStatus, MirrorStatus,
+};
 
 pub type SharedLrsStatusUpdate = Arc<StatusLrsMessageSink>;
 pub type SharedPartitionStatusUpdate = Arc<StatusPartitionMessageSink>;
@@ -44,11 +46,39 @@ where
 
 impl StatusMirrorMessageSink {
     pub async fn send_status(&self, id: String, pair_status: MirrorPairStatus) -> Result<()> {
+        self.send_link_status(id, pair_status, None).await
+    }
+
+    /// status including traffic sent over mirror link
+    pub async fn send_link_status(
+        &self,
+        id: String,
+        pair_status: MirrorPairStatus,
+        link: Option<MirrorLinkStat>,
+    ) -> Result<()> {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_millis();
+
+        let status = MirrorStatus::new_by_spu_link(pair_status, now as u64, link);
+        self.send(MirrorStatRequest::new(id, status)).await;
+        Ok(())
+    }
+
+    /// status of one replication direction of bidirectional mirror
+    pub async fn send_direction_status(
+        &self,
+        id: String,
+        direction: MirrorDirection,
+        pair_status: MirrorPairStatus,
+        link: Option<MirrorLinkStat>,
+    ) -> Result<()> {
         let now = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)?
             .as_millis();
 
-        let status = MirrorStatus::new_by_spu(pair_status, now as u64);
+        let mut status = MirrorStatus::new_by_spu_direction(direction, pair_status, now as u64);
+        status.connection_stat.link = link;
         self.send(MirrorStatRequest::new(id, status)).await;
         Ok(())
     }
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use fluvio_compression::Dictionary as CompressionDictionary;
+use fluvio_controlplane::replica::Replica;
+use fluvio_controlplane::spu_api::update_dictionary::Dictionary;
+use fluvio_protocol::link::ErrorCode;
+
+use crate::core::Spec;
+use crate::core::LocalStore;
+
+pub type DictionaryLocalStore = LocalStore<Dictionary>;
+
+pub type SharedDictionaryLocalStore = Arc<DictionaryLocalStore>;
+
+impl Spec for Dictionary {
+    const LABEL: &'static str = "Dictionary";
+
+    type Key = String;
+
+    fn key(&self) -> &Self::Key {
+        &self.name
+    }
+
+    fn key_owned(&self) -> Self::Key {
+        self.name.clone()
+    }
+}
+
+impl LocalStore<Dictionary> {
+    /// zstd dictionary used to compress records of the replica, if topic has one
+    pub fn for_replica(
+        &self,
+        replica: &Replica,
+    ) -> Result<Option<CompressionDictionary>, ErrorCode> {
+        let Some(name) = &replica.compression_dictionary else {
+            return Ok(None);
+        };
+        let dictionary = self.spec(name).ok_or(ErrorCode::DictionaryNotFound)?;
+        CompressionDictionary::new(dictionary.spec.data.to_vec())
+            .map(Some)
+            .map_err(|_| ErrorCode::DictionaryError)
+    }
+}
@@ -3,13 +3,17 @@
 //!
 //! Global Context maintains states need to be shared across in the SPU
 
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::fmt::Debug;
+use std::collections::HashMap;
 
-use tracing::{debug, error, instrument};
+use tracing::{debug, error, info, instrument};
 
 use fluvio_types::SpuId;
 use fluvio_storage::ReplicaStorage;
+use fluvio_compression::Dictionary;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
 
 use crate::config::SpuConfig;
 use crate::control_plane::SharedMirrorStatusUpdate;
@@ -24,9 +28,12 @@ use crate::replication::leader::{
 };
 use crate::control_plane::{StatusLrsMessageSink, SharedLrsStatusUpdate};
 use crate::core::metrics::SpuMetrics;
+use crate::mirroring::link::MirrorRateLimit;
 use crate::smartengine::SmartEngine;
 
 use super::leader_client::LeaderConnections;
+use super::dictionary::DictionaryLocalStore;
+use super::dictionary::SharedDictionaryLocalStore;
 use super::mirror::MirrorLocalStore;
 use super::mirror::SharedMirrorLocalStore;
 use super::smartmodule::SmartModuleLocalStore;
@@ -54,7 +61,9 @@ pub struct GlobalContext<S> {
     sm_engine: SmartEngine,
     leaders: Arc<LeaderConnections>,
     mirrors: SharedMirrorLocalStore,
+    dictionaries: SharedDictionaryLocalStore,
     metrics: Arc<SpuMetrics>,
+    mirror_rate_limits: RwLock<HashMap<String, Arc<MirrorRateLimit>>>,
     consumer_offset: SharedConsumerOffsetStorages,
 }
 
@@ -89,7 +98,9 @@ where
             sm_engine: SmartEngine::new(),
             leaders: LeaderConnections::shared(spus, replicas),
             mirrors: MirrorLocalStore::new_shared(),
+            dictionaries: DictionaryLocalStore::new_shared(),
             metrics,
+            mirror_rate_limits: RwLock::new(HashMap::new()),
             consumer_offset: SharedConsumerOffsetStorages::default(),
         }
     }
@@ -111,6 +122,10 @@ where
         &self.replica_localstore
     }
 
+    pub fn replica_localstore_owned(&self) -> SharedReplicaLocalStore {
+        self.replica_localstore.clone()
+    }
+
     pub fn smartmodule_localstore(&self) -> &SmartModuleLocalStore {
         &self.smartmodule_localstore
     }
@@ -123,10 +138,29 @@ where
         self.mirrors.clone()
     }
 
+    pub fn dictionaries_localstore(&self) -> &DictionaryLocalStore {
+        &self.dictionaries
+    }
+
+    /// zstd dictionary used to compress records of the replica, if topic has one
+    pub fn replica_dictionary(
+        &self,
+        replica: &ReplicaKey,
+    ) -> Result<Option<Dictionary>, ErrorCode> {
+        match self.replica_localstore.spec(replica) {
+            Some(replica) => self.dictionaries.for_replica(&replica),
+            None => Ok(None),
+        }
+    }
+
     pub fn leaders_state(&self) -> &ReplicaLeadersState<S> {
         &self.leaders_state
     }
 
+    pub fn leaders_state_owned(&self) -> SharedReplicaLeadersState<S> {
+        self.leaders_state.clone()
+    }
+
     pub fn followers_state(&self) -> &FollowersState<S> {
         &self.followers_state
     }
@@ -194,9 +228,26 @@ where
         self.metrics.clone()
     }
 
+    /// rate limit of given mirror, shared by all its partitions led by this spu
+    pub(crate) fn mirror_rate_limit(&self, mirror: &str) -> Arc<MirrorRateLimit> {
+        if let Some(rate_limit) = self.mirror_rate_limits.read().unwrap().get(mirror) {
+            return rate_limit.clone();
+        }
+        self.mirror_rate_limits
+            .write()
+            .unwrap()
+            .entry(mirror.to_owned())
+            .or_default()
+            .clone()
+    }
+
     pub(crate) fn consumer_offset(&self) -> &SharedConsumerOffsetStorages {
         &self.consumer_offset
     }
+
+    pub(crate) fn consumer_offset_owned(&self) -> SharedConsumerOffsetStorages {
+        self.consumer_offset.clone()
+    }
 }
 
 mod file_replica {
@@ -329,7 +380,7 @@ mod file_replica {
                                     outputs.push(ReplicaChange::StorageError(err));
                                 }
                             } else {
-                                debug!(replica = %new_replica.id, "not application to this spu, ignoring");
+                                debug!(replica = %new_replica.id, "not applicable to this spu, ignoring");
                             }
                         }
                     }
@@ -351,22 +402,31 @@ mod file_replica {
                                     if old_replica.leader == local_id {
                                         self.demote_replica(new_replica).await
                                     } else {
-                                        // we stay as follower but we switch to new leader
-                                        debug!(
-                                            "still follower but switching leader: {}",
-                                            new_replica
-                                        );
-                                        self.switch_leader_for_follower(new_replica, old_replica)
-                                            .await
+                                        self.update_follower_replica(
+                                            &mut outputs,
+                                            new_replica,
+                                            old_replica,
+                                        )
+                                        .await
                                     }
                                 }
                             } else if new_replica.leader == local_id {
-                                if self.leaders_state().get(&new_replica.id).await.is_some() {
+                                if let Some(leader) =
+                                    self.leaders_state().get(&new_replica.id).await
+                                {
+                                    if new_replica.replicas != old_replica.replicas {
+                                        leader.update_followers(&new_replica.replicas).await;
+                                    }
                                 } else {
                                     error!("leader controller was not found: {}", new_replica.id);
                                 }
                             } else {
-                                self.followers_state().update_replica(new_replica).await;
+                                self.update_follower_replica(
+                                    &mut outputs,
+                                    new_replica,
+                                    old_replica,
+                                )
+                                .await;
                             }
                         }
                     }
@@ -432,6 +492,8 @@ mod file_replica {
                 .remove_replica(replica.leader, &replica.id)
                 .await
             {
+                replica_state.signal_topic_deleted().await;
+
                 if let Err(err) = replica_state.remove().await {
                     error!("error {}, removing replica: {}", err, replica);
                 }
@@ -463,6 +525,48 @@ mod file_replica {
             }
         }
 
+        /// apply change of replica which is not led by this spu.
+        /// Replicas are added to or removed from this spu when they are migrated between spus
+        #[instrument(
+            skip(self, outputs, new, old),
+            fields(
+                replica = %new.id,
+            )
+        )]
+        async fn update_follower_replica(
+            &self,
+            outputs: &mut Vec<ReplicaChange>,
+            new: Replica,
+            old: Replica,
+        ) {
+            let local_id = self.local_spu_id();
+            match (
+                old.replicas.contains(&local_id),
+                new.replicas.contains(&local_id),
+            ) {
+                (true, true) => {
+                    if new.leader != old.leader {
+                        self.switch_leader_for_follower(new, old).await
+                    } else {
+                        self.followers_state().update_replica(new).await
+                    }
+                }
+                (false, true) => {
+                    info!(replica = %new.id, "replica migrated to this spu");
+                    if let Err(err) = self.followers_state_owned().add_replica(self, new).await {
+                        outputs.push(ReplicaChange::StorageError(err));
+                    }
+                }
+                (true, false) => {
+                    info!(replica = %old.id, "replica migrated off this spu");
+                    self.remove_follower_replica(old).await
+                }
+                (false, false) => {
+                    debug!(replica = %new.id, "not applicable to this spu, ignoring");
+                }
+            }
+        }
+
         /// Demote leader replica as follower.
         /// This only happens on manual election
         #[instrument(
@@ -2,9 +2,10 @@ use std::{
     collections::HashMap,
     sync::{
         atomic::{AtomicU64, Ordering},
-        RwLock,
+        Arc, RwLock,
     },
     ops::AddAssign,
+    time::Duration,
 };
 
 use fluvio_protocol::record::Batch;
@@ -13,15 +14,22 @@ use fluvio_smartengine::metrics::SmartModuleChainMetrics;
 #[cfg(not(feature = "smartengine"))]
 use crate::smartengine::SmartModuleChainMetrics;
 
+use fluvio_controlplane_metadata::mirror::MirrorLinkStat;
 use fluvio_spu_schema::fetch::FilePartitionResponse;
+use fluvio_metrics::{Histogram, MetricType, OpenMetricsEncoder};
 use serde::Serialize;
 
 #[derive(Default, Debug, Serialize)]
 pub(crate) struct SpuMetrics {
     inbound: Activity,
     outbound: Activity,
+    recompression: Recompression,
     #[serde(skip)] // Skip serializing the RwLock wrapper
     smartmodule_metrics: RwLock<HashMap<String, SmartModuleChainMetrics>>,
+    #[serde(skip)]
+    latency: RequestLatency,
+    #[serde(skip)]
+    mirror_links: RwLock<HashMap<String, Arc<MirrorLinkMetrics>>>,
 }
 
 impl SpuMetrics {
@@ -29,7 +37,10 @@ impl SpuMetrics {
         Self {
             inbound: Activity::default(),
             outbound: Activity::default(),
+            recompression: Recompression::default(),
             smartmodule_metrics: RwLock::new(HashMap::new()),
+            latency: RequestLatency::default(),
+            mirror_links: RwLock::new(HashMap::new()),
         }
     }
 
@@ -41,6 +52,27 @@ impl SpuMetrics {
         &self.outbound
     }
 
+    pub fn recompression(&self) -> &Recompression {
+        &self.recompression
+    }
+
+    pub fn latency(&self) -> &RequestLatency {
+        &self.latency
+    }
+
+    /// traffic of mirror links to given mirror, shared by all its partitions
+    pub(crate) fn mirror_link(&self, mirror: &str) -> Arc<MirrorLinkMetrics> {
+        if let Some(metrics) = self.mirror_links.read().unwrap().get(mirror) {
+            return metrics.clone();
+        }
+        self.mirror_links
+            .write()
+            .unwrap()
+            .entry(mirror.to_owned())
+            .or_default()
+            .clone()
+    }
+
     pub fn smartmodule_metrics(&self) -> HashMap<String, SmartModuleChainMetrics> {
         // Return a copy of the metrics to avoid holding the lock
         self.smartmodule_metrics.read().unwrap().clone()
@@ -69,6 +101,169 @@ impl SpuMetrics {
     }
 }
 
+impl SpuMetrics {
+    /// encode all counters in OpenMetrics format
+    pub(crate) fn encode(&self, encoder: &mut OpenMetricsEncoder) {
+        let activities = [("inbound", &self.inbound), ("outbound", &self.outbound)];
+
+        let mut records = encoder.family(
+            "fluvio_spu_records",
+            MetricType::Counter,
+            "Records produced to and consumed from the SPU",
+        );
+        for (direction, activity) in activities {
+            for (client, record) in activity.records() {
+                records.sample(
+                    &[("direction", direction), ("client", client)],
+                    record.records.load(Ordering::SeqCst),
+                );
+            }
+        }
+
+        let mut bytes = encoder.family(
+            "fluvio_spu_bytes",
+            MetricType::Counter,
+            "Bytes produced to and consumed from the SPU",
+        );
+        for (direction, activity) in activities {
+            for (client, record) in activity.records() {
+                bytes.sample(
+                    &[("direction", direction), ("client", client)],
+                    record.bytes.load(Ordering::SeqCst),
+                );
+            }
+        }
+
+        self.recompression.encode(encoder);
+
+        encoder
+            .family(
+                "fluvio_spu_request_duration_seconds",
+                MetricType::Histogram,
+                "Time spent serving client requests",
+            )
+            .histogram(&[("api", "produce")], &self.latency.produce)
+            .histogram(&[("api", "fetch")], &self.latency.fetch);
+
+        encode_smartmodule_metrics(encoder, &self.smartmodule_metrics());
+
+        let mirror_links = self.mirror_links.read().unwrap().clone();
+        encode_mirror_link_metrics(encoder, &mirror_links);
+    }
+}
+
+fn encode_mirror_link_metrics(
+    encoder: &mut OpenMetricsEncoder,
+    links: &HashMap<String, Arc<MirrorLinkMetrics>>,
+) {
+    let mut bytes = encoder.family(
+        "fluvio_spu_mirror_link_bytes",
+        MetricType::Counter,
+        "Bytes of records sent over mirror links, before and after link compression",
+    );
+    for (mirror, link) in links {
+        let stat = link.stat();
+        bytes
+            .sample(
+                &[("mirror", mirror.as_str()), ("stage", "uncompressed")],
+                stat.bytes_uncompressed,
+            )
+            .sample(
+                &[("mirror", mirror.as_str()), ("stage", "wire")],
+                stat.bytes_sent,
+            );
+    }
+
+    let mut throttled = encoder.family(
+        "fluvio_spu_mirror_link_throttled_seconds",
+        MetricType::Counter,
+        "Time mirror links waited for rate limit",
+    );
+    for (mirror, link) in links {
+        throttled.sample(
+            &[("mirror", mirror.as_str())],
+            link.stat().throttled_ms as f64 / 1000.0,
+        );
+    }
+
+    let mut pending = encoder.family(
+        "fluvio_spu_mirror_link_pending_records",
+        MetricType::Gauge,
+        "Records not yet acknowledged by mirror",
+    );
+    for (mirror, link) in links {
+        pending.sample(&[("mirror", mirror.as_str())], link.stat().pending_records);
+    }
+}
+
+fn encode_smartmodule_metrics(
+    encoder: &mut OpenMetricsEncoder,
+    metrics: &HashMap<String, SmartModuleChainMetrics>,
+) {
+    let counters: [(&str, &str, fn(&SmartModuleChainMetrics) -> u64); 5] = [
+        (
+            "fluvio_spu_smartmodule_invocations",
+            "SmartModule chain invocations",
+            SmartModuleChainMetrics::invocation_count,
+        ),
+        (
+            "fluvio_spu_smartmodule_bytes_in",
+            "Bytes processed by SmartModule chain",
+            SmartModuleChainMetrics::bytes_in,
+        ),
+        (
+            "fluvio_spu_smartmodule_records_out",
+            "Records returned by SmartModule chain",
+            SmartModuleChainMetrics::records_out,
+        ),
+        (
+            "fluvio_spu_smartmodule_records_err",
+            "Records failed in SmartModule chain",
+            SmartModuleChainMetrics::records_err,
+        ),
+        (
+            "fluvio_spu_smartmodule_fuel_used",
+            "Fuel consumed by SmartModule chain",
+            SmartModuleChainMetrics::fuel_used,
+        ),
+    ];
+    for (name, help, value) in counters {
+        let mut family = encoder.family(name, MetricType::Counter, help);
+        for (smartmodule, chain) in metrics {
+            family.sample(&[("smartmodule", smartmodule.as_str())], value(chain));
+        }
+    }
+
+    let mut cpu = encoder.family(
+        "fluvio_spu_smartmodule_cpu_seconds",
+        MetricType::Counter,
+        "CPU time spent in SmartModule chain",
+    );
+    for (smartmodule, chain) in metrics {
+        cpu.sample(
+            &[("smartmodule", smartmodule.as_str())],
+            chain.cpu_ms() as f64 / 1000.0,
+        );
+    }
+}
+
+/// Latency of client requests served by the SPU
+#[derive(Default, Debug)]
+pub(crate) struct RequestLatency {
+    produce: Histogram,
+    fetch: Histogram,
+}
+
+impl RequestLatency {
+    pub(crate) fn produce(&self) -> &Histogram {
+        &self.produce
+    }
+
+    pub(crate) fn fetch(&self) -> &Histogram {
+        &self.fetch
+    }
+}
+
 #[derive(Default, Debug, Serialize)]
 pub(crate) struct Record {
     records: AtomicU64,
@@ -88,6 +283,100 @@ pub struct Activity {
     client: Record,
 }
 
+/// Work spent converting produced batches to the topic compression
+#[derive(Default, Debug, Serialize)]
+pub(crate) struct Recompression {
+    batches: AtomicU64,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    elapsed_us: AtomicU64,
+}
+
+impl Recompression {
+    pub(crate) fn increase(&self, bytes_in: u64, bytes_out: u64, elapsed: Duration) {
+        self.batches.fetch_add(1, Ordering::SeqCst);
+        self.bytes_in.fetch_add(bytes_in, Ordering::SeqCst);
+        self.bytes_out.fetch_add(bytes_out, Ordering::SeqCst);
+        self.elapsed_us
+            .fetch_add(elapsed.as_micros() as u64, Ordering::SeqCst);
+    }
+}
+
+impl Recompression {
+    fn encode(&self, encoder: &mut OpenMetricsEncoder) {
+        encoder.counter(
+            "fluvio_spu_recompression_batches",
+            "Produced batches recompressed to the topic compression",
+            self.batches.load(Ordering::SeqCst),
+        );
+        encoder
+            .family(
+                "fluvio_spu_recompression_bytes",
+                MetricType::Counter,
+                "Bytes before and after recompression",
+            )
+            .sample(&[("stage", "in")], self.bytes_in.load(Ordering::SeqCst))
+            .sample(&[("stage", "out")], self.bytes_out.load(Ordering::SeqCst));
+        encoder
+            .family(
+                "fluvio_spu_recompression_seconds",
+                MetricType::Counter,
+                "Wall-clock time spent recompressing batches",
+            )
+            .sample(
+                &[],
+                self.elapsed_us.load(Ordering::SeqCst) as f64 / 1_000_000.0,
+            );
+    }
+}
+
+#[cfg(test)]
+impl Recompression {
+    pub fn batches(&self) -> u64 {
+        self.batches.load(Ordering::SeqCst)
+    }
+}
+
+/// Traffic of records sent to a mirror
+#[derive(Default, Debug)]
+pub(crate) struct MirrorLinkMetrics {
+    bytes_sent: AtomicU64,
+    bytes_uncompressed: AtomicU64,
+    throttled_ms: AtomicU64,
+    pending_records: AtomicU64,
+}
+
+impl MirrorLinkMetrics {
+    pub(crate) fn increase_sent(&self, bytes_sent: u64, bytes_uncompressed: u64) {
+        self.bytes_sent.fetch_add(bytes_sent, Ordering::SeqCst);
+        self.bytes_uncompressed
+            .fetch_add(bytes_uncompressed, Ordering::SeqCst);
+    }
+
+    pub(crate) fn increase_throttled(&self, wait: Duration) {
+        self.throttled_ms
+            .fetch_add(wait.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    /// partitions replace their own share of pending records
+    pub(crate) fn replace_pending(&self, old: u64, new: u64) {
+        if new >= old {
+            self.pending_records.fetch_add(new - old, Ordering::SeqCst);
+        } else {
+            self.pending_records.fetch_sub(old - new, Ordering::SeqCst);
+        }
+    }
+
+    pub(crate) fn stat(&self) -> MirrorLinkStat {
+        MirrorLinkStat {
+            bytes_sent: self.bytes_sent.load(Ordering::SeqCst),
+            bytes_uncompressed: self.bytes_uncompressed.load(Ordering::SeqCst),
+            throttled_ms: self.throttled_ms.load(Ordering::SeqCst),
+            pending_records: self.pending_records.load(Ordering::SeqCst),
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 pub(crate) struct IncreaseValue {
     records: u64,
@@ -107,6 +396,10 @@ impl Activity {
         let IncreaseValue { records, bytes } = value;
         self.increase(connector, records, bytes)
     }
+
+    fn records(&self) -> [(&'static str, &Record); 2] {
+        [("connector", &self.connector), ("client", &self.client)]
+    }
 }
 
 #[cfg(test)]
@@ -205,4 +498,58 @@ mod tests {
         assert_eq!(activity.connector.records.load(Ordering::SeqCst), 1);
         assert_eq!(activity.connector.bytes.load(Ordering::SeqCst), 123);
     }
+
+    #[test]
+    fn test_encode_openmetrics() {
+        //given
+        let metrics = SpuMetrics::new();
+        metrics.inbound().increase(true, 2, 20);
+        metrics.outbound().increase(false, 1, 10);
+        metrics
+            .latency()
+            .produce()
+            .observe(std::time::Duration::from_millis(2));
+
+        //when
+        let mut encoder = OpenMetricsEncoder::new();
+        metrics.encode(&mut encoder);
+        let text = encoder.finish();
+
+        //then
+        assert!(
+            text.contains(
+                "fluvio_spu_records_total{direction=\"inbound\",client=\"connector\"} 2\n"
+            )
+        );
+        assert!(
+            text.contains("fluvio_spu_bytes_total{direction=\"outbound\",client=\"client\"} 10\n")
+        );
+        assert!(text.contains("fluvio_spu_request_duration_seconds_count{api=\"produce\"} 1\n"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn test_mirror_link_metrics() {
+        //given
+        let metrics = SpuMetrics::new();
+        let link = metrics.mirror_link("edge1");
+        link.increase_sent(100, 400);
+        link.replace_pending(0, 10);
+        metrics.mirror_link("edge1").replace_pending(0, 5);
+        link.replace_pending(10, 2);
+
+        //when
+        let mut encoder = OpenMetricsEncoder::new();
+        metrics.encode(&mut encoder);
+        let text = encoder.finish();
+
+        //then
+        assert_eq!(link.stat().pending_records, 7);
+        assert!(
+            text.contains(
+                "fluvio_spu_mirror_link_bytes_total{mirror=\"edge1\",stage=\"wire\"} 100\n"
+            )
+        );
+        assert!(text.contains("fluvio_spu_mirror_link_pending_records{mirror=\"edge1\"} 7\n"));
+    }
 }
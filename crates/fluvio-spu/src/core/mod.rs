@@ -7,6 +7,7 @@ pub mod replica;
 pub mod smartmodule;
 pub mod metrics;
 pub mod mirror;
+pub mod dictionary;
 
 pub use self::global_context::{GlobalContext, ReplicaChange};
 pub use self::store::Spec;
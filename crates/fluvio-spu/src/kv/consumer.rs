@@ -10,7 +10,8 @@ use async_lock::RwLock;
 use tracing::{debug, trace};
 
 use fluvio_kv_storage::KVStorage;
-use fluvio_protocol::{record::ReplicaKey, Encoder, Decoder};
+use fluvio_protocol::{bytes::Buf, record::ReplicaKey, Encoder, Decoder, Version};
+use fluvio_spu_schema::server::consumer_offset::MirroredOffsetSource;
 use fluvio_storage::FileReplica;
 
 use crate::replication::leader::{
@@ -21,7 +22,7 @@ pub(crate) type TimestampSecs = u64;
 
 const DEFAULT_FLUSH_THRESHOLD: usize = 100;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct SharedConsumerOffsetStorages(
     Arc<RwLock<HashMap<ReplicaKey, SharableConsumerOffsetStorage>>>,
 );
@@ -34,12 +35,34 @@ pub(crate) struct ConsumerOffsetKey {
     pub replica_id: ReplicaKey,
     pub consumer_id: String,
 }
-/// Consumer offset value. Keeps the last offset seen by a consumer, and
-/// the modification time (UTC timestamp in seconds).
-#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Encoder, Decoder)]
+/// Consumer offset value. Keeps the last offset seen by a consumer,
+/// the modification time (UTC timestamp in seconds), the opaque metadata
+/// committed with the offset and where the offset came from if it was mirrored.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder)]
 pub(crate) struct ConsumerOffset {
     pub offset: i64,
     pub modified_time: TimestampSecs,
+    pub metadata: Vec<u8>,
+    pub mirror_source: Option<MirroredOffsetSource>,
+}
+
+impl Decoder for ConsumerOffset {
+    fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), std::io::Error>
+    where
+        T: Buf,
+    {
+        self.offset.decode(src, version)?;
+        self.modified_time.decode(src, version)?;
+        // values stored before metadata was introduced end here
+        if src.has_remaining() {
+            self.metadata.decode(src, version)?;
+        }
+        // values stored before mirror source was introduced end here
+        if src.has_remaining() {
+            self.mirror_source.decode(src, version)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -89,6 +112,63 @@ impl ConsumerOffsetStorage {
         }
     }
 
+    /// store offset mirrored from another cluster unless offset committed here is newer.
+    /// returns true if offset was written
+    async fn put_mirrored(
+        &mut self,
+        key: ConsumerOffsetKey,
+        value: ConsumerOffset,
+        source: MirroredOffsetSource,
+    ) -> Result<bool> {
+        let value = value.with_mirror_source(source);
+        if let Some(existing) = self.kv.get(&key).await? {
+            if existing == value {
+                return Ok(false);
+            }
+            let same_source = existing
+                .mirror_source
+                .as_ref()
+                .zip(value.mirror_source.as_ref())
+                .is_some_and(|(existing, source)| existing.cluster == source.cluster);
+            if existing.modified_time > value.modified_time
+                || (existing.modified_time == value.modified_time && !same_source)
+            {
+                trace!(?key, ?existing, ?value, "newer offset exists, skipping");
+                return Ok(false);
+            }
+        }
+        self.write(key, value).await?;
+        Ok(true)
+    }
+
+    /// store offset committed on this cluster. keeps the stored metadata unless new metadata is given
+    async fn commit(
+        &mut self,
+        key: ConsumerOffsetKey,
+        offset: i64,
+        metadata: Option<Vec<u8>>,
+    ) -> Result<()> {
+        let metadata = match metadata {
+            Some(metadata) => metadata,
+            None => self
+                .kv
+                .get(&key)
+                .await?
+                .map(|existing| existing.metadata)
+                .unwrap_or_default(),
+        };
+        self.put(key, ConsumerOffset::new(offset).with_metadata(metadata))
+            .await
+    }
+
+    async fn write(&mut self, key: ConsumerOffsetKey, value: ConsumerOffset) -> Result<()> {
+        trace!(?key, ?value, "put");
+        let result = self.kv.put(key, value).await;
+        self.changes_since_flush.add_assign(1);
+        self.maybe_flush().await?;
+        result
+    }
+
     async fn maybe_flush(&mut self) -> Result<()> {
         if self.changes_since_flush > self.flush_threshold {
             self.kv.flush().await?;
@@ -117,13 +197,10 @@ impl KVStorage<ConsumerOffsetKey, ConsumerOffset> for ConsumerOffsetStorage {
         key: impl Into<ConsumerOffsetKey>,
         value: impl Into<ConsumerOffset>,
     ) -> Result<()> {
-        let key = key.into();
-        let value = value.into();
-        trace!(?key, ?value, "put");
-        let result = self.kv.put(key, value).await;
-        self.changes_since_flush.add_assign(1);
-        self.maybe_flush().await?;
-        result
+        // committed on this cluster, replaces any mirrored offset
+        let mut value = value.into();
+        value.mirror_source = None;
+        self.write(key.into(), value).await
     }
 
     async fn entries(&self) -> Result<Vec<(ConsumerOffsetKey, ConsumerOffset)>> {
@@ -150,8 +227,20 @@ impl ConsumerOffset {
         Self {
             offset,
             modified_time,
+            metadata: Vec::new(),
+            mirror_source: None,
         }
     }
+
+    pub(crate) fn with_metadata(mut self, metadata: Vec<u8>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    fn with_mirror_source(mut self, source: MirroredOffsetSource) -> Self {
+        self.mirror_source = Some(source);
+        self
+    }
 }
 
 impl From<ConsumerOffsetStorage> for SharableConsumerOffsetStorage {
@@ -201,9 +290,27 @@ impl SharableConsumerOffsetStorage {
         self.0.write().await.put(key, value).await
     }
 
+    pub(crate) async fn commit(
+        &self,
+        key: ConsumerOffsetKey,
+        offset: i64,
+        metadata: Option<Vec<u8>>,
+    ) -> Result<()> {
+        self.0.write().await.commit(key, offset, metadata).await
+    }
+
     pub async fn list(&self) -> Result<Vec<(ConsumerOffsetKey, ConsumerOffset)>> {
         self.0.read().await.entries().await
     }
+
+    pub(crate) async fn put_mirrored(
+        &self,
+        key: ConsumerOffsetKey,
+        value: ConsumerOffset,
+        source: MirroredOffsetSource,
+    ) -> Result<bool> {
+        self.0.write().await.put_mirrored(key, value, source).await
+    }
 }
 
 fn now_timestamp() -> TimestampSecs {
@@ -229,6 +336,41 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_decode_value_without_metadata() {
+        #[derive(Encoder)]
+        struct LegacyConsumerOffset {
+            offset: i64,
+            modified_time: TimestampSecs,
+        }
+
+        let mut legacy = Vec::new();
+        LegacyConsumerOffset {
+            offset: 7,
+            modified_time: 100,
+        }
+        .encode(&mut legacy, 0)
+        .expect("encoded");
+        let decoded =
+            ConsumerOffset::decode_from(&mut std::io::Cursor::new(legacy), 0).expect("decoded");
+        assert_eq!(decoded, ConsumerOffset::with(7, 100));
+
+        let value = ConsumerOffset::with(8, 200).with_metadata(b"txn-1".to_vec());
+        let mut encoded = Vec::new();
+        value.encode(&mut encoded, 0).expect("encoded");
+        let decoded =
+            ConsumerOffset::decode_from(&mut std::io::Cursor::new(encoded), 0).expect("decoded");
+        assert_eq!(decoded, value);
+
+        // value stored before mirror source was introduced
+        let mut encoded = Vec::new();
+        value.encode(&mut encoded, 0).expect("encoded");
+        encoded.pop();
+        let decoded =
+            ConsumerOffset::decode_from(&mut std::io::Cursor::new(encoded), 0).expect("decoded");
+        assert_eq!(decoded, value);
+    }
+
     #[fluvio_future::test]
     async fn test_flush_invoked_no_records() {
         //given
@@ -312,6 +454,153 @@ mod tests {
         leader.remove().await.expect("removed");
     }
 
+    #[fluvio_future::test]
+    async fn test_put_mirrored() {
+        //given
+        let leader = create_offset_replica("test_put_mirrored").await;
+        let notifier = FollowerNotifier::shared();
+        let storage = SharedConsumerOffsetStorages::default();
+        let shared_storage = storage
+            .get_or_insert(&leader, &notifier)
+            .await
+            .expect("storage");
+        let key = ConsumerOffsetKey::new(("topic1", 0), "consumer1");
+        let source = MirroredOffsetSource {
+            cluster: "home".to_owned(),
+            replica_id: ("topic1", 0).into(),
+            offset: 10,
+        };
+        let now = now_timestamp();
+
+        //when
+        let written = shared_storage
+            .put_mirrored(
+                key.clone(),
+                ConsumerOffset::with(10, now - 100),
+                source.clone(),
+            )
+            .await
+            .expect("put mirrored");
+
+        //then
+        assert!(written);
+        assert_eq!(
+            shared_storage.get(&key).await.expect("get"),
+            Some(ConsumerOffset::with(10, now - 100).with_mirror_source(source.clone()))
+        );
+
+        //when committed locally
+        shared_storage
+            .put(key.clone(), ConsumerOffset::with(20, now - 50))
+            .await
+            .expect("put");
+
+        //then older mirrored offset does not overwrite it
+        let written = shared_storage
+            .put_mirrored(
+                key.clone(),
+                ConsumerOffset::with(15, now - 80),
+                source.clone(),
+            )
+            .await
+            .expect("put mirrored");
+        assert!(!written);
+        assert_eq!(
+            shared_storage.get(&key).await.expect("get"),
+            Some(ConsumerOffset::with(20, now - 50))
+        );
+
+        //when newer offset is mirrored
+        let written = shared_storage
+            .put_mirrored(key.clone(), ConsumerOffset::with(30, now), source.clone())
+            .await
+            .expect("put mirrored");
+
+        //then
+        assert!(written);
+        let mirrored = ConsumerOffset::with(30, now).with_mirror_source(source);
+        assert_eq!(
+            shared_storage.get(&key).await.expect("get"),
+            Some(mirrored.clone())
+        );
+
+        //then source is kept after offsets are reloaded from log
+        let reloaded = SharedConsumerOffsetStorages::default()
+            .get_or_insert(&leader, &notifier)
+            .await
+            .expect("storage");
+        assert_eq!(reloaded.get(&key).await.expect("get"), Some(mirrored));
+
+        leader.remove().await.expect("removed");
+    }
+
+    #[fluvio_future::test]
+    async fn test_commit_keeps_metadata() {
+        //given
+        let leader = create_offset_replica("test_commit_keeps_metadata").await;
+        let notifier = FollowerNotifier::shared();
+        let storage = SharedConsumerOffsetStorages::default();
+        let shared_storage = storage
+            .get_or_insert(&leader, &notifier)
+            .await
+            .expect("storage");
+        let key = ConsumerOffsetKey::new(("topic1", 0), "consumer1");
+
+        //when committed with metadata
+        shared_storage
+            .commit(key.clone(), 10, Some(b"checkpoint-1".to_vec()))
+            .await
+            .expect("commit");
+
+        //then
+        let stored = shared_storage
+            .get(&key)
+            .await
+            .expect("get")
+            .expect("offset");
+        assert_eq!(stored.offset, 10);
+        assert_eq!(stored.metadata, b"checkpoint-1");
+
+        //when committed without metadata
+        shared_storage
+            .commit(key.clone(), 20, None)
+            .await
+            .expect("commit");
+
+        //then stored metadata is kept
+        let list = shared_storage.list().await.expect("list");
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].0, key);
+        assert_eq!(list[0].1.offset, 20);
+        assert_eq!(list[0].1.metadata, b"checkpoint-1");
+
+        //then metadata is kept after offsets are reloaded from log
+        let reloaded = SharedConsumerOffsetStorages::default()
+            .get_or_insert(&leader, &notifier)
+            .await
+            .expect("storage");
+        let stored = reloaded.get(&key).await.expect("get").expect("offset");
+        assert_eq!(stored.offset, 20);
+        assert_eq!(stored.metadata, b"checkpoint-1");
+
+        //when committed with empty metadata
+        shared_storage
+            .commit(key.clone(), 30, Some(Vec::new()))
+            .await
+            .expect("commit");
+
+        //then metadata is cleared
+        let stored = shared_storage
+            .get(&key)
+            .await
+            .expect("get")
+            .expect("offset");
+        assert_eq!(stored.offset, 30);
+        assert!(stored.metadata.is_empty());
+
+        leader.remove().await.expect("removed");
+    }
+
     async fn create_offset_replica(dir: impl AsRef<Path>) -> LeaderReplicaState<FileReplica> {
         let base_dir = temp_dir().join(dir);
         ensure_clean_dir(&base_dir);
@@ -0,0 +1,371 @@
+//! Active-active mirroring between two peer clusters.
+//!
+//! Both clusters accept producers for the same partition and replicate locally
+//! produced batches to each other over the existing home/remote mirror connection.
+//! Every replicated batch is stamped with its origin cluster and offset in the origin log:
+//!
+//! * batches that already carry an origin are never sent back out, which prevents loops
+//! * each side appends peer batches after its own, so there is no offset conflict;
+//!   consumers can tell local from mirrored records by the origin stamp
+//! * how far the peer log has been mirrored is recovered from the stamps in the local log;
+//!   a checkpoint of where peer batches were last appended bounds that scan after restart
+//!
+//! Home and remote run the same loop; only the transport differs, see [`PeerSink`].
+
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use futures_util::{Stream, StreamExt};
+use tokio::select;
+use tracing::{debug, info, instrument, warn};
+
+use fluvio_controlplane_metadata::mirror::{MirrorDirection, MirrorLinkConfig, MirrorPairStatus};
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::{Batch, MirrorOrigin, Offset, RawRecords, RecordSet};
+use fluvio_spu_schema::Isolation;
+use fluvio_storage::{ReplicaStorage, checkpoint::CheckPoint, iterators::read_batches_up_to};
+
+use crate::control_plane::SharedMirrorStatusUpdate;
+use crate::replication::leader::{FollowerNotifier, ReplicaOffsetRequest, SharedLeaderState};
+
+use super::consumer_offsets::{ConsumerOffsetMirror, ConsumerOffsetStore, MirrorConsumerOffsets};
+use super::link::{CompressedSyncRecords, LinkSyncRecords, MirrorLink};
+use super::remote::sync::DefaultRemotePartitionSyncRequest;
+use super::transform::{FilteredBatch, MirrorTransform};
+
+const BIDIRECTIONAL_RECONCILIATION_INTERVAL_SEC: u64 = 60; // 1 min
+
+/// message received from peer cluster
+pub(crate) enum PeerMessage {
+    /// records produced on peer
+    Sync(DefaultRemotePartitionSyncRequest),
+    /// how far peer has mirrored our log
+    Offset(ReplicaOffsetRequest),
+    /// consumer offsets committed on peer
+    ConsumerOffsets(MirrorConsumerOffsets),
+    /// link settings from home
+    LinkConfig(MirrorLinkConfig),
+}
+
+/// connection to peer cluster
+pub(crate) trait PeerSink {
+    async fn send_records(&mut self, request: DefaultRemotePartitionSyncRequest) -> Result<()>;
+
+    async fn send_compressed_records(&mut self, records: CompressedSyncRecords) -> Result<()>;
+
+    async fn send_offset(&mut self, request: ReplicaOffsetRequest) -> Result<()>;
+
+    async fn send_consumer_offsets(&mut self, offsets: MirrorConsumerOffsets) -> Result<()>;
+}
+
+pub(crate) struct BidirectionalSync<'a, S> {
+    pub(crate) leader: &'a SharedLeaderState<S>,
+    /// cluster id stamped on batches produced here
+    pub(crate) local_cluster: &'a str,
+    /// cluster id stamped on batches produced on peer
+    pub(crate) peer_cluster: &'a str,
+    /// mirror name which status is reported under
+    pub(crate) mirror_id: &'a str,
+    pub(crate) status_update: &'a SharedMirrorStatusUpdate,
+    pub(crate) follower_notifier: &'a FollowerNotifier,
+    /// smartmodules applied to local records before they are sent to peer
+    pub(crate) transform: Option<&'a MirrorTransform>,
+    pub(crate) consumer_offsets: &'a ConsumerOffsetStore,
+    /// peer is able to receive consumer offsets
+    pub(crate) mirror_consumer_offsets: bool,
+    /// rate limit and compression of records sent to peer
+    pub(crate) link: &'a MirrorLink,
+    pub(crate) max_bytes: u32,
+}
+
+impl<S> BidirectionalSync<'_, S>
+where
+    S: ReplicaStorage,
+{
+    /// replicate in both directions until peer closes connection
+    #[instrument(skip(self, sink, peer_stream), fields(replica = %self.leader.id(), peer = self.peer_cluster))]
+    pub(crate) async fn run<K, M>(&self, sink: &mut K, mut peer_stream: M) -> Result<()>
+    where
+        K: PeerSink,
+        M: Stream<Item = Result<PeerMessage>> + Unpin,
+    {
+        // tell peer where to resume sending its records
+        let mut checkpoint = self.leader.checkpoint(&self.checkpoint_name(), 0).await?;
+        let scan_from = checkpoint
+            .as_ref()
+            .map_or(0, |checkpoint| checkpoint.get_offset());
+        let mut received = self.recover_peer_cursor(scan_from).await?;
+        info!(received, "starting bidirectional mirror");
+        sink.send_offset(self.offset_request(received)).await?;
+
+        // next local offset peer needs, unknown until peer tells us
+        let mut peer_cursor: Option<Offset> = None;
+        let mut local_update_needed = false;
+
+        let mut consumer_offsets =
+            ConsumerOffsetMirror::new(self.consumer_offsets.clone(), self.peer_cluster, true);
+
+        let mut offset_listener = self.leader.offset_listener(&Isolation::ReadUncommitted);
+        let mut timer = sleep(Duration::from_secs(
+            BIDIRECTIONAL_RECONCILIATION_INTERVAL_SEC,
+        ));
+
+        loop {
+            if let (true, Some(cursor)) = (local_update_needed, peer_cursor) {
+                let next = self.send_local_records(sink, cursor).await?;
+                peer_cursor = Some(next);
+                self.update_status(MirrorDirection::Outbound, MirrorPairStatus::Successful)
+                    .await?;
+                // more records than fit in max bytes, keep sending
+                local_update_needed = next != cursor && next < self.leader.leo();
+                if local_update_needed {
+                    continue;
+                }
+            }
+
+            select! {
+                _ = offset_listener.listen() => {
+                    debug!("leader offset has changed, peer needs to be updated");
+                    local_update_needed = true;
+                },
+
+                _ = &mut timer => {
+                    debug!("timer expired, sending reconciliation");
+                    sink.send_offset(self.offset_request(received)).await?;
+                    if self.mirror_consumer_offsets {
+                        if let Some(offsets) = consumer_offsets.source_offsets(self.leader).await {
+                            sink.send_consumer_offsets(offsets).await?;
+                        }
+                    }
+                    timer = sleep(Duration::from_secs(BIDIRECTIONAL_RECONCILIATION_INTERVAL_SEC));
+                },
+
+                msg = peer_stream.next() => {
+                    match msg {
+                        Some(Ok(PeerMessage::Sync(request))) => {
+                            self.receive_peer_records(sink, request, &mut received, checkpoint.as_mut())
+                                .await?;
+                            self.update_status(MirrorDirection::Inbound, MirrorPairStatus::Successful)
+                                .await?;
+                        }
+                        Some(Ok(PeerMessage::Offset(request))) => {
+                            // first offset is where peer wants to resume, later ones are acks
+                            if peer_cursor.is_none_or(|cursor| request.leo > cursor) {
+                                debug!(leo = request.leo, "peer cursor updated");
+                                peer_cursor = Some(request.leo);
+                            }
+                            local_update_needed = true;
+                        }
+                        Some(Ok(PeerMessage::ConsumerOffsets(offsets))) => {
+                            consumer_offsets.apply(self.leader, offsets).await;
+                        }
+                        Some(Ok(PeerMessage::LinkConfig(config))) => {
+                            self.link.set_config(&config).await;
+                        }
+                        Some(Err(err)) => return Err(err),
+                        None => {
+                            info!("peer has closed connection");
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// send batches produced locally starting from cursor, returns next cursor
+    async fn send_local_records<K: PeerSink>(
+        &self,
+        sink: &mut K,
+        cursor: Offset,
+    ) -> Result<Offset> {
+        let (log_start, _) = self.leader.start_offset_info().await;
+        let cursor = cursor.max(log_start);
+        let leo = self.leader.leo();
+        self.link.update_pending((leo - cursor).max(0) as u64);
+        if cursor >= leo {
+            return Ok(cursor);
+        }
+
+        let batches = self.read_local_batches(cursor).await?;
+        let Some(next) = batches.last().map(|batch| batch.get_last_offset() + 1) else {
+            return Ok(cursor);
+        };
+
+        // skip already sent, or came from peer which must not go back
+        let local_batches: Vec<_> = batches
+            .into_iter()
+            .filter(|batch| batch.get_last_offset() >= cursor && !batch.get_header().is_mirrored())
+            .collect();
+
+        // peer appends at its own end, so there is no gap to fill for fully filtered batches
+        let mut records = match self.transform {
+            Some(transform) => {
+                transform
+                    .process(local_batches, FilteredBatch::Skip)
+                    .await?
+            }
+            None => RecordSet {
+                batches: local_batches,
+            },
+        };
+        for batch in records.batches.iter_mut() {
+            let origin = MirrorOrigin::new(self.local_cluster, batch.get_base_offset());
+            batch.set_mirror_origin(origin);
+        }
+
+        debug!(
+            cursor,
+            next,
+            batches = records.batches.len(),
+            "sending local records"
+        );
+        let request = DefaultRemotePartitionSyncRequest {
+            hw: self.leader.hw(),
+            leo: next,
+            records,
+        };
+        match self.link.pack(request).await? {
+            LinkSyncRecords::Plain(request) => sink.send_records(request).await?,
+            LinkSyncRecords::Compressed(records) => sink.send_compressed_records(records).await?,
+        }
+
+        Ok(next)
+    }
+
+    /// append peer batches, skipping any already received
+    async fn receive_peer_records<K: PeerSink>(
+        &self,
+        sink: &mut K,
+        mut request: DefaultRemotePartitionSyncRequest,
+        received: &mut Offset,
+        checkpoint: Option<&mut CheckPoint>,
+    ) -> Result<()> {
+        let peer_cluster = self.peer_cluster;
+        let cursor = *received;
+        request
+            .records
+            .batches
+            .retain(|batch| match batch.mirror_origin() {
+                Some(origin) if origin.is_from(peer_cluster) => origin.offset >= cursor,
+                _ => {
+                    warn!("dropping batch without peer origin");
+                    false
+                }
+            });
+
+        let appended = request.records.total_records() > 0;
+        let (base_offset, leo, _) = self
+            .leader
+            .append_mirrored_record_set(&mut request.records, self.follower_notifier)
+            .await?;
+        debug!(leo, peer_leo = request.leo, "appended peer records");
+        // latest peer batch is at or after base offset, recovery starts scanning from there
+        if let (true, Some(checkpoint)) = (appended, checkpoint) {
+            checkpoint.write(base_offset);
+        }
+
+        *received = cursor.max(request.leo);
+        sink.send_offset(self.offset_request(*received)).await
+    }
+
+    /// find how far peer log has been mirrored from origin stamps in the local log.
+    /// Peer batches are appended in origin order, so scan can start from last appended one
+    async fn recover_peer_cursor(&self, scan_from: Offset) -> Result<Offset> {
+        let (log_start, _) = self.leader.start_offset_info().await;
+        let mut offset = scan_from.max(log_start);
+        let mut cursor = 0;
+
+        while offset < self.leader.leo() {
+            let batches = self.read_local_batches(offset).await?;
+            let Some(last) = batches.last() else {
+                break;
+            };
+            let next = last.get_last_offset() + 1;
+
+            for batch in &batches {
+                if let Some(origin) = batch.mirror_origin() {
+                    if origin.is_from(self.peer_cluster) {
+                        cursor =
+                            cursor.max(origin.offset + batch.last_offset_delta() as Offset + 1);
+                    }
+                }
+            }
+
+            if next <= offset {
+                break;
+            }
+            offset = next;
+        }
+
+        Ok(cursor)
+    }
+
+    /// checkpoint file of mirror from peer, cluster id may not be a valid file name
+    fn checkpoint_name(&self) -> String {
+        format!(
+            "mirror-{:08x}.chk",
+            MirrorOrigin::fingerprint(self.peer_cluster) as u32
+        )
+    }
+
+    async fn read_local_batches(&self, offset: Offset) -> Result<Vec<Batch<RawRecords>>> {
+        read_leader_batches(
+            self.leader,
+            offset,
+            self.max_bytes,
+            Isolation::ReadUncommitted,
+        )
+        .await
+    }
+
+    fn offset_request(&self, received: Offset) -> ReplicaOffsetRequest {
+        ReplicaOffsetRequest {
+            replica: self.leader.id().clone(),
+            leo: received,
+            hw: self.leader.hw(),
+        }
+    }
+
+    async fn update_status(
+        &self,
+        direction: MirrorDirection,
+        pair_status: MirrorPairStatus,
+    ) -> Result<()> {
+        self.status_update
+            .send_direction_status(
+                self.mirror_id.to_owned(),
+                direction,
+                pair_status,
+                Some(self.link.stat()),
+            )
+            .await
+    }
+}
+
+/// decode batches from leader log starting at offset
+pub(crate) async fn read_leader_batches<S: ReplicaStorage>(
+    leader: &SharedLeaderState<S>,
+    offset: Offset,
+    max_bytes: u32,
+    isolation: Isolation,
+) -> Result<Vec<Batch<RawRecords>>> {
+    let slice = leader
+        .read_records(offset, max_bytes, isolation)
+        .await
+        .map_err(|err| anyhow!("error reading records: {}", err))?;
+
+    match slice.file_slice {
+        Some(file_slice) => {
+            let key_provider = leader.key_provider().await;
+            Ok(read_batches_up_to(
+                &file_slice,
+                key_provider.as_deref(),
+                max_bytes as usize,
+            )?)
+        }
+        None => Ok(vec![]),
+    }
+}
@@ -0,0 +1,461 @@
+//! Mirror consumer offsets between clusters for failover.
+//!
+//! Source side of a mirror periodically sends offsets committed for the mirrored partition.
+//! Target side translates them into its own log and stores them for the local partition
+//! under the same consumer id, so a consumer switching its profile to the target cluster
+//! resumes close to where it left off.
+//!
+//! * one-way mirrors keep offsets aligned, translation only clamps to the target log
+//! * bidirectional mirrors append peer records at their own offsets; origin stamps
+//!   in the local log are used to map peer offsets, see [`OriginIndex`]
+//! * a mirrored offset never replaces one committed more recently on the target
+
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use tracing::{debug, trace, warn};
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::server::consumer_offset::MirroredOffsetSource;
+use fluvio_storage::{FileReplica, ReplicaStorage};
+use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
+
+use crate::core::spus::SharedSpuLocalStore;
+use crate::core::{GlobalContext, SharedReplicaLocalStore};
+use crate::kv::consumer::{
+    ConsumerOffset, ConsumerOffsetKey, SharableConsumerOffsetStorage, SharedConsumerOffsetStorages,
+};
+use crate::replication::leader::{FollowerNotifier, SharedLeaderState, SharedReplicaLeadersState};
+use crate::services::internal::{
+    FetchReplicaConsumerOffsetsRequest, MirroredConsumerOffset, ReplicaConsumerOffset,
+    UpdateMirroredConsumerOffsetsRequest,
+};
+use crate::services::public::send_private_request_to_replica_leader;
+
+use super::bidirectional::read_leader_batches;
+
+pub(crate) const CONSUMER_OFFSETS_MIRROR_INTERVAL_SEC: u64 = 30;
+
+const ORIGIN_SCAN_MAX_BYTES: u32 = 1024 * 1024; // 1MB
+
+/// peer batches kept in origin index, oldest are dropped first
+const MAX_ORIGIN_RANGES: usize = 100_000;
+
+/// Consumer offsets committed on source cluster for mirrored partition
+#[derive(Encoder, Decoder, Default, Debug, Clone)]
+pub(crate) struct MirrorConsumerOffsets {
+    /// partition on source cluster
+    pub replica: ReplicaKey,
+    pub consumers: Vec<ReplicaConsumerOffset>,
+}
+
+/// Consumer offsets of this cluster.
+/// They are kept by leader of consumer offsets partition, which may be another SPU.
+#[derive(Clone)]
+pub(crate) struct ConsumerOffsetStore {
+    leaders: SharedReplicaLeadersState<FileReplica>,
+    replicas: SharedReplicaLocalStore,
+    spus: SharedSpuLocalStore,
+    storages: SharedConsumerOffsetStorages,
+    follower_notifier: Arc<FollowerNotifier>,
+}
+
+impl ConsumerOffsetStore {
+    pub(crate) fn new(ctx: &GlobalContext<FileReplica>) -> Self {
+        Self {
+            leaders: ctx.leaders_state_owned(),
+            replicas: ctx.replica_localstore_owned(),
+            spus: ctx.spu_localstore_owned(),
+            storages: ctx.consumer_offset_owned(),
+            follower_notifier: ctx.follower_notifier_owned(),
+        }
+    }
+
+    /// offsets committed for replica, excluding ones mirrored from another cluster
+    pub(crate) async fn list(&self, replica: &ReplicaKey) -> Result<Vec<ReplicaConsumerOffset>> {
+        if let Some(storage) = self.local_storage().await? {
+            return list_replica_offsets(&storage, replica).await;
+        }
+
+        let response = self
+            .send_to_leader(FetchReplicaConsumerOffsetsRequest {
+                replica_id: replica.clone(),
+            })
+            .await?;
+        if response.error_code.is_error() {
+            return Err(anyhow!(
+                "fetch consumer offsets failed: {}",
+                response.error_code
+            ));
+        }
+        Ok(response.consumers)
+    }
+
+    pub(crate) async fn put_mirrored(
+        &self,
+        replica: &ReplicaKey,
+        consumers: Vec<MirroredConsumerOffset>,
+    ) -> Result<()> {
+        if let Some(storage) = self.local_storage().await? {
+            return put_mirrored_offsets(&storage, replica, consumers).await;
+        }
+
+        let response = self
+            .send_to_leader(UpdateMirroredConsumerOffsetsRequest {
+                replica_id: replica.clone(),
+                consumers,
+            })
+            .await?;
+        if response.error_code.is_error() {
+            return Err(anyhow!(
+                "update mirrored consumer offsets failed: {}",
+                response.error_code
+            ));
+        }
+        Ok(())
+    }
+
+    async fn local_storage(&self) -> Result<Option<SharableConsumerOffsetStorage>> {
+        match self.leaders.get(&CONSUMER_REPLICA_KEY.into()).await {
+            Some(leader) => Ok(Some(
+                self.storages
+                    .get_or_insert(&leader, &self.follower_notifier)
+                    .await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    async fn send_to_leader<R: Request>(&self, request: R) -> Result<R::Response> {
+        send_private_request_to_replica_leader(
+            &self.replicas,
+            &self.spus,
+            &CONSUMER_REPLICA_KEY.into(),
+            request,
+        )
+        .await
+        .map_err(|err| anyhow!("error sending to consumer offsets leader: {}", err))
+    }
+}
+
+/// offsets committed for replica on this cluster.
+/// mirrored ones are left out so they are not sent back to where they came from
+pub(crate) async fn list_replica_offsets(
+    storage: &SharableConsumerOffsetStorage,
+    replica: &ReplicaKey,
+) -> Result<Vec<ReplicaConsumerOffset>> {
+    Ok(storage
+        .list()
+        .await?
+        .into_iter()
+        .filter(|(key, value)| key.replica_id == *replica && value.mirror_source.is_none())
+        .map(|(key, value)| ReplicaConsumerOffset {
+            consumer_id: key.consumer_id,
+            offset: value.offset,
+            modified_time: value.modified_time,
+        })
+        .collect())
+}
+
+pub(crate) async fn put_mirrored_offsets(
+    storage: &SharableConsumerOffsetStorage,
+    replica: &ReplicaKey,
+    consumers: Vec<MirroredConsumerOffset>,
+) -> Result<()> {
+    for consumer in consumers {
+        let MirroredConsumerOffset {
+            consumer_id,
+            offset,
+            modified_time,
+            source,
+        } = consumer;
+        let key = ConsumerOffsetKey::new(replica.clone(), consumer_id);
+        let written = storage
+            .put_mirrored(key, ConsumerOffset::with(offset, modified_time), source)
+            .await?;
+        trace!(offset, written, "mirrored consumer offset");
+    }
+    Ok(())
+}
+
+/// Consumer offset mirroring for one side of a mirror link
+pub(crate) struct ConsumerOffsetMirror {
+    store: ConsumerOffsetStore,
+    /// cluster on the other side of the link
+    peer_cluster: String,
+    /// only for bidirectional mirror
+    origin_index: Option<OriginIndex>,
+}
+
+impl ConsumerOffsetMirror {
+    pub(crate) fn new(
+        store: ConsumerOffsetStore,
+        peer_cluster: impl Into<String>,
+        bidirectional: bool,
+    ) -> Self {
+        Self {
+            store,
+            peer_cluster: peer_cluster.into(),
+            origin_index: bidirectional.then(OriginIndex::default),
+        }
+    }
+
+    /// offsets to send to peer, none if no consumer has committed.
+    /// errors are only logged, offsets are resent on next interval
+    pub(crate) async fn source_offsets<S>(
+        &self,
+        leader: &SharedLeaderState<S>,
+    ) -> Option<MirrorConsumerOffsets> {
+        let consumers = match self.store.list(leader.id()).await {
+            Ok(consumers) => consumers,
+            Err(err) => {
+                warn!(%err, replica = %leader.id(), "unable to read consumer offsets");
+                return None;
+            }
+        };
+        if consumers.is_empty() {
+            return None;
+        }
+        Some(MirrorConsumerOffsets {
+            replica: leader.id().clone(),
+            consumers,
+        })
+    }
+
+    /// translate offsets received from peer and store them for local partition.
+    /// errors are only logged, so records keep flowing
+    pub(crate) async fn apply<S: ReplicaStorage>(
+        &mut self,
+        leader: &SharedLeaderState<S>,
+        offsets: MirrorConsumerOffsets,
+    ) {
+        if let Err(err) = self.try_apply(leader, offsets).await {
+            warn!(%err, replica = %leader.id(), "unable to store mirrored consumer offsets");
+        }
+    }
+
+    async fn try_apply<S: ReplicaStorage>(
+        &mut self,
+        leader: &SharedLeaderState<S>,
+        offsets: MirrorConsumerOffsets,
+    ) -> Result<()> {
+        if let Some(index) = &mut self.origin_index {
+            index.refresh(leader, &self.peer_cluster).await?;
+        }
+        let (log_start, _) = leader.start_offset_info().await;
+        let leo = leader.leo();
+
+        let MirrorConsumerOffsets { replica, consumers } = offsets;
+        let consumers: Vec<_> = consumers
+            .into_iter()
+            .map(|consumer| {
+                let translated = match &self.origin_index {
+                    Some(index) => index.translate(consumer.offset, leo),
+                    None => consumer.offset,
+                };
+                MirroredConsumerOffset {
+                    consumer_id: consumer.consumer_id,
+                    offset: translated.min(leo).max(log_start),
+                    modified_time: consumer.modified_time,
+                    source: MirroredOffsetSource {
+                        cluster: self.peer_cluster.clone(),
+                        replica_id: replica.clone(),
+                        offset: consumer.offset,
+                    },
+                }
+            })
+            .collect();
+
+        debug!(
+            replica = %leader.id(),
+            source = %replica,
+            consumers = consumers.len(),
+            "storing mirrored consumer offsets"
+        );
+        self.store.put_mirrored(leader.id(), consumers).await
+    }
+}
+
+/// Maps offsets in peer log to local offsets using origin stamps of mirrored batches.
+/// Built incrementally as local log grows. Batches that follow each other in both logs
+/// share one range, and at most [`MAX_ORIGIN_RANGES`] ranges are kept, so offsets older
+/// than that resolve to the oldest range left.
+#[derive(Debug, Default)]
+pub(crate) struct OriginIndex {
+    /// local offset scanned up to
+    scanned: Offset,
+    /// peer batches in local log, ordered by origin offset
+    ranges: Vec<OriginRange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct OriginRange {
+    /// base offset in peer log
+    origin: Offset,
+    /// base offset in local log
+    base: Offset,
+    len: Offset,
+}
+
+impl OriginIndex {
+    async fn refresh<S: ReplicaStorage>(
+        &mut self,
+        leader: &SharedLeaderState<S>,
+        peer_cluster: &str,
+    ) -> Result<()> {
+        let (log_start, _) = leader.start_offset_info().await;
+        self.ranges
+            .retain(|range| range.base + range.len > log_start);
+
+        let mut offset = self.scanned.max(log_start);
+        while offset < leader.leo() {
+            let batches = read_leader_batches(
+                leader,
+                offset,
+                ORIGIN_SCAN_MAX_BYTES,
+                Isolation::ReadUncommitted,
+            )
+            .await?;
+            let Some(last) = batches.last() else {
+                break;
+            };
+            let next = last.get_last_offset() + 1;
+
+            for batch in batches
+                .iter()
+                .filter(|batch| batch.get_last_offset() >= offset)
+            {
+                if let Some(origin) = batch.mirror_origin() {
+                    if origin.is_from(peer_cluster) {
+                        self.insert(OriginRange {
+                            origin: origin.offset,
+                            base: batch.get_base_offset(),
+                            len: batch.last_offset_delta() as Offset + 1,
+                        });
+                    }
+                }
+            }
+
+            if next <= offset {
+                break;
+            }
+            offset = next;
+        }
+        self.scanned = offset;
+        Ok(())
+    }
+
+    fn insert(&mut self, range: OriginRange) {
+        let pos = self
+            .ranges
+            .partition_point(|existing| existing.origin < range.origin);
+        if let Some(prev) = pos
+            .checked_sub(1)
+            .and_then(|prev| self.ranges.get_mut(prev))
+        {
+            if prev.origin + prev.len == range.origin && prev.base + prev.len == range.base {
+                prev.len += range.len;
+                return;
+            }
+        }
+        self.ranges.insert(pos, range);
+        if self.ranges.len() > MAX_ORIGIN_RANGES {
+            let excess = self.ranges.len() - MAX_ORIGIN_RANGES;
+            self.ranges.drain(..excess);
+        }
+    }
+
+    /// local offset for peer offset.
+    /// if the record has not been mirrored yet, first peer record after it is used,
+    /// so nothing is skipped after failover
+    fn translate(&self, offset: Offset, leo: Offset) -> Offset {
+        let pos = self
+            .ranges
+            .partition_point(|range| range.origin + range.len <= offset);
+        match self.ranges.get(pos) {
+            Some(range) if range.origin <= offset => range.base + (offset - range.origin),
+            Some(range) => range.base,
+            None => leo,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_index_translate() {
+        let mut index = OriginIndex::default();
+        // peer records 0..5 at local 10, peer 5..8 at local 20, peer 20..22 at local 30
+        index.insert(OriginRange {
+            origin: 5,
+            base: 20,
+            len: 3,
+        });
+        index.insert(OriginRange {
+            origin: 0,
+            base: 10,
+            len: 5,
+        });
+        index.insert(OriginRange {
+            origin: 20,
+            base: 30,
+            len: 2,
+        });
+        let leo = 40;
+
+        assert_eq!(index.translate(0, leo), 10);
+        assert_eq!(index.translate(3, leo), 13);
+        assert_eq!(index.translate(5, leo), 20);
+        assert_eq!(index.translate(7, leo), 22);
+        // peer records 8..20 were never mirrored, resume at next mirrored one
+        assert_eq!(index.translate(8, leo), 30);
+        assert_eq!(index.translate(21, leo), 31);
+        // not mirrored yet
+        assert_eq!(index.translate(22, leo), leo);
+    }
+
+    #[test]
+    fn test_origin_index_compact() {
+        let mut index = OriginIndex::default();
+        // peer batches appended back to back
+        for i in 0..10 {
+            index.insert(OriginRange {
+                origin: i * 2,
+                base: 100 + i * 2,
+                len: 2,
+            });
+        }
+        assert_eq!(
+            index.ranges,
+            vec![OriginRange {
+                origin: 0,
+                base: 100,
+                len: 20
+            }]
+        );
+        assert_eq!(index.translate(13, 200), 113);
+    }
+
+    #[test]
+    fn test_origin_index_capped() {
+        let mut index = OriginIndex::default();
+        // local batch between every peer batch, so ranges can't be merged
+        for i in 0..(MAX_ORIGIN_RANGES as Offset + 5) {
+            index.insert(OriginRange {
+                origin: i,
+                base: i * 2,
+                len: 1,
+            });
+        }
+        assert_eq!(index.ranges.len(), MAX_ORIGIN_RANGES);
+        assert_eq!(index.ranges[0].origin, 5);
+        // oldest ones are dropped
+        assert_eq!(index.translate(0, i64::MAX), 10);
+        assert_eq!(index.translate(6, i64::MAX), 12);
+    }
+}
@@ -7,4 +7,7 @@ pub enum MirrorHomeApiEnum {
     #[default]
     UpdateHomeOffset = 0,
     SyncRecords = 1,
+    UpdateConsumerOffsets = 2,
+    SyncCompressedRecords = 3,
+    UpdateLinkConfig = 4,
 }
@@ -8,30 +8,49 @@ use anyhow::{Result, anyhow};
 use futures_util::StreamExt;
 
 use fluvio_auth::{AuthContext, InstanceAction};
-use fluvio_controlplane_metadata::mirror::{MirrorPairStatus, MirrorType};
+use fluvio_controlplane_metadata::mirror::{MirrorLinkConfig, MirrorPairStatus, MirrorType};
 use fluvio_controlplane_metadata::extended::ObjectType;
 use fluvio_future::timer::sleep;
-use fluvio_protocol::{record::Offset, api::RequestMessage};
-use fluvio_spu_schema::server::mirror::StartMirrorRequest;
+use fluvio_protocol::{
+    record::{Offset, RecordSet},
+    api::RequestMessage,
+};
+use fluvio_spu_schema::server::consumer_offset::MIRRORED_CONSUMER_OFFSET_API;
+use fluvio_spu_schema::server::mirror::{MIRROR_LINK_API, StartMirrorRequest};
 use fluvio_socket::{ExclusiveFlvSink, FluvioStream};
 use fluvio::Isolation;
 
 use crate::control_plane::SharedMirrorStatusUpdate;
 use crate::core::DefaultSharedGlobalContext;
+use crate::mirroring::bidirectional::{BidirectionalSync, PeerMessage, PeerSink, read_leader_batches};
+use crate::mirroring::consumer_offsets::{
+    CONSUMER_OFFSETS_MIRROR_INTERVAL_SEC, ConsumerOffsetMirror, ConsumerOffsetStore,
+    MirrorConsumerOffsets,
+};
+use crate::mirroring::link::{
+    CompressedSyncRecords, LinkSyncRecords, MAX_SYNC_RECORDS_BYTES, MirrorLink,
+};
 use crate::mirroring::remote::api_key::MirrorRemoteApiEnum;
 use crate::mirroring::remote::remote_api::RemoteMirrorRequest;
 use crate::mirroring::remote::sync::{DefaultRemotePartitionSyncRequest, MirrorPartitionSyncRequest};
 use crate::mirroring::remote::update_offsets::UpdateRemoteOffsetRequest;
-use crate::replication::leader::SharedFileLeaderState;
+use crate::mirroring::transform::MirrorTransform;
+use crate::replication::leader::{ReplicaOffsetRequest, SharedFileLeaderState};
 use crate::services::auth::SpuAuthServiceContext;
 
-use super::sync::HomeFilePartitionSyncRequest;
-use super::update_offsets::UpdateHomeOffsetRequest;
+use super::sync::{
+    DefaultHomePartitionSyncRequest, HomeCompressedSyncRequest, HomeFilePartitionSyncRequest,
+};
+use super::update_offsets::{
+    UpdateHomeConsumerOffsetsRequest, UpdateHomeOffsetRequest, UpdateLinkConfigRequest,
+};
 
 const MIRROR_RECONCILIATION_INTERVAL_SEC: u64 = 60; // 1 min
 
 const UNKNOWN_LEO: i64 = -1;
 
+const PEER_MAX_BYTES: u32 = 1024 * 1024; // 1MB
+
 pub(crate) struct MirrorRequestMetrics {
     loop_count: AtomicU64,
     remote_leo: AtomicI64,
@@ -70,6 +89,14 @@ pub(crate) struct MirrorHomeHandler {
     ctx: DefaultSharedGlobalContext,
     status_update: SharedMirrorStatusUpdate,
     remote_cluster_id: String,
+    transform: Option<MirrorTransform>,
+    consumer_offsets: ConsumerOffsetStore,
+    /// remote is able to receive consumer offsets
+    mirror_consumer_offsets: bool,
+    /// rate limit and compression from remote spec
+    link: MirrorLink,
+    /// remote accepts link settings and compressed records
+    link_supported: bool,
 }
 
 impl fmt::Debug for MirrorHomeHandler {
@@ -125,8 +152,11 @@ impl MirrorHomeHandler {
         }
 
         debug!("handling mirror request: {:#?}", req_msg);
+        let mirror_consumer_offsets = req_msg.header.api_version() >= MIRRORED_CONSUMER_OFFSET_API;
+        let link_supported = req_msg.header.api_version() >= MIRROR_LINK_API;
         let remote_replica = req_msg.request.remote_replica;
         let remote_cluster_id = req_msg.request.remote_cluster_id;
+        let home_cluster_id = req_msg.request.home_cluster_id;
 
         if let Some((leader, source)) = auth_ctx
             .global_ctx
@@ -138,15 +168,83 @@ impl MirrorHomeHandler {
             // map to actual home
             let metrics = Arc::new(MirrorRequestMetrics::new());
 
+            let transforms = leader
+                .get_replica()
+                .mirror
+                .as_ref()
+                .map(|mirror| mirror.transforms().to_vec())
+                .unwrap_or_default();
+            let dictionary = auth_ctx
+                .global_ctx
+                .dictionaries_localstore()
+                .for_replica(leader.get_replica());
+            let transform = match dictionary {
+                Ok(dictionary) => {
+                    MirrorTransform::try_new(&transforms, dictionary, &auth_ctx.global_ctx).await
+                }
+                Err(err) => Err(err.into()),
+            };
+            let transform = match transform {
+                Ok(transform) => transform,
+                Err(err) => {
+                    error!("error building mirror transform: {:#?}", err);
+                    if let Err(err) = mirror_status_update
+                        .send_status(
+                            remote_cluster_id.clone(),
+                            MirrorPairStatus::DetailFailure(err.to_string()),
+                        )
+                        .await
+                    {
+                        error!("error updating status: {}", err);
+                    }
+                    return;
+                }
+            };
+
             let handler: MirrorHomeHandler = Self {
                 metrics: metrics.clone(),
                 leader,
                 ctx: auth_ctx.global_ctx.clone(),
                 status_update: mirror_status_update.clone(),
                 remote_cluster_id: remote_cluster_id.clone(),
+                transform,
+                consumer_offsets: ConsumerOffsetStore::new(&auth_ctx.global_ctx),
+                mirror_consumer_offsets,
+                link: MirrorLink::new(
+                    auth_ctx
+                        .global_ctx
+                        .metrics()
+                        .mirror_link(&remote_cluster_id),
+                    auth_ctx.global_ctx.mirror_rate_limit(&remote_cluster_id),
+                ),
+                link_supported,
             };
 
-            if source {
+            let bidirectional = handler
+                .leader
+                .get_replica()
+                .mirror
+                .as_ref()
+                .is_some_and(|mirror| mirror.is_bidirectional());
+
+            if bidirectional {
+                if let Err(err) = handler
+                    .respond_as_peer(&home_cluster_id, sink, stream)
+                    .await
+                {
+                    error!("error handling bidirectional mirror request: {:#?}", err);
+
+                    if let Err(err) = mirror_status_update
+                        .send_status(
+                            remote_cluster_id.clone(),
+                            MirrorPairStatus::DetailFailure(err.to_string()),
+                        )
+                        .await
+                    {
+                        error!("error updating status: {}", err);
+                    }
+                }
+            } else if source {
                 if let Err(err) = handler.respond_as_source(sink, stream).await {
                     error!("error handling mirror request: {:#?}", err);
 
@@ -192,12 +290,20 @@ impl MirrorHomeHandler {
 
         // TODO: Add delete event on replica.
 
+        self.update_link(&mut sink).await?;
+
         // send initial offset state of home
         self.send_offsets_to_remote(&mut sink).await?;
 
         // create timer
         let mut timer = sleep(Duration::from_secs(MIRROR_RECONCILIATION_INTERVAL_SEC));
 
+        let mut consumer_offsets = ConsumerOffsetMirror::new(
+            self.consumer_offsets.clone(),
+            &self.remote_cluster_id,
+            false,
+        );
+
         self.update_status(MirrorPairStatus::Successful).await?;
 
         loop {
@@ -209,6 +315,7 @@ impl MirrorHomeHandler {
             select! {
                 _ = &mut timer => {
                     debug!("timer expired, sending reconciliation");
+                    self.update_link(&mut sink).await?;
                     self.send_offsets_to_remote(&mut sink).await?;
                     timer = sleep(Duration::from_secs(MIRROR_RECONCILIATION_INTERVAL_SEC));
                 },
@@ -220,9 +327,15 @@ impl MirrorHomeHandler {
                             RemoteMirrorRequest::SyncRecords(sync_request)=> {
                                 self.sync_record_from_remote(&mut sink,sync_request.request).await?;
                             }
+                            RemoteMirrorRequest::SyncCompressedRecords(sync_request)=> {
+                                self.sync_record_from_remote(&mut sink,sync_request.request.inner().decompress(MAX_SYNC_RECORDS_BYTES)?).await?;
+                            }
                             RemoteMirrorRequest::UpdateRemoteOffset(_req) => {
                                 return Err(anyhow!("received  offset request from remote, this should not happen, since we are target"));
                             }
+                            RemoteMirrorRequest::UpdateConsumerOffsets(req) => {
+                                consumer_offsets.apply(&self.leader, req.request.inner()).await;
+                            }
                          }
 
                     } else {
@@ -241,10 +354,48 @@ impl MirrorHomeHandler {
 
     async fn update_status(&self, status: MirrorPairStatus) -> Result<()> {
         self.status_update
-            .send_status(self.remote_cluster_id.clone(), status)
+            .send_link_status(
+                self.remote_cluster_id.clone(),
+                status,
+                Some(self.link.stat()),
+            )
             .await
     }
 
+    /// link settings of remote, unlimited if remote is no longer registered
+    fn remote_link_config(&self) -> MirrorLinkConfig {
+        self.ctx
+            .mirrors_localstore()
+            .all_values()
+            .into_iter()
+            .find_map(|mirror| match mirror.spec.mirror_type {
+                MirrorType::Remote(remote) if remote.id == self.remote_cluster_id => remote.link,
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// apply link settings of remote to records sent from here and push them to remote
+    async fn update_link(&self, sink: &mut ExclusiveFlvSink) -> Result<()> {
+        let config = self.remote_link_config();
+        if !self.link_supported {
+            // older remote can't decompress, only rate limit is applied
+            self.link
+                .set_config(&MirrorLinkConfig {
+                    compression: None,
+                    ..config
+                })
+                .await;
+            return Ok(());
+        }
+
+        self.link.set_config(&config).await;
+        let req_msg = RequestMessage::new_request(UpdateLinkConfigRequest::from(config))
+            .set_client_id("mirror home");
+        sink.send_request(&req_msg).await?;
+        Ok(())
+    }
+
     // send mirror home's offset to remote so it can synchronize
     async fn send_offsets_to_remote(&self, sink: &mut ExclusiveFlvSink) -> Result<()> {
         let offset_request = UpdateHomeOffsetRequest {
@@ -275,10 +426,61 @@ impl MirrorHomeHandler {
         self.send_offsets_to_remote(sink).await
     }
 
+    /// both home and remote accept producers, replicate in both directions
+    async fn respond_as_peer(
+        self,
+        home_cluster_id: &str,
+        mut sink: ExclusiveFlvSink,
+        mut stream: FluvioStream,
+    ) -> Result<()> {
+        if home_cluster_id.is_empty() {
+            return Err(anyhow!(
+                "remote did not send home cluster id, bidirectional mirror requires newer remote"
+            ));
+        }
+
+        let api_stream = stream
+            .api_stream::<RemoteMirrorRequest, MirrorRemoteApiEnum>()
+            .map(|msg| -> Result<PeerMessage> {
+                let msg = msg?;
+                Ok(match msg {
+                    RemoteMirrorRequest::SyncRecords(req) => PeerMessage::Sync(req.request),
+                    RemoteMirrorRequest::UpdateRemoteOffset(req) => {
+                        PeerMessage::Offset(req.request.offset().clone())
+                    }
+                    RemoteMirrorRequest::UpdateConsumerOffsets(req) => {
+                        PeerMessage::ConsumerOffsets(req.request.inner())
+                    }
+                    RemoteMirrorRequest::SyncCompressedRecords(req) => {
+                        PeerMessage::Sync(req.request.inner().decompress(MAX_SYNC_RECORDS_BYTES)?)
+                    }
+                })
+            });
+
+        // link settings are sent once, changes apply when remote reconnects
+        self.update_link(&mut sink).await?;
+
+        BidirectionalSync {
+            leader: &self.leader,
+            local_cluster: home_cluster_id,
+            peer_cluster: &self.remote_cluster_id,
+            mirror_id: &self.remote_cluster_id,
+            status_update: &self.status_update,
+            follower_notifier: self.ctx.follower_notifier(),
+            transform: self.transform.as_ref(),
+            consumer_offsets: &self.consumer_offsets,
+            mirror_consumer_offsets: self.mirror_consumer_offsets,
+            link: &self.link,
+            max_bytes: PEER_MAX_BYTES,
+        }
+        .run(&mut sink, api_stream)
+        .await
+    }
+
     /// respond to mirror request from remote as source
     async fn respond_as_source(
         self,
-        sink: ExclusiveFlvSink,
+        mut sink: ExclusiveFlvSink,
         mut stream: FluvioStream,
     ) -> Result<()> {
         // first send
@@ -289,6 +491,17 @@ impl MirrorHomeHandler {
 
         let mut leader_offset_listener = self.leader.offset_listener(&Isolation::ReadUncommitted);
 
+        let consumer_offsets = ConsumerOffsetMirror::new(
+            self.consumer_offsets.clone(),
+            &self.remote_cluster_id,
+            false,
+        );
+        let mut consumer_offsets_timer =
+            sleep(Duration::from_secs(CONSUMER_OFFSETS_MIRROR_INTERVAL_SEC));
+
+        self.update_link(&mut sink).await?;
+        let mut link_timer = sleep(Duration::from_secs(MIRROR_RECONCILIATION_INTERVAL_SEC));
+
         #[allow(unused_assignments)]
         loop {
             let remote_leo = self.metrics.get_remote_leo();
@@ -310,6 +523,20 @@ impl MirrorHomeHandler {
                     remote_updated_needed = true;
                 },
 
+                _ = &mut consumer_offsets_timer => {
+                    if self.mirror_consumer_offsets {
+                        if let Some(offsets) = consumer_offsets.source_offsets(&self.leader).await {
+                            debug!(consumers = offsets.consumers.len(), "sending consumer offsets");
+                            sink.send_consumer_offsets(offsets).await?;
+                        }
+                    }
+                    consumer_offsets_timer = sleep(Duration::from_secs(CONSUMER_OFFSETS_MIRROR_INTERVAL_SEC));
+                },
+
+                _ = &mut link_timer => {
+                    self.update_link(&mut sink).await?;
+                    link_timer = sleep(Duration::from_secs(MIRROR_RECONCILIATION_INTERVAL_SEC));
+                },
 
                 remote_msg = api_stream.next() => {
                     if let Some(req_msg_res) = remote_msg {
@@ -322,6 +549,12 @@ impl MirrorHomeHandler {
                             RemoteMirrorRequest::UpdateRemoteOffset(req) => {
                                 remote_updated_needed = self.update_from_remote(req)?;
                             }
+                            RemoteMirrorRequest::UpdateConsumerOffsets(_req) => {
+                                return Err(anyhow!("received consumer offsets from remote, this should not happen, since we are source"));
+                            }
+                            RemoteMirrorRequest::SyncCompressedRecords(_sync_request)=> {
+                                return Err(anyhow!("received sync request from remote, this should not happen, since we are source"));
+                            }
                          }
 
                     } else {
@@ -347,8 +580,15 @@ impl MirrorHomeHandler {
         remote_leo: Offset,
     ) -> Result<()> {
         debug!("updating home cluster");
+        self.link
+            .update_pending((self.leader.leo() - remote_leo).max(0) as u64);
+        if self.transform.is_some() || self.link.is_compressed().await {
+            return self.send_decoded_records_to_remote(sink, remote_leo).await;
+        }
         if let Some(sync_request) = self.generate_home_records_as_source(remote_leo).await? {
             debug!(?sync_request, "home sync");
+            let bytes = sync_request.records_len();
+            self.link.throttle(bytes, bytes).await;
             let request = RequestMessage::new_request(sync_request)
                 .set_client_id(format!("leader: {}", self.leader.id()));
 
@@ -362,6 +602,65 @@ impl MirrorHomeHandler {
         }
     }
 
+    /// records are decoded to be transformed or compressed, so zero copy is not used
+    async fn send_decoded_records_to_remote(
+        &self,
+        sink: &ExclusiveFlvSink,
+        remote_leo: Offset,
+    ) -> Result<()> {
+        const MAX_BYTES: u32 = 1024 * 1024; // 1MB
+
+        let leader_offset = self.leader.as_offset();
+        if leader_offset.leo <= remote_leo {
+            debug!("remote has caught up, just chilling out");
+            return Ok(());
+        }
+
+        let records = match &self.transform {
+            Some(transform) => {
+                transform
+                    .read_records(
+                        &self.leader,
+                        remote_leo,
+                        MAX_BYTES,
+                        Isolation::ReadUncommitted,
+                    )
+                    .await?
+            }
+            None => RecordSet {
+                batches: read_leader_batches(
+                    &self.leader,
+                    remote_leo,
+                    MAX_BYTES,
+                    Isolation::ReadUncommitted,
+                )
+                .await?,
+            },
+        };
+        let sync_request = MirrorPartitionSyncRequest {
+            leo: leader_offset.leo,
+            hw: leader_offset.hw,
+            records,
+        };
+        debug!(?sync_request, "home sync from memory");
+        let client_id = format!("leader: {}", self.leader.id());
+        match self.link.pack(sync_request).await? {
+            LinkSyncRecords::Plain(sync_request) => {
+                let request = RequestMessage::new_request(DefaultHomePartitionSyncRequest::from(
+                    sync_request,
+                ))
+                .set_client_id(client_id);
+                sink.send_request(&request).await?;
+            }
+            LinkSyncRecords::Compressed(records) => {
+                let request = RequestMessage::new_request(HomeCompressedSyncRequest::from(records))
+                    .set_client_id(client_id);
+                sink.send_request(&request).await?;
+            }
+        }
+        Ok(())
+    }
+
     /// home is source, generate missing records to send to \remote
     async fn generate_home_records_as_source(
         &self,
@@ -470,3 +769,33 @@ impl MirrorHomeHandler {
         }
     }
 }
+
+impl PeerSink for ExclusiveFlvSink {
+    async fn send_records(&mut self, request: DefaultRemotePartitionSyncRequest) -> Result<()> {
+        let req_msg = RequestMessage::new_request(DefaultHomePartitionSyncRequest::from(request))
+            .set_client_id("mirror home");
+        self.send_request(&req_msg).await?;
+        Ok(())
+    }
+
+    async fn send_compressed_records(&mut self, records: CompressedSyncRecords) -> Result<()> {
+        let req_msg = RequestMessage::new_request(HomeCompressedSyncRequest::from(records))
+            .set_client_id("mirror home");
+        self.send_request(&req_msg).await?;
+        Ok(())
+    }
+
+    async fn send_offset(&mut self, request: ReplicaOffsetRequest) -> Result<()> {
+        let req_msg = RequestMessage::<UpdateHomeOffsetRequest>::new_request(request)
+            .set_client_id("mirror home");
+        self.send_request(&req_msg).await?;
+        Ok(())
+    }
+
+    async fn send_consumer_offsets(&mut self, offsets: MirrorConsumerOffsets) -> Result<()> {
+        let req_msg = RequestMessage::new_request(UpdateHomeConsumerOffsetsRequest::from(offsets))
+            .set_client_id("mirror home");
+        self.send_request(&req_msg).await?;
+        Ok(())
+    }
+}
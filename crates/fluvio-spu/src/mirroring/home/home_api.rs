@@ -7,16 +7,21 @@ use fluvio_protocol::bytes::Buf;
 use fluvio_protocol::Decoder;
 use fluvio_protocol::api::{RequestMessage, ApiMessage, RequestHeader};
 
-use crate::mirroring::home::sync::DefaultHomePartitionSyncRequest;
+use crate::mirroring::home::sync::{DefaultHomePartitionSyncRequest, HomeCompressedSyncRequest};
 
 use super::api_key::MirrorHomeApiEnum;
-use super::update_offsets::UpdateHomeOffsetRequest;
+use super::update_offsets::{
+    UpdateHomeConsumerOffsetsRequest, UpdateHomeOffsetRequest, UpdateLinkConfigRequest,
+};
 
 /// Requests from home to remote
 #[derive(Debug)]
 pub enum HomeMirrorRequest {
     UpdateHomeOffset(RequestMessage<UpdateHomeOffsetRequest>),
     SyncRecords(RequestMessage<DefaultHomePartitionSyncRequest>),
+    UpdateConsumerOffsets(RequestMessage<UpdateHomeConsumerOffsetsRequest>),
+    SyncCompressedRecords(RequestMessage<HomeCompressedSyncRequest>),
+    UpdateLinkConfig(RequestMessage<UpdateLinkConfigRequest>),
 }
 
 impl Default for HomeMirrorRequest {
@@ -45,6 +50,22 @@ impl ApiMessage for HomeMirrorRequest {
                 header,
                 DefaultHomePartitionSyncRequest::decode_from(src, version)?,
             ))),
+            MirrorHomeApiEnum::UpdateConsumerOffsets => {
+                Ok(Self::UpdateConsumerOffsets(RequestMessage::new(
+                    header,
+                    UpdateHomeConsumerOffsetsRequest::decode_from(src, version)?,
+                )))
+            }
+            MirrorHomeApiEnum::SyncCompressedRecords => {
+                Ok(Self::SyncCompressedRecords(RequestMessage::new(
+                    header,
+                    HomeCompressedSyncRequest::decode_from(src, version)?,
+                )))
+            }
+            MirrorHomeApiEnum::UpdateLinkConfig => Ok(Self::UpdateLinkConfig(RequestMessage::new(
+                header,
+                UpdateLinkConfigRequest::decode_from(src, version)?,
+            ))),
         }
     }
 }
@@ -15,6 +15,7 @@ use fluvio_spu_schema::file::FileRecordSet;
 use crate::mirroring::remote::sync::MirrorPartitionSyncRequest;
 use crate::mirroring::remote::sync::MirrorPartitionSyncResponse;
 use crate::mirroring::COMMON_MIRROR_VERSION;
+use crate::mirroring::link::CompressedSyncRecords;
 
 use super::api_key::MirrorHomeApiEnum;
 
@@ -53,6 +54,35 @@ where
     }
 }
 
+/// Records compressed with mirror link compression
+#[derive(Encoder, Decoder, Default, Debug)]
+pub(crate) struct HomeCompressedSyncRequest(CompressedSyncRecords);
+
+impl From<CompressedSyncRecords> for HomeCompressedSyncRequest {
+    fn from(records: CompressedSyncRecords) -> Self {
+        Self(records)
+    }
+}
+
+impl HomeCompressedSyncRequest {
+    pub fn inner(self) -> CompressedSyncRecords {
+        self.0
+    }
+}
+
+impl Request for HomeCompressedSyncRequest {
+    const API_KEY: u16 = MirrorHomeApiEnum::SyncCompressedRecords as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_MIRROR_VERSION;
+    type Response = MirrorPartitionSyncResponse;
+}
+
+impl HomeFilePartitionSyncRequest {
+    /// bytes of records sent with zero copy
+    pub fn records_len(&self) -> usize {
+        self.0.records.len()
+    }
+}
+
 impl FileWrite for HomeFilePartitionSyncRequest {
     fn file_encode(
         &self,
@@ -1,7 +1,9 @@
+use fluvio_controlplane_metadata::mirror::MirrorLinkConfig;
 use fluvio_protocol::{Encoder, Decoder};
 use fluvio_protocol::api::Request;
 
 use crate::mirroring::COMMON_MIRROR_VERSION;
+use crate::mirroring::consumer_offsets::MirrorConsumerOffsets;
 use crate::replication::leader::ReplicaOffsetRequest;
 
 use super::api_key::MirrorHomeApiEnum;
@@ -18,3 +20,47 @@ impl Request for UpdateHomeOffsetRequest {
 // no content, this is one way request
 #[derive(Decoder, Encoder, Default, Debug)]
 pub struct UpdateHomeOffsetResponse {}
+
+/// Consumer offsets committed on home
+#[derive(Decoder, Encoder, Default, Clone, Debug)]
+pub(crate) struct UpdateHomeConsumerOffsetsRequest(MirrorConsumerOffsets);
+
+impl From<MirrorConsumerOffsets> for UpdateHomeConsumerOffsetsRequest {
+    fn from(offsets: MirrorConsumerOffsets) -> Self {
+        Self(offsets)
+    }
+}
+
+impl UpdateHomeConsumerOffsetsRequest {
+    pub fn inner(self) -> MirrorConsumerOffsets {
+        self.0
+    }
+}
+
+impl Request for UpdateHomeConsumerOffsetsRequest {
+    const API_KEY: u16 = MirrorHomeApiEnum::UpdateConsumerOffsets as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_MIRROR_VERSION;
+    type Response = UpdateHomeOffsetResponse;
+}
+
+/// Link settings of remote, owned by home
+#[derive(Decoder, Encoder, Default, Clone, Debug)]
+pub(crate) struct UpdateLinkConfigRequest(MirrorLinkConfig);
+
+impl From<MirrorLinkConfig> for UpdateLinkConfigRequest {
+    fn from(config: MirrorLinkConfig) -> Self {
+        Self(config)
+    }
+}
+
+impl UpdateLinkConfigRequest {
+    pub fn inner(self) -> MirrorLinkConfig {
+        self.0
+    }
+}
+
+impl Request for UpdateLinkConfigRequest {
+    const API_KEY: u16 = MirrorHomeApiEnum::UpdateLinkConfig as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_MIRROR_VERSION;
+    type Response = UpdateHomeOffsetResponse;
+}
@@ -0,0 +1,336 @@
+//! Settings applied to records sent over a mirror connection.
+//!
+//! Home owns the [`MirrorLinkConfig`] of each remote and pushes it over the connection,
+//! so edge sites on metered links can be throttled centrally:
+//!
+//! * rate limit is a token bucket holding at most one second of traffic, shared by all
+//!   partitions of the mirror on an SPU, so the limit applies per SPU;
+//!   a sync request that takes the bucket into debt is held back until the debt is paid off
+//! * compression is applied to the whole sync payload, batches keep the topic compression.
+//!   Zero copy can't be used while link compression is on, records are read into memory
+//!
+//! Bytes sent, time spent throttled and records waiting to be acknowledged are accumulated
+//! per mirror and reported in mirror status.
+
+use std::io::Cursor;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use async_lock::Mutex;
+use tracing::debug;
+
+use fluvio_compression::Compression;
+use fluvio_controlplane_metadata::mirror::{MirrorLinkConfig, MirrorLinkStat};
+use fluvio_controlplane_metadata::topic::CompressionAlgorithm;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::{ByteBuf, Decoder, Encoder};
+use fluvio_protocol::record::{RawRecords, RecordSet};
+
+use crate::core::metrics::MirrorLinkMetrics;
+
+use super::COMMON_MIRROR_VERSION;
+use super::remote::sync::DefaultRemotePartitionSyncRequest;
+
+/// largest uncompressed sync payload accepted from peer.
+/// Well above what either side reads for one sync, so only corrupt or hostile payloads hit it
+pub(crate) const MAX_SYNC_RECORDS_BYTES: usize = 64 * 1024 * 1024; // 64MB
+
+/// Sync records compressed as a whole for the wire
+#[derive(Encoder, Decoder, Default, Debug)]
+pub(crate) struct CompressedSyncRecords {
+    pub compression: CompressionAlgorithm,
+    pub hw: i64,
+    pub leo: i64,
+    /// encoded record set
+    pub payload: ByteBuf,
+}
+
+impl CompressedSyncRecords {
+    fn compress(
+        request: &DefaultRemotePartitionSyncRequest,
+        compression: &CompressionAlgorithm,
+    ) -> Result<Self> {
+        let mut buf = Vec::with_capacity(request.records.write_size(COMMON_MIRROR_VERSION));
+        request.records.encode(&mut buf, COMMON_MIRROR_VERSION)?;
+        let payload = to_compression(compression).compress(&buf)?;
+        Ok(Self {
+            compression: compression.clone(),
+            hw: request.hw,
+            leo: request.leo,
+            payload: payload.into(),
+        })
+    }
+
+    /// restore sync request as sent by peer, payload can't expand beyond max_bytes
+    pub(crate) fn decompress(self, max_bytes: usize) -> Result<DefaultRemotePartitionSyncRequest> {
+        let payload = match to_compression(&self.compression)
+            .uncompress_bounded(&self.payload, max_bytes)
+            .map_err(|err| anyhow!("invalid compressed sync records: {err}"))?
+        {
+            Some(payload) if payload.len() <= max_bytes => payload,
+            None if self.payload.len() <= max_bytes => self.payload.to_vec(),
+            _ => return Err(anyhow!("sync records exceed {max_bytes} bytes")),
+        };
+        let records =
+            RecordSet::<RawRecords>::decode_from(&mut Cursor::new(payload), COMMON_MIRROR_VERSION)?;
+        Ok(DefaultRemotePartitionSyncRequest {
+            hw: self.hw,
+            leo: self.leo,
+            records,
+        })
+    }
+}
+
+fn to_compression(compression: &CompressionAlgorithm) -> Compression {
+    match compression {
+        CompressionAlgorithm::None | CompressionAlgorithm::Any => Compression::None,
+        CompressionAlgorithm::Gzip => Compression::Gzip,
+        CompressionAlgorithm::Snappy => Compression::Snappy,
+        CompressionAlgorithm::Lz4 => Compression::Lz4,
+        CompressionAlgorithm::Zstd => Compression::Zstd,
+    }
+}
+
+/// Sync request ready to be written to the connection
+pub(crate) enum LinkSyncRecords {
+    Plain(DefaultRemotePartitionSyncRequest),
+    Compressed(CompressedSyncRecords),
+}
+
+/// Token bucket, allows burst of one second
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    rate: u64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(rate: u64, now: Instant) -> Self {
+        Self {
+            rate: rate.max(1),
+            tokens: rate.max(1) as f64,
+            last: now,
+        }
+    }
+
+    /// take bytes from the bucket, returns how long sender has to wait before sending them
+    pub(crate) fn reserve(&mut self, bytes: u64, now: Instant) -> Duration {
+        let rate = self.rate as f64;
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.last = self.last.max(now);
+        self.tokens = (self.tokens + elapsed * rate).min(rate) - bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / rate)
+        }
+    }
+}
+
+/// Rate limit of a mirror, shared by all its partitions led by this SPU
+#[derive(Debug, Default)]
+pub(crate) struct MirrorRateLimit {
+    limiter: Mutex<Option<RateLimiter>>,
+}
+
+impl MirrorRateLimit {
+    /// unused tokens are kept if rate has not changed
+    async fn set_rate(&self, rate: Option<u64>) {
+        let mut limiter = self.limiter.lock().await;
+        *limiter = rate.map(|rate| match limiter.take() {
+            Some(limiter) if limiter.rate == rate => limiter,
+            _ => RateLimiter::new(rate, Instant::now()),
+        });
+    }
+
+    async fn reserve(&self, bytes: u64) -> Duration {
+        self.limiter
+            .lock()
+            .await
+            .as_mut()
+            .map(|limiter| limiter.reserve(bytes, Instant::now()))
+            .unwrap_or_default()
+    }
+}
+
+/// Sending side of mirror connection for one partition
+#[derive(Debug)]
+pub(crate) struct MirrorLink {
+    config: Mutex<MirrorLinkConfig>,
+    rate_limit: Arc<MirrorRateLimit>,
+    metrics: Arc<MirrorLinkMetrics>,
+    /// share of this partition in pending records of mirror
+    pending: AtomicU64,
+}
+
+impl MirrorLink {
+    pub(crate) fn new(metrics: Arc<MirrorLinkMetrics>, rate_limit: Arc<MirrorRateLimit>) -> Self {
+        Self {
+            config: Mutex::new(MirrorLinkConfig::default()),
+            rate_limit,
+            metrics,
+            pending: AtomicU64::new(0),
+        }
+    }
+
+    /// apply new settings, unused tokens are kept if rate has not changed
+    pub(crate) async fn set_config(&self, config: &MirrorLinkConfig) {
+        let mut current = self.config.lock().await;
+        if *current == *config {
+            return;
+        }
+        debug!(%config, "mirror link config changed");
+        self.rate_limit.set_rate(config.max_bytes_per_sec).await;
+        *current = config.clone();
+    }
+
+    /// records are read into memory when compressed on the wire
+    pub(crate) async fn is_compressed(&self) -> bool {
+        self.config.lock().await.wire_compression().is_some()
+    }
+
+    /// compress request if configured and wait until it can be sent under the rate limit
+    pub(crate) async fn pack(
+        &self,
+        request: DefaultRemotePartitionSyncRequest,
+    ) -> Result<LinkSyncRecords> {
+        let uncompressed = request.records.write_size(COMMON_MIRROR_VERSION);
+        let compression = self.config.lock().await.wire_compression().cloned();
+        let (records, wire) = match compression {
+            Some(compression) => {
+                let compressed = CompressedSyncRecords::compress(&request, &compression)?;
+                let wire = compressed.payload.len();
+                (LinkSyncRecords::Compressed(compressed), wire)
+            }
+            None => (LinkSyncRecords::Plain(request), uncompressed),
+        };
+        self.throttle(wire, uncompressed).await;
+        Ok(records)
+    }
+
+    /// wait until bytes can be sent under the rate limit and count them as sent
+    pub(crate) async fn throttle(&self, wire: usize, uncompressed: usize) {
+        let wait = self.rate_limit.reserve(wire as u64).await;
+        if !wait.is_zero() {
+            debug!(
+                wait_ms = wait.as_millis() as u64,
+                wire, "mirror link throttled"
+            );
+            self.metrics.increase_throttled(wait);
+            sleep(wait).await;
+        }
+        self.metrics.increase_sent(wire as u64, uncompressed as u64);
+    }
+
+    /// records in local log mirror has not acknowledged yet
+    pub(crate) fn update_pending(&self, pending: u64) {
+        let old = self.pending.swap(pending, Ordering::SeqCst);
+        self.metrics.replace_pending(old, pending);
+    }
+
+    pub(crate) fn stat(&self) -> MirrorLinkStat {
+        self.metrics.stat()
+    }
+}
+
+impl Drop for MirrorLink {
+    fn drop(&mut self) {
+        self.update_pending(0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fluvio_protocol::record::{Batch, Record};
+
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_reserve() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(1000, start);
+
+        // burst of one second is available
+        assert_eq!(limiter.reserve(500, start), Duration::ZERO);
+        // exceeding burst waits for debt
+        assert_eq!(limiter.reserve(1000, start), Duration::from_millis(500));
+        // after waiting, bucket is empty but not in debt
+        let after_wait = start + Duration::from_millis(500);
+        assert_eq!(limiter.reserve(0, after_wait), Duration::ZERO);
+        // idle time does not accumulate more than burst
+        let idle = after_wait + Duration::from_secs(10);
+        assert_eq!(limiter.reserve(2000, idle), Duration::from_secs(1));
+    }
+
+    #[fluvio_future::test]
+    async fn test_rate_limit_shared_by_partitions() {
+        let metrics = Arc::new(MirrorLinkMetrics::default());
+        let rate_limit = Arc::new(MirrorRateLimit::default());
+        let config = MirrorLinkConfig {
+            max_bytes_per_sec: Some(1000),
+            ..Default::default()
+        };
+        let first = MirrorLink::new(metrics.clone(), rate_limit.clone());
+        let second = MirrorLink::new(metrics, rate_limit.clone());
+        first.set_config(&config).await;
+        second.set_config(&config).await;
+
+        // burst of one second is spent by both partitions together
+        first.throttle(600, 600).await;
+        assert!(rate_limit.reserve(0).await.is_zero());
+        let wait = rate_limit.reserve(600).await;
+        assert!(wait > Duration::from_millis(100), "{wait:?}");
+    }
+
+    #[test]
+    fn test_compressed_sync_records_roundtrip() {
+        let mut batch = Batch::from(vec![Record::new("hello"), Record::new("world")]);
+        batch.base_offset = 10;
+        let request = DefaultRemotePartitionSyncRequest {
+            hw: 11,
+            leo: 12,
+            records: RecordSet::default().add(Batch::<RawRecords>::try_from(batch).expect("raw")),
+        };
+
+        let compressed = CompressedSyncRecords::compress(&request, &CompressionAlgorithm::Gzip)
+            .expect("compress");
+        let mut buf = vec![];
+        compressed
+            .encode(&mut buf, COMMON_MIRROR_VERSION)
+            .expect("encode");
+        let decoded =
+            CompressedSyncRecords::decode_from(&mut Cursor::new(buf), COMMON_MIRROR_VERSION)
+                .expect("decode")
+                .decompress(MAX_SYNC_RECORDS_BYTES)
+                .expect("decompress");
+
+        assert_eq!(decoded.hw, 11);
+        assert_eq!(decoded.leo, 12);
+        assert_eq!(decoded.records.batches.len(), 1);
+        assert_eq!(decoded.records.batches[0].base_offset, 10);
+        assert_eq!(decoded.records.batches[0].records_len(), 2);
+    }
+
+    #[test]
+    fn test_compressed_sync_records_bounded() {
+        let batch = Batch::from(vec![Record::new("a".repeat(10_000))]);
+        let request = DefaultRemotePartitionSyncRequest {
+            hw: 1,
+            leo: 1,
+            records: RecordSet::default().add(Batch::<RawRecords>::try_from(batch).expect("raw")),
+        };
+        let size = request.records.write_size(COMMON_MIRROR_VERSION);
+
+        // small payload on the wire can't expand beyond limit
+        for compression in [CompressionAlgorithm::Gzip, CompressionAlgorithm::None] {
+            let compress =
+                || CompressedSyncRecords::compress(&request, &compression).expect("compress");
+            assert!(compress().decompress(size).is_ok());
+            let err = compress().decompress(size - 1).expect_err("too large");
+            assert!(err.to_string().contains("exceed"), "{err}");
+        }
+    }
+}
@@ -1,5 +1,9 @@
 pub(crate) mod remote;
 pub(crate) mod home;
+pub(crate) mod bidirectional;
+pub(crate) mod transform;
+pub(crate) mod consumer_offsets;
+pub(crate) mod link;
 
 #[cfg(test)]
 mod test;
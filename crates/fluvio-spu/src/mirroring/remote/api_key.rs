@@ -7,4 +7,6 @@ pub enum MirrorRemoteApiEnum {
     #[default]
     SyncRecords = 0,
     UpdateEdgeOffset = 1,
+    UpdateConsumerOffsets = 2,
+    SyncCompressedRecords = 3,
 }
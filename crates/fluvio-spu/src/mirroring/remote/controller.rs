@@ -22,15 +22,30 @@ use fluvio_controlplane_metadata::{
 };
 use fluvio_storage::{ReplicaStorage, FileReplica};
 use fluvio_socket::{ClientConfig, FluvioSink, FluvioSocket};
-use fluvio_spu_schema::{Isolation, server::mirror::StartMirrorRequest};
+use fluvio_spu_schema::{
+    Isolation,
+    server::{consumer_offset::MIRRORED_CONSUMER_OFFSET_API, mirror::StartMirrorRequest},
+};
 use fluvio_future::{net::DomainConnector, task::spawn, timer::sleep};
-use fluvio_protocol::{record::Offset, api::RequestMessage};
+use fluvio_protocol::{
+    record::{Offset, RecordSet},
+    api::RequestMessage,
+};
 use fluvio_types::event::offsets::OffsetChangeListener;
 
 use crate::{
     control_plane::SharedMirrorStatusUpdate,
     core::{mirror::SharedMirrorLocalStore, GlobalContext},
-    mirroring::remote::update_offsets::UpdateRemoteOffsetRequest,
+    mirroring::bidirectional::{BidirectionalSync, PeerMessage, PeerSink, read_leader_batches},
+    mirroring::consumer_offsets::{
+        CONSUMER_OFFSETS_MIRROR_INTERVAL_SEC, ConsumerOffsetMirror, ConsumerOffsetStore,
+        MirrorConsumerOffsets,
+    },
+    mirroring::link::{CompressedSyncRecords, LinkSyncRecords, MAX_SYNC_RECORDS_BYTES, MirrorLink},
+    mirroring::transform::MirrorTransform,
+    mirroring::remote::update_offsets::{
+        UpdateRemoteConsumerOffsetsRequest, UpdateRemoteOffsetRequest,
+    },
     replication::leader::{FollowerNotifier, ReplicaOffsetRequest, SharedLeaderState},
 };
 use crate::mirroring::home::{
@@ -38,7 +53,9 @@ use crate::mirroring::home::{
     update_offsets::UpdateHomeOffsetRequest,
 };
 
-use super::sync::{DefaultRemotePartitionSyncRequest, RemoteFilePartitionSyncRequest};
+use super::sync::{
+    DefaultRemotePartitionSyncRequest, RemoteCompressedSyncRequest, RemoteFilePartitionSyncRequest,
+};
 
 pub(crate) type SharedMirrorControllerState = Arc<MirrorControllerState>;
 
@@ -134,6 +151,10 @@ pub(crate) struct MirrorRemoteToHomeController<S> {
     max_bytes: u32,
     isolation: Isolation,
     follower_notifier: Arc<FollowerNotifier>,
+    transform: Option<MirrorTransform>,
+    consumer_offsets: ConsumerOffsetStore,
+    /// rate limit and compression set by home
+    link: MirrorLink,
 }
 
 impl<S> fmt::Debug for MirrorRemoteToHomeController<S>
@@ -158,6 +179,7 @@ where
         ctx: &GlobalContext<FileReplica>,
         leader: SharedLeaderState<S>,
         remote_config: RemotePartitionConfig,
+        transform: Option<MirrorTransform>,
         isolation: Isolation,
         max_bytes: u32,
     ) -> SharedMirrorControllerState {
@@ -166,6 +188,10 @@ where
             max_bytes,
             "starting mirror remote controller {:#?}",remote_config);
         let state = Arc::new(MirrorControllerState::new());
+        let link = MirrorLink::new(
+            ctx.metrics().mirror_link(&remote_config.home_cluster),
+            ctx.mirror_rate_limit(&remote_config.home_cluster),
+        );
 
         let controller = Self {
             leader,
@@ -176,6 +202,9 @@ where
             mirror_store: ctx.mirrors_localstore_owned(),
             status_update: ctx.mirror_status_update_owned(),
             follower_notifier: ctx.follower_notifier_owned(),
+            transform,
+            consumer_offsets: ConsumerOffsetStore::new(ctx),
+            link,
         };
         spawn(controller.dispatch_loop());
         state
@@ -194,7 +223,18 @@ where
                 let home_socket = self.create_socket_to_home(&mut backoff, &home).await;
                 debug!("created socket to home");
 
-                if self.remote_config.target {
+                if self.remote_config.bidirectional {
+                    if let Err(err) = self
+                        .sync_mirror_as_peer(&home, home_socket, &mut backoff)
+                        .await
+                    {
+                        self.update_status(MirrorPairStatus::DetailFailure(err.to_string()))
+                            .await
+                            .unwrap();
+                        error!("error syncing bidirectional mirror {}", err);
+                        self.backoff_and_wait(&mut backoff).await;
+                    }
+                } else if self.remote_config.target {
                     if let Err(err) = self
                         .sync_mirror_as_target(&home, home_socket, &mut backoff)
                         .await
@@ -225,7 +265,7 @@ where
         &self,
         home: &Home,
         leader_offset_listner: &mut OffsetChangeListener,
-        (home_socket, tls): (FluvioSocket, bool),
+        (home_socket, tls, mirror_consumer_offsets): (FluvioSocket, bool, bool),
         backoff: &mut ExponentialBackoff,
     ) -> Result<()> {
         debug!(home_id = home.id, "start syncing mirror as source");
@@ -244,6 +284,11 @@ where
         // this flag is set to true, home need to be refreshed leader's offsets and any recordset.
         let mut home_updated_needed = false;
 
+        let consumer_offsets =
+            ConsumerOffsetMirror::new(self.consumer_offsets.clone(), &home.id, false);
+        let mut consumer_offsets_timer =
+            sleep(Duration::from_secs(CONSUMER_OFFSETS_MIRROR_INTERVAL_SEC));
+
         // home_updated_needed triggers warning, despite being used in loop
         #[allow(unused)]
         loop {
@@ -265,6 +310,16 @@ where
                     home_updated_needed = true;
                 }
 
+                _ = &mut consumer_offsets_timer => {
+                    if mirror_consumer_offsets {
+                        if let Some(offsets) = consumer_offsets.source_offsets(&self.leader).await {
+                            debug!(consumers = offsets.consumers.len(), "sending consumer offsets");
+                            home_sink.send_consumer_offsets(offsets).await?;
+                        }
+                    }
+                    consumer_offsets_timer = sleep(Duration::from_secs(CONSUMER_OFFSETS_MIRROR_INTERVAL_SEC));
+                }
+
                 msg = home_api_stream.next() => {
                     debug!("received response from home");
                     if let Some(req_msg_home) = msg {
@@ -277,6 +332,15 @@ where
                             HomeMirrorRequest::SyncRecords(sync_request)=> {
                                 return Err(anyhow!("received sync record request from home, this should not happen, since we are source"));
                             }
+                            HomeMirrorRequest::UpdateConsumerOffsets(_req)=> {
+                                return Err(anyhow!("received consumer offsets from home, this should not happen, since we are source"));
+                            }
+                            HomeMirrorRequest::SyncCompressedRecords(_req)=> {
+                                return Err(anyhow!("received sync record request from home, this should not happen, since we are source"));
+                            }
+                            HomeMirrorRequest::UpdateLinkConfig(req)=> {
+                                self.link.set_config(&req.request.inner()).await;
+                            }
                          }
                         self.update_status(MirrorPairStatus::Successful).await?;
                         backoff.reset();
@@ -304,7 +368,7 @@ where
     async fn sync_mirror_as_target(
         &self,
         home: &Home,
-        (home_socket, tls): (FluvioSocket, bool),
+        (home_socket, tls, _): (FluvioSocket, bool, bool),
         backoff: &mut ExponentialBackoff,
     ) -> Result<()> {
         info!(home_id = home.id, "start syncing mirror as target");
@@ -322,6 +386,9 @@ where
 
         let mut paired: bool = false; // pairing status
 
+        let mut consumer_offsets =
+            ConsumerOffsetMirror::new(self.consumer_offsets.clone(), &home.id, false);
+
         self.send_offsets_to_home_as_target(&mut home_sink).await?;
 
         // timer to update offsets to home
@@ -356,6 +423,22 @@ where
                                 self.sync_record_from_home(sync_request.request.inner()).await?;
                                 self.send_offsets_to_home_as_target(&mut home_sink).await?;
                             }
+                            HomeMirrorRequest::SyncCompressedRecords(sync_request)=> {
+                                if(!paired) {
+                                    info!("sync received for the first time, indicating paired");
+                                    self.update_status(MirrorPairStatus::Successful).await?;
+                                    paired = true;
+                                }
+
+                                self.sync_record_from_home(sync_request.request.inner().decompress(MAX_SYNC_RECORDS_BYTES)?).await?;
+                                self.send_offsets_to_home_as_target(&mut home_sink).await?;
+                            }
+                            HomeMirrorRequest::UpdateConsumerOffsets(req)=> {
+                                consumer_offsets.apply(&self.leader, req.request.inner()).await;
+                            }
+                            HomeMirrorRequest::UpdateLinkConfig(req)=> {
+                                self.link.set_config(&req.request.inner()).await;
+                            }
                          }
                         backoff.reset();
                     } else {
@@ -377,9 +460,78 @@ where
         Ok(())
     }
 
+    #[instrument(skip(home, home_socket, tls, backoff))]
+    // sync loop when both home and remote accept producers
+    async fn sync_mirror_as_peer(
+        &self,
+        home: &Home,
+        (home_socket, tls, mirror_consumer_offsets): (FluvioSocket, bool, bool),
+        backoff: &mut ExponentialBackoff,
+    ) -> Result<()> {
+        info!(home_id = home.id, "start syncing mirror as peer");
+
+        let (mut home_sink, mut home_stream) = home_socket.split();
+
+        if tls {
+            debug!("tls enabled, disabling zero copy sink");
+            home_sink.disable_zerocopy();
+        }
+
+        let home_api_stream = home_stream
+            .api_stream::<HomeMirrorRequest, MirrorHomeApiEnum>()
+            .map(|msg| -> Result<PeerMessage> {
+                let msg = msg?;
+                Ok(match msg {
+                    HomeMirrorRequest::SyncRecords(req) => PeerMessage::Sync(req.request.inner()),
+                    HomeMirrorRequest::UpdateHomeOffset(req) => PeerMessage::Offset(req.request),
+                    HomeMirrorRequest::UpdateConsumerOffsets(req) => {
+                        PeerMessage::ConsumerOffsets(req.request.inner())
+                    }
+                    HomeMirrorRequest::SyncCompressedRecords(req) => {
+                        PeerMessage::Sync(req.request.inner().decompress(MAX_SYNC_RECORDS_BYTES)?)
+                    }
+                    HomeMirrorRequest::UpdateLinkConfig(req) => {
+                        PeerMessage::LinkConfig(req.request.inner())
+                    }
+                })
+            });
+
+        self.send_initial_request(home, &mut home_sink).await?;
+        backoff.reset();
+
+        BidirectionalSync {
+            leader: &self.leader,
+            local_cluster: &home.remote_id,
+            peer_cluster: &home.id,
+            mirror_id: &self.remote_config.home_cluster,
+            status_update: &self.status_update,
+            follower_notifier: &self.follower_notifier,
+            transform: self.transform.as_ref(),
+            consumer_offsets: &self.consumer_offsets,
+            mirror_consumer_offsets,
+            link: &self.link,
+            max_bytes: self.max_bytes,
+        }
+        .run(&mut home_sink, home_api_stream)
+        .await?;
+
+        warn!("spu socket to home has terminated");
+        self.update_status(MirrorPairStatus::DetailFailure(
+            "closed connection".to_owned(),
+        ))
+        .await?;
+        self.backoff_and_wait(backoff).await;
+
+        Ok(())
+    }
+
     async fn update_status(&self, pair_status: MirrorPairStatus) -> Result<()> {
         self.status_update
-            .send_status(self.remote_config.home_cluster.clone(), pair_status)
+            .send_link_status(
+                self.remote_config.home_cluster.clone(),
+                pair_status,
+                Some(self.link.stat()),
+            )
             .await
     }
 
@@ -390,6 +542,7 @@ where
         let start_mirror_request = RequestMessage::new_request(StartMirrorRequest {
             remote_cluster_id: home.remote_id.clone(),
             remote_replica: self.leader.id().to_string(),
+            home_cluster_id: home.id.clone(),
         });
 
         info!(remote_id = home.remote_id, cluster = %self.leader.id(),"sending start mirror request");
@@ -468,8 +621,15 @@ where
     #[instrument]
     async fn update_remote_as_source(&self, sink: &mut FluvioSink, home_leo: Offset) -> Result<()> {
         debug!("updating home cluster");
+        self.link
+            .update_pending((self.leader.leo() - home_leo).max(0) as u64);
+        if self.transform.is_some() || self.link.is_compressed().await {
+            return self.update_remote_as_source_decoded(sink, home_leo).await;
+        }
         if let Some(sync_request) = self.geneate_remote_record_as_source(home_leo).await? {
             debug!(?sync_request, "home sync");
+            let bytes = sync_request.records.len();
+            self.link.throttle(bytes, bytes).await;
             let request = RequestMessage::new_request(sync_request)
                 .set_client_id(format!("leader: {}", self.leader.id()));
             sink.encode_file_slices(&request, request.header.api_version())
@@ -480,6 +640,56 @@ where
         }
     }
 
+    /// records are decoded to be transformed or compressed, so zero copy is not used
+    async fn update_remote_as_source_decoded(
+        &self,
+        sink: &mut FluvioSink,
+        home_leo: Offset,
+    ) -> Result<()> {
+        let leader_offset = self.leader.as_offset();
+        if leader_offset.leo <= home_leo {
+            debug!("home has caught up, just chilling out");
+            return Ok(());
+        }
+
+        let records = match &self.transform {
+            Some(transform) => {
+                transform
+                    .read_records(&self.leader, home_leo, self.max_bytes, self.isolation)
+                    .await?
+            }
+            None => RecordSet {
+                batches: read_leader_batches(
+                    &self.leader,
+                    home_leo,
+                    self.max_bytes,
+                    self.isolation,
+                )
+                .await?,
+            },
+        };
+        let sync_request = DefaultRemotePartitionSyncRequest {
+            leo: leader_offset.leo,
+            hw: leader_offset.hw,
+            records,
+        };
+        debug!(?sync_request, "home sync from memory");
+        let client_id = format!("leader: {}", self.leader.id());
+        match self.link.pack(sync_request).await? {
+            LinkSyncRecords::Plain(sync_request) => {
+                let request = RequestMessage::new_request(sync_request).set_client_id(client_id);
+                sink.send_request(&request).await?;
+            }
+            LinkSyncRecords::Compressed(records) => {
+                let request =
+                    RequestMessage::new_request(RemoteCompressedSyncRequest::from(records))
+                        .set_client_id(client_id);
+                sink.send_request(&request).await?;
+            }
+        }
+        Ok(())
+    }
+
     /// remote is source, generate missing records to send to home
     async fn geneate_remote_record_as_source(
         &self,
@@ -551,13 +761,14 @@ where
         Ok(())
     }
 
-    /// create socket to home, this will always succeed
+    /// create socket to home, this will always succeed.
+    /// returns socket, whether tls is used and whether home accepts consumer offsets
     #[instrument(skip(self, home))]
     async fn create_socket_to_home(
         &self,
         backoff: &mut ExponentialBackoff,
         home: &Home,
-    ) -> (FluvioSocket, bool) {
+    ) -> (FluvioSocket, bool, bool) {
         let tlspolicy = option_tlspolicy(home);
 
         loop {
@@ -595,9 +806,12 @@ where
 
             match res {
                 Ok(versioned_socket) => {
-                    let (socket, _config, _versions) = versioned_socket.split();
-                    debug!("connected");
-                    return (socket, tlspolicy.is_some());
+                    let (socket, _config, versions) = versioned_socket.split();
+                    let mirror_consumer_offsets = versions
+                        .lookup_version::<StartMirrorRequest>()
+                        .is_some_and(|version| version >= MIRRORED_CONSUMER_OFFSET_API);
+                    debug!(mirror_consumer_offsets, "connected");
+                    return (socket, tlspolicy.is_some(), mirror_consumer_offsets);
                 }
 
                 Err(err) => {
@@ -665,3 +879,32 @@ fn option_tlspolicy(home: &Home) -> Option<TlsPolicy> {
     let tlscfg = TlsConfig::Inline(certs);
     Some(TlsPolicy::from(tlscfg))
 }
+
+impl PeerSink for FluvioSink {
+    async fn send_records(&mut self, request: DefaultRemotePartitionSyncRequest) -> Result<()> {
+        let req_msg = RequestMessage::new_request(request).set_client_id("mirror remote");
+        self.send_request(&req_msg).await?;
+        Ok(())
+    }
+
+    async fn send_compressed_records(&mut self, records: CompressedSyncRecords) -> Result<()> {
+        let req_msg = RequestMessage::new_request(RemoteCompressedSyncRequest::from(records))
+            .set_client_id("mirror remote");
+        self.send_request(&req_msg).await?;
+        Ok(())
+    }
+
+    async fn send_offset(&mut self, request: ReplicaOffsetRequest) -> Result<()> {
+        let req_msg: RequestMessage<UpdateRemoteOffsetRequest> =
+            RequestMessage::new_request(request.into()).set_client_id("mirror remote");
+        self.send_request(&req_msg).await?;
+        Ok(())
+    }
+
+    async fn send_consumer_offsets(&mut self, offsets: MirrorConsumerOffsets) -> Result<()> {
+        let req_msg: RequestMessage<UpdateRemoteConsumerOffsetsRequest> =
+            RequestMessage::new_request(offsets.into()).set_client_id("mirror remote");
+        self.send_request(&req_msg).await?;
+        Ok(())
+    }
+}
@@ -8,14 +8,16 @@ use fluvio_protocol::Decoder;
 use fluvio_protocol::api::{RequestMessage, ApiMessage, RequestHeader};
 
 use super::api_key::MirrorRemoteApiEnum;
-use super::sync::DefaultRemotePartitionSyncRequest;
-use super::update_offsets::UpdateRemoteOffsetRequest;
+use super::sync::{DefaultRemotePartitionSyncRequest, RemoteCompressedSyncRequest};
+use super::update_offsets::{UpdateRemoteConsumerOffsetsRequest, UpdateRemoteOffsetRequest};
 
 /// Requests from remote to home
 #[derive(Debug)]
 pub enum RemoteMirrorRequest {
     SyncRecords(RequestMessage<DefaultRemotePartitionSyncRequest>),
     UpdateRemoteOffset(RequestMessage<UpdateRemoteOffsetRequest>),
+    UpdateConsumerOffsets(RequestMessage<UpdateRemoteConsumerOffsetsRequest>),
+    SyncCompressedRecords(RequestMessage<RemoteCompressedSyncRequest>),
 }
 
 impl Default for RemoteMirrorRequest {
@@ -46,6 +48,18 @@ impl ApiMessage for RemoteMirrorRequest {
                 header,
                 DefaultRemotePartitionSyncRequest::decode_from(src, version)?,
             ))),
+            MirrorRemoteApiEnum::UpdateConsumerOffsets => {
+                Ok(Self::UpdateConsumerOffsets(RequestMessage::new(
+                    header,
+                    UpdateRemoteConsumerOffsetsRequest::decode_from(src, version)?,
+                )))
+            }
+            MirrorRemoteApiEnum::SyncCompressedRecords => {
+                Ok(Self::SyncCompressedRecords(RequestMessage::new(
+                    header,
+                    RemoteCompressedSyncRequest::decode_from(src, version)?,
+                )))
+            }
         }
     }
 }
@@ -15,6 +15,7 @@ use fluvio_protocol::record::RawRecords;
 use fluvio_spu_schema::file::FileRecordSet;
 
 use crate::mirroring::COMMON_MIRROR_VERSION;
+use crate::mirroring::link::CompressedSyncRecords;
 
 use super::api_key::MirrorRemoteApiEnum;
 
@@ -55,6 +56,28 @@ where
 #[derive(Default, Encoder, Decoder, Debug)]
 pub struct MirrorPartitionSyncResponse {}
 
+/// Records compressed with mirror link compression
+#[derive(Encoder, Decoder, Default, Debug)]
+pub(crate) struct RemoteCompressedSyncRequest(CompressedSyncRecords);
+
+impl From<CompressedSyncRecords> for RemoteCompressedSyncRequest {
+    fn from(records: CompressedSyncRecords) -> Self {
+        Self(records)
+    }
+}
+
+impl RemoteCompressedSyncRequest {
+    pub fn inner(self) -> CompressedSyncRecords {
+        self.0
+    }
+}
+
+impl Request for RemoteCompressedSyncRequest {
+    const API_KEY: u16 = MirrorRemoteApiEnum::SyncCompressedRecords as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_MIRROR_VERSION;
+    type Response = MirrorPartitionSyncResponse;
+}
+
 impl FileWrite for RemoteFilePartitionSyncRequest {
     fn file_encode(
         &self,
@@ -2,6 +2,7 @@ use fluvio_protocol::{Encoder, Decoder};
 use fluvio_protocol::api::Request;
 
 use crate::mirroring::COMMON_MIRROR_VERSION;
+use crate::mirroring::consumer_offsets::MirrorConsumerOffsets;
 use crate::replication::leader::ReplicaOffsetRequest;
 
 use super::api_key::MirrorRemoteApiEnum;
@@ -31,3 +32,25 @@ impl Request for UpdateRemoteOffsetRequest {
 // no content, this is one way request
 #[derive(Decoder, Encoder, Default, Debug)]
 pub struct UpdateEdgeOffsetResponse {}
+
+/// Consumer offsets committed on remote
+#[derive(Decoder, Encoder, Default, Clone, Debug)]
+pub(crate) struct UpdateRemoteConsumerOffsetsRequest(MirrorConsumerOffsets);
+
+impl From<MirrorConsumerOffsets> for UpdateRemoteConsumerOffsetsRequest {
+    fn from(offsets: MirrorConsumerOffsets) -> Self {
+        Self(offsets)
+    }
+}
+
+impl UpdateRemoteConsumerOffsetsRequest {
+    pub fn inner(self) -> MirrorConsumerOffsets {
+        self.0
+    }
+}
+
+impl Request for UpdateRemoteConsumerOffsetsRequest {
+    const API_KEY: u16 = MirrorRemoteApiEnum::UpdateConsumerOffsets as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_MIRROR_VERSION;
+    type Response = UpdateEdgeOffsetResponse;
+}
@@ -0,0 +1,197 @@
+use std::env::temp_dir;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use async_channel::{Receiver, Sender};
+use futures_util::{Future, Stream, StreamExt};
+use tokio::select;
+
+use fluvio_controlplane::replica::Replica;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::fixture::create_raw_recordset;
+use fluvio_protocol::record::{MirrorOrigin, Offset};
+use fluvio_spu_schema::Isolation;
+use fluvio_storage::FileReplica;
+use flv_util::fixture::ensure_clean_dir;
+
+use crate::config::SpuConfig;
+use crate::control_plane::{SharedMirrorStatusUpdate, StatusMirrorMessageSink};
+use crate::core::{DefaultSharedGlobalContext, GlobalContext};
+use crate::mirroring::bidirectional::{BidirectionalSync, PeerMessage, PeerSink, read_leader_batches};
+use crate::mirroring::consumer_offsets::{ConsumerOffsetStore, MirrorConsumerOffsets};
+use crate::mirroring::link::{CompressedSyncRecords, MirrorLink};
+use crate::mirroring::remote::sync::DefaultRemotePartitionSyncRequest;
+use crate::replication::leader::{LeaderReplicaState, ReplicaOffsetRequest, SharedFileLeaderState};
+
+const MAX_BYTES: u32 = 1_000_000;
+
+/// peer connection over channel
+struct ChannelSink(Sender<PeerMessage>);
+
+impl ChannelSink {
+    async fn send(&mut self, msg: PeerMessage) -> Result<()> {
+        self.0
+            .send(msg)
+            .await
+            .map_err(|_| anyhow!("peer has closed channel"))
+    }
+}
+
+impl PeerSink for ChannelSink {
+    async fn send_records(&mut self, request: DefaultRemotePartitionSyncRequest) -> Result<()> {
+        self.send(PeerMessage::Sync(request)).await
+    }
+
+    async fn send_compressed_records(&mut self, records: CompressedSyncRecords) -> Result<()> {
+        self.send(PeerMessage::Sync(records.decompress(MAX_BYTES as usize)?))
+            .await
+    }
+
+    async fn send_offset(&mut self, request: ReplicaOffsetRequest) -> Result<()> {
+        self.send(PeerMessage::Offset(request)).await
+    }
+
+    async fn send_consumer_offsets(&mut self, offsets: MirrorConsumerOffsets) -> Result<()> {
+        self.send(PeerMessage::ConsumerOffsets(offsets)).await
+    }
+}
+
+/// cluster with single partition which is mirrored to peer
+struct TestCluster {
+    id: &'static str,
+    ctx: DefaultSharedGlobalContext,
+    leader: SharedFileLeaderState,
+    status_update: SharedMirrorStatusUpdate,
+    consumer_offsets: ConsumerOffsetStore,
+    link: MirrorLink,
+}
+
+impl TestCluster {
+    async fn create(id: &'static str) -> Self {
+        let base_dir = temp_dir().join(format!("bidirectional-mirror-{id}"));
+        ensure_clean_dir(&base_dir);
+        let mut config = SpuConfig::default();
+        config.log.base_dir = base_dir;
+        let ctx = GlobalContext::new_shared_context(config);
+
+        let replica = Replica::new(("topic1", 0), 5001, vec![5001]);
+        let leader = LeaderReplicaState::create(replica, ctx.config(), ctx.status_update_owned())
+            .await
+            .expect("replica")
+            .init(&ctx)
+            .await
+            .expect("init");
+
+        Self {
+            id,
+            consumer_offsets: ConsumerOffsetStore::new(&ctx),
+            link: MirrorLink::new(ctx.metrics().mirror_link(id), ctx.mirror_rate_limit(id)),
+            status_update: StatusMirrorMessageSink::shared(),
+            leader,
+            ctx,
+        }
+    }
+
+    fn sync<'a>(&'a self, peer: &'a TestCluster) -> BidirectionalSync<'a, FileReplica> {
+        BidirectionalSync {
+            leader: &self.leader,
+            local_cluster: self.id,
+            peer_cluster: peer.id,
+            mirror_id: peer.id,
+            status_update: &self.status_update,
+            follower_notifier: self.ctx.follower_notifier(),
+            transform: None,
+            consumer_offsets: &self.consumer_offsets,
+            mirror_consumer_offsets: false,
+            link: &self.link,
+            max_bytes: MAX_BYTES,
+        }
+    }
+
+    async fn produce(&self, records: u16) {
+        self.leader
+            .write_record_set(
+                &mut create_raw_recordset(records),
+                self.ctx.follower_notifier(),
+            )
+            .await
+            .expect("produce");
+    }
+
+    /// origin cluster of each batch in local log
+    async fn origins(&self) -> Vec<Option<i32>> {
+        read_leader_batches(&self.leader, 0, MAX_BYTES, Isolation::ReadUncommitted)
+            .await
+            .expect("read")
+            .iter()
+            .map(|batch| batch.mirror_origin().map(|origin| origin.cluster))
+            .collect()
+    }
+}
+
+fn peer_stream(receiver: Receiver<PeerMessage>) -> impl Stream<Item = Result<PeerMessage>> + Unpin {
+    Box::pin(receiver.map(Ok))
+}
+
+/// mirror between clusters until test is done
+async fn mirror_until<F: Future>(a: &TestCluster, b: &TestCluster, test: F) {
+    let (a_sender, a_receiver) = async_channel::unbounded();
+    let (b_sender, b_receiver) = async_channel::unbounded();
+    let mut a_sink = ChannelSink(a_sender);
+    let mut b_sink = ChannelSink(b_sender);
+    let a_sync = a.sync(b);
+    let b_sync = b.sync(a);
+
+    select! {
+        _ = test => {},
+        result = a_sync.run(&mut a_sink, peer_stream(b_receiver)) => panic!("{} stopped: {result:?}", a.id),
+        result = b_sync.run(&mut b_sink, peer_stream(a_receiver)) => panic!("{} stopped: {result:?}", b.id),
+    }
+}
+
+async fn wait_for_leo(cluster: &TestCluster, leo: Offset) {
+    for _ in 0..100 {
+        if cluster.leader.leo() >= leo {
+            return;
+        }
+        sleep(Duration::from_millis(10)).await;
+    }
+    panic!("{} has not reached leo {leo}", cluster.id);
+}
+
+#[fluvio_future::test]
+async fn test_bidirectional_mirror_exactly_once() {
+    let a = TestCluster::create("cluster-a").await;
+    let b = TestCluster::create("cluster-b").await;
+
+    mirror_until(&a, &b, async {
+        a.produce(2).await;
+        wait_for_leo(&b, 2).await;
+
+        b.produce(3).await;
+        wait_for_leo(&a, 5).await;
+
+        // give records a chance to loop back
+        sleep(Duration::from_millis(200)).await;
+    })
+    .await;
+
+    let from_a = Some(MirrorOrigin::fingerprint("cluster-a"));
+    let from_b = Some(MirrorOrigin::fingerprint("cluster-b"));
+    assert_eq!(a.leader.leo(), 5);
+    assert_eq!(b.leader.leo(), 5);
+    assert_eq!(a.origins().await, vec![None, from_b]);
+    assert_eq!(b.origins().await, vec![from_a, None]);
+
+    // reconnect resumes where mirroring stopped, nothing is appended twice
+    mirror_until(&a, &b, async {
+        a.produce(1).await;
+        wait_for_leo(&b, 6).await;
+        sleep(Duration::from_millis(200)).await;
+    })
+    .await;
+
+    assert_eq!(a.leader.leo(), 6);
+    assert_eq!(b.leader.leo(), 6);
+    assert_eq!(b.origins().await, vec![from_a, None, from_a]);
+}
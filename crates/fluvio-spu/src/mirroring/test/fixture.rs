@@ -3,7 +3,7 @@ use std::path::PathBuf;
 
 use fluvio_controlplane::replica::Replica;
 use fluvio_controlplane::spu_api::update_mirror::Mirror;
-use fluvio_controlplane_metadata::mirror::{Home, MirrorSpec, MirrorType, Remote};
+use fluvio_controlplane_metadata::mirror::{Home, MirrorLinkConfig, MirrorSpec, MirrorType, Remote};
 use fluvio_controlplane_metadata::partition::{
     PartitionMirrorConfig, HomePartitionConfig, RemotePartitionConfig,
 };
@@ -83,6 +83,9 @@ pub(crate) struct ReplicaConfig {
     remote_topic: String,
     #[builder(default)]
     home_to_remote: bool,
+    /// link settings of remote clusters registered on home
+    #[builder(default)]
+    remote_link: Option<MirrorLinkConfig>,
 }
 
 impl ReplicaConfig {
@@ -144,6 +147,8 @@ impl ReplicaConfig {
             home_spu_id: self.base_spu_id,
             home_spu_endpoint: self.home_port.clone(),
             target: self.home_to_remote,
+            bidirectional: false,
+            transforms: vec![],
         }));
         replica
     }
@@ -155,6 +160,8 @@ impl ReplicaConfig {
             remote_cluster: remote_cluster_name.to_string(),
             remote_replica: ReplicaKey::new(self.remote_topic.clone(), 0u32).to_string(),
             source: self.home_to_remote,
+            bidirectional: false,
+            transforms: vec![],
         }));
 
         replica
@@ -247,6 +254,7 @@ impl ReplicaConfig {
                 spec: MirrorSpec {
                     mirror_type: MirrorType::Remote(Remote {
                         id: remote_cluster.clone(),
+                        link: self.remote_link.clone(),
                     }),
                 },
             };
@@ -43,7 +43,9 @@ async fn test_mirroring_from_edge_to_home() {
         &HomePartitionConfig {
             remote_cluster: REMOTE1.to_owned(),
             remote_replica: default_replica().to_owned(),
-            source: false
+            source: false,
+            bidirectional: false,
+            transforms: vec![]
         }
     );
     // check if remote cluster is set
@@ -102,7 +104,9 @@ async fn test_mirroring_from_edge_to_home() {
             home_cluster: default_home_cluster().to_owned(),
             home_spu_id: 5001,
             home_spu_endpoint: home_port.clone(),
-            target: false
+            target: false,
+            bidirectional: false,
+            transforms: vec![]
         }
     );
 
@@ -201,7 +205,9 @@ async fn test_mirror_home_to_edge() {
         &HomePartitionConfig {
             remote_cluster: REMOTE1.to_owned(),
             remote_replica: default_replica().to_owned(),
-            source: true
+            source: true,
+            bidirectional: false,
+            transforms: vec![]
         }
     );
     // check if remote cluster is set
@@ -262,7 +268,9 @@ async fn test_mirror_home_to_edge() {
             home_cluster: default_home_cluster().to_owned(),
             home_spu_id: 5001,
             home_spu_endpoint: home_port.clone(),
-            target: true
+            target: true,
+            bidirectional: false,
+            transforms: vec![]
         }
     );
 
@@ -307,7 +315,9 @@ async fn test_mirror_home_to_edge() {
             home_cluster: default_home_cluster().to_owned(),
             home_spu_id: 5001,
             home_spu_endpoint: home_port.clone(),
-            target: true
+            target: true,
+            bidirectional: false,
+            transforms: vec![]
         }
     );
 
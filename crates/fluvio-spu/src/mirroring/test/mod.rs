@@ -1,2 +1,4 @@
 mod integration;
+mod bidirectional;
+mod transform;
 mod fixture;
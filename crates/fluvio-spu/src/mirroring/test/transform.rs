@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::env::temp_dir;
+
+use fluvio_controlplane::replica::Replica;
+use fluvio_controlplane_metadata::mirror::MirrorPairStatus;
+use fluvio_controlplane_metadata::partition::{PartitionMirrorConfig, RemotePartitionConfig};
+use fluvio_controlplane_metadata::topic::Transform;
+use fluvio_protocol::record::{Batch, Offset, RawRecords, Record, RecordData, RecordSet};
+use flv_util::fixture::ensure_clean_dir;
+
+use crate::config::SpuConfig;
+use crate::core::{DefaultSharedGlobalContext, GlobalContext};
+use crate::mirroring::transform::{FilteredBatch, MirrorTransform};
+use crate::replication::leader::LeaderReplicaState;
+use crate::services::public::tests::load_wasm_module;
+
+const FLUVIO_WASM_FILTER: &str = "fluvio_smartmodule_filter";
+
+fn create_context(test: &str) -> DefaultSharedGlobalContext {
+    let base_dir = temp_dir().join(test);
+    ensure_clean_dir(&base_dir);
+    let mut config = SpuConfig::default();
+    config.log.base_dir = base_dir;
+    GlobalContext::new_shared_context(config)
+}
+
+fn transform(uses: &str) -> Transform {
+    Transform {
+        uses: uses.to_owned(),
+        with: BTreeMap::new(),
+    }
+}
+
+/// source batch as it is read from leader log
+fn source_batch(base_offset: Offset, values: &[&str]) -> Batch<RawRecords> {
+    let mut batch = Batch::default();
+    for value in values {
+        batch.add_record(Record::new(RecordData::from(value.to_string())));
+    }
+    batch.set_base_offset(base_offset);
+    batch.try_into().expect("raw")
+}
+
+fn record_values(batch: &Batch<RawRecords>) -> Vec<String> {
+    let batch: Batch = batch.clone().try_into().expect("memory");
+    batch
+        .records()
+        .iter()
+        .map(|record| record.value().as_utf8_lossy_string().to_string())
+        .collect()
+}
+
+#[fluvio_future::test]
+async fn test_mirror_transform_none_without_transforms() {
+    let ctx = create_context("mirror-transform-none");
+    let transform = MirrorTransform::try_new(&[], None, &ctx)
+        .await
+        .expect("no transform");
+    assert!(transform.is_none());
+}
+
+#[fluvio_future::test]
+async fn test_mirror_transform_unknown_smartmodule() {
+    let ctx = create_context("mirror-transform-unknown");
+    assert!(
+        MirrorTransform::try_new(&[transform("unknown-sm")], None, &ctx)
+            .await
+            .is_err()
+    );
+}
+
+#[fluvio_future::test]
+async fn test_mirror_transform_failure_keeps_leader() {
+    let ctx = create_context("mirror-transform-leader");
+    let mut replica = Replica::new(("topic1", 0), 5001, vec![5001]);
+    replica.mirror = Some(PartitionMirrorConfig::Remote(RemotePartitionConfig {
+        home_cluster: "home".to_owned(),
+        home_spu_key: "home-spu".to_owned(),
+        home_spu_id: 5001,
+        home_spu_endpoint: "localhost:9010".to_owned(),
+        target: false,
+        bidirectional: false,
+        transforms: vec![transform("unknown-sm")],
+    }));
+
+    // only mirror fails, replica is still led
+    let leader = LeaderReplicaState::create(replica, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init");
+    assert_eq!(leader.leo(), 0);
+
+    let status = ctx.mirror_status_update().remove_all().await;
+    assert_eq!(status.len(), 1);
+    assert_eq!(status[0].mirror_id, "home");
+    assert!(matches!(
+        status[0].status.pairing_spu,
+        MirrorPairStatus::DetailFailure(_)
+    ));
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_mirror_transform_keeps_offsets_when_filtered() {
+    let ctx = create_context("mirror-transform-offsets");
+    load_wasm_module(&ctx, FLUVIO_WASM_FILTER);
+    let transform = MirrorTransform::try_new(&[transform(FLUVIO_WASM_FILTER)], None, &ctx)
+        .await
+        .expect("transform")
+        .expect("some");
+
+    let batches = || {
+        vec![
+            source_batch(0, &["a1", "b2"]),
+            source_batch(2, &["b3", "c4", "d5"]),
+            source_batch(5, &["a6", "b7", "a8"]),
+        ]
+    };
+
+    let records = transform
+        .process(batches(), FilteredBatch::FillGap)
+        .await
+        .expect("process");
+    let offsets: Vec<_> = records
+        .batches
+        .iter()
+        .map(|batch| (batch.get_base_offset(), batch.get_last_offset()))
+        .collect();
+    assert_eq!(offsets, vec![(0, 1), (2, 4), (5, 7)]);
+    assert_eq!(record_values(&records.batches[0]), vec!["a1"]);
+    assert!(record_values(&records.batches[1]).is_empty());
+    assert_eq!(record_values(&records.batches[2]), vec!["a6", "a8"]);
+
+    // fully filtered batch is dropped, others still track source offsets
+    let RecordSet { batches } = transform
+        .process(batches(), FilteredBatch::Skip)
+        .await
+        .expect("process");
+    let offsets: Vec<_> = batches
+        .iter()
+        .map(|batch| (batch.get_base_offset(), batch.get_last_offset()))
+        .collect();
+    assert_eq!(offsets, vec![(0, 1), (5, 7)]);
+}
@@ -0,0 +1,118 @@
+//! SmartModule chain applied to records before they are sent over a mirror link.
+//!
+//! Each source batch is transformed on its own and keeps its base offset and offset delta,
+//! so mirror target stays aligned with source offsets even when records are dropped.
+//! A batch where every record was filtered out is handled according to [`FilteredBatch`]:
+//! home/remote mirrors send it as an empty batch to fill the gap, while bidirectional
+//! mirrors skip it since peer appends at its own end.
+
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use async_lock::RwLock;
+use tracing::debug;
+
+use fluvio_compression::Dictionary;
+use fluvio_controlplane_metadata::topic::Transform;
+use fluvio_protocol::record::{Batch, Offset, RawRecords, RecordSet};
+use fluvio_spu_schema::{COMMON_VERSION, Isolation};
+use fluvio_storage::{ReplicaStorage, iterators::read_batches_up_to};
+
+use crate::core::GlobalContext;
+use crate::smartengine::batch::process_batch;
+use crate::smartengine::context::{SharedSmartModuleContext, SmartModuleContext};
+use crate::smartengine::produce_batch::ProduceBatchIterator;
+use crate::smartengine::transform_to_invocation;
+use crate::storage::SharableReplicaStorage;
+
+/// what to send for source batch when all of its records were filtered out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FilteredBatch {
+    /// empty batch with source offsets, target log stays aligned with source
+    FillGap,
+    /// nothing, target appends mirrored batches at its own end
+    Skip,
+}
+
+#[derive(Clone)]
+pub(crate) struct MirrorTransform {
+    sm_ctx: SharedSmartModuleContext,
+}
+
+impl MirrorTransform {
+    /// build chain from mirror config, none if there are no transforms
+    pub(crate) async fn try_new<R: ReplicaStorage>(
+        transforms: &[Transform],
+        dictionary: Option<Dictionary>,
+        ctx: &GlobalContext<R>,
+    ) -> Result<Option<Self>> {
+        let invocations = transforms.iter().map(transform_to_invocation).collect();
+        let sm_ctx = SmartModuleContext::try_from(invocations, COMMON_VERSION, ctx).await?;
+        Ok(sm_ctx.map(|sm_ctx| Self {
+            sm_ctx: Arc::new(RwLock::new(sm_ctx.with_dictionary(dictionary))),
+        }))
+    }
+
+    /// read records from leader starting at offset and transform them
+    pub(crate) async fn read_records<S: ReplicaStorage>(
+        &self,
+        leader: &SharableReplicaStorage<S>,
+        offset: Offset,
+        max_bytes: u32,
+        isolation: Isolation,
+    ) -> Result<RecordSet<RawRecords>> {
+        let slice = leader
+            .read_records(offset, max_bytes, isolation)
+            .await
+            .map_err(|err| anyhow!("error reading records: {}", err))?;
+
+        let Some(file_slice) = slice.file_slice else {
+            return Ok(RecordSet::default());
+        };
+
+        let key_provider = leader.key_provider().await;
+        let batches = read_batches_up_to(&file_slice, key_provider.as_deref(), max_bytes as usize)?;
+        self.process(batches, FilteredBatch::FillGap).await
+    }
+
+    pub(crate) async fn process(
+        &self,
+        batches: Vec<Batch<RawRecords>>,
+        filtered: FilteredBatch,
+    ) -> Result<RecordSet<RawRecords>> {
+        let mut sm_ctx = self.sm_ctx.write().await;
+        let dictionary = sm_ctx.dictionary().cloned();
+        let mut records = RecordSet::default();
+
+        for batch in batches {
+            let input = vec![batch];
+            let mut input_batches =
+                ProduceBatchIterator::new(&input).with_dictionary(dictionary.clone());
+            let (mut output, sm_error) =
+                process_batch(sm_ctx.chain_mut(), &mut input_batches, usize::MAX)?;
+            if let Some(error) = sm_error {
+                return Err(error.into());
+            }
+
+            let source = &input[0];
+            debug!(
+                base_offset = source.base_offset,
+                records_in = source.records_len(),
+                records_out = output.records().len(),
+                "transformed mirror batch"
+            );
+            // offset delta is copied from source, so emptiness must be checked before that
+            if output.records().is_empty() && filtered == FilteredBatch::Skip {
+                continue;
+            }
+            output.base_offset = source.base_offset;
+            output.header.last_offset_delta = source.header.last_offset_delta;
+            output.header.first_timestamp = source.header.first_timestamp;
+            output.header.max_time_stamp = source.header.max_time_stamp;
+            records.batches.push(Batch::<RawRecords>::try_from(output)?);
+        }
+
+        sm_ctx.update_global_metrics();
+        Ok(records)
+    }
+}
@@ -1,6 +1,10 @@
 use std::io::Error as IoError;
+use std::sync::Arc;
 
+use async_trait::async_trait;
 use futures_util::{StreamExt, AsyncWriteExt};
+use fluvio_controlplane::sc_api::update_lrs::LrsRequest;
+use fluvio_metrics::{MetricType, MetricsSource, OpenMetricsEncoder, init_metrics_server};
 use fluvio_types::defaults::SPU_MONITORING_UNIX_SOCKET;
 use fluvio_future::task::spawn;
 use fluvio_future::net::unix::UnixListener;
@@ -62,6 +66,7 @@ async fn start_monitoring(ctx: DefaultSharedGlobalContext) -> Result<(), IoError
                 "spu": {
                     "inbound": ctx.metrics().inbound(),
                     "outbound": ctx.metrics().outbound(),
+                    "recompression": ctx.metrics().recompression(),
                     "smartmodule": ctx.metrics().smartmodule_metrics(),
                 }
             });
@@ -74,3 +79,99 @@ async fn start_monitoring(ctx: DefaultSharedGlobalContext) -> Result<(), IoError
         fluvio_future::timer::sleep(std::time::Duration::from_secs(5)).await;
     }
 }
+
+/// serve OpenMetrics over http if metrics endpoint is configured
+pub(crate) fn init_metrics_endpoint(ctx: DefaultSharedGlobalContext) {
+    if let Some(addr) = ctx.config().metrics_endpoint.clone() {
+        info!(%addr, "starting metrics endpoint");
+        init_metrics_server(addr, Arc::new(SpuMetricsSource { ctx }));
+    }
+}
+
+struct SpuMetricsSource {
+    ctx: DefaultSharedGlobalContext,
+}
+
+#[async_trait]
+impl MetricsSource for SpuMetricsSource {
+    async fn encode(&self, encoder: &mut OpenMetricsEncoder) {
+        self.ctx.metrics().encode(encoder);
+
+        let leaders: Vec<_> = self
+            .ctx
+            .leaders_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        let mut replicas = Vec::with_capacity(leaders.len());
+        for leader in leaders {
+            replicas.push(leader.as_lrs_request().await);
+        }
+        encode_replicas(encoder, &replicas);
+    }
+}
+
+/// encode offsets, sizes and follower lag of replicas led by this SPU
+fn encode_replicas(encoder: &mut OpenMetricsEncoder, replicas: &[LrsRequest]) {
+    let labels: Vec<(String, String)> = replicas
+        .iter()
+        .map(|replica| (replica.id.topic.clone(), replica.id.partition.to_string()))
+        .collect();
+    let gauges: [(&str, &str, fn(&LrsRequest) -> i64); 5] = [
+        (
+            "fluvio_spu_replica_hw",
+            "Replica high watermark",
+            |replica| replica.leader.hw,
+        ),
+        (
+            "fluvio_spu_replica_leo",
+            "Replica log end offset",
+            |replica| replica.leader.leo,
+        ),
+        (
+            "fluvio_spu_replica_log_start_offset",
+            "Replica log start offset",
+            |replica| replica.base_offset,
+        ),
+        (
+            "fluvio_spu_replica_size_bytes",
+            "Replica size on disk",
+            |replica| replica.size,
+        ),
+        (
+            "fluvio_spu_replica_segments",
+            "Replica segment count",
+            |replica| replica.segments as i64,
+        ),
+    ];
+    for (name, help, value) in gauges {
+        let mut family = encoder.family(name, MetricType::Gauge, help);
+        for (replica, (topic, partition)) in replicas.iter().zip(&labels) {
+            family.sample(
+                &[("topic", topic.as_str()), ("partition", partition.as_str())],
+                value(replica),
+            );
+        }
+    }
+
+    let mut lag = encoder.family(
+        "fluvio_spu_replica_follower_lag_records",
+        MetricType::Gauge,
+        "Records the follower is behind the leader",
+    );
+    for (replica, (topic, partition)) in replicas.iter().zip(&labels) {
+        for follower in &replica.replicas {
+            let follower_id = follower.spu.to_string();
+            lag.sample(
+                &[
+                    ("topic", topic.as_str()),
+                    ("partition", partition.as_str()),
+                    ("follower", follower_id.as_str()),
+                ],
+                (replica.leader.leo - follower.leo.max(0)).max(0),
+            );
+        }
+    }
+}
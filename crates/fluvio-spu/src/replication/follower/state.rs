@@ -4,8 +4,8 @@ use std::collections::{HashMap, hash_map::Entry};
 use std::ops::{Deref, DerefMut};
 
 use fluvio_controlplane::replica::Replica;
-use tracing::{debug, warn, instrument};
-use async_lock::RwLock;
+use tracing::{debug, info, warn, instrument};
+use async_lock::{Mutex, RwLock};
 use anyhow::Result;
 
 use fluvio_protocol::record::{BatchRecords, ReplicaKey};
@@ -14,8 +14,9 @@ use fluvio_protocol::record::RecordSet;
 use fluvio_protocol::record::Offset;
 use fluvio_storage::{FileReplica, ReplicaStorage, ReplicaStorageConfig};
 use fluvio_types::SpuId;
+use fluvio_types::event::offsets::{SharedOffsetPublisher, WeakSharedOffsetPublisher, TOPIC_DELETED};
 
-use crate::replication::leader::ReplicaOffsetRequest;
+use crate::replication::leader::{ReplicaOffsetRequest, CLEANUP_FREQUENCY};
 use crate::core::FileGlobalContext;
 use crate::storage::SharableReplicaStorage;
 
@@ -23,6 +24,9 @@ use super::controller::FollowerGroups;
 
 pub type SharedFollowersState<S> = Arc<FollowersState<S>>;
 
+/// last leader epoch follower has synced with
+const LEADER_EPOCH_CHECKPOINT: &str = "leader-epoch.chk";
+
 /// Maintains state for followers
 /// Each follower controller maintains by SPU
 #[derive(Debug)]
@@ -98,7 +102,9 @@ impl FollowersState<FileReplica> {
                 replica_config.update_from_replica(&replica);
 
                 let replica_state =
-                    FollowerReplicaState::create(leader, replica.id, replica_config).await?;
+                    FollowerReplicaState::create(leader, replica.id.clone(), replica_config)
+                        .await?;
+                replica_state.sync_leader_epoch(&replica).await?;
 
                 entry.insert(replica_state.clone());
                 self.groups.check_new(ctx, leader).await;
@@ -148,6 +154,8 @@ impl FollowersState<FileReplica> {
 pub struct FollowerReplicaState<S> {
     leader: SpuId,
     inner: SharableReplicaStorage<S>,
+    // consumers reading from follower
+    consumer_offset_publishers: Arc<Mutex<Vec<WeakSharedOffsetPublisher>>>,
 }
 
 impl<S> Clone for FollowerReplicaState<S> {
@@ -155,6 +163,7 @@ impl<S> Clone for FollowerReplicaState<S> {
         Self {
             leader: self.leader,
             inner: self.inner.clone(),
+            consumer_offset_publishers: self.consumer_offset_publishers.clone(),
         }
     }
 }
@@ -197,6 +206,7 @@ where
         Ok(Self {
             leader,
             inner: replica_storage,
+            consumer_offset_publishers: Arc::new(Mutex::new(Vec::new())),
         })
     }
 
@@ -243,6 +253,36 @@ where
         Ok(changes)
     }
 
+    /// on new leader epoch, truncate records beyond leo of new leader.
+    /// Those were not replicated to new leader and would diverge from its log
+    #[instrument(skip(self, replica), fields(replica = %replica.id))]
+    pub async fn sync_leader_epoch(&self, replica: &Replica) -> Result<()> {
+        let leader_epoch = replica.leader_epoch as Offset;
+        let mut checkpoint = self.checkpoint(LEADER_EPOCH_CHECKPOINT, 0).await?;
+        if let Some(synced_epoch) = checkpoint.as_ref().map(|c| c.get_offset()) {
+            if synced_epoch >= leader_epoch {
+                debug!(synced_epoch, leader_epoch, "leader epoch already synced");
+                return Ok(());
+            }
+        }
+
+        let leo = self.leo();
+        if replica.leader_leo >= 0 && leo > replica.leader_leo {
+            let new_leo = self.truncate(replica.leader_leo).await?;
+            info!(
+                leader_epoch,
+                leader_leo = replica.leader_leo,
+                old_leo = leo,
+                new_leo,
+                "truncated records not replicated to new leader"
+            );
+        }
+        if let Some(checkpoint) = checkpoint.as_mut() {
+            checkpoint.write(leader_epoch);
+        }
+        Ok(())
+    }
+
     /// try to write records
     /// ensure records has correct baseoffset
     async fn write_recordsets<R: BatchRecords>(&self, records: &mut RecordSet<R>) -> Result<bool> {
@@ -273,6 +313,33 @@ where
     pub fn inner_owned(self) -> SharableReplicaStorage<S> {
         self.inner
     }
+
+    pub async fn register_offset_publisher(&self, offset_publisher: &SharedOffsetPublisher) {
+        let mut publishers = self.consumer_offset_publishers.lock().await;
+
+        // Filter out any dead weak pointers every so often
+        if publishers.len() % CLEANUP_FREQUENCY == 0 {
+            publishers.retain(|p| p.strong_count() > 0);
+        }
+
+        publishers.push(Arc::downgrade(offset_publisher));
+    }
+
+    /// publishers of consumers which are still reading from this follower
+    pub async fn offset_publishers(&self) -> Vec<SharedOffsetPublisher> {
+        self.consumer_offset_publishers
+            .lock()
+            .await
+            .iter()
+            .filter_map(|p| p.upgrade())
+            .collect()
+    }
+
+    pub async fn signal_topic_deleted(&self) {
+        for publisher in self.offset_publishers().await {
+            publisher.update(TOPIC_DELETED);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -283,6 +350,7 @@ mod follower_tests {
     use flv_util::fixture::ensure_clean_dir;
     use fluvio_types::{SpuId, PartitionId};
     use fluvio_storage::config::ReplicaConfig;
+    use fluvio_protocol::fixture::create_raw_recordset;
 
     use super::*;
 
@@ -310,4 +378,56 @@ mod follower_tests {
         assert_eq!(follower_replica.hw(), 0);
         assert!(PathBuf::from(test_path).join("spu-5002").exists());
     }
+
+    #[fluvio_future::test]
+    async fn test_follower_truncates_to_new_leader() {
+        let test_path = "/tmp/follower_truncate";
+        ensure_clean_dir(test_path);
+
+        let follower = |spu: &str| {
+            FollowerReplicaState::<FileReplica>::create(
+                LEADER,
+                TEST_REPLICA.into(),
+                ReplicaConfig {
+                    base_dir: PathBuf::from(test_path).join(spu),
+                    ..Default::default()
+                },
+            )
+        };
+
+        // 5002 replicated all records of old leader, 5003 is lagging
+        let caught_up = follower("spu-5002").await.expect("create");
+        caught_up
+            .write_record_set(&mut create_raw_recordset(2), false)
+            .await
+            .expect("write");
+        caught_up
+            .write_record_set(&mut create_raw_recordset(2), false)
+            .await
+            .expect("write");
+        assert_eq!(caught_up.leo(), 4);
+
+        let lagging = follower("spu-5003").await.expect("create");
+        lagging
+            .write_record_set(&mut create_raw_recordset(2), false)
+            .await
+            .expect("write");
+        assert_eq!(lagging.leo(), 2);
+
+        // lagging replica is elected
+        let mut replica = Replica::new(TEST_REPLICA, 5003, vec![LEADER, 5002, 5003]);
+        replica.leader_epoch = 1;
+        replica.leader_leo = lagging.leo();
+
+        caught_up.sync_leader_epoch(&replica).await.expect("sync");
+        assert_eq!(caught_up.leo(), 2);
+
+        // records fetched from new leader in same epoch are kept
+        caught_up
+            .write_record_set(&mut create_raw_recordset(2), false)
+            .await
+            .expect("write");
+        caught_up.sync_leader_epoch(&replica).await.expect("sync");
+        assert_eq!(caught_up.leo(), 4);
+    }
 }
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use tracing::{debug, instrument};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+
+use crate::core::DefaultSharedGlobalContext;
+
+/// min interval between in sync replica checks
+const MIN_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Periodically re-evaluate in sync replicas of every leader.
+/// Followers only report offsets when they change, so lagging followers are detected here.
+pub struct IsrMonitor {
+    ctx: DefaultSharedGlobalContext,
+}
+
+impl IsrMonitor {
+    pub fn run(ctx: DefaultSharedGlobalContext) {
+        spawn(Self { ctx }.check_loop());
+    }
+
+    async fn check_loop(self) {
+        let interval = Duration::from_millis(self.ctx.config().replication.max_lag_ms / 2)
+            .max(MIN_CHECK_INTERVAL);
+        loop {
+            sleep(interval).await;
+            self.check_leaders().await;
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn check_leaders(&self) {
+        let leaders: Vec<_> = self
+            .ctx
+            .leaders_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        debug!(leaders = leaders.len(), "checking in sync replicas");
+        for leader in leaders {
+            leader.check_isr(self.ctx.follower_notifier()).await;
+        }
+    }
+}
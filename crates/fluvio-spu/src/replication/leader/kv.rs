@@ -87,7 +87,9 @@ impl<S: ReplicaStorage + Send + Sync> ReplicaRevStream<S> {
             .read_records(next, u32::MAX, Default::default())
             .await?;
         if let Some(file) = slice.file_slice {
-            let batch_it = FileBatchIterator::from_raw_slice(file).take(1);
+            let batch_it = FileBatchIterator::from_raw_slice(file)
+                .with_key_provider(replica.key_provider().await)
+                .take(1);
             let record_it = FileRecordIterator::new(batch_it, RECORDS_SERIALIZATION_VERSION);
             Ok(record_it.collect::<Result<Vec<_>, _>>()?)
         } else {
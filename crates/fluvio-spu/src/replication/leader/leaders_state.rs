@@ -1,4 +1,5 @@
 use std::ops::Deref;
+use std::sync::Arc;
 use async_lock::RwLock;
 use fluvio_controlplane::replica::Replica;
 use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
@@ -16,7 +17,7 @@ use crate::replication::follower::FollowerReplicaState;
 
 use super::{LeaderReplicaState, replica_state::SharedLeaderState};
 
-pub type SharedReplicaLeadersState<S> = ReplicaLeadersState<S>;
+pub type SharedReplicaLeadersState<S> = Arc<ReplicaLeadersState<S>>;
 
 /// Collection of replicas
 #[derive(Debug)]
@@ -38,7 +39,7 @@ impl<S> Deref for ReplicaLeadersState<S> {
 
 impl<S> ReplicaLeadersState<S> {
     pub fn new_shared() -> SharedReplicaLeadersState<S> {
-        Self::default()
+        Arc::new(Self::default())
     }
 }
 
@@ -158,9 +159,14 @@ impl ReplicaLeadersState<FileReplica> {
         ctx: &GlobalContext<FileReplica>,
     ) -> Result<LeaderReplicaState<FileReplica>> {
         let replica_id = replica.id.clone();
+        // consumers reading from follower keep their stream after promotion
+        let offset_publishers = follower.offset_publishers().await;
         let replica_storage = follower.inner_owned();
         let leader = LeaderReplicaState::new(replica, config, status_update, replica_storage);
         let leader = leader.init(ctx).await?;
+        for publisher in &offset_publishers {
+            leader.register_offset_publisher(publisher).await;
+        }
         self.insert_leader(replica_id, leader.clone()).await;
         Ok(leader)
     }
@@ -3,18 +3,20 @@ use std::{
     collections::{BTreeMap, HashSet, BinaryHeap},
     ops::{Deref, DerefMut},
     sync::Arc,
+    time::{Duration, Instant},
 };
 use std::iter::FromIterator;
 use std::fmt;
 
 use async_lock::Mutex;
 use fluvio_controlplane::{replica::Replica, sc_api::update_lrs::LrsRequest};
-use tracing::{debug, error, warn};
+use tracing::{debug, error, info, warn};
 use tracing::instrument;
 use async_lock::RwLock;
 use anyhow::{Result, Context};
 
 use fluvio_protocol::record::{RecordSet, Offset, ReplicaKey, RawRecords, Batch};
+use fluvio_controlplane_metadata::mirror::MirrorPairStatus;
 use fluvio_controlplane_metadata::partition::{PartitionMirrorConfig, PartitionStatus, ReplicaStatus};
 use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig};
 use fluvio_types::{
@@ -28,6 +30,7 @@ use crate::{
     control_plane::SharedLrsStatusUpdate,
     core::GlobalContext,
     mirroring::remote::controller::{MirrorRemoteToHomeController, SharedMirrorControllerState},
+    mirroring::transform::MirrorTransform,
     smartengine::{
         batch::process_record_set,
         context::{SharedSmartModuleContext, SmartModuleContext},
@@ -47,10 +50,12 @@ pub const CLEANUP_FREQUENCY: usize = 10;
 #[derive(Debug)]
 pub struct LeaderReplicaState<S> {
     replica: Replica,
-    in_sync_replica: u16,
+    min_in_sync_replicas: u16,
     storage: SharableReplicaStorage<S>,
     config: ReplicationConfig,
     followers: Arc<RwLock<BTreeMap<SpuId, OffsetInfo>>>,
+    // always acquired after followers
+    in_sync: Arc<RwLock<BTreeMap<SpuId, FollowerSync>>>,
     status_update: SharedLrsStatusUpdate,
     sm_ctx: Option<SharedSmartModuleContext>,
     consumer_offset_publishers: Arc<Mutex<Vec<WeakSharedOffsetPublisher>>>,
@@ -64,7 +69,8 @@ impl<S> Clone for LeaderReplicaState<S> {
             storage: self.storage.clone(),
             config: self.config.clone(),
             followers: self.followers.clone(),
-            in_sync_replica: self.in_sync_replica,
+            in_sync: self.in_sync.clone(),
+            min_in_sync_replicas: self.min_in_sync_replicas,
             status_update: self.status_update.clone(),
             sm_ctx: self.sm_ctx.clone(),
             consumer_offset_publishers: self.consumer_offset_publishers.clone(),
@@ -96,6 +102,27 @@ impl<S> DerefMut for LeaderReplicaState<S> {
     }
 }
 
+/// membership of follower in the in sync replica set
+#[derive(Debug, Clone, Copy)]
+struct FollowerSync {
+    in_sync: bool,
+    /// last time follower's leo was same as leader's leo
+    caught_up: Instant,
+    /// leader's leo and time when follower's previous offsets were reported
+    last_report: Option<(Offset, Instant)>,
+}
+
+impl FollowerSync {
+    /// followers start as in sync until they fall behind
+    fn new() -> Self {
+        Self {
+            in_sync: true,
+            caught_up: Instant::now(),
+            last_report: None,
+        }
+    }
+}
+
 /// convert follower ids into BtreeMap of this
 fn ids_to_map(leader_id: SpuId, follower_ids: HashSet<SpuId>) -> BTreeMap<SpuId, OffsetInfo> {
     let mut followers = BTreeMap::new();
@@ -110,7 +137,7 @@ where
     S: ReplicaStorage,
 {
     /// create new state from existing storage
-    /// calculate default in_sync_replica from followers
+    /// all followers start as in sync replicas
     pub fn new(
         replica: Replica,
         config: ReplicationConfig,
@@ -118,13 +145,19 @@ where
         inner: SharableReplicaStorage<S>,
     ) -> Uninit<Self> {
         debug!(?replica, "replica storage");
-        let in_sync_replica = replica.replicas.len() as u16;
+        let min_in_sync_replicas = replica
+            .min_in_sync_replicas
+            .unwrap_or(config.min_in_sync_replicas);
         let follower_ids = HashSet::from_iter(replica.replicas.clone());
         let followers = ids_to_map(replica.leader, follower_ids);
         debug!(?followers, "leader followers");
+        let in_sync = followers
+            .keys()
+            .map(|id| (*id, FollowerSync::new()))
+            .collect();
 
         debug!(
-            in_sync_replica,
+            min_in_sync_replicas,
             replica = %replica.id,
             follower = ?replica.replicas,
             "creating leader"
@@ -135,7 +168,8 @@ where
             storage: inner,
             config,
             followers: Arc::new(RwLock::new(followers)),
-            in_sync_replica,
+            in_sync: Arc::new(RwLock::new(in_sync)),
+            min_in_sync_replicas,
             status_update,
             sm_ctx: None,
             consumer_offset_publishers: Arc::new(Mutex::new(Vec::new())),
@@ -176,10 +210,125 @@ where
         &self.replica
     }
 
-    /// override in sync replica
-    #[allow(unused)]
-    fn set_in_sync_replica(&mut self, replica_count: u16) {
-        self.in_sync_replica = replica_count;
+    /// min number of in sync replicas, including leader, required for committed writes
+    pub fn min_in_sync_replicas(&self) -> u16 {
+        self.min_in_sync_replicas
+    }
+
+    /// number of in sync replicas including leader
+    pub async fn in_sync_replica_count(&self) -> u16 {
+        let in_sync = self.in_sync.read().await;
+        1 + in_sync.values().filter(|sync| sync.in_sync).count() as u16
+    }
+
+    /// followers in the in sync replica set
+    pub async fn in_sync_followers(&self) -> Vec<SpuId> {
+        self.in_sync
+            .read()
+            .await
+            .iter()
+            .filter(|(_, sync)| sync.in_sync)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// re-evaluate in sync replica set from follower offsets, `reported` is follower whose offsets just arrived.
+    /// follower is dropped once it hasn't caught up with leader for longer than max lag time
+    /// or is behind by more than max lag records. it is added back when fully caught up.
+    /// Follower which reached leader's leo as of its previous report was caught up at that time,
+    /// so follower keeps up with continuous produce even if it never reaches current leo.
+    /// Follower ahead of leader has records leader doesn't have, it is not in sync until it truncates.
+    /// return true if membership has changed
+    async fn update_in_sync(
+        &self,
+        leader_pos: &OffsetInfo,
+        followers: &BTreeMap<SpuId, OffsetInfo>,
+        reported: Option<SpuId>,
+    ) -> bool {
+        let now = Instant::now();
+        let max_lag = Duration::from_millis(self.config.max_lag_ms);
+        let mut in_sync = self.in_sync.write().await;
+        let mut changed = false;
+        for (follower_id, follower_pos) in followers {
+            let sync = in_sync
+                .entry(*follower_id)
+                .or_insert_with(FollowerSync::new);
+            let caught_up = follower_pos.leo == leader_pos.leo;
+            if caught_up {
+                sync.caught_up = now;
+            }
+            if reported == Some(*follower_id) {
+                if let Some((_, reported_at)) = sync.last_report.filter(|(leader_leo, _)| {
+                    follower_pos.leo < leader_pos.leo && follower_pos.leo >= *leader_leo
+                }) {
+                    sync.caught_up = sync.caught_up.max(reported_at);
+                }
+                sync.last_report = Some((leader_pos.leo, now));
+            }
+            let lag_records = (leader_pos.leo - follower_pos.leo.max(0)).max(0) as u64;
+            let member = caught_up
+                || (sync.in_sync
+                    && now.duration_since(sync.caught_up) <= max_lag
+                    && self
+                        .config
+                        .max_lag_records
+                        .is_none_or(|max| lag_records <= max));
+            if member != sync.in_sync {
+                info!(
+                    replica = %self.id(),
+                    follower_id,
+                    in_sync = member,
+                    lag_records,
+                    "follower in sync state changed"
+                );
+                sync.in_sync = member;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// update hw to min leo of in sync followers
+    async fn update_hw_from_in_sync(
+        &self,
+        leader_pos: &OffsetInfo,
+        followers: &BTreeMap<SpuId, OffsetInfo>,
+    ) {
+        // if our leo and hw is same there is no need to recompute hw
+        if leader_pos.is_committed() {
+            debug!("leader is committed");
+            return;
+        }
+        let in_sync = self.in_sync.read().await;
+        let in_sync_followers: BTreeMap<SpuId, OffsetInfo> = followers
+            .iter()
+            .filter(|(id, _)| in_sync.get(id).is_some_and(|sync| sync.in_sync))
+            .map(|(id, pos)| (*id, pos.clone()))
+            .collect();
+        drop(in_sync);
+
+        if let Some(hw) = compute_in_sync_hw(leader_pos, &in_sync_followers) {
+            debug!(hw, "updating hw");
+            if let Err(err) = self.update_hw(hw).await {
+                error!("error updating hw: {}", err);
+            }
+        } else {
+            debug!("no hw change");
+        }
+    }
+
+    /// check for followers which has fallen behind since their last update
+    pub async fn check_isr(&self, notifier: &FollowerNotifier) {
+        let leader_pos = self.as_offset();
+        let followers = self.followers.read().await;
+        if !self.update_in_sync(&leader_pos, &followers, None).await {
+            return;
+        }
+        self.update_hw_from_in_sync(&leader_pos, &followers).await;
+        drop(followers);
+
+        self.notify_followers(notifier).await;
+        self.update_status().await;
     }
 
     /// update leader's state from follower's offset states
@@ -205,20 +354,12 @@ where
         // get follower info
         let mut followers = self.followers.write().await;
         let update = if let Some(current_follow_info) = followers.get_mut(&follower_id) {
-            if current_follow_info.update(&follower_pos) {
-                // if our leo and hw is same there is no need to recompute hw
-                if !leader_pos.is_committed() {
-                    if let Some(hw) = compute_hw(&leader_pos, self.in_sync_replica, &followers) {
-                        debug!(hw, "updating hw");
-                        if let Err(err) = self.update_hw(hw).await {
-                            error!("error updating hw: {}", err);
-                        }
-                    } else {
-                        debug!("no hw change");
-                    }
-                } else {
-                    debug!("leader is committed");
-                }
+            let follower_changed = current_follow_info.update(&follower_pos);
+            let in_sync_changed = self
+                .update_in_sync(&leader_pos, &followers, Some(follower_id))
+                .await;
+            if follower_changed || in_sync_changed {
+                self.update_hw_from_in_sync(&leader_pos, &followers).await;
                 debug!("follower changed");
                 true
             } else {
@@ -308,7 +449,7 @@ where
     }
 
     /// convert myself as
-    async fn as_lrs_request(&self) -> LrsRequest {
+    pub(crate) async fn as_lrs_request(&self) -> LrsRequest {
         let leader = (self.leader(), self.hw(), self.leo()).into();
         let replicas: Vec<ReplicaStatus> = self
             .followers
@@ -325,8 +466,14 @@ where
             .try_into()
             .unwrap_or(PartitionStatus::SIZE_ERROR);
         let base_offset = storage_reader.get_log_start_offset();
+        let segments = storage_reader.get_segment_count();
+
+        drop(storage_reader);
+        let in_sync_replicas = self.in_sync_followers().await;
 
         LrsRequest::new(self.id().to_owned(), leader, replicas, size, base_offset)
+            .with_segments(segments)
+            .with_in_sync_replicas(in_sync_replicas)
     }
 
     #[instrument(skip(self))]
@@ -354,10 +501,9 @@ where
             return Ok((self.hw(), self.leo(), 0));
         }
 
-        let offsets = self
-            .storage
-            .write_record_set(records, self.in_sync_replica == 1)
-            .await?;
+        // leader alone can commit when there are no other in sync replicas
+        let commit = self.in_sync_replica_count().await == 1;
+        let offsets = self.storage.write_record_set(records, commit).await?;
 
         self.notify_followers(notifiers).await;
         self.update_status().await;
@@ -367,8 +513,11 @@ where
 
     async fn transform(&self, records: &mut RecordSet<RawRecords>) -> Result<()> {
         if let Some(ref sm_ctx) = self.sm_ctx {
+            let mut sm_ctx = sm_ctx.write().await;
+            let dictionary = sm_ctx.dictionary().cloned();
             let (sm_result, sm_error) =
-                process_record_set(sm_ctx.write().await.chain_mut(), records)?;
+                process_record_set(sm_ctx.chain_mut(), records, dictionary)?;
+            drop(sm_ctx);
             if let Some(error) = sm_error {
                 return Err(error.into());
             }
@@ -385,19 +534,45 @@ where
         let leader_offset = self.as_offset();
         let followers = self.followers.read().await;
         debug!(?leader_offset);
-        for follower in &self.replica.replicas {
-            if let Some(follower_info) = followers.get(follower) {
-                debug!(follower, ?follower_info);
-                if follower_info.is_valid() && !follower_info.is_same(&leader_offset) {
-                    debug!(follower, "notify");
-                    notifier.notify_follower(follower, self.id().clone()).await;
-                } else {
-                    debug!(follower, "no update");
-                }
+        for (follower, follower_info) in followers.iter() {
+            debug!(follower, ?follower_info);
+            if follower_info.is_valid() && !follower_info.is_same(&leader_offset) {
+                debug!(follower, "notify");
+                notifier.notify_follower(follower, self.id().clone()).await;
+            } else {
+                debug!(follower, "no update");
             }
         }
     }
 
+    /// change followers after replicas are migrated between spus.
+    /// new followers start out of sync until they catch up with leader
+    pub async fn update_followers(&self, replicas: &[SpuId]) {
+        let leader_id = self.leader();
+        let mut followers = self.followers.write().await;
+        let mut in_sync = self.in_sync.write().await;
+        followers.retain(|id, _| replicas.contains(id));
+        in_sync.retain(|id, _| replicas.contains(id));
+        for id in replicas.iter().filter(|id| **id != leader_id) {
+            followers.entry(*id).or_default();
+            in_sync.entry(*id).or_insert_with(|| FollowerSync {
+                in_sync: false,
+                caught_up: Instant::now(),
+                last_report: None,
+            });
+        }
+        info!(
+            replica = %self.id(),
+            followers = ?followers.keys().collect::<Vec<_>>(),
+            "followers updated"
+        );
+        let leader_pos = self.as_offset();
+        drop(in_sync);
+        self.update_hw_from_in_sync(&leader_pos, &followers).await;
+        drop(followers);
+        self.update_status().await;
+    }
+
     #[allow(dead_code)]
     pub async fn live_replicas(&self) -> Vec<SpuId> {
         self.followers.read().await.keys().cloned().collect()
@@ -467,6 +642,26 @@ where
 
         Ok(true)
     }
+
+    /// append records received from bidirectional mirror peer.
+    /// records are written at current leo, smartmodule transform is skipped since peer already applied it
+    pub(crate) async fn append_mirrored_record_set(
+        &self,
+        records: &mut RecordSet<RawRecords>,
+        notifier: &FollowerNotifier,
+    ) -> Result<(Offset, Offset, usize)> {
+        if records.total_records() == 0 {
+            return Ok((self.hw(), self.leo(), 0));
+        }
+
+        let commit = self.in_sync_replica_count().await == 1;
+        let offsets = self.storage.write_record_set(records, commit).await?;
+
+        self.notify_followers(notifier).await;
+        self.update_status().await;
+
+        Ok(offsets)
+    }
 }
 
 pub struct Uninit<S>(S);
@@ -480,9 +675,11 @@ where
         if let Some(dedup) = &state.replica.deduplication {
             debug!(?state.replica.deduplication, "init leader smartmodule context");
             let dedup_filter = dedup_to_invocation(dedup);
+            let dictionary = ctx.dictionaries_localstore().for_replica(&state.replica)?;
             let mut sm_ctx = SmartModuleContext::try_from(vec![dedup_filter], COMMON_VERSION, ctx)
                 .await?
-                .ok_or_else(|| anyhow::anyhow!("SmartModule context is required here"))?;
+                .ok_or_else(|| anyhow::anyhow!("SmartModule context is required here"))?
+                .with_dictionary(dictionary);
             sm_ctx
                 .look_back(&state)
                 .await
@@ -494,10 +691,36 @@ where
             match mirror {
                 PartitionMirrorConfig::Remote(r) => {
                     debug!("found mirror remote, starting controller");
+                    let transform = match ctx.dictionaries_localstore().for_replica(&state.replica)
+                    {
+                        Ok(dictionary) => MirrorTransform::try_new(&r.transforms, dictionary, ctx)
+                            .await
+                            .context("mirror smartmodule transform failed"),
+                        Err(err) => Err(err.into()),
+                    };
+                    // invalid transform only stops mirroring, replica keeps serving as leader
+                    let transform = match transform {
+                        Ok(transform) => transform,
+                        Err(err) => {
+                            error!(replica = %state.id(), "mirror not started: {:#}", err);
+                            if let Err(err) = ctx
+                                .mirror_status_update()
+                                .send_status(
+                                    r.home_cluster.clone(),
+                                    MirrorPairStatus::DetailFailure(format!("{err:#}")),
+                                )
+                                .await
+                            {
+                                error!("error updating mirror status: {}", err);
+                            }
+                            return Ok(state);
+                        }
+                    };
                     let mirror_controller_state = MirrorRemoteToHomeController::run(
                         ctx,
                         state.clone(),
                         r.clone(),
+                        transform,
                         Isolation::ReadUncommitted,
                         10000000,
                     );
@@ -517,6 +740,22 @@ where
     }
 }
 
+/// compute hw from followers in the in sync replica set
+/// leader commits its own leo when there are no in sync followers
+fn compute_in_sync_hw(
+    leader: &OffsetInfo,
+    in_sync_followers: &BTreeMap<SpuId, OffsetInfo>,
+) -> Option<Offset> {
+    if in_sync_followers.is_empty() {
+        return (leader.leo > leader.hw).then_some(leader.leo);
+    }
+    compute_hw(
+        leader,
+        in_sync_followers.len() as u16 + 1,
+        in_sync_followers,
+    )
+}
+
 /// compute leader's updated hw based on follower offset
 /// this is done after follower's leo updated
 /// min_replica must be at least 1 and must be less than followers.len(0)
@@ -553,9 +792,10 @@ fn compute_hw(
     let min_lrs = min((min_replica - 1) as usize, followers.len());
 
     // compute offsets that is greater than min leader's HW
+    // follower can't commit records beyond leader's leo
     let mut qualified_leos_iter = followers
         .values()
-        .map(|follower_info| follower_info.leo)
+        .map(|follower_info| follower_info.leo.min(leader.leo))
         .filter(|leo| *leo > leader.hw);
 
     if min_lrs == 0 {
@@ -679,6 +919,16 @@ mod test_hw_updates {
             Some(10)
         );
 
+        //  follower has records which new leader doesn't have, hw stops at leader leo
+        assert_eq!(
+            compute_hw(
+                &OffsetInfo { hw: 6, leo: 8 },
+                2,
+                &offsets_maps(vec![(5001, OffsetInfo { leo: 10, hw: 6 })])
+            ),
+            Some(8)
+        );
+
         // followers send back same, no hw update
         assert_eq!(
             compute_hw(
@@ -923,6 +1173,12 @@ mod test_leader {
             Ok(true)
         }
 
+        async fn truncate(&mut self, offset: Offset) -> Result<Offset> {
+            self.pos.leo = self.pos.leo.min(offset);
+            self.pos.hw = self.pos.hw.min(self.pos.leo);
+            Ok(self.pos.leo)
+        }
+
         type ReplicaConfig = MockConfig;
 
         fn get_log_start_offset(&self) -> Offset {
@@ -952,7 +1208,145 @@ mod test_leader {
         .expect("state")
         .0;
 
-        assert_eq!(state.in_sync_replica, 1);
+        assert_eq!(state.in_sync_replica_count().await, 1);
+        assert_eq!(state.min_in_sync_replicas(), 1);
+    }
+
+    #[fluvio_future::test]
+    async fn test_in_sync_replica_records_lag() {
+        let mut leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+        leader_config.replication.max_lag_records = Some(5);
+
+        let notifier = FollowerNotifier::shared();
+        let mut replica = Replica::new(("test", 1), 5000, vec![5000, 5001]);
+        replica.min_in_sync_replicas = Some(2);
+        let state: LeaderReplicaState<MockStorage> =
+            LeaderReplicaState::create(replica, &leader_config, StatusLrsMessageSink::shared())
+                .await
+                .expect("state")
+                .0;
+        assert_eq!(state.min_in_sync_replicas(), 2);
+        assert_eq!(state.in_sync_replica_count().await, 2);
+
+        state
+            .write_record_set(&mut create_raw_recordset(10), &notifier)
+            .await
+            .expect("write");
+        assert_eq!(state.hw(), 0);
+
+        // follower is too far behind, leader commits without it
+        assert!(
+            state
+                .update_states_from_followers(5001, OffsetInfo { leo: 2, hw: 0 }, &notifier)
+                .await
+        );
+        assert_eq!(state.in_sync_replica_count().await, 1);
+        assert!(state.in_sync_followers().await.is_empty());
+        assert_eq!(state.hw(), 10);
+
+        // partially caught up follower is not added back
+        assert!(
+            state
+                .update_states_from_followers(5001, OffsetInfo { leo: 8, hw: 0 }, &notifier)
+                .await
+        );
+        assert_eq!(state.in_sync_replica_count().await, 1);
+
+        // fully caught up follower is added back
+        assert!(
+            state
+                .update_states_from_followers(5001, OffsetInfo { leo: 10, hw: 10 }, &notifier)
+                .await
+        );
+        assert_eq!(state.in_sync_followers().await, vec![5001]);
+        assert_eq!(state.as_lrs_request().await.in_sync_replicas, vec![5001]);
+    }
+
+    #[fluvio_future::test]
+    async fn test_in_sync_replica_time_lag() {
+        let mut leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+        leader_config.replication.max_lag_ms = 100;
+
+        let notifier = FollowerNotifier::shared();
+        let state: LeaderReplicaState<MockStorage> = LeaderReplicaState::create(
+            Replica::new(("test", 1), 5000, vec![5000, 5001, 5002]),
+            &leader_config,
+            StatusLrsMessageSink::shared(),
+        )
+        .await
+        .expect("state")
+        .0;
+
+        state
+            .write_record_set(&mut create_raw_recordset(10), &notifier)
+            .await
+            .expect("write");
+        assert!(
+            state
+                .update_states_from_followers(5001, OffsetInfo { leo: 10, hw: 0 }, &notifier)
+                .await
+        );
+        assert_eq!(state.hw(), 0);
+
+        // 5002 never reported back, it is dropped after max lag time
+        fluvio_future::timer::sleep(Duration::from_millis(200)).await;
+        state.check_isr(&notifier).await;
+        assert_eq!(state.in_sync_followers().await, vec![5001]);
+        assert_eq!(state.in_sync_replica_count().await, 2);
+        assert_eq!(state.hw(), 10);
+    }
+
+    #[fluvio_future::test]
+    async fn test_in_sync_replica_continuous_produce() {
+        let mut leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+        leader_config.replication.max_lag_ms = 200;
+
+        let notifier = FollowerNotifier::shared();
+        let state: LeaderReplicaState<MockStorage> = LeaderReplicaState::create(
+            Replica::new(("test", 1), 5000, vec![5000, 5001]),
+            &leader_config,
+            StatusLrsMessageSink::shared(),
+        )
+        .await
+        .expect("state")
+        .0;
+
+        // follower is always one batch behind, but reaches leader's leo of its previous report
+        let mut follower_leo = 0;
+        for _ in 0..8 {
+            let mut records = create_raw_recordset(10);
+            records.batches[0].set_base_offset(state.leo());
+            state
+                .write_record_set(&mut records, &notifier)
+                .await
+                .expect("write");
+            state
+                .update_states_from_followers(
+                    5001,
+                    OffsetInfo {
+                        leo: follower_leo,
+                        hw: state.hw(),
+                    },
+                    &notifier,
+                )
+                .await;
+            follower_leo = state.leo();
+            fluvio_future::timer::sleep(Duration::from_millis(50)).await;
+        }
+
+        state.check_isr(&notifier).await;
+        assert_eq!(state.leo(), 80);
+        assert_eq!(state.in_sync_followers().await, vec![5001]);
+        assert_eq!(state.hw(), 70);
     }
 
     #[fluvio_future::test]
@@ -10,6 +10,8 @@ use fluvio_protocol::api::{RequestMessage, ApiMessage, RequestHeader};
 use super::fetch_consumer_offset_request::FetchConsumerOffsetRequest;
 use super::update_consumer_offset_request::UpdateConsumerOffsetRequest;
 use super::fetch_stream_request::FetchStreamRequest;
+use super::fetch_replica_consumer_offsets_request::FetchReplicaConsumerOffsetsRequest;
+use super::update_mirrored_consumer_offsets_request::UpdateMirroredConsumerOffsetsRequest;
 
 #[repr(u16)]
 #[derive(Eq, PartialEq, Debug, Encoder, Decoder, Clone, Copy)]
@@ -18,6 +20,8 @@ pub enum SPUPeerApiEnum {
     FetchStream = 0,
     FetchConsumerOffset = 1,
     UpdateConsumerOffset = 2,
+    FetchReplicaConsumerOffsets = 3,
+    UpdateMirroredConsumerOffsets = 4,
 }
 
 impl Default for SPUPeerApiEnum {
@@ -34,6 +38,10 @@ pub enum SpuPeerRequest {
     FetchConsumerOffset(RequestMessage<FetchConsumerOffsetRequest>),
     #[fluvio(tag = 2)]
     UpdateConsumerOffset(RequestMessage<UpdateConsumerOffsetRequest>),
+    #[fluvio(tag = 3)]
+    FetchReplicaConsumerOffsets(RequestMessage<FetchReplicaConsumerOffsetsRequest>),
+    #[fluvio(tag = 4)]
+    UpdateMirroredConsumerOffsets(RequestMessage<UpdateMirroredConsumerOffsetsRequest>),
 }
 
 impl Default for SpuPeerRequest {
@@ -70,6 +78,18 @@ impl ApiMessage for SpuPeerRequest {
                     UpdateConsumerOffsetRequest::decode_from(src, version)?,
                 )))
             }
+            SPUPeerApiEnum::FetchReplicaConsumerOffsets => Ok(
+                SpuPeerRequest::FetchReplicaConsumerOffsets(RequestMessage::new(
+                    header,
+                    FetchReplicaConsumerOffsetsRequest::decode_from(src, version)?,
+                )),
+            ),
+            SPUPeerApiEnum::UpdateMirroredConsumerOffsets => Ok(
+                SpuPeerRequest::UpdateMirroredConsumerOffsets(RequestMessage::new(
+                    header,
+                    UpdateMirroredConsumerOffsetsRequest::decode_from(src, version)?,
+                )),
+            ),
         }
     }
 }
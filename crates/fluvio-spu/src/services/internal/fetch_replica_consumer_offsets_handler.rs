@@ -0,0 +1,55 @@
+use std::io::Error as IoError;
+
+use fluvio_protocol::{
+    api::{RequestMessage, ResponseMessage},
+    link::ErrorCode,
+};
+use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
+use tracing::{instrument, debug};
+
+use crate::{core::DefaultSharedGlobalContext, mirroring::consumer_offsets::list_replica_offsets};
+
+use super::fetch_replica_consumer_offsets_request::{
+    FetchReplicaConsumerOffsetsRequest, FetchReplicaConsumerOffsetsResponse,
+};
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_fetch_replica_consumer_offsets_request(
+    req_msg: RequestMessage<FetchReplicaConsumerOffsetsRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<FetchReplicaConsumerOffsetsResponse>, IoError> {
+    let FetchReplicaConsumerOffsetsRequest { replica_id } = &req_msg.request;
+
+    let (consumers, error_code) =
+        if let Some(ref replica) = ctx.leaders_state().get(&CONSUMER_REPLICA_KEY.into()).await {
+            let result = match ctx
+                .consumer_offset()
+                .get_or_insert(replica, ctx.follower_notifier())
+                .await
+            {
+                Ok(storage) => list_replica_offsets(&storage, replica_id).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(consumers) => (consumers, ErrorCode::None),
+                Err(e) => (vec![], ErrorCode::Other(e.to_string())),
+            }
+        } else {
+            (vec![], ErrorCode::PartitionNotLeader)
+        };
+    debug!(
+        consumers = consumers.len(),
+        ?error_code,
+        "replica consumer offsets fetch result"
+    );
+    let response = FetchReplicaConsumerOffsetsResponse {
+        error_code,
+        consumers,
+    };
+    Ok(
+        RequestMessage::<FetchReplicaConsumerOffsetsRequest>::response_with_header(
+            &req_msg.header,
+            response,
+        ),
+    )
+}
@@ -0,0 +1,32 @@
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_spu_schema::COMMON_VERSION;
+
+use super::SPUPeerApiEnum;
+
+/// Fetch all consumer offsets committed for a replica
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct FetchReplicaConsumerOffsetsRequest {
+    pub replica_id: ReplicaKey,
+}
+
+impl Request for FetchReplicaConsumerOffsetsRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::FetchReplicaConsumerOffsets as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = FetchReplicaConsumerOffsetsResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct FetchReplicaConsumerOffsetsResponse {
+    pub error_code: ErrorCode,
+    pub consumers: Vec<ReplicaConsumerOffset>,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone, PartialEq, Eq)]
+pub struct ReplicaConsumerOffset {
+    pub consumer_id: String,
+    pub offset: Offset,
+    pub modified_time: u64,
+}
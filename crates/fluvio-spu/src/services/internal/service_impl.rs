@@ -12,6 +12,8 @@ use crate::core::DefaultSharedGlobalContext;
 use crate::replication::leader::FollowerHandler;
 use crate::services::internal::fetch_consumer_offset_handler::handle_fetch_consumer_offset_request;
 use crate::services::internal::update_consumer_offset_handler::handle_update_consumer_offset_request;
+use crate::services::internal::fetch_replica_consumer_offsets_handler::handle_fetch_replica_consumer_offsets_request;
+use crate::services::internal::update_mirrored_consumer_offsets_handler::handle_update_mirrored_consumer_offsets_request;
 use super::SpuPeerRequest;
 use super::SPUPeerApiEnum;
 use super::FetchStreamResponse;
@@ -96,6 +98,18 @@ impl FluvioService for InternalService {
                 let api_version = req_msg.header.api_version();
                 let response = handle_update_consumer_offset_request(req_msg, ctx).await?;
                 sink.send_response(&response, api_version).await?;
+            },
+            SpuPeerRequest::FetchReplicaConsumerOffsets(req_msg) => {
+                debug!(replica = %req_msg.request.replica_id, "fetch replica consumer offsets request");
+                let api_version = req_msg.header.api_version();
+                let response = handle_fetch_replica_consumer_offsets_request(req_msg, ctx).await?;
+                sink.send_response(&response, api_version).await?;
+            },
+            SpuPeerRequest::UpdateMirroredConsumerOffsets(req_msg) => {
+                debug!(replica = %req_msg.request.replica_id, consumers = req_msg.request.consumers.len(), "update mirrored consumer offsets request");
+                let api_version = req_msg.header.api_version();
+                let response = handle_update_mirrored_consumer_offsets_request(req_msg, ctx).await?;
+                sink.send_response(&response, api_version).await?;
             }
 
         );
@@ -13,7 +13,7 @@ use tracing::{instrument, trace};
 use crate::{
     core::DefaultSharedGlobalContext,
     replication::leader::LeaderReplicaState,
-    kv::consumer::{ConsumerOffset, ConsumerOffsetKey},
+    kv::consumer::ConsumerOffsetKey,
 };
 
 use super::update_consumer_offset_request::{UpdateConsumerOffsetRequest, UpdateConsumerOffsetResponse};
@@ -27,11 +27,12 @@ pub(crate) async fn handle_update_consumer_offset_request(
         consumer_id,
         offset,
         replica_id,
+        metadata,
     } = req_msg.request;
 
     let error_code =
         if let Some(ref replica) = ctx.leaders_state().get(&CONSUMER_REPLICA_KEY.into()).await {
-            match update_offset(ctx, replica, replica_id, consumer_id, offset).await {
+            match update_offset(ctx, replica, replica_id, consumer_id, offset, metadata).await {
                 Ok(_) => ErrorCode::None,
                 Err(e) => ErrorCode::Other(e.to_string()),
             }
@@ -54,12 +55,12 @@ async fn update_offset(
     target_replica: ReplicaKey,
     consumer_id: String,
     offset: Offset,
+    metadata: Option<Vec<u8>>,
 ) -> anyhow::Result<()> {
     let consumers = ctx
         .consumer_offset()
         .get_or_insert(replica, ctx.follower_notifier())
         .await?;
     let key = ConsumerOffsetKey::new(target_replica, consumer_id);
-    let consumer = ConsumerOffset::new(offset);
-    consumers.put(key, consumer).await
+    consumers.commit(key, offset, metadata).await
 }
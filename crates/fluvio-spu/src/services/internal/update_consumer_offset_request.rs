@@ -14,6 +14,9 @@ pub struct UpdateConsumerOffsetRequest {
     pub replica_id: ReplicaKey,
     pub consumer_id: String,
     pub offset: Offset,
+    /// replaces the metadata stored with the offset, keeps it if none
+    #[fluvio(min_version = 31)]
+    pub metadata: Option<Vec<u8>>,
 }
 
 impl Request for UpdateConsumerOffsetRequest {
@@ -34,8 +37,14 @@ impl UpdateConsumerOffsetRequest {
             replica_id,
             consumer_id: consumer_id.into(),
             offset,
+            metadata: None,
         }
     }
+
+    pub fn with_metadata(mut self, metadata: Option<Vec<u8>>) -> Self {
+        self.metadata = metadata;
+        self
+    }
 }
 
 #[derive(Encoder, Decoder, Default, Debug)]
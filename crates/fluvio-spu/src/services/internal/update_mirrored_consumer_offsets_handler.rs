@@ -0,0 +1,51 @@
+use std::io::Error as IoError;
+
+use fluvio_protocol::{
+    api::{RequestMessage, ResponseMessage},
+    link::ErrorCode,
+};
+use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
+use tracing::{instrument, trace};
+
+use crate::{core::DefaultSharedGlobalContext, mirroring::consumer_offsets::put_mirrored_offsets};
+
+use super::update_mirrored_consumer_offsets_request::{
+    UpdateMirroredConsumerOffsetsRequest, UpdateMirroredConsumerOffsetsResponse,
+};
+
+#[instrument(skip(req_msg, ctx))]
+pub(crate) async fn handle_update_mirrored_consumer_offsets_request(
+    req_msg: RequestMessage<UpdateMirroredConsumerOffsetsRequest>,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<ResponseMessage<UpdateMirroredConsumerOffsetsResponse>, IoError> {
+    let header = req_msg.header;
+    let UpdateMirroredConsumerOffsetsRequest {
+        replica_id,
+        consumers,
+    } = req_msg.request;
+
+    let error_code =
+        if let Some(ref replica) = ctx.leaders_state().get(&CONSUMER_REPLICA_KEY.into()).await {
+            let result = match ctx
+                .consumer_offset()
+                .get_or_insert(replica, ctx.follower_notifier())
+                .await
+            {
+                Ok(storage) => put_mirrored_offsets(&storage, &replica_id, consumers).await,
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(_) => ErrorCode::None,
+                Err(e) => ErrorCode::Other(e.to_string()),
+            }
+        } else {
+            ErrorCode::PartitionNotLeader
+        };
+    trace!(?error_code, "mirrored consumer offsets update result");
+    let response = UpdateMirroredConsumerOffsetsResponse { error_code };
+    Ok(
+        RequestMessage::<UpdateMirroredConsumerOffsetsRequest>::response_with_header(
+            &header, response,
+        ),
+    )
+}
@@ -0,0 +1,35 @@
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_spu_schema::COMMON_VERSION;
+use fluvio_spu_schema::server::consumer_offset::MirroredOffsetSource;
+
+use super::SPUPeerApiEnum;
+
+/// Store consumer offsets mirrored from another cluster, already translated to replica
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateMirroredConsumerOffsetsRequest {
+    pub replica_id: ReplicaKey,
+    pub consumers: Vec<MirroredConsumerOffset>,
+}
+
+impl Request for UpdateMirroredConsumerOffsetsRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::UpdateMirroredConsumerOffsets as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = UpdateMirroredConsumerOffsetsResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug, Clone)]
+pub struct MirroredConsumerOffset {
+    pub consumer_id: String,
+    pub offset: Offset,
+    /// time offset was committed on source cluster
+    pub modified_time: u64,
+    pub source: MirroredOffsetSource,
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct UpdateMirroredConsumerOffsetsResponse {
+    pub error_code: ErrorCode,
+}
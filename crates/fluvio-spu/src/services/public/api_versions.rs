@@ -9,6 +9,7 @@ use fluvio_spu_schema::server::SpuServerApiKey;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
 use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
 use fluvio_spu_schema::server::update_offset::UpdateOffsetsRequest;
+use fluvio_spu_schema::server::stream_seek::StreamSeekRequest;
 use fluvio_spu_schema::{ApiVersionsRequest, ApiVersionsResponse};
 
 #[instrument(skip(request))]
@@ -47,6 +48,11 @@ pub async fn handle_api_version_request(
         0,
         UpdateOffsetsRequest::DEFAULT_API_VERSION,
     ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::StreamSeek,
+        0,
+        StreamSeekRequest::DEFAULT_API_VERSION,
+    ));
 
     trace!("Returning ApiVersionsResponse: {:#?}", &response);
     Ok(request.new_response(response))
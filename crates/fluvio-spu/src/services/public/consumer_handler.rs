@@ -10,6 +10,7 @@ use fluvio_spu_schema::server::consumer_offset::FetchConsumerOffsetsResponse;
 use fluvio_spu_schema::server::consumer_offset::UpdateConsumerOffsetRequest;
 use fluvio_spu_schema::server::consumer_offset::UpdateConsumerOffsetResponse;
 use fluvio_spu_schema::server::consumer_offset::ConsumerOffset as ConsumerOffsetResponse;
+use fluvio_spu_schema::server::consumer_offset::MAX_CONSUMER_OFFSET_METADATA_SIZE;
 use fluvio_storage::FileReplica;
 use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
 use fluvio_types::PartitionId;
@@ -24,7 +25,6 @@ use tracing::trace;
 use tracing::warn;
 
 use crate::core::DefaultSharedGlobalContext;
-use crate::kv::consumer::ConsumerOffset;
 use crate::kv::consumer::ConsumerOffsetKey;
 use crate::replication::leader::LeaderReplicaState;
 
@@ -37,9 +37,15 @@ pub(crate) async fn handle_update_consumer_offset_request(
     ctx: DefaultSharedGlobalContext,
     conn_ctx: &mut ConnectionContext,
 ) -> Result<ResponseMessage<UpdateConsumerOffsetResponse>, IoError> {
-    let UpdateConsumerOffsetRequest { offset, session_id } = req_msg.request;
+    let UpdateConsumerOffsetRequest {
+        offset,
+        session_id,
+        metadata,
+    } = req_msg.request;
 
-    let (offset, error_code) = match handle_update(ctx, conn_ctx, offset, session_id).await {
+    let (offset, error_code) = match handle_update(ctx, conn_ctx, offset, session_id, metadata)
+        .await
+    {
         Ok(offset) => (offset, ErrorCode::None),
         Err(error) => (i64::default(), error),
     };
@@ -110,7 +116,16 @@ async fn handle_update(
     conn_ctx: &mut ConnectionContext,
     offset: i64,
     session_id: u32,
+    metadata: Option<Vec<u8>>,
 ) -> std::result::Result<i64, ErrorCode> {
+    if let Some(size) = metadata
+        .as_ref()
+        .map(Vec::len)
+        .filter(|size| *size > MAX_CONSUMER_OFFSET_METADATA_SIZE)
+    {
+        return Err(ErrorCode::OffsetMetadataTooLarge(size as u32));
+    }
+
     let Some(publisher) = conn_ctx.stream_publishers().get_publisher(session_id).await else {
         return Err(ErrorCode::FetchSessionNotFoud);
     };
@@ -133,6 +148,7 @@ async fn handle_update(
             publisher.partition,
             consumer.consumer_id.clone(),
             offset,
+            metadata,
         )
         .await
         {
@@ -151,6 +167,7 @@ async fn handle_update(
             publisher.partition,
             consumer.consumer_id,
             offset,
+            metadata,
         )
         .await?;
     };
@@ -189,11 +206,12 @@ async fn handle_fetch_consumers(
     };
 
     let not_deleted_replicas = ctx.replica_localstore().read().clone();
-    let all_consumers = ctx
+    let storage = ctx
         .consumer_offset()
         .get_or_insert(replica, ctx.follower_notifier())
         .await
-        .map_err(|e| ErrorCode::Other(e.to_string()))?
+        .map_err(|e| ErrorCode::Other(e.to_string()))?;
+    let all_consumers = storage
         .list()
         .await
         .map_err(|e| ErrorCode::Other(format!("unable to list consumers: {e:?}")))?;
@@ -218,12 +236,16 @@ async fn handle_fetch_consumers(
 
             // filter by not deleted replicas
             if not_deleted_replicas.contains_key(&key.replica_id) {
-                Some(ConsumerOffsetResponse::new(
-                    key.consumer_id,
-                    key.replica_id,
-                    consumer.offset,
-                    consumer.modified_time,
-                ))
+                Some(
+                    ConsumerOffsetResponse::new(
+                        key.consumer_id,
+                        key.replica_id,
+                        consumer.offset,
+                        consumer.modified_time,
+                    )
+                    .with_mirror_source(consumer.mirror_source)
+                    .with_metadata(consumer.metadata),
+                )
             } else {
                 None
             }
@@ -240,6 +262,7 @@ async fn update_offset_for_leader(
     partition: PartitionId,
     consumer_id: String,
     offset: i64,
+    metadata: Option<Vec<u8>>,
 ) -> Result<()> {
     let consumers = ctx
         .consumer_offset()
@@ -248,8 +271,7 @@ async fn update_offset_for_leader(
 
     let target_replica: ReplicaKey = (topic, partition).into();
     let key = ConsumerOffsetKey::new(target_replica, consumer_id);
-    let consumer = ConsumerOffset::new(offset);
-    consumers.put(key, consumer).await
+    consumers.commit(key, offset, metadata).await
 }
 
 async fn update_offset_in_peer(
@@ -259,13 +281,15 @@ async fn update_offset_in_peer(
     partition: PartitionId,
     consumer_id: String,
     offset: i64,
+    metadata: Option<Vec<u8>>,
 ) -> Result<(), ErrorCode> {
     let update_req = crate::services::internal::UpdateConsumerOffsetRequest::new(
         topic,
         partition,
         consumer_id,
         offset,
-    );
+    )
+    .with_metadata(metadata);
 
     let response = send_private_request_to_leader(&ctx, consumer_replica_key, update_req)
         .await
@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
 use tracing::warn;
 use tracing::{debug, trace, instrument};
 use anyhow::Result;
@@ -6,11 +10,16 @@ use fluvio_spu_schema::file::FileRecordSet;
 use fluvio_socket::ExclusiveFlvSink;
 use fluvio_socket::SocketError;
 use fluvio_protocol::{link::ErrorCode, api::RequestMessage};
+use fluvio_protocol::record::{RawRecords, RecordSet};
 use fluvio_spu_schema::fetch::{
     FileFetchResponse, FileFetchRequest, FilePartitionResponse, FileTopicResponse,
-    FetchablePartitionResponse, FetchPartition, FetchableTopic, FetchableTopicResponse,
+    FetchablePartitionResponse, FetchPartition, FetchRequest, FetchResponse, FetchableTopic,
+    FetchableTopicResponse,
 };
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_storage::ReplicaStorage;
+use fluvio_storage::encryption::KeyProvider;
+use fluvio_storage::iterators::read_batches_up_to;
 
 use crate::core::DefaultSharedGlobalContext;
 use crate::traffic::TrafficType;
@@ -27,27 +36,51 @@ pub async fn handle_fetch_request(
     ctx: DefaultSharedGlobalContext,
     sink: ExclusiveFlvSink,
 ) -> Result<()> {
+    let start = Instant::now();
     let (header, fetch_request) = request.get_header_request();
     trace!("Handling FileFetchRequest: {:#?}", fetch_request);
     let mut fetch_response = FileFetchResponse::default();
+    let mut key_providers = KeyProviders::new();
 
     for topic_request in &fetch_request.topics {
-        let topic_response =
-            handle_fetch_topic(&ctx, &fetch_request, topic_request, header.is_connector()).await?;
+        let topic_response = handle_fetch_topic(
+            &ctx,
+            &fetch_request,
+            topic_request,
+            header.is_connector(),
+            &mut key_providers,
+        )
+        .await?;
         fetch_response.topics.push(topic_response);
     }
 
-    let response =
-        RequestMessage::<FileFetchRequest>::response_with_header(&header, fetch_response);
-    trace!("Sending FileFetchResponse: {:#?}", response);
-
-    let mut inner = sink.lock().await;
-    inner
-        .encode_file_slices(&response, header.api_version())
-        .await?;
-
-    drop(inner);
+    if key_providers.is_empty() {
+        let response =
+            RequestMessage::<FileFetchRequest>::response_with_header(&header, fetch_response);
+        trace!("Sending FileFetchResponse: {:#?}", response);
+
+        let mut inner = sink.lock().await;
+        inner
+            .encode_file_slices(&response, header.api_version())
+            .await?;
+    } else {
+        // sealed batches can't be sent as file slices, records are opened in memory
+        let fetch_response = open_fetch_response(
+            fetch_response,
+            &key_providers,
+            fetch_request.max_bytes as usize,
+        );
+        let response = RequestMessage::<FetchRequest<RecordSet<RawRecords>>>::response_with_header(
+            &header,
+            fetch_response,
+        );
+        trace!("Sending decrypted FetchResponse: {:#?}", response);
+
+        let mut inner = sink.lock().await;
+        inner.send_response(&response, header.api_version()).await?;
+    }
 
+    ctx.metrics().latency().fetch().observe(start.elapsed());
     trace!("Finished sending FileFetchResponse");
     Ok(())
 }
@@ -61,6 +94,7 @@ async fn handle_fetch_topic(
     fetch_request: &FileFetchRequest,
     topic_request: &FetchableTopic,
     is_connector: bool,
+    key_providers: &mut KeyProviders,
 ) -> Result<FetchableTopicResponse<FileRecordSet>> {
     let topic = &topic_request.name;
 
@@ -77,6 +111,7 @@ async fn handle_fetch_topic(
             fetch_request,
             partition_request,
             is_connector,
+            key_providers,
         )
         .await?;
         topic_response.partitions.push(partition_response);
@@ -86,7 +121,7 @@ async fn handle_fetch_topic(
 }
 
 #[instrument(
-skip(ctx, replica_id, partition_request),
+skip(ctx, replica_id, partition_request, key_providers),
     fields(%replica_id)
 )]
 async fn handle_fetch_partition(
@@ -95,6 +130,7 @@ async fn handle_fetch_partition(
     fetch_request: &FileFetchRequest,
     partition_request: &FetchPartition,
     is_connector: bool,
+    key_providers: &mut KeyProviders,
 ) -> Result<FetchablePartitionResponse<FileRecordSet>, SocketError> {
     trace!("Fetching partition:");
     let fetch_offset = partition_request.fetch_offset;
@@ -113,6 +149,10 @@ async fn handle_fetch_partition(
         }
     };
 
+    if let Some(key_provider) = leader_state.key_provider().await {
+        key_providers.insert(replica_id.clone(), key_provider);
+    }
+
     let metrics = ctx.metrics();
 
     match leader_state
@@ -144,3 +184,67 @@ async fn handle_fetch_partition(
 
     Ok(partition_response)
 }
+
+/// key providers of encrypted replicas in the request
+type KeyProviders = HashMap<ReplicaKey, Arc<dyn KeyProvider>>;
+
+/// Read file slices of the response into memory and open the encrypted batches
+fn open_fetch_response(
+    file_response: FileFetchResponse,
+    key_providers: &KeyProviders,
+    max_bytes: usize,
+) -> FetchResponse<RecordSet<RawRecords>> {
+    let topics = file_response
+        .topics
+        .into_iter()
+        .map(|topic_response| {
+            let partitions = topic_response
+                .partitions
+                .into_iter()
+                .map(|file_partition| {
+                    let replica_id = ReplicaKey::new(
+                        topic_response.name.clone(),
+                        file_partition.partition_index,
+                    );
+                    let mut partition_response = FetchablePartitionResponse {
+                        partition_index: file_partition.partition_index,
+                        error_code: file_partition.error_code,
+                        high_watermark: file_partition.high_watermark,
+                        next_filter_offset: file_partition.next_filter_offset,
+                        log_start_offset: file_partition.log_start_offset,
+                        aborted: file_partition.aborted,
+                        records: RecordSet::default(),
+                    };
+                    if file_partition.records.len() > 0 {
+                        match read_batches_up_to(
+                            &file_partition.records.raw_slice(),
+                            key_providers
+                                .get(&replica_id)
+                                .map(|provider| provider.as_ref()),
+                            max_bytes,
+                        ) {
+                            Ok(batches) => partition_response.records = RecordSet { batches },
+                            Err(err) => {
+                                warn!(%replica_id, %err, "failed to open records");
+                                partition_response.error_code = ErrorCode::Other(err.to_string());
+                            }
+                        }
+                    }
+                    partition_response
+                })
+                .collect();
+            FetchableTopicResponse {
+                name: topic_response.name,
+                partitions,
+                data: Default::default(),
+            }
+        })
+        .collect();
+
+    FetchResponse {
+        throttle_time_ms: file_response.throttle_time_ms,
+        error_code: file_response.error_code,
+        session_id: file_response.session_id,
+        topics,
+    }
+}
@@ -3,11 +3,12 @@ mod produce_handler;
 mod fetch_handler;
 mod offset_request;
 mod offset_update;
+mod stream_seek;
 mod stream_fetch;
 mod consumer_handler;
 
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 mod conn_context;
 
 use std::sync::Arc;
@@ -16,9 +17,9 @@ use fluvio_auth::Authorization;
 use fluvio_protocol::api::Request;
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::link::ErrorCode;
-use fluvio_protocol::record::ReplicaKey;
+use fluvio_protocol::record::{ReplicaKey, TraceContext};
 use fluvio_spu_schema::server::mirror::StartMirrorRequest;
-use tracing::{info, debug, trace, instrument};
+use tracing::{info, debug, trace, instrument, Span};
 use futures_util::StreamExt;
 use anyhow::Result;
 
@@ -29,6 +30,8 @@ use fluvio_spu_schema::server::SpuServerApiKey;
 use fluvio_types::event::StickyEvent;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::replica::ReplicaStore;
+use crate::core::SpuLocalStore;
 use crate::mirroring::home::connection::MirrorHomeHandler;
 use crate::services::auth::SpuAuthGlobalContext;
 use crate::services::auth::SpuAuthServiceContext;
@@ -40,6 +43,7 @@ use self::produce_handler::handle_produce_request;
 use self::fetch_handler::handle_fetch_request;
 use self::offset_request::handle_offset_request;
 use self::offset_update::handle_offset_update;
+use self::stream_seek::handle_stream_seek;
 use self::stream_fetch::{StreamFetchHandler, publishers::StreamPublishers};
 use self::conn_context::ConnectionContext;
 use std::fmt::Debug;
@@ -193,6 +197,12 @@ where
                                     "FetchConsumersRequest"
                                 )
                             }
+                            SpuServerRequest::StreamSeekRequest(request) => call_service!(
+                                request,
+                                handle_stream_seek(request, context.clone(), &mut conn_ctx),
+                                shared_sink,
+                                "StreamSeekRequest"
+                            ),
                             SpuServerRequest::StartMirrorRequest(request) => {
                                 // send mirror mode, afer that mirror cycle will be started
                                 mirror_request = Some(request);
@@ -230,11 +240,27 @@ async fn send_private_request_to_leader<R: Request>(
     replica_id: &ReplicaKey,
     req: R,
 ) -> Result<R::Response, ErrorCode> {
-    let spu = match ctx.replica_localstore().spec(replica_id) {
+    send_private_request_to_replica_leader(
+        ctx.replica_localstore(),
+        ctx.spu_localstore(),
+        replica_id,
+        req,
+    )
+    .await
+}
+
+/// same as above, for tasks which only hold the stores
+pub(crate) async fn send_private_request_to_replica_leader<R: Request>(
+    replicas: &ReplicaStore,
+    spus: &SpuLocalStore,
+    replica_id: &ReplicaKey,
+    req: R,
+) -> Result<R::Response, ErrorCode> {
+    let spu = match replicas.spec(replica_id) {
         Some(replica) => replica.leader,
         None => return Err(ErrorCode::TopicNotFound),
     };
-    let Some(spu_spec) = ctx.spu_localstore().spec(&spu) else {
+    let Some(spu_spec) = spus.spec(&spu) else {
         return Err(ErrorCode::SpuNotFound);
     };
     let leader_endpoint = spu_spec.private_endpoint.to_string();
@@ -254,3 +280,15 @@ async fn send_private_request_to_leader<R: Request>(
 
     Ok(response.response)
 }
+
+/// Attach the trace context propagated by the client to the current span.
+/// The span must declare an empty `traceparent` field.
+pub(crate) fn continue_trace(trace_context: &TraceContext) {
+    if trace_context.is_empty() {
+        return;
+    }
+    let span = Span::current();
+    span.record("traceparent", trace_context.traceparent());
+    #[cfg(feature = "otel")]
+    fluvio::otel::set_parent(&span, trace_context);
+}
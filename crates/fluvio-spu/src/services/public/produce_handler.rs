@@ -1,15 +1,16 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use fluvio_controlplane::sc_api::update_partition::PartitionStatRequest;
 use tokio::select;
 use tracing::{debug, trace, error};
 use tracing::instrument;
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 
 use fluvio_protocol::api::{RequestKind, RequestHeader};
 use fluvio_spu_schema::Isolation;
-use fluvio_protocol::record::{BatchRecords, Offset, Batch, RawRecords};
+use fluvio_protocol::record::{Offset, Batch, RawRecords};
 use fluvio::Compression;
+use fluvio_compression::{CompressionError, CompressionOptions, Dictionary};
 use fluvio_controlplane_metadata::topic::CompressionAlgorithm;
 use fluvio_storage::StorageError;
 use fluvio_spu_schema::produce::{
@@ -22,9 +23,11 @@ use fluvio_protocol::api::ResponseMessage;
 use fluvio_protocol::record::RecordSet;
 use fluvio_controlplane_metadata::partition::{PartitionResolution, ReplicaKey};
 
+use fluvio_future::task::spawn_blocking;
 use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::metrics::Recompression;
 use crate::replication::leader::SharedFileLeaderState;
 use crate::smartengine::batch::process_batch;
 use crate::smartengine::context::SmartModuleContext;
@@ -33,6 +36,7 @@ use crate::smartengine::map_engine_error;
 use crate::smartengine::produce_batch::ProduceBatchIterator;
 
 use crate::traffic::TrafficType;
+use crate::services::public::continue_trace;
 
 struct TopicWriteResult {
     topic: String,
@@ -58,6 +62,7 @@ pub async fn handle_produce_request(
     request: RequestMessage<DefaultProduceRequest>,
     ctx: DefaultSharedGlobalContext,
 ) -> Result<ResponseMessage<ProduceResponse>> {
+    let start = Instant::now();
     let (header, produce_request) = request.get_header_request();
     trace!("Handling ProduceRequest: {:#?}", produce_request);
 
@@ -65,8 +70,14 @@ pub async fn handle_produce_request(
 
     let mut topic_results = Vec::with_capacity(produce_request.topics.len());
     for topic_request in produce_request.topics.into_iter() {
-        let topic_result =
-            handle_produce_topic(&ctx, topic_request, &smartmodules, &header).await?;
+        let topic_result = handle_produce_topic(
+            &ctx,
+            topic_request,
+            &smartmodules,
+            produce_request.isolation,
+            &header,
+        )
+        .await?;
         topic_results.push(topic_result);
     }
     wait_for_acks(
@@ -78,17 +89,19 @@ pub async fn handle_produce_request(
     .await;
     let response = into_response(topic_results);
     trace!("Returning ProduceResponse: {:#?}", &response);
+    ctx.metrics().latency().produce().observe(start.elapsed());
     Ok(RequestMessage::<DefaultProduceRequest>::response_with_header(&header, response))
 }
 
 #[instrument(
-    skip(ctx, topic_request, smartmodules, header),
+    skip(ctx, topic_request, smartmodules, isolation, header),
     fields(topic = %topic_request.name),
 )]
 async fn handle_produce_topic(
     ctx: &DefaultSharedGlobalContext,
     topic_request: DefaultTopicRequest,
     smartmodules: &[SmartModuleInvocation],
+    isolation: Isolation,
     header: &RequestHeader,
 ) -> Result<TopicWriteResult> {
     let topic = &topic_request.name;
@@ -125,6 +138,23 @@ async fn handle_produce_topic(
             }
         }
 
+        // committed writes can't be acknowledged without enough in sync replicas
+        if isolation == Isolation::ReadCommitted {
+            let in_sync = leader_state.in_sync_replica_count().await;
+            let min_in_sync = leader_state.min_in_sync_replicas();
+            if in_sync < min_in_sync {
+                debug!(%replica_id, in_sync, min_in_sync, "not enough in sync replicas");
+                topic_result.partitions.push(PartitionWriteResult::error(
+                    replica_id,
+                    ErrorCode::NotEnoughReplicas {
+                        in_sync,
+                        min_in_sync,
+                    },
+                ));
+                continue;
+            }
+        }
+
         if let Err(err) = apply_smartmodules(
             &mut partition_request,
             smartmodules,
@@ -165,7 +195,7 @@ async fn handle_produce_topic(
 
 #[instrument(
     skip(ctx, replica_key, partition_request, leader_state),
-    fields(%replica_key),
+    fields(%replica_key, traceparent = tracing::field::Empty),
 )]
 async fn handle_produce_partition(
     ctx: &DefaultSharedGlobalContext,
@@ -174,6 +204,7 @@ async fn handle_produce_partition(
     partition_request: PartitionProduceData<RecordSet<RawRecords>>,
     is_connector: bool,
 ) -> PartitionWriteResult {
+    continue_trace(&partition_request.trace_context);
     trace!("Handling produce request for partition:");
 
     let replica_metadata = match ctx.replica_localstore().spec(&replica_key) {
@@ -185,17 +216,38 @@ async fn handle_produce_partition(
     };
 
     let mut records = partition_request.records;
+    clear_mirror_origins(&mut records);
 
-    if validate_records(&records, replica_metadata.compression_type).is_err() {
-        error!(%replica_key, "Compression in batch not supported by this topic");
-        return PartitionWriteResult::error(replica_key, ErrorCode::CompressionError);
+    let metrics = ctx.metrics();
+    if !matches!(replica_metadata.compression_type, CompressionAlgorithm::Any) {
+        let dictionary = match ctx.dictionaries_localstore().for_replica(&replica_metadata) {
+            Ok(dictionary) => dictionary,
+            Err(error_code) => return PartitionWriteResult::error(replica_key, error_code),
+        };
+        // compression is CPU bound, it is kept off the executor
+        let recompression_metrics = metrics.clone();
+        let (recompressed, result) = spawn_blocking(move || {
+            let result = recompress_records(
+                &mut records,
+                &replica_metadata.compression_type,
+                replica_metadata.compression_level,
+                dictionary.as_ref(),
+                recompression_metrics.recompression(),
+            );
+            (records, result)
+        })
+        .await;
+        records = recompressed;
+        if let Err(err) = result {
+            error!(%replica_key, "Failed to convert batch to topic compression: {err}");
+            return PartitionWriteResult::error(replica_key, ErrorCode::CompressionError);
+        }
     }
 
     let write_result = leader_state
         .write_record_set(&mut records, ctx.follower_notifier())
         .await;
 
-    let metrics = ctx.metrics();
     match write_result {
         Ok((base_offset, leo, bytes)) => {
             metrics
@@ -259,18 +311,20 @@ async fn apply_smartmodules(
     leader_state: &SharedFileLeaderState,
     ctx: &DefaultSharedGlobalContext,
 ) -> Result<(), ErrorCode> {
-    let Some(mut sm_ctx) =
+    let Some(sm_ctx) =
         SmartModuleContext::try_from(smartmodules.to_vec(), api_version, ctx).await?
     else {
         return Ok(());
     };
+    let mut sm_ctx = sm_ctx.with_dictionary(ctx.replica_dictionary(leader_state.id())?);
 
     sm_ctx.look_back(leader_state).await?;
 
     let records = &partition_request.records;
     let batches = &records.batches;
 
-    let mut batches = ProduceBatchIterator::new(batches);
+    let mut batches =
+        ProduceBatchIterator::new(batches).with_dictionary(sm_ctx.dictionary().cloned());
 
     let sm_result = match process_batch(
         sm_ctx.chain_mut(),
@@ -302,30 +356,52 @@ async fn apply_smartmodules(
     Ok(())
 }
 
-fn validate_records<R: BatchRecords>(
-    records: &RecordSet<R>,
-    compression: CompressionAlgorithm,
-) -> Result<()> {
-    if records.batches.iter().all(|batch| {
-        let batch_compression = if let Ok(compression) = batch.get_compression() {
-            compression
-        } else {
-            return false;
-        };
-        match compression {
-            CompressionAlgorithm::Any => true,
-            CompressionAlgorithm::None => batch_compression == Compression::None,
-            CompressionAlgorithm::Gzip => batch_compression == Compression::Gzip,
-            CompressionAlgorithm::Snappy => batch_compression == Compression::Snappy,
-            CompressionAlgorithm::Lz4 => batch_compression == Compression::Lz4,
-            CompressionAlgorithm::Zstd => batch_compression == Compression::Zstd,
+/// Only mirroring appends batches of another cluster, origin sent by producers is dropped
+/// so their records are mirrored to peer like any other local records
+pub(super) fn clear_mirror_origins(records: &mut RecordSet<RawRecords>) {
+    for batch in records.batches.iter_mut() {
+        batch.clear_mirror_origin();
+    }
+}
+
+/// Converts batches to the compression configured on the topic, so storage does not depend
+/// on the codec chosen by each producer. `dictionary` of the topic is used to decompress batches
+/// compressed with it and to compress zstd batches.
+pub(super) fn recompress_records(
+    records: &mut RecordSet<RawRecords>,
+    topic_compression: &CompressionAlgorithm,
+    level: Option<i32>,
+    dictionary: Option<&Dictionary>,
+    metrics: &Recompression,
+) -> Result<(), CompressionError> {
+    let target = match topic_compression {
+        CompressionAlgorithm::Any => return Ok(()),
+        CompressionAlgorithm::None => Compression::None,
+        CompressionAlgorithm::Gzip => Compression::Gzip,
+        CompressionAlgorithm::Snappy => Compression::Snappy,
+        CompressionAlgorithm::Lz4 => Compression::Lz4,
+        CompressionAlgorithm::Zstd => Compression::Zstd,
+    };
+    let options = CompressionOptions {
+        level: level.filter(|_| target.level_range().is_some()),
+        dictionary: dictionary.filter(|_| target == Compression::Zstd).cloned(),
+    };
+
+    for batch in records.batches.iter_mut() {
+        if batch.get_compression()? == target
+            && batch.header.has_dictionary() == options.dictionary.is_some()
+        {
+            continue;
         }
-    }) {
-        Ok(())
-    } else {
-        Err(anyhow!("Compression not supported by topic"))
+        let start = Instant::now();
+        let bytes_in = batch.batch_len() as u64;
+        let recompressed = std::mem::take(batch).recompress(target, dictionary, &options)?;
+        metrics.increase(bytes_in, recompressed.batch_len() as u64, start.elapsed());
+        *batch = recompressed;
     }
+    Ok(())
 }
+
 /// For isolation = ReadCommitted wait until the replica's `hw` includes written records offsets or
 /// until `timeout` passes. In case of timeout, the partition response returns `RequestTimedOut`
 /// error code. The timeout is not shared between partitions.
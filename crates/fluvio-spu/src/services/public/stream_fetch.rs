@@ -4,7 +4,7 @@ use std::time::Instant;
 use tracing::{debug, error, instrument, trace, warn};
 use tokio::select;
 
-use fluvio_compression::CompressionError;
+use fluvio_compression::{CompressionError, Dictionary};
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_types::event::{
     offsets::{OffsetPublisher, INIT_OFFSET, TOPIC_DELETED},
@@ -13,12 +13,14 @@ use fluvio_types::event::{
 use fluvio_future::task::spawn;
 use fluvio_protocol::{
     api::{RequestMessage, RequestHeader},
-    record::{RecordSet, Offset, RawRecords},
+    record::{BatchHeader, RecordSet, Offset, RawRecords},
 };
 use fluvio_protocol::link::{ErrorCode, smartmodule::SmartModuleTransformRuntimeError};
 use fluvio_protocol::record::Batch;
 use fluvio_socket::{ExclusiveFlvSink, SocketError};
-use fluvio_storage::iterators::FileBatchIterator;
+use fluvio_storage::FileReplica;
+use fluvio_storage::encryption::KeyProvider;
+use fluvio_storage::iterators::{any_batch_header, read_batches_up_to, FileBatchIterator};
 use fluvio_spu_schema::{
     server::stream_fetch::{
         DefaultStreamFetchRequest, FileStreamFetchRequest, StreamFetchRequest, StreamFetchResponse,
@@ -26,16 +28,67 @@ use fluvio_spu_schema::{
     fetch::{FilePartitionResponse, FetchablePartitionResponse},
     Isolation,
     file::FileRecordSet,
+    DICTIONARY_API, MIRROR_ORIGIN_API, TRACE_CONTEXT_API,
 };
-use fluvio_types::event::offsets::OffsetChangeListener;
+use fluvio_types::event::offsets::{OffsetChangeListener, SharedOffsetPublisher};
+use async_channel::Receiver;
 
 use crate::core::{metrics::IncreaseValue, DefaultSharedGlobalContext};
-use crate::replication::leader::SharedFileLeaderState;
 use crate::services::public::conn_context::ConnectionContext;
+use crate::replication::follower::FollowerReplicaState;
+use crate::replication::leader::SharedFileLeaderState;
+use crate::storage::SharableReplicaStorage;
 use crate::smartengine::context::SmartModuleContext;
 use crate::smartengine::batch::process_batch;
 use crate::core::metrics::SpuMetrics;
 use crate::traffic::TrafficType;
+use crate::services::public::continue_trace;
+
+/// Replica which serves stream fetch
+pub(super) enum FetchSource {
+    Leader(SharedFileLeaderState),
+    Follower(FollowerReplicaState<FileReplica>),
+}
+
+impl FetchSource {
+    /// leader of replica, in sync follower if client allows reading from follower.
+    /// Follower only serves records which are known to be committed
+    pub(super) async fn lookup(
+        ctx: &DefaultSharedGlobalContext,
+        replica: &ReplicaKey,
+        msg: &mut FileStreamFetchRequest,
+    ) -> Option<Self> {
+        if let Some(leader_state) = ctx.leaders_state().get(replica).await {
+            return Some(Self::Leader(leader_state));
+        }
+        if !msg.read_from_follower {
+            return None;
+        }
+        let follower = ctx.followers_state().get(replica).await?;
+        debug!(%replica, "serving stream fetch from follower");
+        msg.isolation = Isolation::ReadCommitted;
+        Some(Self::Follower(follower))
+    }
+
+    /// register consumer so it is notified when replica is deleted
+    pub(super) async fn register_offset_publisher(
+        self,
+        offset_publisher: &SharedOffsetPublisher,
+    ) -> SharableReplicaStorage<FileReplica> {
+        match self {
+            Self::Leader(leader_state) => {
+                leader_state
+                    .register_offset_publisher(offset_publisher)
+                    .await;
+                SharableReplicaStorage::<FileReplica>::clone(&leader_state)
+            }
+            Self::Follower(follower) => {
+                follower.register_offset_publisher(offset_publisher).await;
+                follower.inner_owned()
+            }
+        }
+    }
+}
 
 /// Fetch records as stream
 pub struct StreamFetchHandler {
@@ -47,9 +100,47 @@ pub struct StreamFetchHandler {
     sink: ExclusiveFlvSink,
     end_event: Arc<StickyEvent>,
     consumer_offset_listener: OffsetChangeListener,
-    leader_state: SharedFileLeaderState,
+    seek_receiver: Receiver<publishers::StreamSeek>,
+    seek_epoch: u32,
+    storage: SharableReplicaStorage<FileReplica>,
     stream_id: u32,
     metrics: Arc<SpuMetrics>,
+    key_provider: Option<Arc<dyn KeyProvider>>,
+    legacy: LegacyConsumer,
+    dictionary: Option<Dictionary>,
+    compression_level: Option<i32>,
+}
+
+/// Batch features an older consumer can't decode, batches using them are rewritten in memory
+#[derive(Debug, Default, Clone, Copy)]
+struct LegacyConsumer {
+    /// consumer predates record headers, batches carrying them are sent without
+    record_headers: bool,
+    /// consumer can't decompress with dictionaries, batches are recompressed without
+    dictionary: bool,
+    /// consumer can't decode mirror origin, batches are sent without
+    mirror_origin: bool,
+}
+
+impl LegacyConsumer {
+    fn new(version: i16) -> Self {
+        Self {
+            record_headers: version < TRACE_CONTEXT_API,
+            dictionary: version < DICTIONARY_API,
+            mirror_origin: version < MIRROR_ORIGIN_API,
+        }
+    }
+
+    fn is_legacy(&self) -> bool {
+        self.record_headers || self.dictionary || self.mirror_origin
+    }
+
+    /// batch with this header can't be sent to consumer as it is stored
+    fn needs_rewrite(&self, header: &BatchHeader) -> bool {
+        (self.record_headers && header.has_record_headers())
+            || (self.dictionary && header.has_dictionary())
+            || (self.mirror_origin && header.is_mirrored())
+    }
 }
 
 impl StreamFetchHandler {
@@ -61,17 +152,18 @@ impl StreamFetchHandler {
         sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
     ) -> Result<(), SocketError> {
-        let (header, msg) = request.get_header_request();
+        let (header, mut msg) = request.get_header_request();
         let replica = ReplicaKey::new(msg.topic.clone(), msg.partition);
 
-        if let Some(leader_state) = ctx.leaders_state().get(&replica).await {
+        if let Some(source) = FetchSource::lookup(&ctx, &replica, &mut msg).await {
             let (stream_id, offset_publisher) = conn_ctx
                 .stream_publishers_mut()
                 .create_new_publisher(msg.topic.clone(), msg.partition, msg.consumer_id.clone())
                 .await;
             let consumer_offset_listener = offset_publisher.offset_publisher.change_listener();
+            let seek_receiver = offset_publisher.seek_receiver.clone();
 
-            leader_state
+            let storage = source
                 .register_offset_publisher(&offset_publisher.offset_publisher)
                 .await;
 
@@ -80,11 +172,12 @@ impl StreamFetchHandler {
                     ctx,
                     sink,
                     end_event.clone(),
-                    leader_state,
+                    storage,
                     stream_id,
                     header,
                     replica,
                     consumer_offset_listener,
+                    seek_receiver,
                     msg,
                 )
                 .await
@@ -98,6 +191,7 @@ impl StreamFetchHandler {
             let response = StreamFetchResponse {
                 topic: replica.topic,
                 stream_id: 0,
+                seek_epoch: 0,
                 partition: FilePartitionResponse {
                     partition_index: replica.partition,
                     error_code: ErrorCode::NotLeaderForPartition,
@@ -121,29 +215,37 @@ impl StreamFetchHandler {
 
     #[allow(clippy::too_many_arguments)]
     #[instrument(
-        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener),
+        skip(ctx,replica,end_event,storage,header,msg,consumer_offset_listener,seek_receiver),
         fields(
             replica = %replica,
-            sink = sink.id()
+            sink = sink.id(),
+            traceparent = tracing::field::Empty
         ))
     ]
     pub async fn fetch(
         ctx: DefaultSharedGlobalContext,
         sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
-        leader_state: SharedFileLeaderState,
+        storage: SharableReplicaStorage<FileReplica>,
         stream_id: u32,
         header: RequestHeader,
         replica: ReplicaKey,
         consumer_offset_listener: OffsetChangeListener,
+        seek_receiver: Receiver<publishers::StreamSeek>,
         msg: StreamFetchRequest<FileRecordSet>,
     ) -> Result<(), SocketError> {
         debug!("request: {:#?}", msg);
+        continue_trace(&msg.trace_context);
         let version = header.api_version();
 
-        let sm_ctx = match SmartModuleContext::try_from(msg.smartmodules, version, &ctx).await {
+        let sm_ctx = match SmartModuleContext::try_from(msg.smartmodules, version, &ctx)
+            .await
+            .and_then(|sm_ctx| {
+                let dictionary = ctx.replica_dictionary(&replica)?;
+                Ok(sm_ctx.map(|sm_ctx| sm_ctx.with_dictionary(dictionary)))
+            }) {
             Ok(Some(mut ctx)) => {
-                if let Err(error_code) = ctx.look_back(&leader_state).await {
+                if let Err(error_code) = ctx.look_back(&storage).await {
                     warn!("smartmodule look_back failed: {:?}", error_code);
                     send_back_error(&sink, &replica, &header, stream_id, error_code).await?;
                     return Ok(());
@@ -178,6 +280,25 @@ impl StreamFetchHandler {
             starting_offset,
             "stream fetch");
 
+        let key_provider = storage.key_provider().await;
+
+        let legacy = LegacyConsumer::new(version);
+        let dictionary = if legacy.is_legacy() {
+            match ctx.replica_dictionary(&replica) {
+                Ok(dictionary) => dictionary,
+                Err(error_code) => {
+                    send_back_error(&sink, &replica, &header, stream_id, error_code).await?;
+                    return Ok(());
+                }
+            }
+        } else {
+            None
+        };
+        let compression_level = ctx
+            .replica_localstore()
+            .spec(&replica)
+            .and_then(|replica| replica.compression_level);
+
         let handler = Self {
             isolation,
             replica: replica.clone(),
@@ -186,10 +307,16 @@ impl StreamFetchHandler {
             end_event,
             header: header.clone(),
             consumer_offset_listener,
+            seek_receiver,
+            seek_epoch: 0,
             stream_id,
-            leader_state,
+            storage,
             max_fetch_bytes,
             metrics: ctx.metrics(),
+            key_provider,
+            legacy,
+            dictionary,
+            compression_level,
         };
 
         if let Err(err) = handler.process(starting_offset, sm_ctx).await {
@@ -226,7 +353,7 @@ impl StreamFetchHandler {
             .send_back_records(starting_offset, sm_ctx.as_mut())
             .await?;
 
-        let mut leader_offset_receiver = self.leader_state.offset_listener(&self.isolation);
+        let mut leader_offset_receiver = self.storage.offset_listener(&self.isolation);
         let mut counter: i32 = 0;
         // since we don't need to wait for consumer, can move consumer to same offset as last read
         let mut last_known_consumer_offset: Option<Offset> =
@@ -290,6 +417,22 @@ impl StreamFetchHandler {
                     }
                 },
 
+                // Consumer repositioned the stream, restart reading from the requested offset
+                seek = self.seek_receiver.recv() => {
+                    let Ok(seek) = seek else {
+                        debug!("connection closed, terminating");
+                        break;
+                    };
+                    debug!(epoch = seek.epoch, offset = seek.offset, "seeking stream");
+                    self.seek_epoch = seek.epoch;
+                    // acknowledgements received before the seek refer to the old position
+                    self.consumer_offset_listener.skip_pending();
+
+                    let (offset, wait) = self.send_back_records(seek.offset, sm_ctx.as_mut()).await?;
+                    last_partition_offset = offset;
+                    last_known_consumer_offset = (!wait).then_some(offset);
+                },
+
                 // Received new partition offset from leader, i.e. a new record was produced
                 partition_offset_update = leader_offset_receiver.listen() => {
                     debug!(partition_offset_update, "Received leader update:");
@@ -363,7 +506,7 @@ impl StreamFetchHandler {
         // Returns with the HW/LEO of the latest records available in the leader
         // This describes the range of records that can be read in this request
         let read_end_offset = match self
-            .leader_state
+            .storage
             .read_records(starting_offset, self.max_fetch_bytes, self.isolation)
             .await
         {
@@ -400,6 +543,15 @@ impl StreamFetchHandler {
             return Ok((starting_offset, false));
         }
 
+        let in_memory = self.key_provider.is_some()
+            || (self.legacy.is_legacy()
+                && any_batch_header(
+                    &file_partition_response.records.raw_slice(),
+                    self.max_bytes as usize,
+                    |header| self.legacy.needs_rewrite(header),
+                )
+                .map_err(|err| StreamFetchError::Fetch(ErrorCode::Other(format!("{err}"))))?);
+
         let (offset, wait, metrics_update) = match sm_ctx {
             Some(sm_ctx) => {
                 // If a SmartModule is provided, we need to read records from file to memory
@@ -407,7 +559,9 @@ impl StreamFetchHandler {
 
                 let records = &file_partition_response.records;
                 let mut file_batch_iterator =
-                    FileBatchIterator::from_raw_slice(records.raw_slice());
+                    FileBatchIterator::from_raw_slice(records.raw_slice())
+                        .with_key_provider(self.key_provider.clone())
+                        .with_dictionary(sm_ctx.dictionary().cloned());
 
                 let (batch, smartmodule_error) = process_batch(
                     sm_ctx.chain_mut(),
@@ -431,6 +585,19 @@ impl StreamFetchHandler {
                     .await?;
                 (offset, wait, metrics_update)
             }
+            None if in_memory => {
+                // Sealed records and records older consumers can't decode can't be sent as file slice,
+                // they are read in memory
+                debug!("No SmartModule, sending back log read in memory");
+                let metrics_update = IncreaseValue::from(&file_partition_response);
+                self.send_in_memory_response(file_partition_response)
+                    .await?;
+                (
+                    read_end_offset.isolation(&self.isolation),
+                    true,
+                    metrics_update,
+                )
+            }
             None => {
                 // If no SmartModule is provided, respond using raw file records
                 debug!("No SmartModule, sending back entire log");
@@ -439,6 +606,7 @@ impl StreamFetchHandler {
                 let response = StreamFetchResponse {
                     topic: self.replica.topic.clone(),
                     stream_id: self.stream_id,
+                    seek_epoch: self.seek_epoch,
                     partition: file_partition_response,
                 };
 
@@ -525,6 +693,7 @@ impl StreamFetchHandler {
         let stream_response = StreamFetchResponse {
             topic: self.replica.topic.clone(),
             stream_id: self.stream_id,
+            seek_epoch: self.seek_epoch,
             partition: partition_response,
         };
 
@@ -542,6 +711,82 @@ impl StreamFetchHandler {
 
         Ok((next_offset, true))
     }
+
+    /// Send batches of the slice read in memory, up to `max_bytes`.
+    /// Sealed batches are opened and older consumers get records without headers and dictionary.
+    /// Consumer acknowledgement triggers sending the rest of the slice
+    #[instrument(skip(self, file_partition_response))]
+    async fn send_in_memory_response(
+        &self,
+        file_partition_response: FilePartitionResponse,
+    ) -> Result<(), StreamFetchError> {
+        type DefaultPartitionResponse = FetchablePartitionResponse<RecordSet<RawRecords>>;
+
+        let mut batches = read_batches_up_to(
+            &file_partition_response.records.raw_slice(),
+            self.key_provider.as_deref(),
+            self.max_bytes as usize,
+        )
+        .map_err(|err| StreamFetchError::Fetch(ErrorCode::Other(format!("{err}"))))?;
+
+        if self.legacy.is_legacy() {
+            batches = batches
+                .into_iter()
+                .map(|batch| self.legacy_batch(batch))
+                .collect::<Result<_, _>>()?;
+        }
+
+        debug!(
+            batches = batches.len(),
+            "sending back batches read in memory"
+        );
+
+        let partition_response = DefaultPartitionResponse {
+            partition_index: self.replica.partition,
+            error_code: file_partition_response.error_code,
+            high_watermark: file_partition_response.high_watermark,
+            log_start_offset: file_partition_response.log_start_offset,
+            records: RecordSet { batches },
+            ..Default::default()
+        };
+
+        let stream_response = StreamFetchResponse {
+            topic: self.replica.topic.clone(),
+            stream_id: self.stream_id,
+            seek_epoch: self.seek_epoch,
+            partition: partition_response,
+        };
+
+        let response_msg = RequestMessage::<DefaultStreamFetchRequest>::response_with_header(
+            &self.header,
+            stream_response,
+        );
+
+        let mut inner_sink = self.sink.lock().await;
+        inner_sink
+            .send_response(&response_msg, self.header.api_version())
+            .await?;
+
+        Ok(())
+    }
+
+    /// rewrite batch into encoding older consumer can decode
+    fn legacy_batch(
+        &self,
+        mut batch: Batch<RawRecords>,
+    ) -> Result<Batch<RawRecords>, CompressionError> {
+        if self.legacy.record_headers {
+            batch =
+                batch.without_record_headers(self.dictionary.as_ref(), self.compression_level)?;
+        }
+        if self.legacy.dictionary {
+            batch = batch.without_dictionary(self.dictionary.as_ref(), self.compression_level)?;
+        }
+        if self.legacy.mirror_origin {
+            batch.clear_mirror_origin();
+        }
+        Ok(batch)
+    }
 }
 
 async fn send_back_error(
@@ -561,6 +806,7 @@ async fn send_back_error(
     let stream_response = StreamFetchResponse {
         topic: replica.topic.clone(),
         stream_id,
+        seek_epoch: 0,
         partition: partition_response,
     };
 
@@ -609,6 +855,10 @@ pub mod publishers {
     use fluvio_types::PartitionId;
     use fluvio_types::event::offsets::INIT_OFFSET;
 
+    use async_channel::{Receiver, Sender};
+
+    use fluvio_protocol::record::Offset;
+
     use super::OffsetPublisher;
 
     pub struct StreamPublishers {
@@ -619,11 +869,20 @@ pub mod publishers {
     #[derive(Clone)]
     pub struct StreamPublisher {
         pub offset_publisher: Arc<OffsetPublisher>,
+        pub seek_sender: Sender<StreamSeek>,
+        pub seek_receiver: Receiver<StreamSeek>,
         pub topic: String,
         pub partition: PartitionId,
         pub consumer: Option<Consumer>,
     }
 
+    /// Seek requested by the consumer of a stream
+    #[derive(Debug, Clone, Copy)]
+    pub struct StreamSeek {
+        pub epoch: u32,
+        pub offset: Offset,
+    }
+
     #[derive(Clone)]
     pub struct Consumer {
         pub consumer_id: String,
@@ -657,9 +916,12 @@ pub mod publishers {
         ) -> (u32, StreamPublisher) {
             let stream_id = self.next_stream_id();
             let offset_publisher = OffsetPublisher::shared(INIT_OFFSET);
+            let (seek_sender, seek_receiver) = async_channel::unbounded();
             let consumer = consumer_id.map(|id| Consumer { consumer_id: id });
             let publisher = StreamPublisher {
                 offset_publisher,
+                seek_sender,
+                seek_receiver,
                 topic,
                 partition,
                 consumer,
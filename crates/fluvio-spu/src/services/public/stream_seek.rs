@@ -0,0 +1,76 @@
+use std::io::Error as IoError;
+
+use tracing::{debug, instrument, warn};
+
+use fluvio_protocol::api::{ResponseMessage, RequestMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_spu_schema::server::stream_seek::{StreamSeekRequest, StreamSeekResponse};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::services::public::conn_context::ConnectionContext;
+use crate::services::public::stream_fetch::publishers::StreamSeek;
+
+#[instrument(skip(ctx, conn_ctx, request))]
+pub(crate) async fn handle_stream_seek(
+    request: RequestMessage<StreamSeekRequest>,
+    ctx: DefaultSharedGlobalContext,
+    conn_ctx: &mut ConnectionContext,
+) -> Result<ResponseMessage<StreamSeekResponse>, IoError> {
+    let (header, request) = request.get_header_request();
+    let publisher = conn_ctx
+        .stream_publishers()
+        .get_publisher(request.session_id)
+        .await;
+
+    let error_code = match publisher {
+        Some(publisher) => {
+            debug!(
+                session_id = request.session_id,
+                offset = request.offset,
+                epoch = request.epoch,
+                "seek stream"
+            );
+            let replica = ReplicaKey::new(publisher.topic.clone(), publisher.partition);
+            match ctx.leaders_state().get(&replica).await {
+                Some(leader) => {
+                    let (start_offset, _) = leader.start_offset_info().await;
+                    let leo = leader.leo();
+                    // the stream is left where it is, seeking outside of the log can't be served
+                    if request.offset < start_offset || request.offset > leo {
+                        warn!(
+                            %replica,
+                            offset = request.offset,
+                            start_offset,
+                            leo,
+                            "seek offset out of range"
+                        );
+                        ErrorCode::OffsetOutOfRange
+                    } else {
+                        let seek = StreamSeek {
+                            epoch: request.epoch,
+                            offset: request.offset,
+                        };
+                        match publisher.seek_sender.send(seek).await {
+                            Ok(()) => ErrorCode::None,
+                            Err(_) => ErrorCode::FetchSessionNotFoud,
+                        }
+                    }
+                }
+                None => {
+                    warn!(%replica, "seek for replica not led by this spu");
+                    ErrorCode::NotLeaderForPartition
+                }
+            }
+        }
+        None => {
+            warn!(session_id = request.session_id, "seek for unknown stream");
+            ErrorCode::FetchSessionNotFoud
+        }
+    };
+
+    Ok(RequestMessage::<StreamSeekRequest>::response_with_header(
+        &header,
+        StreamSeekResponse { error_code },
+    ))
+}
@@ -71,7 +71,7 @@ async fn test_get_consumer_offset() {
         .expect("consumer offset replica");
 
     let key = ConsumerOffsetKey::new(target_replica_key.clone(), consumer_id);
-    let consumer = ConsumerOffset::new(5);
+    let consumer = ConsumerOffset::new(5).with_metadata(b"txn-5".to_vec());
     consumer_offset.put(key, consumer).await.expect("put");
 
     let get_consumer_offset_request = FetchConsumerOffsetsRequest::with_opts(
@@ -86,6 +86,7 @@ async fn test_get_consumer_offset() {
 
     assert_eq!(response.consumers.len(), 1);
     assert_eq!(response.consumers[0].offset, 5);
+    assert_eq!(response.consumers[0].metadata, b"txn-5");
     assert_eq!(response.error_code, ErrorCode::None);
 
     server_end_event.notify();
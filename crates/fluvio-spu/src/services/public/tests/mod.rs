@@ -104,7 +104,7 @@ fn read_wasm_module(module_name: &str) -> Vec<u8> {
     read_filter_from_path(wasm_path)
 }
 
-fn load_wasm_module<S: ReplicaStorage>(ctx: &GlobalContext<S>, module_name: &str) {
+pub(crate) fn load_wasm_module<S: ReplicaStorage>(ctx: &GlobalContext<S>, module_name: &str) {
     let wasm = zip(read_wasm_module(module_name));
     ctx.smartmodule_localstore().insert(SmartModule {
         name: module_name.to_owned(),
@@ -1,6 +1,7 @@
 use std::{env::temp_dir, time::Duration};
 
-use fluvio::{SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind};
+use fluvio::{Compression, SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind};
+use fluvio_compression::Dictionary;
 use fluvio_controlplane::replica::Replica;
 use fluvio_smartmodule::{Record, dataplane::smartmodule::Lookback};
 use fluvio_storage::{FileReplica, iterators::FileBatchIterator};
@@ -9,6 +10,7 @@ use tracing::debug;
 use fluvio_protocol::{
     api::{RequestMessage, RequestKind},
     link::ErrorCode,
+    record::MirrorOrigin,
     Decoder,
 };
 use fluvio_controlplane_metadata::topic::{
@@ -26,8 +28,9 @@ use flv_util::fixture::ensure_clean_dir;
 
 use crate::{
     config::SpuConfig,
-    core::GlobalContext,
+    core::{GlobalContext, metrics::Recompression},
     replication::leader::LeaderReplicaState,
+    services::public::produce_handler::{clear_mirror_origins, recompress_records},
     services::public::tests::{
         create_filter_raw_records, create_filter_records, create_public_server_with_root_auth,
         load_wasm_module, vec_to_raw_batch,
@@ -113,6 +116,7 @@ async fn test_produce_basic() {
             records: create_filter_records(records_per_request)
                 .try_into()
                 .expect("partition"),
+            ..Default::default()
         })
         .collect::<Vec<_>>();
     let topic_produce_request = TopicProduceData {
@@ -147,9 +151,114 @@ async fn test_produce_basic() {
     debug!("terminated controller");
 }
 
+#[test]
+fn test_recompress_records_to_topic_compression() {
+    let records_per_request = 9;
+    let mut records = create_filter_raw_records(records_per_request);
+    let metrics = Recompression::default();
+
+    recompress_records(
+        &mut records,
+        &CompressionAlgorithm::Gzip,
+        None,
+        None,
+        &metrics,
+    )
+    .expect("recompress");
+    assert_eq!(metrics.batches(), 1);
+    let batch = &records.batches[0];
+    assert_eq!(
+        batch.get_compression().expect("compression"),
+        Compression::Gzip
+    );
+    assert_eq!(
+        batch.memory_records().expect("records").len(),
+        records_per_request as usize
+    );
+
+    // batches already in the topic compression and `Any` topics are left as is
+    recompress_records(
+        &mut records,
+        &CompressionAlgorithm::Gzip,
+        None,
+        None,
+        &metrics,
+    )
+    .expect("recompress");
+    recompress_records(
+        &mut records,
+        &CompressionAlgorithm::Any,
+        None,
+        None,
+        &metrics,
+    )
+    .expect("recompress");
+    assert_eq!(metrics.batches(), 1);
+}
+
+#[test]
+fn test_recompress_records_with_dictionary() {
+    let dictionary =
+        Dictionary::new(include_bytes!("test_data/records.dict").to_vec()).expect("dictionary");
+    let metrics = Recompression::default();
+
+    // zstd topic compresses with its dictionary
+    let mut records = create_filter_raw_records(3);
+    recompress_records(
+        &mut records,
+        &CompressionAlgorithm::Zstd,
+        None,
+        Some(&dictionary),
+        &metrics,
+    )
+    .expect("recompress");
+    let batch = &records.batches[0];
+    assert!(batch.header.has_dictionary());
+    assert_eq!(
+        batch.get_compression().expect("compression"),
+        Compression::Zstd
+    );
+    assert_eq!(
+        batch
+            .memory_records_with(Some(&dictionary))
+            .expect("records")
+            .len(),
+        3
+    );
+
+    // batches compressed with dictionary are decompressed with it
+    recompress_records(
+        &mut records,
+        &CompressionAlgorithm::Gzip,
+        None,
+        Some(&dictionary),
+        &metrics,
+    )
+    .expect("recompress");
+    let batch = &records.batches[0];
+    assert!(!batch.header.has_dictionary());
+    assert_eq!(
+        batch.get_compression().expect("compression"),
+        Compression::Gzip
+    );
+    assert_eq!(batch.memory_records().expect("records").len(), 3);
+    assert_eq!(metrics.batches(), 2);
+}
+
+#[test]
+fn test_produce_clears_mirror_origin() {
+    let mut records = create_filter_raw_records(2);
+    records.batches[0].set_mirror_origin(MirrorOrigin::new("peer", 10));
+
+    clear_mirror_origins(&mut records);
+    let batch = &records.batches[0];
+    assert!(!batch.get_header().is_mirrored());
+    assert_eq!(batch.mirror_origin(), None);
+}
+
 #[fluvio_future::test(ignore)]
-async fn test_produce_invalid_compression() {
-    let test_path = temp_dir().join("produce_invalid_compression");
+async fn test_produce_recompress_to_topic_compression() {
+    let test_path = temp_dir().join("produce_recompress_to_topic_compression");
     ensure_clean_dir(&test_path);
     let port = portpicker::pick_unused_port().expect("No free ports left");
 
@@ -212,8 +321,10 @@ async fn test_produce_invalid_compression() {
     assert_eq!(produce_response.responses[0].partitions.len(), 1);
     assert_eq!(
         produce_response.responses[0].partitions[0].error_code,
-        ErrorCode::CompressionError
+        ErrorCode::None
     );
+    assert_eq!(ctx.metrics().recompression().batches(), 1);
+    assert_eq!(replica.leo(), records_per_request as i64);
 
     server_end_event.notify();
     debug!("terminated controller");
@@ -7,10 +7,14 @@ use fluvio_controlplane::spu_api::update_smartmodule::SmartModule;
 use fluvio_smartmodule::dataplane::smartmodule::Lookback;
 use tracing::{debug, info};
 
+use fluvio_controlplane::spu_api::update_dictionary::Dictionary;
+use fluvio_controlplane_metadata::dictionary::DictionarySpec;
 use fluvio_controlplane_metadata::smartmodule::{
     SmartModuleWasm, SmartModuleWasmFormat, SmartModuleSpec,
 };
+use fluvio_compression::{Compression, CompressionOptions, Dictionary as CompressionDictionary};
 use fluvio_storage::FileReplica;
+use fluvio_types::event::offsets::{OffsetPublisher, INIT_OFFSET, TOPIC_DELETED};
 use flv_util::fixture::ensure_clean_dir;
 use futures_util::{Future, StreamExt};
 
@@ -21,7 +25,7 @@ use fluvio_spu_schema::server::smartmodule::{
 };
 use fluvio_protocol::{
     fixture::BatchProducer,
-    record::{RecordData, Record, Batch},
+    record::{RecordData, Record, Batch, MirrorOrigin},
     link::{smartmodule::SmartModuleKind as SmartModuleKindError, ErrorCode},
     ByteBuf,
 };
@@ -30,7 +34,8 @@ use fluvio_spu_schema::{
     server::update_offset::{UpdateOffsetsRequest, OffsetUpdate},
     fetch::DefaultFetchRequest,
 };
-use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
+use fluvio_spu_schema::server::stream_fetch::{DefaultStreamFetchRequest, FileStreamFetchRequest};
+use fluvio_spu_schema::{Isolation, DICTIONARY_API, MIRROR_ORIGIN_API};
 use crate::services::public::tests::{
     create_filter_raw_records, create_public_server_with_root_auth, read_records, vec_to_batch,
 };
@@ -39,7 +44,9 @@ use crate::{
     services::public::tests::{create_filter_records, vec_to_raw_batch},
 };
 use crate::config::SpuConfig;
+use crate::replication::follower::FollowerReplicaState;
 use crate::replication::leader::LeaderReplicaState;
+use crate::services::public::stream_fetch::FetchSource;
 
 use fluvio_protocol::{api::RequestMessage, record::RecordSet};
 
@@ -3078,3 +3085,296 @@ async fn test_stream_fetch_sends_topic_delete_error_on_topic_delete() {
     server_end_event.notify();
     debug!("terminated controller");
 }
+
+#[fluvio_future::test]
+async fn test_stream_fetch_source_from_follower() {
+    let test_path = temp_dir().join("test_stream_fetch_source_from_follower");
+    ensure_clean_dir(&test_path);
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let follower_replica = Replica::new(("follower", 0), 5002, vec![5002, 5001]);
+    let follower = FollowerReplicaState::<FileReplica>::create(
+        5002,
+        follower_replica.id.clone(),
+        ctx.config().into(),
+    )
+    .await
+    .expect("follower");
+    ctx.followers_state()
+        .write()
+        .await
+        .insert(follower_replica.id.clone(), follower);
+
+    let request = |topic: &str, read_from_follower: bool| {
+        FileStreamFetchRequest::builder()
+            .topic(topic)
+            .isolation(Isolation::ReadUncommitted)
+            .read_from_follower(read_from_follower)
+            .build()
+            .expect("request")
+    };
+
+    // follower is not used unless client allows it
+    let mut msg = request("follower", false);
+    assert!(
+        FetchSource::lookup(&ctx, &follower_replica.id, &mut msg)
+            .await
+            .is_none()
+    );
+    assert_eq!(msg.isolation, Isolation::ReadUncommitted);
+
+    // follower only serves committed records
+    let mut msg = request("follower", true);
+    assert!(matches!(
+        FetchSource::lookup(&ctx, &follower_replica.id, &mut msg).await,
+        Some(FetchSource::Follower(_))
+    ));
+    assert_eq!(msg.isolation, Isolation::ReadCommitted);
+
+    // leader is preferred and keeps requested isolation
+    let leader_replica = Replica::new(("leader", 0), 5001, vec![5001]);
+    let leader_id = leader_replica.id.clone();
+    let leader =
+        LeaderReplicaState::create(leader_replica, ctx.config(), ctx.status_update_owned())
+            .await
+            .expect("replica")
+            .init(&ctx)
+            .await
+            .expect("init succeeded");
+    ctx.leaders_state().insert(leader_id.clone(), leader).await;
+
+    let mut msg = request("leader", true);
+    assert!(matches!(
+        FetchSource::lookup(&ctx, &leader_id, &mut msg).await,
+        Some(FetchSource::Leader(_))
+    ));
+    assert_eq!(msg.isolation, Isolation::ReadUncommitted);
+
+    // unknown replica
+    let mut msg = request("unknown", true);
+    assert!(
+        FetchSource::lookup(&ctx, &("unknown", 0).into(), &mut msg)
+            .await
+            .is_none()
+    );
+}
+
+#[fluvio_future::test]
+async fn test_stream_fetch_follower_topic_deleted() {
+    let test_path = temp_dir().join("test_stream_fetch_follower_topic_deleted");
+    ensure_clean_dir(&test_path);
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let replica = Replica::new(("follower", 0), 5002, vec![5002, 5001]);
+    let follower =
+        FollowerReplicaState::<FileReplica>::create(5002, replica.id.clone(), ctx.config().into())
+            .await
+            .expect("follower");
+    ctx.followers_state()
+        .write()
+        .await
+        .insert(replica.id.clone(), follower.clone());
+
+    let mut msg = FileStreamFetchRequest::builder()
+        .topic("follower")
+        .read_from_follower(true)
+        .build()
+        .expect("request");
+    let source = FetchSource::lookup(&ctx, &replica.id, &mut msg)
+        .await
+        .expect("follower");
+    let publisher = OffsetPublisher::shared(INIT_OFFSET);
+    source.register_offset_publisher(&publisher).await;
+
+    follower.signal_topic_deleted().await;
+    assert_eq!(publisher.current_value(), TOPIC_DELETED);
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_dictionary_legacy_consumer() {
+    let test_path = temp_dir().join("test_stream_fetch_dictionary_legacy_consumer");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let data = include_bytes!("test_data/records.dict").to_vec();
+    let dictionary = CompressionDictionary::new(data.clone()).expect("dictionary");
+    ctx.dictionaries_localstore().insert(Dictionary {
+        name: "records".to_owned(),
+        spec: DictionarySpec {
+            dictionary_id: dictionary.id(),
+            data: ByteBuf::from(data),
+        },
+    });
+
+    let topic = "dictionary";
+    let mut test = Replica::new((topic, 0), 5001, vec![5001]);
+    test.compression_dictionary = Some("records".to_owned());
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    let mut batch = Batch::from(vec![
+        Record::new(r#"{"id":1,"city":"berlin","status":"active"}"#),
+        Record::new(r#"{"id":2,"city":"paris","status":"closed"}"#),
+    ]);
+    batch.header.set_compression(Compression::Zstd);
+    let batch = batch
+        .compress_with(&CompressionOptions::default().dictionary(dictionary.clone()))
+        .expect("compress");
+    assert!(batch.header.has_dictionary());
+    replica
+        .write_record_set(
+            &mut RecordSet {
+                batches: vec![batch],
+            },
+            ctx.follower_notifier(),
+        )
+        .await
+        .expect("write");
+
+    for version in [DICTIONARY_API - 1, DICTIONARY_API] {
+        let stream_request = DefaultStreamFetchRequest::builder()
+            .topic(topic)
+            .max_bytes(1000)
+            .build()
+            .expect("request");
+        let mut request = RequestMessage::new_request(stream_request);
+        request.header.set_api_version(version);
+
+        let mut stream = client_socket
+            .create_stream(request, 1)
+            .await
+            .expect("create stream");
+        let response = stream.next().await.expect("first").expect("response");
+        let partition = &response.partition;
+        assert_eq!(partition.error_code, ErrorCode::None);
+        assert_eq!(partition.records.batches.len(), 1);
+        let batch = &partition.records.batches[0];
+
+        let records = if version < DICTIONARY_API {
+            // older consumer gets batch it can decompress without dictionary
+            assert!(!batch.header.has_dictionary());
+            assert_eq!(
+                batch.get_compression().expect("compression"),
+                Compression::Zstd
+            );
+            batch.memory_records().expect("records")
+        } else {
+            assert!(batch.header.has_dictionary());
+            batch
+                .memory_records_with(Some(&dictionary))
+                .expect("records")
+        };
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[1].value().as_ref(),
+            br#"{"id":2,"city":"paris","status":"closed"}"#
+        );
+    }
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_mirror_origin_legacy_consumer() {
+    let test_path = temp_dir().join("test_stream_fetch_mirror_origin_legacy_consumer");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "mirrored";
+    let test = Replica::new((topic, 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    let origin = MirrorOrigin::new("peer", 10);
+    let mut batch = Batch::from(vec![Record::new("a"), Record::new("b")]);
+    batch.set_mirror_origin(origin);
+    replica
+        .write_record_set(
+            &mut RecordSet {
+                batches: vec![batch.try_into().expect("raw")],
+            },
+            ctx.follower_notifier(),
+        )
+        .await
+        .expect("write");
+
+    for version in [MIRROR_ORIGIN_API - 1, MIRROR_ORIGIN_API] {
+        let stream_request = DefaultStreamFetchRequest::builder()
+            .topic(topic)
+            .max_bytes(1000)
+            .build()
+            .expect("request");
+        let mut request = RequestMessage::new_request(stream_request);
+        request.header.set_api_version(version);
+
+        let mut stream = client_socket
+            .create_stream(request, 1)
+            .await
+            .expect("create stream");
+        let response = stream.next().await.expect("first").expect("response");
+        let partition = &response.partition;
+        assert_eq!(partition.error_code, ErrorCode::None);
+        assert_eq!(partition.records.batches.len(), 1);
+        let batch = &partition.records.batches[0];
+
+        if version < MIRROR_ORIGIN_API {
+            // older consumer doesn't know origin follows the header
+            assert!(!batch.header.is_mirrored());
+            assert_eq!(batch.mirror_origin(), None);
+        } else {
+            assert!(batch.header.is_mirrored());
+            assert_eq!(batch.mirror_origin(), Some(origin));
+        }
+        let records = batch.memory_records().expect("records");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].value().as_ref(), b"b");
+    }
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
@@ -1,12 +1,12 @@
 use std::time::Instant;
-use std::io::Error as IoError;
+use std::io::{Cursor, Error as IoError};
 
 use anyhow::Error;
 use tracing::{instrument, debug, trace};
 
-use fluvio_compression::{Compression, CompressionError};
-use fluvio_protocol::record::{RecordSet, RawRecords};
-use fluvio_protocol::Encoder;
+use fluvio_compression::{Compression, CompressionError, Dictionary};
+use fluvio_protocol::record::{RecordSet, RawRecords, RECORD_HEADERS_VERSION};
+use fluvio_protocol::{Decoder, Encoder, Version};
 use fluvio_protocol::{
     record::{Batch, MemoryRecords, Offset},
     link::smartmodule::SmartModuleTransformRuntimeError,
@@ -26,13 +26,16 @@ pub(crate) trait SmartModuleInputBatch {
     fn offset_delta(&self) -> i32;
 
     fn get_compression(&self) -> Result<Compression, CompressionError>;
+
+    fn records_version(&self) -> Version;
 }
 
 pub(crate) fn process_record_set(
     sm_chain: &mut SmartModuleChainInstance,
     records: &mut RecordSet<RawRecords>,
+    dictionary: Option<Dictionary>,
 ) -> Result<(Batch, Option<SmartModuleTransformRuntimeError>), Error> {
-    let mut batches = ProduceBatchIterator::new(&records.batches);
+    let mut batches = ProduceBatchIterator::new(&records.batches).with_dictionary(dictionary);
 
     process_batch(sm_chain, &mut batches, usize::MAX)
 }
@@ -63,8 +66,13 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
         );
 
         let now = Instant::now();
+        let raw_records = if input_batch.records_version() >= RECORD_HEADERS_VERSION {
+            without_headers(input_batch.records(), input_batch.records_version())?
+        } else {
+            input_batch.records().clone()
+        };
         let input = SmartModuleInput::new(
-            input_batch.records().clone(),
+            raw_records,
             input_batch.base_offset(),
             input_batch.base_timestamp(),
         );
@@ -140,6 +148,15 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
     Ok((smartmodule_batch, None))
 }
 
+/// SmartModules decode records without headers, so records carrying them are encoded again without
+fn without_headers(records: &[u8], version: Version) -> Result<Vec<u8>, IoError> {
+    let mut decoded = MemoryRecords::default();
+    decoded.decode(&mut Cursor::new(records), version)?;
+    let mut raw_records = Vec::with_capacity(records.len());
+    decoded.encode(&mut raw_records, 0)?;
+    Ok(raw_records)
+}
+
 fn set_compression(
     input_batch: &impl SmartModuleInputBatch,
     smartmodule_batch: &mut Batch<MemoryRecords>,
@@ -3,6 +3,7 @@ use std::time::Duration;
 
 use async_lock::RwLock;
 use chrono::Utc;
+use fluvio_compression::Dictionary;
 use fluvio_protocol::link::ErrorCode;
 use fluvio_smartmodule::Record;
 use fluvio_spu_schema::server::smartmodule::{SmartModuleInvocation, SmartModuleInvocationWasm};
@@ -13,7 +14,7 @@ use tracing::{debug, trace, error};
 
 use crate::core::GlobalContext;
 use crate::core::metrics::SpuMetrics;
-use crate::replication::leader::LeaderReplicaState;
+use crate::storage::SharableReplicaStorage;
 
 use crate::smartengine::chain;
 use crate::smartengine::Lookback;
@@ -26,6 +27,7 @@ pub struct SmartModuleContext {
     chain: SmartModuleChainInstance,
     version: Version,
     spu_metrics: Arc<SpuMetrics>,
+    dictionary: Option<Dictionary>,
 }
 
 pub type SharedSmartModuleContext = Arc<RwLock<SmartModuleContext>>;
@@ -43,12 +45,24 @@ impl SmartModuleContext {
         &mut self.chain
     }
 
+    /// zstd dictionary used to uncompress records of the replica
+    pub fn with_dictionary(mut self, dictionary: Option<Dictionary>) -> Self {
+        self.dictionary = dictionary;
+        self
+    }
+
+    pub fn dictionary(&self) -> Option<&Dictionary> {
+        self.dictionary.as_ref()
+    }
+
     pub async fn look_back<R: ReplicaStorage>(
         &mut self,
-        replica: &LeaderReplicaState<R>,
+        replica: &SharableReplicaStorage<R>,
     ) -> Result<(), ErrorCode> {
         self.chain
-            .look_back(|lookback| read_records(replica, lookback, self.version))
+            .look_back(|lookback| {
+                read_records(replica, lookback, self.version, self.dictionary.as_ref())
+            })
             .await
             .map_err(|err| {
                 error!("look_back chain error: {err:#}");
@@ -84,6 +98,7 @@ impl SmartModuleContext {
             chain,
             version,
             spu_metrics: ctx.metrics(),
+            dictionary: None,
         }))
     }
 
@@ -120,11 +135,12 @@ fn resolve_invocation<R: ReplicaStorage>(
 }
 
 async fn read_records<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     lookback: Lookback,
     version: Version,
+    dictionary: Option<&Dictionary>,
 ) -> anyhow::Result<Vec<Record>> {
-    let iter = lookback_iterator(replica, lookback, version).await?;
+    let iter = lookback_iterator(replica, lookback, version, dictionary).await?;
 
     let result: Vec<Record> = iter.collect::<Result<Vec<Record>, std::io::Error>>()?;
     debug!("read {} records", result.len());
@@ -133,22 +149,26 @@ async fn read_records<R: ReplicaStorage>(
 }
 
 async fn lookback_iterator<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     lookback: Lookback,
     version: Version,
+    dictionary: Option<&Dictionary>,
 ) -> anyhow::Result<Box<dyn Iterator<Item = Result<Record, std::io::Error>>>> {
     let iter = match lookback {
-        Lookback::Last(last) => lookback_last_iterator(replica, last, version).await,
-        Lookback::Age { age, last } => lookback_age_iterator(replica, age, last, version).await,
+        Lookback::Last(last) => lookback_last_iterator(replica, last, version, dictionary).await,
+        Lookback::Age { age, last } => {
+            lookback_age_iterator(replica, age, last, version, dictionary).await
+        }
     }?;
     let iter = iter.map(|it| it.map(|res| res.record));
     Ok(Box::new(iter))
 }
 
 async fn lookback_last_iterator<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     last: u64,
     version: Version,
+    dictionary: Option<&Dictionary>,
 ) -> anyhow::Result<Box<dyn Iterator<Item = Result<RecordItem, std::io::Error>>>> {
     let (start_offset, hw) = replica.start_offset_info().await;
 
@@ -166,7 +186,9 @@ async fn lookback_last_iterator<R: ReplicaStorage>(
         return Ok(Box::new(std::iter::empty()));
     };
 
-    let batch_iter = FileBatchIterator::from_raw_slice(file_slice);
+    let batch_iter = FileBatchIterator::from_raw_slice(file_slice)
+        .with_key_provider(replica.key_provider().await)
+        .with_dictionary(dictionary.cloned());
     let records_iter = FileRecordIterator::new(batch_iter, version);
 
     Ok(Box::new(records_iter.filter(move |r| match r {
@@ -176,10 +198,11 @@ async fn lookback_last_iterator<R: ReplicaStorage>(
 }
 
 async fn lookback_age_iterator<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     age: Duration,
     last: u64,
     version: Version,
+    dictionary: Option<&Dictionary>,
 ) -> anyhow::Result<Box<dyn Iterator<Item = Result<RecordItem, std::io::Error>>>> {
     let min_timestamp: Timestamp = Utc::now()
         .timestamp_millis()
@@ -189,9 +212,9 @@ async fn lookback_age_iterator<R: ReplicaStorage>(
     debug!(?age, last, min_timestamp, "iterating for lookback");
 
     let records_iter = if last > 0 {
-        lookback_last_iterator(replica, last, version).await?
+        lookback_last_iterator(replica, last, version, dictionary).await?
     } else {
-        let batches = read_batches_by_age(replica, min_timestamp).await?;
+        let batches = read_batches_by_age(replica, min_timestamp, dictionary).await?;
         Box::new(FileRecordIterator::new(
             batches.into_iter().map(Ok),
             version,
@@ -204,11 +227,13 @@ async fn lookback_age_iterator<R: ReplicaStorage>(
 }
 
 async fn read_batches_by_age<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     min_timestamp: Timestamp,
+    dictionary: Option<&Dictionary>,
 ) -> anyhow::Result<Vec<FileBatch>> {
     let mut result = Vec::new();
     let mut offset = replica.hw() - 1;
+    let key_provider = replica.key_provider().await;
     loop {
         if offset.is_negative() {
             break;
@@ -221,7 +246,9 @@ async fn read_batches_by_age<R: ReplicaStorage>(
             trace!(?slice);
             break;
         };
-        let mut batch_iter = FileBatchIterator::from_raw_slice(file_slice);
+        let mut batch_iter = FileBatchIterator::from_raw_slice(file_slice)
+            .with_key_provider(key_provider.clone())
+            .with_dictionary(dictionary.cloned());
         let Some(batch) = batch_iter.next() else {
             break;
         };
@@ -1,5 +1,6 @@
 use fluvio_types::Timestamp;
 
+use fluvio_protocol::Version;
 use fluvio_protocol::record::Offset;
 use fluvio_compression::{Compression, CompressionError};
 
@@ -27,4 +28,8 @@ impl SmartModuleInputBatch for FileBatch {
     fn get_compression(&self) -> Result<Compression, CompressionError> {
         self.batch.get_compression()
     }
+
+    fn records_version(&self) -> Version {
+        self.batch.header.records_version()
+    }
 }
@@ -3,7 +3,7 @@ use std::collections::BTreeMap;
 use fluvio::{
     SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind, SmartModuleExtraParams,
 };
-use fluvio_controlplane_metadata::topic::Deduplication;
+use fluvio_controlplane_metadata::topic::{Deduplication, Transform};
 use fluvio_protocol::link::ErrorCode;
 
 pub(crate) mod batch;
@@ -80,6 +80,14 @@ mod null_smartengine {
             self.invocation_count.load(Ordering::SeqCst)
         }
 
+        pub fn records_err(&self) -> u64 {
+            self.records_err.load(Ordering::SeqCst)
+        }
+
+        pub fn cpu_ms(&self) -> u64 {
+            self.cpu_ms.load(Ordering::SeqCst)
+        }
+
         // Added append method
         pub fn append(&self, other: &Self) {
             self.bytes_in.fetch_add(other.bytes_in(), Ordering::SeqCst);
@@ -204,6 +212,16 @@ pub(crate) fn dedup_to_invocation(dedup: &Deduplication) -> SmartModuleInvocatio
     }
 }
 
+/// smartmodule kind is resolved from wasm exports when chain is built
+pub(crate) fn transform_to_invocation(transform: &Transform) -> SmartModuleInvocation {
+    SmartModuleInvocation {
+        wasm: SmartModuleInvocationWasm::Predefined(transform.uses.clone()),
+        kind: SmartModuleKind::Generic(Default::default()),
+        params: SmartModuleExtraParams::new(transform.with.clone(), None),
+        name: Some(transform.uses.clone()),
+    }
+}
+
 pub(crate) fn map_engine_error(err: &EngineError) -> ErrorCode {
     match err {
         EngineError::UnknownSmartModule => ErrorCode::Other("Unknown SmartModule type".to_string()),
@@ -258,4 +276,25 @@ mod tests {
             Some(&"param_value".to_string())
         );
     }
+
+    #[test]
+    fn test_transform_to_inv() {
+        //given
+        let transform = Transform {
+            uses: "redact@0.1.0".to_string(),
+            with: BTreeMap::from([("field".to_string(), "email".to_string())]),
+        };
+
+        //when
+        let inv = transform_to_invocation(&transform);
+
+        //then
+        assert!(matches!(
+            inv.wasm,
+            SmartModuleInvocationWasm::Predefined(str) if str.eq("redact@0.1.0")
+        ));
+        assert!(matches!(inv.kind, SmartModuleKind::Generic(_)));
+        assert_eq!(inv.params.get("field"), Some(&"email".to_string()));
+        assert!(inv.params.lookback().is_none());
+    }
 }
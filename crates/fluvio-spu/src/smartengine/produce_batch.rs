@@ -1,7 +1,8 @@
 use std::io::Error as IoError;
 
+use fluvio_protocol::Version;
 use fluvio_protocol::record::{Batch, RawRecords, Offset};
-use fluvio_compression::{Compression, CompressionError};
+use fluvio_compression::{Compression, CompressionError, Dictionary};
 
 use super::batch::SmartModuleInputBatch;
 
@@ -15,6 +16,7 @@ pub struct ProduceBatchIterator<'a> {
     batches: &'a Vec<Batch<RawRecords>>,
     index: usize,
     len: usize,
+    dictionary: Option<Dictionary>,
 }
 
 impl SmartModuleInputBatch for ProduceBatch<'_> {
@@ -37,6 +39,10 @@ impl SmartModuleInputBatch for ProduceBatch<'_> {
     fn get_compression(&self) -> Result<Compression, CompressionError> {
         self.batch.get_compression()
     }
+
+    fn records_version(&self) -> Version {
+        self.batch.header.records_version()
+    }
 }
 
 impl<'a> ProduceBatchIterator<'a> {
@@ -45,8 +51,15 @@ impl<'a> ProduceBatchIterator<'a> {
             batches,
             index: 0,
             len: batches.len(),
+            dictionary: None,
         }
     }
+
+    /// zstd dictionary used to uncompress batches compressed with one
+    pub fn with_dictionary(mut self, dictionary: Option<Dictionary>) -> Self {
+        self.dictionary = dictionary;
+        self
+    }
 }
 
 impl<'a> Iterator for ProduceBatchIterator<'a> {
@@ -71,7 +84,7 @@ impl<'a> Iterator for ProduceBatchIterator<'a> {
             }
         };
 
-        let records = match compression.uncompress(raw_bytes) {
+        let records = match compression.uncompress_with(raw_bytes, self.dictionary.as_ref()) {
             Ok(Some(records)) => records,
             Ok(None) => raw_bytes.to_vec(),
             Err(err) => return Some(Err(IoError::other(format!("uncompress error {err}")))),
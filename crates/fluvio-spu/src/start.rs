@@ -10,6 +10,7 @@ use crate::services::public::create_public_server;
 use crate::core::DefaultSharedGlobalContext;
 use crate::core::GlobalContext;
 use crate::control_plane::ScDispatcher;
+use crate::replication::leader::IsrMonitor;
 
 type FileReplicaContext = GlobalContext<FileReplica>;
 
@@ -24,7 +25,7 @@ pub fn main_loop(opt: SpuOpt) {
     use fluvio_future::task::run_block_on;
     use fluvio_future::timer::sleep;
 
-    use crate::monitoring::init_monitoring;
+    use crate::monitoring::{init_monitoring, init_metrics_endpoint};
 
     // parse configuration (program exits on error)
     let (spu_config, tls_acceptor_option) = opt.process_spu_cli_or_exit();
@@ -47,6 +48,7 @@ pub fn main_loop(opt: SpuOpt) {
     run_block_on(async move {
         let ctx = create_services(spu_config.clone(), true, true);
 
+        init_metrics_endpoint(ctx.clone());
         init_monitoring(ctx);
 
         if let Some(tls_config) = tls_acceptor_option {
@@ -88,6 +90,8 @@ pub fn create_services(
     let sc_dispatcher = ScDispatcher::new(ctx.clone());
     sc_dispatcher.run();
 
+    IsrMonitor::run(ctx.clone());
+
     ctx
 }
 
@@ -13,6 +13,8 @@ use fluvio_protocol::Encoder;
 use fluvio_protocol::record::{Offset, RecordSet};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_storage::{ReplicaStorage, StorageError, OffsetInfo, ReplicaSlice};
+use fluvio_storage::checkpoint::CheckPoint;
+use fluvio_storage::encryption::KeyProvider;
 use fluvio_types::event::offsets::OffsetChangeListener;
 use fluvio_types::event::offsets::OffsetPublisher;
 
@@ -118,6 +120,25 @@ where
             .await
     }
 
+    /// key provider to open sealed batches, only if replica is encrypted
+    pub async fn key_provider(&self) -> Option<Arc<dyn KeyProvider>> {
+        let reader = self.read().await;
+        if reader.is_encrypted() {
+            reader.key_provider()
+        } else {
+            None
+        }
+    }
+
+    /// offset checkpoint kept in replica directory
+    pub async fn checkpoint(
+        &self,
+        name: &str,
+        initial_offset: Offset,
+    ) -> Result<Option<CheckPoint>> {
+        self.read().await.checkpoint(name, initial_offset).await
+    }
+
     pub async fn update_hw(&self, hw: Offset) -> Result<bool, StorageError> {
         let mut writer = self.write().await;
         if writer.update_high_watermark(hw).await? {
@@ -163,6 +184,15 @@ where
         Ok((base_offset, leo, bytes_written))
     }
 
+    /// remove records from batch containing offset up to the end, returns new leo
+    pub async fn truncate(&self, offset: Offset) -> Result<Offset> {
+        let mut writer = self.write().await;
+        let leo = writer.truncate(offset).await?;
+        self.leo.update(leo);
+        self.hw.update(writer.get_hw());
+        Ok(leo)
+    }
+
     /// perform permanent remove
     pub async fn remove(&self) -> Result<(), StorageError> {
         self.leo.update(REMOVAL_START);
@@ -19,6 +19,7 @@ use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
 use fluvio_protocol::record::{Size, Size64};
 
 use crate::ReplicaStorageConfig;
+use crate::encryption::{FileKeyProvider, KeyProvider};
 
 // Replica specific config
 #[derive(Builder, Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -57,6 +58,14 @@ pub struct ReplicaConfig {
     #[builder(default = "default_max_partition_size()")]
     #[serde(default = "default_max_partition_size")]
     pub max_partition_size: Size64,
+    /// directory of master keys used for encryption at rest
+    #[builder(default)]
+    #[serde(default)]
+    pub encryption_key_dir: Option<PathBuf>,
+    /// master key of the replica, set from topic storage config
+    #[builder(default)]
+    #[serde(default)]
+    pub encryption_key_id: Option<String>,
 }
 
 impl fmt::Display for ReplicaConfig {
@@ -89,6 +98,13 @@ impl ReplicaStorageConfig for ReplicaConfig {
         {
             self.max_partition_size = max_partition_size;
         }
+        if let Some(encryption) = replica
+            .storage
+            .as_ref()
+            .and_then(|storage| storage.encryption.as_ref())
+        {
+            self.encryption_key_id = Some(encryption.key_id.clone());
+        }
     }
 }
 
@@ -161,6 +177,8 @@ impl Default for ReplicaConfig {
             retention_seconds: default_retention_seconds(),
             max_partition_size: default_max_partition_size(),
             update_hw: true,
+            encryption_key_dir: None,
+            encryption_key_id: None,
         }
     }
 }
@@ -238,6 +256,8 @@ pub struct SharedReplicaConfig {
     pub update_hw: bool, // if true, enable hw update
     pub retention_seconds: SharedConfigU32Value,
     pub max_partition_size: SharedConfigU64Value,
+    pub encryption_key_id: Option<String>,
+    pub key_provider: Option<Arc<dyn KeyProvider>>,
 }
 
 impl From<ReplicaConfig> for SharedReplicaConfig {
@@ -254,6 +274,10 @@ impl From<ReplicaConfig> for SharedReplicaConfig {
             update_hw: config.update_hw,
             retention_seconds: SharedConfigU32Value::new(config.retention_seconds),
             max_partition_size: SharedConfigU64Value::new(config.max_partition_size),
+            encryption_key_id: config.encryption_key_id,
+            key_provider: config
+                .encryption_key_dir
+                .map(|dir| Arc::new(FileKeyProvider::new(dir)) as Arc<dyn KeyProvider>),
         }
     }
 }
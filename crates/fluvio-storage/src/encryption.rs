@@ -0,0 +1,566 @@
+//!
+//! # Encryption at rest
+//!
+//! Records of a batch are sealed with AES-256-GCM using a data key generated for each segment.
+//! The data key is wrapped with a master key from a [`KeyProvider`] and carried in an envelope
+//! in front of the ciphertext, so any replica with access to the master key can open the batch.
+//! Batch header stays in clear text, index, validation and replication work on it unchanged.
+//!
+//! Envelope layout:
+//!
+//! | field        | size                   |
+//! |--------------|------------------------|
+//! | version      | 1                      |
+//! | key id len   | 2                      |
+//! | key id       | key id len             |
+//! | key version  | 4                      |
+//! | wrapped key  | 12 + 32 + 16           |
+//! | nonce        | 12                     |
+//! | ciphertext   | records + 16           |
+//!
+//! Records are sealed with the envelope header and [`batch_aad`] as AAD, so an envelope can't be
+//! moved to another batch or offset. Envelopes of version 1 are bound to the envelope header only.
+//!
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::Error as IoError;
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use bytes::Bytes;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use tracing::{debug, info};
+
+use fluvio_protocol::Encoder;
+use fluvio_protocol::record::{
+    Batch, BatchHeader, BatchRecords, Offset, RawRecords, BATCH_HEADER_SIZE, RECORD_HEADERS_VERSION,
+};
+
+pub const ENVELOPE_VERSION: u8 = 2;
+/// envelopes of this version don't bind the batch header
+const UNBOUND_ENVELOPE_VERSION: u8 = 1;
+pub const KEY_LEN: usize = 32;
+const TAG_LEN: usize = 16;
+const WRAPPED_KEY_LEN: usize = NONCE_LEN + KEY_LEN + TAG_LEN;
+const KEY_FILE_EXTENSION: &str = "key";
+
+#[derive(Debug, thiserror::Error)]
+pub enum EncryptionError {
+    #[error("no master key found for: {0}")]
+    NoKey(String),
+    #[error("master key: {key_id} version: {version} not found")]
+    KeyNotFound { key_id: String, version: u32 },
+    #[error("invalid master key: {key_id}, {reason}")]
+    InvalidKey { key_id: String, reason: String },
+    #[error("invalid encryption envelope: {0}")]
+    InvalidEnvelope(String),
+    #[error("encryption key directory is not configured")]
+    NoKeyProvider,
+    #[error("cipher failed to seal or open records")]
+    Cipher,
+    #[error(transparent)]
+    Io(#[from] IoError),
+}
+
+impl From<ring::error::Unspecified> for EncryptionError {
+    fn from(_: ring::error::Unspecified) -> Self {
+        Self::Cipher
+    }
+}
+
+/// Versioned master key used to wrap segment data keys
+#[derive(Clone)]
+pub struct MasterKey {
+    version: u32,
+    bytes: [u8; KEY_LEN],
+}
+
+impl fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MasterKey(version={})", self.version)
+    }
+}
+
+impl MasterKey {
+    pub fn new(version: u32, bytes: [u8; KEY_LEN]) -> Self {
+        Self { version, bytes }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    fn cipher(&self) -> Result<LessSafeKey, EncryptionError> {
+        Ok(LessSafeKey::new(UnboundKey::new(
+            &AES_256_GCM,
+            &self.bytes,
+        )?))
+    }
+}
+
+/// Source of master keys.
+/// A key is identified by id and version, rotation is done by adding a new version
+pub trait KeyProvider: fmt::Debug + Send + Sync {
+    /// latest version of the key, this is used when new segment is created
+    fn current_key(&self, key_id: &str) -> Result<MasterKey, EncryptionError>;
+
+    /// specific version of the key, this is used to open existing batches
+    fn key(&self, key_id: &str, version: u32) -> Result<MasterKey, EncryptionError>;
+}
+
+/// Master keys stored in a directory as hex encoded files named `<key_id>.<version>.key`
+#[derive(Debug)]
+pub struct FileKeyProvider {
+    dir: PathBuf,
+    keys: RwLock<HashMap<(String, u32), MasterKey>>,
+}
+
+impl FileKeyProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            keys: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn key_path(&self, key_id: &str, version: u32) -> PathBuf {
+        self.dir
+            .join(format!("{key_id}.{version}.{KEY_FILE_EXTENSION}"))
+    }
+
+    /// versions of the key found in the directory
+    fn versions(&self, key_id: &str) -> Result<Vec<u32>, EncryptionError> {
+        let mut versions = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if let Some(version) = parse_key_file_name(&path, key_id) {
+                versions.push(version);
+            }
+        }
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    fn load(&self, key_id: &str, version: u32) -> Result<MasterKey, EncryptionError> {
+        let path = self.key_path(key_id, version);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(EncryptionError::KeyNotFound {
+                    key_id: key_id.to_owned(),
+                    version,
+                });
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let decoded = hex::decode(content.trim()).map_err(|err| EncryptionError::InvalidKey {
+            key_id: key_id.to_owned(),
+            reason: err.to_string(),
+        })?;
+        let bytes: [u8; KEY_LEN] =
+            decoded
+                .try_into()
+                .map_err(|invalid: Vec<u8>| EncryptionError::InvalidKey {
+                    key_id: key_id.to_owned(),
+                    reason: format!("expected {KEY_LEN} bytes, found {}", invalid.len()),
+                })?;
+        debug!(key_id, version, "loaded master key");
+        Ok(MasterKey::new(version, bytes))
+    }
+}
+
+impl KeyProvider for FileKeyProvider {
+    fn current_key(&self, key_id: &str) -> Result<MasterKey, EncryptionError> {
+        let version = self
+            .versions(key_id)?
+            .pop()
+            .ok_or_else(|| EncryptionError::NoKey(key_id.to_owned()))?;
+        self.key(key_id, version)
+    }
+
+    fn key(&self, key_id: &str, version: u32) -> Result<MasterKey, EncryptionError> {
+        let cache_key = (key_id.to_owned(), version);
+        if let Some(key) = self
+            .keys
+            .read()
+            .expect("key cache poisoned")
+            .get(&cache_key)
+        {
+            return Ok(key.clone());
+        }
+
+        let key = self.load(key_id, version)?;
+        self.keys
+            .write()
+            .expect("key cache poisoned")
+            .insert(cache_key, key.clone());
+        Ok(key)
+    }
+}
+
+fn parse_key_file_name(path: &Path, key_id: &str) -> Option<u32> {
+    if path.extension()? != KEY_FILE_EXTENSION {
+        return None;
+    }
+    let (id, version) = path.file_stem()?.to_str()?.rsplit_once('.')?;
+    if id != key_id {
+        return None;
+    }
+    version.parse().ok()
+}
+
+/// Seals batch records of a segment.
+/// Data key is generated when the cipher is created, so each segment is sealed with its own key
+pub struct SegmentCipher {
+    data_key: LessSafeKey,
+    header: Vec<u8>,
+    rng: SystemRandom,
+}
+
+impl fmt::Debug for SegmentCipher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SegmentCipher")
+    }
+}
+
+impl SegmentCipher {
+    pub fn new(provider: &dyn KeyProvider, key_id: &str) -> Result<Self, EncryptionError> {
+        let master_key = provider.current_key(key_id)?;
+        let rng = SystemRandom::new();
+
+        let mut data_key = [0u8; KEY_LEN];
+        rng.fill(&mut data_key)?;
+
+        // envelope header up to the wrapped key is used as AAD for wrapping
+        let mut header = Vec::with_capacity(envelope_prefix_len(key_id) + WRAPPED_KEY_LEN);
+        header.push(ENVELOPE_VERSION);
+        header.extend_from_slice(&(key_id.len() as u16).to_be_bytes());
+        header.extend_from_slice(key_id.as_bytes());
+        header.extend_from_slice(&master_key.version().to_be_bytes());
+
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill(&mut nonce)?;
+        let mut wrapped = data_key.to_vec();
+        master_key.cipher()?.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(&header),
+            &mut wrapped,
+        )?;
+        header.extend_from_slice(&nonce);
+        header.extend_from_slice(&wrapped);
+
+        info!(
+            key_id,
+            key_version = master_key.version(),
+            "generated segment data key"
+        );
+
+        Ok(Self {
+            data_key: LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &data_key)?),
+            header,
+            rng,
+        })
+    }
+
+    /// size added to records by sealing
+    pub fn overhead(&self) -> usize {
+        self.header.len() + NONCE_LEN + TAG_LEN
+    }
+
+    /// seal records into envelope, `batch_aad` must be given again to open it
+    pub fn seal(&self, records: &[u8], batch_aad: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng.fill(&mut nonce)?;
+
+        let mut aad = Vec::with_capacity(self.header.len() + batch_aad.len());
+        aad.extend_from_slice(&self.header);
+        aad.extend_from_slice(batch_aad);
+
+        let mut sealed = records.to_vec();
+        self.data_key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(&aad),
+            &mut sealed,
+        )?;
+
+        let mut envelope = Vec::with_capacity(self.header.len() + NONCE_LEN + sealed.len());
+        envelope.extend_from_slice(&self.header);
+        envelope.extend_from_slice(&nonce);
+        envelope.extend_from_slice(&sealed);
+        Ok(envelope)
+    }
+}
+
+fn envelope_prefix_len(key_id: &str) -> usize {
+    size_of::<u8>() + size_of::<u16>() + key_id.len() + size_of::<u32>()
+}
+
+pub const BATCH_AAD_LEN: usize = size_of::<Offset>() + size_of::<i32>() + size_of::<i16>();
+
+/// Batch fields bound to the sealed records: base offset, last offset delta and
+/// attributes of records. Fields which change after the batch is stored are left out.
+pub fn batch_aad(base_offset: Offset, header: &BatchHeader) -> [u8; BATCH_AAD_LEN] {
+    let mut aad = [0u8; BATCH_AAD_LEN];
+    let (offset, rest) = aad.split_at_mut(size_of::<Offset>());
+    let (delta, attributes) = rest.split_at_mut(size_of::<i32>());
+    offset.copy_from_slice(&base_offset.to_be_bytes());
+    delta.copy_from_slice(&header.last_offset_delta.to_be_bytes());
+    attributes.copy_from_slice(&header.records_attributes().to_be_bytes());
+    aad
+}
+
+/// open envelope and return records, `batch_aad` must be the same as when sealed
+pub fn open_envelope(
+    provider: &dyn KeyProvider,
+    envelope: &[u8],
+    batch_aad: &[u8],
+) -> Result<Vec<u8>, EncryptionError> {
+    let invalid = |reason: &str| EncryptionError::InvalidEnvelope(reason.to_owned());
+
+    let (&version, rest) = envelope.split_first().ok_or_else(|| invalid("empty"))?;
+    if version != ENVELOPE_VERSION && version != UNBOUND_ENVELOPE_VERSION {
+        return Err(EncryptionError::InvalidEnvelope(format!(
+            "unknown version: {version}"
+        )));
+    }
+    if rest.len() < size_of::<u16>() {
+        return Err(invalid("missing key id"));
+    }
+    let (key_id_len, rest) = rest.split_at(size_of::<u16>());
+    let key_id_len = u16::from_be_bytes([key_id_len[0], key_id_len[1]]) as usize;
+    if rest.len() < key_id_len + size_of::<u32>() + WRAPPED_KEY_LEN + NONCE_LEN + TAG_LEN {
+        return Err(invalid("too short"));
+    }
+    let (key_id, rest) = rest.split_at(key_id_len);
+    let key_id = std::str::from_utf8(key_id).map_err(|_| invalid("key id is not utf8"))?;
+    let (key_version, rest) = rest.split_at(size_of::<u32>());
+    let key_version =
+        u32::from_be_bytes(key_version.try_into().map_err(|_| invalid("key version"))?);
+
+    let prefix_len = envelope_prefix_len(key_id);
+    let header_len = prefix_len + WRAPPED_KEY_LEN;
+
+    let (wrap_nonce, rest) = rest.split_at(NONCE_LEN);
+    let (wrapped, rest) = rest.split_at(KEY_LEN + TAG_LEN);
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+
+    let master_key = provider.key(key_id, key_version)?;
+    let mut data_key = wrapped.to_vec();
+    let data_key = master_key.cipher()?.open_in_place(
+        Nonce::try_assume_unique_for_key(wrap_nonce)?,
+        Aad::from(&envelope[..prefix_len]),
+        &mut data_key,
+    )?;
+    let data_key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, data_key)?);
+
+    let mut aad = envelope[..header_len].to_vec();
+    if version != UNBOUND_ENVELOPE_VERSION {
+        aad.extend_from_slice(batch_aad);
+    }
+
+    let mut records = sealed.to_vec();
+    let len = data_key
+        .open_in_place(
+            Nonce::try_assume_unique_for_key(nonce)?,
+            Aad::from(&aad),
+            &mut records,
+        )?
+        .len();
+    records.truncate(len);
+    Ok(records)
+}
+
+/// seal records of the batch, header is kept as it is except encryption flag
+pub fn encrypt_batch<R: BatchRecords>(
+    cipher: &SegmentCipher,
+    batch: &Batch<R>,
+) -> Result<Batch<RawRecords>, EncryptionError> {
+    let records_version = batch.records_version();
+    let mut records = Vec::with_capacity(batch.records().write_size(records_version));
+    batch.records().encode(&mut records, records_version)?;
+
+    let mut header = batch.get_header().clone();
+    header.set_encrypted(true);
+    header.set_record_headers(records_version >= RECORD_HEADERS_VERSION);
+    let envelope = cipher.seal(&records, &batch_aad(batch.base_offset, &header))?;
+    Ok(raw_batch(batch, header, envelope))
+}
+
+/// open records of encrypted batch, batches which are not encrypted are returned as they are
+pub fn decrypt_batch(
+    provider: &dyn KeyProvider,
+    batch: Batch<RawRecords>,
+) -> Result<Batch<RawRecords>, EncryptionError> {
+    if !batch.get_header().is_encrypted() {
+        return Ok(batch);
+    }
+
+    let records = open_envelope(
+        provider,
+        &batch.records().0,
+        &batch_aad(batch.base_offset, batch.get_header()),
+    )?;
+    let mut header = batch.get_header().clone();
+    header.set_encrypted(false);
+    Ok(raw_batch(&batch, header, records))
+}
+
+fn raw_batch<R>(batch: &Batch<R>, header: BatchHeader, records: Vec<u8>) -> Batch<RawRecords> {
+    let mut raw = Batch::<RawRecords>::new_with_len(
+        (BATCH_HEADER_SIZE + header.extensions_len() + records.len()) as i32,
+    );
+    raw.base_offset = batch.base_offset;
+    raw.header = header;
+    raw.schema_id = batch.schema_id();
+    if let Some(origin) = batch.mirror_origin() {
+        raw.set_mirror_origin(origin);
+    }
+    *raw.mut_records() = RawRecords(Bytes::from(records));
+    raw
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+    use std::fs;
+
+    use fluvio_protocol::record::{Batch, Record, RawRecords};
+    use fluvio_protocol::{Decoder, Encoder};
+
+    use super::*;
+
+    fn key_dir(name: &str) -> PathBuf {
+        let dir = temp_dir().join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("key dir");
+        dir
+    }
+
+    fn write_key(dir: &Path, key_id: &str, version: u32, byte: u8) {
+        fs::write(
+            dir.join(format!("{key_id}.{version}.key")),
+            hex::encode([byte; KEY_LEN]),
+        )
+        .expect("write key");
+    }
+
+    #[test]
+    fn test_file_key_provider_current_version() {
+        let dir = key_dir("encryption-current-key");
+        write_key(&dir, "topic-key", 1, 1);
+        write_key(&dir, "topic-key", 3, 3);
+        write_key(&dir, "other", 7, 7);
+
+        let provider = FileKeyProvider::new(&dir);
+        assert_eq!(provider.current_key("topic-key").expect("key").version(), 3);
+        assert_eq!(provider.key("topic-key", 1).expect("key").version(), 1);
+        assert!(matches!(
+            provider.key("topic-key", 2),
+            Err(EncryptionError::KeyNotFound { version: 2, .. })
+        ));
+        assert!(matches!(
+            provider.current_key("missing"),
+            Err(EncryptionError::NoKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_seal_and_open_envelope() {
+        let dir = key_dir("encryption-seal-open");
+        write_key(&dir, "topic-key", 1, 1);
+        let provider = FileKeyProvider::new(&dir);
+
+        let cipher = SegmentCipher::new(&provider, "topic-key").expect("cipher");
+        let envelope = cipher.seal(b"hello world", b"batch").expect("seal");
+        assert_eq!(envelope.len(), b"hello world".len() + cipher.overhead());
+        assert_eq!(
+            open_envelope(&provider, &envelope, b"batch").expect("open"),
+            b"hello world"
+        );
+
+        // rotated key doesn't affect existing envelopes
+        write_key(&dir, "topic-key", 2, 2);
+        assert_eq!(
+            open_envelope(&provider, &envelope, b"batch").expect("open"),
+            b"hello world"
+        );
+
+        let mut tampered = envelope.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0xff;
+        assert!(matches!(
+            open_envelope(&provider, &tampered, b"batch"),
+            Err(EncryptionError::Cipher)
+        ));
+
+        assert!(matches!(
+            open_envelope(&provider, &envelope, b"other batch"),
+            Err(EncryptionError::Cipher)
+        ));
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_batch() {
+        let dir = key_dir("encryption-batch");
+        write_key(&dir, "topic-key", 1, 9);
+        let provider = FileKeyProvider::new(&dir);
+        let cipher = SegmentCipher::new(&provider, "topic-key").expect("cipher");
+
+        let mut batch = Batch::from(vec![Record::new("record-1"), Record::new("record-2")]);
+        batch.set_base_offset(100);
+
+        let encrypted = encrypt_batch(&cipher, &batch).expect("encrypt");
+        assert!(encrypted.get_header().is_encrypted());
+        assert_eq!(encrypted.get_base_offset(), 100);
+        assert_eq!(encrypted.get_last_offset(), batch.get_last_offset());
+
+        // encrypted batch is still a valid batch on the wire
+        let bytes = encrypted.as_bytes(0).expect("encode");
+        let decoded =
+            Batch::<RawRecords>::decode_from(&mut std::io::Cursor::new(bytes), 0).expect("decode");
+        assert!(decoded.validate_decoding());
+
+        let decrypted = decrypt_batch(&provider, decoded).expect("decrypt");
+        assert!(!decrypted.get_header().is_encrypted());
+        let records = decrypted.memory_records().expect("records");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].value().as_ref(), b"record-2");
+    }
+
+    #[test]
+    fn test_encrypted_records_bound_to_batch() {
+        let dir = key_dir("encryption-batch-aad");
+        write_key(&dir, "topic-key", 1, 5);
+        let provider = FileKeyProvider::new(&dir);
+        let cipher = SegmentCipher::new(&provider, "topic-key").expect("cipher");
+
+        let mut batch = Batch::from(vec![Record::new("record-1"), Record::new("record-2")]);
+        batch.set_base_offset(100);
+        let encrypted = encrypt_batch(&cipher, &batch).expect("encrypt");
+
+        // envelope spliced into batch at another offset
+        let mut moved = encrypted.clone();
+        moved.set_base_offset(200);
+        assert!(matches!(
+            decrypt_batch(&provider, moved),
+            Err(EncryptionError::Cipher)
+        ));
+
+        // envelope claiming more records
+        let mut extended = encrypted.clone();
+        extended.header.last_offset_delta += 1;
+        assert!(matches!(
+            decrypt_batch(&provider, extended),
+            Err(EncryptionError::Cipher)
+        ));
+
+        // header fields which are not bound can still change
+        let mut replicated = encrypted;
+        replicated.header.partition_leader_epoch += 1;
+        assert!(decrypt_batch(&provider, replicated).is_ok());
+    }
+}
@@ -2,15 +2,22 @@ use std::collections::VecDeque;
 use std::os::fd::BorrowedFd;
 use std::os::unix::io::RawFd;
 use std::io::{Error as IoError, ErrorKind, Cursor};
+use std::sync::Arc;
 
+use bytes::Buf;
 use nix::sys::uio::pread;
 
+use fluvio_compression::Dictionary;
 use fluvio_protocol::types::Timestamp;
 use fluvio_protocol::{Decoder, Version};
 
-use fluvio_protocol::record::{Batch, Offset, BATCH_FILE_HEADER_SIZE, BATCH_HEADER_SIZE, Record};
+use fluvio_protocol::record::{
+    Batch, BatchHeader, Offset, RawRecords, BATCH_FILE_HEADER_SIZE, BATCH_HEADER_SIZE, Record,
+};
 use fluvio_future::file_slice::AsyncFileSlice;
 
+use crate::encryption::{batch_aad, decrypt_batch, open_envelope, EncryptionError, KeyProvider};
+
 // only encode information necessary to decode batches efficiently
 pub struct FileBatch {
     pub batch: Batch,
@@ -29,6 +36,8 @@ pub struct FileBatchIterator {
     fd: RawFd,
     offset: Offset,
     end: i64,
+    key_provider: Option<Arc<dyn KeyProvider>>,
+    dictionary: Option<Dictionary>,
 }
 
 impl FileBatchIterator {
@@ -38,6 +47,8 @@ impl FileBatchIterator {
             fd,
             offset,
             end: offset + len,
+            key_provider: None,
+            dictionary: None,
         }
     }
 
@@ -48,8 +59,22 @@ impl FileBatchIterator {
             fd: slice.as_raw_fd(),
             offset,
             end: offset + slice.len() as i64,
+            key_provider: None,
+            dictionary: None,
         }
     }
+
+    /// key provider used to open encrypted batches
+    pub fn with_key_provider(mut self, key_provider: Option<Arc<dyn KeyProvider>>) -> Self {
+        self.key_provider = key_provider;
+        self
+    }
+
+    /// zstd dictionary used to uncompress batches compressed with one
+    pub fn with_dictionary(mut self, dictionary: Option<Dictionary>) -> Self {
+        self.dictionary = dictionary;
+        self
+    }
 }
 
 impl Iterator for FileBatchIterator {
@@ -132,6 +157,43 @@ impl Iterator for FileBatchIterator {
             )));
         }
 
+        // schema id and mirror origin are between header and records
+        let extensions_len = batch.header.extensions_len();
+        if raw_records.len() < extensions_len {
+            return Some(Err(IoError::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "batch len {} is less than header extensions {}",
+                    raw_records.len(),
+                    extensions_len
+                ),
+            )));
+        }
+        let records = raw_records.split_off(extensions_len);
+        if let Err(err) = batch.decode_header_extensions(&mut Cursor::new(raw_records), 0) {
+            return Some(Err(IoError::other(format!(
+                "decoding batch header extensions error {err}"
+            ))));
+        }
+        let raw_records = records;
+
+        let raw_records = if batch.header.is_encrypted() {
+            let opened = match &self.key_provider {
+                Some(provider) => open_envelope(
+                    provider.as_ref(),
+                    &raw_records,
+                    &batch_aad(batch.base_offset, &batch.header),
+                ),
+                None => Err(EncryptionError::NoKeyProvider),
+            };
+            match opened {
+                Ok(records) => records,
+                Err(err) => return Some(Err(IoError::other(format!("decrypt error {err}")))),
+            }
+        } else {
+            raw_records
+        };
+
         let compression = match batch.get_compression() {
             Ok(compression) => compression,
             Err(err) => {
@@ -141,7 +203,7 @@ impl Iterator for FileBatchIterator {
             }
         };
 
-        let records = match compression.uncompress(&raw_records) {
+        let records = match compression.uncompress_with(&raw_records, self.dictionary.as_ref()) {
             Ok(Some(records)) => records,
             Ok(None) => raw_records,
             Err(err) => return Some(Err(IoError::other(format!("uncompress error {err}")))),
@@ -149,10 +211,145 @@ impl Iterator for FileBatchIterator {
 
         self.offset += bytes_read as i64;
 
+        batch.header.set_encrypted(false);
         Some(Ok(FileBatch { batch, records }))
     }
 }
 
+/// Read batches of the slice into memory.
+/// Encrypted batches are opened when key provider is present, otherwise reading them is an error.
+/// Unlike [`FileBatchIterator`], records are kept compressed so they can be sent to consumers as they are
+pub fn read_batches(
+    slice: &AsyncFileSlice,
+    key_provider: Option<&dyn KeyProvider>,
+) -> Result<Vec<Batch<RawRecords>>, IoError> {
+    read_batches_up_to(slice, key_provider, usize::MAX)
+}
+
+/// Like [`read_batches`], reading up to `max_bytes` but always the first batch
+pub fn read_batches_up_to(
+    slice: &AsyncFileSlice,
+    key_provider: Option<&dyn KeyProvider>,
+    max_bytes: usize,
+) -> Result<Vec<Batch<RawRecords>>, IoError> {
+    use std::os::unix::io::AsRawFd;
+
+    let fd = unsafe { BorrowedFd::borrow_raw(slice.as_raw_fd()) };
+    let len = bounded_batches_len(fd, slice, max_bytes)?;
+
+    let offset = slice.position() as i64;
+
+    // ugly hack for armv7 pread offset = i32
+    // needed for gnu but not zig musl
+    #[cfg(all(target_pointer_width = "32", target_env = "gnu"))]
+    let offset: i32 = offset.try_into().unwrap();
+
+    let mut buf = vec![0u8; len];
+    let bytes_read =
+        pread(fd, &mut buf, offset).map_err(|err| IoError::other(format!("pread error {err}")))?;
+    buf.truncate(bytes_read);
+
+    let mut src = Cursor::new(buf);
+    let mut batches = vec![];
+    while src.remaining() >= BATCH_FILE_HEADER_SIZE {
+        let batch = Batch::<RawRecords>::decode_from(&mut src, 0)?;
+        // slice may end in the middle of batch
+        if !batch.validate_decoding() {
+            break;
+        }
+        let batch = match key_provider {
+            Some(key_provider) => decrypt_batch(key_provider, batch)
+                .map_err(|err| IoError::other(format!("decrypt error {err}")))?,
+            None if batch.get_header().is_encrypted() => {
+                return Err(IoError::other(format!(
+                    "decrypt error {}",
+                    EncryptionError::NoKeyProvider
+                )));
+            }
+            None => batch,
+        };
+        batches.push(batch);
+    }
+    Ok(batches)
+}
+
+/// Whether header of any batch read by [`read_batches_up_to`] with `max_bytes` matches `predicate`.
+/// Only batch headers are read, records are skipped
+pub fn any_batch_header(
+    slice: &AsyncFileSlice,
+    max_bytes: usize,
+    predicate: impl Fn(&BatchHeader) -> bool,
+) -> Result<bool, IoError> {
+    use std::os::unix::io::AsRawFd;
+
+    let fd = unsafe { BorrowedFd::borrow_raw(slice.as_raw_fd()) };
+    let slice_len = bounded_batches_len(fd, slice, max_bytes)?;
+
+    let mut len = 0;
+    let mut header = vec![0u8; BATCH_FILE_HEADER_SIZE];
+    while len + BATCH_FILE_HEADER_SIZE <= slice_len {
+        let offset = slice.position() as i64 + len as i64;
+
+        // ugly hack for armv7 pread offset = i32
+        // needed for gnu but not zig musl
+        #[cfg(all(target_pointer_width = "32", target_env = "gnu"))]
+        let offset: i32 = offset.try_into().unwrap();
+
+        let bytes_read = pread(fd, &mut header, offset)
+            .map_err(|err| IoError::other(format!("pread error {err}")))?;
+        if bytes_read < header.len() {
+            break;
+        }
+
+        let mut batch: Batch = Batch::default();
+        batch.decode_from_file_buf(&mut Cursor::new(&header), 0)?;
+        if predicate(batch.get_header()) {
+            return Ok(true);
+        }
+        len += BATCH_FILE_HEADER_SIZE + batch.batch_len as usize - BATCH_HEADER_SIZE;
+    }
+    Ok(false)
+}
+
+/// Length of the whole batches at the start of the slice which fit into `max_bytes`.
+/// First batch is always included, so readers make progress on batches larger than `max_bytes`
+fn bounded_batches_len(
+    fd: BorrowedFd<'_>,
+    slice: &AsyncFileSlice,
+    max_bytes: usize,
+) -> Result<usize, IoError> {
+    let slice_len = slice.len() as usize;
+    if slice_len <= max_bytes {
+        return Ok(slice_len);
+    }
+
+    let mut len = 0;
+    let mut header = vec![0u8; BATCH_FILE_HEADER_SIZE];
+    while len + BATCH_FILE_HEADER_SIZE <= slice_len {
+        let offset = slice.position() as i64 + len as i64;
+
+        // ugly hack for armv7 pread offset = i32
+        // needed for gnu but not zig musl
+        #[cfg(all(target_pointer_width = "32", target_env = "gnu"))]
+        let offset: i32 = offset.try_into().unwrap();
+
+        let bytes_read = pread(fd, &mut header, offset)
+            .map_err(|err| IoError::other(format!("pread error {err}")))?;
+        if bytes_read < header.len() {
+            break;
+        }
+
+        let mut batch: Batch = Batch::default();
+        batch.decode_from_file_buf(&mut Cursor::new(&header), 0)?;
+        let batch_len = BATCH_FILE_HEADER_SIZE + batch.batch_len as usize - BATCH_HEADER_SIZE;
+        if len + batch_len > slice_len || (len > 0 && len + batch_len > max_bytes) {
+            break;
+        }
+        len += batch_len;
+    }
+    Ok(len)
+}
+
 /// Iterator that converts an iterator over file batches to an iterator over record items.
 /// RecordItem is a record with resolved offset and timestamp.
 pub struct FileRecordIterator<T: Iterator<Item = Result<FileBatch, IoError>>> {
@@ -185,12 +382,14 @@ impl<T: Iterator<Item = Result<FileBatch, IoError>>> Iterator for FileRecordIter
 
                 let base_offset = next_batch.batch.base_offset;
                 let base_timestamp = next_batch.batch.header.first_timestamp;
+                // records of batches with headers must be decoded with their version
+                let version = self.version.max(next_batch.batch.header.records_version());
 
                 let mut records: Vec<Record> = vec![];
                 if let Err(err) = Decoder::decode(
                     &mut records,
                     &mut std::io::Cursor::new(next_batch.records),
-                    self.version,
+                    version,
                 ) {
                     return Some(Err(err));
                 }
@@ -326,4 +525,119 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_batches_max_bytes() -> anyhow::Result<()> {
+        //given
+        let base_dir = temp_dir().join("test_read_batches_max_bytes");
+        let mut replica = run_block_on(FileReplica::create_or_load_inner(
+            format!(
+                "test_read_batches_max_bytes_{}",
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)?
+                    .as_millis()
+            ),
+            Default::default(),
+            Default::default(),
+            ReplicaConfigBuilder::default().base_dir(base_dir).build(),
+            Arc::new(StorageConfigBuilder::default().build()?),
+        ))?;
+
+        let mut batches = vec![];
+        for base_offset in 0..3 {
+            let mut batch = Batch::default();
+            batch.base_offset = base_offset;
+            batch.add_record(Record::new(vec![0u8; 100]));
+            batches.push(batch);
+        }
+        let mut records = RecordSet { batches };
+        run_block_on(replica.write_recordset(&mut records, false))?;
+
+        let slice = run_block_on(replica.read_partition_slice(
+            0,
+            u32::MAX,
+            fluvio_spu_schema::Isolation::ReadUncommitted,
+        ))?;
+        let file_slice = slice
+            .file_slice
+            .ok_or_else(|| anyhow::anyhow!("expected file slice"))?;
+        let batch_len = file_slice.len() as usize / 3;
+
+        //then
+        assert_eq!(read_batches_up_to(&file_slice, None, usize::MAX)?.len(), 3);
+        assert_eq!(
+            read_batches_up_to(&file_slice, None, 2 * batch_len + 1)?.len(),
+            2
+        );
+        assert_eq!(
+            read_batches_up_to(&file_slice, None, 1)?.len(),
+            1,
+            "first batch is read even if larger than max bytes"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_headers_in_slice() -> anyhow::Result<()> {
+        //given
+        let base_dir = temp_dir().join("test_record_headers_in_slice");
+        let mut replica = run_block_on(FileReplica::create_or_load_inner(
+            format!(
+                "test_record_headers_in_slice_{}",
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)?
+                    .as_millis()
+            ),
+            Default::default(),
+            Default::default(),
+            ReplicaConfigBuilder::default().base_dir(base_dir).build(),
+            Arc::new(StorageConfigBuilder::default().build()?),
+        ))?;
+
+        let mut batch1 = Batch::default();
+        batch1.add_record(Record::new("plain"));
+
+        let mut batch2 = Batch::default();
+        batch2.base_offset = 1;
+        let mut record = Record::new("traced");
+        record.headers_mut().insert("traceparent", "abc");
+        batch2.add_record(record);
+
+        let mut records = RecordSet {
+            batches: vec![batch1, batch2],
+        };
+        run_block_on(replica.write_recordset(&mut records, false))?;
+
+        //when
+        let slice = run_block_on(replica.read_partition_slice(
+            0,
+            u32::MAX,
+            fluvio_spu_schema::Isolation::ReadUncommitted,
+        ))?;
+        let file_slice = slice
+            .file_slice
+            .ok_or_else(|| anyhow::anyhow!("expected file slice"))?;
+
+        //then
+        assert!(any_batch_header(&file_slice, usize::MAX, |header| {
+            header.has_record_headers()
+        })?);
+        assert!(
+            !any_batch_header(&file_slice, 1, |header| header.has_record_headers())?,
+            "only first batch is read with 1 max byte"
+        );
+
+        let record_iter = FileRecordIterator::new(FileBatchIterator::from_raw_slice(file_slice), 0);
+        let records: Vec<RecordItem> =
+            record_iter.collect::<Result<Vec<RecordItem>, std::io::Error>>()?;
+        assert_eq!(records.len(), 2);
+        assert!(records[0].record.headers().is_empty());
+        assert_eq!(
+            records[1].record.headers().get("traceparent"),
+            Some(b"abc".as_slice())
+        );
+
+        Ok(())
+    }
 }
@@ -13,6 +13,7 @@ mod util;
 mod validator;
 mod file;
 pub mod config;
+pub mod encryption;
 #[cfg(feature = "iterators")]
 pub mod iterators;
 
@@ -29,6 +30,8 @@ pub use crate::replica::FileReplica;
 pub use inner::*;
 mod inner {
 
+    use std::sync::Arc;
+
     use async_trait::async_trait;
     use anyhow::Result;
 
@@ -40,6 +43,9 @@ mod inner {
     use fluvio_future::file_slice::AsyncFileSlice;
     use fluvio_controlplane::replica::Replica;
 
+    use crate::checkpoint::CheckPoint;
+    use crate::encryption::KeyProvider;
+
     #[derive(Debug, Clone, Eq, PartialEq)]
     pub struct OffsetInfo {
         pub hw: Offset,
@@ -156,6 +162,11 @@ mod inner {
 
         fn get_partition_size(&self) -> Size64;
 
+        /// number of segments, including active segment
+        fn get_segment_count(&self) -> u32 {
+            1
+        }
+
         /// write record set
         async fn write_recordset<R: BatchRecords>(
             &mut self,
@@ -165,8 +176,31 @@ mod inner {
 
         async fn update_high_watermark(&mut self, offset: Offset) -> Result<bool, StorageError>;
 
+        /// remove records from the batch containing offset up to the end of the log.
+        /// returns new log end offset, which can be less than offset
+        async fn truncate(&mut self, offset: Offset) -> Result<Offset>;
+
         /// permanently remove
         async fn remove(&self) -> Result<(), StorageError>;
+
+        /// true if new batches are sealed with encryption key of the topic
+        fn is_encrypted(&self) -> bool {
+            false
+        }
+
+        /// key provider to open sealed batches
+        fn key_provider(&self) -> Option<Arc<dyn KeyProvider>> {
+            None
+        }
+
+        /// offset checkpoint kept along with replica, None if storage can't persist one
+        async fn checkpoint(
+            &self,
+            _name: &str,
+            _initial_offset: Offset,
+        ) -> Result<Option<CheckPoint>> {
+            Ok(None)
+        }
     }
 
     #[cfg(test)]
@@ -143,6 +143,25 @@ impl MutLogIndex {
         self.file.set_len(target_len).await
     }
 
+    /// drop entries at or beyond file position, log was cut at that position
+    pub(crate) fn truncate(&mut self, file_position: Size) {
+        while self.first_empty_slot > 0 {
+            let last_slot = self.first_empty_slot as usize - 1;
+            if self[last_slot].to_be().position() < file_position {
+                break;
+            }
+            self[last_slot] = (0, 0);
+            self.first_empty_slot -= 1;
+        }
+        self.accumulated_batch_len = 0;
+        self.last_offset_delta = 0;
+        debug!(
+            file_position,
+            first_empty_slot = self.first_empty_slot,
+            "truncated index"
+        );
+    }
+
     #[inline]
     pub fn ptr(&self) -> *const (Size, Size) {
         self.ptr as *const (Size, Size)
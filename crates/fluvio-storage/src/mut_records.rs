@@ -26,6 +26,7 @@ use fluvio_protocol::record::{Offset, Size, Size64};
 use fluvio_protocol::Encoder;
 
 use crate::config::SharedReplicaConfig;
+use crate::encryption::{encrypt_batch, EncryptionError, SegmentCipher};
 use crate::mut_index::MutLogIndex;
 use crate::util::generate_file_name;
 use crate::validator::LogValidationError;
@@ -52,6 +53,7 @@ pub struct MutFileRecords {
     flush_count: Arc<AtomicU32>,
     path: PathBuf,
     _flush_time_tx: Option<Sender<Instant>>,
+    cipher: Option<SegmentCipher>,
 }
 
 impl fmt::Debug for MutFileRecords {
@@ -92,6 +94,7 @@ impl MutFileRecords {
         let metadata = file.metadata().await?;
         let len = metadata.len() as u32;
         info!(len, "log file created");
+        let cipher = segment_cipher(&option).map_err(IoError::other)?;
         Ok(MutFileRecords {
             base_offset,
             file,
@@ -102,6 +105,7 @@ impl MutFileRecords {
             flush_count: Arc::new(AtomicU32::new(0)),
             path: log_path.to_owned(),
             _flush_time_tx: None,
+            cipher,
         })
     }
 
@@ -141,7 +145,18 @@ impl MutFileRecords {
             .into());
         }
 
-        let batch_len = batch.write_size(0);
+        // batches replicated from leader are already sealed
+        let buffer = match &self.cipher {
+            Some(cipher) if !batch.get_header().is_encrypted() => {
+                encrypt_batch(cipher, batch)?.as_bytes(0)?.to_vec()
+            }
+            _ => {
+                let mut buffer: Vec<u8> = Vec::with_capacity(batch.write_size(0));
+                batch.encode(&mut buffer, 0)?;
+                buffer
+            }
+        };
+        let batch_len = buffer.len();
         debug!(batch_len, "writing batch of size",);
 
         if (batch_len as u32 + self.len) > self.max_len {
@@ -149,10 +164,6 @@ impl MutFileRecords {
             return Ok((false, batch_len, self.len));
         }
 
-        let mut buffer: Vec<u8> = Vec::with_capacity(batch_len);
-        batch.encode(&mut buffer, 0)?;
-        assert_eq!(buffer.len(), batch_len);
-
         let raw_fd = self.file.as_raw_fd();
         let mut std_file = unsafe { std::fs::File::from_raw_fd(raw_fd) };
         if let Err(err) = std_file.write_all(&buffer) {
@@ -188,6 +199,18 @@ impl MutFileRecords {
     }
 }
 
+/// cipher for new segment if replica is encrypted
+fn segment_cipher(option: &SharedReplicaConfig) -> Result<Option<SegmentCipher>, EncryptionError> {
+    let Some(key_id) = &option.encryption_key_id else {
+        return Ok(None);
+    };
+    let provider = option
+        .key_provider
+        .as_ref()
+        .ok_or(EncryptionError::NoKeyProvider)?;
+    SegmentCipher::new(provider.as_ref(), key_id).map(Some)
+}
+
 impl FileRecords for MutFileRecords {
     fn get_base_offset(&self) -> Offset {
         self.base_offset
@@ -276,13 +299,14 @@ mod tests {
 
     use fluvio_protocol::record::Offset;
     use flv_util::fixture::{ensure_new_dir};
-    use fluvio_protocol::record::{Batch, MemoryRecords};
+    use fluvio_protocol::record::{Batch, MemoryRecords, RawRecords};
     use fluvio_protocol::{Decoder, Encoder};
     use fluvio_protocol::fixture::read_bytes_from_file;
 
     use crate::config::ReplicaConfig;
     use crate::records::FileRecords;
     use crate::fixture::BatchProducer;
+    use crate::encryption::decrypt_batch;
     use super::MutFileRecords;
 
     #[fluvio_future::test]
@@ -383,6 +407,50 @@ mod tests {
         assert_eq!(old_msg_sink.get_pos() as usize, write_size * 2);
     }
 
+    #[fluvio_future::test]
+    async fn test_write_encrypted_records() {
+        const BASE_OFFSET: Offset = 100;
+
+        let test_dir = temp_dir().join("write_encrypted_records");
+        ensure_new_dir(&test_dir).expect("new");
+        let key_dir = test_dir.join("keys");
+        ensure_new_dir(&key_dir).expect("new");
+        std::fs::write(key_dir.join("test-key.1.key"), "01".repeat(32)).expect("key");
+
+        let options = ReplicaConfig {
+            base_dir: test_dir,
+            segment_max_bytes: 1000,
+            encryption_key_dir: Some(key_dir),
+            encryption_key_id: Some("test-key".to_owned()),
+            ..Default::default()
+        }
+        .shared();
+        let mut msg_sink = MutFileRecords::create(BASE_OFFSET, options.clone())
+            .await
+            .expect("create");
+
+        let mut builder = BatchProducer::builder()
+            .base_offset(BASE_OFFSET)
+            .build()
+            .expect("build");
+
+        let batch = builder.batch();
+        let (_, write_size, _) = msg_sink.write_batch(&batch).await.expect("write");
+        assert!(write_size > batch.write_size(0));
+
+        let bytes = read_bytes_from_file(msg_sink.get_path()).expect("read bytes");
+        assert_eq!(bytes.len(), write_size);
+        let sealed = Batch::<RawRecords>::decode_from(&mut Cursor::new(bytes), 0).expect("decode");
+        assert!(sealed.get_header().is_encrypted());
+        assert_eq!(sealed.get_last_offset(), batch.get_last_offset());
+
+        let key_provider = options.key_provider.as_ref().expect("provider");
+        let opened = decrypt_batch(key_provider.as_ref(), sealed).expect("decrypt");
+        let records = opened.memory_records().expect("records");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].value.as_ref(), vec![10, 20]);
+    }
+
     // This Test configures policy to flush after every NUM_WRITES
     // and checks to see when the flush occurs relative to the write count
 
@@ -24,6 +24,7 @@ use crate::config::{ReplicaConfig, SharedReplicaConfig, StorageConfig};
 use crate::ReplicaSlice;
 use crate::{StorageError, ReplicaStorage};
 use crate::cleaner::Cleaner;
+use crate::encryption::KeyProvider;
 
 /// Replica is public abstraction for commit log which are distributed.
 /// Internally it is stored as list of segments.  Each segment contains finite sets of record batches.
@@ -127,6 +128,10 @@ impl ReplicaStorage for FileReplica {
         total_prev_segments_len + active_len
     }
 
+    fn get_segment_count(&self) -> u32 {
+        self.prev_segments.count() as u32 + 1
+    }
+
     /// write records to this replica
     /// if update_highwatermark is set, set high watermark is end
     //  this is used when LRS = 1
@@ -184,6 +189,43 @@ impl ReplicaStorage for FileReplica {
         }
     }
 
+    /// drop segments above offset, then cut active segment
+    #[instrument(skip(self))]
+    async fn truncate(&mut self, offset: Offset) -> Result<Offset> {
+        if offset >= self.get_leo() {
+            return Ok(self.get_leo());
+        }
+        while offset < self.active_segment.get_base_offset() && self.prev_segments.count() > 0 {
+            let Some(prev_segment) = self.prev_segments.take_last_segment().await else {
+                break;
+            };
+            let base_offset = prev_segment.get_base_offset();
+            self.size.sub_prev(prev_segment.occupied_memory());
+            drop(prev_segment);
+            let mut segment =
+                MutableSegment::open_for_write(base_offset, self.option.clone()).await?;
+            segment.validate_and_repair().await?;
+            let old_segment = mem::replace(&mut self.active_segment, segment);
+            old_segment.remove().await?;
+        }
+        self.active_segment.truncate(offset).await?;
+        self.size
+            .store_active(self.active_segment.occupied_memory());
+        self.short_circuit = false;
+
+        let leo = self.get_leo();
+        if self.get_hw() > leo {
+            self.commit_checkpoint.write(leo);
+        }
+        info!(offset, leo, hw = self.get_hw(), "replica truncated");
+        Ok(leo)
+    }
+
+    async fn checkpoint(&self, name: &str, initial_offset: Offset) -> Result<Option<CheckPoint>> {
+        let checkpoint = CheckPoint::create(self.option.clone(), name, initial_offset).await?;
+        Ok(Some(checkpoint))
+    }
+
     #[instrument(skip(self))]
     async fn remove(&self) -> Result<(), StorageError> {
         remove_dir_all(&self.option.base_dir)
@@ -193,6 +235,14 @@ impl ReplicaStorage for FileReplica {
         self.cleaner.shutdown();
         Ok(())
     }
+
+    fn is_encrypted(&self) -> bool {
+        self.option.encryption_key_id.is_some()
+    }
+
+    fn key_provider(&self) -> Option<Arc<dyn KeyProvider>> {
+        self.option.key_provider.clone()
+    }
 }
 
 impl FileReplica {
@@ -496,6 +546,11 @@ impl ReplicaSize {
             .fetch_add(prev_segment, Ordering::Release);
     }
 
+    fn sub_prev(&self, prev_segment: Size64) {
+        self.prev_segments
+            .fetch_sub(prev_segment, Ordering::Release);
+    }
+
     fn get_prev(&self) -> Size64 {
         self.prev_segments.load(Ordering::Acquire)
     }
@@ -769,6 +824,76 @@ mod tests {
         assert_eq!(seg1_metadata.len(), 8);
     }
 
+    #[fluvio_future::test]
+    async fn test_replica_truncate() {
+        let option = base_option("test_truncate");
+        let mut replica = create_replica("test", START_OFFSET, option.clone()).await;
+
+        let mut records = RecordSet::default().add(create_batch()).add(create_batch());
+        replica
+            .write_recordset(&mut records, true)
+            .await
+            .expect("write");
+        assert_eq!(replica.get_leo(), START_OFFSET + 4);
+        assert_eq!(replica.get_hw(), START_OFFSET + 4);
+
+        // offset in the middle of second batch, whole batch is removed
+        let leo = replica.truncate(START_OFFSET + 3).await.expect("truncate");
+        assert_eq!(leo, START_OFFSET + 2);
+        assert_eq!(replica.get_leo(), START_OFFSET + 2);
+        assert_eq!(replica.get_hw(), START_OFFSET + 2);
+
+        // beyond leo is no op
+        let leo = replica.truncate(START_OFFSET + 10).await.expect("truncate");
+        assert_eq!(leo, START_OFFSET + 2);
+
+        replica
+            .write_batch(&mut create_batch())
+            .await
+            .expect("write");
+        assert_eq!(replica.get_leo(), START_OFFSET + 4);
+        drop(replica);
+
+        let replica = create_replica("test", START_OFFSET, option).await;
+        assert_eq!(replica.get_leo(), START_OFFSET + 4);
+        assert_eq!(replica.size.get(), 158);
+    }
+
+    #[fluvio_future::test]
+    async fn test_replica_truncate_across_segments() {
+        let option = rollover_option("test_truncate_segments");
+        let mut replica = create_replica("test", START_OFFSET, option.clone()).await;
+
+        for _ in 0..3 {
+            replica
+                .write_batch(&mut create_batch())
+                .await
+                .expect("write");
+        }
+        assert_eq!(replica.get_leo(), START_OFFSET + 6);
+        assert_eq!(replica.get_segment_count(), 3);
+
+        let leo = replica.truncate(START_OFFSET + 3).await.expect("truncate");
+        assert_eq!(leo, START_OFFSET + 2);
+        assert_eq!(replica.get_segment_count(), 2);
+        assert_eq!(replica.active_segment.get_base_offset(), START_OFFSET + 2);
+        assert_eq!(replica.get_log_start_offset(), START_OFFSET);
+
+        let replica_dir = &option.base_dir.join("test-0");
+        assert!(!replica_dir.join("00000000000000000024.log").exists());
+        assert!(!replica_dir.join("00000000000000000024.index").exists());
+
+        replica
+            .write_batch(&mut create_batch())
+            .await
+            .expect("write");
+        assert_eq!(replica.get_leo(), START_OFFSET + 4);
+        drop(replica);
+
+        let replica = create_replica("test", START_OFFSET, option).await;
+        assert_eq!(replica.get_leo(), START_OFFSET + 4);
+    }
+
     #[fluvio_future::test]
     async fn test_replica_commit() {
         let option = base_option("test_commit");
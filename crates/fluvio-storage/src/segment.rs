@@ -14,7 +14,7 @@ use fluvio_protocol::record::{Offset, Size, Size64};
 use fluvio_protocol::link::ErrorCode;
 
 use crate::batch_header::{BatchHeaderStream, FileEmptyRecords};
-use crate::mut_index::MutLogIndex;
+use crate::mut_index::{MutLogIndex, EXTENSION as INDEX_EXTENSION};
 use crate::index::LogIndex;
 use crate::index::Index;
 use crate::records::FileRecords;
@@ -25,6 +25,7 @@ use crate::StorageError;
 use crate::batch::FileBatchStream;
 use crate::index::OffsetPosition;
 use crate::validator::LogValidationError;
+use crate::util::generate_file_name;
 
 pub type MutableSegment = Segment<MutLogIndex, MutFileRecords>;
 pub type ReadSegment = Segment<LogIndex, FileRecordsSlice>;
@@ -458,6 +459,40 @@ impl Segment<MutLogIndex, MutFileRecords> {
     pub async fn flush(&mut self) -> Result<(), StorageError> {
         self.msg_log.flush().await.map_err(|err| err.into())
     }
+
+    /// remove batches starting with the one containing offset.
+    /// end offset becomes base offset of that batch
+    #[instrument(skip(self))]
+    pub async fn truncate(&mut self, offset: Offset) -> Result<()> {
+        let offset = offset.max(self.base_offset);
+        let Some(BatchPosition { batch, pos }) = self.find_offset_position(offset).await? else {
+            debug!(offset, end_offset = self.end_offset, "nothing to truncate");
+            return Ok(());
+        };
+        info!(
+            base_offset = self.base_offset,
+            end_offset = self.end_offset,
+            new_end_offset = batch.base_offset,
+            pos,
+            "truncating active segment"
+        );
+        self.msg_log.set_len(pos).await?;
+        self.index.truncate(pos);
+        self.end_offset = batch.base_offset;
+        Ok(())
+    }
+
+    /// permanently remove segment files
+    pub(crate) async fn remove(self) -> Result<(), StorageError> {
+        let log_path = self.msg_log.get_path().to_owned();
+        let index_path =
+            generate_file_name(&self.option.base_dir, self.base_offset, INDEX_EXTENSION);
+        drop(self);
+        info!(log_path = %log_path.display(), "removing active segment");
+        remove_file(&log_path).await?;
+        remove_file(&index_path).await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
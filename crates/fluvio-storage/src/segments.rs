@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 use std::ops::Bound::Included;
 use std::ffi::OsStr;
 use std::sync::Arc;
-use std::sync::atomic::AtomicI64;
+use std::sync::atomic::{AtomicI64, AtomicUsize};
 use std::time::Duration;
 
 use async_lock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
@@ -26,14 +26,17 @@ const MEM_ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqC
 pub(crate) struct SharedSegments {
     inner: Arc<RwLock<SegmentList>>,
     min_offset: AtomicI64,
+    count: AtomicUsize,
 }
 
 impl SharedSegments {
     pub(crate) fn from(list: SegmentList) -> Arc<Self> {
         let min = list.min_offset;
+        let count = list.len();
         Arc::new(Self {
             inner: Arc::new(RwLock::new(list)),
             min_offset: AtomicI64::new(min),
+            count: AtomicUsize::new(count),
         })
     }
 
@@ -104,10 +107,16 @@ impl SharedSegments {
         self.min_offset.load(MEM_ORDER)
     }
 
+    /// number of segments in the list
+    pub fn count(&self) -> usize {
+        self.count.load(MEM_ORDER)
+    }
+
     pub async fn add_segment(&self, segment: ReadSegment) {
         let mut writer = self.write().await;
         let min_offset = writer.add_segment(segment);
         self.min_offset.store(min_offset, MEM_ORDER);
+        self.count.store(writer.len(), MEM_ORDER);
     }
 
     #[instrument(skip(self))]
@@ -118,6 +127,16 @@ impl SharedSegments {
         }
     }
 
+    /// remove last segment from the list without removing its files
+    pub(crate) async fn take_last_segment(&self) -> Option<ReadSegment> {
+        let mut write = self.write().await;
+        let base_offset = *write.segments.keys().next_back()?;
+        let (segment, min_offset) = write.remove_segment(&base_offset)?;
+        self.count.store(write.len(), MEM_ORDER);
+        self.min_offset.store(min_offset, MEM_ORDER);
+        Some(segment)
+    }
+
     /// find slice in the segments
     /// if not found, return OutOfRange error
     pub async fn find_slice(
@@ -144,6 +163,7 @@ impl SharedSegments {
         let mut write = self.write().await;
 
         if let Some((old_segment, min_offset)) = write.remove_segment(base_offset) {
+            self.count.store(write.len(), MEM_ORDER);
             drop(write);
             self.min_offset.store(min_offset, MEM_ORDER);
             if let Err(err) = old_segment.remove().await {
@@ -3,10 +3,12 @@ use std::fmt;
 use std::fmt::Debug;
 use std::io::Error as IoError;
 use std::io::ErrorKind;
+use std::sync::Arc;
 
 use fluvio_future::task::spawn;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_stream_model::store::NameSpace;
+use fluvio_types::event::StickyEvent;
 use futures_util::stream::StreamExt;
 use tracing::debug;
 use tracing::error;
@@ -78,6 +80,29 @@ where
         spawn(dispatcher.outer_loop())
     }
 
+    /// start dispatcher which stops once `shutdown` is notified
+    pub fn start_with_shutdown(
+        namespace: impl Into<NameSpace>,
+        client: SharedClient<C>,
+        ctx: StoreContext<S, M>,
+        shutdown: Arc<StickyEvent>,
+    ) {
+        use tokio::select;
+
+        let dispatcher = Self {
+            namespace: namespace.into(),
+            client,
+            ctx,
+        };
+
+        spawn(async move {
+            select! {
+                _ = dispatcher.outer_loop() => {},
+                _ = shutdown.listen() => debug!(spec = S::LABEL, "dispatcher shutdown"),
+            }
+        });
+    }
+
     #[instrument(
         name = "MetadataDispatcher",
         skip(self),
@@ -94,6 +94,7 @@ impl LocalMetadataItem {
 cfg_if::cfg_if! {
     if #[cfg(feature = "k8")] {
         use std::{
+            collections::HashSet,
             path::{Path, PathBuf},
             sync::{Arc, atomic::AtomicU64},
             any::Any,
@@ -104,6 +105,7 @@ cfg_if::cfg_if! {
         use async_channel::{Sender, Receiver, bounded};
         use parking_lot::RwLock;
         use futures_util::{stream::BoxStream, StreamExt};
+        use once_cell::sync::OnceCell;
         use serde::{de::DeserializeOwned};
         use tracing::{warn, debug, trace};
 
@@ -117,13 +119,78 @@ cfg_if::cfg_if! {
         use super::MetadataClient;
 
         const MAX_UPDATES_CAPACITY: usize = 100;
+        const SPEC_FILE_EXTENSION: &str = "yaml";
+
         #[derive(Debug)]
         pub struct LocalMetadataStorage {
             path: PathBuf,
             stores: RwLock<HashMap<&'static str, Arc<SpecStore>>>,
+            journal: OnceCell<Arc<dyn MetadataJournal>>,
         }
         pub type LocalStoreObject<S> = MetadataStoreObject<S, LocalMetadataItem>;
 
+        /// Write of a single spec file. Writes are replicated in this form
+        /// so replicas can apply them without knowing the spec type.
+        #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+        pub enum StoreOp {
+            Put {
+                kind: String,
+                name: String,
+                content: String,
+                /// revision stored spec must have when write is applied, write is rejected otherwise.
+                /// Not checked if not set.
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                expected_revision: Option<u64>,
+            },
+            Delete {
+                kind: String,
+                name: String,
+            },
+        }
+
+        impl StoreOp {
+            fn kind(&self) -> &str {
+                match self {
+                    Self::Put { kind, .. } | Self::Delete { kind, .. } => kind,
+                }
+            }
+
+            fn name(&self) -> &str {
+                match self {
+                    Self::Put { name, .. } | Self::Delete { name, .. } => name,
+                }
+            }
+        }
+
+        /// Put rejected because spec was changed after write was proposed
+        #[derive(Debug)]
+        pub struct RevisionConflict {
+            kind: String,
+            name: String,
+            expected: u64,
+            current: Option<u64>,
+        }
+
+        impl std::fmt::Display for RevisionConflict {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(
+                    f,
+                    "conflicting update of {} '{}': expected revision: {}, current: {:?}",
+                    self.kind, self.name, self.expected, self.current
+                )
+            }
+        }
+
+        impl std::error::Error for RevisionConflict {}
+
+        /// Orders writes of local storage.
+        /// Once journal is set, writes are only applied when journal passes them back to [`LocalMetadataStorage::apply_op`].
+        #[async_trait::async_trait]
+        pub trait MetadataJournal: std::fmt::Debug + Send + Sync {
+            /// returns after op has been applied to local storage
+            async fn append(&self, op: StoreOp) -> Result<()>;
+        }
+
         #[async_trait::async_trait]
         impl MetadataClient<LocalMetadataItem> for LocalMetadataStorage {
             async fn retrieve_items<S>(
@@ -148,7 +215,7 @@ cfg_if::cfg_if! {
                         self.unlink_parent::<S>(owner, item.ctx().item()).await?;
                     }
                     self.delete_children(item).await?;
-                    store.delete_item(&metadata).await
+                    store.delete_item(&metadata).await?;
                 };
                 Ok(())
             }
@@ -282,17 +349,25 @@ cfg_if::cfg_if! {
 
         #[derive(Debug)]
         struct SpecStore {
+            kind: &'static str,
             version: AtomicU64,
             data: RwLock<HashMap<String, SpecPointer>>,
             sender: Sender<SpecUpdate>,
             receiver: Receiver<SpecUpdate>,
             path: PathBuf,
+            journal: Option<Arc<dyn MetadataJournal>>,
+            decode: DecodeSpecFn,
         }
 
+        /// decode spec file content of the spec type of a store
+        type DecodeSpecFn = fn(&str, PathBuf) -> Result<(String, SpecPointer)>;
+
         #[derive(Debug, Clone)]
         struct SpecPointer {
             inner: Arc<dyn Any + Send + Sync>,
             store_revision: u64,
+            /// revision of spec metadata
+            revision: u64,
             path: PathBuf,
         }
 
@@ -306,7 +381,127 @@ cfg_if::cfg_if! {
             pub fn new<P: AsRef<Path>>(path: P) -> Self {
                 let path = path.as_ref().to_path_buf();
                 let stores = Default::default();
-                Self { path, stores }
+                Self {
+                    path,
+                    stores,
+                    journal: OnceCell::new(),
+                }
+            }
+
+            /// route all writes through journal, must be set before any store is used
+            pub fn set_journal(&self, journal: Arc<dyn MetadataJournal>) -> Result<()> {
+                if !self.stores.read().is_empty() {
+                    anyhow::bail!("journal must be set before stores are loaded");
+                }
+                self.journal
+                    .set(journal)
+                    .map_err(|_| anyhow!("journal is already set"))
+            }
+
+            /// apply write passed by journal.
+            /// Put whose expected revision doesn't match stored spec fails with [`RevisionConflict`]
+            /// and leaves storage unchanged.
+            pub async fn apply_op(&self, op: &StoreOp) -> Result<()> {
+                trace!(?op, "apply op");
+                let stores = self.stores.read();
+                match stores.get(op.kind()).cloned() {
+                    Some(store) => {
+                        drop(stores);
+                        match op {
+                            StoreOp::Put {
+                                name,
+                                content,
+                                expected_revision,
+                                ..
+                            } => store.put_content(name, content, *expected_revision).await,
+                            StoreOp::Delete { name, .. } => {
+                                store.remove(name).await;
+                                Ok(())
+                            }
+                        }
+                    }
+                    None => {
+                        // store is loaded from files on first use,
+                        // holding lock prevents it from being loaded while file is written
+                        let dir = self.path.join(op.kind());
+                        let file = dir.join(format!("{}.{SPEC_FILE_EXTENSION}", op.name()));
+                        match op {
+                            StoreOp::Put {
+                                content,
+                                expected_revision,
+                                ..
+                            } => {
+                                if let Some(expected) = expected_revision {
+                                    let current = if file.exists() {
+                                        Some(file_revision(&std::fs::read_to_string(&file)?)?)
+                                    } else {
+                                        None
+                                    };
+                                    check_revision(op.kind(), op.name(), *expected, current)?;
+                                }
+                                std::fs::create_dir_all(&dir)?;
+                                std::fs::write(file, content)?;
+                            }
+                            StoreOp::Delete { .. } => {
+                                if file.exists() {
+                                    std::fs::remove_file(file)?;
+                                }
+                            }
+                        }
+                        drop(stores);
+                        Ok(())
+                    }
+                }
+            }
+
+            /// all stored specs as writes which recreate them
+            pub fn dump(&self) -> Result<Vec<StoreOp>> {
+                let mut ops = vec![];
+                if !self.path.exists() {
+                    return Ok(ops);
+                }
+                for kind_entry in std::fs::read_dir(&self.path)? {
+                    let kind_path = kind_entry?.path();
+                    if !kind_path.is_dir() {
+                        continue;
+                    }
+                    let Some(kind) = kind_path.file_name().and_then(OsStr::to_str) else {
+                        continue;
+                    };
+                    for entry in std::fs::read_dir(&kind_path)? {
+                        let path = entry?.path();
+                        if !path.extension().eq(&Some(OsStr::new(SPEC_FILE_EXTENSION))) {
+                            continue;
+                        }
+                        let Some(name) = path.file_stem().and_then(OsStr::to_str) else {
+                            continue;
+                        };
+                        ops.push(StoreOp::Put {
+                            kind: kind.to_owned(),
+                            name: name.to_owned(),
+                            content: std::fs::read_to_string(&path)?,
+                            expected_revision: None,
+                        });
+                    }
+                }
+                Ok(ops)
+            }
+
+            /// replace all stored specs with dumped ones
+            pub async fn restore(&self, ops: Vec<StoreOp>) -> Result<()> {
+                let mut stale: HashSet<(String, String)> = self
+                    .dump()?
+                    .iter()
+                    .map(|op| (op.kind().to_owned(), op.name().to_owned()))
+                    .collect();
+                for op in ops {
+                    stale.remove(&(op.kind().to_owned(), op.name().to_owned()));
+                    self.apply_op(&op).await?;
+                }
+                for (kind, name) in stale {
+                    self.apply_op(&StoreOp::Delete { kind, name }).await?;
+                }
+                Ok(())
             }
 
             fn get_store<S: Spec + DeserializeOwned>(&self) -> Result<Arc<SpecStore>> {
@@ -317,7 +512,10 @@ cfg_if::cfg_if! {
                     None => {
                         drop(read);
                         let mut write = self.stores.write();
-                        let store = Arc::new(SpecStore::load::<S, _>(self.path.join(key))?);
+                        let store = Arc::new(SpecStore::load::<S, _>(
+                            self.path.join(key),
+                            self.journal.get().cloned(),
+                        )?);
                         write.insert(key, store.clone());
                         drop(write);
                         store
@@ -331,7 +529,7 @@ cfg_if::cfg_if! {
                         let child_store = self.get_store_by_key(kind).await?;
                         for child in children {
                             trace!(?item, ?child, "delete child");
-                            child_store.delete_item(child).await;
+                            child_store.delete_item(child).await?;
                         }
                     }
                 }
@@ -378,7 +576,10 @@ cfg_if::cfg_if! {
         }
 
         impl SpecStore {
-            fn load<S: Spec, P: AsRef<Path>>(path: P) -> Result<Self> {
+            fn load<S: Spec, P: AsRef<Path>>(
+                path: P,
+                journal: Option<Arc<dyn MetadataJournal>>,
+            ) -> Result<Self> {
                 std::fs::create_dir_all(&path)?;
                 let version = Default::default();
                 let mut data: HashMap<String, SpecPointer> = Default::default();
@@ -387,7 +588,7 @@ cfg_if::cfg_if! {
                         continue;
                     };
                     let path = entry.path();
-                    if !path.extension().eq(&Some(OsStr::new("yaml"))) {
+                    if !path.extension().eq(&Some(OsStr::new(SPEC_FILE_EXTENSION))) {
                         continue;
                     }
                     let (name, item) = SpecPointer::load::<S, _>(&path).context(format!(
@@ -402,11 +603,14 @@ cfg_if::cfg_if! {
                 let (sender, receiver) = bounded(MAX_UPDATES_CAPACITY);
                 let path = path.as_ref().to_path_buf();
                 Ok(Self {
+                    kind: S::LABEL,
                     version,
                     data: RwLock::new(data),
                     sender,
                     receiver,
                     path,
+                    journal,
+                    decode: SpecPointer::decode::<S>,
                 })
             }
 
@@ -449,10 +653,27 @@ cfg_if::cfg_if! {
                     .ok_or_else(|| anyhow!("'{}' not found", metadata.uid()))
             }
 
-            async fn delete_item(&self, metadata: &LocalMetadataItem) {
+            async fn delete_item(&self, metadata: &LocalMetadataItem) -> Result<()> {
+                match &self.journal {
+                    Some(journal) => {
+                        if self.data.read().contains_key(metadata.uid()) {
+                            journal
+                                .append(StoreOp::Delete {
+                                    kind: self.kind.to_owned(),
+                                    name: metadata.uid().to_owned(),
+                                })
+                                .await?;
+                        }
+                    }
+                    None => self.remove(metadata.uid()).await,
+                }
+                Ok(())
+            }
+
+            async fn remove(&self, name: &str) {
                 let removed = {
                     let mut write = self.data.write();
-                    if let Some(removed) = write.remove(metadata.uid()) {
+                    if let Some(removed) = write.remove(name) {
                         removed.delete();
                         drop(write);
                         Some(removed)
@@ -471,18 +692,27 @@ cfg_if::cfg_if! {
                 S: Spec + Serialize,
             {
                 let id = value.ctx().item().uid().to_owned();
+                if let Some(journal) = &self.journal {
+                    let expected_revision = {
+                        let read = self.data.read();
+                        let prev = read.get(&id);
+                        set_next_revision(prev, &mut value)?;
+                        prev.map(|prev| prev.revision)
+                    };
+                    let content = SpecPointer::new(self.spec_file_name(&id), value).encode::<S>()?;
+                    return journal
+                        .append(StoreOp::Put {
+                            kind: self.kind.to_owned(),
+                            name: id,
+                            content,
+                            expected_revision,
+                        })
+                        .await;
+                }
                 let pointer =
                 {
                     let mut write = self.data.write();
-                    if let Some(prev) = write.get(&id) {
-                        let prev_meta = prev.downcast_ref::<S>()?.ctx().item();
-                        let prev_rev = prev_meta.revision;
-                        if prev_meta.is_newer(value.ctx().item()) {
-                            let new_rev = value.ctx().item().revision;
-                            anyhow::bail!("attempt to update by stale value: current version: {prev_rev}, proposed: {new_rev}");
-                        }
-                        value.ctx_mut().item_mut().revision = prev_rev + 1;
-                    };
+                    set_next_revision(write.get(&id), &mut value)?;
                     let pointer = SpecPointer::new(self.spec_file_name(&id), value);
                     write.insert(id, pointer.clone());
                     pointer.flush::<S>()?;
@@ -521,8 +751,29 @@ cfg_if::cfg_if! {
                 }
             }
 
+            /// store spec file content written by journal
+            async fn put_content(
+                &self,
+                name: &str,
+                content: &str,
+                expected_revision: Option<u64>,
+            ) -> Result<()> {
+                let (_, pointer) = (self.decode)(content, self.spec_file_name(name))?;
+                {
+                    let mut write = self.data.write();
+                    if let Some(expected) = expected_revision {
+                        let current = write.get(name).map(|prev| prev.revision);
+                        check_revision(self.kind, name, expected, current)?;
+                    }
+                    std::fs::write(&pointer.path, content)?;
+                    write.insert(name.to_owned(), pointer.clone());
+                }
+                self.send_update(SpecUpdate::Mod(pointer)).await;
+                Ok(())
+            }
+
             fn spec_file_name(&self, name: &str) -> PathBuf {
-                self.path.join(format!("{name}.yaml"))
+                self.path.join(format!("{name}.{SPEC_FILE_EXTENSION}"))
             }
 
             async fn send_update(&self, mut update: SpecUpdate) {
@@ -537,8 +788,51 @@ cfg_if::cfg_if! {
             }
         }
 
+        fn check_revision(kind: &str, name: &str, expected: u64, current: Option<u64>) -> Result<()> {
+            if current == Some(expected) {
+                return Ok(());
+            }
+            Err(RevisionConflict {
+                kind: kind.to_owned(),
+                name: name.to_owned(),
+                expected,
+                current,
+            }
+            .into())
+        }
+
+        /// revision of spec file content, regardless of spec type
+        fn file_revision(content: &str) -> Result<u64> {
+            #[derive(Deserialize)]
+            enum VersionedMeta {
+                #[serde(rename = "1.0.0")]
+                V1 { meta: LocalMetadataItem },
+            }
+
+            let VersionedMeta::V1 { meta } = serde_yaml::from_str(content)?;
+            Ok(meta.revision)
+        }
+
+        /// reject stale value and set revision following previous one
+        fn set_next_revision<S: Spec>(
+            prev: Option<&SpecPointer>,
+            value: &mut LocalStoreObject<S>,
+        ) -> Result<()> {
+            if let Some(prev) = prev {
+                let prev_meta = prev.downcast_ref::<S>()?.ctx().item();
+                let prev_rev = prev_meta.revision;
+                if prev_meta.is_newer(value.ctx().item()) {
+                    let new_rev = value.ctx().item().revision;
+                    anyhow::bail!("attempt to update by stale value: current version: {prev_rev}, proposed: {new_rev}");
+                }
+                value.ctx_mut().item_mut().revision = prev_rev + 1;
+            };
+            Ok(())
+        }
+
         impl SpecPointer {
             fn new<S: Spec, P: AsRef<Path>>(path: P, obj: LocalStoreObject<S>) -> Self {
+                let revision = obj.ctx().item().revision;
                 let inner = Arc::new(obj);
                 let path = path.as_ref().to_path_buf();
                 let store_revision = Default::default();
@@ -546,6 +840,7 @@ cfg_if::cfg_if! {
                     inner,
                     path,
                     store_revision,
+                    revision,
                 }
             }
 
@@ -557,6 +852,18 @@ cfg_if::cfg_if! {
                 Ok((name, pointer))
             }
 
+            fn decode<S: Spec>(content: &str, path: PathBuf) -> Result<(String, Self)> {
+                let storage: VersionedSpecStorage<S> = serde_yaml::from_str(content)?;
+                let name = storage.meta().uid().clone();
+                let pointer = SpecPointer::try_from((storage, path))?;
+                Ok((name, pointer))
+            }
+
+            fn encode<S: Spec>(&self) -> Result<String> {
+                let storage: VersionedSpecStorage<S> = self.try_into()?;
+                Ok(serde_yaml::to_string(&storage)?)
+            }
+
             fn downcast_ref<S: Spec>(&self) -> Result<&LocalStoreObject<S>> {
                 self.inner
                     .downcast_ref::<LocalStoreObject<S>>()
@@ -1584,6 +1891,66 @@ spec:
                 assert!(kind1.contains(&LocalMetadataItem::new("child2")));
             }
 
+            #[derive(Debug)]
+            struct ForwardJournal(std::sync::Weak<LocalMetadataStorage>);
+
+            #[async_trait::async_trait]
+            impl MetadataJournal for ForwardJournal {
+                async fn append(&self, op: StoreOp) -> Result<()> {
+                    self.0.upgrade().expect("storage").apply_op(&op).await
+                }
+            }
+
+            #[fluvio_future::test]
+            async fn test_journal_writes_and_restore() {
+                //given
+                let meta_folder = tempfile::tempdir().expect("temp dir created");
+                let meta_store = Arc::new(LocalMetadataStorage::new(&meta_folder));
+                meta_store
+                    .set_journal(Arc::new(ForwardJournal(Arc::downgrade(&meta_store))))
+                    .expect("journal set");
+                let obj = default_test_store_obj();
+                let mut watch = meta_store.watch_stream_since::<TestSpec>(&NameSpace::All, None);
+
+                //when
+                meta_store.apply(obj.clone()).await.expect("applied");
+                meta_store
+                    .update_status::<TestSpec>(
+                        obj.ctx().item().clone(),
+                        TestStatus("new status".to_string()),
+                        &NameSpace::All,
+                    )
+                    .await
+                    .expect("updated status");
+
+                //then
+                let items = meta_store
+                    .retrieve_items::<TestSpec>(&NameSpace::All)
+                    .await
+                    .expect("retrieved");
+                assert_eq!(items.items.len(), 1);
+                assert_eq!(items.items[0].status().to_string(), "new status");
+                assert_eq!(items.items[0].ctx().item().revision, 1);
+                assert!(watch.next().await.is_some());
+                assert!(meta_store.set_journal(Arc::new(ForwardJournal(Default::default()))).is_err());
+
+                //when
+                let dump = meta_store.dump().expect("dumped");
+                let other_folder = tempfile::tempdir().expect("temp dir created");
+                let other_store = LocalMetadataStorage::new(&other_folder);
+                other_store.apply(test_store_obj("stale")).await.expect("applied");
+                other_store.restore(dump.clone()).await.expect("restored");
+
+                //then
+                assert_eq!(other_store.dump().expect("dumped"), dump);
+                let items = other_store
+                    .retrieve_items::<TestSpec>(&NameSpace::All)
+                    .await
+                    .expect("retrieved");
+                assert_eq!(items.items.len(), 1);
+                assert_eq!(items.items[0].key(), "meta");
+            }
+
             fn default_test_store_obj() -> LocalStoreObject<TestSpec> {
                 test_store_obj("meta")
             }
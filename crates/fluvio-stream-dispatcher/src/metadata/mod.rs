@@ -2,6 +2,8 @@
 pub mod k8;
 #[cfg(feature = "local")]
 pub mod local;
+#[cfg(feature = "raft")]
+pub mod raft;
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "k8")] {
@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use super::NodeId;
+use super::transport::RaftTls;
+
+const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_millis(150);
+const DEFAULT_ELECTION_TIMEOUT: Duration = Duration::from_millis(1500);
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_SNAPSHOT_THRESHOLD: u64 = 10_000;
+const DEFAULT_MAX_APPEND_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone)]
+pub struct RaftConfig {
+    /// id of this node
+    pub id: NodeId,
+    /// raft address of every member, including this node
+    pub members: BTreeMap<NodeId, String>,
+    /// interval of leader heartbeats
+    pub heartbeat_interval: Duration,
+    /// follower starts election if it doesn't hear from leader within this timeout,
+    /// actual timeout is randomized up to twice this value
+    pub election_timeout: Duration,
+    /// timeout of requests to other members and of proposed writes
+    pub request_timeout: Duration,
+    /// number of applied entries after which log is compacted
+    pub snapshot_threshold: u64,
+    /// max entries sent to follower in single request
+    pub max_append_entries: usize,
+    /// TLS of connections between members, plain TCP if not set
+    pub tls: Option<RaftTls>,
+}
+
+impl RaftConfig {
+    pub fn new(id: NodeId, members: BTreeMap<NodeId, String>) -> Self {
+        Self {
+            id,
+            members,
+            heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
+            election_timeout: DEFAULT_ELECTION_TIMEOUT,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            snapshot_threshold: DEFAULT_SNAPSHOT_THRESHOLD,
+            max_append_entries: DEFAULT_MAX_APPEND_ENTRIES,
+            tls: None,
+        }
+    }
+
+    /// address this node is listening on
+    pub fn addr(&self) -> Option<&String> {
+        self.members.get(&self.id)
+    }
+
+    /// members other than this node
+    pub fn peers(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.members.keys().copied().filter(|id| *id != self.id)
+    }
+
+    /// number of members, including leader, which must store entry before it is committed
+    pub fn quorum(&self) -> usize {
+        self.members.len() / 2 + 1
+    }
+
+    /// parse members from `id=host:port` entries
+    pub fn parse_members<I, S>(members: I) -> anyhow::Result<BTreeMap<NodeId, String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        members
+            .into_iter()
+            .map(|member| {
+                let member = member.as_ref();
+                let (id, addr) = member
+                    .split_once('=')
+                    .ok_or_else(|| anyhow::anyhow!("expected id=host:port, got: {member}"))?;
+                let id = id
+                    .trim()
+                    .parse::<NodeId>()
+                    .map_err(|err| anyhow::anyhow!("invalid member id in {member}: {err}"))?;
+                Ok((id, addr.trim().to_owned()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RaftConfig;
+
+    #[test]
+    fn test_parse_members() {
+        let members = RaftConfig::parse_members(["1=sc-0:9010", "2=sc-1:9010", " 3 = sc-2:9010"])
+            .expect("parsed");
+        let config = RaftConfig::new(2, members);
+
+        assert_eq!(config.addr().map(|addr| addr.as_str()), Some("sc-1:9010"));
+        assert_eq!(config.peers().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(config.quorum(), 2);
+
+        assert!(RaftConfig::parse_members(["sc-0:9010"]).is_err());
+        assert!(RaftConfig::parse_members(["a=sc-0:9010"]).is_err());
+    }
+}
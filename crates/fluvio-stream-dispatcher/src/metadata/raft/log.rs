@@ -0,0 +1,328 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use tracing::{debug, warn};
+
+use crate::metadata::local::StoreOp;
+
+use super::NodeId;
+
+const HARD_STATE_FILE: &str = "hard_state.json";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const LOG_FILE: &str = "log.jsonl";
+
+/// Replicated log entry. Entry without ops is appended by newly elected leader.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub index: u64,
+    pub term: u64,
+    pub ops: Vec<StoreOp>,
+}
+
+/// state which must survive restarts to keep elections safe
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct HardState {
+    term: u64,
+    voted_for: Option<NodeId>,
+}
+
+/// last entry covered by snapshot, snapshot itself is the local metadata store
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SnapshotMeta {
+    index: u64,
+    term: u64,
+}
+
+/// Raft log persisted in a directory.
+/// Entries are appended to a json lines file which is rewritten on truncation and compaction.
+#[derive(Debug)]
+pub struct RaftLog {
+    dir: PathBuf,
+    hard_state: HardState,
+    snapshot: SnapshotMeta,
+    entries: Vec<Entry>,
+}
+
+impl RaftLog {
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        let hard_state: HardState = read_json(&dir.join(HARD_STATE_FILE))?.unwrap_or_default();
+        let snapshot: SnapshotMeta = read_json(&dir.join(SNAPSHOT_FILE))?.unwrap_or_default();
+
+        let mut entries: Vec<Entry> = vec![];
+        let log_path = dir.join(LOG_FILE);
+        if log_path.exists() {
+            for line in BufReader::new(File::open(&log_path)?).lines() {
+                let line = line?;
+                let entry: Entry = match serde_json::from_str(&line) {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        // only last write can be partial
+                        warn!(%err, "discarding partially written log entry");
+                        break;
+                    }
+                };
+                // entries may remain if compaction was interrupted
+                if entry.index <= snapshot.index {
+                    continue;
+                }
+                let expected = entries.last().map(|e| e.index).unwrap_or(snapshot.index) + 1;
+                if entry.index != expected {
+                    anyhow::bail!(
+                        "raft log is not contiguous: expected index {expected}, found {}",
+                        entry.index
+                    );
+                }
+                entries.push(entry);
+            }
+        }
+        debug!(
+            ?hard_state,
+            ?snapshot,
+            entries = entries.len(),
+            "raft log opened"
+        );
+        Ok(Self {
+            dir,
+            hard_state,
+            snapshot,
+            entries,
+        })
+    }
+
+    pub fn term(&self) -> u64 {
+        self.hard_state.term
+    }
+
+    pub fn voted_for(&self) -> Option<NodeId> {
+        self.hard_state.voted_for
+    }
+
+    pub fn set_hard_state(&mut self, term: u64, voted_for: Option<NodeId>) -> Result<()> {
+        let hard_state = HardState { term, voted_for };
+        if hard_state != self.hard_state {
+            write_json(&self.dir.join(HARD_STATE_FILE), &hard_state)?;
+            self.hard_state = hard_state;
+        }
+        Ok(())
+    }
+
+    pub fn snapshot_index(&self) -> u64 {
+        self.snapshot.index
+    }
+
+    pub fn last_index(&self) -> u64 {
+        self.entries
+            .last()
+            .map(|entry| entry.index)
+            .unwrap_or(self.snapshot.index)
+    }
+
+    pub fn last_term(&self) -> u64 {
+        self.entries
+            .last()
+            .map(|entry| entry.term)
+            .unwrap_or(self.snapshot.term)
+    }
+
+    /// term of entry at index, None if entry doesn't exist or is compacted
+    pub fn term_at(&self, index: u64) -> Option<u64> {
+        if index == self.snapshot.index {
+            Some(self.snapshot.term)
+        } else {
+            self.entry(index).map(|entry| entry.term)
+        }
+    }
+
+    pub fn entry(&self, index: u64) -> Option<&Entry> {
+        if index <= self.snapshot.index {
+            return None;
+        }
+        self.entries.get((index - self.snapshot.index - 1) as usize)
+    }
+
+    /// first index of entries with same term as entry at index
+    pub fn first_index_of_term(&self, index: u64) -> u64 {
+        let Some(term) = self.term_at(index) else {
+            return self.snapshot.index + 1;
+        };
+        let mut first = index;
+        while first > self.snapshot.index + 1 && self.term_at(first - 1) == Some(term) {
+            first -= 1;
+        }
+        first
+    }
+
+    /// up to max entries starting at index
+    pub fn entries_from(&self, index: u64, max: usize) -> Vec<Entry> {
+        if index <= self.snapshot.index {
+            return vec![];
+        }
+        let start = (index - self.snapshot.index - 1) as usize;
+        self.entries.iter().skip(start).take(max).cloned().collect()
+    }
+
+    /// append entries following last index
+    pub fn append(&mut self, entries: Vec<Entry>) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(LOG_FILE))?;
+        let mut buf = vec![];
+        for entry in &entries {
+            serde_json::to_writer(&mut buf, entry)?;
+            buf.push(b'\n');
+        }
+        file.write_all(&buf)?;
+        file.sync_data()?;
+        self.entries.extend(entries);
+        Ok(())
+    }
+
+    /// remove entries starting at index, committed entries must never be truncated
+    pub fn truncate_from(&mut self, index: u64) -> Result<()> {
+        if index > self.last_index() {
+            return Ok(());
+        }
+        let keep = index.saturating_sub(self.snapshot.index + 1) as usize;
+        self.entries.truncate(keep);
+        self.rewrite()
+    }
+
+    /// discard entries up to index, which must be applied to local store
+    pub fn compact(&mut self, index: u64) -> Result<()> {
+        let Some(term) = self.term_at(index) else {
+            return Ok(());
+        };
+        if index <= self.snapshot.index {
+            return Ok(());
+        }
+        let remove = (index - self.snapshot.index) as usize;
+        self.set_snapshot(SnapshotMeta { index, term })?;
+        self.entries.drain(..remove);
+        self.rewrite()
+    }
+
+    /// snapshot received from leader replaced local store.
+    /// Entries following snapshot are kept if log agrees with it.
+    pub fn install_snapshot(&mut self, index: u64, term: u64) -> Result<()> {
+        if index > self.snapshot.index && self.term_at(index) == Some(term) {
+            return self.compact(index);
+        }
+        self.set_snapshot(SnapshotMeta { index, term })?;
+        self.entries.clear();
+        self.rewrite()
+    }
+
+    fn set_snapshot(&mut self, snapshot: SnapshotMeta) -> Result<()> {
+        write_json(&self.dir.join(SNAPSHOT_FILE), &snapshot)?;
+        self.snapshot = snapshot;
+        Ok(())
+    }
+
+    fn rewrite(&self) -> Result<()> {
+        let mut buf = vec![];
+        for entry in &self.entries {
+            serde_json::to_writer(&mut buf, entry)?;
+            buf.push(b'\n');
+        }
+        write_atomic(&self.dir.join(LOG_FILE), &buf)
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read(path)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    write_atomic(path, &serde_json::to_vec(value)?)
+}
+
+fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    let mut file = File::create(&tmp)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    std::fs::rename(tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::metadata::local::StoreOp;
+
+    use super::{Entry, RaftLog};
+
+    fn entry(index: u64, term: u64) -> Entry {
+        Entry {
+            index,
+            term,
+            ops: vec![StoreOp::Delete {
+                kind: "TEST_SPEC".to_owned(),
+                name: format!("item-{index}"),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_log_append_truncate_reopen() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut log = RaftLog::open(dir.path()).expect("opened");
+        assert_eq!(log.last_index(), 0);
+        assert_eq!(log.term_at(0), Some(0));
+
+        log.set_hard_state(2, Some(1)).expect("hard state");
+        log.append(vec![entry(1, 1), entry(2, 1), entry(3, 2)])
+            .expect("appended");
+        assert_eq!(log.last_index(), 3);
+        assert_eq!(log.last_term(), 2);
+        assert_eq!(log.first_index_of_term(2), 1);
+        assert_eq!(log.entries_from(2, 10), vec![entry(2, 1), entry(3, 2)]);
+
+        log.truncate_from(3).expect("truncated");
+        log.append(vec![entry(3, 3)]).expect("appended");
+        drop(log);
+
+        let log = RaftLog::open(dir.path()).expect("reopened");
+        assert_eq!(log.term(), 2);
+        assert_eq!(log.voted_for(), Some(1));
+        assert_eq!(log.last_index(), 3);
+        assert_eq!(log.term_at(3), Some(3));
+    }
+
+    #[test]
+    fn test_log_compact_and_snapshot() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut log = RaftLog::open(dir.path()).expect("opened");
+        log.append((1..=5).map(|i| entry(i, 1)).collect())
+            .expect("appended");
+
+        log.compact(3).expect("compacted");
+        assert_eq!(log.snapshot_index(), 3);
+        assert_eq!(log.entry(3), None);
+        assert_eq!(log.term_at(3), Some(1));
+        assert_eq!(log.entries_from(4, 10), vec![entry(4, 1), entry(5, 1)]);
+        drop(log);
+
+        let mut log = RaftLog::open(dir.path()).expect("reopened");
+        assert_eq!(log.snapshot_index(), 3);
+        assert_eq!(log.last_index(), 5);
+
+        // snapshot from leader which conflicts with local entries
+        log.install_snapshot(7, 2).expect("installed");
+        assert_eq!(log.last_index(), 7);
+        assert_eq!(log.last_term(), 2);
+        assert!(log.entries_from(8, 10).is_empty());
+    }
+}
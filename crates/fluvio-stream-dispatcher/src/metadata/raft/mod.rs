@@ -0,0 +1,489 @@
+//! Local metadata storage replicated across SC instances with Raft.
+//!
+//! Every write to [`LocalMetadataStorage`] is appended to replicated log and applied
+//! once committed by majority of members. Followers forward writes to leader.
+mod config;
+mod log;
+mod node;
+mod transport;
+
+pub use config::RaftConfig;
+pub use node::Role;
+pub use transport::RaftTls;
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use async_lock::Mutex;
+use futures_util::stream::BoxStream;
+use tracing::info;
+
+use fluvio_future::timer::sleep;
+use fluvio_stream_model::{
+    core::Spec,
+    store::{MetadataStoreList, k8::K8ExtendedSpec, actions::LSUpdate, NameSpace},
+};
+
+use super::MetadataClient;
+use super::local::{LocalMetadataItem, LocalMetadataStorage, LocalStoreObject};
+
+use self::log::RaftLog;
+use self::node::RaftNode;
+use self::transport::{RaftTransport, TcpTransport, init_raft_server};
+
+pub type NodeId = u64;
+
+/// sub directory of metadata path holding raft log
+const RAFT_DIR: &str = ".raft";
+
+#[derive(Debug)]
+pub struct RaftMetadataStorage {
+    local: Arc<LocalMetadataStorage>,
+    node: Arc<RaftNode>,
+    /// writes of single operation must not interleave with other operations
+    write_lock: Mutex<()>,
+}
+
+impl RaftMetadataStorage {
+    /// open storage at path and join cluster, members communicate over TCP, with TLS if configured
+    pub async fn start<P: AsRef<Path>>(path: P, config: RaftConfig) -> Result<Self> {
+        let addr = config
+            .addr()
+            .cloned()
+            .ok_or_else(|| anyhow!("raft member {} has no address", config.id))?;
+        let members: Vec<String> = config.members.values().cloned().collect();
+        let tls = config.tls.clone();
+        let transport =
+            TcpTransport::new(config.members.clone(), config.request_timeout, tls.as_ref());
+        let storage = Self::with_transport(path, config, Box::new(transport))?;
+        init_raft_server(addr, members, tls, storage.node.clone());
+        Ok(storage)
+    }
+
+    fn with_transport<P: AsRef<Path>>(
+        path: P,
+        config: RaftConfig,
+        transport: Box<dyn RaftTransport>,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        info!(id = config.id, members = ?config.members, "starting replicated metadata storage");
+        let log = RaftLog::open(path.join(RAFT_DIR))?;
+        let local = Arc::new(LocalMetadataStorage::new(path));
+        let node = RaftNode::new(config, log, local.clone(), transport);
+        local.set_journal(node.journal())?;
+        node.start();
+        Ok(Self {
+            local,
+            node,
+            write_lock: Mutex::new(()),
+        })
+    }
+
+    pub fn id(&self) -> NodeId {
+        self.node.id()
+    }
+
+    pub async fn role(&self) -> Role {
+        self.node.role().await
+    }
+
+    pub async fn leader(&self) -> Option<NodeId> {
+        self.node.leader().await
+    }
+
+    /// wait until this member is leader and has applied all committed writes
+    pub async fn wait_for_leadership(&self) {
+        while !self.node.is_leader_ready().await {
+            sleep(self.poll_interval()).await;
+        }
+    }
+
+    /// wait until this member is no longer leader
+    pub async fn wait_for_demotion(&self) {
+        while self.node.role().await == Role::Leader {
+            sleep(self.poll_interval()).await;
+        }
+    }
+
+    fn poll_interval(&self) -> std::time::Duration {
+        self.node.heartbeat_interval()
+    }
+}
+
+#[async_trait::async_trait]
+impl MetadataClient<LocalMetadataItem> for RaftMetadataStorage {
+    async fn retrieve_items<S>(
+        &self,
+        namespace: &NameSpace,
+    ) -> Result<MetadataStoreList<S, LocalMetadataItem>>
+    where
+        S: K8ExtendedSpec,
+    {
+        self.local.retrieve_items(namespace).await
+    }
+
+    async fn delete_item<S>(&self, metadata: LocalMetadataItem) -> Result<()>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _write = self.write_lock.lock().await;
+        self.local.delete_item::<S>(metadata).await
+    }
+
+    async fn finalize_delete_item<S>(&self, metadata: LocalMetadataItem) -> Result<()>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _write = self.write_lock.lock().await;
+        self.local.finalize_delete_item::<S>(metadata).await
+    }
+
+    async fn apply<S>(&self, value: LocalStoreObject<S>) -> Result<()>
+    where
+        S: K8ExtendedSpec,
+        <S as Spec>::Owner: K8ExtendedSpec,
+    {
+        let _write = self.write_lock.lock().await;
+        self.local.apply(value).await
+    }
+
+    async fn update_spec<S>(&self, metadata: LocalMetadataItem, spec: S) -> Result<()>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _write = self.write_lock.lock().await;
+        self.local.update_spec(metadata, spec).await
+    }
+
+    async fn update_spec_by_key<S>(
+        &self,
+        key: S::IndexKey,
+        namespace: &NameSpace,
+        spec: S,
+    ) -> Result<()>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _write = self.write_lock.lock().await;
+        self.local.update_spec_by_key(key, namespace, spec).await
+    }
+
+    async fn update_status<S>(
+        &self,
+        metadata: LocalMetadataItem,
+        status: S::Status,
+        namespace: &NameSpace,
+    ) -> Result<LocalStoreObject<S>>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _write = self.write_lock.lock().await;
+        self.local.update_status(metadata, status, namespace).await
+    }
+
+    fn watch_stream_since<S>(
+        &self,
+        namespace: &NameSpace,
+        resource_version: Option<String>,
+    ) -> BoxStream<'_, Result<Vec<LSUpdate<S, LocalMetadataItem>>>>
+    where
+        S: K8ExtendedSpec,
+    {
+        self.local.watch_stream_since(namespace, resource_version)
+    }
+
+    async fn patch_status<S>(
+        &self,
+        metadata: LocalMetadataItem,
+        status: S::Status,
+        namespace: &NameSpace,
+    ) -> Result<LocalStoreObject<S>>
+    where
+        S: K8ExtendedSpec,
+    {
+        let _write = self.write_lock.lock().await;
+        self.local.patch_status(metadata, status, namespace).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use fluvio_future::timer::sleep;
+    use fluvio_stream_model::store::{MetadataStoreObject, NameSpace};
+    use fluvio_stream_model::core::MetadataContext;
+
+    use crate::metadata::MetadataClient;
+    use crate::metadata::fixture::{TestSpec, TestStatus};
+    use crate::metadata::local::{LocalMetadataItem, MetadataJournal, StoreOp};
+
+    use super::transport::memory::MemoryNetwork;
+    use super::{NodeId, RaftConfig, RaftMetadataStorage, Role};
+
+    const WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    struct TestCluster {
+        network: Arc<MemoryNetwork>,
+        members: Vec<RaftMetadataStorage>,
+        _dirs: Vec<tempfile::TempDir>,
+    }
+
+    impl TestCluster {
+        fn start(size: NodeId, snapshot_threshold: u64) -> Self {
+            let network = Arc::new(MemoryNetwork::default());
+            let addresses: BTreeMap<NodeId, String> =
+                (1..=size).map(|id| (id, format!("sc-{id}"))).collect();
+            let mut members = vec![];
+            let mut dirs = vec![];
+            for id in 1..=size {
+                let dir = tempfile::tempdir().expect("temp dir");
+                let mut config = RaftConfig::new(id, addresses.clone());
+                config.heartbeat_interval = Duration::from_millis(20);
+                config.election_timeout = Duration::from_millis(100);
+                config.request_timeout = Duration::from_secs(2);
+                config.snapshot_threshold = snapshot_threshold;
+                let storage = RaftMetadataStorage::with_transport(
+                    dir.path(),
+                    config,
+                    Box::new(network.transport(id)),
+                )
+                .expect("started");
+                network.register(&storage.node);
+                members.push(storage);
+                dirs.push(dir);
+            }
+            Self {
+                network,
+                members,
+                _dirs: dirs,
+            }
+        }
+
+        fn member(&self, id: NodeId) -> &RaftMetadataStorage {
+            &self.members[(id - 1) as usize]
+        }
+
+        async fn wait_for_leader(&self, except: Option<NodeId>) -> NodeId {
+            let deadline = Instant::now() + WAIT_TIMEOUT;
+            loop {
+                for member in &self.members {
+                    if Some(member.id()) != except && member.node.is_leader_ready().await {
+                        return member.id();
+                    }
+                }
+                assert!(Instant::now() < deadline, "no leader elected");
+                sleep(Duration::from_millis(20)).await;
+            }
+        }
+
+        async fn wait_for_dump(&self, id: NodeId, expected: &[StoreOp]) {
+            let deadline = Instant::now() + WAIT_TIMEOUT;
+            loop {
+                let mut dump = self.member(id).local.dump().expect("dumped");
+                dump.sort_by_key(|op| format!("{op:?}"));
+                if dump == expected {
+                    return;
+                }
+                assert!(
+                    Instant::now() < deadline,
+                    "member {id} did not converge: {dump:?}"
+                );
+                sleep(Duration::from_millis(20)).await;
+            }
+        }
+    }
+
+    fn put(name: &str) -> StoreOp {
+        StoreOp::Put {
+            kind: "TEST_SPEC".to_owned(),
+            name: name.to_owned(),
+            content: format!("content of {name}"),
+            expected_revision: None,
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_write_forwarded_and_replicated() {
+        let cluster = TestCluster::start(3, 1000);
+        let leader = cluster.wait_for_leader(None).await;
+        let follower = (1..=3).find(|id| *id != leader).expect("follower");
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        while cluster.member(follower).leader().await != Some(leader) {
+            assert!(Instant::now() < deadline, "follower did not learn leader");
+            sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(cluster.member(follower).role().await, Role::Follower);
+
+        // write through follower's store
+        let meta = LocalMetadataItem::new("meta");
+        let obj = MetadataStoreObject::new_with_context(
+            "meta".to_owned(),
+            TestSpec {
+                replica: 3,
+                ..Default::default()
+            },
+            MetadataContext::new(meta.clone()),
+        );
+        cluster.member(follower).apply(obj).await.expect("applied");
+        cluster
+            .member(follower)
+            .update_status::<TestSpec>(meta, TestStatus("ok".to_owned()), &NameSpace::All)
+            .await
+            .expect("status updated");
+
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        for id in 1..=3 {
+            loop {
+                let items = cluster
+                    .member(id)
+                    .retrieve_items::<TestSpec>(&NameSpace::All)
+                    .await
+                    .expect("retrieved");
+                if items.items.len() == 1 && items.items[0].status().0 == "ok" {
+                    assert_eq!(items.items[0].spec().replica, 3);
+                    assert_eq!(items.items[0].ctx().item().revision, 1);
+                    break;
+                }
+                assert!(Instant::now() < deadline, "member {id} did not converge");
+                sleep(Duration::from_millis(20)).await;
+            }
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_conflicting_write_rejected() {
+        let cluster = TestCluster::start(3, 1000);
+        let leader = cluster.wait_for_leader(None).await;
+        let follower = (1..=3).find(|id| *id != leader).expect("follower");
+
+        let meta = LocalMetadataItem::new("meta");
+        let obj = MetadataStoreObject::new_with_context(
+            "meta".to_owned(),
+            TestSpec::default(),
+            MetadataContext::new(meta.clone()),
+        );
+        cluster.member(follower).apply(obj).await.expect("applied");
+
+        // write proposed against revision which is no longer stored
+        let Some(StoreOp::Put {
+            kind,
+            name,
+            content,
+            ..
+        }) = cluster.member(follower).local.dump().expect("dumped").pop()
+        else {
+            panic!("expected stored spec");
+        };
+        let stale = StoreOp::Put {
+            kind,
+            name,
+            content,
+            expected_revision: Some(5),
+        };
+        let err = cluster
+            .member(follower)
+            .node
+            .journal()
+            .append(stale)
+            .await
+            .expect_err("rejected");
+        assert!(err.to_string().contains("conflicting update"), "{err}");
+
+        // rejected write doesn't stop applying of later writes
+        cluster
+            .member(follower)
+            .update_status::<TestSpec>(meta, TestStatus("ok".to_owned()), &NameSpace::All)
+            .await
+            .expect("status updated");
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        for id in 1..=3 {
+            loop {
+                let items = cluster
+                    .member(id)
+                    .retrieve_items::<TestSpec>(&NameSpace::All)
+                    .await
+                    .expect("retrieved");
+                if items.items.len() == 1 && items.items[0].status().0 == "ok" {
+                    assert_eq!(items.items[0].ctx().item().revision, 1);
+                    break;
+                }
+                assert!(Instant::now() < deadline, "member {id} did not converge");
+                sleep(Duration::from_millis(20)).await;
+            }
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_leader_failover() {
+        let cluster = TestCluster::start(3, 1000);
+        let leader = cluster.wait_for_leader(None).await;
+        cluster
+            .member(leader)
+            .node
+            .journal()
+            .append(put("a"))
+            .await
+            .expect("written");
+
+        cluster.network.isolate(leader);
+        let new_leader = cluster.wait_for_leader(Some(leader)).await;
+        assert_ne!(new_leader, leader);
+
+        // isolated member can't commit writes
+        assert!(
+            cluster
+                .member(leader)
+                .node
+                .submit(vec![put("lost")])
+                .await
+                .is_err()
+        );
+        assert_ne!(cluster.member(leader).role().await, Role::Leader);
+
+        cluster
+            .member(new_leader)
+            .node
+            .journal()
+            .append(put("b"))
+            .await
+            .expect("written");
+
+        cluster.network.reconnect(leader);
+        let expected = vec![put("a"), put("b")];
+        for id in 1..=3 {
+            cluster.wait_for_dump(id, &expected).await;
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_lagging_member_receives_snapshot() {
+        let cluster = TestCluster::start(3, 2);
+        let leader = cluster.wait_for_leader(None).await;
+        let lagging = (1..=3).find(|id| *id != leader).expect("follower");
+        cluster.network.isolate(lagging);
+
+        let journal = cluster.member(leader).node.journal();
+        let mut expected = vec![];
+        for i in 0..5 {
+            let op = put(&format!("item-{i}"));
+            journal.append(op.clone()).await.expect("written");
+            expected.push(op);
+        }
+        journal
+            .append(StoreOp::Delete {
+                kind: "TEST_SPEC".to_owned(),
+                name: "item-0".to_owned(),
+            })
+            .await
+            .expect("deleted");
+        expected.remove(0);
+        expected.sort_by_key(|op| format!("{op:?}"));
+
+        cluster.network.reconnect(lagging);
+        cluster.wait_for_dump(lagging, &expected).await;
+    }
+}
@@ -0,0 +1,886 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, anyhow};
+use async_channel::{Sender, Receiver, bounded};
+use async_lock::Mutex;
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use rand::Rng;
+use tokio::select;
+use tracing::{debug, error, info, trace, instrument};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_types::event::offsets::{OffsetPublisher, SharedOffsetPublisher};
+
+use crate::metadata::local::{LocalMetadataStorage, MetadataJournal, RevisionConflict, StoreOp};
+
+use super::NodeId;
+use super::config::RaftConfig;
+use super::log::{Entry, RaftLog};
+use super::transport::{
+    AppendRequest, AppendResponse, ProposeResponse, RaftRequest, RaftResponse, RaftTransport,
+    SnapshotRequest, SnapshotResponse, VoteRequest, VoteResponse,
+};
+
+/// max number of rejected entries remembered until their proposers pick them up
+const MAX_REJECTED_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Follower,
+    Candidate,
+    Leader,
+}
+
+/// replication progress of follower, tracked by leader
+#[derive(Debug)]
+struct Progress {
+    next_index: u64,
+    match_index: u64,
+    in_flight: bool,
+    /// last response received in current term
+    last_contact: Instant,
+}
+
+#[derive(Debug)]
+struct RaftState {
+    role: Role,
+    leader: Option<NodeId>,
+    log: RaftLog,
+    commit_index: u64,
+    election_deadline: Instant,
+    progress: BTreeMap<NodeId, Progress>,
+    /// index of entry appended when this node became leader
+    leader_start_index: u64,
+}
+
+/// Member of Raft cluster replicating writes to local metadata storage.
+///
+/// Committed entries are applied to storage in log order by single task.
+/// Lock order is `apply_lock` then `state`.
+pub struct RaftNode {
+    config: RaftConfig,
+    state: Mutex<RaftState>,
+    storage: Arc<LocalMetadataStorage>,
+    transport: Box<dyn RaftTransport>,
+    commit: SharedOffsetPublisher,
+    applied: SharedOffsetPublisher,
+    apply_lock: Mutex<()>,
+    /// applied entries whose write was rejected by storage, by index
+    rejected: parking_lot::Mutex<BTreeMap<u64, String>>,
+    /// error which stopped applying of committed entries
+    apply_error: parking_lot::Mutex<Option<String>>,
+    replicate_sender: Sender<()>,
+    replicate_receiver: Receiver<()>,
+}
+
+impl fmt::Debug for RaftNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RaftNode({})", self.config.id)
+    }
+}
+
+impl RaftNode {
+    pub fn new(
+        config: RaftConfig,
+        log: RaftLog,
+        storage: Arc<LocalMetadataStorage>,
+        transport: Box<dyn RaftTransport>,
+    ) -> Arc<Self> {
+        let snapshot_index = log.snapshot_index();
+        let election_deadline = Instant::now() + random_election_timeout(&config);
+        let (replicate_sender, replicate_receiver) = bounded(1);
+        Arc::new(Self {
+            config,
+            state: Mutex::new(RaftState {
+                role: Role::Follower,
+                leader: None,
+                log,
+                commit_index: snapshot_index,
+                election_deadline,
+                progress: BTreeMap::new(),
+                leader_start_index: 0,
+            }),
+            storage,
+            transport,
+            commit: OffsetPublisher::shared(snapshot_index as i64),
+            applied: OffsetPublisher::shared(snapshot_index as i64),
+            apply_lock: Mutex::new(()),
+            rejected: Default::default(),
+            apply_error: Default::default(),
+            replicate_sender,
+            replicate_receiver,
+        })
+    }
+
+    /// start election timer, heartbeats and applying of committed entries
+    pub fn start(self: &Arc<Self>) {
+        spawn(Self::tick_loop(Arc::downgrade(self)));
+        spawn(Self::apply_loop(Arc::downgrade(self)));
+    }
+
+    pub fn id(&self) -> NodeId {
+        self.config.id
+    }
+
+    pub fn heartbeat_interval(&self) -> Duration {
+        self.config.heartbeat_interval
+    }
+
+    pub async fn role(&self) -> Role {
+        self.state.lock().await.role
+    }
+
+    pub async fn leader(&self) -> Option<NodeId> {
+        self.state.lock().await.leader
+    }
+
+    /// this node is leader and has applied all entries committed by previous leaders
+    pub async fn is_leader_ready(&self) -> bool {
+        let state = self.state.lock().await;
+        state.role == Role::Leader
+            && self.applied.current_value() as u64 >= state.leader_start_index
+    }
+
+    pub(crate) fn journal(self: &Arc<Self>) -> Arc<dyn MetadataJournal> {
+        Arc::new(RaftJournal {
+            node: Arc::downgrade(self),
+        })
+    }
+
+    pub async fn handle(&self, request: RaftRequest) -> RaftResponse {
+        trace!(id = self.id(), ?request, "raft request");
+        match request {
+            RaftRequest::RequestVote(request) => {
+                RaftResponse::Vote(self.handle_vote(request).await)
+            }
+            RaftRequest::AppendEntries(request) => {
+                RaftResponse::Append(self.handle_append(request).await)
+            }
+            RaftRequest::InstallSnapshot(request) => {
+                RaftResponse::Snapshot(self.handle_snapshot(request).await)
+            }
+            RaftRequest::Propose(ops) => RaftResponse::Propose(self.handle_propose(ops).await),
+        }
+    }
+
+    /// replicate ops through current leader, returns index of committed entry
+    pub async fn submit(&self, ops: Vec<StoreOp>) -> Result<u64> {
+        let deadline = Instant::now() + self.config.request_timeout;
+        loop {
+            let (role, leader) = {
+                let state = self.state.lock().await;
+                (state.role, state.leader)
+            };
+            match (role, leader) {
+                (Role::Leader, _) => return self.propose(ops).await,
+                (_, Some(leader)) => {
+                    match self
+                        .transport
+                        .send(leader, RaftRequest::Propose(ops.clone()))
+                        .await
+                    {
+                        Ok(RaftResponse::Propose(ProposeResponse::Committed(index))) => {
+                            return Ok(index);
+                        }
+                        Ok(RaftResponse::Propose(ProposeResponse::Failed(err))) => {
+                            return Err(anyhow!("leader {leader} failed to commit write: {err}"));
+                        }
+                        Ok(response) => debug!(leader, ?response, "write not accepted by leader"),
+                        Err(err) => debug!(leader, %err, "unable to forward write to leader"),
+                    }
+                }
+                _ => trace!("no leader to forward write to"),
+            }
+            if Instant::now() >= deadline {
+                return Err(anyhow!("no metadata leader available"));
+            }
+            sleep(self.config.heartbeat_interval).await;
+        }
+    }
+
+    /// wait until entry at index is applied to local storage,
+    /// fails if storage rejected its write
+    pub async fn wait_applied(&self, index: u64) -> Result<()> {
+        if wait_for(&self.applied, index, self.config.request_timeout)
+            .await
+            .is_err()
+        {
+            return Err(match self.apply_error.lock().as_ref() {
+                Some(err) => anyhow!("entry {index} not applied, applying stopped: {err}"),
+                None => anyhow!("timed out waiting for entry {index} to be applied"),
+            });
+        }
+        match self.rejected.lock().remove(&index) {
+            Some(err) => Err(anyhow!(err)),
+            None => Ok(()),
+        }
+    }
+
+    async fn propose(&self, ops: Vec<StoreOp>) -> Result<u64> {
+        let (index, term) = {
+            let mut state = self.state.lock().await;
+            if state.role != Role::Leader {
+                return Err(anyhow!("not a metadata leader"));
+            }
+            let index = state.log.last_index() + 1;
+            let term = state.log.term();
+            state.log.append(vec![Entry { index, term, ops }])?;
+            self.advance_commit(&mut state);
+            (index, term)
+        };
+        self.trigger_replicate();
+        wait_for(&self.commit, index, self.config.request_timeout)
+            .await
+            .map_err(|_| anyhow!("timed out waiting for entry {index} to be committed"))?;
+        let state = self.state.lock().await;
+        match state.log.term_at(index) {
+            Some(entry_term) if entry_term != term => {
+                Err(anyhow!("entry {index} was replaced by new leader"))
+            }
+            _ => Ok(index),
+        }
+    }
+
+    async fn tick_loop(node: Weak<Self>) {
+        loop {
+            let Some(this) = node.upgrade() else {
+                break;
+            };
+            let interval = this.config.heartbeat_interval;
+            let signal = this.replicate_receiver.clone();
+            drop(this);
+
+            select! {
+                _ = sleep(interval) => {},
+                _ = signal.recv() => {},
+            }
+
+            let Some(this) = node.upgrade() else {
+                break;
+            };
+            let (role, election_deadline) = {
+                let state = this.state.lock().await;
+                (state.role, state.election_deadline)
+            };
+            match role {
+                Role::Leader => {
+                    if this.check_quorum().await {
+                        this.replicate_all().await
+                    }
+                }
+                _ if Instant::now() >= election_deadline => {
+                    spawn(this.run_election());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    async fn apply_loop(node: Weak<Self>) {
+        let Some(this) = node.upgrade() else {
+            return;
+        };
+        let mut listener = this.commit.change_listener();
+        let interval = this.config.heartbeat_interval;
+        drop(this);
+        loop {
+            let Some(this) = node.upgrade() else {
+                break;
+            };
+            this.apply_committed().await;
+            drop(this);
+
+            select! {
+                _ = listener.listen() => {},
+                _ = sleep(interval) => {},
+            }
+        }
+    }
+
+    async fn apply_committed(&self) {
+        let _apply = self.apply_lock.lock().await;
+        loop {
+            let applied = self.applied.current_value() as u64;
+            let entry = {
+                let state = self.state.lock().await;
+                if applied >= state.commit_index {
+                    break;
+                }
+                match state.log.entry(applied + 1) {
+                    Some(entry) => entry.clone(),
+                    None => {
+                        error!(index = applied + 1, "committed entry is missing");
+                        break;
+                    }
+                }
+            };
+            for op in &entry.ops {
+                if let Err(err) = self.storage.apply_op(op).await {
+                    // conflict is decided same way on every member, entry is applied as no-op
+                    if err.downcast_ref::<RevisionConflict>().is_some() {
+                        debug!(index = entry.index, "metadata write rejected: {err}");
+                        let mut rejected = self.rejected.lock();
+                        rejected.insert(entry.index, err.to_string());
+                        if rejected.len() > MAX_REJECTED_ENTRIES {
+                            rejected.pop_first();
+                        }
+                        continue;
+                    }
+                    // applying later entries would diverge storage from other members,
+                    // retry from this entry on next commit or tick
+                    error!(
+                        index = entry.index,
+                        ?op,
+                        "error applying metadata write, stopped applying: {err:#}"
+                    );
+                    *self.apply_error.lock() = Some(format!("{err:#}"));
+                    return;
+                }
+            }
+            if self.apply_error.lock().take().is_some() {
+                info!(index = entry.index, "resumed applying metadata writes");
+            }
+            self.applied.update(entry.index as i64);
+        }
+
+        let applied = self.applied.current_value() as u64;
+        let mut state = self.state.lock().await;
+        if applied.saturating_sub(state.log.snapshot_index()) >= self.config.snapshot_threshold {
+            debug!(applied, "compacting raft log");
+            if let Err(err) = state.log.compact(applied) {
+                error!("error compacting raft log: {err:#}");
+            }
+        }
+    }
+
+    #[instrument(skip(self), fields(id = self.id()))]
+    async fn run_election(self: Arc<Self>) {
+        let request = {
+            let mut state = self.state.lock().await;
+            // heartbeat may have arrived since election was scheduled
+            if state.role == Role::Leader || Instant::now() < state.election_deadline {
+                return;
+            }
+            let term = state.log.term() + 1;
+            if let Err(err) = state.log.set_hard_state(term, Some(self.id())) {
+                error!("unable to persist raft state: {err:#}");
+                return;
+            }
+            state.role = Role::Candidate;
+            state.leader = None;
+            self.reset_election_deadline(&mut state);
+            info!(term, "starting metadata leader election");
+            if self.config.quorum() == 1 {
+                self.become_leader(&mut state);
+                return;
+            }
+            VoteRequest {
+                term,
+                candidate: self.id(),
+                last_log_index: state.log.last_index(),
+                last_log_term: state.log.last_term(),
+            }
+        };
+
+        let transport = &self.transport;
+        let mut votes: FuturesUnordered<_> = self
+            .config
+            .peers()
+            .map(|peer| {
+                let request = RaftRequest::RequestVote(request.clone());
+                async move { transport.send(peer, request).await }
+            })
+            .collect();
+        let mut granted = 1;
+        while let Some(response) = votes.next().await {
+            let response = match response {
+                Ok(RaftResponse::Vote(response)) => response,
+                Ok(response) => {
+                    debug!(?response, "unexpected vote response");
+                    continue;
+                }
+                Err(err) => {
+                    debug!(%err, "vote request failed");
+                    continue;
+                }
+            };
+            let mut state = self.state.lock().await;
+            if response.term > state.log.term() {
+                self.step_down(&mut state, response.term, None);
+                return;
+            }
+            if state.role != Role::Candidate || state.log.term() != request.term {
+                return;
+            }
+            if response.granted {
+                granted += 1;
+                if granted >= self.config.quorum() {
+                    self.become_leader(&mut state);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn become_leader(&self, state: &mut RaftState) {
+        let term = state.log.term();
+        let index = state.log.last_index() + 1;
+        // committing entry of own term commits entries of previous leaders
+        if let Err(err) = state.log.append(vec![Entry {
+            index,
+            term,
+            ops: vec![],
+        }]) {
+            error!("unable to append to raft log: {err:#}");
+            state.role = Role::Follower;
+            return;
+        }
+        info!(id = self.id(), term, "elected metadata leader");
+        state.role = Role::Leader;
+        state.leader = Some(self.id());
+        state.leader_start_index = index;
+        state.progress = self
+            .config
+            .peers()
+            .map(|peer| {
+                (
+                    peer,
+                    Progress {
+                        next_index: index,
+                        match_index: 0,
+                        in_flight: false,
+                        last_contact: Instant::now(),
+                    },
+                )
+            })
+            .collect();
+        self.advance_commit(state);
+        self.trigger_replicate();
+    }
+
+    /// follow leader of term, if known
+    fn step_down(&self, state: &mut RaftState, term: u64, leader: Option<NodeId>) {
+        if term > state.log.term() {
+            if let Err(err) = state.log.set_hard_state(term, None) {
+                error!("unable to persist raft state: {err:#}");
+            }
+        }
+        if state.role == Role::Leader {
+            info!(id = self.id(), term, "lost metadata leadership");
+        }
+        state.role = Role::Follower;
+        state.leader = leader;
+        state.progress.clear();
+    }
+
+    fn reset_election_deadline(&self, state: &mut RaftState) {
+        state.election_deadline = Instant::now() + random_election_timeout(&self.config);
+    }
+
+    fn trigger_replicate(&self) {
+        let _ = self.replicate_sender.try_send(());
+    }
+
+    /// commit latest entry of current term stored by quorum
+    fn advance_commit(&self, state: &mut RaftState) {
+        let term = state.log.term();
+        let mut index = state.log.last_index();
+        while index > state.commit_index && state.log.term_at(index) == Some(term) {
+            let replicated = 1 + state
+                .progress
+                .values()
+                .filter(|progress| progress.match_index >= index)
+                .count();
+            if replicated >= self.config.quorum() {
+                trace!(index, "committed");
+                state.commit_index = index;
+                self.commit.update(index as i64);
+                // let followers know
+                self.trigger_replicate();
+                return;
+            }
+            index -= 1;
+        }
+    }
+
+    /// Leader which can't reach quorum within election timeout steps down,
+    /// so it stops serving while other members may elect new leader.
+    async fn check_quorum(&self) -> bool {
+        let mut state = self.state.lock().await;
+        if state.role != Role::Leader {
+            return false;
+        }
+        let now = Instant::now();
+        let reachable = 1 + state
+            .progress
+            .values()
+            .filter(|progress| {
+                now.duration_since(progress.last_contact) < self.config.election_timeout
+            })
+            .count();
+        if reachable >= self.config.quorum() {
+            return true;
+        }
+        info!(
+            id = self.id(),
+            reachable, "metadata leader lost contact with quorum"
+        );
+        let term = state.log.term();
+        self.step_down(&mut state, term, None);
+        self.reset_election_deadline(&mut state);
+        false
+    }
+
+    async fn replicate_all(self: &Arc<Self>) {
+        let mut state = self.state.lock().await;
+        if state.role != Role::Leader {
+            return;
+        }
+        for (peer, progress) in state.progress.iter_mut() {
+            if progress.in_flight {
+                continue;
+            }
+            progress.in_flight = true;
+            spawn(self.clone().replicate_to(*peer));
+        }
+    }
+
+    async fn replicate_to(self: Arc<Self>, peer: NodeId) {
+        let request = {
+            let state = self.state.lock().await;
+            match state.progress.get(&peer) {
+                Some(progress) if state.role == Role::Leader => {
+                    let next_index = progress.next_index;
+                    if next_index <= state.log.snapshot_index() {
+                        None
+                    } else {
+                        let prev_log_index = next_index - 1;
+                        Some(AppendRequest {
+                            term: state.log.term(),
+                            leader: self.id(),
+                            prev_log_index,
+                            prev_log_term: state.log.term_at(prev_log_index).unwrap_or_default(),
+                            entries: state
+                                .log
+                                .entries_from(next_index, self.config.max_append_entries),
+                            leader_commit: state.commit_index,
+                        })
+                    }
+                }
+                _ => return,
+            }
+        };
+        let result = match request {
+            Some(request) => self.send_append(peer, request).await,
+            None => self.send_snapshot(peer).await,
+        };
+        if let Err(err) = result {
+            debug!(peer, %err, "unable to replicate to follower");
+        }
+        if let Some(progress) = self.state.lock().await.progress.get_mut(&peer) {
+            progress.in_flight = false;
+        }
+    }
+
+    async fn send_append(&self, peer: NodeId, request: AppendRequest) -> Result<()> {
+        let term = request.term;
+        let response = match self
+            .transport
+            .send(peer, RaftRequest::AppendEntries(request))
+            .await?
+        {
+            RaftResponse::Append(response) => response,
+            response => return Err(anyhow!("unexpected append response: {response:?}")),
+        };
+        let mut state = self.state.lock().await;
+        if response.term > state.log.term() {
+            self.step_down(&mut state, response.term, None);
+            return Ok(());
+        }
+        if state.role != Role::Leader || state.log.term() != term {
+            return Ok(());
+        }
+        let last_index = state.log.last_index();
+        let Some(progress) = state.progress.get_mut(&peer) else {
+            return Ok(());
+        };
+        progress.last_contact = Instant::now();
+        if response.success {
+            progress.match_index = progress.match_index.max(response.index);
+            progress.next_index = progress.match_index + 1;
+            let behind = progress.next_index <= last_index;
+            self.advance_commit(&mut state);
+            if behind {
+                self.trigger_replicate();
+            }
+        } else {
+            progress.next_index = response
+                .index
+                .min(progress.next_index.saturating_sub(1))
+                .max(1);
+            self.trigger_replicate();
+        }
+        Ok(())
+    }
+
+    async fn send_snapshot(&self, peer: NodeId) -> Result<()> {
+        let request = {
+            let _apply = self.apply_lock.lock().await;
+            let last_index = self.applied.current_value() as u64;
+            let state = self.state.lock().await;
+            if state.role != Role::Leader {
+                return Ok(());
+            }
+            let last_term = state
+                .log
+                .term_at(last_index)
+                .ok_or_else(|| anyhow!("term of applied entry {last_index} not found"))?;
+            let term = state.log.term();
+            drop(state);
+            SnapshotRequest {
+                term,
+                leader: self.id(),
+                last_index,
+                last_term,
+                ops: self.storage.dump()?,
+            }
+        };
+        debug!(peer, last_index = request.last_index, "sending snapshot");
+        let term = request.term;
+        let last_index = request.last_index;
+        let response = match self
+            .transport
+            .send(peer, RaftRequest::InstallSnapshot(request))
+            .await?
+        {
+            RaftResponse::Snapshot(response) => response,
+            response => return Err(anyhow!("unexpected snapshot response: {response:?}")),
+        };
+        let mut state = self.state.lock().await;
+        if response.term > state.log.term() {
+            self.step_down(&mut state, response.term, None);
+            return Ok(());
+        }
+        if state.role != Role::Leader || state.log.term() != term || !response.installed {
+            return Ok(());
+        }
+        if let Some(progress) = state.progress.get_mut(&peer) {
+            progress.last_contact = Instant::now();
+            progress.match_index = progress.match_index.max(last_index);
+            progress.next_index = progress.match_index + 1;
+        }
+        self.trigger_replicate();
+        Ok(())
+    }
+
+    async fn handle_vote(&self, request: VoteRequest) -> VoteResponse {
+        let mut state = self.state.lock().await;
+        if request.term > state.log.term() {
+            self.step_down(&mut state, request.term, None);
+        }
+        let term = state.log.term();
+        let log_up_to_date = (request.last_log_term, request.last_log_index)
+            >= (state.log.last_term(), state.log.last_index());
+        let mut granted = request.term == term
+            && state
+                .log
+                .voted_for()
+                .is_none_or(|voted_for| voted_for == request.candidate)
+            && log_up_to_date;
+        if granted {
+            if let Err(err) = state.log.set_hard_state(term, Some(request.candidate)) {
+                error!("unable to persist raft state: {err:#}");
+                granted = false;
+            } else {
+                self.reset_election_deadline(&mut state);
+            }
+        }
+        debug!(
+            id = self.id(),
+            candidate = request.candidate,
+            term,
+            granted,
+            "vote"
+        );
+        VoteResponse { term, granted }
+    }
+
+    async fn handle_append(&self, request: AppendRequest) -> AppendResponse {
+        let mut state = self.state.lock().await;
+        let term = state.log.term();
+        if request.term < term {
+            return AppendResponse {
+                term,
+                success: false,
+                index: 0,
+            };
+        }
+        if state.role != Role::Follower
+            || state.leader != Some(request.leader)
+            || request.term > term
+        {
+            self.step_down(&mut state, request.term, Some(request.leader));
+        }
+        self.reset_election_deadline(&mut state);
+        let term = state.log.term();
+        let fail = |index| AppendResponse {
+            term,
+            success: false,
+            index,
+        };
+
+        if request.prev_log_index > state.log.last_index() {
+            return fail(state.log.last_index() + 1);
+        }
+        let snapshot_index = state.log.snapshot_index();
+        // entries covered by snapshot are committed and match leader
+        if request.prev_log_index >= snapshot_index
+            && state.log.term_at(request.prev_log_index) != Some(request.prev_log_term)
+        {
+            return fail(state.log.first_index_of_term(request.prev_log_index));
+        }
+
+        let last_new_index = request.prev_log_index + request.entries.len() as u64;
+        let mut new_entries = vec![];
+        for entry in request.entries {
+            if entry.index <= snapshot_index {
+                continue;
+            }
+            if new_entries.is_empty() {
+                match state.log.term_at(entry.index) {
+                    Some(entry_term) if entry_term == entry.term => continue,
+                    Some(_) => {
+                        if let Err(err) = state.log.truncate_from(entry.index) {
+                            error!("unable to truncate raft log: {err:#}");
+                            return fail(entry.index);
+                        }
+                    }
+                    None => {}
+                }
+            }
+            new_entries.push(entry);
+        }
+        if let Some(first) = new_entries.first().map(|entry| entry.index) {
+            if let Err(err) = state.log.append(new_entries) {
+                error!("unable to append to raft log: {err:#}");
+                return fail(first);
+            }
+        }
+
+        let commit_index = request.leader_commit.min(last_new_index);
+        if commit_index > state.commit_index {
+            state.commit_index = commit_index;
+            self.commit.update(commit_index as i64);
+        }
+        AppendResponse {
+            term,
+            success: true,
+            index: last_new_index,
+        }
+    }
+
+    async fn handle_snapshot(&self, request: SnapshotRequest) -> SnapshotResponse {
+        {
+            let mut state = self.state.lock().await;
+            let term = state.log.term();
+            if request.term < term {
+                return SnapshotResponse {
+                    term,
+                    installed: false,
+                };
+            }
+            self.step_down(&mut state, request.term, Some(request.leader));
+            self.reset_election_deadline(&mut state);
+        }
+
+        let _apply = self.apply_lock.lock().await;
+        let installed = if request.last_index <= self.applied.current_value() as u64 {
+            true
+        } else {
+            info!(
+                id = self.id(),
+                last_index = request.last_index,
+                "installing metadata snapshot"
+            );
+            match self.storage.restore(request.ops).await {
+                Ok(()) => true,
+                Err(err) => {
+                    error!("unable to restore metadata snapshot: {err:#}");
+                    false
+                }
+            }
+        };
+        let mut state = self.state.lock().await;
+        if installed && request.last_index > self.applied.current_value() as u64 {
+            if let Err(err) = state
+                .log
+                .install_snapshot(request.last_index, request.last_term)
+            {
+                error!("unable to install raft snapshot: {err:#}");
+            }
+            if request.last_index > state.commit_index {
+                state.commit_index = request.last_index;
+                self.commit.update(request.last_index as i64);
+            }
+            self.applied.update(request.last_index as i64);
+        }
+        SnapshotResponse {
+            term: state.log.term(),
+            installed,
+        }
+    }
+
+    async fn handle_propose(&self, ops: Vec<StoreOp>) -> ProposeResponse {
+        {
+            let state = self.state.lock().await;
+            if state.role != Role::Leader {
+                return ProposeResponse::NotLeader(state.leader);
+            }
+        }
+        match self.propose(ops).await {
+            Ok(index) => ProposeResponse::Committed(index),
+            Err(err) => ProposeResponse::Failed(err.to_string()),
+        }
+    }
+}
+
+/// Journal of local metadata storage replicating its writes through Raft
+#[derive(Debug)]
+struct RaftJournal {
+    node: Weak<RaftNode>,
+}
+
+#[async_trait::async_trait]
+impl MetadataJournal for RaftJournal {
+    async fn append(&self, op: StoreOp) -> Result<()> {
+        let node = self
+            .node
+            .upgrade()
+            .ok_or_else(|| anyhow!("metadata replication stopped"))?;
+        let index = node.submit(vec![op]).await?;
+        node.wait_applied(index).await
+    }
+}
+
+fn random_election_timeout(config: &RaftConfig) -> Duration {
+    let min = config.election_timeout.as_millis() as u64;
+    Duration::from_millis(rand::thread_rng().gen_range(min..=min * 2))
+}
+
+/// wait until publisher reaches value
+async fn wait_for(publisher: &SharedOffsetPublisher, value: u64, timeout: Duration) -> Result<()> {
+    let mut listener = publisher.change_listener();
+    let timer = sleep(timeout);
+    futures_util::pin_mut!(timer);
+    while (publisher.current_value() as u64) < value {
+        select! {
+            _ = listener.listen() => {},
+            _ = &mut timer => return Err(anyhow!("timed out")),
+        }
+    }
+    Ok(())
+}
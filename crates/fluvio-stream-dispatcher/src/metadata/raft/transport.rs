@@ -0,0 +1,444 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::io::{Error as IoError, ErrorKind};
+use std::net::{IpAddr, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use async_lock::Mutex;
+use async_trait::async_trait;
+use futures_util::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, StreamExt};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
+use tokio::select;
+use tracing::{debug, error, info, warn};
+
+use fluvio_future::net::{
+    BoxReadConnection, BoxWriteConnection, DefaultDomainConnector, DomainConnector, TcpListener,
+    TcpStream,
+};
+use fluvio_future::rust_tls::{TlsAcceptor, TlsConnector, TlsDomainConnector};
+use fluvio_future::task::{spawn, spawn_blocking};
+use fluvio_future::timer::sleep;
+
+use crate::metadata::local::StoreOp;
+
+use super::NodeId;
+use super::log::Entry;
+use super::node::RaftNode;
+
+/// snapshots carry whole metadata store
+const MAX_FRAME_SIZE: usize = 512 * 1024 * 1024;
+/// frames are read in chunks so that length prefix alone can't make us allocate whole max frame
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RaftRequest {
+    RequestVote(VoteRequest),
+    AppendEntries(AppendRequest),
+    InstallSnapshot(SnapshotRequest),
+    /// write forwarded by follower to leader
+    Propose(Vec<StoreOp>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RaftResponse {
+    Vote(VoteResponse),
+    Append(AppendResponse),
+    Snapshot(SnapshotResponse),
+    Propose(ProposeResponse),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteRequest {
+    pub term: u64,
+    pub candidate: NodeId,
+    pub last_log_index: u64,
+    pub last_log_term: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteResponse {
+    pub term: u64,
+    pub granted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppendRequest {
+    pub term: u64,
+    pub leader: NodeId,
+    pub prev_log_index: u64,
+    pub prev_log_term: u64,
+    pub entries: Vec<Entry>,
+    pub leader_commit: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppendResponse {
+    pub term: u64,
+    pub success: bool,
+    /// on success, last index matching leader log.
+    /// Otherwise, index from which leader should retry.
+    pub index: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotRequest {
+    pub term: u64,
+    pub leader: NodeId,
+    pub last_index: u64,
+    pub last_term: u64,
+    pub ops: Vec<StoreOp>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotResponse {
+    pub term: u64,
+    pub installed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposeResponse {
+    /// committed at index of leader log
+    Committed(u64),
+    NotLeader(Option<NodeId>),
+    Failed(String),
+}
+
+/// Sends requests to other members
+#[async_trait]
+pub trait RaftTransport: Send + Sync + 'static {
+    async fn send(&self, to: NodeId, request: RaftRequest) -> Result<RaftResponse>;
+}
+
+/// TLS between members, each member authenticates peers with same CA
+#[derive(Clone)]
+pub struct RaftTls {
+    pub acceptor: TlsAcceptor,
+    /// connector presenting member certificate, peer certificate must be valid for host of its address
+    pub connector: TlsConnector,
+}
+
+impl fmt::Debug for RaftTls {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RaftTls").finish_non_exhaustive()
+    }
+}
+
+/// Transport over TCP, requests are sent as length prefixed json frames
+/// over single persistent connection to each member
+pub struct TcpTransport {
+    peers: BTreeMap<NodeId, Peer>,
+    timeout: Duration,
+}
+
+impl fmt::Debug for TcpTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TcpTransport")
+            .field("peers", &self.peers.keys())
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+struct Peer {
+    addr: String,
+    connector: DomainConnector,
+    connection: Mutex<Option<Connection>>,
+}
+
+struct Connection {
+    write: BoxWriteConnection,
+    read: BoxReadConnection,
+}
+
+impl TcpTransport {
+    pub fn new(
+        members: BTreeMap<NodeId, String>,
+        timeout: Duration,
+        tls: Option<&RaftTls>,
+    ) -> Self {
+        let peers = members
+            .into_iter()
+            .map(|(id, addr)| {
+                let connector: DomainConnector = match tls {
+                    Some(tls) => Box::new(TlsDomainConnector::new(
+                        tls.connector.clone(),
+                        host(&addr).to_owned(),
+                    )),
+                    None => Box::new(DefaultDomainConnector::new()),
+                };
+                let peer = Peer {
+                    addr,
+                    connector,
+                    connection: Mutex::new(None),
+                };
+                (id, peer)
+            })
+            .collect();
+        Self { peers, timeout }
+    }
+
+    async fn call(
+        peer: &Peer,
+        connection: &mut Option<Connection>,
+        request: &RaftRequest,
+    ) -> Result<RaftResponse> {
+        let connection = match connection {
+            Some(connection) => connection,
+            None => {
+                let (write, read, _fd) = peer.connector.connect(&peer.addr).await?;
+                debug!(addr = %peer.addr, "connected to raft member");
+                connection.insert(Connection { write, read })
+            }
+        };
+        write_frame(&mut connection.write, request).await?;
+        Ok(read_frame(&mut connection.read).await?)
+    }
+}
+
+#[async_trait]
+impl RaftTransport for TcpTransport {
+    async fn send(&self, to: NodeId, request: RaftRequest) -> Result<RaftResponse> {
+        let peer = self
+            .peers
+            .get(&to)
+            .ok_or_else(|| anyhow!("unknown raft member: {to}"))?;
+        let mut connection = peer.connection.lock().await;
+        let response = select! {
+            response = Self::call(peer, &mut connection, &request) => response,
+            _ = sleep(self.timeout) => Err(anyhow!("raft request to {} timed out", peer.addr)),
+        };
+        // connection may be in the middle of frame, next request reconnects
+        if response.is_err() {
+            *connection = None;
+        }
+        response
+    }
+}
+
+/// host part of `host:port` address
+fn host(addr: &str) -> &str {
+    addr.rsplit_once(':').map_or(addr, |(host, _)| host)
+}
+
+/// spawn server accepting requests from other members,
+/// connections from addresses which don't belong to any member are refused
+pub(crate) fn init_raft_server(
+    addr: String,
+    members: Vec<String>,
+    tls: Option<RaftTls>,
+    node: Arc<RaftNode>,
+) {
+    let acceptor = tls.map(|tls| tls.acceptor);
+    spawn(async move {
+        loop {
+            if let Err(err) =
+                start_raft_server(&addr, &members, acceptor.clone(), node.clone()).await
+            {
+                error!(%addr, "error running raft server: {}", err);
+            }
+            info!("raft server stopped. Trying to restart in 5 seconds");
+            sleep(Duration::from_secs(5)).await;
+        }
+    });
+}
+
+async fn start_raft_server(
+    addr: &str,
+    members: &[String],
+    acceptor: Option<TlsAcceptor>,
+    node: Arc<RaftNode>,
+) -> Result<(), IoError> {
+    let listener = TcpListener::bind(addr).await?;
+    info!(addr, tls = acceptor.is_some(), "raft server started");
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        let stream = stream?;
+        let peer = stream.peer_addr()?.ip();
+        if !is_member(peer, members).await {
+            warn!(%peer, "refusing raft connection from non member");
+            continue;
+        }
+        let node = node.clone();
+        let acceptor = acceptor.clone();
+        spawn(async move {
+            let result = match acceptor {
+                Some(acceptor) => match acceptor.accept(stream).await {
+                    Ok(stream) => handle_connection(stream, node).await,
+                    Err(err) => Err(err),
+                },
+                None => handle_connection(stream, node).await,
+            };
+            if let Err(err) = result {
+                debug!(%peer, "error serving raft connection: {}", err);
+            }
+        });
+    }
+    Ok(())
+}
+
+/// whether ip belongs to one of member addresses,
+/// addresses are resolved on every check since member ips may change
+async fn is_member(ip: IpAddr, members: &[String]) -> bool {
+    let members = members.to_vec();
+    let resolved: HashSet<IpAddr> = spawn_blocking(move || {
+        members
+            .iter()
+            .filter_map(|addr| addr.to_socket_addrs().ok())
+            .flatten()
+            .map(|addr| addr.ip())
+            .collect()
+    })
+    .await;
+    resolved.contains(&ip)
+}
+
+/// serve requests of single member until it disconnects
+async fn handle_connection<S>(mut stream: S, node: Arc<RaftNode>) -> Result<(), IoError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    loop {
+        let request: RaftRequest = match read_frame(&mut stream).await {
+            Ok(request) => request,
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        let response = node.handle(request).await;
+        write_frame(&mut stream, &response).await?;
+    }
+}
+
+async fn write_frame<W, T>(stream: &mut W, value: &T) -> Result<(), IoError>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let payload = serde_json::to_vec(value)?;
+    let len = u32::try_from(payload.len())
+        .map_err(|_| IoError::new(ErrorKind::InvalidData, "frame too large"))?;
+    stream.write_all(&len.to_be_bytes()).await?;
+    stream.write_all(&payload).await?;
+    stream.flush().await
+}
+
+async fn read_frame<R, T>(stream: &mut R) -> Result<T, IoError>
+where
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned,
+{
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len).await?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_FRAME_SIZE {
+        return Err(IoError::new(ErrorKind::InvalidData, "frame too large"));
+    }
+    // grow buffer only as payload arrives
+    let mut payload = Vec::with_capacity(len.min(READ_CHUNK_SIZE));
+    while payload.len() < len {
+        let start = payload.len();
+        let end = len.min(start + READ_CHUNK_SIZE);
+        payload.resize(end, 0);
+        stream.read_exact(&mut payload[start..end]).await?;
+    }
+    Ok(serde_json::from_slice(&payload)?)
+}
+
+#[cfg(test)]
+pub(crate) mod memory {
+    use std::collections::{BTreeMap, HashSet};
+    use std::sync::{Arc, Weak};
+
+    use anyhow::{Result, anyhow};
+    use async_trait::async_trait;
+    use parking_lot::RwLock;
+
+    use super::{NodeId, RaftNode, RaftRequest, RaftResponse, RaftTransport};
+
+    /// routes requests directly to nodes in same process
+    #[derive(Debug, Default)]
+    pub(crate) struct MemoryNetwork {
+        nodes: RwLock<BTreeMap<NodeId, Weak<RaftNode>>>,
+        isolated: RwLock<HashSet<NodeId>>,
+    }
+
+    impl MemoryNetwork {
+        pub(crate) fn register(&self, node: &Arc<RaftNode>) {
+            self.nodes.write().insert(node.id(), Arc::downgrade(node));
+        }
+
+        pub(crate) fn isolate(&self, id: NodeId) {
+            self.isolated.write().insert(id);
+        }
+
+        pub(crate) fn reconnect(&self, id: NodeId) {
+            self.isolated.write().remove(&id);
+        }
+
+        pub(crate) fn transport(self: &Arc<Self>, from: NodeId) -> MemoryTransport {
+            MemoryTransport {
+                from,
+                network: self.clone(),
+            }
+        }
+    }
+
+    pub(crate) struct MemoryTransport {
+        from: NodeId,
+        network: Arc<MemoryNetwork>,
+    }
+
+    #[async_trait]
+    impl RaftTransport for MemoryTransport {
+        async fn send(&self, to: NodeId, request: RaftRequest) -> Result<RaftResponse> {
+            {
+                let isolated = self.network.isolated.read();
+                if isolated.contains(&self.from) || isolated.contains(&to) {
+                    return Err(anyhow!("{to} is not reachable from {}", self.from));
+                }
+            }
+            let node = self
+                .network
+                .nodes
+                .read()
+                .get(&to)
+                .and_then(Weak::upgrade)
+                .ok_or_else(|| anyhow!("{to} is not running"))?;
+            Ok(node.handle(request).await)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::ErrorKind;
+
+    use futures_util::io::Cursor;
+
+    use super::{READ_CHUNK_SIZE, read_frame, write_frame};
+
+    #[fluvio_future::test]
+    async fn test_frame_read_in_chunks() {
+        let value = "x".repeat(3 * READ_CHUNK_SIZE + 1);
+        let mut buffer = Cursor::new(vec![]);
+        write_frame(&mut buffer, &value).await.expect("written");
+        buffer.set_position(0);
+
+        let read: String = read_frame(&mut buffer).await.expect("read");
+        assert_eq!(read, value);
+    }
+
+    #[fluvio_future::test]
+    async fn test_truncated_frame() {
+        // length prefix claims more than is sent
+        let mut frame = (64 * 1024 * 1024u32).to_be_bytes().to_vec();
+        frame.extend_from_slice(b"\"short\"");
+        let mut buffer = Cursor::new(frame);
+
+        let err = read_frame::<_, String>(&mut buffer)
+            .await
+            .expect_err("truncated");
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
@@ -34,6 +34,7 @@ pub const SPU_PRIVATE_HOSTNAME: &str = "0.0.0.0";
 pub const SPU_CREDENTIALS_FILE: &str = "/etc/fluvio/.credentials/token_secret";
 pub const SPU_RETRY_SC_TIMEOUT_MS: u16 = 3000;
 pub const SPU_MIN_IN_SYNC_REPLICAS: u16 = 1;
+pub const SPU_REPLICA_MAX_LAG_MS: u64 = 30_000;
 pub const SPU_LOG_BASE_DIR: &str = "/var/lib/fluvio/data";
 pub const SPU_LOG_SIZE: &str = "10Gi";
 pub const SPU_LOG_INDEX_MAX_BYTES: u32 = 10485760;
@@ -162,6 +162,12 @@ pub mod offsets {
             self.last_value
         }
 
+        /// mark the current value of publisher as seen, discarding any pending change
+        pub fn skip_pending(&mut self) -> i64 {
+            self.last_value = self.current_value();
+            self.last_value
+        }
+
         // wait for new values from publisher in lock-free fashin
         pub async fn listen(&mut self) -> i64 {
             if let Some(new_value) = self.has_new_value() {
@@ -303,4 +309,16 @@ mod test {
             assert!(status.load(Ordering::SeqCst), "status should be set");
         }
     }
+
+    #[fluvio_future::test]
+    async fn test_offset_listener_skip_pending() {
+        let publisher = OffsetPublisher::shared(0);
+        let mut listener = publisher.change_listener();
+
+        publisher.update(5);
+        assert_eq!(listener.skip_pending(), 5);
+
+        publisher.update(3);
+        assert_eq!(listener.listen().await, 3);
+    }
 }
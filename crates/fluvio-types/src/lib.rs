@@ -4,6 +4,7 @@ pub mod compression;
 pub mod defaults;
 pub mod macros;
 pub mod partition;
+pub mod partitioner;
 pub mod config_file;
 
 #[cfg(feature = "events")]
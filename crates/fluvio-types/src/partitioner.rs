@@ -0,0 +1,77 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use serde::{Serialize, Deserialize};
+
+/// Built-in partitioners that can be selected by name
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Serialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum PartitionerKind {
+    /// Keys are hashed with siphash, key-less records are spread one by one across partitions
+    #[default]
+    #[serde(alias = "round_robin", alias = "siphash")]
+    RoundRobin,
+    /// Keys are hashed with siphash, key-less records stick to one partition
+    /// until a batch is filled or linger expires
+    Sticky,
+    /// Keys are placed the same way as Kafka default partitioner (murmur2),
+    /// key-less records stick to one partition
+    Murmur2,
+}
+
+impl Display for PartitionerKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::RoundRobin => "round-robin",
+            Self::Sticky => "sticky",
+            Self::Murmur2 => "murmur2",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for PartitionerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "round-robin" | "round_robin" | "roundrobin" | "siphash" => Ok(Self::RoundRobin),
+            "sticky" => Ok(Self::Sticky),
+            "murmur2" | "kafka" => Ok(Self::Murmur2),
+            _ => Err(format!(
+                "unrecognized partitioner: {s}. Supported: round-robin, sticky, murmur2"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partitioner_kind_from_str() {
+        assert_eq!(
+            "round-robin".parse::<PartitionerKind>(),
+            Ok(PartitionerKind::RoundRobin)
+        );
+        assert_eq!(
+            "Sticky".parse::<PartitionerKind>(),
+            Ok(PartitionerKind::Sticky)
+        );
+        assert_eq!(
+            "murmur2".parse::<PartitionerKind>(),
+            Ok(PartitionerKind::Murmur2)
+        );
+        assert!("random".parse::<PartitionerKind>().is_err());
+
+        for kind in [
+            PartitionerKind::RoundRobin,
+            PartitionerKind::Sticky,
+            PartitionerKind::Murmur2,
+        ] {
+            assert_eq!(kind.to_string().parse::<PartitionerKind>(), Ok(kind));
+        }
+    }
+}
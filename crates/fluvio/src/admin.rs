@@ -18,6 +18,7 @@ use fluvio_sc_schema::objects::{
     CommonCreateRequest,
 };
 use fluvio_sc_schema::{AdminSpec, DeletableAdminSpec, CreatableAdminSpec, TryEncodableFrom};
+use fluvio_sc_schema::partition::{PartitionUsage, PartitionUsageRequest};
 use fluvio_socket::{ClientConfig, VersionedSerialSocket, SerialFrame, MultiplexerSocket};
 
 use crate::FluvioClusterConfig;
@@ -122,7 +123,7 @@ impl FluvioAdmin {
         let client_config =
             ClientConfig::new(&config.endpoint, connector, config.use_spu_local_address);
         let inner_client = client_config.connect().await?;
-        debug!(addr = %inner_client.config().addr(), "connected to cluster");
+        debug!(addr = ?inner_client.config().connected_addr(), "connected to cluster");
 
         let (socket, config, versions) = inner_client.split();
         if let Some(watch_version) = versions.lookup_version::<ObjectApiWatchRequest>() {
@@ -364,6 +365,21 @@ impl FluvioAdmin {
         });
         Ok(Box::pin(mapped_stream))
     }
+
+    /// Storage usage and lag of partitions
+    #[instrument(skip(self))]
+    pub async fn partition_usage(
+        &self,
+        request: PartitionUsageRequest,
+    ) -> Result<Vec<PartitionUsage>> {
+        let version = self
+            .socket
+            .lookup_version::<PartitionUsageRequest>()
+            .ok_or(anyhow!("partition usage is not supported by the cluster"))?;
+        let req_msg = self.socket.new_request(request, Some(version));
+        let response = self.socket.send_and_receive(req_msg).await?;
+        Ok(response.partitions)
+    }
 }
 
 /// API for streaming cached metadata
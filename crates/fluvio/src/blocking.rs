@@ -0,0 +1,524 @@
+//! Blocking (synchronous) facade over the async Fluvio client.
+//!
+//! Every type in this module wraps its async counterpart and drives it to
+//! completion on the Fluvio runtime, so it can be used from code that is not
+//! running inside an async executor. Each call is bounded by the timeout of
+//! the [`Fluvio`] instance it was created from; when the timeout elapses the
+//! call fails with [`FluvioError::Timeout`].
+//!
+//! These wrappers must not be called from within an async context, since
+//! blocking the executor thread may deadlock the client.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use fluvio::{Offset, RecordKey};
+//! use fluvio::blocking::Fluvio;
+//! use fluvio::consumer::ConsumerConfigExt;
+//!
+//! fn produce_and_consume() -> anyhow::Result<()> {
+//!     let fluvio = Fluvio::connect()?.with_timeout(Duration::from_secs(10));
+//!
+//!     let producer = fluvio.topic_producer("my-topic")?;
+//!     producer.send(RecordKey::NULL, "Hello, Fluvio!")?;
+//!     producer.flush()?;
+//!
+//!     let config = ConsumerConfigExt::builder()
+//!         .topic("my-topic")
+//!         .offset_start(Offset::beginning())
+//!         .build()?;
+//!     for record in fluvio.consumer_with_config(config)?.take(1) {
+//!         println!("{}", String::from_utf8_lossy(record?.as_ref()));
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures_util::StreamExt;
+
+use fluvio_future::task::run_block_on;
+use fluvio_protocol::record::{RecordData, ReplicaKey};
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_sc_schema::objects::{CommonCreateRequest, ListFilter, Metadata};
+use fluvio_sc_schema::partition::{PartitionUsage, PartitionUsageRequest};
+use fluvio_sc_schema::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec, UpdatableAdminSpec};
+
+use crate::consumer::{
+    BoxConsumerStream, ConsumerBatch, ConsumerConfigExt, ConsumerOffset, ConsumerStream, Record,
+};
+use crate::metrics::ClientMetrics;
+use crate::producer::{ProduceOutput as AsyncProduceOutput, RecordMetadata, TopicProducerConfig};
+use crate::{FluvioClusterConfig, FluvioError, Offset, PartitionId, RecordKey, TopicProducerPool};
+
+/// Timeout applied to blocking calls unless overridden with [`Fluvio::with_timeout`]
+pub const DEFAULT_BLOCKING_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Runs `future` to completion on the current thread, failing if it does not
+/// complete within `timeout`.
+fn block_on<T, F>(timeout: Duration, future: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    run_block_on(async move {
+        match fluvio_future::future::timeout(timeout, future).await {
+            Ok(result) => result,
+            Err(_) => Err(FluvioError::Timeout(timeout).into()),
+        }
+    })
+}
+
+/// Blocking counterpart of [`crate::Fluvio`]
+pub struct Fluvio {
+    inner: crate::Fluvio,
+    timeout: Duration,
+}
+
+impl Fluvio {
+    /// Creates a new client using the current profile from `~/.fluvio/config`
+    pub fn connect() -> Result<Self> {
+        let inner = block_on(DEFAULT_BLOCKING_TIMEOUT, crate::Fluvio::connect())?;
+        Ok(Self::from_async(inner))
+    }
+
+    /// Creates a new client with the given configuration
+    pub fn connect_with_config(config: &FluvioClusterConfig) -> Result<Self> {
+        let inner = block_on(
+            DEFAULT_BLOCKING_TIMEOUT,
+            crate::Fluvio::connect_with_config(config),
+        )?;
+        Ok(Self::from_async(inner))
+    }
+
+    /// Creates a new client with the given profile
+    pub fn connect_with_profile(profile: &str) -> Result<Self> {
+        let inner = block_on(
+            DEFAULT_BLOCKING_TIMEOUT,
+            crate::Fluvio::connect_with_profile(profile),
+        )?;
+        Ok(Self::from_async(inner))
+    }
+
+    /// Wraps an already connected async client
+    pub fn from_async(inner: crate::Fluvio) -> Self {
+        Self {
+            inner,
+            timeout: DEFAULT_BLOCKING_TIMEOUT,
+        }
+    }
+
+    /// Sets the timeout for calls made through this client and every producer,
+    /// consumer or admin created from it afterwards
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns the underlying async client
+    pub fn as_async(&self) -> &crate::Fluvio {
+        &self.inner
+    }
+
+    /// Creates a producer for the given topic with the default configuration
+    pub fn topic_producer(&self, topic: impl Into<String>) -> Result<TopicProducer> {
+        let inner = block_on(self.timeout, self.inner.topic_producer(topic))?;
+        Ok(TopicProducer {
+            inner,
+            timeout: self.timeout,
+        })
+    }
+
+    /// Creates a producer for the given topic with a custom configuration
+    pub fn topic_producer_with_config(
+        &self,
+        topic: impl Into<String>,
+        config: TopicProducerConfig,
+    ) -> Result<TopicProducer> {
+        let inner = block_on(
+            self.timeout,
+            self.inner.topic_producer_with_config(topic, config),
+        )?;
+        Ok(TopicProducer {
+            inner,
+            timeout: self.timeout,
+        })
+    }
+
+    /// Creates a consumer that yields records as an [`Iterator`]
+    pub fn consumer_with_config(&self, config: ConsumerConfigExt) -> Result<ConsumerIterator> {
+        let stream = block_on(self.timeout, self.inner.consumer_with_config(config))?;
+        Ok(ConsumerIterator {
+            stream: Box::pin(stream),
+            timeout: self.timeout,
+        })
+    }
+
+    /// Returns all consumer offsets currently stored in the cluster
+    pub fn consumer_offsets(&self) -> Result<Vec<ConsumerOffset>> {
+        block_on(self.timeout, self.inner.consumer_offsets())
+    }
+
+    /// Returns the offset committed by the consumer for the replica, with its metadata
+    pub fn consumer_offset(
+        &self,
+        consumer_id: impl Into<String>,
+        replica_id: impl Into<ReplicaKey>,
+    ) -> Result<Option<ConsumerOffset>> {
+        block_on(
+            self.timeout,
+            self.inner.consumer_offset(consumer_id, replica_id),
+        )
+    }
+
+    /// Deletes the consumer offset for the given consumer and replica
+    pub fn delete_consumer_offset(
+        &self,
+        consumer_id: impl Into<String>,
+        replica_id: impl Into<ReplicaKey>,
+    ) -> Result<()> {
+        block_on(
+            self.timeout,
+            self.inner.delete_consumer_offset(consumer_id, replica_id),
+        )
+    }
+
+    /// Provides a blocking interface for managing the cluster
+    pub fn admin(&self) -> Result<FluvioAdmin> {
+        let inner = block_on(self.timeout, async { Ok(self.inner.admin().await) })?;
+        Ok(FluvioAdmin {
+            inner,
+            timeout: self.timeout,
+        })
+    }
+
+    pub fn platform_version(&self) -> &semver::Version {
+        self.inner.platform_version()
+    }
+
+    pub fn metrics(&self) -> Arc<ClientMetrics> {
+        self.inner.metrics()
+    }
+}
+
+/// Blocking counterpart of [`crate::TopicProducer`]
+pub struct TopicProducer {
+    inner: TopicProducerPool,
+    timeout: Duration,
+}
+
+impl TopicProducer {
+    /// Enqueues a record to be sent.
+    ///
+    /// The record is batched like with the async producer; call
+    /// [`ProduceOutput::wait`] or [`TopicProducer::flush`] to make sure it was delivered.
+    pub fn send(
+        &self,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+    ) -> Result<ProduceOutput> {
+        let inner = block_on(self.timeout, self.inner.send(key, value))?;
+        Ok(ProduceOutput {
+            inner,
+            timeout: self.timeout,
+        })
+    }
+
+    /// Enqueues all given records to be sent
+    pub fn send_all(
+        &self,
+        records: impl IntoIterator<Item = (impl Into<RecordKey>, impl Into<RecordData>)>,
+    ) -> Result<Vec<ProduceOutput>> {
+        let outputs = block_on(self.timeout, self.inner.send_all(records))?;
+        Ok(outputs
+            .into_iter()
+            .map(|inner| ProduceOutput {
+                inner,
+                timeout: self.timeout,
+            })
+            .collect())
+    }
+
+    /// Sends all pending records and waits for them to be acknowledged
+    pub fn flush(&self) -> Result<()> {
+        block_on(self.timeout, self.inner.flush())
+    }
+
+    /// Clears partition producer errors so new records can be sent again
+    pub fn clear_errors(&self) -> Result<()> {
+        block_on(self.timeout, async {
+            self.inner.clear_errors().await;
+            Ok(())
+        })
+    }
+
+    pub fn metrics(&self) -> Arc<ClientMetrics> {
+        self.inner.metrics()
+    }
+
+    /// Returns the underlying async producer
+    pub fn as_async(&self) -> &TopicProducerPool {
+        &self.inner
+    }
+}
+
+/// Blocking counterpart of [`crate::ProduceOutput`]
+pub struct ProduceOutput {
+    inner: AsyncProduceOutput,
+    timeout: Duration,
+}
+
+impl ProduceOutput {
+    /// Waits until the record has been acknowledged by the cluster
+    pub fn wait(self) -> Result<RecordMetadata> {
+        block_on(self.timeout, async move { Ok(self.inner.wait().await?) })
+    }
+
+    /// Waits for all records produced by the SmartModule chain to be acknowledged
+    #[cfg(feature = "smartengine")]
+    pub fn wait_all(self) -> Result<Vec<RecordMetadata>> {
+        block_on(
+            self.timeout,
+            async move { Ok(self.inner.wait_all().await?) },
+        )
+    }
+}
+
+/// Blocking consumer yielding records as an [`Iterator`].
+///
+/// Each call to [`Iterator::next`] waits at most the client timeout for a new
+/// record and yields [`FluvioError::Timeout`] otherwise. A timeout does not end
+/// the iteration: the next call keeps waiting on the same stream.
+/// The iteration ends once the underlying stream is closed.
+pub struct ConsumerIterator {
+    stream: BoxConsumerStream,
+    timeout: Duration,
+}
+
+impl ConsumerIterator {
+    /// Waits at most `timeout` for the next record
+    pub fn next_timeout(&mut self, timeout: Duration) -> Option<Result<Record>> {
+        let next = block_on(timeout, async { Ok(self.stream.next().await) });
+        match next {
+            Ok(Some(Ok(record))) => Some(Ok(record)),
+            Ok(Some(Err(code))) => Some(Err(code.into())),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    /// Reads up to `max_records` records, waiting at most `max_wait` for them.
+    /// An error ends the batch and is returned by the next call.
+    /// Returns `None` once the stream has ended.
+    pub fn poll_batch(
+        &mut self,
+        max_records: usize,
+        max_wait: Duration,
+    ) -> Option<Result<ConsumerBatch>> {
+        let batch = block_on(max_wait + self.timeout, async {
+            Ok(self.stream.poll_batch(max_records, max_wait).await)
+        });
+        match batch {
+            Ok(Some(Ok(batch))) => Some(Ok(batch)),
+            Ok(Some(Err(code))) => Some(Err(code.into())),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    /// Marks the last record of every partition in `batch` as committed
+    pub fn offset_commit_batch(&mut self, batch: &ConsumerBatch) -> Result<()> {
+        block_on(self.timeout, async {
+            Ok(self.stream.offset_commit_batch(batch).await?)
+        })
+    }
+
+    /// Marks the last record of every partition in `batch` as committed, along with `metadata`
+    pub fn offset_commit_batch_with_metadata(
+        &mut self,
+        batch: &ConsumerBatch,
+        metadata: Vec<u8>,
+    ) -> Result<()> {
+        block_on(self.timeout, async {
+            Ok(self
+                .stream
+                .offset_commit_batch_with_metadata(batch, metadata)
+                .await?)
+        })
+    }
+
+    /// Marks the offset of the last yielded record as committed
+    pub fn offset_commit(&mut self) -> Result<()> {
+        block_on(self.timeout, async {
+            Ok(self.stream.offset_commit().await?)
+        })
+    }
+
+    /// Sends the committed offset to the cluster and waits for the acknowledgment
+    pub fn offset_flush(&mut self) -> Result<()> {
+        block_on(self.timeout, async {
+            Ok(self.stream.offset_flush().await?)
+        })
+    }
+
+    /// Marks `offset` of `partition` as committed
+    pub fn offset_commit_partition(&mut self, partition: PartitionId, offset: i64) -> Result<()> {
+        block_on(self.timeout, async {
+            Ok(self
+                .stream
+                .offset_commit_partition(partition, offset)
+                .await?)
+        })
+    }
+
+    /// Marks `offset` of `partition` as committed, along with `metadata`
+    pub fn offset_commit_with_metadata(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+        metadata: Vec<u8>,
+    ) -> Result<()> {
+        block_on(self.timeout, async {
+            Ok(self
+                .stream
+                .offset_commit_with_metadata(partition, offset, metadata)
+                .await?)
+        })
+    }
+
+    /// Continues reading `partition` from `offset`
+    pub fn seek(&mut self, partition: PartitionId, offset: Offset) -> Result<()> {
+        block_on(self.timeout, async {
+            Ok(self.stream.seek(partition, offset).await?)
+        })
+    }
+
+    /// Stops yielding records of `partitions` until they are resumed
+    pub fn pause(&mut self, partitions: &[PartitionId]) -> Result<()> {
+        block_on(self.timeout, async {
+            Ok(self.stream.pause(partitions).await?)
+        })
+    }
+
+    /// Resumes yielding records of paused `partitions`
+    pub fn resume(&mut self, partitions: &[PartitionId]) -> Result<()> {
+        block_on(self.timeout, async {
+            Ok(self.stream.resume(partitions).await?)
+        })
+    }
+}
+
+impl Iterator for ConsumerIterator {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_timeout(self.timeout)
+    }
+}
+
+/// Blocking counterpart of [`crate::FluvioAdmin`]
+pub struct FluvioAdmin {
+    inner: crate::FluvioAdmin,
+    timeout: Duration,
+}
+
+impl FluvioAdmin {
+    /// Creates a new object
+    pub fn create<S>(&self, name: String, dry_run: bool, spec: S) -> Result<()>
+    where
+        S: CreatableAdminSpec + Sync + Send,
+    {
+        block_on(self.timeout, self.inner.create(name, dry_run, spec))
+    }
+
+    pub fn create_with_config<S>(&self, config: CommonCreateRequest, spec: S) -> Result<()>
+    where
+        S: CreatableAdminSpec + Sync + Send,
+    {
+        block_on(self.timeout, self.inner.create_with_config(config, spec))
+    }
+
+    /// Deletes an object by key
+    pub fn delete<S>(&self, key: impl Into<S::DeleteKey>) -> Result<()>
+    where
+        S: DeletableAdminSpec + Sync + Send,
+    {
+        block_on(self.timeout, self.inner.delete::<S>(key))
+    }
+
+    /// Forcibly deletes an object by key, including objects marked as 'system'
+    pub fn force_delete<S>(&self, key: impl Into<S::DeleteKey>) -> Result<()>
+    where
+        S: DeletableAdminSpec + Sync + Send,
+    {
+        block_on(self.timeout, self.inner.force_delete::<S>(key))
+    }
+
+    /// Updates an object by key
+    pub fn update<S>(&self, key: impl Into<S::UpdateKey>, action: S::UpdateAction) -> Result<()>
+    where
+        S: UpdatableAdminSpec + Sync + Send,
+    {
+        block_on(self.timeout, self.inner.update::<S>(key, action))
+    }
+
+    /// Returns all instances of this spec
+    pub fn all<S>(&self) -> Result<Vec<Metadata<S>>>
+    where
+        S: AdminSpec,
+        S::Status: Encoder + Decoder + Debug,
+    {
+        block_on(self.timeout, self.inner.all::<S>())
+    }
+
+    /// Returns all instances of this spec matching the filters
+    pub fn list<S, F>(&self, filters: Vec<F>) -> Result<Vec<Metadata<S>>>
+    where
+        S: AdminSpec,
+        ListFilter: From<F>,
+        S::Status: Encoder + Decoder + Debug,
+    {
+        block_on(self.timeout, self.inner.list::<S, F>(filters))
+    }
+
+    pub fn partition_usage(&self, request: PartitionUsageRequest) -> Result<Vec<PartitionUsage>> {
+        block_on(self.timeout, self.inner.partition_usage(request))
+    }
+
+    /// Returns the underlying async admin
+    pub fn as_async(&self) -> &crate::FluvioAdmin {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_on_completes() {
+        let value = block_on(Duration::from_secs(1), async { Ok(42) }).expect("value");
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    fn test_block_on_times_out() {
+        let timeout = Duration::from_millis(10);
+        let err =
+            block_on(timeout, futures_util::future::pending::<Result<()>>()).expect_err("timeout");
+        assert!(matches!(
+            err.downcast_ref::<FluvioError>(),
+            Some(FluvioError::Timeout(t)) if *t == timeout
+        ));
+    }
+}
@@ -19,7 +19,8 @@ pub type FluvioConfig = FluvioClusterConfig;
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct FluvioClusterConfig {
-    /// The address to connect to the Fluvio cluster
+    /// The address to connect to the Fluvio cluster.
+    /// Comma separated addresses are tried in order, which allows failover between replicated SCs
     // TODO use a validated address type.
     // We don't want to have a "" address.
     #[serde(alias = "addr")]
@@ -38,6 +39,14 @@ pub struct FluvioClusterConfig {
     #[serde(default = "Metadata::new", skip_serializing_if = "Metadata::is_empty")]
     metadata: Metadata,
 
+    /// Placement of this client in the cluster
+    #[serde(default, skip_serializing_if = "ClientPlacement::is_empty")]
+    pub client: ClientPlacement,
+
+    /// Connections opened to every SPU
+    #[serde(default, skip_serializing_if = "SpuConnectionConfig::is_default")]
+    pub connections: SpuConnectionConfig,
+
     /// This is not part of profile and doesn't persist.
     /// It is purely to override client id when creating ClientConfig
     #[serde(skip)]
@@ -67,6 +76,8 @@ impl FluvioClusterConfig {
             use_spu_local_address: false,
             tls: TlsPolicy::Disabled,
             metadata: Metadata::new(),
+            client: ClientPlacement::default(),
+            connections: SpuConnectionConfig::default(),
             client_id: None,
         }
     }
@@ -109,6 +120,59 @@ impl FluvioClusterConfig {
     }
 }
 
+/// Location of client, used to read from nearest replica
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientPlacement {
+    /// rack or zone of client, matched against SPU rack
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rack: Option<String>,
+}
+
+impl ClientPlacement {
+    fn is_empty(&self) -> bool {
+        self.rack.is_none()
+    }
+}
+
+/// How requests to an SPU are spread over connections.
+///
+/// By default every producer and consumer of the client shares one multiplexed connection per SPU.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpuConnectionConfig {
+    /// connections per SPU in each pool, requests for the same partition always use the same connection
+    #[serde(default = "default_connections_per_spu")]
+    pub per_spu: usize,
+
+    /// use different connections for producing and fetching,
+    /// so large fetches do not delay produce acknowledgements
+    #[serde(default)]
+    pub separate_pools: bool,
+
+    /// maximum requests awaiting a response on a connection, further requests are queued
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_in_flight: Option<usize>,
+}
+
+fn default_connections_per_spu() -> usize {
+    1
+}
+
+impl Default for SpuConnectionConfig {
+    fn default() -> Self {
+        Self {
+            per_spu: default_connections_per_spu(),
+            separate_pools: false,
+            max_in_flight: None,
+        }
+    }
+}
+
+impl SpuConnectionConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 impl TryFrom<FluvioClusterConfig> for fluvio_socket::ClientConfig {
     type Error = anyhow::Error;
     fn try_from(config: FluvioClusterConfig) -> Result<Self, Self::Error> {
@@ -158,6 +222,49 @@ name = "foo"
         );
     }
 
+    #[test]
+    fn test_client_rack() {
+        let toml = r#"version = "2"
+[profile.local]
+cluster = "local"
+
+[cluster.local]
+endpoint = "127.0.0.1:9003"
+
+[cluster.local.client]
+rack = "us-east-1a"
+"#;
+        let profile = Config::load_str(toml).unwrap();
+        let config = profile.cluster("local").unwrap();
+
+        assert_eq!(config.client.rack.as_deref(), Some("us-east-1a"));
+    }
+
+    #[test]
+    fn test_spu_connections() {
+        let toml = r#"version = "2"
+[profile.local]
+cluster = "local"
+
+[cluster.local]
+endpoint = "127.0.0.1:9003"
+
+[cluster.local.connections]
+per_spu = 4
+separate_pools = true
+max_in_flight = 16
+"#;
+        let profile = Config::load_str(toml).unwrap();
+        let config = profile.cluster("local").unwrap();
+
+        assert_eq!(config.connections.per_spu, 4);
+        assert!(config.connections.separate_pools);
+        assert_eq!(config.connections.max_in_flight, Some(16));
+
+        let toml = toml::to_string(&super::FluvioClusterConfig::new("127.0.0.1:9003")).unwrap();
+        assert!(!toml.contains("connections"));
+    }
+
     #[test]
     fn test_create_metadata() {
         let toml = r#"version = "2"
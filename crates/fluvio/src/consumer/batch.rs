@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use fluvio_types::PartitionId;
+
+use super::Record;
+
+/// Decoded records read by a single [`poll_batch`] call, possibly across partitions.
+///
+/// [`poll_batch`]: super::ConsumerStream::poll_batch
+#[derive(Default)]
+pub struct ConsumerBatch {
+    records: Vec<Record>,
+}
+
+impl ConsumerBatch {
+    pub(crate) fn new(records: Vec<Record>) -> Self {
+        Self { records }
+    }
+
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    pub fn into_records(self) -> Vec<Record> {
+        self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Record> {
+        self.records.iter()
+    }
+
+    /// Offset of the last record of every partition in the batch
+    pub fn last_offsets(&self) -> BTreeMap<PartitionId, i64> {
+        let mut offsets = BTreeMap::new();
+        for record in &self.records {
+            offsets
+                .entry(record.partition())
+                .and_modify(|offset: &mut i64| *offset = (*offset).max(record.offset()))
+                .or_insert(record.offset());
+        }
+        offsets
+    }
+}
+
+impl IntoIterator for ConsumerBatch {
+    type Item = Record;
+    type IntoIter = std::vec::IntoIter<Record>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ConsumerBatch {
+    type Item = &'a Record;
+    type IntoIter = std::slice::Iter<'a, Record>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::Batch;
+    use fluvio_smartmodule::RecordData;
+
+    use super::*;
+
+    fn records(partition: PartitionId, input: &[&'static str]) -> Vec<Record> {
+        let mut records: Vec<_> = input
+            .iter()
+            .map(|item| fluvio_protocol::record::Record::new(RecordData::from(item.as_bytes())))
+            .collect();
+        let mut batch = Batch::default();
+        batch.add_records(&mut records);
+        batch.into_consumer_records_iter(partition).collect()
+    }
+
+    #[test]
+    fn test_last_offsets() {
+        let mut input = records(1, &["a", "b", "c"]);
+        input.extend(records(0, &["d"]));
+        let batch = ConsumerBatch::new(input);
+
+        assert_eq!(batch.len(), 4);
+        assert_eq!(
+            batch.last_offsets().into_iter().collect::<Vec<_>>(),
+            [(0, 0), (1, 2)]
+        );
+        assert!(ConsumerBatch::default().last_offsets().is_empty());
+    }
+}
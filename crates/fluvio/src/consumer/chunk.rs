@@ -0,0 +1,403 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use futures_util::future::ready;
+use futures_util::stream::{Stream, StreamExt};
+use tracing::warn;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{CHUNK_HEADER, RecordChunk, RecordData};
+
+use super::Record;
+
+const DEFAULT_REASSEMBLY_MAX_BYTES: usize = 64 * 1024 * 1024;
+const DEFAULT_REASSEMBLY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Limits for reassembling records which the producer split into chunks.
+///
+/// Values over the limits are dropped with a warning, they are never yielded partially.
+/// SmartModules of the consumer run on the SPU, which passes them the chunks without
+/// their chunk header, so chunked values are not reassembled when consuming with SmartModules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkReassemblyConfig {
+    /// Bytes buffered for values still missing chunks, oldest values are dropped beyond it
+    pub max_bytes: usize,
+    /// Time to wait for the last chunk of a value after its first chunk was read
+    pub timeout: Duration,
+}
+
+impl Default for ChunkReassemblyConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_REASSEMBLY_MAX_BYTES,
+            timeout: DEFAULT_REASSEMBLY_TIMEOUT,
+        }
+    }
+}
+
+/// Value of which some chunks were read
+struct PendingValue {
+    id: u64,
+    /// offset of the first chunk
+    first_offset: i64,
+    next_index: u32,
+    value: Vec<u8>,
+    /// bytes accounted against reassembly memory
+    reserved: usize,
+    started: Instant,
+}
+
+/// Joins chunk records of a partition back into the original records.
+///
+/// Chunks of a value are in order, but may be interleaved with other records.
+/// The reassembled record takes the offset of its last chunk.
+pub(crate) struct ChunkReassembly {
+    config: ChunkReassemblyConfig,
+    /// oldest first
+    pending: Vec<PendingValue>,
+    buffered: usize,
+    pending_chunks: PendingChunks,
+}
+
+/// Offset of the first chunk of the earliest value still missing chunks.
+/// Shared with the stream tracking consumed offsets, so that commits don't skip these chunks.
+#[derive(Debug, Clone)]
+pub(crate) struct PendingChunks(Arc<AtomicI64>);
+
+impl Default for PendingChunks {
+    fn default() -> Self {
+        Self(Arc::new(AtomicI64::new(i64::MAX)))
+    }
+}
+
+impl PendingChunks {
+    /// Highest offset which can be committed once the record at `offset` was read
+    pub(crate) fn committable(&self, offset: i64) -> i64 {
+        offset.min(self.0.load(Ordering::Relaxed).saturating_sub(1))
+    }
+
+    fn set(&self, first_offset: Option<i64>) {
+        self.0
+            .store(first_offset.unwrap_or(i64::MAX), Ordering::Relaxed);
+    }
+}
+
+impl ChunkReassembly {
+    pub(crate) fn new(config: ChunkReassemblyConfig) -> Self {
+        Self {
+            config,
+            pending: Vec::new(),
+            buffered: 0,
+            pending_chunks: PendingChunks::default(),
+        }
+    }
+
+    pub(crate) fn pending_chunks(&self) -> PendingChunks {
+        self.pending_chunks.clone()
+    }
+
+    /// Returns the record if it is complete, either not chunked or the last chunk of a value
+    pub(crate) fn push(&mut self, record: Record) -> Option<Record> {
+        self.expire(Instant::now());
+        let output = self.reassemble(record);
+        self.pending_chunks.set(
+            self.pending
+                .iter()
+                .map(|pending| pending.first_offset)
+                .min(),
+        );
+        output
+    }
+
+    fn reassemble(&mut self, mut record: Record) -> Option<Record> {
+        let Some(chunk) = RecordChunk::extract(record.headers()) else {
+            return Some(record);
+        };
+
+        if chunk.index() == 0 {
+            // value read again after seeking back
+            self.discard(chunk.id());
+            if chunk.value_len() > self.config.max_bytes as u64 {
+                warn!(
+                    offset = record.offset,
+                    value_len = chunk.value_len(),
+                    max_bytes = self.config.max_bytes,
+                    "chunked value exceeds reassembly memory, dropping it"
+                );
+                return None;
+            }
+            let capacity = chunk.value_len() as usize;
+            self.evict(capacity);
+            self.buffered += capacity;
+            self.pending.push(PendingValue {
+                id: chunk.id(),
+                first_offset: record.offset,
+                next_index: 0,
+                value: Vec::with_capacity(capacity),
+                reserved: capacity,
+                started: Instant::now(),
+            });
+        }
+
+        let Some(position) = self
+            .pending
+            .iter()
+            .position(|pending| pending.id == chunk.id())
+        else {
+            // first chunks were dropped or read before the start offset
+            return None;
+        };
+        let pending = &mut self.pending[position];
+        if pending.next_index != chunk.index() {
+            warn!(
+                offset = record.offset,
+                index = chunk.index(),
+                expected = pending.next_index,
+                "chunk out of order, dropping value"
+            );
+            self.remove(position);
+            return None;
+        }
+        if pending.value.len() + record.value().len() > pending.reserved {
+            warn!(
+                offset = record.offset,
+                value_len = chunk.value_len(),
+                "chunks exceed length of value, dropping it"
+            );
+            self.remove(position);
+            return None;
+        }
+        pending.next_index += 1;
+        pending.value.extend_from_slice(record.value());
+
+        if !chunk.is_last() {
+            return None;
+        }
+
+        let pending = self.remove(position);
+        if pending.value.len() as u64 != chunk.value_len() {
+            warn!(
+                offset = record.offset,
+                len = pending.value.len(),
+                value_len = chunk.value_len(),
+                "chunked value has wrong length, dropping it"
+            );
+            return None;
+        }
+        record.record.value = RecordData::from(pending.value);
+        record.record.headers_mut().remove(CHUNK_HEADER);
+        Some(record)
+    }
+
+    /// Drop values which did not complete in time
+    fn expire(&mut self, now: Instant) {
+        while let Some(oldest) = self.pending.first() {
+            if now.duration_since(oldest.started) < self.config.timeout {
+                break;
+            }
+            warn!(
+                id = oldest.id,
+                read_chunks = oldest.next_index,
+                "timed out waiting for chunks, dropping value"
+            );
+            self.remove(0);
+        }
+    }
+
+    /// Drop oldest values until `required` more bytes fit
+    fn evict(&mut self, required: usize) {
+        while !self.pending.is_empty() && self.buffered + required > self.config.max_bytes {
+            warn!(
+                id = self.pending[0].id,
+                "reassembly memory is full, dropping incomplete value"
+            );
+            self.remove(0);
+        }
+    }
+
+    fn discard(&mut self, id: u64) {
+        if let Some(position) = self.pending.iter().position(|pending| pending.id == id) {
+            self.remove(position);
+        }
+    }
+
+    fn remove(&mut self, position: usize) -> PendingValue {
+        let pending = self.pending.remove(position);
+        self.buffered -= pending.reserved;
+        pending
+    }
+}
+
+/// Reassembles chunked records of a single partition stream
+pub(crate) fn reassemble_chunks<S>(
+    stream: S,
+    mut reassembly: ChunkReassembly,
+) -> impl Stream<Item = Result<Record, ErrorCode>>
+where
+    S: Stream<Item = Result<Record, ErrorCode>>,
+{
+    stream.filter_map(move |result| {
+        ready(match result {
+            Ok(record) => reassembly.push(record).map(Ok),
+            Err(err) => Some(Err(err)),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::{Batch, Record as ProducerRecord};
+
+    use super::*;
+
+    fn chunk(id: u64, index: u32, count: u32, value_len: u64, value: &str) -> ProducerRecord {
+        let mut record = ProducerRecord::new(value.to_owned());
+        RecordChunk::new(id, index, count, value_len).inject(record.headers_mut());
+        record.headers_mut().insert("traceparent", "abc");
+        record
+    }
+
+    fn consumer_records(mut input: Vec<ProducerRecord>) -> Vec<Record> {
+        let mut batch = Batch::default();
+        batch.add_records(&mut input);
+        batch.into_consumer_records_iter(0).collect()
+    }
+
+    fn reassemble(config: ChunkReassemblyConfig, input: Vec<ProducerRecord>) -> Vec<Record> {
+        let mut reassembly = ChunkReassembly::new(config);
+        consumer_records(input)
+            .into_iter()
+            .filter_map(|record| reassembly.push(record))
+            .collect()
+    }
+
+    #[test]
+    fn test_reassemble_interleaved_chunks() {
+        let output = reassemble(
+            ChunkReassemblyConfig::default(),
+            vec![
+                chunk(1, 0, 2, 6, "abc"),
+                ProducerRecord::new("plain"),
+                chunk(2, 0, 1, 3, "xyz"),
+                chunk(1, 1, 2, 6, "def"),
+            ],
+        );
+
+        let values: Vec<_> = output.iter().map(|record| record.value()).collect();
+        assert_eq!(values, [b"plain".as_slice(), b"xyz", b"abcdef"]);
+        assert_eq!(output[2].offset, 3, "takes offset of last chunk");
+        assert_eq!(output[2].headers().get(CHUNK_HEADER), None);
+        assert_eq!(
+            output[2].headers().get("traceparent"),
+            Some(b"abc".as_slice())
+        );
+    }
+
+    #[test]
+    fn test_drop_incomplete_values() {
+        // missing first chunk, missing middle chunk, wrong length
+        let output = reassemble(
+            ChunkReassemblyConfig::default(),
+            vec![
+                chunk(1, 1, 2, 6, "def"),
+                chunk(2, 0, 3, 9, "abc"),
+                chunk(2, 2, 3, 9, "ghi"),
+                chunk(3, 0, 2, 7, "abc"),
+                chunk(3, 1, 2, 7, "def"),
+            ],
+        );
+        assert!(output.is_empty());
+
+        // chunks longer than value
+        let output = reassemble(
+            ChunkReassemblyConfig::default(),
+            vec![chunk(1, 0, 2, 4, "abc"), chunk(1, 1, 2, 4, "def")],
+        );
+        assert!(output.is_empty());
+
+        // value read again after seeking back
+        let output = reassemble(
+            ChunkReassemblyConfig::default(),
+            vec![
+                chunk(1, 0, 2, 6, "abc"),
+                chunk(1, 0, 2, 6, "abc"),
+                chunk(1, 1, 2, 6, "def"),
+            ],
+        );
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].value(), b"abcdef");
+    }
+
+    #[test]
+    fn test_reassembly_limits() {
+        let config = ChunkReassemblyConfig {
+            max_bytes: 10,
+            ..Default::default()
+        };
+        let output = reassemble(
+            config,
+            vec![
+                // larger than memory
+                chunk(1, 0, 4, 12, "abc"),
+                // evicted by next value
+                chunk(2, 0, 2, 6, "abc"),
+                chunk(3, 0, 2, 6, "uvw"),
+                chunk(2, 1, 2, 6, "def"),
+                chunk(3, 1, 2, 6, "xyz"),
+            ],
+        );
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].value(), b"uvwxyz");
+
+        let config = ChunkReassemblyConfig {
+            timeout: Duration::ZERO,
+            ..Default::default()
+        };
+        let output = reassemble(
+            config,
+            vec![chunk(1, 0, 2, 6, "abc"), chunk(1, 1, 2, 6, "def")],
+        );
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_committable_offset_with_pending_chunks() {
+        let mut reassembly = ChunkReassembly::new(ChunkReassemblyConfig::default());
+        let pending_chunks = reassembly.pending_chunks();
+        let committable: Vec<_> = consumer_records(vec![
+            ProducerRecord::new("plain"),
+            chunk(1, 0, 2, 6, "abc"),
+            ProducerRecord::new("plain"),
+            chunk(1, 1, 2, 6, "def"),
+        ])
+        .into_iter()
+        .filter_map(|record| {
+            let offset = record.offset;
+            reassembly
+                .push(record)
+                .map(|_| pending_chunks.committable(offset))
+        })
+        .collect();
+        assert_eq!(committable, [0, 0, 3]);
+
+        // expired on next record, even if it is not a chunk
+        let mut reassembly = ChunkReassembly::new(ChunkReassemblyConfig {
+            timeout: Duration::ZERO,
+            ..Default::default()
+        });
+        let pending_chunks = reassembly.pending_chunks();
+        let mut records =
+            consumer_records(vec![chunk(1, 0, 2, 6, "abc"), ProducerRecord::new("plain")])
+                .into_iter();
+        assert!(reassembly.push(records.next().unwrap()).is_none());
+        assert_eq!(pending_chunks.committable(0), -1);
+        assert!(reassembly.push(records.next().unwrap()).is_some());
+        assert_eq!(pending_chunks.committable(1), 1);
+    }
+}
@@ -9,6 +9,7 @@ use fluvio_types::PartitionId;
 use crate::{FluvioError, Offset};
 
 use super::MAX_FETCH_BYTES;
+use super::ChunkReassemblyConfig;
 
 const DEFAULT_OFFSET_FLUSH_PERIOD: Duration = Duration::from_secs(10);
 const DEFAULT_OFFSET_FLUSHER_CHECK_PERIOD: Duration = Duration::from_millis(100);
@@ -26,6 +27,10 @@ pub struct ConsumerConfig {
     pub isolation: Isolation,
     #[builder(default)]
     pub smartmodule: Vec<SmartModuleInvocation>,
+    /// read committed records from in sync replica in same rack as client,
+    /// configured by `client.rack` in profile
+    #[builder(default)]
+    pub read_from_follower: bool,
 }
 
 impl ConsumerConfig {
@@ -106,6 +111,13 @@ pub struct ConsumerConfigExt {
     pub smartmodule: Vec<SmartModuleInvocation>,
     #[builder(default = "DEFAULT_RETRY_MODE")]
     pub retry_mode: RetryMode,
+    /// read committed records from in sync replica in same rack as client,
+    /// configured by `client.rack` in profile
+    #[builder(default)]
+    pub read_from_follower: bool,
+    /// limits for reassembling records split into chunks by the producer
+    #[builder(default)]
+    pub chunk_reassembly: ChunkReassemblyConfig,
 }
 
 impl ConsumerConfigExt {
@@ -137,6 +149,8 @@ impl ConsumerConfigExt {
             offset_flush,
             offset_flusher_check_period,
             retry_mode: _,
+            read_from_follower,
+            chunk_reassembly: _,
         } = self;
 
         let config = ConsumerConfig {
@@ -144,6 +158,7 @@ impl ConsumerConfigExt {
             max_bytes,
             isolation,
             smartmodule,
+            read_from_follower,
         };
 
         (
@@ -197,6 +212,7 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             isolation,
             smartmodule,
             retry_mode: _,
+            read_from_follower,
         } = value;
 
         Self {
@@ -204,6 +220,7 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             max_bytes,
             isolation,
             smartmodule,
+            read_from_follower,
         }
     }
 }
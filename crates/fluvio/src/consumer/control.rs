@@ -0,0 +1,200 @@
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::task::Waker;
+
+use async_channel::{Sender, bounded};
+use futures_util::task::AtomicWaker;
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_types::PartitionId;
+
+use crate::Offset;
+use super::{StreamToServer, StreamToServerCallback};
+
+const DEFAULT_ORDERING: Ordering = Ordering::Relaxed;
+
+/// Read position of a partition stream.
+///
+/// Every seek starts a new epoch. The SPU tags the responses it sends with the
+/// epoch of the last seek it processed, so records which were already in
+/// flight when the consumer moved the stream can be recognized and dropped.
+#[derive(Debug, Default)]
+pub(crate) struct StreamPosition {
+    epoch: AtomicU32,
+    floor: AtomicI64,
+}
+
+impl StreamPosition {
+    pub(crate) fn new(start_offset: i64) -> Self {
+        Self {
+            epoch: AtomicU32::new(0),
+            floor: AtomicI64::new(start_offset),
+        }
+    }
+
+    pub(crate) fn epoch(&self) -> u32 {
+        self.epoch.load(DEFAULT_ORDERING)
+    }
+
+    /// First offset the stream yields in the current epoch
+    pub(crate) fn floor(&self) -> i64 {
+        self.floor.load(DEFAULT_ORDERING)
+    }
+
+    /// Moves the stream to `offset` in a new epoch. Returns the previous
+    /// position, to be restored if the SPU rejects the seek.
+    pub(crate) fn seek(&self, offset: i64) -> (u32, i64) {
+        let previous = (self.epoch(), self.floor());
+        self.floor.store(offset, DEFAULT_ORDERING);
+        self.epoch
+            .store(previous.0.wrapping_add(1), DEFAULT_ORDERING);
+        previous
+    }
+
+    pub(crate) fn restore(&self, (epoch, floor): (u32, i64)) {
+        self.floor.store(floor, DEFAULT_ORDERING);
+        self.epoch.store(epoch, DEFAULT_ORDERING);
+    }
+
+    /// Returns true if a record at `offset` read in `epoch` belongs to the current position
+    pub(crate) fn accepts(&self, epoch: u32, offset: i64) -> bool {
+        epoch == self.epoch() && offset >= self.floor()
+    }
+}
+
+/// Controls a running partition stream: seeking and pausing.
+pub(crate) struct PartitionControl {
+    partition: PartitionId,
+    stream_to_server: Sender<StreamToServer>,
+    paused: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl PartitionControl {
+    pub(crate) fn new(partition: PartitionId, stream_to_server: Sender<StreamToServer>) -> Self {
+        Self {
+            partition,
+            stream_to_server,
+            paused: AtomicBool::new(false),
+            waker: AtomicWaker::new(),
+        }
+    }
+
+    pub(crate) fn partition(&self) -> PartitionId {
+        self.partition
+    }
+
+    /// Moves the stream to `offset`, returning the resolved absolute offset
+    pub(crate) async fn seek(&self, offset: Offset) -> Result<i64, ErrorCode> {
+        let (sender, receiver) = bounded(1);
+        self.stream_to_server
+            .send(StreamToServer::Seek {
+                offset,
+                callback: StreamToServerCallback::Channel(sender),
+            })
+            .await
+            .map_err(|err| ErrorCode::Other(err.to_string()))?;
+        receiver
+            .recv()
+            .await
+            .map_err(|err| ErrorCode::Other(err.to_string()))?
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused.load(DEFAULT_ORDERING)
+    }
+
+    pub(crate) fn pause(&self) {
+        self.paused.store(true, DEFAULT_ORDERING);
+    }
+
+    pub(crate) fn resume(&self) {
+        self.paused.store(false, DEFAULT_ORDERING);
+        self.waker.wake();
+    }
+
+    /// Returns true if the stream is paused, registering `waker` to be woken on resume
+    pub(crate) fn poll_paused(&self, waker: &Waker) -> bool {
+        if !self.is_paused() {
+            return false;
+        }
+        self.waker.register(waker);
+        // resumed while registering
+        self.is_paused()
+    }
+}
+
+/// Finds the controls of `partitions` among `controls`
+pub(crate) fn find_controls<'a, T>(
+    controls: &'a [(Arc<PartitionControl>, T)],
+    partitions: &[PartitionId],
+) -> Result<Vec<&'a (Arc<PartitionControl>, T)>, ErrorCode> {
+    partitions
+        .iter()
+        .map(|partition| {
+            controls
+                .iter()
+                .find(|(control, _)| control.partition() == *partition)
+                .ok_or(ErrorCode::StreamPartitionNotFound(*partition))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_position_seek() {
+        let position = StreamPosition::new(10);
+        assert!(position.accepts(0, 10));
+        assert!(!position.accepts(0, 9));
+
+        let previous = position.seek(2);
+        assert_eq!(previous, (0, 10));
+        assert_eq!(position.epoch(), 1);
+        assert!(!position.accepts(0, 12), "in flight records are dropped");
+        assert!(position.accepts(1, 2));
+        assert!(!position.accepts(1, 1));
+
+        position.restore(previous);
+        assert!(position.accepts(0, 10));
+    }
+
+    #[fluvio_future::test]
+    async fn test_partition_control_seek() {
+        let (sender, receiver) = async_channel::unbounded();
+        let control = PartitionControl::new(1, sender);
+
+        fluvio_future::task::spawn(async move {
+            if let Ok(StreamToServer::Seek { offset, callback }) = receiver.recv().await {
+                assert_eq!(offset, Offset::absolute(5).expect("offset"));
+                callback.send(Ok(5)).await;
+            }
+        });
+
+        assert_eq!(
+            control
+                .seek(Offset::absolute(5).expect("offset"))
+                .await
+                .expect("seek"),
+            5
+        );
+    }
+
+    #[test]
+    fn test_find_controls() {
+        let (sender, _receiver) = async_channel::unbounded();
+        let controls = vec![
+            (Arc::new(PartitionControl::new(0, sender.clone())), ()),
+            (Arc::new(PartitionControl::new(2, sender)), ()),
+        ];
+
+        let found = find_controls(&controls, &[2]).expect("found");
+        assert_eq!(found[0].0.partition(), 2);
+        assert_eq!(
+            find_controls(&controls, &[0, 1]).unwrap_err(),
+            ErrorCode::StreamPartitionNotFound(1)
+        );
+    }
+}
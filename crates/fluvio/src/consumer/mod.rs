@@ -1,10 +1,14 @@
 #![allow(dead_code)]
 
+mod batch;
+mod chunk;
 mod config;
+mod control;
 mod stream;
 mod offset;
 mod retry;
 
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -18,7 +22,7 @@ use async_lock::Mutex;
 use fluvio_future::timer::sleep;
 use fluvio_socket::VersionedSerialSocket;
 use fluvio_spu_schema::server::consumer_offset::{
-    FetchConsumerOffsetsRequest, UpdateConsumerOffsetRequest,
+    CONSUMER_OFFSET_METADATA_API, FetchConsumerOffsetsRequest, UpdateConsumerOffsetRequest,
 };
 use tracing::{debug, error, trace, instrument, info, warn};
 use futures_util::stream::{Stream, select_all};
@@ -34,24 +38,31 @@ use fluvio_types::defaults::{
 };
 use fluvio_spu_schema::server::stream_fetch::{
     DefaultStreamFetchRequest, DefaultStreamFetchResponse, CHAIN_SMARTMODULE_API,
-    OFFSET_MANAGEMENT_API,
+    FOLLOWER_FETCH_API, OFFSET_MANAGEMENT_API, STREAM_SEEK_API,
 };
+use fluvio_spu_schema::server::stream_seek::StreamSeekRequest;
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::Batch;
+use fluvio_compression::Dictionary;
 
 use crate::FluvioError;
 use crate::metrics::ClientMetrics;
 use crate::offset::{Offset, fetch_offsets};
 use crate::spu::{SpuDirectory, SpuSocketPool};
 
+use self::chunk::{ChunkReassembly, reassemble_chunks};
+use self::control::StreamPosition;
+
 pub use config::{ConsumerConfig, ConsumerConfigBuilder};
 pub use config::{ConsumerConfigExt, ConsumerConfigExtBuilder, OffsetManagementStrategy, RetryMode};
+pub use batch::ConsumerBatch;
+pub use chunk::ChunkReassemblyConfig;
 pub use stream::{
     ConsumerStream, MultiplePartitionConsumerStream, SinglePartitionConsumerStream,
     ConsumerBoxFuture,
 };
-pub use offset::ConsumerOffset;
+pub use offset::{ConsumerOffset, MirroredOffset};
 pub use retry::ConsumerRetryStream;
 pub use fluvio_protocol::record::ConsumerRecord;
 
@@ -98,6 +109,7 @@ pub struct PartitionConsumer<P = SpuSocketPool> {
     partition: PartitionId,
     pool: Arc<P>,
     metrics: Arc<ClientMetrics>,
+    dictionary: Option<Dictionary>,
 }
 
 // Manually implement Clone because the derive macro would require the
@@ -109,6 +121,7 @@ impl<P> Clone for PartitionConsumer<P> {
             partition: self.partition,
             pool: self.pool.clone(),
             metrics: self.metrics.clone(),
+            dictionary: self.dictionary.clone(),
         }
     }
 }
@@ -128,9 +141,16 @@ where
             partition,
             pool,
             metrics,
+            dictionary: None,
         }
     }
 
+    /// Use compression dictionary to decode batches compressed with it
+    pub(crate) fn with_dictionary(mut self, dictionary: Option<Dictionary>) -> Self {
+        self.dictionary = dictionary;
+        self
+    }
+
     /// Returns the name of the Topic that this consumer reads from
     pub fn topic(&self) -> &str {
         &self.topic
@@ -246,9 +266,10 @@ where
         offset: Offset,
         config: ConsumerConfig,
     ) -> Result<impl Stream<Item = Result<Record, ErrorCode>> + use<P>> {
-        let (stream, start_offset, _) = self
+        let (stream, position, _) = self
             .inner_stream_batches_with_config(offset, config, None)
             .await?;
+        let start_offset = position.floor();
         let partition = self.partition;
         let flattened = stream.flat_map(move |result: Result<Batch, _>| match result {
             Err(e) => Either::Right(once(err(e))),
@@ -267,7 +288,10 @@ where
             }
         });
 
-        Ok(flattened)
+        Ok(reassemble_chunks(
+            flattened,
+            ChunkReassembly::new(ChunkReassemblyConfig::default()),
+        ))
     }
 
     /// Continuously streams batches of messages, starting an offset in the consumer's partition
@@ -301,14 +325,14 @@ where
         offset: Offset,
         config: ConsumerConfig,
     ) -> Result<impl Stream<Item = Result<Batch, ErrorCode>> + use<P>> {
-        let (stream, _start_offset, _) = self
+        let (stream, _position, _) = self
             .inner_stream_batches_with_config(offset, config, None)
             .await?;
         Ok(stream)
     }
 
     /// Continuously streams batches of messages, starting an offset in the consumer's partition
-    /// Returns both the stream and the start offset of the stream.
+    /// Returns both the stream and its read position.
     #[instrument(skip(self, offset, config))]
     async fn inner_stream_batches_with_config(
         &self,
@@ -317,12 +341,14 @@ where
         consumer_id: Option<String>,
     ) -> Result<(
         impl Stream<Item = Result<Batch, ErrorCode>> + use<P>,
-        fluvio_protocol::record::Offset,
+        Arc<StreamPosition>,
         Sender<StreamToServer>,
     )> {
-        let (stream, start_offset, stream_to_server) =
+        let (stream, position, stream_to_server) =
             self.request_stream(offset, config, consumer_id).await?;
         let metrics = self.metrics.clone();
+        let dictionary = self.dictionary.clone();
+        let batch_position = position.clone();
         let flattened =
             stream.flat_map(move |batch_result: Result<DefaultStreamFetchResponse, _>| {
                 let response = match batch_result {
@@ -336,29 +362,33 @@ where
                 // processed before hitting an error, so that the error does not obscure those records.
 
                 let inner_metrics = metrics.clone();
-                let batches =
-                    response
-                        .partition
-                        .records
-                        .batches
-                        .into_iter()
-                        .map(move |raw_batch| {
-                            inner_metrics
-                                .consumer()
-                                .add_records(raw_batch.records_len() as u64);
-                            inner_metrics
-                                .consumer()
-                                .add_bytes(raw_batch.batch_len() as u64);
-
-                            let batch: Result<Batch, _> = raw_batch.try_into();
-                            match batch {
-                                Ok(batch) => Ok(batch),
-                                Err(err) => {
-                                    tracing::error!("{err:?}");
-                                    Err(ErrorCode::Other(err.to_string()))
-                                }
+                let dictionary = dictionary.clone();
+                // batches still buffered when the stream is moved belong to the old position
+                let position = batch_position.clone();
+                let seek_epoch = response.seek_epoch;
+                let batches = response
+                    .partition
+                    .records
+                    .batches
+                    .into_iter()
+                    .filter(move |_| position.epoch() == seek_epoch)
+                    .map(move |raw_batch| {
+                        inner_metrics
+                            .consumer()
+                            .add_records(raw_batch.records_len() as u64);
+                        inner_metrics
+                            .consumer()
+                            .add_bytes(raw_batch.batch_len() as u64);
+
+                        let batch = raw_batch.into_memory_batch_with(dictionary.as_ref());
+                        match batch {
+                            Ok(batch) => Ok(batch),
+                            Err(err) => {
+                                tracing::error!("{err:?}");
+                                Err(ErrorCode::Other(err.to_string()))
                             }
-                        });
+                        }
+                    });
                 let error = {
                     let code = response.partition.error_code;
                     match code {
@@ -371,13 +401,13 @@ where
                 Either::Left(iter(items))
             });
 
-        Ok((flattened, start_offset, stream_to_server))
+        Ok((flattened, position, stream_to_server))
     }
 
     /// Creates a stream of `DefaultStreamFetchResponse` for older consumers who rely
     /// on the internal structure of the fetch response. New clients should use the
     /// `stream` and `stream_with_config` methods.
-    /// Returns both the stream and the start offset of the stream.
+    /// Returns both the stream and its read position.
     #[instrument(skip(self, config))]
     async fn request_stream(
         &self,
@@ -386,7 +416,7 @@ where
         consumer_id: Option<String>,
     ) -> Result<(
         impl Stream<Item = Result<DefaultStreamFetchResponse, ErrorCode>> + use<P>,
-        fluvio_protocol::record::Offset,
+        Arc<StreamPosition>,
         Sender<StreamToServer>,
     )> {
         use fluvio_future::task::spawn;
@@ -426,7 +456,18 @@ where
         debug!(start_absolute_offset, end_absolute_offset, record_count);
 
         let with_consumer_id = consumer_id.is_some();
-        let stream_request = DefaultStreamFetchRequest::builder()
+        let stream_fetch_version = serial_socket
+            .versions()
+            .lookup_version::<DefaultStreamFetchRequest>()
+            .unwrap_or(CHAIN_SMARTMODULE_API - 1);
+        let read_from_follower =
+            config.read_from_follower && stream_fetch_version >= FOLLOWER_FETCH_API;
+        if config.read_from_follower && !read_from_follower {
+            warn!("SPU does not support follower fetch, reading from leader");
+        }
+
+        let mut stream_request_builder = DefaultStreamFetchRequest::builder();
+        stream_request_builder
             .topic(self.topic.to_owned())
             .partition(self.partition)
             .fetch_offset(start_absolute_offset)
@@ -434,12 +475,13 @@ where
             .max_bytes(config.max_bytes)
             .smartmodules(config.smartmodule)
             .consumer_id(consumer_id)
-            .build()?;
+            .read_from_follower(read_from_follower);
+        #[cfg(feature = "otel")]
+        if let Some(trace_context) = crate::otel::current_trace_context() {
+            stream_request_builder.trace_context(trace_context);
+        }
+        let stream_request = stream_request_builder.build()?;
 
-        let stream_fetch_version = serial_socket
-            .versions()
-            .lookup_version::<DefaultStreamFetchRequest>()
-            .unwrap_or(CHAIN_SMARTMODULE_API - 1);
         debug!(%stream_fetch_version, "stream_fetch_version");
         if stream_fetch_version < CHAIN_SMARTMODULE_API {
             warn!(
@@ -450,15 +492,23 @@ where
             warn!("SPU does not support Offset Management API");
         }
 
-        let mut stream = self
-            .pool
-            .create_stream_with_version(&replica, stream_request, stream_fetch_version)
-            .await?;
+        let mut stream = if read_from_follower {
+            self.pool
+                .create_nearest_stream_with_version(&replica, stream_request, stream_fetch_version)
+                .await?
+        } else {
+            self.pool
+                .create_stream_with_version(&replica, stream_request, stream_fetch_version)
+                .await?
+        };
 
         let (server_sender, server_recv) =
             async_channel::bounded::<StreamToServer>(STREAM_TO_SERVER_CHANNEL_SIZE);
 
         let server_sender_clone = server_sender.clone();
+        let position = Arc::new(StreamPosition::new(start_absolute_offset));
+        let seek_position = position.clone();
+        let update_position = position.clone();
 
         let ft_stream = async move {
             if let Some(Ok(raw_response)) = stream.next().await {
@@ -503,12 +553,28 @@ where
                                 debug!("fetch last is end, terminating");
                                 break;
                             }
-                            Ok(StreamToServer::FlushManagedOffset { offset, callback }) => {
+                            Ok(StreamToServer::FlushManagedOffset {
+                                offset,
+                                metadata,
+                                callback,
+                            }) => {
                                 debug!(offset, stream_id, "flush offset request");
-                                let request = UpdateConsumerOffsetRequest {
-                                    session_id: stream_id,
-                                    offset,
-                                };
+                                if metadata
+                                    .as_ref()
+                                    .is_some_and(|metadata| !metadata.is_empty())
+                                    && serial_socket
+                                        .versions()
+                                        .lookup_version::<UpdateConsumerOffsetRequest>()
+                                        .is_none_or(|version| {
+                                            version < CONSUMER_OFFSET_METADATA_API
+                                        })
+                                {
+                                    callback.send(ErrorCode::OffsetMetadataNotSupported).await;
+                                    continue;
+                                }
+                                let mut request =
+                                    UpdateConsumerOffsetRequest::new(offset, stream_id);
+                                request.metadata = metadata;
                                 let response = serial_socket.send_receive(request).await;
                                 match response {
                                     Ok(response) => callback.send(response.error_code).await,
@@ -523,6 +589,22 @@ where
                                     }
                                 };
                             }
+                            Ok(StreamToServer::Seek { offset, callback }) => {
+                                debug!(?offset, stream_id, "seek request");
+                                let result = if stream_fetch_version < STREAM_SEEK_API {
+                                    Err(ErrorCode::StreamControlNotSupported)
+                                } else {
+                                    seek_stream(
+                                        &mut serial_socket,
+                                        &replica,
+                                        stream_id,
+                                        &seek_position,
+                                        offset,
+                                    )
+                                    .await
+                                };
+                                callback.send(result).await;
+                            }
                             Err(err) => {
                                 debug!("stream to server channel closed: {err:?}");
                                 break;
@@ -543,6 +625,10 @@ where
                 let server_sender_clone2 = server_sender_clone.clone();
                 let update_stream = StreamExt::map(stream, move |item| {
                     item.inspect(|response| {
+                        // responses sent before a seek must not move the SPU back
+                        if response.seek_epoch != update_position.epoch() {
+                            return;
+                        }
                         if let Some(last_offset) = response.partition.next_offset_for_fetch() {
                             debug!(last_offset, stream_id, "received last offset from spu");
                             let _ = server_sender_clone
@@ -572,7 +658,7 @@ where
             ft_stream.flatten_stream().boxed()
         };
 
-        Ok((stream, start_absolute_offset, server_sender))
+        Ok((stream, position, server_sender))
     }
 
     async fn create_serial_socket_retry(&self) -> Result<VersionedSerialSocket> {
@@ -608,20 +694,24 @@ where
         config: ConsumerConfigExt,
     ) -> Result<SinglePartitionConsumerStream<impl Stream<Item = Result<Record, ErrorCode>> + use<P>>>
     {
+        let chunk_reassembly = config.chunk_reassembly;
         let (offset, config, consumer_id, strategy, flush_period, flusher_check_period) =
             config.into_parts();
-        let (stream, start_offset, stream_to_server) = self
+        let (stream, position, stream_to_server) = self
             .inner_stream_batches_with_config(offset, config, consumer_id)
             .await?;
         let partition = self.partition;
+        let record_position = position.clone();
         let flattened = stream.flat_map(move |result: Result<Batch, _>| match result {
             Err(e) => Either::Right(once(err(e))),
             Ok(batch) => {
+                let position = record_position.clone();
+                let seek_epoch = position.epoch();
                 let records =
                     batch
                         .into_consumer_records_iter(partition)
                         .filter_map(move |record| {
-                            if record.offset >= start_offset {
+                            if position.accepts(seek_epoch, record.offset) {
                                 Some(Ok(record))
                             } else {
                                 None
@@ -630,13 +720,17 @@ where
                 Either::Left(iter(records))
             }
         });
+        let reassembly = ChunkReassembly::new(chunk_reassembly);
+        let pending_chunks = reassembly.pending_chunks();
         Ok(SinglePartitionConsumerStream::new(
-            flattened,
+            reassemble_chunks(flattened, reassembly),
+            partition,
             strategy,
             flush_period,
             flusher_check_period,
             stream_to_server,
-        ))
+        )
+        .with_pending_chunks(pending_chunks))
     }
 }
 
@@ -800,6 +894,7 @@ pub struct MultiplePartitionConsumer {
     strategy: PartitionSelectionStrategy,
     pool: Arc<SpuSocketPool>,
     metrics: Arc<ClientMetrics>,
+    dictionaries: HashMap<String, Dictionary>,
 }
 
 impl MultiplePartitionConsumer {
@@ -812,9 +907,16 @@ impl MultiplePartitionConsumer {
             strategy,
             pool,
             metrics,
+            dictionaries: HashMap::new(),
         }
     }
 
+    /// Use compression dictionaries of topics to decode batches compressed with them
+    pub(crate) fn with_dictionaries(mut self, dictionaries: HashMap<String, Dictionary>) -> Self {
+        self.dictionaries = dictionaries;
+        self
+    }
+
     /// Continuously streams events from a particular offset in the selected partitions
     ///
     /// Streaming is one of the two ways to consume events in Fluvio.
@@ -920,12 +1022,14 @@ impl MultiplePartitionConsumer {
             .await?
             .into_iter()
             .map(|(topic, partition)| {
+                let dictionary = self.dictionaries.get(&topic).cloned();
                 PartitionConsumer::new(
                     topic,
                     partition as PartitionId,
                     self.pool.clone(),
                     self.metrics.clone(),
                 )
+                .with_dictionary(dictionary)
             })
             .collect();
 
@@ -947,11 +1051,50 @@ pub(crate) enum StreamToServer {
     UpdateOffset(i64),
     FlushManagedOffset {
         offset: i64,
+        /// replaces the metadata stored with the offset, keeps it if none
+        metadata: Option<Vec<u8>>,
         callback: StreamToServerCallback<ErrorCode>,
     },
+    Seek {
+        offset: Offset,
+        callback: StreamToServerCallback<Result<i64, ErrorCode>>,
+    },
     Close,
 }
 
+/// Resolves `offset` and moves the stream there, returning the absolute offset
+async fn seek_stream(
+    socket: &mut VersionedSerialSocket,
+    replica: &ReplicaKey,
+    stream_id: u32,
+    position: &StreamPosition,
+    offset: Offset,
+) -> Result<i64, ErrorCode> {
+    let offsets = fetch_offsets(socket, replica)
+        .await
+        .map_err(|err| ErrorCode::Other(err.to_string()))?;
+    let absolute = offset
+        .resolve(&offsets, None)
+        .await
+        .map_err(|err| ErrorCode::Other(err.to_string()))?
+        .clamp(offsets.start_offset, offsets.last_stable_offset);
+
+    // bump the epoch before the SPU sees the seek so in-flight records are dropped
+    let previous = position.seek(absolute);
+    let request = StreamSeekRequest::new(stream_id, absolute, position.epoch());
+    let error_code = match socket.send_receive(request).await {
+        Ok(response) => response.error_code,
+        Err(err) => ErrorCode::Other(err.to_string()),
+    };
+    match error_code {
+        ErrorCode::None => Ok(absolute),
+        other => {
+            position.restore(previous);
+            Err(other)
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum StreamToServerCallback<T> {
     NoOp,
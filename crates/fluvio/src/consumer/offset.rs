@@ -1,6 +1,6 @@
 use std::{
     fmt::{Display, Formatter},
-    sync::atomic::AtomicI64,
+    sync::{Mutex, MutexGuard, atomic::AtomicI64},
 };
 
 use async_channel::{Sender, bounded};
@@ -19,6 +19,9 @@ pub(crate) struct OffsetLocalStore {
     seen: AtomicI64,
     comitted: AtomicI64,
     flushed: AtomicI64,
+    /// metadata last committed by this stream, none if the stored one is kept.
+    /// also guards updates of `comitted`
+    metadata: Mutex<Option<Vec<u8>>>,
     stream_to_server: Sender<StreamToServer>,
 }
 
@@ -28,6 +31,7 @@ impl OffsetLocalStore {
             seen: AtomicI64::new(-1),
             comitted: AtomicI64::new(-1),
             flushed: AtomicI64::new(-1),
+            metadata: Mutex::new(None),
             stream_to_server,
         }
     }
@@ -36,19 +40,42 @@ impl OffsetLocalStore {
         self.seen.fetch_max(offset, DEFAULT_ORDERING);
     }
 
+    /// Commits the last seen offset, keeping the committed metadata
     pub fn commit(&self) {
+        let _metadata = self.lock_metadata();
         self.comitted
             .store(self.seen.load(DEFAULT_ORDERING), DEFAULT_ORDERING);
     }
 
+    /// Commits `offset`, even if it is lower than the current committed offset.
+    /// The committed metadata is replaced by `metadata` if given
+    pub fn commit_offset(&self, offset: i64, metadata: Option<Vec<u8>>) {
+        let mut current = self.lock_metadata();
+        self.comitted.store(offset, DEFAULT_ORDERING);
+        if metadata.is_some() {
+            *current = metadata;
+        }
+        // flush again even if only the metadata changed
+        self.flushed.fetch_min(offset - 1, DEFAULT_ORDERING);
+    }
+
+    /// Rewinds the store for a stream which continues from `offset`,
+    /// so that the next commit and flush are not ignored when moving backwards.
+    pub fn seek(&self, offset: i64) {
+        self.seen.store(offset - 1, DEFAULT_ORDERING);
+        self.flushed.fetch_min(offset - 1, DEFAULT_ORDERING);
+    }
+
     pub async fn flush(&self) -> Result<(), ErrorCode> {
         if self.flushed() >= self.comitted() {
             return Ok(());
         }
+        let (offset, metadata) = self.comitted_with_metadata();
         let (s, r) = bounded(1);
         self.stream_to_server
             .send(StreamToServer::FlushManagedOffset {
-                offset: self.comitted(),
+                offset,
+                metadata,
                 callback: StreamToServerCallback::Channel(s),
             })
             .await
@@ -59,7 +86,7 @@ impl OffsetLocalStore {
             .map_err(|e| ErrorCode::Other(e.to_string()))?
         {
             ErrorCode::None => {
-                self.set_flushed(self.comitted());
+                self.set_flushed(offset);
                 Ok(())
             }
             other => Err(other),
@@ -70,12 +97,14 @@ impl OffsetLocalStore {
         if self.flushed() >= self.comitted() {
             return Ok(());
         }
+        let (offset, metadata) = self.comitted_with_metadata();
         self.stream_to_server
             .try_send(StreamToServer::FlushManagedOffset {
-                offset: self.comitted(),
+                offset,
+                metadata,
                 callback: StreamToServerCallback::NoOp,
             })?;
-        self.set_flushed(self.comitted());
+        self.set_flushed(offset);
         Ok(())
     }
 
@@ -90,6 +119,15 @@ impl OffsetLocalStore {
     fn set_flushed(&self, val: i64) {
         self.flushed.store(val, DEFAULT_ORDERING)
     }
+
+    fn comitted_with_metadata(&self) -> (i64, Option<Vec<u8>>) {
+        let metadata = self.lock_metadata();
+        (self.comitted(), metadata.clone())
+    }
+
+    fn lock_metadata(&self) -> MutexGuard<'_, Option<Vec<u8>>> {
+        self.metadata.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 impl Display for OffsetLocalStore {
@@ -111,6 +149,29 @@ pub struct ConsumerOffset {
     pub partition: PartitionId,
     pub offset: i64,
     pub modified_time: u64,
+    /// set when offset was mirrored from another cluster
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mirror_source: Option<MirroredOffset>,
+    /// opaque metadata committed with the offset, empty if none
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub metadata: Vec<u8>,
+}
+
+/// Source of consumer offset mirrored from another cluster.
+/// Offset on this cluster is translated from source offset.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MirroredOffset {
+    pub cluster: String,
+    pub topic: String,
+    pub partition: PartitionId,
+    pub offset: i64,
+}
+
+impl ConsumerOffset {
+    /// where offset was mirrored from, none if it was committed on this cluster
+    pub fn mirror_source(&self) -> Option<&MirroredOffset> {
+        self.mirror_source.as_ref()
+    }
 }
 
 impl From<ConsumerOffsetRequest> for ConsumerOffset {
@@ -120,6 +181,8 @@ impl From<ConsumerOffsetRequest> for ConsumerOffset {
             replica_id,
             offset,
             modified_time,
+            mirror_source,
+            metadata,
         } = value;
 
         Self {
@@ -128,6 +191,13 @@ impl From<ConsumerOffsetRequest> for ConsumerOffset {
             consumer_id,
             offset,
             modified_time,
+            mirror_source: mirror_source.map(|source| MirroredOffset {
+                cluster: source.cluster,
+                topic: source.replica_id.topic,
+                partition: source.replica_id.partition,
+                offset: source.offset,
+            }),
+            metadata,
         }
     }
 }
@@ -152,7 +222,7 @@ mod tests {
         //then
         assert!(matches!(
             recv.try_recv(),
-            Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 1
+            Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 1
         ))
     }
 
@@ -185,7 +255,7 @@ mod tests {
         //then
         assert!(matches!(
             recv.try_recv(),
-            Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 1
+            Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 1
         ));
         assert!(matches!(recv.try_recv(), Err(TryRecvError::Empty)))
     }
@@ -208,7 +278,96 @@ mod tests {
         //then
         assert!(matches!(
             recv.try_recv(),
-            Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 10
+            Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 10
+        ));
+        assert!(matches!(recv.try_recv(), Err(TryRecvError::Empty)))
+    }
+
+    #[test]
+    fn test_commit_offset_moves_backwards() {
+        //given
+        let (sender, recv) = async_channel::bounded(2);
+        let store = OffsetLocalStore::new(sender);
+        store.update(10);
+        store.commit();
+        store.try_flush().expect("flushed");
+
+        //when
+        store.commit_offset(4, None);
+        store.try_flush().expect("flushed");
+
+        //then
+        assert!(matches!(
+            recv.try_recv(),
+            Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 10
+        ));
+        assert!(matches!(
+            recv.try_recv(),
+            Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 4
+        ));
+    }
+
+    #[test]
+    fn test_commit_offset_with_metadata() {
+        //given
+        let (sender, recv) = async_channel::bounded(4);
+        let store = OffsetLocalStore::new(sender);
+
+        //when
+        store.update(3);
+        store.commit();
+        store.try_flush().expect("flushed");
+        store.commit_offset(4, Some(b"txn-1".to_vec()));
+        store.try_flush().expect("flushed");
+        store.commit_offset(4, Some(b"txn-2".to_vec()));
+        store.try_flush().expect("flushed");
+        store.update(5);
+        store.commit();
+        store.try_flush().expect("flushed");
+
+        //then
+        assert!(matches!(
+            recv.try_recv(),
+            Ok(StreamToServer::FlushManagedOffset {
+                offset: 3,
+                metadata: None,
+                ..
+            })
+        ));
+        assert!(matches!(
+            recv.try_recv(),
+            Ok(StreamToServer::FlushManagedOffset { offset: 4, metadata: Some(metadata), .. }) if metadata == b"txn-1"
+        ));
+        assert!(matches!(
+            recv.try_recv(),
+            Ok(StreamToServer::FlushManagedOffset { offset: 4, metadata: Some(metadata), .. }) if metadata == b"txn-2"
+        ));
+        assert!(matches!(
+            recv.try_recv(),
+            Ok(StreamToServer::FlushManagedOffset { offset: 5, metadata: Some(metadata), .. }) if metadata == b"txn-2"
+        ));
+    }
+
+    #[test]
+    fn test_seek_backwards() {
+        //given
+        let (sender, recv) = async_channel::bounded(2);
+        let store = OffsetLocalStore::new(sender);
+        store.update(10);
+        store.commit();
+        store.try_flush().expect("flushed");
+        assert!(recv.try_recv().is_ok());
+
+        //when
+        store.seek(3);
+        store.update(3);
+        store.commit();
+        store.try_flush().expect("flushed");
+
+        //then
+        assert!(matches!(
+            recv.try_recv(),
+            Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 3
         ));
         assert!(matches!(recv.try_recv(), Err(TryRecvError::Empty)))
     }
@@ -224,11 +383,12 @@ mod tests {
             let message1 = recv.recv().await;
             assert!(matches!(
                 message1,
-                Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 1
+                Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 1
             ));
             if let Ok(StreamToServer::FlushManagedOffset {
                 offset: _,
                 callback,
+                ..
             }) = message1
             {
                 callback.send(ErrorCode::None).await;
@@ -237,11 +397,12 @@ mod tests {
             let message2 = recv.recv().await;
             assert!(matches!(
                 message2,
-                Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 2
+                Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 2
             ));
             if let Ok(StreamToServer::FlushManagedOffset {
                 offset: _,
                 callback,
+                ..
             }) = message2
             {
                 callback.send(ErrorCode::None).await;
@@ -283,11 +444,12 @@ mod tests {
             let message1 = recv.recv().await;
             assert!(matches!(
                 message1,
-                Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 1
+                Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 1
             ));
             if let Ok(StreamToServer::FlushManagedOffset {
                 offset: _,
                 callback,
+                ..
             }) = message1
             {
                 callback.send(ErrorCode::None).await;
@@ -315,11 +477,12 @@ mod tests {
             let message1 = recv.recv().await;
             assert!(matches!(
                 message1,
-                Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 1
+                Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 1
             ));
             if let Ok(StreamToServer::FlushManagedOffset {
                 offset: _,
                 callback,
+                ..
             }) = message1
             {
                 callback.send(ErrorCode::SpuError).await;
@@ -10,6 +10,7 @@ use adaptive_backoff::prelude::{
 use async_lock::Mutex;
 use async_trait::async_trait;
 use fluvio_socket::ClientConfig;
+use fluvio_types::PartitionId;
 use fluvio_types::defaults::{
     RECONNECT_BACKOFF_FACTOR, RECONNECT_BACKOFF_MAX_DURATION, RECONNECT_BACKOFF_MIN_DURATION,
 };
@@ -40,6 +41,7 @@ pub struct ConsumerRetryStream {
     inner: ConsumerRetryInner,
     state: ConsumerRetryState,
     stream: ShararedConsumerStream,
+    deferred_error: Option<ErrorCode>,
 }
 
 impl ConsumerRetryStream {
@@ -182,6 +184,56 @@ impl ConsumerStream for ConsumerRetryStream {
             stream.offset_flush().await
         })
     }
+
+    fn offset_commit_partition(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+    ) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move {
+            let mut stream = self.stream.lock().await;
+            stream.offset_commit_partition(partition, offset).await
+        })
+    }
+
+    fn offset_commit_with_metadata(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+        metadata: Vec<u8>,
+    ) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move {
+            let mut stream = self.stream.lock().await;
+            stream
+                .offset_commit_with_metadata(partition, offset, metadata)
+                .await
+        })
+    }
+
+    fn seek(&mut self, partition: PartitionId, offset: Offset) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move {
+            let mut stream = self.stream.lock().await;
+            stream.seek(partition, offset).await
+        })
+    }
+
+    fn pause<'a>(&'a mut self, partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        Box::pin(async move {
+            let mut stream = self.stream.lock().await;
+            stream.pause(partitions).await
+        })
+    }
+
+    fn resume<'a>(&'a mut self, partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        Box::pin(async move {
+            let mut stream = self.stream.lock().await;
+            stream.resume(partitions).await
+        })
+    }
+
+    fn deferred_error(&mut self) -> Option<&mut Option<ErrorCode>> {
+        Some(&mut self.deferred_error)
+    }
 }
 
 impl ConsumerRetryStream {
@@ -219,6 +271,7 @@ impl ConsumerRetryStream {
             },
             state: ConsumerRetryState::Idle,
             stream: Arc::new(Mutex::new(Box::pin(stream))),
+            deferred_error: None,
         };
 
         Ok(retry_stream)
@@ -435,6 +488,7 @@ mod tests {
     {
         let partition_stream = SinglePartitionConsumerStream::new(
             input,
+            0,
             OffsetManagementStrategy::Auto,
             Default::default(),
             Duration::from_millis(100),
@@ -463,6 +517,7 @@ mod tests {
             inner: inner.clone(),
             state: ConsumerRetryState::Idle,
             stream: Arc::new(Mutex::new(Box::pin(multi_stream))),
+            deferred_error: None,
         }
     }
 
@@ -496,6 +551,7 @@ mod tests {
         let (tx1, rx1) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
             records_stream(0, ["1", "3", "5"]),
+            0,
             OffsetManagementStrategy::Manual,
             Default::default(),
             Duration::from_millis(100),
@@ -504,6 +560,7 @@ mod tests {
         let (tx2, rx2) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
             records_stream(1, ["2", "4", "6"]),
+            1,
             OffsetManagementStrategy::Manual,
             Default::default(),
             Duration::from_millis(100),
@@ -530,6 +587,7 @@ mod tests {
             },
             state: ConsumerRetryState::Idle,
             stream: Arc::new(Mutex::new(Box::pin(multi_stream))),
+            deferred_error: None,
         };
 
         //when
@@ -559,6 +617,7 @@ mod tests {
             if let Ok(StreamToServer::FlushManagedOffset {
                 offset: _,
                 callback,
+                ..
             }) = message
             {
                 callback.send(ErrorCode::None).await;
@@ -569,6 +628,7 @@ mod tests {
             if let Ok(StreamToServer::FlushManagedOffset {
                 callback,
                 offset: _,
+                ..
             }) = message
             {
                 callback.send(ErrorCode::None).await;
@@ -1,3 +1,4 @@
+use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -6,14 +7,20 @@ use std::time::{Duration, SystemTime};
 use async_channel::Sender;
 use fluvio_future::timer::sleep;
 use fluvio_protocol::{link::ErrorCode, record::ConsumerRecord as Record};
+use fluvio_spu_schema::server::consumer_offset::MAX_CONSUMER_OFFSET_METADATA_SIZE;
+use fluvio_types::PartitionId;
 use futures_util::stream::select_all;
 use futures_util::{future::try_join_all, ready, FutureExt};
-use futures_util::Stream;
+use futures_util::{Stream, StreamExt};
 use tokio::select;
 use tokio::sync::Notify;
 use tracing::{debug, info, warn};
 
+use crate::Offset;
+use super::batch::ConsumerBatch;
+use super::chunk::PendingChunks;
 use super::config::OffsetManagementStrategy;
+use super::control::{PartitionControl, find_controls};
 use super::{offset::OffsetLocalStore, StreamToServer};
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -30,16 +37,152 @@ pub trait ConsumerStream: Stream<Item = Result<Record, ErrorCode>> + Unpin {
 
     /// Send the committed offset to the server. The method waits for the server's acknowledgment before it finishes.
     fn offset_flush(&mut self) -> ConsumerBoxFuture<'_>;
+
+    /// Mark `offset` of `partition` as committed, even if it is lower than the last committed one.
+    /// Like `offset_commit()`, it may require a subsequent `offset_flush()` call.
+    /// The metadata stored with the previous offset of the partition is kept.
+    fn offset_commit_partition(
+        &mut self,
+        _partition: PartitionId,
+        _offset: i64,
+    ) -> ConsumerBoxFuture<'_> {
+        Box::pin(async { Err(ErrorCode::StreamControlNotSupported) })
+    }
+
+    /// Like `offset_commit_partition()`, also replacing the opaque `metadata` blob stored with the offset,
+    /// for instance the id of the external transaction the records were written in.
+    /// The metadata is returned along with the offset by `Fluvio::consumer_offset()`.
+    fn offset_commit_with_metadata(
+        &mut self,
+        _partition: PartitionId,
+        _offset: i64,
+        _metadata: Vec<u8>,
+    ) -> ConsumerBoxFuture<'_> {
+        Box::pin(async { Err(ErrorCode::StreamControlNotSupported) })
+    }
+
+    /// Continue reading `partition` from `offset` without reopening the stream.
+    /// Records of the partition fetched before the seek are discarded.
+    fn seek(&mut self, _partition: PartitionId, _offset: Offset) -> ConsumerBoxFuture<'_> {
+        Box::pin(async { Err(ErrorCode::StreamControlNotSupported) })
+    }
+
+    /// Stop yielding records of `partitions` until they are resumed.
+    /// The SPU stops sending records of a paused partition once the client stops acknowledging them.
+    fn pause<'a>(&'a mut self, _partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        Box::pin(async { Err(ErrorCode::StreamControlNotSupported) })
+    }
+
+    /// Resume yielding records of paused `partitions`.
+    fn resume<'a>(&'a mut self, _partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        Box::pin(async { Err(ErrorCode::StreamControlNotSupported) })
+    }
+
+    /// Read up to `max_records` records, waiting at most `max_wait` for them to arrive.
+    /// The batch may be empty if no record arrived in time. Returns `None` once the stream has ended.
+    ///
+    /// An error from the stream ends the batch: the records read before it are returned
+    /// and the error is returned by the next call.
+    fn poll_batch(
+        &mut self,
+        max_records: usize,
+        max_wait: Duration,
+    ) -> impl Future<Output = Option<Result<ConsumerBatch, ErrorCode>>> + '_
+    where
+        Self: Sized,
+    {
+        async move {
+            if let Some(err) = self.deferred_error().and_then(Option::take) {
+                return Some(Err(err));
+            }
+            let mut records = Vec::with_capacity(max_records.min(MAX_BATCH_PREALLOCATION));
+            let deadline = sleep(max_wait);
+            let mut deadline = std::pin::pin!(deadline);
+            while records.len() < max_records {
+                let next = select! {
+                    biased;
+                    next = self.next() => next,
+                    _ = &mut deadline => break,
+                };
+                match next {
+                    Some(Ok(record)) => records.push(record),
+                    Some(Err(err)) if records.is_empty() => return Some(Err(err)),
+                    Some(Err(err)) => match self.deferred_error() {
+                        Some(deferred) => {
+                            *deferred = Some(err);
+                            break;
+                        }
+                        None => return Some(Err(err)),
+                    },
+                    None if records.is_empty() => return None,
+                    None => break,
+                }
+            }
+            Some(Ok(ConsumerBatch::new(records)))
+        }
+    }
+
+    /// Slot keeping the error which ended a `poll_batch()` call for the next one.
+    /// Streams without it return the error right away, dropping the records read before it.
+    #[doc(hidden)]
+    fn deferred_error(&mut self) -> Option<&mut Option<ErrorCode>> {
+        None
+    }
+
+    /// Mark the last record of every partition in `batch` as committed.
+    /// Like `offset_commit()`, it may require a subsequent `offset_flush()` call.
+    fn offset_commit_batch<'a>(
+        &'a mut self,
+        batch: &ConsumerBatch,
+    ) -> impl Future<Output = Result<(), ErrorCode>> + 'a
+    where
+        Self: Sized,
+    {
+        let offsets = batch.last_offsets();
+        async move {
+            for (partition, offset) in offsets {
+                self.offset_commit_partition(partition, offset).await?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Like `offset_commit_batch()`, storing the same opaque `metadata` with the offset of every partition.
+    fn offset_commit_batch_with_metadata<'a>(
+        &'a mut self,
+        batch: &ConsumerBatch,
+        metadata: Vec<u8>,
+    ) -> impl Future<Output = Result<(), ErrorCode>> + 'a
+    where
+        Self: Sized,
+    {
+        let offsets = batch.last_offsets();
+        async move {
+            for (partition, offset) in offsets {
+                self.offset_commit_with_metadata(partition, offset, metadata.clone())
+                    .await?;
+            }
+            Ok(())
+        }
+    }
 }
 
+/// Upper bound of records allocated upfront by `poll_batch()`
+const MAX_BATCH_PREALLOCATION: usize = 1024;
+
 pub struct MultiplePartitionConsumerStream<T> {
     partition_streams: futures_util::stream::SelectAll<SinglePartitionConsumerStream<T>>,
-    offset_mgnts: Vec<Arc<OffsetManagement>>,
+    partitions: Vec<(Arc<PartitionControl>, Arc<OffsetManagement>)>,
+    deferred_error: Option<ErrorCode>,
 }
 
 pub struct SinglePartitionConsumerStream<T> {
     offset_mngt: Arc<OffsetManagement>,
+    control: Arc<PartitionControl>,
     inner: T,
+    deferred_error: Option<ErrorCode>,
+    /// keeps commits before chunks which were not reassembled yet
+    pending_chunks: PendingChunks,
 }
 
 impl<T> Drop for SinglePartitionConsumerStream<T> {
@@ -83,15 +226,19 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> MultiplePartitionConsu
         I: IntoIterator<Item = SinglePartitionConsumerStream<T>>,
     {
         let mut partition_streams = Vec::new();
-        let mut offset_mgnts = Vec::new();
+        let mut partitions = Vec::new();
         for partition_stream in streams.into_iter() {
-            offset_mgnts.push(partition_stream.offset_mngt.clone());
+            partitions.push((
+                partition_stream.control.clone(),
+                partition_stream.offset_mngt.clone(),
+            ));
             partition_streams.push(partition_stream);
         }
         let partition_streams = select_all(partition_streams);
         Self {
             partition_streams,
-            offset_mgnts,
+            partitions,
+            deferred_error: None,
         }
     }
 }
@@ -99,11 +246,13 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> MultiplePartitionConsu
 impl<T> SinglePartitionConsumerStream<T> {
     pub(super) fn new(
         inner: T,
+        partition: PartitionId,
         offset_strategy: OffsetManagementStrategy,
         flush_period: Duration,
         flusher_check_period: Duration,
         stream_to_server: Sender<StreamToServer>,
     ) -> Self {
+        let control = Arc::new(PartitionControl::new(partition, stream_to_server.clone()));
         let offset_mngt = match offset_strategy {
             OffsetManagementStrategy::None => OffsetManagement::None,
             OffsetManagementStrategy::Manual => OffsetManagement::Manual {
@@ -127,7 +276,18 @@ impl<T> SinglePartitionConsumerStream<T> {
             });
         }
 
-        Self { offset_mngt, inner }
+        Self {
+            offset_mngt,
+            control,
+            inner,
+            deferred_error: None,
+            pending_chunks: PendingChunks::default(),
+        }
+    }
+
+    pub(super) fn with_pending_chunks(mut self, pending_chunks: PendingChunks) -> Self {
+        self.pending_chunks = pending_chunks;
+        self
     }
 }
 
@@ -141,10 +301,15 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> Stream
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
         let self_mut = self.get_mut();
+        if self_mut.control.poll_paused(cx.waker()) {
+            return std::task::Poll::Pending;
+        }
         let pinned = std::pin::pin!(&mut self_mut.inner);
         match ready!(pinned.poll_next(cx)) {
             Some(Ok(last)) => {
-                self_mut.offset_mngt.update(last.offset);
+                self_mut
+                    .offset_mngt
+                    .update(self_mut.pending_chunks.committable(last.offset));
                 std::task::Poll::Ready(Some(Ok(last)))
             }
             other => {
@@ -164,6 +329,47 @@ impl ConsumerStream for Pin<Box<dyn ConsumerStream + Send>> {
     fn offset_flush(&mut self) -> ConsumerBoxFuture {
         Box::pin(async move { self.as_mut().offset_flush().await })
     }
+
+    fn offset_commit_partition(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+    ) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move {
+            self.as_mut()
+                .offset_commit_partition(partition, offset)
+                .await
+        })
+    }
+
+    fn offset_commit_with_metadata(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+        metadata: Vec<u8>,
+    ) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move {
+            self.as_mut()
+                .offset_commit_with_metadata(partition, offset, metadata)
+                .await
+        })
+    }
+
+    fn seek(&mut self, partition: PartitionId, offset: Offset) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move { self.as_mut().seek(partition, offset).await })
+    }
+
+    fn pause<'a>(&'a mut self, partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        Box::pin(async move { self.as_mut().pause(partitions).await })
+    }
+
+    fn resume<'a>(&'a mut self, partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        Box::pin(async move { self.as_mut().resume(partitions).await })
+    }
+
+    fn deferred_error(&mut self) -> Option<&mut Option<ErrorCode>> {
+        self.as_mut().get_mut().deferred_error()
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -175,6 +381,47 @@ impl ConsumerStream for Pin<Box<dyn ConsumerStream>> {
     fn offset_flush(&mut self) -> ConsumerBoxFuture {
         Box::pin(async move { self.as_mut().offset_flush().await })
     }
+
+    fn offset_commit_partition(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+    ) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move {
+            self.as_mut()
+                .offset_commit_partition(partition, offset)
+                .await
+        })
+    }
+
+    fn offset_commit_with_metadata(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+        metadata: Vec<u8>,
+    ) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move {
+            self.as_mut()
+                .offset_commit_with_metadata(partition, offset, metadata)
+                .await
+        })
+    }
+
+    fn seek(&mut self, partition: PartitionId, offset: Offset) -> ConsumerBoxFuture<'_> {
+        Box::pin(async move { self.as_mut().seek(partition, offset).await })
+    }
+
+    fn pause<'a>(&'a mut self, partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        Box::pin(async move { self.as_mut().pause(partitions).await })
+    }
+
+    fn resume<'a>(&'a mut self, partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        Box::pin(async move { self.as_mut().resume(partitions).await })
+    }
+
+    fn deferred_error(&mut self) -> Option<&mut Option<ErrorCode>> {
+        self.as_mut().get_mut().deferred_error()
+    }
 }
 
 impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
@@ -187,13 +434,93 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
     fn offset_flush(&mut self) -> ConsumerBoxFuture {
         Box::pin(self.offset_mngt.flush())
     }
+
+    fn offset_commit_partition(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+    ) -> ConsumerBoxFuture<'_> {
+        let result = self
+            .check_partition(partition)
+            .and_then(|_| self.offset_mngt.commit_offset(offset, None));
+        Box::pin(async move { result })
+    }
+
+    fn offset_commit_with_metadata(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+        metadata: Vec<u8>,
+    ) -> ConsumerBoxFuture<'_> {
+        let result = self
+            .check_partition(partition)
+            .and_then(|_| self.offset_mngt.commit_offset(offset, Some(metadata)));
+        Box::pin(async move { result })
+    }
+
+    fn seek(&mut self, partition: PartitionId, offset: Offset) -> ConsumerBoxFuture<'_> {
+        if let Err(err) = self.check_partition(partition) {
+            return Box::pin(async { Err(err) });
+        }
+        let control = self.control.clone();
+        let offset_mngt = self.offset_mngt.clone();
+        Box::pin(async move { seek_partition(&control, &offset_mngt, offset).await })
+    }
+
+    fn pause<'a>(&'a mut self, partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        let result = partitions
+            .iter()
+            .try_for_each(|partition| self.check_partition(*partition))
+            .map(|_| {
+                if !partitions.is_empty() {
+                    self.control.pause()
+                }
+            });
+        Box::pin(async move { result })
+    }
+
+    fn resume<'a>(&'a mut self, partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        let result = partitions
+            .iter()
+            .try_for_each(|partition| self.check_partition(*partition))
+            .map(|_| {
+                if !partitions.is_empty() {
+                    self.control.resume()
+                }
+            });
+        Box::pin(async move { result })
+    }
+
+    fn deferred_error(&mut self) -> Option<&mut Option<ErrorCode>> {
+        Some(&mut self.deferred_error)
+    }
+}
+
+impl<T> SinglePartitionConsumerStream<T> {
+    fn check_partition(&self, partition: PartitionId) -> Result<(), ErrorCode> {
+        if partition == self.control.partition() {
+            Ok(())
+        } else {
+            Err(ErrorCode::StreamPartitionNotFound(partition))
+        }
+    }
+}
+
+async fn seek_partition(
+    control: &PartitionControl,
+    offset_mngt: &OffsetManagement,
+    offset: Offset,
+) -> Result<(), ErrorCode> {
+    let offset = control.seek(offset).await?;
+    offset_mngt.seek(offset);
+    Ok(())
 }
 
 impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
     for MultiplePartitionConsumerStream<T>
 {
     fn offset_commit(&mut self) -> ConsumerBoxFuture {
-        for partition in &self.offset_mgnts {
+        for (_, partition) in &self.partitions {
             if let Err(err) = partition.commit() {
                 return Box::pin(async { Err(err) });
             }
@@ -203,9 +530,60 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
     }
 
     fn offset_flush(&mut self) -> ConsumerBoxFuture {
-        let futures: Vec<_> = self.offset_mgnts.iter().map(|p| p.flush()).collect();
+        let futures: Vec<_> = self.partitions.iter().map(|(_, p)| p.flush()).collect();
         Box::pin(try_join_all(futures).map(|r| r.map(|_| ())))
     }
+
+    fn offset_commit_partition(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+    ) -> ConsumerBoxFuture<'_> {
+        let result = find_controls(&self.partitions, &[partition])
+            .and_then(|found| found[0].1.commit_offset(offset, None));
+        Box::pin(async move { result })
+    }
+
+    fn offset_commit_with_metadata(
+        &mut self,
+        partition: PartitionId,
+        offset: i64,
+        metadata: Vec<u8>,
+    ) -> ConsumerBoxFuture<'_> {
+        let result = find_controls(&self.partitions, &[partition])
+            .and_then(|found| found[0].1.commit_offset(offset, Some(metadata)));
+        Box::pin(async move { result })
+    }
+
+    fn seek(&mut self, partition: PartitionId, offset: Offset) -> ConsumerBoxFuture<'_> {
+        let (control, offset_mngt) = match find_controls(&self.partitions, &[partition]) {
+            Ok(found) => found[0].clone(),
+            Err(err) => return Box::pin(async { Err(err) }),
+        };
+        Box::pin(async move { seek_partition(&control, &offset_mngt, offset).await })
+    }
+
+    fn pause<'a>(&'a mut self, partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        let result = find_controls(&self.partitions, partitions).map(|found| {
+            for (control, _) in found {
+                control.pause();
+            }
+        });
+        Box::pin(async move { result })
+    }
+
+    fn resume<'a>(&'a mut self, partitions: &'a [PartitionId]) -> ConsumerBoxFuture<'a> {
+        let result = find_controls(&self.partitions, partitions).map(|found| {
+            for (control, _) in found {
+                control.resume();
+            }
+        });
+        Box::pin(async move { result })
+    }
+
+    fn deferred_error(&mut self) -> Option<&mut Option<ErrorCode>> {
+        Some(&mut self.deferred_error)
+    }
 }
 
 impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> Stream
@@ -294,6 +672,32 @@ impl OffsetManagement {
         }
     }
 
+    fn commit_offset(&self, offset: i64, metadata: Option<Vec<u8>>) -> Result<(), ErrorCode> {
+        if let Some(size) = metadata
+            .as_ref()
+            .map(Vec::len)
+            .filter(|size| *size > MAX_CONSUMER_OFFSET_METADATA_SIZE)
+        {
+            return Err(ErrorCode::OffsetMetadataTooLarge(size as u32));
+        }
+        match self {
+            OffsetManagement::None => Err(ErrorCode::OffsetManagementDisabled),
+            OffsetManagement::Manual { offset_store }
+            | OffsetManagement::Auto { offset_store, .. } => {
+                offset_store.commit_offset(offset, metadata);
+                Ok(())
+            }
+        }
+    }
+
+    fn seek(&self, offset: i64) {
+        match self {
+            OffsetManagement::None => {}
+            OffsetManagement::Manual { offset_store }
+            | OffsetManagement::Auto { offset_store, .. } => offset_store.seek(offset),
+        }
+    }
+
     async fn flush(&self) -> Result<(), ErrorCode> {
         match self {
             OffsetManagement::None => Err(ErrorCode::OffsetManagementDisabled),
@@ -357,6 +761,7 @@ mod tests {
         let (tx, _rx) = async_channel::unbounded();
         let partition_stream = SinglePartitionConsumerStream::new(
             records_stream(0, ["1", "2"]),
+            0,
             Default::default(),
             Default::default(),
             Duration::from_millis(100),
@@ -384,6 +789,7 @@ mod tests {
         let (tx, _rx) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
             records_stream(0, ["1"]),
+            0,
             Default::default(),
             Default::default(),
             Duration::from_millis(100),
@@ -392,6 +798,7 @@ mod tests {
         let (tx, _rx) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
             records_stream(1, ["2", "4", "6"]),
+            1,
             Default::default(),
             Default::default(),
             Duration::from_millis(100),
@@ -400,6 +807,7 @@ mod tests {
         let (tx, _rx) = async_channel::unbounded();
         let partition_stream3 = SinglePartitionConsumerStream::new(
             records_stream(2, ["3", "5"]),
+            2,
             Default::default(),
             Default::default(),
             Duration::from_millis(100),
@@ -426,12 +834,235 @@ mod tests {
         assert_eq!(result, ["1", "2", "3", "4", "5", "6"]);
     }
 
+    #[fluvio_future::test]
+    async fn test_multi_partition_stream_pause_and_resume() {
+        //given
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream1 = SinglePartitionConsumerStream::new(
+            records_stream(0, ["1", "3"]),
+            0,
+            Default::default(),
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream2 = SinglePartitionConsumerStream::new(
+            records_stream(1, ["2", "4"]),
+            1,
+            Default::default(),
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+        let mut multi_stream =
+            MultiplePartitionConsumerStream::new([partition_stream1, partition_stream2]);
+
+        //when
+        multi_stream.pause(&[0]).await.expect("paused");
+        let mut result = Vec::new();
+        for _ in 0..2 {
+            let record = multi_stream
+                .next()
+                .await
+                .expect("record")
+                .expect("no error");
+            result.push(String::from_utf8_lossy(record.as_ref()).to_string());
+        }
+        let paused = fluvio_future::future::timeout(Duration::from_millis(50), multi_stream.next())
+            .await
+            .is_err();
+        multi_stream.resume(&[0]).await.expect("resumed");
+        while let Some(record) = multi_stream.next().await {
+            result.push(String::from_utf8_lossy(record.expect("no error").as_ref()).to_string());
+        }
+
+        //then
+        assert!(paused, "paused partition must not yield records");
+        assert_eq!(result, ["2", "4", "1", "3"]);
+    }
+
+    #[fluvio_future::test]
+    async fn test_multi_partition_stream_poll_batch() {
+        //given
+        let (tx1, rx1) = async_channel::unbounded();
+        let partition_stream1 = SinglePartitionConsumerStream::new(
+            records_stream(0, ["1", "3"]),
+            0,
+            OffsetManagementStrategy::Manual,
+            Default::default(),
+            Duration::from_millis(100),
+            tx1,
+        );
+        let (tx2, rx2) = async_channel::unbounded();
+        let partition_stream2 = SinglePartitionConsumerStream::new(
+            records_stream(1, ["2", "4", "6"]),
+            1,
+            OffsetManagementStrategy::Manual,
+            Default::default(),
+            Duration::from_millis(100),
+            tx2,
+        );
+        let mut multi_stream =
+            MultiplePartitionConsumerStream::new([partition_stream1, partition_stream2]);
+
+        //when
+        let first = multi_stream
+            .poll_batch(4, Duration::from_secs(1))
+            .await
+            .expect("batch")
+            .expect("no error");
+        multi_stream
+            .offset_commit_batch(&first)
+            .await
+            .expect("committed");
+        let second = multi_stream
+            .poll_batch(4, Duration::from_secs(1))
+            .await
+            .expect("batch")
+            .expect("no error");
+        let end = multi_stream.poll_batch(4, Duration::from_secs(1)).await;
+
+        //then
+        let values: Vec<_> = first
+            .iter()
+            .map(|r| String::from_utf8_lossy(r.as_ref()).to_string())
+            .collect();
+        assert_eq!(values, ["1", "2", "3", "4"]);
+        assert_eq!(second.len(), 1);
+        assert!(end.is_none());
+
+        fluvio_future::task::spawn(async move {
+            for (rx, expected) in [(rx1, 1), (rx2, 1)] {
+                let message = rx.recv().await;
+                assert!(
+                    matches!(
+                        message,
+                        Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == expected
+                    ),
+                    "{message:?}"
+                );
+                if let Ok(StreamToServer::FlushManagedOffset {
+                    offset: _,
+                    callback,
+                    ..
+                }) = message
+                {
+                    callback.send(ErrorCode::None).await;
+                }
+            }
+        });
+        assert!(multi_stream.offset_flush().await.is_ok());
+    }
+
+    #[fluvio_future::test]
+    async fn test_poll_batch_defers_error() {
+        //given
+        let records: Vec<_> = records_stream(0, ["1", "2", "3"]).collect().await;
+        let mut input = records.into_iter();
+        let items = vec![
+            input.next().expect("record"),
+            input.next().expect("record"),
+            Err(ErrorCode::Other("broken".to_owned())),
+            input.next().expect("record"),
+        ];
+        let (tx, _rx) = async_channel::unbounded();
+        let mut partition_stream = SinglePartitionConsumerStream::new(
+            futures_util::stream::iter(items),
+            0,
+            OffsetManagementStrategy::Manual,
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+
+        //when
+        let first = partition_stream
+            .poll_batch(10, Duration::from_secs(1))
+            .await
+            .expect("batch")
+            .expect("records before error");
+        let error = partition_stream
+            .poll_batch(10, Duration::from_secs(1))
+            .await
+            .expect("error");
+        let last = partition_stream
+            .poll_batch(10, Duration::from_secs(1))
+            .await
+            .expect("batch")
+            .expect("records after error");
+
+        //then
+        assert_eq!(first.len(), 2);
+        assert_eq!(error.err(), Some(ErrorCode::Other("broken".to_owned())));
+        assert_eq!(last.len(), 1);
+        assert_eq!(last.records()[0].offset(), 2);
+    }
+
+    #[fluvio_future::test]
+    async fn test_stream_control_unknown_partition() {
+        //given
+        let (tx, _rx) = async_channel::unbounded();
+        let mut partition_stream = SinglePartitionConsumerStream::new(
+            records_stream(0, []),
+            0,
+            OffsetManagementStrategy::Manual,
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+
+        //when
+        let pause = partition_stream.pause(&[0, 3]).await;
+        let seek = partition_stream.seek(3, Offset::beginning()).await;
+        let commit = partition_stream.offset_commit_partition(3, 1).await;
+
+        //then
+        assert_eq!(pause, Err(ErrorCode::StreamPartitionNotFound(3)));
+        assert!(!partition_stream.control.is_paused());
+        assert_eq!(seek, Err(ErrorCode::StreamPartitionNotFound(3)));
+        assert_eq!(commit, Err(ErrorCode::StreamPartitionNotFound(3)));
+        assert!(partition_stream.offset_commit_partition(0, 1).await.is_ok());
+    }
+
+    #[fluvio_future::test]
+    async fn test_offset_commit_metadata_too_large() {
+        //given
+        let (tx, _rx) = async_channel::unbounded();
+        let mut partition_stream = SinglePartitionConsumerStream::new(
+            records_stream(0, []),
+            0,
+            OffsetManagementStrategy::Manual,
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+
+        //when
+        let too_large = partition_stream
+            .offset_commit_with_metadata(0, 1, vec![0; MAX_CONSUMER_OFFSET_METADATA_SIZE + 1])
+            .await;
+        let largest = partition_stream
+            .offset_commit_with_metadata(0, 1, vec![0; MAX_CONSUMER_OFFSET_METADATA_SIZE])
+            .await;
+
+        //then
+        assert_eq!(
+            too_large,
+            Err(ErrorCode::OffsetMetadataTooLarge(
+                MAX_CONSUMER_OFFSET_METADATA_SIZE as u32 + 1
+            ))
+        );
+        assert!(largest.is_ok());
+    }
+
     #[fluvio_future::test]
     async fn test_none_offset_strategy_raise_error_on_commit() {
         //given
         let (tx, _rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
             records_stream(0, []),
+            0,
             OffsetManagementStrategy::None,
             Default::default(),
             Duration::from_millis(100),
@@ -451,6 +1082,7 @@ mod tests {
         let (tx, _rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
             records_stream(0, []),
+            0,
             OffsetManagementStrategy::None,
             Default::default(),
             Duration::from_millis(100),
@@ -470,6 +1102,7 @@ mod tests {
         let (tx, rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
             records_stream(0, ["1", "2", "3", "4"]),
+            0,
             OffsetManagementStrategy::Manual,
             Default::default(),
             Duration::from_millis(100),
@@ -489,11 +1122,12 @@ mod tests {
             let message = rx.recv().await;
             assert!(matches!(
                 message,
-                Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 2
+                Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 2
             ));
             if let Ok(StreamToServer::FlushManagedOffset {
                 offset: _,
                 callback,
+                ..
             }) = message
             {
                 callback.send(ErrorCode::None).await;
@@ -510,6 +1144,7 @@ mod tests {
         let (tx1, rx1) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
             records_stream(0, ["1"]),
+            0,
             OffsetManagementStrategy::Manual,
             Default::default(),
             Duration::from_millis(100),
@@ -518,6 +1153,7 @@ mod tests {
         let (tx2, rx2) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
             records_stream(1, ["2", "4", "6"]),
+            1,
             OffsetManagementStrategy::Manual,
             Default::default(),
             Duration::from_millis(100),
@@ -540,13 +1176,14 @@ mod tests {
             assert!(
                 matches!(
                     message,
-                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 0
+                    Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 0
                 ),
                 "{message:?}"
             );
             if let Ok(StreamToServer::FlushManagedOffset {
                 offset: _,
                 callback,
+                ..
             }) = message
             {
                 callback.send(ErrorCode::None).await;
@@ -558,13 +1195,14 @@ mod tests {
             assert!(
                 matches!(
                     message,
-                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 1
+                    Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 1
                 ),
                 "{message:?}"
             );
             if let Ok(StreamToServer::FlushManagedOffset {
                 offset: _,
                 callback,
+                ..
             }) = message
             {
                 callback.send(ErrorCode::None).await;
@@ -581,6 +1219,7 @@ mod tests {
         let (tx, rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
             records_stream(0, ["1", "2", "3", "4"]),
+            0,
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1000),
             Duration::from_millis(100),
@@ -598,7 +1237,7 @@ mod tests {
         assert!(
             matches!(
                 message1,
-                Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 0
+                Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 0
             ),
             "{message1:?}"
         );
@@ -606,7 +1245,7 @@ mod tests {
         assert!(
             matches!(
                 message2,
-                Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 2
+                Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 2
             ),
             "{message2:?}"
         );
@@ -621,6 +1260,7 @@ mod tests {
         let (tx1, rx1) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
             records_stream(0, ["1"]),
+            0,
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1000),
             Duration::from_millis(100),
@@ -629,6 +1269,7 @@ mod tests {
         let (tx2, rx2) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
             records_stream(1, ["2", "4", "6"]),
+            1,
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1000),
             Duration::from_millis(100),
@@ -650,7 +1291,7 @@ mod tests {
             assert!(
                 matches!(
                     message1,
-                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 0
+                    Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 0
                 ),
                 "{message1:?}"
             );
@@ -662,7 +1303,7 @@ mod tests {
             assert!(
                 matches!(
                     message1,
-                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 0
+                    Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 0
                 ),
                 "{message1:?}"
             );
@@ -670,7 +1311,7 @@ mod tests {
             assert!(
                 matches!(
                     message2,
-                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 1
+                    Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 1
                 ),
                 "{message2:?}"
             );
@@ -685,6 +1326,7 @@ mod tests {
         let (tx, rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
             records_stream(0, ["1", "2", "3", "4"]),
+            0,
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1),
             Duration::from_millis(100),
@@ -701,7 +1343,7 @@ mod tests {
         assert!(
             matches!(
                 message1,
-                Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 0
+                Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 0
             ),
             "{message1:?}"
         );
@@ -710,7 +1352,7 @@ mod tests {
         assert!(
             matches!(
                 message2,
-                Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 1
+                Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 1
             ),
             "{message2:?}"
         );
@@ -725,6 +1367,7 @@ mod tests {
         let (tx1, rx1) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
             records_stream(0, ["1"]),
+            0,
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1),
             Duration::from_millis(100),
@@ -733,6 +1376,7 @@ mod tests {
         let (tx2, rx2) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
             records_stream(1, ["2", "4", "6"]),
+            1,
             OffsetManagementStrategy::Auto,
             Duration::from_secs(1),
             Duration::from_millis(100),
@@ -754,7 +1398,7 @@ mod tests {
             assert!(
                 matches!(
                     message1,
-                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 0
+                    Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 0
                 ),
                 "{message1:?}"
             );
@@ -766,7 +1410,7 @@ mod tests {
             assert!(
                 matches!(
                     message1,
-                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 0
+                    Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 0
                 ),
                 "{message1:?}"
             );
@@ -774,7 +1418,7 @@ mod tests {
             assert!(
                 matches!(
                     message2,
-                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 1
+                    Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 1
                 ),
                 "{message2:?}"
             );
@@ -789,6 +1433,7 @@ mod tests {
         let (tx, rx) = async_channel::unbounded();
         let mut partition_stream = SinglePartitionConsumerStream::new(
             records_stream(0, ["1", "2", "3", "4"]),
+            0,
             OffsetManagementStrategy::Manual,
             Default::default(),
             Duration::from_millis(100),
@@ -802,13 +1447,14 @@ mod tests {
             assert!(
                 matches!(
                     message,
-                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 0
+                    Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 0
                 ),
                 "{message:?}"
             );
             if let Ok(StreamToServer::FlushManagedOffset {
                 offset: _,
                 callback,
+                ..
             }) = message
             {
                 callback.send(ErrorCode::SpuOffline).await;
@@ -829,6 +1475,7 @@ mod tests {
         let (tx1, rx1) = async_channel::unbounded();
         let partition_stream1 = SinglePartitionConsumerStream::new(
             records_stream(0, ["1"]),
+            0,
             OffsetManagementStrategy::Manual,
             Default::default(),
             Duration::from_millis(100),
@@ -837,6 +1484,7 @@ mod tests {
         let (tx2, rx2) = async_channel::unbounded();
         let partition_stream2 = SinglePartitionConsumerStream::new(
             records_stream(1, ["2", "4", "6"]),
+            1,
             OffsetManagementStrategy::Manual,
             Default::default(),
             Duration::from_millis(100),
@@ -854,13 +1502,14 @@ mod tests {
             assert!(
                 matches!(
                     message,
-                    Ok(StreamToServer::FlushManagedOffset { callback: _, offset }) if offset == 0
+                    Ok(StreamToServer::FlushManagedOffset { offset, .. }) if offset == 0
                 ),
                 "{message:?}"
             );
             if let Ok(StreamToServer::FlushManagedOffset {
                 offset: _,
                 callback,
+                ..
             }) = message
             {
                 callback.send(ErrorCode::SpuOffline).await;
@@ -871,6 +1520,7 @@ mod tests {
             if let Ok(StreamToServer::FlushManagedOffset {
                 callback,
                 offset: _,
+                ..
             }) = message
             {
                 callback.send(ErrorCode::None).await;
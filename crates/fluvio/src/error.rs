@@ -1,4 +1,5 @@
 use std::io::Error as IoError;
+use std::time::Duration;
 
 use fluvio_types::PartitionId;
 use fluvio_types::SpuId;
@@ -17,6 +18,7 @@ pub type Result<T, Err = FluvioError> = std::result::Result<T, Err>;
 
 /// Possible errors that may arise when using Fluvio
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum FluvioError {
     #[error(transparent)]
     Io(#[from] IoError),
@@ -65,6 +67,8 @@ To interact with this cluster, please install the matching CLI version using the
     #[cfg(feature = "smartengine")]
     #[error("SmartModuleEngine config: {0}")]
     SmartModuleConfigBuilder(#[from] fluvio_smartengine::SmartModuleConfigBuilderError),
+    #[error("Operation timed out after {0:?}")]
+    Timeout(Duration),
     #[error("Unknown error: {0}")]
     Other(String),
 }
@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::sync::Arc;
 
@@ -9,7 +10,9 @@ use tracing::{debug, info};
 
 use fluvio_future::net::DomainConnector;
 use fluvio_sc_schema::partition::PartitionMirrorConfig;
-use fluvio_sc_schema::topic::{MirrorConfig, PartitionMap, ReplicaSpec};
+use fluvio_sc_schema::topic::{MirrorConfig, PartitionMap, ReplicaSpec, TopicSpec};
+use fluvio_sc_schema::dictionary::DictionarySpec;
+use fluvio_compression::Dictionary;
 use fluvio_sc_schema::objects::ObjectApiWatchRequest;
 use fluvio_types::PartitionId;
 use fluvio_socket::{
@@ -161,8 +164,10 @@ impl Fluvio {
             .get_or_try_init(|| async {
                 let metadata =
                     MetadataStores::start(self.socket.clone(), self.watch_version).await?;
-                let pool = SpuSocketPool::start(self.config.clone(), metadata);
-                Ok(Arc::new(pool?))
+                let pool = SpuSocketPool::start(self.config.clone(), metadata)?
+                    .with_client_rack(self.cluster_config.client.rack.clone())
+                    .with_connections(self.cluster_config.connections.clone(), self.metrics());
+                Ok(Arc::new(pool))
             })
             .await
             .cloned()
@@ -223,8 +228,63 @@ impl Fluvio {
         if !spu_pool.topic_exists(topic.clone()).await? {
             return Err(FluvioError::TopicNotFound(topic).into());
         }
+        let topic_spec = spu_pool
+            .metadata
+            .topics()
+            .lookup_by_key(&topic)
+            .await?
+            .ok_or_else(|| FluvioError::TopicNotFound(topic.clone()))?
+            .spec;
+        let dictionary = self.topic_dictionary(&topic_spec).await?;
+
+        TopicProducer::new(
+            topic,
+            spu_pool,
+            Arc::new(config),
+            self.metric.clone(),
+            dictionary,
+        )
+        .await
+    }
+
+    /// Load zstd dictionary used by the topic compression, if any
+    pub(crate) async fn topic_dictionary(
+        &self,
+        topic_spec: &TopicSpec,
+    ) -> Result<Option<Dictionary>> {
+        let Some(name) = topic_spec.get_compression_dictionary() else {
+            return Ok(None);
+        };
+
+        let dictionary = self
+            .admin()
+            .await
+            .list::<DictionarySpec, _>(vec![name.clone()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                FluvioError::Other(format!("compression dictionary '{name}' not found"))
+            })?;
 
-        TopicProducer::new(topic, spu_pool, Arc::new(config), self.metric.clone()).await
+        Ok(Some(Dictionary::new(dictionary.spec.data.to_vec())?))
+    }
+
+    /// Load zstd dictionary of the topic, none if topic is not known yet
+    async fn topic_dictionary_by_name(
+        &self,
+        spu_pool: &SpuSocketPool,
+        topic: &str,
+    ) -> Result<Option<Dictionary>> {
+        match spu_pool
+            .metadata
+            .topics()
+            .lookup_by_key(&topic.to_owned())
+            .await?
+        {
+            Some(topic) => self.topic_dictionary(&topic.spec).await,
+            None => Ok(None),
+        }
     }
 
     /// Creates a new `PartitionConsumer` for the given topic and partition
@@ -241,12 +301,12 @@ impl Fluvio {
     ) -> Result<PartitionConsumer> {
         let topic = topic.into();
         debug!(topic = &*topic, "Creating consumer");
-        Ok(PartitionConsumer::new(
-            topic,
-            partition,
-            self.spu_pool().await?,
-            self.metric.clone(),
-        ))
+        let spu_pool = self.spu_pool().await?;
+        let dictionary = self.topic_dictionary_by_name(&spu_pool, &topic).await?;
+        Ok(
+            PartitionConsumer::new(topic, partition, spu_pool, self.metric.clone())
+                .with_dictionary(dictionary),
+        )
     }
 
     /// Creates a new `MultiplePartitionConsumer`
@@ -274,11 +334,23 @@ impl Fluvio {
         &self,
         strategy: PartitionSelectionStrategy,
     ) -> Result<MultiplePartitionConsumer> {
-        Ok(MultiplePartitionConsumer::new(
-            strategy,
-            self.spu_pool().await?,
-            self.metric.clone(),
-        ))
+        let spu_pool = self.spu_pool().await?;
+        let topics: HashSet<&String> = match &strategy {
+            PartitionSelectionStrategy::All(topic) => HashSet::from([topic]),
+            PartitionSelectionStrategy::Multiple(pairs) => {
+                pairs.iter().map(|(topic, _)| topic).collect()
+            }
+        };
+        let mut dictionaries = HashMap::new();
+        for topic in topics {
+            if let Some(dictionary) = self.topic_dictionary_by_name(&spu_pool, topic).await? {
+                dictionaries.insert(topic.clone(), dictionary);
+            }
+        }
+        Ok(
+            MultiplePartitionConsumer::new(strategy, spu_pool, self.metric.clone())
+                .with_dictionaries(dictionaries),
+        )
     }
 
     /// Creates a new [ConsumerStream] instance.
@@ -414,10 +486,12 @@ impl Fluvio {
         } else {
             config.partition.clone()
         };
+        let dictionary = self.topic_dictionary(&topic_spec).await?;
         let mut partition_streams = Vec::with_capacity(partitions.len());
         for partition in partitions {
             let consumer =
-                PartitionConsumer::new(topic.clone(), partition, spu_pool.clone(), self.metrics());
+                PartitionConsumer::new(topic.clone(), partition, spu_pool.clone(), self.metrics())
+                    .with_dictionary(dictionary.clone());
             partition_streams.push(consumer.consumer_stream_with_config(config.clone()).await?);
         }
         Ok(MultiplePartitionConsumerStream::new(partition_streams))
@@ -452,6 +526,41 @@ impl Fluvio {
             .collect())
     }
 
+    /// Returns the offset committed by the consumer for the replica, with its metadata.
+    ///
+    /// Sinks storing an external transaction id in the offset metadata (see
+    /// [`ConsumerStream::offset_commit_with_metadata()`]) can use it at startup to find
+    /// where to resume from.
+    pub async fn consumer_offset(
+        &self,
+        consumer_id: impl Into<String>,
+        replica_id: impl Into<fluvio_protocol::record::ReplicaKey>,
+    ) -> Result<Option<ConsumerOffset>> {
+        use fluvio_protocol::link::ErrorCode;
+        use fluvio_spu_schema::server::consumer_offset::FetchConsumerOffsetsRequest;
+
+        use crate::spu::SpuDirectory;
+
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool
+            .create_serial_socket(&CONSUMER_REPLICA_KEY.into())
+            .await?;
+        let response = socket
+            .send_receive(FetchConsumerOffsetsRequest::with_opts(
+                Some(replica_id.into()),
+                Some(consumer_id.into()),
+            ))
+            .await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("fetch consumer offset failed with: {}", response.error_code);
+        }
+        Ok(response
+            .consumers
+            .into_iter()
+            .next()
+            .map(ConsumerOffset::from))
+    }
+
     /// Delete a consumer offset for the given name and the replica.
     pub async fn delete_consumer_offset(
         &self,
@@ -11,9 +11,13 @@ mod offset;
 mod producer;
 mod sync;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod blocking;
 pub mod config;
 pub mod consumer;
 pub mod metrics;
+#[cfg(feature = "otel")]
+pub mod otel;
 pub mod spu;
 
 pub use error::FluvioError;
@@ -22,7 +26,8 @@ pub use producer::{
     ProducerCallback, SharedProducerCallback, ProduceCompletionBatchEvent,
     TopicProducerConfigBuilder, TopicProducerConfig, TopicProducer, TopicProducerPool, RecordKey,
     ProduceOutput, FutureRecordMetadata, RecordMetadata, DeliverySemantic, RetryPolicy,
-    RetryStrategy, Partitioner, PartitionerConfig, ProducerError,
+    RetryStrategy, Partitioner, PartitionerConfig, PartitionerKind, ProducerError, SpillConfig,
+    SpillOverflowPolicy,
 };
 #[cfg(feature = "smartengine")]
 pub use producer::{SmartModuleChainBuilder, SmartModuleConfig, SmartModuleInitialData};
@@ -39,7 +44,7 @@ pub use offset::Offset;
 pub use crate::admin::FluvioAdmin;
 pub use crate::fluvio::Fluvio;
 
-pub use fluvio_compression::Compression;
+pub use fluvio_compression::{Compression, Dictionary};
 
 pub use fluvio_types::PartitionId;
 use tracing::instrument;
@@ -196,6 +201,10 @@ pub mod metadata {
         pub use fluvio_sc_schema::tableformat::*;
     }
 
+    pub mod dictionary {
+        pub use fluvio_sc_schema::dictionary::*;
+    }
+
     pub mod core {
         pub use fluvio_sc_schema::core::*;
     }
@@ -11,6 +11,10 @@ pub struct ClientMetrics {
     consumer: RecordCounter,
     producer_connector: RecordCounter,
     producer_client: RecordCounter,
+    #[serde(default)]
+    producer_spill: SpillMetrics,
+    #[serde(default)]
+    connections: ConnectionMetrics,
     #[cfg(feature = "smartengine")]
     smartmodules: Mutex<HashMap<String, fluvio_smartengine::metrics::SmartModuleChainMetrics>>,
 }
@@ -37,6 +41,26 @@ impl ClientMetrics {
         &self.producer_client
     }
 
+    /// producer spill buffer counters
+    #[inline]
+    pub fn producer_spill(&self) -> &SpillMetrics {
+        &self.producer_spill
+    }
+
+    /// queueing of requests on connections to SPUs
+    #[inline]
+    pub fn connections(&self) -> &ConnectionMetrics {
+        &self.connections
+    }
+
+    /// copy of metrics of SmartModule chains executed by the client
+    #[cfg(feature = "smartengine")]
+    pub fn smartmodules(
+        &self,
+    ) -> HashMap<String, fluvio_smartengine::metrics::SmartModuleChainMetrics> {
+        self.smartmodules.lock().expect("Poisoned lock").clone()
+    }
+
     #[cfg(feature = "smartengine")]
     pub(crate) fn metrics_append(
         &self,
@@ -54,6 +78,34 @@ impl ClientMetrics {
     }
 }
 
+/// Records moved through producer spill buffers
+#[derive(Default, Debug, Deserialize, Serialize)]
+pub struct SpillMetrics {
+    spilled: RecordCounter,
+    drained: RecordCounter,
+    dropped: RecordCounter,
+}
+
+impl SpillMetrics {
+    /// written to disk while leader was unreachable
+    #[inline]
+    pub fn spilled(&self) -> &RecordCounter {
+        &self.spilled
+    }
+
+    /// sent from disk after leader became reachable
+    #[inline]
+    pub fn drained(&self) -> &RecordCounter {
+        &self.drained
+    }
+
+    /// removed from disk before being sent, because of size or age limits
+    #[inline]
+    pub fn dropped(&self) -> &RecordCounter {
+        &self.dropped
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(any(target_arch = "wasm32", target_arch = "arm"))] {
 
@@ -70,6 +122,47 @@ cfg_if::cfg_if! {
             #[inline]
             pub(crate) fn add_bytes(&self, _value: u64) {
             }
+
+            #[inline]
+            pub fn records(&self) -> u64 {
+                0
+            }
+
+            #[inline]
+            pub fn bytes(&self) -> u64 {
+                0
+            }
+        }
+
+        #[derive(Default, Debug, Deserialize, Serialize)]
+        pub struct ConnectionMetrics {
+
+        }
+
+        impl ConnectionMetrics {
+            #[inline]
+            pub(crate) fn add_queue_time(&self, _value: std::time::Duration) {
+            }
+
+            #[inline]
+            pub fn requests(&self) -> u64 {
+                0
+            }
+
+            #[inline]
+            pub fn queued(&self) -> u64 {
+                0
+            }
+
+            #[inline]
+            pub fn queue_time_micros(&self) -> u64 {
+                0
+            }
+
+            #[inline]
+            pub fn max_queue_time_micros(&self) -> u64 {
+                0
+            }
         }
 
     } else {
@@ -91,6 +184,63 @@ cfg_if::cfg_if! {
             pub(crate) fn add_bytes(&self, value: u64) {
                 self.bytes.fetch_add(value, Ordering::SeqCst);
             }
+
+            #[inline]
+            pub fn records(&self) -> u64 {
+                self.records.load(Ordering::SeqCst)
+            }
+
+            #[inline]
+            pub fn bytes(&self) -> u64 {
+                self.bytes.load(Ordering::SeqCst)
+            }
+        }
+
+        /// Requests limited by maximum in flight requests of a connection
+        #[derive(Default, Debug, Serialize, Deserialize)]
+        pub struct ConnectionMetrics {
+            pub requests: AtomicU64,
+            pub queued: AtomicU64,
+            pub queue_time_micros: AtomicU64,
+            pub max_queue_time_micros: AtomicU64,
+        }
+
+        impl ConnectionMetrics {
+            #[inline]
+            pub(crate) fn add_queue_time(&self, value: std::time::Duration) {
+                self.requests.fetch_add(1, Ordering::SeqCst);
+                if value.is_zero() {
+                    return;
+                }
+                let micros = u64::try_from(value.as_micros()).unwrap_or(u64::MAX);
+                self.queued.fetch_add(1, Ordering::SeqCst);
+                self.queue_time_micros.fetch_add(micros, Ordering::SeqCst);
+                self.max_queue_time_micros.fetch_max(micros, Ordering::SeqCst);
+            }
+
+            /// requests sent
+            #[inline]
+            pub fn requests(&self) -> u64 {
+                self.requests.load(Ordering::SeqCst)
+            }
+
+            /// requests which waited for an earlier request to be answered
+            #[inline]
+            pub fn queued(&self) -> u64 {
+                self.queued.load(Ordering::SeqCst)
+            }
+
+            /// total time requests waited before being sent
+            #[inline]
+            pub fn queue_time_micros(&self) -> u64 {
+                self.queue_time_micros.load(Ordering::SeqCst)
+            }
+
+            /// longest time a request waited before being sent
+            #[inline]
+            pub fn max_queue_time_micros(&self) -> u64 {
+                self.max_queue_time_micros.load(Ordering::SeqCst)
+            }
         }
 
     }
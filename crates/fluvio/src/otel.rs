@@ -0,0 +1,172 @@
+//! OpenTelemetry integration.
+//!
+//! With the `otel` feature the producer attaches the W3C trace context of the
+//! current [`tracing`] span to every record it sends, and consumers forward it
+//! when opening a stream, so producer, SPU and consumer spans end up in the same
+//! trace. Consumers read it back with [`ConsumerRecord::trace_context`] and
+//! continue the trace with [`set_parent`].
+//!
+//! The `otlp` feature adds [`init_otlp_tracer`], which installs a subscriber
+//! exporting spans to an OTLP collector.
+//!
+//! [`ConsumerRecord::trace_context`]: crate::consumer::Record::trace_context
+
+use opentelemetry::Context;
+use opentelemetry::trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use fluvio_protocol::record::TraceContext;
+
+/// Returns the trace context of the current span, if it belongs to a trace
+pub fn current_trace_context() -> Option<TraceContext> {
+    trace_context_of(&Span::current())
+}
+
+/// Returns the trace context of `span`, if it belongs to a trace
+pub fn trace_context_of(span: &Span) -> Option<TraceContext> {
+    let context = span.context();
+    let otel_span = context.span();
+    let span_context = otel_span.span_context();
+    if !span_context.is_valid() {
+        return None;
+    }
+    let trace_context = TraceContext::new(
+        u128::from_be_bytes(span_context.trace_id().to_bytes()),
+        u64::from_be_bytes(span_context.span_id().to_bytes()),
+        span_context.is_sampled(),
+    );
+    Some(trace_context.with_tracestate(span_context.trace_state().header()))
+}
+
+/// Converts a propagated trace context into a remote OpenTelemetry parent
+pub fn to_context(trace_context: &TraceContext) -> Context {
+    if trace_context.is_empty() {
+        return Context::new();
+    }
+    let flags = if trace_context.is_sampled() {
+        TraceFlags::SAMPLED
+    } else {
+        TraceFlags::default()
+    };
+    let trace_state = trace_context
+        .tracestate()
+        .and_then(|state| state.parse::<TraceState>().ok())
+        .unwrap_or_default();
+    let span_context = SpanContext::new(
+        TraceId::from_bytes(trace_context.trace_id().to_be_bytes()),
+        SpanId::from_bytes(trace_context.span_id().to_be_bytes()),
+        flags,
+        true,
+        trace_state,
+    );
+    Context::new().with_remote_span_context(span_context)
+}
+
+/// Makes `span` a child of the span the trace context was propagated from
+pub fn set_parent(span: &Span, trace_context: &TraceContext) {
+    if !trace_context.is_empty() {
+        span.set_parent(to_context(trace_context));
+    }
+}
+
+#[cfg(feature = "otlp")]
+pub use otlp::{OtlpGuard, init_otlp_tracer};
+
+#[cfg(feature = "otlp")]
+mod otlp {
+    use anyhow::Result;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::SpanExporter;
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+    use tracing_subscriber::prelude::*;
+
+    const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+    const OTLP_TRACES_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT";
+
+    /// Flushes and shuts down the exporter when dropped
+    pub struct OtlpGuard {
+        provider: SdkTracerProvider,
+    }
+
+    impl Drop for OtlpGuard {
+        fn drop(&mut self) {
+            if let Err(err) = self.provider.shutdown() {
+                tracing::warn!(%err, "failed to shut down OTLP exporter");
+            }
+        }
+    }
+
+    /// Installs a global subscriber logging to stderr (filtered by `RUST_LOG`)
+    /// and exporting spans over OTLP/HTTP.
+    ///
+    /// Returns `None` without installing anything when neither
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT` nor `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`
+    /// is set, so callers can fall back to their regular logger. The exporter
+    /// is configured through the standard `OTEL_*` environment variables.
+    pub fn init_otlp_tracer(service_name: &'static str) -> Result<Option<OtlpGuard>> {
+        if std::env::var_os(OTLP_ENDPOINT_ENV).is_none()
+            && std::env::var_os(OTLP_TRACES_ENDPOINT_ENV).is_none()
+        {
+            return Ok(None);
+        }
+
+        let exporter = SpanExporter::builder().with_http().build()?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(Resource::builder().with_service_name(service_name).build())
+            .build();
+        let tracer = provider.tracer(service_name);
+
+        tracing_subscriber::registry()
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(std::io::stderr)
+                    .with_filter(EnvFilter::from_default_env()),
+            )
+            .with(
+                tracing_opentelemetry::layer()
+                    .with_tracer(tracer)
+                    .with_filter(LevelFilter::INFO),
+            )
+            .try_init()?;
+
+        Ok(Some(OtlpGuard { provider }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_context_round_trip() {
+        let trace_context =
+            TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+                .expect("valid")
+                .with_tracestate("vendor=value");
+
+        let context = to_context(&trace_context);
+        let span = context.span();
+        let span_context = span.span_context();
+        assert!(span_context.is_remote());
+        assert!(span_context.is_sampled());
+        assert_eq!(
+            span_context.trace_id(),
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").expect("trace id")
+        );
+        assert_eq!(
+            span_context.span_id(),
+            SpanId::from_hex("00f067aa0ba902b7").expect("span id")
+        );
+        assert_eq!(span_context.trace_state().get("vendor"), Some("value"));
+    }
+
+    #[test]
+    fn test_no_context_outside_trace() {
+        assert!(current_trace_context().is_none());
+        assert!(!to_context(&TraceContext::default()).has_active_span());
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use chrono::Utc;
+use once_cell::sync::Lazy;
+
+use fluvio_protocol::Encoder;
+use fluvio_protocol::record::{
+    Batch, RawRecords, Record, RecordChunk, RecordData, RECORD_HEADERS_VERSION,
+};
+
+use super::ProducerError;
+
+/// room for record and value lengths growing when the value is added to a chunk
+const VARINT_MARGIN: usize = 10;
+
+/// Largest record which can be sent in a request of `max_request_size`
+fn max_record_size(max_request_size: usize) -> usize {
+    let empty_batch =
+        Batch::<RawRecords>::default().write_size(0) + Vec::<RawRecords>::default().write_size(0);
+    max_request_size.saturating_sub(empty_batch)
+}
+
+pub(crate) fn is_oversized(record: &Record, max_request_size: usize) -> bool {
+    record.write_size(RECORD_HEADERS_VERSION) > max_record_size(max_request_size)
+}
+
+/// Id of the next value split into chunks, unique across producers
+fn next_chunk_id() -> u64 {
+    static SEED: Lazy<RandomState> = Lazy::new(RandomState::new);
+    static SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+
+    SEED.hash_one((
+        SEQUENCE.fetch_add(1, Ordering::Relaxed),
+        Utc::now().timestamp_nanos_opt(),
+    ))
+}
+
+/// Splits the value of `record` into records which fit into `max_request_size`.
+/// Every chunk keeps the key and headers of the record.
+pub(crate) fn split_record(
+    record: Record,
+    max_request_size: usize,
+) -> Result<Vec<Record>, ProducerError> {
+    let record_size = record.write_size(RECORD_HEADERS_VERSION);
+    let value = record.value;
+    let value_len = value.len();
+
+    let mut template = Record {
+        key: record.key,
+        header_entries: record.header_entries,
+        ..Default::default()
+    };
+    RecordChunk::default().inject(template.headers_mut());
+    let chunk_size = max_record_size(max_request_size)
+        .saturating_sub(template.write_size(RECORD_HEADERS_VERSION) + VARINT_MARGIN);
+    if chunk_size == 0 {
+        return Err(ProducerError::RecordTooLarge(record_size, max_request_size));
+    }
+
+    let count = u32::try_from(value_len.div_ceil(chunk_size))
+        .map_err(|_| ProducerError::RecordTooLarge(record_size, max_request_size))?;
+    let id = next_chunk_id();
+
+    Ok((0..count)
+        .map(|index| {
+            let start = index as usize * chunk_size;
+            let end = value_len.min(start + chunk_size);
+            let mut chunk = template.clone();
+            chunk.value = RecordData::from(value.slice(start..end));
+            RecordChunk::new(id, index, count, value_len as u64).inject(chunk.headers_mut());
+            chunk
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::RecordKey;
+
+    use super::*;
+
+    #[test]
+    fn test_split_record() {
+        let value: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+        let mut record = Record::from((RecordKey::from("key"), value.clone()));
+        record.headers_mut().insert("traceparent", "abc");
+        let max_request_size = 4096;
+        assert!(is_oversized(&record, max_request_size));
+
+        let chunks = split_record(record, max_request_size).expect("split");
+        assert_eq!(chunks.len(), 3);
+
+        let mut restored = Vec::new();
+        for (index, chunk) in chunks.iter().enumerate() {
+            assert!(!is_oversized(chunk, max_request_size));
+            assert_eq!(chunk.key().map(|key| key.as_ref()), Some(b"key".as_slice()));
+            assert_eq!(chunk.headers().get("traceparent"), Some(b"abc".as_slice()));
+
+            let position = RecordChunk::extract(chunk.headers()).expect("chunk header");
+            assert_eq!(
+                position.id(),
+                RecordChunk::extract(chunks[0].headers()).unwrap().id()
+            );
+            assert_eq!(position.index() as usize, index);
+            assert_eq!(position.count(), 3);
+            assert_eq!(position.value_len(), value.len() as u64);
+            restored.extend_from_slice(chunk.value().as_ref());
+        }
+        assert_eq!(restored, value);
+    }
+
+    #[test]
+    fn test_split_record_key_too_large() {
+        let record = Record::from((RecordKey::from(vec![0u8; 200]), vec![0u8; 1000]));
+        assert!(matches!(
+            split_record(record, 200),
+            Err(ProducerError::RecordTooLarge(_, 200))
+        ));
+    }
+
+    #[test]
+    fn test_chunk_ids_differ() {
+        assert_ne!(next_chunk_id(), next_chunk_id());
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::{self, Debug, Display, Formatter};
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -11,12 +12,13 @@ use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
 
 use fluvio_compression::Compression;
 use fluvio_types::PartitionId;
+use fluvio_types::partitioner::PartitionerKind;
 use serde::{Serialize, Deserialize};
 
 use crate::producer::partitioning::{Partitioner, SiphashRoundRobinPartitioner};
 
 use super::accumulator::SharedProducerCallback;
-use super::partitioning::SpecificPartitioner;
+use super::partitioning::{SpecificPartitioner, partitioner_by_kind};
 
 const DEFAULT_LINGER_MS: u64 = 0;
 const DEFAULT_TIMEOUT_MS: u64 = 1500;
@@ -29,6 +31,9 @@ const DEFAULT_INITIAL_DELAY: Duration = Duration::from_millis(20);
 const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(200);
 const DEFAULT_MAX_RETRIES: usize = 4;
 
+const DEFAULT_SPILL_MAX_BYTES: u64 = 1_073_741_824;
+const DEFAULT_SPILL_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 fn default_batch_size() -> usize {
     DEFAULT_BATCH_SIZE_BYTES
 }
@@ -97,6 +102,11 @@ pub struct TopicProducerConfig {
     #[allow(dead_code)]
     pub(crate) compression: Option<Compression>,
 
+    /// Compression level, overrides the topic level compression level.
+    /// Valid range depends on the algorithm: gzip 0-9, zstd 1-22 (negative values for fast modes).
+    #[builder(setter(into, strip_option), default)]
+    pub(crate) compression_level: Option<i32>,
+
     /// Max time duration that the server is allowed to process the batch.
     #[builder(default = "default_timeout()")]
     pub(crate) timeout: Duration,
@@ -124,12 +134,30 @@ pub struct TopicProducerConfig {
     /// Callback that will be called after the record is sent to the server.
     #[builder(setter(into, strip_option), default)]
     pub(crate) callback: Option<SharedProducerCallback>,
+
+    /// Keep batches on disk while the partition leader is unreachable, see [`SpillConfig`].
+    #[builder(setter(into, strip_option), default)]
+    pub(crate) spill: Option<SpillConfig>,
+
+    /// Split values too large for `max_request_size` into chunk records of the same partition,
+    /// instead of failing with [`ProducerError::RecordTooLarge`].
+    /// Consumers reassemble the chunks into the original record.
+    /// Can't be combined with `smartmodules`, which the SPU would apply to every chunk.
+    ///
+    /// [`ProducerError::RecordTooLarge`]: crate::producer::ProducerError::RecordTooLarge
+    #[builder(default)]
+    pub(crate) chunking: bool,
 }
 
 impl TopicProducerConfigBuilder {
     pub fn set_specific_partitioner(&mut self, partition_id: PartitionId) -> &mut Self {
         self.partitioner(Arc::new(SpecificPartitioner::new(partition_id)))
     }
+
+    /// Use one of built-in partitioners, see [`PartitionerKind`]
+    pub fn partitioner_kind(&mut self, kind: PartitionerKind) -> &mut Self {
+        self.partitioner(partitioner_by_kind(kind))
+    }
 }
 
 impl TopicProducerConfig {
@@ -153,6 +181,10 @@ impl TopicProducerConfig {
         self.compression
     }
 
+    pub fn compression_level(&self) -> Option<i32> {
+        self.compression_level
+    }
+
     pub fn timeout(&self) -> Duration {
         self.timeout
     }
@@ -168,6 +200,14 @@ impl TopicProducerConfig {
     pub fn smartmodules(&self) -> &Vec<SmartModuleInvocation> {
         &self.smartmodules
     }
+
+    pub fn spill(&self) -> Option<&SpillConfig> {
+        self.spill.as_ref()
+    }
+
+    pub fn chunking(&self) -> bool {
+        self.chunking
+    }
 }
 
 impl Default for TopicProducerConfig {
@@ -179,13 +219,70 @@ impl Default for TopicProducerConfig {
             batch_queue_size: default_batch_queue_size(),
             partitioner: default_partitioner(),
             compression: None,
+            compression_level: None,
             timeout: default_timeout(),
             isolation: default_isolation(),
             delivery_semantic: default_delivery(),
             smartmodules: vec![],
             callback: None,
+            spill: None,
+            chunking: false,
+        }
+    }
+}
+
+/// On-disk buffer for batches that can't be sent because the partition leader is unreachable.
+///
+/// While the leader is unreachable, batches are appended to the buffer instead of waiting in memory,
+/// so the producer keeps accepting records. Once the leader is back, spilled batches are sent in order
+/// before new ones. Spilled batches survive producer restarts.
+/// Futures of spilled records resolve once the batch is delivered.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SpillConfig {
+    /// Directory of the buffer, a sub-directory is created for each topic partition
+    pub dir: PathBuf,
+    /// Max bytes kept on disk for a partition
+    pub max_bytes: u64,
+    /// Spilled batches older than this are dropped
+    pub max_age: Duration,
+    /// What happens when the buffer is full
+    pub overflow: SpillOverflowPolicy,
+}
+
+impl SpillConfig {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_bytes: DEFAULT_SPILL_MAX_BYTES,
+            max_age: DEFAULT_SPILL_MAX_AGE,
+            overflow: SpillOverflowPolicy::default(),
         }
     }
+
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    pub fn overflow(mut self, overflow: SpillOverflowPolicy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+/// Behavior of [`SpillConfig`] buffer when it is full
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub enum SpillOverflowPolicy {
+    /// Oldest spilled batches are dropped to make room for new ones
+    #[default]
+    DropOldest,
+    /// New batches are rejected and their records fail
+    Reject,
 }
 
 /// Defines guarantees that Producer must follow delivering records to SPU.
@@ -27,4 +27,8 @@ pub enum ProducerError {
     ProduceRequestRetryTimeout(#[from] TimeoutError),
     #[error("the batch enqueue timeout limit reached")]
     BatchQueueWaitTimeout,
+    #[error("spill buffer is full")]
+    SpillBufferFull,
+    #[error("spill buffer error: {0}")]
+    Spill(String),
 }
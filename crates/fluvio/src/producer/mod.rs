@@ -20,7 +20,7 @@ use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::record::Record;
-use fluvio_compression::Compression;
+use fluvio_compression::{Compression, CompressionOptions, Dictionary};
 #[cfg(feature = "compress")]
 use fluvio_sc_schema::topic::CompressionAlgorithm;
 use fluvio_sc_schema::topic::TopicSpec;
@@ -29,6 +29,7 @@ use fluvio_types::PartitionId;
 use fluvio_types::event::StickyEvent;
 
 mod accumulator;
+mod chunk;
 mod config;
 mod error;
 mod output;
@@ -36,6 +37,7 @@ mod record;
 mod partitioning;
 mod partition_producer;
 mod memory_batch;
+mod spill;
 
 pub mod event;
 
@@ -49,6 +51,7 @@ use crate::metrics::ClientMetrics;
 use crate::producer::accumulator::{RecordAccumulator, PushRecord};
 
 pub use crate::producer::partitioning::{Partitioner, PartitionerConfig};
+pub use fluvio_types::partitioner::PartitionerKind;
 
 use self::accumulator::BatchEvents;
 use self::accumulator::BatchHandler;
@@ -58,7 +61,7 @@ pub use self::accumulator::ProducerCallback;
 pub use self::accumulator::ProduceCompletionBatchEvent;
 pub use self::config::{
     TopicProducerConfigBuilder, TopicProducerConfig, TopicProducerConfigBuilderError,
-    DeliverySemantic, RetryPolicy, RetryStrategy,
+    DeliverySemantic, RetryPolicy, RetryStrategy, SpillConfig, SpillOverflowPolicy,
 };
 pub use self::error::ProducerError;
 use self::event::EventHandler;
@@ -87,6 +90,7 @@ where
     batch_events: Arc<BatchEvents>,
     client_metric: Arc<ClientMetrics>,
     callback: Option<SharedProducerCallback>,
+    compression_options: Arc<CompressionOptions>,
 }
 
 impl ProducerPool {
@@ -97,6 +101,7 @@ impl ProducerPool {
         batches: Arc<HashMap<PartitionId, BatchHandler>>,
         client_metric: Arc<ClientMetrics>,
         callback: Option<SharedProducerCallback>,
+        compression_options: Arc<CompressionOptions>,
     ) -> Self
     where
         S: SpuPool + Send + Sync + 'static,
@@ -117,6 +122,7 @@ impl ProducerPool {
                 batch_events: batch_events.clone(),
                 client_metric: client_metric.clone(),
                 callback: callback.clone(),
+                compression_options: compression_options.clone(),
             };
 
             PartitionProducer::start(
@@ -245,6 +251,7 @@ where
     partition_tracker: Arc<PartitionAvailabilityTracker>,
     producer_pool: Arc<RwLock<ProducerPool>>,
     metrics: Arc<ClientMetrics>,
+    compression_options: Arc<CompressionOptions>,
 }
 
 impl<S> InnerTopicProducer<S>
@@ -258,24 +265,49 @@ where
     }
 
     async fn push_record(self: Arc<Self>, record: Record) -> Result<PushRecord> {
+        let partition = self.select_partition(&record).await?;
+        self.push_record_to_partition(record, partition).await
+    }
+
+    /// Split an oversize record into chunks and send all of them to the partition of the record
+    async fn push_chunked_record(self: Arc<Self>, record: Record) -> Result<Vec<PushRecord>> {
+        let partition = self.select_partition(&record).await?;
+        let chunks = chunk::split_record(record, self.config.max_request_size)?;
+        let mut push_records = Vec::with_capacity(chunks.len());
+        for chunk in chunks {
+            push_records.push(
+                self.clone()
+                    .push_record_to_partition(chunk, partition)
+                    .await?,
+            );
+        }
+        Ok(push_records)
+    }
+
+    async fn select_partition(&self, record: &Record) -> Result<PartitionId> {
         let partition_count = self.partition_tracker.partition_count();
         let available_partitions = self.partition_tracker.available_partitions();
         let available_partitions_lock = available_partitions.read().await;
 
-        let partition_config = PartitionerConfig {
-            partition_count,
-            available_partitions: available_partitions_lock.clone(),
-        };
+        let partition_config =
+            PartitionerConfig::new(partition_count, available_partitions_lock.clone())?
+                .with_batching(self.config.batch_size, self.config.linger);
 
         drop(available_partitions_lock);
 
         let key = record.key.as_ref().map(|k| k.as_ref());
         let value = record.value.as_ref();
-        let partition = self
+        Ok(self
             .config
             .partitioner
-            .partition(&partition_config, key, value);
+            .partition(&partition_config, key, value))
+    }
 
+    async fn push_record_to_partition(
+        self: Arc<Self>,
+        record: Record,
+        partition: PartitionId,
+    ) -> Result<PushRecord> {
         let mut producer_pool = self.producer_pool.write().await;
 
         if let Some(error) = producer_pool.last_error(partition).await {
@@ -289,6 +321,7 @@ where
             batch_events: BatchEvents::shared(),
             client_metric: self.metrics.clone(),
             callback: self.config.callback.clone(),
+            compression_options: self.compression_options.clone(),
         };
 
         let _ = producer_pool
@@ -518,7 +551,15 @@ where
         spu_pool: Arc<S>,
         config: Arc<TopicProducerConfig>,
         metrics: Arc<ClientMetrics>,
+        dictionary: Option<Dictionary>,
     ) -> Result<Self> {
+        if config.chunking && !config.smartmodules.is_empty() {
+            return Err(FluvioError::Producer(ProducerError::InvalidConfiguration(
+                "chunking can't be combined with SmartModules, the SPU would apply them to every chunk".to_string(),
+            ))
+            .into());
+        }
+
         let topic_store = spu_pool.topics();
         let topic_spec = topic_store
             .lookup_by_key(&topic)
@@ -530,11 +571,20 @@ where
 
         cfg_if::cfg_if! {
             if #[cfg(feature = "compress")] {
-                let compression = determine_producer_compression_algo(config.clone(), topic_spec)?;
+                let compression = determine_producer_compression_algo(config.clone(), topic_spec.clone())?;
+                let compression_options = determine_producer_compression_options(
+                    &config,
+                    &topic_spec,
+                    compression,
+                    dictionary,
+                )?;
             } else {
                 let compression = Compression::None;
+                let compression_options = CompressionOptions::default();
+                let _ = dictionary;
             }
         }
+        let compression_options = Arc::new(compression_options);
 
         let record_accumulator = RecordAccumulator::new(
             config.batch_size,
@@ -553,6 +603,7 @@ where
             Arc::new(record_accumulator.batches().await),
             metrics.clone(),
             config.callback.clone(),
+            compression_options.clone(),
         );
 
         let partition_tracker = PartitionAvailabilityTracker::start(
@@ -571,6 +622,7 @@ where
                 record_accumulator: Arc::new(record_accumulator),
                 partition_tracker,
                 metrics: metrics.clone(),
+                compression_options,
             }),
             #[cfg(feature = "smartengine")]
             sm_chain: Default::default(),
@@ -620,19 +672,31 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    #[instrument(
-        skip(self, key, value),
-        fields(topic = %self.inner.topic),
-    )]
     pub async fn send(
         &self,
         key: impl Into<RecordKey>,
         value: impl Into<RecordData>,
     ) -> Result<ProduceOutput> {
-        let record_key = key.into();
-        let record_value = value.into();
-        let record = Record::from((record_key, record_value));
+        self.send_record(traced_record(key, value)).await
+    }
 
+    pub async fn send_all(
+        &self,
+        records: impl IntoIterator<Item = (impl Into<RecordKey>, impl Into<RecordData>)>,
+    ) -> Result<Vec<ProduceOutput>> {
+        let records = records
+            .into_iter()
+            .map(|(key, value)| traced_record(key, value))
+            .collect();
+        self.send_records(records).await
+    }
+
+    #[instrument(
+        name = "send",
+        skip(self, record),
+        fields(topic = %self.inner.topic),
+    )]
+    async fn send_record(&self, record: Record) -> Result<ProduceOutput> {
         cfg_if::cfg_if! {
             if #[cfg(feature = "smartengine")] {
                 let mut entries = vec![record];
@@ -665,23 +729,28 @@ where
 
         let mut results = ProduceOutput::default();
         for record in entries {
-            let push_record = self.inner.clone().push_record(record).await?;
-            results.add(push_record.future);
+            if self.inner.config.chunking
+                && chunk::is_oversized(&record, self.inner.config.max_request_size)
+            {
+                let push_records = self.inner.clone().push_chunked_record(record).await?;
+                results.add_chunks(push_records.into_iter().map(|push| push.future).collect());
+            } else {
+                let push_record = self.inner.clone().push_record(record).await?;
+                results.add(push_record.future);
+            }
         }
         Ok(results)
     }
 
     #[instrument(
+        name = "send_all",
         skip(self, records),
         fields(topic = %self.inner.topic),
     )]
-    pub async fn send_all(
-        &self,
-        records: impl IntoIterator<Item = (impl Into<RecordKey>, impl Into<RecordData>)>,
-    ) -> Result<Vec<ProduceOutput>> {
+    async fn send_records(&self, records: Vec<Record>) -> Result<Vec<ProduceOutput>> {
         let mut results = vec![];
-        for (key, value) in records {
-            let produce_output = self.send(key, value).await?;
+        for record in records {
+            let produce_output = self.send_record(record).await?;
             results.push(produce_output);
         }
 
@@ -712,6 +781,19 @@ where
     }
 }
 
+/// Record carrying the trace context of the caller, if it is in a trace.
+/// The context is read before entering the producer spans, otherwise
+/// every record would carry a trace started by the producer itself
+fn traced_record(key: impl Into<RecordKey>, value: impl Into<RecordData>) -> Record {
+    #[allow(unused_mut)]
+    let mut record = Record::from((key.into(), value.into()));
+    #[cfg(feature = "otel")]
+    if let Some(trace_context) = crate::otel::current_trace_context() {
+        trace_context.inject(record.headers_mut());
+    }
+    record
+}
+
 #[cfg(feature = "compress")]
 fn determine_producer_compression_algo(
     config: Arc<TopicProducerConfig>,
@@ -755,6 +837,30 @@ fn determine_producer_compression_algo(
     Ok(result)
 }
 
+/// Level and dictionary applied to the batches, producer level takes precedence over topic level.
+/// Dictionary is only used when records are compressed with zstd.
+#[cfg(feature = "compress")]
+fn determine_producer_compression_options(
+    config: &TopicProducerConfig,
+    topic_spec: &TopicSpec,
+    compression: Compression,
+    dictionary: Option<Dictionary>,
+) -> Result<CompressionOptions> {
+    let level = config
+        .compression_level
+        .or_else(|| topic_spec.get_compression_level());
+    if let Some(level) = level {
+        compression.validate_level(level).map_err(|err| {
+            FluvioError::Producer(ProducerError::InvalidConfiguration(err.to_string()))
+        })?;
+    }
+
+    Ok(CompressionOptions {
+        level,
+        dictionary: dictionary.filter(|_| compression.supports_dictionary()),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -768,6 +874,7 @@ mod tests {
         topic::TopicSpec,
     };
     use fluvio_socket::{ClientConfig, SocketError, StreamSocket, VersionedSerialSocket};
+    use fluvio_spu_schema::server::smartmodule::SmartModuleInvocation;
     use fluvio_stream_dispatcher::metadata::local::LocalMetadataItem;
     use fluvio_types::{PartitionId, SpuId};
 
@@ -775,9 +882,11 @@ mod tests {
         metrics::ClientMetrics,
         spu::SpuPool,
         sync::{MetadataStores, StoreContext},
-        FluvioError, TopicProducer, TopicProducerConfig,
+        FluvioError, TopicProducer, TopicProducerConfig, TopicProducerConfigBuilder,
     };
 
+    use super::ProducerError;
+
     struct SpuPoolMock {
         topics: StoreContext<TopicSpec>,
         partitions: StoreContext<PartitionSpec>,
@@ -848,7 +957,7 @@ mod tests {
         let spu_pool = Arc::new(SpuPoolMock { topics, partitions });
         spu_pool.topics().store().sync_all(topic_2_partitions).await;
         spu_pool.partitions().store().sync_all(partition_2).await;
-        let producer = TopicProducer::new(topic.clone(), spu_pool.clone(), config, metrics)
+        let producer = TopicProducer::new(topic.clone(), spu_pool.clone(), config, metrics, None)
             .await
             .expect("producer");
 
@@ -907,4 +1016,34 @@ mod tests {
         );
         drop(producer_pool);
     }
+
+    #[fluvio_future::test]
+    async fn test_chunking_with_smartmodules_rejected() {
+        let config = TopicProducerConfigBuilder::default()
+            .chunking(true)
+            .smartmodules(vec![SmartModuleInvocation::default()])
+            .build()
+            .expect("config");
+        let spu_pool = Arc::new(SpuPoolMock {
+            topics: StoreContext::<TopicSpec>::new(),
+            partitions: StoreContext::<PartitionSpec>::new(),
+        });
+
+        let result = TopicProducer::new(
+            "test".to_string(),
+            spu_pool,
+            Arc::new(config),
+            Arc::new(ClientMetrics::default()),
+            None,
+        )
+        .await;
+
+        let err = result.err().expect("rejected");
+        assert!(matches!(
+            err.downcast_ref::<FluvioError>(),
+            Some(FluvioError::Producer(ProducerError::InvalidConfiguration(
+                _
+            )))
+        ));
+    }
 }
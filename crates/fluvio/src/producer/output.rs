@@ -7,6 +7,8 @@ use crate::error::Result;
 #[derive(Default)]
 pub struct ProduceOutput {
     record_metadata: Vec<FutureRecordMetadata>,
+    /// all but last chunk of records split into chunks, the last one is in `record_metadata`
+    chunks: Vec<FutureRecordMetadata>,
 }
 
 impl ProduceOutput {
@@ -15,9 +17,26 @@ impl ProduceOutput {
         self.record_metadata.push(record_metadata);
     }
 
+    /// Add future record metadata of the chunks of a record.
+    /// The record is delivered once all chunks are, its metadata is that of the last chunk.
+    pub(crate) fn add_chunks(&mut self, mut chunks: Vec<FutureRecordMetadata>) {
+        if let Some(last) = chunks.pop() {
+            self.chunks.append(&mut chunks);
+            self.record_metadata.push(last);
+        }
+    }
+
+    async fn wait_chunks(&mut self) -> Result<()> {
+        for future in std::mem::take(&mut self.chunks) {
+            future.wait().await?;
+        }
+        Ok(())
+    }
+
     /// Wait for all record metadata of all records sent using smartmodule
     #[cfg(feature = "smartengine")]
-    pub async fn wait_all(self) -> Result<Vec<RecordMetadata>> {
+    pub async fn wait_all(mut self) -> Result<Vec<RecordMetadata>> {
+        self.wait_chunks().await?;
         let mut records_metadata = Vec::with_capacity(self.record_metadata.len());
 
         for future in self.record_metadata.into_iter() {
@@ -28,7 +47,8 @@ impl ProduceOutput {
     }
 
     /// Wait for the record metadata
-    pub async fn wait(self) -> Result<RecordMetadata> {
+    pub async fn wait(mut self) -> Result<RecordMetadata> {
+        self.wait_chunks().await?;
         let future_record_metadata = self
             .record_metadata
             .into_iter()
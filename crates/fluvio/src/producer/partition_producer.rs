@@ -1,16 +1,24 @@
 use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
 
 use adaptive_backoff::prelude::{
     Backoff, BackoffBuilder, ExponentialBackoff, ExponentialBackoffBuilder,
 };
-use async_lock::RwLock;
+use async_channel::Sender;
+use async_lock::{Mutex, RwLock};
+use futures_util::future::join_all;
 use fluvio_types::defaults::{
     RECONNECT_BACKOFF_FACTOR, RECONNECT_BACKOFF_MAX_DURATION, RECONNECT_BACKOFF_MIN_DURATION,
 };
-use tracing::{debug, info, instrument, error, trace};
+use tracing::{debug, info, instrument, error, trace, warn};
 
-use fluvio_protocol::record::ReplicaKey;
-use fluvio_protocol::record::{RawRecords, Batch};
+use fluvio_compression::CompressionOptions;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::record::{RawRecords, Batch, TraceContext};
 use fluvio_spu_schema::produce::{DefaultPartitionRequest, DefaultTopicRequest, DefaultProduceRequest};
 use fluvio_future::timer::sleep;
 use fluvio_types::SpuId;
@@ -27,8 +35,9 @@ use crate::TopicProducerConfig;
 use super::{
     PartitionProducerParams, ProduceCompletionBatchEvent, SharedProducerCallback, ProducerError,
 };
-use super::accumulator::{BatchEvents, BatchesDeque};
+use super::accumulator::{BatchEvents, BatchesDeque, ProducerBatch};
 use super::event::EventHandler;
+use super::spill::PartitionSpill;
 
 /// Struct that is responsible for sending produce requests to the SPU in a given partition.
 pub(crate) struct PartitionProducer<S>
@@ -43,6 +52,8 @@ where
     last_error: Arc<RwLock<Option<ProducerError>>>,
     metrics: Arc<ClientMetrics>,
     callback: Option<SharedProducerCallback>,
+    compression_options: Arc<CompressionOptions>,
+    spill: Option<Mutex<PartitionSpill>>,
 }
 
 impl<S> PartitionProducer<S>
@@ -53,6 +64,7 @@ where
         params: PartitionProducerParams<S>,
         replica: ReplicaKey,
         last_error: Arc<RwLock<Option<ProducerError>>>,
+        spill: Option<Mutex<PartitionSpill>>,
     ) -> Self {
         Self {
             config: params.config,
@@ -63,6 +75,8 @@ where
             last_error,
             metrics: params.client_metric,
             callback: params.callback,
+            compression_options: params.compression_options,
+            spill,
         }
     }
 
@@ -70,8 +84,9 @@ where
         params: PartitionProducerParams<S>,
         replica: ReplicaKey,
         error: Arc<RwLock<Option<ProducerError>>>,
+        spill: Option<Mutex<PartitionSpill>>,
     ) -> Arc<Self> {
-        Arc::new(PartitionProducer::new(params, replica, error))
+        Arc::new(PartitionProducer::new(params, replica, error, spill))
     }
 
     pub(crate) fn start(
@@ -81,12 +96,31 @@ where
         flush_event: (Arc<EventHandler>, Arc<EventHandler>),
         replica: ReplicaKey,
     ) {
-        let producer = PartitionProducer::shared(params, replica, error);
         fluvio_future::task::spawn(async move {
+            let spill = Self::open_spill(&params.config, &replica, &error).await;
+            let producer = PartitionProducer::shared(params, replica, error, spill);
             producer.run(end_event, flush_event).await;
         });
     }
 
+    /// Open spill buffer of the partition if configured.
+    /// Producer keeps working without it if it can't be opened
+    async fn open_spill(
+        config: &TopicProducerConfig,
+        replica: &ReplicaKey,
+        last_error: &RwLock<Option<ProducerError>>,
+    ) -> Option<Mutex<PartitionSpill>> {
+        let spill_config = config.spill.as_ref()?;
+        match PartitionSpill::open(spill_config, &replica.topic, replica.partition).await {
+            Ok(spill) => Some(Mutex::new(spill)),
+            Err(err) => {
+                error!(%replica, %err, "failed to open spill buffer");
+                *last_error.write().await = Some(err);
+                None
+            }
+        }
+    }
+
     #[instrument(skip(self, end_event, flush_event))]
     async fn run(
         &self,
@@ -96,8 +130,18 @@ where
         use tokio::select;
 
         let mut linger_sleep = None;
+        let mut drain_sleep = None;
+        let mut drain_backoff = create_backoff().ok();
 
         loop {
+            if drain_sleep.is_none() && self.has_spilled().await {
+                let wait = drain_backoff
+                    .as_mut()
+                    .map(|backoff| backoff.wait())
+                    .unwrap_or(RECONNECT_BACKOFF_MIN_DURATION);
+                drain_sleep = Some(sleep(wait));
+            }
+
             select! {
                 _ = end_event.listen() => {
                     info!("partition producer end event received");
@@ -136,6 +180,20 @@ where
                     }
                     linger_sleep = None;
                 }
+
+                _ = async { drain_sleep.as_mut().expect("unexpected failure").await }, if drain_sleep.is_some() => {
+                    debug!("retrying to send spilled batches");
+                    if let Err(e) = self.drain_spill().await {
+                        error!("Failed to drain spill buffer: {:?}", e);
+                        self.set_error(e).await;
+                    }
+                    if !self.has_spilled().await {
+                        if let Some(backoff) = drain_backoff.as_mut() {
+                            backoff.reset();
+                        }
+                    }
+                    drain_sleep = None;
+                }
             }
         }
         info!("partition producer end");
@@ -165,8 +223,200 @@ where
     /// Flush all the batches that are full or have reached the linger time.
     /// If force is set to true, flush all batches regardless of linger time.
     pub(crate) async fn flush(&self, force: bool) -> Result<()> {
+        if let Some(spill) = &self.spill {
+            return self.flush_or_spill(spill, force).await;
+        }
+
         let spu_socket = self.connect_spu_with_reconnect().await?;
+        let batches_ready = self.ready_batches(force).await;
+        self.send_batches(&spu_socket, batches_ready).await
+    }
+
+    /// Send ready batches if leader is reachable and nothing is spilled.
+    /// Batches that can't be sent or are rejected by the leader are appended to the spill
+    /// buffer behind already spilled ones, together with the batches after them to keep order
+    async fn flush_or_spill(&self, spill: &Mutex<PartitionSpill>, force: bool) -> Result<()> {
+        let mut batches = vec![];
+        for p_batch in self.ready_batches(force).await {
+            let notify = p_batch.notify.clone();
+            let created_at = p_batch.metadata().created_at;
+            let batch = p_batch.batch();
+            let trace_context = batch
+                .records()
+                .iter()
+                .find_map(|record| TraceContext::extract(record.headers()))
+                .unwrap_or_default();
+            let raw_batch: Batch<RawRecords> = batch.compress_with(&self.compression_options)?;
+            batches.push(SpillableBatch {
+                raw_batch,
+                trace_context,
+                notify,
+                created_at,
+            });
+        }
+        if batches.is_empty() {
+            return self.drain_spill().await;
+        }
+
+        {
+            let mut spill = spill.lock().await;
+            if spill.is_empty() {
+                let accepted = self.send_spillable(&batches).await;
+                for (batch, (offset, error_code)) in batches.drain(..accepted.len()).zip(accepted) {
+                    self.batch_sent(&batch.raw_batch, batch.created_at).await;
+                    let response = ProducePartitionResponseFuture::ready(offset, error_code);
+                    if batch.notify.send(response).await.is_err() {
+                        trace!("Failed to notify produce result because receiver was dropped");
+                    }
+                }
+            }
+
+            let spill_metrics = self.metrics.producer_spill();
+            for batch in batches {
+                let records_len = batch.raw_batch.records_len() as u64;
+                let bytes_size = batch.raw_batch.batch_len() as u64;
+                match spill.push(batch.raw_batch, batch.notify).await {
+                    Ok(dropped) => {
+                        spill_metrics.spilled().add_records(records_len);
+                        spill_metrics.spilled().add_bytes(bytes_size);
+                        spill_metrics.dropped().add_records(dropped);
+                    }
+                    Err(ProducerError::SpillBufferFull) => {
+                        warn!(replica = %self.replica, records_len, "spill buffer is full, batch rejected");
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        }
+        self.drain_spill().await
+    }
+
+    /// Send batches to the leader and wait for their responses.
+    /// Returns results of the leading batches accepted by the leader
+    async fn send_spillable(&self, batches: &[SpillableBatch]) -> Vec<(Offset, ErrorCode)> {
+        let spu_socket = match self.connect_spu().await {
+            Ok(spu_socket) => spu_socket,
+            Err(err) => {
+                warn!(replica = %self.replica, %err, "leader unreachable, spilling batches to disk");
+                return vec![];
+            }
+        };
+        let partitions = batches
+            .iter()
+            .map(|batch| {
+                self.partition_request(batch.raw_batch.clone(), batch.trace_context.clone())
+            })
+            .collect();
+        let results = match self.send_and_wait(&spu_socket, partitions).await {
+            Ok(results) => results,
+            Err(err) => {
+                warn!(replica = %self.replica, %err, "failed to send batches, spilling them to disk");
+                return vec![];
+            }
+        };
+        if let Some((_, error_code)) = results.iter().find(|(_, error_code)| error_code.is_error())
+        {
+            warn!(replica = %self.replica, ?error_code, "leader rejected batch, spilling it with the following ones");
+        }
+        results
+            .into_iter()
+            .take_while(|(_, error_code)| error_code.is_ok())
+            .collect()
+    }
+
+    /// Record metrics and callback event of a batch accepted by the leader
+    async fn batch_sent(&self, raw_batch: &Batch<RawRecords>, created_at: Instant) {
+        let records_len = raw_batch.records_len() as u64;
+        let bytes_size = raw_batch.batch_len() as u64;
+        let producer_metrics = self.metrics.producer_client();
+        producer_metrics.add_records(records_len);
+        producer_metrics.add_bytes(bytes_size);
+
+        if let Some(callback) = self.callback.as_ref() {
+            let event = ProduceCompletionBatchEvent {
+                created_at,
+                partition: self.replica.partition,
+                bytes_size,
+                records_len,
+                elapsed: created_at.elapsed(),
+            };
+            if let Err(e) = callback.finished(event).await {
+                error!("Failed to send event to callback: {}", e);
+            }
+        }
+    }
+
+    async fn has_spilled(&self) -> bool {
+        match &self.spill {
+            Some(spill) => !spill.lock().await.is_empty(),
+            None => false,
+        }
+    }
+
+    /// Send spilled batches in order if leader is reachable.
+    /// Batches stay spilled if leader can't be reached, sending fails or leader rejects them
+    async fn drain_spill(&self) -> Result<()> {
+        let Some(spill) = &self.spill else {
+            return Ok(());
+        };
+        let mut spill = spill.lock().await;
+        if spill.is_empty() {
+            return Ok(());
+        }
+        let spu_socket = match self.connect_spu().await {
+            Ok(spu_socket) => spu_socket,
+            Err(err) => {
+                debug!(replica = %self.replica, %err, "leader still unreachable");
+                return Ok(());
+            }
+        };
+
+        let spill_metrics = self.metrics.producer_spill();
+        while !spill.is_empty() {
+            let batches = spill.read(self.config.max_request_size).await?;
+            let partitions = batches
+                .iter()
+                .map(|batch| self.partition_request(batch.clone(), TraceContext::default()))
+                .collect();
 
+            let results = match self.send_and_wait(&spu_socket, partitions).await {
+                Ok(results) => results,
+                Err(err) => {
+                    warn!(replica = %self.replica, %err, "failed to send spilled batches, will retry");
+                    return Ok(());
+                }
+            };
+            let rejected = results
+                .iter()
+                .find(|(_, error_code)| error_code.is_error())
+                .map(|(_, error_code)| error_code.clone());
+
+            let delivered = spill.delivered(&batches, results).await?;
+            let records_len: u64 = batches[..delivered]
+                .iter()
+                .map(|b| b.records_len() as u64)
+                .sum();
+            let bytes_size: u64 = batches[..delivered]
+                .iter()
+                .map(|b| b.batch_len() as u64)
+                .sum();
+            spill_metrics.drained().add_records(records_len);
+            spill_metrics.drained().add_bytes(bytes_size);
+            let producer_metrics = self.metrics.producer_client();
+            producer_metrics.add_records(records_len);
+            producer_metrics.add_bytes(bytes_size);
+            debug!(replica = %self.replica, records_len, "spilled batches delivered");
+
+            if let Some(error_code) = rejected {
+                warn!(replica = %self.replica, ?error_code, "leader rejected spilled batch, will retry");
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    /// Take batches that are full or have reached the linger time out of the queue
+    async fn ready_batches(&self, force: bool) -> Vec<ProducerBatch> {
         let mut batches_ready = vec![];
         {
             let mut batches = self.batches_lock.batches.write().await;
@@ -185,10 +435,15 @@ where
                 }
             }
         }
+        batches_ready
+    }
 
+    async fn send_batches(
+        &self,
+        spu_socket: &VersionedSerialSocket,
+        batches_ready: Vec<ProducerBatch>,
+    ) -> Result<()> {
         // Send each batch and notify base offset
-        let mut request = DefaultProduceRequest::default();
-
         let mut topic_request = DefaultTopicRequest {
             name: self.replica.topic.to_string(),
             ..Default::default()
@@ -206,8 +461,14 @@ where
             let notify = p_batch.notify.clone();
             let metadata = p_batch.metadata().clone();
             let batch = p_batch.batch();
+            // records carry their own context, the request one lets the SPU join the trace
+            partition_request.trace_context = batch
+                .records()
+                .iter()
+                .find_map(|record| TraceContext::extract(record.headers()))
+                .unwrap_or_default();
 
-            let raw_batch: Batch<RawRecords> = batch.try_into()?;
+            let raw_batch: Batch<RawRecords> = batch.compress_with(&self.compression_options)?;
 
             let producer_metrics = self.metrics.producer_client();
             let records_len = raw_batch.records_len() as u64;
@@ -234,11 +495,7 @@ where
             }
         }
 
-        request.isolation = self.config.isolation;
-        request.timeout = self.config.timeout;
-        request.smartmodules.clone_from(&self.config.smartmodules);
-        request.topics.push(topic_request);
-
+        let request = self.produce_request(topic_request);
         let (response, _) = self.send_to_socket(spu_socket, request).await?;
 
         for (batch_notifier, partition_response_fut) in
@@ -260,9 +517,52 @@ where
         Ok(())
     }
 
+    fn produce_request(&self, topic_request: DefaultTopicRequest) -> DefaultProduceRequest {
+        let mut request = DefaultProduceRequest {
+            isolation: self.config.isolation,
+            timeout: self.config.timeout,
+            ..Default::default()
+        };
+        request.smartmodules.clone_from(&self.config.smartmodules);
+        request.topics.push(topic_request);
+        request
+    }
+
+    fn partition_request(
+        &self,
+        batch: Batch<RawRecords>,
+        trace_context: TraceContext,
+    ) -> DefaultPartitionRequest {
+        let mut partition_request = DefaultPartitionRequest {
+            partition_index: self.replica.partition,
+            trace_context,
+            ..Default::default()
+        };
+        partition_request.records.batches.push(batch);
+        partition_request
+    }
+
+    /// Send partition requests in one produce request and wait for all partition responses
+    async fn send_and_wait(
+        &self,
+        socket: &VersionedSerialSocket,
+        partitions: Vec<DefaultPartitionRequest>,
+    ) -> Result<Vec<(Offset, ErrorCode)>> {
+        let topic_request = DefaultTopicRequest {
+            name: self.replica.topic.to_string(),
+            partitions,
+            ..Default::default()
+        };
+        let request = self.produce_request(topic_request);
+        let (responses, _) = self.send_to_socket(socket, request).await?;
+        Ok(join_all(responses).await)
+    }
+
     async fn connect_spu(&self) -> Result<VersionedSerialSocket> {
         let leader = self.current_leader().await?;
-        self.spu_pool.create_serial_socket_from_leader(leader).await
+        self.spu_pool
+            .create_producer_socket(leader, &self.replica)
+            .await
     }
 
     async fn connect_spu_with_reconnect(&self) -> Result<VersionedSerialSocket> {
@@ -283,7 +583,7 @@ where
 
     async fn send_to_socket(
         &self,
-        socket: VersionedSerialSocket,
+        socket: &VersionedSerialSocket,
         request: DefaultProduceRequest,
     ) -> Result<(Vec<ProducePartitionResponseFuture>, Option<i64>)> {
         let partition_count: usize = request.topics.iter().map(|t| t.partitions.len()).sum();
@@ -323,6 +623,14 @@ where
     }
 }
 
+/// Ready batch that is spilled to disk if the leader doesn't accept it
+struct SpillableBatch {
+    raw_batch: Batch<RawRecords>,
+    trace_context: TraceContext,
+    notify: Sender<ProducePartitionResponseFuture>,
+    created_at: Instant,
+}
+
 /// Creates an exponential backoff configuration.
 fn create_backoff() -> anyhow::Result<ExponentialBackoff> {
     ExponentialBackoffBuilder::default()
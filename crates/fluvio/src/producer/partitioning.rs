@@ -1,6 +1,13 @@
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
 use siphasher::sip::SipHasher;
 use fluvio_types::{PartitionId, PartitionCount};
+use fluvio_types::partitioner::PartitionerKind;
+
+use super::ProducerError;
 
 /// A trait for defining a partitioning strategy for key/value records.
 ///
@@ -12,6 +19,8 @@ use fluvio_types::{PartitionId, PartitionCount};
 /// partitions. This includes deciding what partition to assign to records
 /// with no keys (represented by `None` values in the keys slice).
 ///
+/// Partitioner is only called for topics with at least one partition.
+///
 /// See [`SiphashRoundRobinPartitioner`] for a reference implementation.
 pub trait Partitioner {
     fn partition(
@@ -22,16 +31,56 @@ pub trait Partitioner {
     ) -> PartitionId;
 }
 
+#[derive(Debug, Clone, Default)]
 pub struct PartitionerConfig {
     pub partition_count: PartitionCount,
     pub available_partitions: Vec<PartitionCount>,
+    /// Batch size of the producer, in bytes
+    pub batch_size: usize,
+    /// Linger of the producer
+    pub linger: Duration,
 }
 
 impl PartitionerConfig {
     /// Creates a new `PartitionerConfig` with the given partition count and available partitions.
+    /// Fails if there are no partitions to assign records to.
+    pub fn new(
+        partition_count: PartitionCount,
+        available_partitions: Vec<PartitionCount>,
+    ) -> Result<Self, ProducerError> {
+        if partition_count == 0 {
+            return Err(ProducerError::Internal(
+                "topic has no partitions".to_owned(),
+            ));
+        }
+        Ok(Self {
+            partition_count,
+            available_partitions,
+            batch_size: 0,
+            linger: Duration::ZERO,
+        })
+    }
+
+    /// Sets batching of the producer, sticky partitioners move on when batch is full or linger expires
+    pub fn with_batching(mut self, batch_size: usize, linger: Duration) -> Self {
+        self.batch_size = batch_size;
+        self.linger = linger;
+        self
+    }
+
     pub fn partition_count(&self) -> PartitionCount {
         self.partition_count
     }
+
+    /// Batch size of the producer, in bytes
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Linger of the producer
+    pub fn linger(&self) -> Duration {
+        self.linger
+    }
 }
 
 /// A [`Partitioner`] which combines hashing and round-robin partition assignment
@@ -73,6 +122,132 @@ impl Partitioner for SiphashRoundRobinPartitioner {
     }
 }
 
+/// A [`Partitioner`] which keeps key-less records on one partition
+///
+/// - Records with keys get their keys hashed with siphash
+/// - Records without keys stick to one partition until a batch worth of bytes
+///   has been assigned to it or linger has expired, then move to the next available partition
+pub(crate) struct StickyPartitioner {
+    sticky: StickyPartition,
+}
+
+impl StickyPartitioner {
+    pub fn new() -> Self {
+        Self {
+            sticky: StickyPartition::default(),
+        }
+    }
+}
+
+impl Partitioner for StickyPartitioner {
+    fn partition(
+        &self,
+        config: &PartitionerConfig,
+        maybe_key: Option<&[u8]>,
+        value: &[u8],
+    ) -> PartitionId {
+        match maybe_key {
+            Some(key) => partition_siphash(key, config.partition_count()),
+            None => self.sticky.partition(config, value.len(), Instant::now()),
+        }
+    }
+}
+
+/// A [`Partitioner`] compatible with Kafka default partitioner
+///
+/// - Records with keys are placed by murmur2 hash of the key, same as Kafka clients do,
+///   so keyed data lands on the same partition number in both systems
+/// - Records without keys behave as in [`StickyPartitioner`]
+pub(crate) struct Murmur2Partitioner {
+    sticky: StickyPartition,
+}
+
+impl Murmur2Partitioner {
+    pub fn new() -> Self {
+        Self {
+            sticky: StickyPartition::default(),
+        }
+    }
+}
+
+impl Partitioner for Murmur2Partitioner {
+    fn partition(
+        &self,
+        config: &PartitionerConfig,
+        maybe_key: Option<&[u8]>,
+        value: &[u8],
+    ) -> PartitionId {
+        match maybe_key {
+            Some(key) => partition_murmur2(key, config.partition_count()),
+            None => self.sticky.partition(config, value.len(), Instant::now()),
+        }
+    }
+}
+
+/// Creates built-in partitioner by its name
+pub(crate) fn partitioner_by_kind(kind: PartitionerKind) -> Arc<dyn Partitioner + Send + Sync> {
+    match kind {
+        PartitionerKind::RoundRobin => Arc::new(SiphashRoundRobinPartitioner::new()),
+        PartitionerKind::Sticky => Arc::new(StickyPartitioner::new()),
+        PartitionerKind::Murmur2 => Arc::new(Murmur2Partitioner::new()),
+    }
+}
+
+/// Partition that key-less records stick to
+#[derive(Default)]
+struct StickyPartition {
+    index: AtomicU32,
+    current: Mutex<Option<StickyState>>,
+}
+
+struct StickyState {
+    partition: PartitionId,
+    bytes: usize,
+    since: Instant,
+}
+
+impl StickyState {
+    /// batch is not filled yet, linger has not expired and partition is still available
+    fn is_open(&self, config: &PartitionerConfig, now: Instant) -> bool {
+        self.bytes < config.batch_size.max(1)
+            && (config.linger.is_zero() || now.duration_since(self.since) < config.linger)
+            && is_available(config, self.partition)
+    }
+}
+
+impl StickyPartition {
+    fn partition(&self, config: &PartitionerConfig, bytes: usize, now: Instant) -> PartitionId {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        let state = match current.as_mut() {
+            Some(state) if state.is_open(config, now) => state,
+            _ => current.insert(StickyState {
+                partition: self.next_partition(config),
+                bytes: 0,
+                since: now,
+            }),
+        };
+        state.bytes += bytes;
+        state.partition
+    }
+
+    fn next_partition(&self, config: &PartitionerConfig) -> PartitionId {
+        let index = self.index.fetch_add(1, Ordering::Relaxed);
+        if config.available_partitions.is_empty() {
+            return index % config.partition_count;
+        }
+        let partition = index as usize % config.available_partitions.len();
+        config.available_partitions[partition]
+    }
+}
+
+fn is_available(config: &PartitionerConfig, partition: PartitionId) -> bool {
+    if config.available_partitions.is_empty() {
+        partition < config.partition_count
+    } else {
+        config.available_partitions.contains(&partition)
+    }
+}
+
 fn partition_siphash(key: &[u8], partition_count: PartitionCount) -> PartitionId {
     use std::hash::{Hash, Hasher};
 
@@ -87,6 +262,44 @@ fn partition_siphash(key: &[u8], partition_count: PartitionCount) -> PartitionId
     }
 }
 
+fn partition_murmur2(key: &[u8], partition_count: PartitionCount) -> PartitionId {
+    // same as Kafka `Utils.toPositive(Utils.murmur2(key)) % numPartitions`
+    (murmur2(key) & 0x7fff_ffff) % partition_count
+}
+
+/// 32-bit murmur2 hash with the seed used by Kafka
+fn murmur2(data: &[u8]) -> u32 {
+    const SEED: u32 = 0x9747_b28c;
+    const M: u32 = 0x5bd1_e995;
+    const R: u32 = 24;
+
+    let mut h = SEED ^ data.len() as u32;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M);
+        h ^= k;
+    }
+    let tail = chunks.remainder();
+    if tail.len() >= 3 {
+        h ^= (tail[2] as u32) << 16;
+    }
+    if tail.len() >= 2 {
+        h ^= (tail[1] as u32) << 8;
+    }
+    if !tail.is_empty() {
+        h ^= tail[0] as u32;
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
 /// A [`Partitioner`] which assigns all records to a specific partition
 pub(crate) struct SpecificPartitioner {
     partition_id: PartitionId,
@@ -116,10 +329,7 @@ mod tests {
     /// Ensure that feeding keyless records one-at-a-time does not assign the same partition
     #[test]
     fn test_round_robin_individual() {
-        let config = PartitionerConfig {
-            partition_count: 3,
-            available_partitions: vec![0, 1, 2],
-        };
+        let config = PartitionerConfig::new(3, vec![0, 1, 2]).expect("config");
         let partitioner = SiphashRoundRobinPartitioner::new();
 
         let key1_partition = partitioner.partition(&config, None, &[]);
@@ -142,10 +352,7 @@ mod tests {
 
         let (tx, rx) = std::sync::mpsc::channel();
         let partitioner = Arc::new(SiphashRoundRobinPartitioner::new());
-        let config = Arc::new(PartitionerConfig {
-            partition_count: 4,
-            available_partitions: vec![0, 1, 2, 3],
-        });
+        let config = Arc::new(PartitionerConfig::new(4, vec![0, 1, 2, 3]).expect("config"));
 
         // We have 5 threads calculating partitions 400 times each for NULL key (aka round-robin).
         // This is 20,000 records total, among 4 partitions. If it is evenly distributed like we
@@ -177,10 +384,8 @@ mod tests {
 
     #[test]
     fn test_available_partitions() {
-        let config = PartitionerConfig {
-            partition_count: 3,
-            available_partitions: vec![0, 2], // Only partitions 0 and 2 are available
-        };
+        // Only partitions 0 and 2 are available
+        let config = PartitionerConfig::new(3, vec![0, 2]).expect("config");
         let partitioner = SiphashRoundRobinPartitioner::new();
 
         let key1_partition = partitioner.partition(&config, None, &[]);
@@ -196,4 +401,100 @@ mod tests {
         let key6_partition = partitioner.partition(&config, None, &[]);
         assert_eq!(key6_partition, 2);
     }
+
+    #[test]
+    fn test_config_from_struct_literal() {
+        let config = PartitionerConfig {
+            partition_count: 2,
+            available_partitions: vec![0, 1],
+            ..Default::default()
+        };
+        let partitioner = SiphashRoundRobinPartitioner::new();
+
+        assert_eq!(config.batch_size(), 0);
+        assert_eq!(partitioner.partition(&config, None, &[]), 0);
+        assert_eq!(partitioner.partition(&config, None, &[]), 1);
+    }
+
+    #[test]
+    fn test_sticky_until_batch_is_full() {
+        let config = PartitionerConfig::new(3, vec![0, 1, 2])
+            .expect("config")
+            .with_batching(10, Duration::ZERO);
+        let partitioner = StickyPartitioner::new();
+
+        let partitions: Vec<_> = (0..7)
+            .map(|_| partitioner.partition(&config, None, &[0; 4]))
+            .collect();
+        assert_eq!(partitions, vec![0, 0, 0, 1, 1, 1, 2]);
+
+        // keyed records are not affected by sticky partition
+        let key_partition = partitioner.partition(&config, Some(b"key"), &[0; 4]);
+        assert_eq!(key_partition, partition_siphash(b"key", 3));
+        assert_eq!(partitioner.partition(&config, None, &[0; 4]), 2);
+    }
+
+    #[test]
+    fn test_sticky_until_linger_expires() {
+        let config = PartitionerConfig::new(2, vec![0, 1])
+            .expect("config")
+            .with_batching(1000, Duration::from_millis(100));
+        let sticky = StickyPartition::default();
+        let start = Instant::now();
+
+        assert_eq!(sticky.partition(&config, 1, start), 0);
+        assert_eq!(
+            sticky.partition(&config, 1, start + Duration::from_millis(99)),
+            0
+        );
+        assert_eq!(
+            sticky.partition(&config, 1, start + Duration::from_millis(100)),
+            1
+        );
+    }
+
+    #[test]
+    fn test_sticky_skips_unavailable_partition() {
+        let mut config = PartitionerConfig::new(3, vec![0, 1, 2])
+            .expect("config")
+            .with_batching(1000, Duration::ZERO);
+        let partitioner = StickyPartitioner::new();
+
+        assert_eq!(partitioner.partition(&config, None, &[]), 0);
+        config.available_partitions = vec![1, 2];
+        assert_eq!(partitioner.partition(&config, None, &[]), 2);
+        assert_eq!(partitioner.partition(&config, None, &[]), 2);
+    }
+
+    #[test]
+    fn test_config_without_partitions() {
+        assert!(PartitionerConfig::new(0, vec![]).is_err());
+    }
+
+    /// Reference values from Kafka `UtilsTest.testMurmur2`
+    #[test]
+    fn test_murmur2_kafka_compatible() {
+        let cases: [(&[u8], i32); 6] = [
+            (b"21", -973932308),
+            (b"foobar", -790332482),
+            (b"a-little-bit-long-string", -985981536),
+            (b"a-little-bit-longer-string", -1486304829),
+            (
+                b"lkjh234lh9fiuh90y23oiuhsafujhadof229phr9h19h89h8",
+                -58897971,
+            ),
+            (b"abc", 479470107),
+        ];
+        for (key, expected) in cases {
+            assert_eq!(murmur2(key) as i32, expected);
+        }
+
+        let config = PartitionerConfig::new(3, vec![]).expect("config");
+        let partitioner = Murmur2Partitioner::new();
+        assert_eq!(partitioner.partition(&config, Some(b"21"), &[]), 0);
+        assert_eq!(
+            partitioner.partition(&config, Some(b"a-little-bit-long-string"), &[]),
+            2
+        );
+    }
 }
@@ -0,0 +1,703 @@
+//! On-disk buffer for batches the producer could not send because the cluster was unreachable.
+//!
+//! Each partition gets its own directory `<dir>/<topic>-<partition>` laid out like SPU storage:
+//! segment files named `<base offset:020>.log` holding encoded batches, and `replication.chk`
+//! holding the offset up to which spilled batches were delivered.
+//! Spilled batches survive restarts of the producer and are drained in order once the leader
+//! is reachable again.
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Cursor, Error as IoError, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use async_channel::Sender;
+use tracing::{debug, info, warn};
+
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, Offset, RawRecords, BATCH_FILE_HEADER_SIZE, BATCH_PREAMBLE_SIZE};
+use fluvio_types::PartitionId;
+
+use crate::producer::accumulator::ProducePartitionResponseFuture;
+
+use super::config::{SpillConfig, SpillOverflowPolicy};
+use super::error::ProducerError;
+
+const LOG_EXTENSION: &str = "log";
+const CHECKPOINT_FILE_NAME: &str = "replication.chk";
+/// segments are sized so that dropping the oldest one frees a fraction of the buffer
+const SEGMENTS_PER_BUFFER: u64 = 4;
+const MIN_SEGMENT_BYTES: u64 = 1_048_576;
+
+/// Spilled batches of a partition and callers waiting for their offsets.
+/// File IO of the log runs on the blocking thread pool
+pub(crate) struct PartitionSpill {
+    log: Arc<StdMutex<SpillLog>>,
+    /// log has undelivered batches, kept outside of the lock so it can be checked
+    /// without waiting for file IO
+    pending: Arc<AtomicBool>,
+    /// base offset in spill log of batches whose senders are still waiting
+    waiting: VecDeque<(Offset, Sender<ProducePartitionResponseFuture>)>,
+}
+
+impl PartitionSpill {
+    pub(crate) async fn open(
+        config: &SpillConfig,
+        topic: &str,
+        partition: PartitionId,
+    ) -> Result<Self, ProducerError> {
+        let config = config.clone();
+        let topic = topic.to_owned();
+        let log = unblock(move || SpillLog::open(&config, &topic, partition))
+            .await
+            .map_err(spill_error)?;
+        Ok(Self {
+            pending: Arc::new(AtomicBool::new(!log.is_empty())),
+            log: Arc::new(StdMutex::new(log)),
+            waiting: VecDeque::new(),
+        })
+    }
+
+    /// true if there are no batches waiting to be delivered
+    pub(crate) fn is_empty(&self) -> bool {
+        !self.pending.load(Ordering::Acquire)
+    }
+
+    /// run `f` with the log on the blocking thread pool, pending flag follows changes of the log
+    async fn with_log<T, F>(&self, f: F) -> Result<T, ProducerError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut SpillLog) -> Result<T, ProducerError> + Send + 'static,
+    {
+        let log = self.log.clone();
+        let pending = self.pending.clone();
+        unblock(move || {
+            let mut log = lock_log(&log);
+            let result = f(&mut log);
+            pending.store(!log.is_empty(), Ordering::Release);
+            result
+        })
+        .await
+    }
+
+    /// append batch to the spill log, returns number of records dropped to make room for it.
+    /// If the batch can't be spilled, its sender is notified with the error
+    pub(crate) async fn push(
+        &mut self,
+        mut batch: Batch<RawRecords>,
+        notify: Sender<ProducePartitionResponseFuture>,
+    ) -> Result<u64, ProducerError> {
+        let append = match self.with_log(move |log| log.append(&mut batch)).await {
+            Ok(append) => append,
+            Err(err) => {
+                let response = ProducePartitionResponseFuture::ready(
+                    Offset::default(),
+                    ErrorCode::Other(err.to_string()),
+                );
+                if notify.send(response).await.is_err() {
+                    debug!("Failed to notify produce result because receiver was dropped");
+                }
+                return Err(err);
+            }
+        };
+        self.waiting.push_back((append.base_offset, notify));
+        if append.dropped_records > 0 {
+            self.fail_dropped(append.drained).await;
+        }
+        Ok(append.dropped_records)
+    }
+
+    /// read next spilled batches up to `max_bytes`, at least one batch is returned if not empty
+    pub(crate) async fn read(
+        &self,
+        max_bytes: usize,
+    ) -> Result<Vec<Batch<RawRecords>>, ProducerError> {
+        self.with_log(move |log| log.read(max_bytes).map_err(spill_error))
+            .await
+    }
+
+    /// mark the leading batches that were accepted by the leader as delivered and hand their
+    /// offsets to the waiting senders. Batches from the first failed one on stay spilled.
+    /// Returns the number of delivered batches
+    pub(crate) async fn delivered(
+        &mut self,
+        batches: &[Batch<RawRecords>],
+        results: Vec<(Offset, ErrorCode)>,
+    ) -> Result<usize, ProducerError> {
+        let accepted = results
+            .iter()
+            .take_while(|(_, error_code)| error_code.is_ok())
+            .count()
+            .min(batches.len());
+        let Some(last) = batches[..accepted].last() else {
+            return Ok(0);
+        };
+        let commit_offset = last.get_last_offset() + 1;
+        self.with_log(move |log| log.commit(commit_offset).map_err(spill_error))
+            .await?;
+        for (batch, (offset, error_code)) in batches.iter().zip(results).take(accepted) {
+            while let Some((base_offset, _)) = self.waiting.front() {
+                if *base_offset > batch.get_base_offset() {
+                    break;
+                }
+                let (base_offset, notify) = self.waiting.pop_front().expect("front exists");
+                if base_offset == batch.get_base_offset() {
+                    let response = ProducePartitionResponseFuture::ready(offset, error_code);
+                    if notify.send(response).await.is_err() {
+                        debug!("Failed to notify produce result because receiver was dropped");
+                    }
+                    break;
+                }
+            }
+        }
+        Ok(accepted)
+    }
+
+    /// notify senders of batches that were removed from the log before being delivered
+    async fn fail_dropped(&mut self, drained: Offset) {
+        while let Some((base_offset, _)) = self.waiting.front() {
+            if *base_offset >= drained {
+                break;
+            }
+            let (_, notify) = self.waiting.pop_front().expect("front exists");
+            let response = ProducePartitionResponseFuture::ready(
+                Offset::default(),
+                ErrorCode::Other("batch dropped from spill buffer".to_string()),
+            );
+            if notify.send(response).await.is_err() {
+                debug!("Failed to notify produce result because receiver was dropped");
+            }
+        }
+    }
+}
+
+fn spill_error(err: IoError) -> ProducerError {
+    ProducerError::Spill(err.to_string())
+}
+
+fn lock_log(log: &StdMutex<SpillLog>) -> MutexGuard<'_, SpillLog> {
+    // in-memory state is only updated after file operations succeed
+    log.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn unblock<T, F>(f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    fluvio_future::task::spawn_blocking(f).await
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn unblock<T, F>(f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    f()
+}
+
+pub(crate) struct SpillAppend {
+    pub base_offset: Offset,
+    /// records removed by the overflow policy or age limit
+    pub dropped_records: u64,
+    /// offset up to which batches were delivered or dropped
+    pub drained: Offset,
+}
+
+/// Append only log of spilled batches
+pub(crate) struct SpillLog {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_age: Duration,
+    overflow: SpillOverflowPolicy,
+    segment_max_bytes: u64,
+    segments: VecDeque<SpillSegment>,
+    /// offset up to which batches were delivered
+    drained: Offset,
+}
+
+#[derive(Debug)]
+struct SpillSegment {
+    base_offset: Offset,
+    end_offset: Offset,
+    size: u64,
+    last_write: SystemTime,
+}
+
+impl SpillLog {
+    pub(crate) fn open(
+        config: &SpillConfig,
+        topic: &str,
+        partition: PartitionId,
+    ) -> Result<Self, IoError> {
+        let dir = config.dir.join(format!("{topic}-{partition}"));
+        fs::create_dir_all(&dir)?;
+
+        let mut base_offsets = vec![];
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != LOG_EXTENSION) {
+                continue;
+            }
+            match path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<Offset>().ok())
+            {
+                Some(base_offset) => base_offsets.push(base_offset),
+                None => warn!(path = %path.display(), "ignoring unknown file in spill directory"),
+            }
+        }
+        base_offsets.sort_unstable();
+
+        let mut segments = VecDeque::with_capacity(base_offsets.len());
+        for base_offset in base_offsets {
+            segments.push_back(SpillSegment::load(&dir, base_offset)?);
+        }
+
+        let mut log = Self {
+            max_bytes: config.max_bytes,
+            max_age: config.max_age,
+            overflow: config.overflow,
+            segment_max_bytes: (config.max_bytes / SEGMENTS_PER_BUFFER).max(MIN_SEGMENT_BYTES),
+            segments,
+            drained: read_checkpoint(&dir)?,
+            dir,
+        };
+        log.drained = log.drained.clamp(log.start_offset(), log.end_offset());
+        if !log.is_empty() {
+            info!(
+                dir = %log.dir.display(),
+                drained = log.drained,
+                end = log.end_offset(),
+                "found spilled batches"
+            );
+        }
+        Ok(log)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.drained >= self.end_offset()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn drained(&self) -> Offset {
+        self.drained
+    }
+
+    /// bytes occupied by segments
+    pub(crate) fn size(&self) -> u64 {
+        self.segments.iter().map(|segment| segment.size).sum()
+    }
+
+    fn start_offset(&self) -> Offset {
+        self.segments
+            .front()
+            .map(|segment| segment.base_offset)
+            .unwrap_or(self.drained)
+    }
+
+    fn end_offset(&self) -> Offset {
+        self.segments
+            .back()
+            .map(|segment| segment.end_offset)
+            .unwrap_or(self.drained)
+    }
+
+    pub(crate) fn append(
+        &mut self,
+        batch: &mut Batch<RawRecords>,
+    ) -> Result<SpillAppend, ProducerError> {
+        let batch_size = batch.write_size(0) as u64;
+        let dropped_records = self.enforce_limits(batch_size)?;
+
+        let needs_roll = self
+            .segments
+            .back()
+            .is_none_or(|segment| segment.size >= self.segment_max_bytes);
+        if needs_roll {
+            let base_offset = self.end_offset();
+            File::create(segment_path(&self.dir, base_offset)).map_err(spill_error)?;
+            self.segments.push_back(SpillSegment {
+                base_offset,
+                end_offset: base_offset,
+                size: 0,
+                last_write: SystemTime::now(),
+            });
+        }
+
+        let segment = self.segments.back_mut().expect("active segment");
+        let base_offset = segment.end_offset;
+        batch.set_base_offset(base_offset);
+        let mut buf = Vec::with_capacity(batch_size as usize);
+        batch.encode(&mut buf, 0).map_err(spill_error)?;
+
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(segment_path(&self.dir, segment.base_offset))
+            .map_err(spill_error)?;
+        file.write_all(&buf).map_err(spill_error)?;
+        file.sync_data().map_err(spill_error)?;
+
+        segment.size += buf.len() as u64;
+        segment.end_offset = batch.get_last_offset() + 1;
+        segment.last_write = SystemTime::now();
+        debug!(base_offset, size = buf.len(), "batch spilled");
+
+        Ok(SpillAppend {
+            base_offset,
+            dropped_records,
+            drained: self.drained,
+        })
+    }
+
+    /// remove segments over age or size limit before appending `incoming` bytes
+    fn enforce_limits(&mut self, incoming: u64) -> Result<u64, ProducerError> {
+        let now = SystemTime::now();
+        let mut dropped_records = 0;
+        while let Some(oldest) = self.segments.front() {
+            let expired = now
+                .duration_since(oldest.last_write)
+                .is_ok_and(|age| age > self.max_age);
+            if !expired {
+                break;
+            }
+            dropped_records += self.remove_oldest().map_err(spill_error)?;
+        }
+
+        while !self.segments.is_empty() && self.size() + incoming > self.max_bytes {
+            match self.overflow {
+                SpillOverflowPolicy::Reject => return Err(ProducerError::SpillBufferFull),
+                SpillOverflowPolicy::DropOldest => {
+                    dropped_records += self.remove_oldest().map_err(spill_error)?;
+                }
+            }
+        }
+        if dropped_records > 0 {
+            warn!(
+                dropped_records,
+                dir = %self.dir.display(),
+                "spill buffer limit reached, dropped oldest records"
+            );
+            write_checkpoint(&self.dir, self.drained).map_err(spill_error)?;
+        }
+        Ok(dropped_records)
+    }
+
+    /// remove oldest segment, returns number of records that were not delivered yet
+    fn remove_oldest(&mut self) -> Result<u64, IoError> {
+        let Some(segment) = self.segments.pop_front() else {
+            return Ok(0);
+        };
+        fs::remove_file(segment_path(&self.dir, segment.base_offset))?;
+        let dropped = (segment.end_offset - self.drained.max(segment.base_offset)).max(0);
+        self.drained = self.drained.max(segment.end_offset);
+        Ok(dropped as u64)
+    }
+
+    /// read batches starting at drained offset
+    pub(crate) fn read(&self, max_bytes: usize) -> Result<Vec<Batch<RawRecords>>, IoError> {
+        let mut batches = vec![];
+        let mut total = 0;
+        for segment in self
+            .segments
+            .iter()
+            .filter(|segment| segment.end_offset > self.drained)
+        {
+            let mut file = File::open(segment_path(&self.dir, segment.base_offset))?;
+            let mut pos = 0;
+            while pos < segment.size {
+                let (header, len) = read_batch_header(&mut file, pos)?;
+                if header.get_last_offset() >= self.drained {
+                    if !batches.is_empty() && total + len > max_bytes as u64 {
+                        return Ok(batches);
+                    }
+                    let mut buf = vec![0u8; len as usize];
+                    file.seek(SeekFrom::Start(pos))?;
+                    file.read_exact(&mut buf)?;
+                    let batch = Batch::<RawRecords>::decode_from(&mut Cursor::new(buf), 0)?;
+                    batches.push(batch);
+                    total += len;
+                }
+                pos += len;
+            }
+        }
+        Ok(batches)
+    }
+
+    /// batches before `offset` were delivered, fully delivered segments are removed
+    pub(crate) fn commit(&mut self, offset: Offset) -> Result<(), IoError> {
+        self.drained = offset.clamp(self.drained, self.end_offset());
+        write_checkpoint(&self.dir, self.drained)?;
+        while self
+            .segments
+            .front()
+            .is_some_and(|segment| segment.end_offset <= self.drained)
+        {
+            self.remove_oldest()?;
+        }
+        Ok(())
+    }
+}
+
+impl SpillSegment {
+    /// scan segment for its end offset, partially written batch at the end is truncated
+    fn load(dir: &Path, base_offset: Offset) -> Result<Self, IoError> {
+        let path = segment_path(dir, base_offset);
+        let mut file = OpenOptions::new().read(true).write(true).open(&path)?;
+        let metadata = file.metadata()?;
+        let file_len = metadata.len();
+
+        let mut end_offset = base_offset;
+        let mut pos = 0;
+        while pos < file_len {
+            match read_batch_header(&mut file, pos) {
+                Ok((header, len)) if pos + len <= file_len => {
+                    end_offset = header.get_last_offset() + 1;
+                    pos += len;
+                }
+                _ => {
+                    warn!(path = %path.display(), pos, "truncating incomplete spilled batch");
+                    file.set_len(pos)?;
+                    break;
+                }
+            }
+        }
+
+        Ok(Self {
+            base_offset,
+            end_offset,
+            size: pos,
+            last_write: metadata.modified().unwrap_or_else(|_| SystemTime::now()),
+        })
+    }
+}
+
+/// read batch header at position, returns the header and length of the whole batch in file
+fn read_batch_header(file: &mut File, pos: u64) -> Result<(Batch<RawRecords>, u64), IoError> {
+    let mut buf = [0u8; BATCH_FILE_HEADER_SIZE];
+    file.seek(SeekFrom::Start(pos))?;
+    file.read_exact(&mut buf)?;
+    let mut header = Batch::<RawRecords>::default();
+    header.decode_from_file_buf(&mut Cursor::new(buf), 0)?;
+    if header.batch_len() <= 0 {
+        return Err(IoError::new(
+            ErrorKind::InvalidData,
+            format!("invalid batch length {}", header.batch_len()),
+        ));
+    }
+    let len = (BATCH_PREAMBLE_SIZE + header.batch_len() as usize) as u64;
+    Ok((header, len))
+}
+
+fn segment_path(dir: &Path, base_offset: Offset) -> PathBuf {
+    dir.join(format!("{base_offset:020}.{LOG_EXTENSION}"))
+}
+
+fn read_checkpoint(dir: &Path) -> Result<Offset, IoError> {
+    match fs::read(dir.join(CHECKPOINT_FILE_NAME)) {
+        Ok(contents) => {
+            let bytes: [u8; 8] = contents.try_into().map_err(|_| {
+                IoError::new(ErrorKind::InvalidData, "spill checkpoint should be 8 bytes")
+            })?;
+            Ok(Offset::from_be_bytes(bytes))
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(0),
+        Err(err) => Err(err),
+    }
+}
+
+fn write_checkpoint(dir: &Path, offset: Offset) -> Result<(), IoError> {
+    fs::write(dir.join(CHECKPOINT_FILE_NAME), offset.to_be_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::Record;
+
+    use super::*;
+
+    fn raw_batch(values: &[&str]) -> Batch<RawRecords> {
+        let records: Vec<Record> = values.iter().map(|value| Record::new(*value)).collect();
+        Batch::<RawRecords>::try_from(Batch::from(records)).expect("raw batch")
+    }
+
+    fn spill_config(dir: &Path) -> SpillConfig {
+        SpillConfig::new(dir)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fluvio-spill-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_spill_append_read_commit() {
+        let dir = temp_dir("append");
+        let config = spill_config(&dir);
+        let mut log = SpillLog::open(&config, "topic", 0).expect("open");
+        assert!(log.is_empty());
+
+        let first = log.append(&mut raw_batch(&["a", "b"])).expect("append");
+        let second = log.append(&mut raw_batch(&["c"])).expect("append");
+        assert_eq!(first.base_offset, 0);
+        assert_eq!(second.base_offset, 2);
+        assert!(!log.is_empty());
+
+        let batches = log.read(1024).expect("read");
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[1].get_base_offset(), 2);
+
+        log.commit(2).expect("commit");
+        let batches = log.read(1024).expect("read");
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].records_len(), 1);
+
+        // spilled batches survive reopening
+        drop(log);
+        let mut log = SpillLog::open(&config, "topic", 0).expect("reopen");
+        assert_eq!(log.drained(), 2);
+        let batches = log.read(1024).expect("read");
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].get_base_offset(), 2);
+
+        log.commit(3).expect("commit");
+        assert!(log.is_empty());
+        assert_eq!(log.size(), 0);
+        let next = log.append(&mut raw_batch(&["d"])).expect("append");
+        assert_eq!(next.base_offset, 3);
+
+        fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn test_spill_truncates_incomplete_batch() {
+        let dir = temp_dir("truncate");
+        let config = spill_config(&dir);
+        let mut log = SpillLog::open(&config, "topic", 1).expect("open");
+        log.append(&mut raw_batch(&["a"])).expect("append");
+        let size = log.size();
+        drop(log);
+
+        let path = segment_path(&dir.join("topic-1"), 0);
+        let mut file = OpenOptions::new().append(true).open(&path).expect("open");
+        file.write_all(&[0, 0, 0]).expect("write");
+
+        let log = SpillLog::open(&config, "topic", 1).expect("reopen");
+        assert_eq!(log.size(), size);
+        assert_eq!(log.read(1024).expect("read").len(), 1);
+
+        fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn test_spill_overflow_policy() {
+        let dir = temp_dir("overflow");
+        let batch_size = raw_batch(&["a"]).write_size(0) as u64;
+        let mut config = spill_config(&dir);
+        config.max_bytes = batch_size;
+        config.overflow = SpillOverflowPolicy::Reject;
+
+        let mut log = SpillLog::open(&config, "reject", 0).expect("open");
+        log.append(&mut raw_batch(&["a"])).expect("append");
+        assert!(matches!(
+            log.append(&mut raw_batch(&["b"])),
+            Err(ProducerError::SpillBufferFull)
+        ));
+
+        config.overflow = SpillOverflowPolicy::DropOldest;
+        let mut log = SpillLog::open(&config, "drop", 0).expect("open");
+        log.append(&mut raw_batch(&["a"])).expect("append");
+        let append = log.append(&mut raw_batch(&["b"])).expect("append");
+        assert_eq!(append.dropped_records, 1);
+        assert_eq!(append.base_offset, 1);
+        assert_eq!(log.drained(), 1);
+        let batches = log.read(1024).expect("read");
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].get_base_offset(), 1);
+
+        fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn test_spill_max_age() {
+        let dir = temp_dir("age");
+        let mut config = spill_config(&dir);
+        config.max_age = Duration::ZERO;
+
+        let mut log = SpillLog::open(&config, "topic", 0).expect("open");
+        log.append(&mut raw_batch(&["a"])).expect("append");
+        std::thread::sleep(Duration::from_millis(5));
+        let append = log.append(&mut raw_batch(&["b"])).expect("append");
+        assert_eq!(append.dropped_records, 1);
+
+        fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn test_read_respects_max_bytes() {
+        let dir = temp_dir("max-bytes");
+        let config = spill_config(&dir);
+        let mut log = SpillLog::open(&config, "topic", 0).expect("open");
+        for _ in 0..3 {
+            log.append(&mut raw_batch(&["value"])).expect("append");
+        }
+        // first batch is always returned
+        assert_eq!(log.read(0).expect("read").len(), 1);
+        let two = 2 * raw_batch(&["value"]).write_size(0);
+        assert_eq!(log.read(two).expect("read").len(), 2);
+
+        fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[fluvio_future::test]
+    async fn test_delivered_commits_accepted_prefix() {
+        let dir = temp_dir("delivered");
+        let config = spill_config(&dir);
+        let mut spill = PartitionSpill::open(&config, "topic", 0)
+            .await
+            .expect("open");
+        assert!(spill.is_empty());
+        let mut receivers = vec![];
+        for value in ["a", "b", "c"] {
+            let (sender, receiver) = async_channel::bounded(1);
+            spill.push(raw_batch(&[value]), sender).await.expect("push");
+            receivers.push(receiver);
+        }
+
+        let batches = spill.read(1024).await.expect("read");
+        assert_eq!(batches.len(), 3);
+        let results = vec![
+            (10, ErrorCode::None),
+            (0, ErrorCode::NotLeaderForPartition),
+            (12, ErrorCode::None),
+        ];
+        let delivered = spill.delivered(&batches, results).await.expect("delivered");
+        assert_eq!(delivered, 1);
+        assert_eq!(
+            receivers[0].recv().await.expect("response").await,
+            (10, ErrorCode::None)
+        );
+        assert!(receivers[1].is_empty());
+
+        // rejected batch and the ones after it stay spilled
+        let batches = spill.read(1024).await.expect("read");
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].get_base_offset(), 1);
+        assert!(!spill.is_empty());
+
+        let results = vec![(11, ErrorCode::None), (12, ErrorCode::None)];
+        let delivered = spill.delivered(&batches, results).await.expect("delivered");
+        assert_eq!(delivered, 2);
+        assert!(spill.is_empty());
+
+        fs::remove_dir_all(&dir).expect("cleanup");
+    }
+}
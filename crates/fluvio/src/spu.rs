@@ -1,8 +1,10 @@
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
 use anyhow::Result;
 
-use fluvio_sc_schema::partition::PartitionSpec;
+use fluvio_sc_schema::partition::{PartitionSpec, PartitionStatus};
+use fluvio_sc_schema::spu::SpuSpec;
 use fluvio_sc_schema::topic::TopicSpec;
 use tracing::{debug, trace, instrument};
 use async_lock::Mutex;
@@ -12,9 +14,11 @@ use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::api::Request;
 use fluvio_types::SpuId;
 use fluvio_socket::{
-    AsyncResponse, ClientConfig, MultiplexerSocket, SocketError, StreamSocket,
+    AsyncResponse, ClientConfig, InFlightLimit, MultiplexerSocket, SocketError, StreamSocket,
     VersionedSerialSocket,
 };
+use crate::config::SpuConnectionConfig;
+use crate::metrics::ClientMetrics;
 use crate::FluvioError;
 use crate::sync::{MetadataStores, StoreContext};
 
@@ -24,7 +28,7 @@ use crate::sync::{MetadataStores, StoreContext};
 pub trait SpuDirectory {
     /// Create request/response socket to SPU for a replica
     ///
-    /// All sockets to same SPU use a single TCP connection.
+    /// Sockets for same replica use a single TCP connection, shared with streams of that replica.
     /// First this looks up SPU address in SPU metadata and try to see if there is an existing TCP connection.
     /// If not, it will create a new connection and creates socket to it
     async fn create_serial_socket(
@@ -41,6 +45,36 @@ pub trait SpuDirectory {
     ) -> Result<AsyncResponse<R>, FluvioError>
     where
         R: Sync + Send;
+
+    /// create stream to in sync replica nearest to client.
+    /// Directories without knowledge of replica placement use leader
+    async fn create_nearest_stream_with_version<R: Request>(
+        &self,
+        replica: &ReplicaKey,
+        request: R,
+        version: i16,
+    ) -> Result<AsyncResponse<R>, FluvioError>
+    where
+        R: Sync + Send,
+    {
+        self.create_stream_with_version(replica, request, version)
+            .await
+    }
+}
+
+/// Kind of requests sent over a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ConnectionPurpose {
+    Produce,
+    Fetch,
+}
+
+/// Identifies one of the connections to a SPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ConnectionKey {
+    spu: SpuId,
+    purpose: ConnectionPurpose,
+    slot: usize,
 }
 
 /// connection pool to spu
@@ -48,7 +82,10 @@ pub trait SpuDirectory {
 pub struct SpuSocketPool {
     config: Arc<ClientConfig>,
     pub(crate) metadata: MetadataStores,
-    spu_clients: Arc<Mutex<HashMap<SpuId, StreamSocket>>>,
+    spu_clients: Arc<Mutex<HashMap<ConnectionKey, StreamSocket>>>,
+    client_rack: Option<String>,
+    connections: SpuConnectionConfig,
+    metrics: Option<Arc<ClientMetrics>>,
 }
 
 impl Drop for SpuSocketPool {
@@ -74,6 +111,17 @@ pub trait SpuPool {
         leader_id: SpuId,
     ) -> Result<VersionedSerialSocket, FluvioError>;
 
+    /// Create request/response socket to leader for producing records to `replica`.
+    ///
+    /// Records for same replica are always sent over the same connection, so they are not reordered.
+    async fn create_producer_socket(
+        &self,
+        leader_id: SpuId,
+        _replica: &ReplicaKey,
+    ) -> Result<VersionedSerialSocket, FluvioError> {
+        self.create_serial_socket_from_leader(leader_id).await
+    }
+
     async fn topic_exists(&self, topic: String) -> Result<bool, FluvioError>;
 
     fn shutdown(&mut self);
@@ -93,6 +141,9 @@ impl SpuPool for SpuSocketPool {
             metadata,
             config,
             spu_clients: Arc::new(Mutex::new(HashMap::new())),
+            client_rack: None,
+            connections: SpuConnectionConfig::default(),
+            metrics: None,
         })
     }
 
@@ -116,11 +167,11 @@ impl SpuPool for SpuSocketPool {
         client_config.set_addr(spu_addr);
         let versioned_socket = client_config.connect().await?;
         let (socket, config, versions) = versioned_socket.split();
-        Ok(StreamSocket::new(
-            config,
-            MultiplexerSocket::shared(socket),
-            versions,
-        ))
+        let mut socket = MultiplexerSocket::new(socket);
+        if let Some(limit) = self.in_flight_limit() {
+            socket = socket.with_in_flight_limit(limit);
+        }
+        Ok(StreamSocket::new(config, Arc::new(socket), versions))
     }
 
     #[instrument(skip(self))]
@@ -128,22 +179,18 @@ impl SpuPool for SpuSocketPool {
         &self,
         leader_id: SpuId,
     ) -> Result<VersionedSerialSocket, FluvioError> {
-        // check if already have existing connection to same SPU
-        let mut client_lock = self.spu_clients.lock().await;
-
-        if let Some(spu_socket) = client_lock.get_mut(&leader_id) {
-            if !spu_socket.is_stale() {
-                return Ok(spu_socket.create_serial_socket().await);
-            } else {
-                client_lock.remove(&leader_id);
-            }
-        }
-
-        let mut spu_socket = self.connect_to_leader(leader_id).await?;
-        let serial_socket = spu_socket.create_serial_socket().await;
-        client_lock.insert(leader_id, spu_socket);
+        let key = self.connection_key(leader_id, ConnectionPurpose::Produce, None);
+        self.create_serial_socket_to_spu(key).await
+    }
 
-        Ok(serial_socket)
+    #[instrument(skip(self, replica))]
+    async fn create_producer_socket(
+        &self,
+        leader_id: SpuId,
+        replica: &ReplicaKey,
+    ) -> Result<VersionedSerialSocket, FluvioError> {
+        let key = self.connection_key(leader_id, ConnectionPurpose::Produce, Some(replica));
+        self.create_serial_socket_to_spu(key).await
     }
 
     async fn topic_exists(&self, topic: String) -> Result<bool, FluvioError> {
@@ -164,12 +211,138 @@ impl SpuPool for SpuSocketPool {
     }
 }
 
+impl SpuSocketPool {
+    /// rack of client, consumers reading from followers prefer replicas in same rack
+    pub(crate) fn with_client_rack(mut self, rack: Option<String>) -> Self {
+        self.client_rack = rack;
+        self
+    }
+
+    /// number of connections and in flight requests per connection,
+    /// time requests wait for a free slot is recorded in `metrics`
+    pub(crate) fn with_connections(
+        mut self,
+        connections: SpuConnectionConfig,
+        metrics: Arc<ClientMetrics>,
+    ) -> Self {
+        self.connections = connections;
+        self.metrics = Some(metrics);
+        self
+    }
+
+    fn in_flight_limit(&self) -> Option<InFlightLimit> {
+        let limit = InFlightLimit::new(self.connections.max_in_flight?);
+        Some(match self.metrics.clone() {
+            Some(metrics) => limit.with_observer(Arc::new(move |queue_time| {
+                metrics.connections().add_queue_time(queue_time)
+            })),
+            None => limit,
+        })
+    }
+
+    /// connection to use for request to spu.
+    /// Requests for same replica always map to same connection
+    fn connection_key(
+        &self,
+        spu: SpuId,
+        purpose: ConnectionPurpose,
+        replica: Option<&ReplicaKey>,
+    ) -> ConnectionKey {
+        let purpose = if self.connections.separate_pools {
+            purpose
+        } else {
+            ConnectionPurpose::Produce
+        };
+        let per_spu = self.connections.per_spu.max(1);
+        let slot = match replica {
+            Some(replica) if per_spu > 1 => {
+                let mut hasher = DefaultHasher::new();
+                replica.hash(&mut hasher);
+                (hasher.finish() % per_spu as u64) as usize
+            }
+            _ => 0,
+        };
+        ConnectionKey { spu, purpose, slot }
+    }
+
+    /// create request/response socket over pooled connection to spu
+    async fn create_serial_socket_to_spu(
+        &self,
+        key: ConnectionKey,
+    ) -> Result<VersionedSerialSocket, FluvioError> {
+        // check if already have existing connection
+        let mut client_lock = self.spu_clients.lock().await;
+
+        if let Some(spu_socket) = client_lock.get_mut(&key) {
+            if !spu_socket.is_stale() {
+                return Ok(spu_socket.create_serial_socket().await);
+            } else {
+                client_lock.remove(&key);
+            }
+        }
+
+        debug!(?key, "new connection to spu");
+        let mut spu_socket = self.connect_to_leader(key.spu).await?;
+        let serial_socket = spu_socket.create_serial_socket().await;
+        client_lock.insert(key, spu_socket);
+
+        Ok(serial_socket)
+    }
+
+    /// in sync replica on online spu in same rack as client, leader if there is none
+    async fn nearest_replica(&self, spec: &PartitionSpec, status: &PartitionStatus) -> SpuId {
+        let Some(rack) = &self.client_rack else {
+            return spec.leader;
+        };
+        let spus = self.metadata.spus().store().read().await;
+        nearest_replica_in_rack(
+            rack,
+            spec,
+            status,
+            spus.values().map(|spu| (&spu.spec, spu.status.is_online())),
+        )
+    }
+
+    /// create stream of replica over pooled connection to spu
+    async fn create_stream_to_spu<R: Request>(
+        &self,
+        spu_id: SpuId,
+        replica: &ReplicaKey,
+        request: R,
+        version: i16,
+    ) -> Result<AsyncResponse<R>, FluvioError>
+    where
+        R: Sync + Send,
+    {
+        let key = self.connection_key(spu_id, ConnectionPurpose::Fetch, Some(replica));
+
+        // check if already have existing connection or create new connection to spu
+        let mut client_lock = self.spu_clients.lock().await;
+
+        if let Some(spu_socket) = client_lock.get_mut(&key) {
+            return spu_socket
+                .create_stream_with_version(request, version)
+                .await
+                .map_err(|err| err.into());
+        }
+
+        debug!(?key, "new connection to spu");
+        let mut spu_socket = self.connect_to_leader(spu_id).await?;
+        let stream = spu_socket
+            .create_stream_with_version(request, version)
+            .await?;
+        client_lock.insert(key, spu_socket);
+
+        Ok(stream)
+    }
+}
+
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl SpuDirectory for SpuSocketPool {
     /// Create request/response socket to SPU for a replica
     ///
-    /// All sockets to same SPU use a single TCP connection.
+    /// Sockets for same replica use a single TCP connection, shared with streams of that replica.
     /// First this looks up SPU address in SPU metadata and try to see if there is an existing TCP connection.
     /// If not, it will create a new connection and creates socket to it
     #[instrument(skip(self, replica))]
@@ -187,9 +360,12 @@ impl SpuDirectory for SpuSocketPool {
             ));
         };
 
-        let leader_id = partition.spec.leader;
-        let socket = self.create_serial_socket_from_leader(leader_id).await?;
-        Ok(socket)
+        let key = self.connection_key(
+            partition.spec.leader,
+            ConnectionPurpose::Fetch,
+            Some(replica),
+        );
+        self.create_serial_socket_to_spu(key).await
     }
 
     #[instrument(skip(self, replica, request, version))]
@@ -213,24 +389,130 @@ impl SpuDirectory for SpuSocketPool {
             ));
         };
 
-        let leader_id = partition.spec.leader;
+        self.create_stream_to_spu(partition.spec.leader, replica, request, version)
+            .await
+    }
 
-        // check if already have existing leader or create new connection to leader
-        let mut client_lock = self.spu_clients.lock().await;
+    #[instrument(skip(self, replica, request, version))]
+    async fn create_nearest_stream_with_version<R: Request>(
+        &self,
+        replica: &ReplicaKey,
+        request: R,
+        version: i16,
+    ) -> Result<AsyncResponse<R>, FluvioError>
+    where
+        R: Sync + Send,
+    {
+        let Some(partition) = self.metadata.partitions().lookup_by_key(replica).await? else {
+            return Err(FluvioError::PartitionNotFound(
+                replica.topic.to_owned(),
+                replica.partition,
+            ));
+        };
 
-        if let Some(spu_socket) = client_lock.get_mut(&leader_id) {
-            return spu_socket
-                .create_stream_with_version(request, version)
-                .await
-                .map_err(|err| err.into());
+        let spu_id = self
+            .nearest_replica(&partition.spec, &partition.status)
+            .await;
+        debug!(%replica, spu_id, leader = partition.spec.leader, "nearest replica");
+        self.create_stream_to_spu(spu_id, replica, request, version)
+            .await
+    }
+}
+
+/// first in sync replica, in partition replica order, whose spu is online and in `rack`.
+/// Leader if there is none
+fn nearest_replica_in_rack<'a>(
+    rack: &str,
+    spec: &PartitionSpec,
+    status: &PartitionStatus,
+    spus: impl IntoIterator<Item = (&'a SpuSpec, bool)>,
+) -> SpuId {
+    let nearby: HashSet<SpuId> = spus
+        .into_iter()
+        .filter(|(spu, online)| *online && spu.rack.as_deref() == Some(rack))
+        .map(|(spu, _)| spu.id)
+        .collect();
+    let in_sync = status.in_sync_replicas();
+    std::iter::once(spec.leader)
+        .chain(spec.replicas.iter().copied())
+        .find(|replica| in_sync.contains(replica) && nearby.contains(replica))
+        .unwrap_or(spec.leader)
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_sc_schema::partition::ReplicaStatus;
+
+    use super::*;
+
+    fn spu(id: SpuId, rack: &str) -> SpuSpec {
+        SpuSpec {
+            id,
+            rack: Some(rack.to_owned()),
+            ..Default::default()
         }
+    }
 
-        let mut spu_socket = self.connect_to_leader(leader_id).await?;
-        let stream = spu_socket
-            .create_stream_with_version(request, version)
-            .await?;
-        client_lock.insert(leader_id, spu_socket);
+    /// leader 0 with followers 1 and 2, follower 2 is not in sync
+    fn partition() -> (PartitionSpec, PartitionStatus) {
+        let replica = |spu, in_sync| ReplicaStatus {
+            in_sync,
+            ..ReplicaStatus::new(spu, 10, 10)
+        };
+        let status =
+            PartitionStatus::new(replica(0, true), vec![replica(1, true), replica(2, false)]);
+        (PartitionSpec::new(0, vec![0, 1, 2]), status)
+    }
 
-        Ok(stream)
+    #[test]
+    fn test_nearest_replica_in_same_rack() {
+        let (spec, status) = partition();
+        let spus = [spu(0, "us-east"), spu(1, "us-west"), spu(2, "eu")];
+
+        assert_eq!(
+            nearest_replica_in_rack(
+                "us-west",
+                &spec,
+                &status,
+                spus.iter().map(|spu| (spu, true))
+            ),
+            1
+        );
+        assert_eq!(
+            nearest_replica_in_rack(
+                "us-east",
+                &spec,
+                &status,
+                spus.iter().map(|spu| (spu, true))
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn test_nearest_replica_falls_back_to_leader() {
+        let (spec, status) = partition();
+        let spus = [spu(0, "us-east"), spu(1, "us-west"), spu(2, "eu")];
+
+        // replica in rack is not in sync
+        assert_eq!(
+            nearest_replica_in_rack("eu", &spec, &status, spus.iter().map(|spu| (spu, true))),
+            0
+        );
+        // replica in rack is offline
+        assert_eq!(
+            nearest_replica_in_rack(
+                "us-west",
+                &spec,
+                &status,
+                spus.iter().map(|spu| (spu, spu.id != 1))
+            ),
+            0
+        );
+        // no spu in rack
+        assert_eq!(
+            nearest_replica_in_rack("ap", &spec, &status, spus.iter().map(|spu| (spu, true))),
+            0
+        );
     }
 }
@@ -54,10 +54,7 @@ mod test {
         let partitioner = AlphabetPartitioning::default();
         assert_eq!(
             partitioner.partition(
-                &PartitionerConfig {
-                    partition_count: 3,
-                    available_partitions: vec![0, 1, 2]
-                },
+                &PartitionerConfig::new(3, vec![0, 1, 2]).expect("config"),
                 Some(b"aa"),
                 &[]
             ),
@@ -65,10 +62,7 @@ mod test {
         );
         assert_eq!(
             partitioner.partition(
-                &PartitionerConfig {
-                    partition_count: 3,
-                    available_partitions: vec![0, 1, 2]
-                },
+                &PartitionerConfig::new(3, vec![0, 1, 2]).expect("config"),
                 Some(b"a"),
                 &[]
             ),
@@ -76,10 +70,7 @@ mod test {
         );
         assert_eq!(
             partitioner.partition(
-                &PartitionerConfig {
-                    partition_count: 3,
-                    available_partitions: vec![0, 1, 2]
-                },
+                &PartitionerConfig::new(3, vec![0, 1, 2]).expect("config"),
                 None,
                 &[]
             ),
@@ -87,10 +78,7 @@ mod test {
         );
         assert_eq!(
             partitioner.partition(
-                &PartitionerConfig {
-                    partition_count: 3,
-                    available_partitions: vec![0, 1, 2]
-                },
+                &PartitionerConfig::new(3, vec![0, 1, 2]).expect("config"),
                 Some(b"abcdefg"),
                 &[]
             ),
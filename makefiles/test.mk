@@ -247,6 +247,20 @@ cli-fluvio-smoke:
 cli-fluvio-read-only-smoke:
 	bats $(shell ls -1 ./tests/cli/fluvio_read_only/*.bats | sort -R)
 
+FFI_LIB_DIR=$(if $(TARGET),./target/$(TARGET)/$(BUILD_PROFILE),./target/$(BUILD_PROFILE))
+
+# C API test harness, expects a running cluster in the current profile
+ffi-c-test:
+	cargo build -p fluvio-ffi $(BUILD_FLAGS)
+	$(CC) -Wall -Werror -I crates/fluvio-ffi/include crates/fluvio-ffi/tests/c/test_client.c \
+		$(FFI_LIB_DIR)/libfluvio_ffi.a -lpthread -ldl -lm -o $(FFI_LIB_DIR)/fluvio-ffi-test
+	$(FFI_LIB_DIR)/fluvio-ffi-test
+
+# Runs the C API test harness against a throwaway local cluster
+ffi-c-test-local: build-cli build-cluster
+	$(FLUVIO_BIN) cluster start --local --spu 1
+	$(MAKE) ffi-c-test; status=$$?; $(FLUVIO_BIN) cluster delete --force; exit $$status
+
 cli-fluvio-mirroring-smoke:
 	bats $(shell ls -1 ./tests/cli/mirroring_smoke_tests/*.bats | sort -R)
 